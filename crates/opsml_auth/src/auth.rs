@@ -136,6 +136,11 @@ impl AuthManager {
     }
 
     pub fn validate_user(&self, user: &User, password: &str) -> Result<(), AuthError> {
+        // service accounts authenticate exclusively with API tokens
+        if user.is_service_account() {
+            return Err(AuthError::ServiceAccountLogin);
+        }
+
         verify_password(password, &user.password_hash).map_err(|_| AuthError::InvalidPassword)
     }
 
@@ -20,6 +20,9 @@ pub enum AuthError {
     #[error("Error creating JWT token")]
     JWTError,
 
+    #[error("Service accounts cannot log in with a password")]
+    ServiceAccountLogin,
+
     #[error("Invalid recovery code provided")]
     InvalidRecoveryCode,
 
@@ -1,3 +1,4 @@
+use opsml_sql::schemas::schema::User;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -8,6 +9,55 @@ pub struct UserPermissions {
 }
 
 impl UserPermissions {
+    /// Builds the effective permissions for a request authenticated with an API token.
+    ///
+    /// Token scopes can only narrow what the owning user is currently allowed to do, so
+    /// permissions removed from the user after the token was issued are not retained.
+    /// Group admin access is only granted if the token was explicitly scoped with `admin`.
+    pub fn from_api_token(user: &User, scopes: &[String]) -> Self {
+        let owner = Self::from_user_permissions(user);
+        let permissions = scopes
+            .iter()
+            .filter(|scope| owner.grants_scope(scope))
+            .cloned()
+            .collect();
+
+        let group_permissions = user
+            .group_permissions
+            .iter()
+            .filter(|group| *group != "admin" || scopes.contains(group))
+            .cloned()
+            .collect();
+
+        Self {
+            username: user.username.clone(),
+            permissions,
+            group_permissions,
+        }
+    }
+
+    /// Permissions assigned directly to a user, without any group permissions
+    pub fn from_user_permissions(user: &User) -> Self {
+        Self {
+            username: user.username.clone(),
+            permissions: user.permissions.clone(),
+            group_permissions: Vec::new(),
+        }
+    }
+
+    /// Whether a token scope is covered by these permissions.
+    ///
+    /// `read:<space>`, `write:<space>` and `delete:<space>` scopes are also covered by the
+    /// matching `read:all`, `write:all` and `delete:all` permissions
+    pub fn grants_scope(&self, scope: &str) -> bool {
+        match scope.split_once(':') {
+            Some(("read", space_id)) => self.has_read_permission(space_id),
+            Some(("write", space_id)) => self.has_write_permission(space_id),
+            Some(("delete", space_id)) => self.has_delete_permission(space_id),
+            _ => self.has_permission(scope),
+        }
+    }
+
     pub fn has_permission(&self, permission: &str) -> bool {
         self.permissions.contains(&permission.to_string())
             || self.group_permissions.contains(&"admin".to_string())
@@ -23,6 +73,21 @@ impl UserPermissions {
             || self.permissions.contains(&"write:all".to_string())
     }
 
+    /// Spaces these permissions can read, or `None` if every space can be read
+    pub fn readable_spaces(&self) -> Option<Vec<String>> {
+        if self.has_read_permission("all") {
+            return None;
+        }
+
+        Some(
+            self.permissions
+                .iter()
+                .filter_map(|permission| permission.strip_prefix("read:"))
+                .map(|space| space.to_string())
+                .collect(),
+        )
+    }
+
     pub fn has_delete_permission(&self, space_id: &str) -> bool {
         self.has_permission(&format!("delete:{space_id}"))
             || self.permissions.contains(&"delete:all".to_string())
@@ -2,6 +2,10 @@ use password_auth::generate_hash;
 use rand::distr::Alphanumeric;
 use rand::Rng;
 use rayon::prelude::*;
+use sha2::{Digest, Sha256};
+
+/// Prefix used to distinguish API tokens from JWTs
+pub const API_TOKEN_PREFIX: &str = "opsml_pat_";
 
 pub fn generate_recovery_codes_with_hashes(count: usize) -> (Vec<String>, Vec<String>) {
     // Generate codes in parallel
@@ -25,3 +29,36 @@ pub fn generate_recovery_codes_with_hashes(count: usize) -> (Vec<String>, Vec<St
     // Unzip results into separate vectors
     results.into_iter().unzip()
 }
+
+/// Generates a new API token and its hashed representation.
+///
+/// The raw token is only returned once at creation time. The server stores the
+/// SHA-256 digest so tokens can be looked up deterministically on each request.
+///
+/// # Returns
+/// A tuple of (raw token, hashed token)
+pub fn generate_api_token() -> (String, String) {
+    let secret: String = rand::rng()
+        .sample_iter(&Alphanumeric)
+        .take(40)
+        .map(char::from)
+        .collect();
+
+    let token = format!("{API_TOKEN_PREFIX}{secret}");
+    let hashed = hash_api_token(&token);
+
+    (token, hashed)
+}
+
+/// Hashes an API token with SHA-256 and returns the hex digest
+pub fn hash_api_token(token: &str) -> String {
+    Sha256::digest(token.as_bytes())
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// Checks whether a bearer token is an API token rather than a JWT
+pub fn is_api_token(token: &str) -> bool {
+    token.starts_with(API_TOKEN_PREFIX)
+}
@@ -1,13 +1,12 @@
 use crate::error::CardError;
 use crate::utils::BaseArgs;
 use chrono::{DateTime, Utc};
-use opsml_crypt::decrypt_directory;
 use opsml_interfaces::data::{
     ArrowData, DataInterface, DataInterfaceMetadata, DataLoadKwargs, DataSaveKwargs, NumpyData,
     PandasData, PolarsData, SqlData, TorchData,
 };
 use opsml_interfaces::FeatureSchema;
-use opsml_storage::storage_client;
+use opsml_storage::download_artifacts;
 use opsml_types::contracts::{ArtifactKey, CardRecord, DataCardClientRecord};
 use opsml_types::interfaces::types::DataInterfaceType;
 use opsml_types::{DataType, RegistryType, SaveName, Suffix};
@@ -352,21 +351,13 @@ impl DataCard {
         }
     }
 
-    fn get_decryption_key(&self) -> Result<Vec<u8>, CardError> {
-        if self.artifact_key.is_none() {
-            Err(CardError::DecryptionKeyNotFoundError)
-        } else {
-            Ok(self.artifact_key.as_ref().unwrap().get_decrypt_key()?)
-        }
-    }
-
     fn download_all_artifacts(&mut self, lpath: &Path) -> Result<(), CardError> {
-        let decrypt_key = self.get_decryption_key()?;
-        let uri = self.artifact_key.as_ref().unwrap().storage_path();
-
-        storage_client()?.get(lpath, &uri, true)?;
+        let key = self
+            .artifact_key
+            .as_ref()
+            .ok_or(CardError::DecryptionKeyNotFoundError)?;
 
-        decrypt_directory(lpath, &decrypt_key)?;
+        download_artifacts(key, lpath, &key.storage_path(), true)?;
 
         Ok(())
     }
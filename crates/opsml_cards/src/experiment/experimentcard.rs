@@ -1,11 +1,10 @@
 use crate::error::CardError;
 use crate::utils::BaseArgs;
 use chrono::{DateTime, Utc};
-use opsml_crypt::decrypt_directory;
-use opsml_storage::storage_client;
+use opsml_storage::{download_artifacts, storage_client};
 use opsml_types::contracts::{CardRecord, ExperimentCardClientRecord};
 use opsml_types::{
-    cards::{ComputeEnvironment, Metrics, Parameters},
+    cards::{ComputeEnvironment, EnvironmentSnapshot, Metrics, Parameters, Sweep},
     contracts::ArtifactKey,
     RegistryType, SaveName, Suffix,
 };
@@ -82,6 +81,14 @@ pub struct ExperimentCard {
     #[pyo3(get)]
     pub is_card: bool,
 
+    /// Set when the experiment ran a hyperparameter sweep
+    #[pyo3(get)]
+    pub sweep: Option<Sweep>,
+
+    /// Environment captured when the experiment started
+    #[pyo3(get)]
+    pub environment: Option<EnvironmentSnapshot>,
+
     artifact_key: Option<ArtifactKey>,
 }
 
@@ -121,6 +128,8 @@ impl ExperimentCard {
             subexperiment: false,
             is_card: true,
             opsml_version: opsml_version::version(),
+            sweep: None,
+            environment: None,
         })
     }
 
@@ -277,30 +286,63 @@ impl ExperimentCard {
         // if rpath has an extension, set recursive to false
         let recursive = rpath.extension().is_none();
 
-        storage_client()?
-            .get(&lpath, &rpath, recursive)
-            .inspect_err(|e| {
-                error!("Failed to download artifacts: {e}");
-            })?;
-
-        let decrypt_key = self
-            .artifact_key
-            .as_ref()
-            .unwrap()
-            .get_decrypt_key()
-            .inspect_err(|e| {
-                error!("Failed to get decryption key: {e}");
-            })?;
-        decrypt_directory(&lpath, &decrypt_key)?;
+        download_artifacts(
+            self.artifact_key.as_ref().unwrap(),
+            &lpath,
+            &rpath,
+            recursive,
+        )
+        .inspect_err(|e| {
+            error!("Failed to download artifacts: {e}");
+        })?;
 
         Ok(())
     }
 }
 
 impl ExperimentCard {
+    /// Card of a run recorded by another tracking tool. The compute environment of the run is
+    /// unknown and left empty
+    pub fn imported(
+        space: &str,
+        name: &str,
+        tags: Vec<String>,
+        created_at: DateTime<Utc>,
+    ) -> Result<Self, CardError> {
+        let registry_type = RegistryType::Experiment;
+        let base_args = BaseArgs::create_args(Some(name), Some(space), None, None, &registry_type)?;
+
+        Ok(Self {
+            space: base_args.0,
+            name: base_args.1,
+            version: base_args.2,
+            uid: base_args.3,
+            tags,
+            registry_type,
+            artifact_key: None,
+            app_env: std::env::var("APP_ENV").unwrap_or_else(|_| "dev".to_string()),
+            created_at,
+            compute_environment: ComputeEnvironment::default(),
+            uids: UidMetadata::default(),
+            subexperiment: false,
+            is_card: true,
+            opsml_version: opsml_version::version(),
+            sweep: None,
+            environment: None,
+        })
+    }
+
     pub fn set_artifact_key(&mut self, key: ArtifactKey) {
         self.artifact_key = Some(key);
     }
+
+    pub fn set_sweep(&mut self, sweep: Sweep) {
+        self.sweep = Some(sweep);
+    }
+
+    pub fn set_environment(&mut self, environment: EnvironmentSnapshot) {
+        self.environment = Some(environment);
+    }
 }
 
 impl Serialize for ExperimentCard {
@@ -308,7 +350,7 @@ impl Serialize for ExperimentCard {
     where
         S: Serializer,
     {
-        let mut state = serializer.serialize_struct("ExperimentCard", 12)?;
+        let mut state = serializer.serialize_struct("ExperimentCard", 15)?;
 
         // set session to none
         state.serialize_field("name", &self.name)?;
@@ -324,6 +366,8 @@ impl Serialize for ExperimentCard {
         state.serialize_field("subexperiment", &self.subexperiment)?;
         state.serialize_field("is_card", &self.is_card)?;
         state.serialize_field("opsml_version", &self.opsml_version)?;
+        state.serialize_field("sweep", &self.sweep)?;
+        state.serialize_field("environment", &self.environment)?;
         state.end()
     }
 }
@@ -349,6 +393,8 @@ impl<'de> Deserialize<'de> for ExperimentCard {
             Subexperiment,
             IsCard,
             OpsmlVersion,
+            Sweep,
+            Environment,
         }
 
         struct ExperimentCardVisitor;
@@ -377,6 +423,8 @@ impl<'de> Deserialize<'de> for ExperimentCard {
                 let mut subexperiment = None;
                 let mut is_card = None;
                 let mut opsml_version = None;
+                let mut sweep = None;
+                let mut environment = None;
 
                 while let Some(key) = map.next_key()? {
                     match key {
@@ -422,6 +470,12 @@ impl<'de> Deserialize<'de> for ExperimentCard {
                         Field::OpsmlVersion => {
                             opsml_version = Some(map.next_value()?);
                         }
+                        Field::Sweep => {
+                            sweep = map.next_value()?;
+                        }
+                        Field::Environment => {
+                            environment = map.next_value()?;
+                        }
                     }
                 }
 
@@ -460,6 +514,8 @@ impl<'de> Deserialize<'de> for ExperimentCard {
                     subexperiment,
                     is_card,
                     opsml_version,
+                    sweep,
+                    environment,
                 })
             }
         }
@@ -479,6 +535,8 @@ impl<'de> Deserialize<'de> for ExperimentCard {
             "subexperiment",
             "is_card",
             "opsml_version",
+            "sweep",
+            "environment",
         ];
         deserializer.deserialize_struct("ExperimentCard", FIELDS, ExperimentCardVisitor)
     }
@@ -499,6 +557,8 @@ impl FromPyObject<'_> for ExperimentCard {
         let subexperiment = ob.getattr("subexperiment")?.extract()?;
         let artifact_key = None;
         let opsml_version = ob.getattr("opsml_version")?.extract()?;
+        let sweep = ob.getattr("sweep")?.extract()?;
+        let environment = ob.getattr("environment")?.extract()?;
 
         Ok(ExperimentCard {
             space,
@@ -515,6 +575,8 @@ impl FromPyObject<'_> for ExperimentCard {
             artifact_key,
             is_card: true,
             opsml_version,
+            sweep,
+            environment,
         })
     }
 }
@@ -2,7 +2,6 @@ use crate::error::CardError;
 use crate::model::error::interface_error;
 use crate::utils::BaseArgs;
 use chrono::{DateTime, Utc};
-use opsml_crypt::decrypt_directory;
 use opsml_interfaces::base::DriftProfileMap;
 use opsml_interfaces::{error::ModelInterfaceError, OnnxModel, OnnxSession};
 use opsml_interfaces::{
@@ -11,8 +10,9 @@ use opsml_interfaces::{
 };
 use opsml_interfaces::{ModelInterface, TensorFlowModel};
 use opsml_interfaces::{ModelInterfaceMetadata, ModelLoadKwargs, ModelSaveKwargs};
-use opsml_storage::storage_client;
+use opsml_storage::download_artifacts;
 use opsml_types::contracts::{ArtifactKey, CardRecord, ModelCardClientRecord};
+use opsml_types::CardStage;
 use opsml_types::{
     DataType, ModelInterfaceType, ModelType, RegistryType, SaveName, Suffix, TaskType,
 };
@@ -433,6 +433,8 @@ impl ModelCard {
             task_type: self.metadata.interface_metadata.task_type.to_string(),
             opsml_version: self.opsml_version.clone(),
             username: std::env::var("OPSML_USERNAME").unwrap_or_else(|_| "guest".to_string()),
+            // stages are managed by the registry and are not changed by card updates
+            stage: CardStage::default(),
         };
 
         Ok(CardRecord::Model(record))
@@ -712,20 +714,13 @@ impl<'de> Deserialize<'de> for ModelCard {
 }
 
 impl ModelCard {
-    fn get_decryption_key(&self) -> Result<Vec<u8>, CardError> {
-        if self.artifact_key.is_none() {
-            Err(CardError::DecryptionKeyNotFoundError)
-        } else {
-            Ok(self.artifact_key.as_ref().unwrap().get_decrypt_key()?)
-        }
-    }
     fn download_all_artifacts(&mut self, lpath: &Path) -> Result<(), CardError> {
-        let decrypt_key = self.get_decryption_key()?;
-        let uri = self.artifact_key.as_ref().unwrap().storage_path();
-
-        storage_client()?.get(lpath, &uri, true)?;
+        let key = self
+            .artifact_key
+            .as_ref()
+            .ok_or(CardError::DecryptionKeyNotFoundError)?;
 
-        decrypt_directory(lpath, &decrypt_key)?;
+        download_artifacts(key, lpath, &key.storage_path(), true)?;
 
         Ok(())
     }
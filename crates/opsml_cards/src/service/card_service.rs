@@ -5,11 +5,11 @@ use crate::{DataCard, ExperimentCard, ModelCard, PromptCard};
 use chrono::{DateTime, Utc};
 use opsml_interfaces::{DataLoadKwargs, ModelLoadKwargs};
 use opsml_types::contracts::CardEntry;
-use opsml_types::CommonKwargs;
 use opsml_types::{
     contracts::{CardRecord, ServiceCardClientRecord},
     RegistryType, SaveName, Suffix,
 };
+use opsml_types::{CardStage, CommonKwargs};
 use opsml_utils::{extract_py_attr, PyHelperFuncs};
 use pyo3::IntoPyObjectExt;
 use pyo3::PyTraverseError;
@@ -393,6 +393,8 @@ impl ServiceCard {
             cards: self.cards.to_card_entries(),
             opsml_version: self.opsml_version.clone(),
             username: std::env::var("OPSML_USERNAME").unwrap_or_else(|_| "guest".to_string()),
+            // stages are managed by the registry and are not changed by card updates
+            stage: CardStage::default(),
         };
 
         Ok(CardRecord::Service(record))
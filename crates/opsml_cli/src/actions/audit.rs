@@ -0,0 +1,71 @@
+use crate::cli::arg::AuditExportArgs;
+use crate::error::CliError;
+use opsml_colors::Colorize;
+use opsml_registry::base::OpsmlRegistry;
+use opsml_types::contracts::AuditExportRequest;
+use opsml_types::RegistryType;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+/// Verify the audit log hash chain and print a summary
+///
+/// # Returns
+/// Result<(), CliError> - Err if the chain is broken
+pub fn verify_audit_chain() -> Result<(), CliError> {
+    let registry = OpsmlRegistry::new(RegistryType::Audit)?;
+    let report = registry.verify_audit_chain()?;
+
+    println!(
+        "\nChecked {} audit events ({} recorded before chaining was enabled)",
+        Colorize::purple(&report.events_checked.to_string()),
+        report.unchained_events
+    );
+
+    if let Some(chain_break) = report.first_break {
+        println!(
+            "{}",
+            Colorize::alert(&format!(
+                "Audit chain broken at event {} ({})",
+                chain_break.id, chain_break.created_at
+            ))
+        );
+        return Err(CliError::AuditChainBroken(
+            chain_break.id,
+            chain_break.reason,
+        ));
+    }
+
+    println!("{}", Colorize::green("Audit chain verified"));
+
+    Ok(())
+}
+
+/// Export audit events for a time range as JSON Lines
+///
+/// # Arguments
+/// * `args` - AuditExportArgs
+///
+/// # Returns
+/// Result<(), CliError>
+pub fn export_audit_events(args: &AuditExportArgs) -> Result<(), CliError> {
+    let registry = OpsmlRegistry::new(RegistryType::Audit)?;
+    let request = AuditExportRequest {
+        start_time: args.start_time,
+        end_time: args.end_time,
+    };
+
+    match &args.output {
+        Some(path) => {
+            let mut writer = BufWriter::new(File::create(path)?);
+            registry.export_audit_events(&request, &mut writer)?;
+            writer.flush()?;
+            println!(
+                "Audit events written to {}",
+                Colorize::green(&path.display().to_string())
+            );
+        }
+        None => registry.export_audit_events(&request, &mut std::io::stdout().lock())?,
+    }
+
+    Ok(())
+}
@@ -0,0 +1,414 @@
+use crate::cli::arg::{ExportArgs, ImportArgs, IntoQueryArgs};
+use crate::error::CliError;
+use base64::prelude::*;
+use chrono::{DateTime, Utc};
+use opsml_colors::Colorize;
+use opsml_crypt::{decrypt_key, derive_master_key, encrypted_key, generate_salt};
+use opsml_registry::base::OpsmlRegistry;
+use opsml_storage::storage_client;
+use opsml_types::contracts::{
+    CardLineageRequest, CardQueryArgs, CardRecord, LineageDirection, MAX_LINEAGE_DEPTH,
+};
+use opsml_types::RegistryType;
+use opsml_utils::get_utc_datetime;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs::File;
+use std::path::Path;
+use zip::write::SimpleFileOptions;
+
+const BUNDLE_MANIFEST: &str = "manifest.json";
+const BUNDLE_ARTIFACT_DIR: &str = "artifacts";
+const BUNDLE_PASSWORD_ENV: &str = "OPSML_BUNDLE_PASSWORD";
+
+/// Describes the cards in a bundle. Artifacts are stored as they are in the exporting registry,
+/// encrypted with their artifact key, under `artifacts/<uid>`
+#[derive(Serialize, Deserialize, Debug)]
+pub struct BundleManifest {
+    pub opsml_version: String,
+    pub created_at: DateTime<Utc>,
+    /// Base64 salt of the password-derived key wrapping the artifact keys. Keys are unwrapped when not set
+    pub key_salt: Option<String>,
+    pub cards: Vec<BundleCard>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct BundleCard {
+    pub card: CardRecord,
+    /// Base64 artifact key of the card
+    pub encryption_key: String,
+    /// Whether the artifacts of the card include a manifest. Not set in bundles of older versions
+    #[serde(default)]
+    pub has_manifest: bool,
+}
+
+/// Reads the bundle password from the environment, so it does not end up in the shell history
+fn bundle_password() -> Option<String> {
+    std::env::var(BUNDLE_PASSWORD_ENV)
+        .ok()
+        .filter(|password| !password.is_empty())
+}
+
+fn card_name(card: &CardRecord) -> String {
+    format!(
+        "{}: {}/{} v{}",
+        card.registry_type(),
+        card.space(),
+        card.name(),
+        card.version()
+    )
+}
+
+/// Adds every file under `dir` to a zip archive at `output`
+fn write_archive(dir: &Path, output: &Path) -> Result<(), CliError> {
+    fn add_dir(zip: &mut zip::ZipWriter<File>, root: &Path, dir: &Path) -> Result<(), CliError> {
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+
+            if path.is_dir() {
+                add_dir(zip, root, &path)?;
+                continue;
+            }
+
+            let name = path
+                .strip_prefix(root)
+                .map_err(|_| CliError::WritePathError)?
+                .to_string_lossy()
+                .replace('\\', "/");
+
+            // artifacts are already encrypted, so compressing them gains nothing
+            let options = SimpleFileOptions::default()
+                .compression_method(zip::CompressionMethod::Stored)
+                .large_file(path.metadata()?.len() >= u32::MAX as u64);
+
+            zip.start_file(name, options)
+                .map_err(CliError::BundleArchiveError)?;
+            std::io::copy(&mut File::open(&path)?, zip)?;
+        }
+
+        Ok(())
+    }
+
+    let mut zip = zip::ZipWriter::new(File::create(output)?);
+    add_dir(&mut zip, dir, dir)?;
+    zip.finish().map_err(CliError::BundleArchiveError)?;
+
+    Ok(())
+}
+
+fn extract_archive(path: &Path, dir: &Path) -> Result<(), CliError> {
+    let mut archive =
+        zip::ZipArchive::new(File::open(path)?).map_err(CliError::BundleArchiveError)?;
+    archive.extract(dir).map_err(CliError::BundleArchiveError)?;
+
+    Ok(())
+}
+
+/// Finds the cards the exported cards were produced from that are not already part of the export
+fn upstream_cards(
+    registry: &OpsmlRegistry,
+    cards: &[CardRecord],
+) -> Result<Vec<CardRecord>, CliError> {
+    let mut seen: HashSet<String> = cards.iter().map(|card| card.uid().to_string()).collect();
+    let mut upstream = Vec::new();
+
+    for card in cards {
+        let lineage = registry.get_card_lineage(&CardLineageRequest {
+            uid: card.uid().to_string(),
+            depth: Some(MAX_LINEAGE_DEPTH),
+            direction: Some(LineageDirection::Upstream),
+        })?;
+
+        for node in lineage.nodes {
+            if !seen.insert(node.uid.clone()) {
+                continue;
+            }
+
+            if node.deleted {
+                println!(
+                    "Skipping {} as it is in the trash",
+                    Colorize::purple(&format!("{}/{} v{}", node.space, node.name, node.version))
+                );
+                continue;
+            }
+
+            let query_args = CardQueryArgs {
+                uid: Some(node.uid.clone()),
+                registry_type: node.registry_type.clone(),
+                ..Default::default()
+            };
+
+            upstream.extend(OpsmlRegistry::new(node.registry_type)?.list_cards(query_args)?);
+        }
+    }
+
+    Ok(upstream)
+}
+
+/// Export cards, their artifact keys and artifacts to a zip bundle
+///
+/// # Example
+/// opsml export --registry model --space space --name name --with-lineage --output bundle.zip
+///
+/// # Arguments
+/// * `args` - ExportArgs
+///
+/// # Returns
+/// Result<(), CliError>
+pub fn export_bundle(args: &ExportArgs) -> Result<(), CliError> {
+    let password = match (bundle_password(), args.insecure_plaintext_keys) {
+        (Some(password), _) => Some(password),
+        (None, true) => {
+            println!(
+                "{}",
+                Colorize::alert("Artifact keys are stored unprotected in the bundle")
+            );
+            None
+        }
+        (None, false) => return Err(CliError::BundleKeysUnprotected),
+    };
+
+    let query_args = args.into_query_args(RegistryType::from_string(&args.registry)?)?;
+    let registry = OpsmlRegistry::new(query_args.registry_type.clone())?;
+
+    let mut cards = registry.list_cards(query_args)?;
+    if cards.is_empty() {
+        return Err(CliError::NoCardsToExport);
+    }
+
+    if args.with_lineage {
+        let upstream = upstream_cards(&registry, &cards)?;
+
+        // upstream cards are listed first so they are imported before the cards that use them
+        cards.splice(0..0, upstream);
+    }
+
+    let salt = password.as_ref().map(|_| generate_salt()).transpose()?;
+    let password_key = match (&password, &salt) {
+        (Some(password), Some(salt)) => Some(derive_master_key(password.as_bytes(), salt, None)?),
+        _ => None,
+    };
+
+    let tmp_dir = tempfile::tempdir()?;
+    let mut bundle_cards = Vec::with_capacity(cards.len());
+
+    for card in cards {
+        let registry_type = card.registry_type();
+        let key = OpsmlRegistry::new(registry_type.clone())?
+            .get_artifact_key(card.uid(), &registry_type)?;
+
+        let mut encryption_key = key.get_decrypt_key()?;
+        if let Some(password_key) = &password_key {
+            encryption_key = encrypted_key(password_key, &encryption_key)?;
+        }
+
+        println!("Exporting {}", Colorize::purple(&card_name(&card)));
+
+        // artifacts are copied without decrypting them
+        let lpath = tmp_dir.path().join(BUNDLE_ARTIFACT_DIR).join(card.uid());
+        std::fs::create_dir_all(&lpath)?;
+        storage_client()?.get(&lpath, &key.storage_path(), true)?;
+
+        bundle_cards.push(BundleCard {
+            card,
+            encryption_key: BASE64_STANDARD.encode(encryption_key),
+            has_manifest: key.has_manifest,
+        });
+    }
+
+    let manifest = BundleManifest {
+        opsml_version: opsml_version::version(),
+        created_at: get_utc_datetime(),
+        key_salt: salt.map(|salt| BASE64_STANDARD.encode(salt)),
+        cards: bundle_cards,
+    };
+
+    std::fs::write(
+        tmp_dir.path().join(BUNDLE_MANIFEST),
+        serde_json::to_string_pretty(&manifest)?,
+    )?;
+    write_archive(tmp_dir.path(), Path::new(&args.output))?;
+
+    println!(
+        "Exported {} card(s) to {}",
+        manifest.cards.len(),
+        Colorize::green(&args.output)
+    );
+
+    Ok(())
+}
+
+/// Checks whether a card with the same uid, or the same space, name and version, is already registered
+fn card_exists(registry: &OpsmlRegistry, card: &CardRecord) -> Result<bool, CliError> {
+    if registry.check_card_uid(card.uid())? {
+        return Ok(true);
+    }
+
+    let versions = registry.list_cards(CardQueryArgs {
+        space: Some(card.space().to_string()),
+        name: Some(card.name().to_string()),
+        version: Some(card.version().to_string()),
+        registry_type: card.registry_type(),
+        ..Default::default()
+    })?;
+
+    Ok(versions
+        .iter()
+        .any(|existing| existing.version() == card.version()))
+}
+
+/// Import a bundle created with `opsml export`. Cards keep their uid and version and existing
+/// cards are never overwritten
+///
+/// # Example
+/// opsml import --path bundle.zip
+///
+/// # Arguments
+/// * `args` - ImportArgs
+///
+/// # Returns
+/// Result<(), CliError>
+pub fn import_bundle(args: &ImportArgs) -> Result<(), CliError> {
+    let path = args.path.as_deref().ok_or(CliError::MissingImportPath)?;
+    let tmp_dir = tempfile::tempdir()?;
+    extract_archive(Path::new(path), tmp_dir.path())?;
+
+    let manifest: BundleManifest = serde_json::from_str(&std::fs::read_to_string(
+        tmp_dir.path().join(BUNDLE_MANIFEST),
+    )?)?;
+
+    let password_key = match (&manifest.key_salt, &bundle_password()) {
+        (Some(salt), Some(password)) => Some(derive_master_key(
+            password.as_bytes(),
+            &BASE64_STANDARD.decode(salt)?,
+            None,
+        )?),
+        (Some(_), None) => return Err(CliError::BundlePasswordRequired),
+        (None, _) => None,
+    };
+
+    // conflicts are checked up front so a bundle is not left half imported
+    let mut conflicts = HashSet::new();
+    for bundle_card in &manifest.cards {
+        let registry = OpsmlRegistry::new(bundle_card.card.registry_type())?;
+
+        if card_exists(&registry, &bundle_card.card)? {
+            println!(
+                "{} already exists",
+                Colorize::purple(&card_name(&bundle_card.card))
+            );
+            conflicts.insert(bundle_card.card.uid().to_string());
+        }
+    }
+
+    if !conflicts.is_empty() && !args.skip_existing {
+        return Err(CliError::ImportConflicts(conflicts.len()));
+    }
+
+    let mut imported = 0;
+    for bundle_card in manifest.cards {
+        if conflicts.contains(bundle_card.card.uid()) {
+            continue;
+        }
+
+        let mut encryption_key = BASE64_STANDARD.decode(&bundle_card.encryption_key)?;
+        if let Some(password_key) = &password_key {
+            encryption_key = decrypt_key(password_key, &encryption_key)?;
+        }
+
+        println!(
+            "Importing {}",
+            Colorize::purple(&card_name(&bundle_card.card))
+        );
+
+        let lpath = tmp_dir
+            .path()
+            .join(BUNDLE_ARTIFACT_DIR)
+            .join(bundle_card.card.uid());
+
+        // artifacts are uploaded before the card is registered, so a failed upload never leaves
+        // a registered card without its artifacts
+        if lpath.exists() {
+            storage_client()?.put(&lpath, &bundle_card.card.uri()?, true)?;
+        }
+
+        OpsmlRegistry::new(bundle_card.card.registry_type())?.import_card(
+            bundle_card.card,
+            encryption_key,
+            bundle_card.has_manifest,
+        )?;
+
+        imported += 1;
+    }
+
+    println!(
+        "Imported {} card(s), skipped {} existing card(s)",
+        imported,
+        conflicts.len()
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_bundle_archive_round_trip() {
+        let source = tempfile::tempdir().unwrap();
+        let nested = source.path().join(BUNDLE_ARTIFACT_DIR).join("uid");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(source.path().join(BUNDLE_MANIFEST), "{}").unwrap();
+        File::create(nested.join("card.json"))
+            .unwrap()
+            .write_all(b"encrypted")
+            .unwrap();
+
+        let output = tempfile::tempdir().unwrap();
+        let bundle = output.path().join("bundle.zip");
+        write_archive(source.path(), &bundle).unwrap();
+
+        let target = tempfile::tempdir().unwrap();
+        extract_archive(&bundle, target.path()).unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(target.path().join(BUNDLE_MANIFEST)).unwrap(),
+            "{}"
+        );
+        assert_eq!(
+            std::fs::read(
+                target
+                    .path()
+                    .join(BUNDLE_ARTIFACT_DIR)
+                    .join("uid")
+                    .join("card.json")
+            )
+            .unwrap(),
+            b"encrypted"
+        );
+    }
+
+    #[test]
+    fn test_export_requires_password() {
+        std::env::remove_var(BUNDLE_PASSWORD_ENV);
+
+        let args = ExportArgs {
+            registry: "model".to_string(),
+            space: Some("space".to_string()),
+            name: None,
+            version: None,
+            uid: None,
+            limit: None,
+            with_lineage: false,
+            insecure_plaintext_keys: false,
+            output: "bundle.zip".to_string(),
+        };
+
+        assert!(matches!(
+            export_bundle(&args),
+            Err(CliError::BundleKeysUnprotected)
+        ));
+    }
+}
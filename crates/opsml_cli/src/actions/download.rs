@@ -4,9 +4,8 @@ use crate::error::CliError;
 use opsml_cards::ModelCard;
 use opsml_cards::ServiceCard;
 use opsml_colors::Colorize;
-use opsml_crypt::decrypt_directory;
 use opsml_registry::base::OpsmlRegistry;
-use opsml_storage::storage_client;
+use opsml_storage::download_artifacts;
 use opsml_types::{
     cards::ServiceCardMapping,
     contracts::{ArtifactKey, CardQueryArgs},
@@ -15,27 +14,28 @@ use opsml_types::{
 use opsml_utils::PyHelperFuncs;
 use std::path::Path;
 use tracing::debug;
-/// Download all artifacts of a card
+/// Download all artifacts of a card after checking its manifest signature
 ///
 /// # Arguments
+/// * `registry` - OpsmlRegistry
 /// * `key` - ArtifactKey
 /// * `write_dir` - str
 ///
 /// # Returns
 ///
 /// Result<(), CliError>
-fn download_card_artifacts(key: &ArtifactKey, lpath: &Path) -> Result<(), CliError> {
-    // get registry
-    let decryption_key = key.get_decrypt_key()?;
-    let rpath = key.storage_path();
+fn download_card_artifacts(
+    registry: &OpsmlRegistry,
+    key: &ArtifactKey,
+    lpath: &Path,
+) -> Result<(), CliError> {
+    registry.check_card_signature(key)?;
 
     if !lpath.exists() {
         std::fs::create_dir_all(lpath)?;
     }
     // download card artifacts
-    storage_client()?.get(lpath, &rpath, true)?;
-
-    decrypt_directory(lpath, &decryption_key)?;
+    download_artifacts(key, lpath, &key.storage_path(), true)?;
 
     Ok(())
 }
@@ -80,7 +80,7 @@ pub fn download_card(args: &DownloadCard, registry_type: RegistryType) -> Result
         Colorize::green(&args.write_dir)
     );
 
-    download_card_artifacts(&key, &args.write_path())?;
+    download_card_artifacts(&registry, &key, &args.write_path())?;
 
     Ok(())
 }
@@ -101,7 +101,7 @@ pub fn download_service(args: &DownloadCard) -> Result<(), CliError> {
     }
 
     // download service card card
-    download_card_artifacts(&key, &base_path)?;
+    download_card_artifacts(&registry, &key, &base_path)?;
 
     // read Card.json file
     let service =
@@ -139,7 +139,7 @@ pub fn download_service(args: &DownloadCard) -> Result<(), CliError> {
                 .join(&card.alias);
 
             // Download card artifacts
-            download_card_artifacts(&key, &card_path)?;
+            download_card_artifacts(&registry, &key, &card_path)?;
             mapping.add_card_path(&card.alias, &card_path);
 
             // If model card, load and process drift paths
@@ -0,0 +1,114 @@
+use crate::cli::arg::{
+    ExperimentEnvDiffArgs, ExperimentSyncArgs, ImportMlflowArgs, ImportTensorboardArgs,
+};
+use crate::error::CliError;
+use opsml_colors::Colorize;
+use opsml_experiment::{
+    diff_environments, import_mlflow_runs, import_tensorboard_runs, sync_offline_experiments,
+    ImportedExperiment,
+};
+
+/// Registers experiments that were recorded offline
+pub fn sync_experiments(args: &ExperimentSyncArgs) -> Result<(), CliError> {
+    let synced = sync_offline_experiments(&args.path)?;
+
+    let mut skipped = 0;
+    for experiment in &synced {
+        let name = format!(
+            "{}/{} v{}",
+            experiment.space, experiment.name, experiment.version
+        );
+
+        match experiment.skipped {
+            true => {
+                skipped += 1;
+                println!("{} already synced", Colorize::purple(&name));
+            }
+            false => println!(
+                "{} - {} ({})",
+                Colorize::green("Synced experiment"),
+                Colorize::purple(&name),
+                experiment.uid
+            ),
+        }
+    }
+
+    println!(
+        "Synced {} experiment(s), skipped {} already synced experiment(s)",
+        synced.len() - skipped,
+        skipped
+    );
+
+    Ok(())
+}
+
+/// Prints the differences between the environments of two experiments
+pub fn diff_experiment_environments(args: &ExperimentEnvDiffArgs) -> Result<(), CliError> {
+    let diff = diff_environments(&args.left_uid, &args.right_uid)?;
+
+    if diff.changes.is_empty() {
+        println!(
+            "{} and {} ran in the same environment",
+            Colorize::purple(&diff.left_uid),
+            Colorize::purple(&diff.right_uid)
+        );
+        return Ok(());
+    }
+
+    println!(
+        "Environment changes from {} (left) to {} (right)",
+        Colorize::purple(&diff.left_uid),
+        Colorize::purple(&diff.right_uid)
+    );
+    diff.as_table();
+
+    Ok(())
+}
+
+fn print_imported_experiments(imported: &[ImportedExperiment]) {
+    let mut skipped = 0;
+    for experiment in imported {
+        let name = format!(
+            "{}/{} v{}",
+            experiment.space, experiment.name, experiment.version
+        );
+
+        match experiment.skipped {
+            true => {
+                skipped += 1;
+                println!("{} already imported", Colorize::purple(&name));
+            }
+            false => println!(
+                "{} - {} ({}) - {} metric value(s), {} parameter(s), {} artifact(s)",
+                Colorize::green("Imported experiment"),
+                Colorize::purple(&name),
+                experiment.uid,
+                experiment.metrics,
+                experiment.parameters,
+                experiment.artifacts
+            ),
+        }
+    }
+
+    println!(
+        "Imported {} experiment(s), skipped {} already imported experiment(s)",
+        imported.len() - skipped,
+        skipped
+    );
+}
+
+/// Imports runs from a local MLflow tracking directory
+pub fn import_mlflow(args: &ImportMlflowArgs) -> Result<(), CliError> {
+    let imported = import_mlflow_runs(&args.path)?;
+    print_imported_experiments(&imported);
+
+    Ok(())
+}
+
+/// Imports runs from a TensorBoard log directory
+pub fn import_tensorboard(args: &ImportTensorboardArgs) -> Result<(), CliError> {
+    let imported = import_tensorboard_runs(&args.path, &args.space)?;
+    print_imported_experiments(&imported);
+
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use crate::cli::arg::IntoQueryArgs;
+use crate::cli::arg::LineageArgs;
+use crate::error::CliError;
+use opsml_registry::base::OpsmlRegistry;
+use opsml_types::contracts::{CardLineage, CardLineageRequest, LineageDirection};
+use opsml_types::RegistryType;
+
+/// Print the lineage graph of a card as Mermaid or Graphviz DOT
+///
+/// # Example
+/// opsml lineage --registry model --space space --name name --version 1.0.0 --format dot
+///
+/// # Arguments
+/// * `args` - LineageArgs
+///
+/// # Returns
+/// Result<(), CliError>
+pub fn print_card_lineage(args: &LineageArgs) -> Result<(), CliError> {
+    let render: fn(&CardLineage) -> String = match args.format.to_lowercase().as_str() {
+        "mermaid" => CardLineage::to_mermaid,
+        "dot" => CardLineage::to_dot,
+        _ => return Err(CliError::InvalidLineageFormat(args.format.clone())),
+    };
+    let direction = LineageDirection::from_string(&args.direction)?;
+
+    let query_args = args.into_query_args(RegistryType::from_string(&args.registry)?)?;
+    let registry = OpsmlRegistry::new(query_args.registry_type.clone())?;
+
+    let uid = match &args.uid {
+        Some(uid) => uid.clone(),
+        None => registry.get_key(&query_args)?.uid,
+    };
+
+    let lineage = registry.get_card_lineage(&CardLineageRequest {
+        uid,
+        depth: args.depth,
+        direction: Some(direction),
+    })?;
+
+    // only the graph is printed so the output can be piped to a renderer
+    println!("{}", render(&lineage));
+
+    Ok(())
+}
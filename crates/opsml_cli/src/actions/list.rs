@@ -1,9 +1,11 @@
 use crate::cli::arg::IntoQueryArgs;
+use crate::cli::arg::ListAuditEvents;
 use crate::cli::arg::ListCards;
+use crate::cli::arg::SearchCards;
 use crate::error::CliError;
 use opsml_colors::Colorize;
 use opsml_registry::base::OpsmlRegistry;
-use opsml_types::contracts::CardList;
+use opsml_types::contracts::{CardList, CardSearchResults};
 use opsml_types::RegistryType;
 
 /// List cards from a registry and print them as a table
@@ -36,3 +38,61 @@ pub fn list_cards(args: &ListCards, registry_type: RegistryType) -> Result<(), C
 
     Ok(())
 }
+
+/// List audit events from the server and print them as a table
+///
+/// # Example
+/// opsml list audit-events --username admin --operation delete
+///
+/// # Arguments
+/// * `args` - ListAuditEvents
+///
+/// # Returns
+/// Result<(), CliError>
+pub fn list_audit_events(args: &ListAuditEvents) -> Result<(), CliError> {
+    println!("\nListing {}", Colorize::green("audit events"));
+
+    let query_args = args.into_query_args()?;
+    let registry = OpsmlRegistry::new(RegistryType::Audit)?;
+    let page = registry.get_audit_events(&query_args)?;
+
+    page.as_table();
+
+    if let Some(cursor) = page.next_cursor {
+        println!(
+            "More events available. Use {} to see the next page",
+            Colorize::purple(&format!("--cursor {cursor}"))
+        );
+    }
+
+    Ok(())
+}
+
+/// Search cards across registries and print the matches as a table
+///
+/// # Example
+/// opsml search "fraud xgboost" --registry model
+///
+/// # Arguments
+/// * `args` - SearchCards
+///
+/// # Returns
+/// Result<(), CliError>
+pub fn search_cards(args: &SearchCards) -> Result<(), CliError> {
+    println!("\nSearching cards for {}", Colorize::green(&args.query));
+
+    let search_query = args.into_search_query()?;
+
+    // the registry type of the client does not restrict the search
+    let registry = OpsmlRegistry::new(
+        search_query
+            .registry_type
+            .clone()
+            .unwrap_or(RegistryType::Model),
+    )?;
+    let results = registry.search_cards(&search_query)?;
+
+    CardSearchResults { results }.as_table();
+
+    Ok(())
+}
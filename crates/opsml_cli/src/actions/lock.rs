@@ -35,6 +35,7 @@ fn get_service_from_registry(
         None,
         Some(true),
         1,
+        None,
     )?;
     Ok(cards.cards.first().cloned())
 }
@@ -72,6 +73,7 @@ fn get_latest_card(registries: &CardRegistries, card: &Card) -> Result<CardRecor
         None,
         Some(false),
         1,
+        None,
     )?;
 
     // return the first card in the list
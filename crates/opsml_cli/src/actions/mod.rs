@@ -1,16 +1,32 @@
+pub mod audit;
+pub mod bundle;
+pub mod db;
 pub mod demo;
 pub mod download;
+pub mod experiment;
 pub mod generate;
+pub mod lineage;
 pub mod ui;
 
 pub mod list;
 pub mod lock;
+pub mod signing;
 pub mod update_profile;
 pub mod utils;
 pub mod validate;
+pub mod verify;
 
+pub use audit::{export_audit_events, verify_audit_chain};
+pub use bundle::{export_bundle, import_bundle};
+pub use db::{migrate_database, rotate_encryption_secret};
 pub use download::download_card;
+pub use experiment::{
+    diff_experiment_environments, import_mlflow, import_tensorboard, sync_experiments,
+};
 pub use generate::generate_key;
-pub use list::list_cards;
+pub use lineage::print_card_lineage;
+pub use list::{list_audit_events, list_cards, search_cards};
+pub use signing::{create_signing_key, list_signing_keys, revoke_signing_key};
 pub use ui::start_ui;
 pub use update_profile::update_drift_profile_status;
+pub use verify::verify_card;
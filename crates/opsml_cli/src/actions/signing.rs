@@ -0,0 +1,105 @@
+use crate::cli::arg::{SigningKeyCreateArgs, SigningKeyListArgs, SigningKeyRevokeArgs};
+use crate::error::CliError;
+use base64::prelude::*;
+use opsml_colors::Colorize;
+use opsml_crypt::SigningKey;
+use opsml_registry::base::OpsmlRegistry;
+use opsml_types::contracts::{
+    CreateSigningKeyRequest, RevokeSigningKeyRequest, SignerKeyFile, SigningKeyListResponse,
+    SigningKeyQuery,
+};
+use opsml_types::RegistryType;
+
+/// Generate a signing key for a space and register its public key.
+///
+/// The private key is written before the public key is registered, so a registered key is never
+/// lost. The file is removed again if registration fails
+///
+/// # Example
+/// opsml signing-key create --space my-space --output my-space.key.json
+///
+/// # Arguments
+/// * `args` - SigningKeyCreateArgs
+///
+/// # Returns
+/// Result<(), CliError>
+pub fn create_signing_key(args: &SigningKeyCreateArgs) -> Result<(), CliError> {
+    if args.output.exists() {
+        return Err(CliError::SigningKeyFileExists(
+            args.output.display().to_string(),
+        ));
+    }
+
+    let registry = OpsmlRegistry::new(RegistryType::Model)?;
+    let key = SigningKey::generate()?;
+
+    SignerKeyFile::new(&args.space, &key).write(&args.output)?;
+
+    let registered = registry.create_signing_key(&CreateSigningKeyRequest {
+        space: args.space.clone(),
+        public_key: BASE64_STANDARD.encode(key.public_key()),
+    });
+
+    let registered = match registered {
+        Ok(registered) => registered,
+        Err(e) => {
+            std::fs::remove_file(&args.output)?;
+            return Err(e.into());
+        }
+    };
+
+    println!(
+        "\nRegistered signing key {} for space {}",
+        Colorize::purple(&registered.key_id),
+        Colorize::green(&registered.space)
+    );
+    println!(
+        "Private key written to {}. Set OPSML_SIGNING_KEY={} to sign cards when registering them",
+        args.output.display(),
+        args.output.display()
+    );
+
+    Ok(())
+}
+
+/// Print the registered signing keys as a table
+///
+/// # Example
+/// opsml signing-key list --space my-space
+///
+/// # Arguments
+/// * `args` - SigningKeyListArgs
+///
+/// # Returns
+/// Result<(), CliError>
+pub fn list_signing_keys(args: &SigningKeyListArgs) -> Result<(), CliError> {
+    let registry = OpsmlRegistry::new(RegistryType::Model)?;
+    let keys = registry.get_signing_keys(&SigningKeyQuery {
+        space: args.space.clone(),
+    })?;
+
+    SigningKeyListResponse { keys }.as_table();
+
+    Ok(())
+}
+
+/// Revoke a signing key. Cards signed with it fail signature checks afterwards
+///
+/// # Example
+/// opsml signing-key revoke --key-id 3f2a9c0d1b7e4a65
+///
+/// # Arguments
+/// * `args` - SigningKeyRevokeArgs
+///
+/// # Returns
+/// Result<(), CliError>
+pub fn revoke_signing_key(args: &SigningKeyRevokeArgs) -> Result<(), CliError> {
+    let registry = OpsmlRegistry::new(RegistryType::Model)?;
+    registry.revoke_signing_key(&RevokeSigningKeyRequest {
+        key_id: args.key_id.clone(),
+    })?;
+
+    println!("\nRevoked signing key {}", Colorize::purple(&args.key_id));
+
+    Ok(())
+}
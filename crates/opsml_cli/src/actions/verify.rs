@@ -0,0 +1,54 @@
+use crate::cli::arg::IntoQueryArgs;
+use crate::cli::arg::VerifyArgs;
+use crate::error::CliError;
+use opsml_colors::Colorize;
+use opsml_registry::base::OpsmlRegistry;
+use opsml_types::contracts::VerifyArtifactsRequest;
+use opsml_types::RegistryType;
+
+/// Re-hash the stored artifacts of a card and print the files that do not match its manifest
+///
+/// # Example
+/// opsml verify --registry model --space space --name name --version 1.0.0
+///
+/// # Arguments
+/// * `args` - VerifyArgs
+///
+/// # Returns
+/// Result<(), CliError> - Err if an artifact does not match the manifest
+pub fn verify_card(args: &VerifyArgs) -> Result<(), CliError> {
+    let query_args = args.into_query_args(RegistryType::from_string(&args.registry)?)?;
+    let registry = OpsmlRegistry::new(query_args.registry_type.clone())?;
+
+    let uid = match &args.uid {
+        Some(uid) => uid.clone(),
+        None => registry.get_key(&query_args)?.uid,
+    };
+
+    let report = registry.verify_card_artifacts(&VerifyArtifactsRequest {
+        uid,
+        registry_type: query_args.registry_type,
+    })?;
+
+    if !report.has_manifest {
+        return Err(CliError::MissingArtifactManifest(report.uid));
+    }
+
+    println!(
+        "\nChecked {} artifacts of card {}",
+        Colorize::purple(&report.files_checked.to_string()),
+        report.uid
+    );
+
+    if !report.mismatches.is_empty() {
+        report.as_table();
+        return Err(CliError::ArtifactVerificationFailed(
+            report.mismatches.len(),
+            report.uid,
+        ));
+    }
+
+    println!("{}", Colorize::green("Artifacts verified"));
+
+    Ok(())
+}
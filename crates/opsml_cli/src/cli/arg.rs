@@ -1,8 +1,13 @@
 use std::path::PathBuf;
 
+use crate::cli::commands::ImportCommands;
 use crate::error::CliError;
+use chrono::{DateTime, Utc};
 use clap::Args;
-use opsml_types::{contracts::CardQueryArgs, RegistryType};
+use opsml_types::{
+    contracts::{AuditEventQueryArgs, AuditStatus, CardQueryArgs, CardSearchQuery, Operation},
+    CardStage, RegistryType,
+};
 use opsml_utils::clean_string;
 use pyo3::{pyclass, pymethods};
 use scouter_client::DriftType;
@@ -22,7 +27,7 @@ pub struct ListCards {
     #[arg(long = "name")]
     pub name: Option<String>,
 
-    /// Card version
+    /// Card version, or a version alias such as @champion
     #[arg(long = "version")]
     pub version: Option<String>,
 
@@ -45,6 +50,10 @@ pub struct ListCards {
     /// ignore release candidate
     #[arg(long = "sort_by_timestamp", default_value = "true")]
     pub sort_by_timestamp: bool,
+
+    /// Stage (unassigned, staging, production or archived). Model and service cards only
+    #[arg(long = "stage")]
+    pub stage: Option<String>,
 }
 
 impl IntoQueryArgs for ListCards {
@@ -71,10 +80,415 @@ impl IntoQueryArgs for ListCards {
             tags: self.tags.clone(),
             max_date: self.max_date.clone(),
             sort_by_timestamp: Some(self.sort_by_timestamp),
+            stage: self
+                .stage
+                .as_deref()
+                .map(CardStage::from_string)
+                .transpose()?,
+        })
+    }
+}
+
+#[derive(Args)]
+pub struct ListAuditEvents {
+    /// Username that performed the action
+    #[arg(long = "username")]
+    pub username: Option<String>,
+
+    /// Space of the audited resource
+    #[arg(long = "space")]
+    pub space: Option<String>,
+
+    /// Registry type (e.g. model, data)
+    #[arg(long = "registry-type")]
+    pub registry_type: Option<String>,
+
+    /// Operation (e.g. create, update, delete)
+    #[arg(long = "operation")]
+    pub operation: Option<String>,
+
+    /// Status (success, failed or denied)
+    #[arg(long = "status")]
+    pub status: Option<String>,
+
+    /// Resource id (e.g. card uid)
+    #[arg(long = "resource-id")]
+    pub resource_id: Option<String>,
+
+    /// Only events created at or after this time (RFC 3339)
+    #[arg(long = "start-time")]
+    pub start_time: Option<DateTime<Utc>>,
+
+    /// Only events created at or before this time (RFC 3339)
+    #[arg(long = "end-time")]
+    pub end_time: Option<DateTime<Utc>>,
+
+    /// Cursor returned by a previous page
+    #[arg(long = "cursor")]
+    pub cursor: Option<i32>,
+
+    /// Maximum number of events to return
+    #[arg(long = "limit")]
+    pub limit: Option<i32>,
+}
+
+impl ListAuditEvents {
+    pub fn into_query_args(&self) -> Result<AuditEventQueryArgs, CliError> {
+        Ok(AuditEventQueryArgs {
+            username: self.username.clone(),
+            space: self.space.clone(),
+            registry_type: self
+                .registry_type
+                .as_deref()
+                .map(RegistryType::from_string)
+                .transpose()?,
+            operation: self
+                .operation
+                .as_deref()
+                .map(Operation::from_string)
+                .transpose()?,
+            status: self
+                .status
+                .as_deref()
+                .map(AuditStatus::from_string)
+                .transpose()?,
+            resource_id: self.resource_id.clone(),
+            start_time: self.start_time,
+            end_time: self.end_time,
+            cursor: self.cursor,
+            limit: self.limit,
+        })
+    }
+}
+
+#[derive(Args)]
+pub struct SearchCards {
+    /// Words to search for in card names, spaces, tags, READMEs and metadata
+    pub query: String,
+
+    /// Registry type (e.g. model, data). Searches all registries when not set
+    #[arg(long = "registry")]
+    pub registry: Option<String>,
+
+    /// Space name
+    #[arg(long = "space")]
+    pub space: Option<String>,
+
+    /// Maximum number of results to return
+    #[arg(long = "limit")]
+    pub limit: Option<i32>,
+}
+
+impl SearchCards {
+    pub fn into_search_query(&self) -> Result<CardSearchQuery, CliError> {
+        Ok(CardSearchQuery {
+            query: self.query.clone(),
+            registry_type: self
+                .registry
+                .as_deref()
+                .map(RegistryType::from_string)
+                .transpose()?,
+            space: self.space.clone(),
+            limit: self.limit,
+        })
+    }
+}
+
+#[derive(Args)]
+pub struct LineageArgs {
+    /// Registry type of the card (e.g. model, data)
+    #[arg(long = "registry")]
+    pub registry: String,
+
+    /// Card space
+    #[arg(long = "space")]
+    pub space: Option<String>,
+
+    /// Name given to card
+    #[arg(long = "name")]
+    pub name: Option<String>,
+
+    /// Card version, or a version alias such as @champion
+    #[arg(long = "version")]
+    pub version: Option<String>,
+
+    /// Card uid
+    #[arg(long = "uid")]
+    pub uid: Option<String>,
+
+    /// Number of hops to walk in each direction
+    #[arg(long = "depth")]
+    pub depth: Option<i32>,
+
+    /// Direction to walk (upstream, downstream or both)
+    #[arg(long = "direction", default_value = "both")]
+    pub direction: String,
+
+    /// Output format (mermaid or dot)
+    #[arg(long = "format", default_value = "mermaid")]
+    pub format: String,
+}
+
+impl IntoQueryArgs for LineageArgs {
+    fn into_query_args(&self, registry_type: RegistryType) -> Result<CardQueryArgs, CliError> {
+        let name = self
+            .name
+            .clone()
+            .map(|name| clean_string(&name))
+            .transpose()?;
+
+        let space = self
+            .space
+            .clone()
+            .map(|space| clean_string(&space))
+            .transpose()?;
+
+        Ok(CardQueryArgs {
+            uid: self.uid.clone(),
+            name,
+            space,
+            version: self.version.clone(),
+            registry_type,
+            ..Default::default()
         })
     }
 }
 
+#[derive(Args)]
+pub struct VerifyArgs {
+    /// Registry type of the card (e.g. model, data)
+    #[arg(long = "registry")]
+    pub registry: String,
+
+    /// Card space
+    #[arg(long = "space")]
+    pub space: Option<String>,
+
+    /// Name given to card
+    #[arg(long = "name")]
+    pub name: Option<String>,
+
+    /// Card version, or a version alias such as @champion
+    #[arg(long = "version")]
+    pub version: Option<String>,
+
+    /// Card uid
+    #[arg(long = "uid")]
+    pub uid: Option<String>,
+}
+
+impl IntoQueryArgs for VerifyArgs {
+    fn into_query_args(&self, registry_type: RegistryType) -> Result<CardQueryArgs, CliError> {
+        let name = self
+            .name
+            .clone()
+            .map(|name| clean_string(&name))
+            .transpose()?;
+
+        let space = self
+            .space
+            .clone()
+            .map(|space| clean_string(&space))
+            .transpose()?;
+
+        Ok(CardQueryArgs {
+            uid: self.uid.clone(),
+            name,
+            space,
+            version: self.version.clone(),
+            registry_type,
+            ..Default::default()
+        })
+    }
+}
+
+#[derive(Args)]
+pub struct ExportArgs {
+    /// Registry type of the cards (e.g. model, data)
+    #[arg(long = "registry")]
+    pub registry: String,
+
+    /// Card space
+    #[arg(long = "space")]
+    pub space: Option<String>,
+
+    /// Name given to card. All matching versions are exported when no version is given
+    #[arg(long = "name")]
+    pub name: Option<String>,
+
+    /// Card version, or a version alias such as @champion
+    #[arg(long = "version")]
+    pub version: Option<String>,
+
+    /// Card uid
+    #[arg(long = "uid")]
+    pub uid: Option<String>,
+
+    /// Maximum number of cards to export
+    #[arg(long = "limit")]
+    pub limit: Option<i32>,
+
+    /// Also export the upstream lineage of each card (e.g. the datacard of a model)
+    #[arg(long = "with-lineage", default_value = "false")]
+    pub with_lineage: bool,
+
+    /// Store the artifact keys in the bundle without a password. Anyone with the bundle can
+    /// decrypt its artifacts. Keys are otherwise protected with OPSML_BUNDLE_PASSWORD
+    #[arg(long = "insecure-plaintext-keys", default_value = "false")]
+    pub insecure_plaintext_keys: bool,
+
+    /// Path of the bundle to write
+    #[arg(long = "output", default_value = "opsml_bundle.zip")]
+    pub output: String,
+}
+
+impl IntoQueryArgs for ExportArgs {
+    fn into_query_args(&self, registry_type: RegistryType) -> Result<CardQueryArgs, CliError> {
+        let name = self
+            .name
+            .clone()
+            .map(|name| clean_string(&name))
+            .transpose()?;
+
+        let space = self
+            .space
+            .clone()
+            .map(|space| clean_string(&space))
+            .transpose()?;
+
+        Ok(CardQueryArgs {
+            uid: self.uid.clone(),
+            name,
+            space,
+            version: self.version.clone(),
+            limit: self.limit,
+            registry_type,
+            ..Default::default()
+        })
+    }
+}
+
+#[derive(Args)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct ImportArgs {
+    #[command(subcommand)]
+    pub source: Option<ImportCommands>,
+
+    /// Path of the bundle to import
+    #[arg(long = "path")]
+    pub path: Option<String>,
+
+    /// Skip cards that already exist in the registry instead of aborting the import
+    #[arg(long = "skip-existing", default_value = "false")]
+    pub skip_existing: bool,
+}
+
+#[derive(Args)]
+pub struct ExperimentSyncArgs {
+    /// Offline directory passed to `start_experiment`, or a single run directory inside it
+    pub path: PathBuf,
+}
+
+#[derive(Args)]
+pub struct ImportMlflowArgs {
+    /// MLflow `mlruns` directory, a single experiment directory or a single run directory
+    pub path: PathBuf,
+}
+
+#[derive(Args)]
+pub struct ImportTensorboardArgs {
+    /// TensorBoard log directory. Every directory holding event files is imported as a run
+    pub path: PathBuf,
+
+    /// Space to register the runs in
+    #[arg(long = "space")]
+    pub space: String,
+}
+
+#[derive(Args)]
+pub struct ExperimentEnvDiffArgs {
+    /// Uid of the first experiment
+    pub left_uid: String,
+
+    /// Uid of the experiment to compare against
+    pub right_uid: String,
+}
+
+#[derive(Args)]
+pub struct DbMigrateArgs {
+    /// Connection uri of the database to copy from
+    #[arg(long = "from")]
+    pub from: String,
+
+    /// Connection uri of the database to copy to. Tables are created if they do not exist
+    #[arg(long = "to")]
+    pub to: String,
+
+    /// Number of rows copied per batch
+    #[arg(long = "batch-size", default_value = "1000", value_parser = clap::value_parser!(i64).range(1..))]
+    pub batch_size: i64,
+
+    /// File recording copy progress, used to resume an interrupted migration
+    #[arg(long = "checkpoint", default_value = "opsml_db_migrate.json")]
+    pub checkpoint: PathBuf,
+}
+
+#[derive(Args)]
+pub struct DbRotateKeyArgs {
+    /// Connection uri of the registry database
+    #[arg(long = "database")]
+    pub database: String,
+
+    /// Number of artifact keys re-wrapped per transaction
+    #[arg(long = "batch-size", default_value = "500", value_parser = clap::value_parser!(i64).range(1..))]
+    pub batch_size: i64,
+
+    /// File recording rotation progress, used to resume an interrupted rotation
+    #[arg(long = "checkpoint", default_value = "opsml_db_rotate_key.json")]
+    pub checkpoint: PathBuf,
+
+    /// Check that every key can be decrypted without updating the database
+    #[arg(long = "dry-run", default_value = "false")]
+    pub dry_run: bool,
+
+    /// Key file the keys are currently wrapped with. Defaults to OPSML_OLD_ENCRYPT_SECRET
+    #[arg(long = "old-key-file")]
+    pub old_key_file: Option<PathBuf>,
+
+    /// Key file to wrap the keys with. Defaults to OPSML_NEW_ENCRYPT_SECRET
+    #[arg(long = "new-key-file")]
+    pub new_key_file: Option<PathBuf>,
+
+    /// Label of the key in the new key file to wrap the keys with
+    #[arg(long = "new-key-label", requires = "new_key_file")]
+    pub new_key_label: Option<String>,
+}
+
+#[derive(Args)]
+pub struct SigningKeyCreateArgs {
+    /// Space whose cards the key signs
+    #[arg(long = "space")]
+    pub space: String,
+
+    /// File to write the private signing key to. Must not already exist
+    #[arg(long = "output", default_value = "opsml_signing_key.json")]
+    pub output: PathBuf,
+}
+
+#[derive(Args)]
+pub struct SigningKeyListArgs {
+    /// Only list the keys of this space
+    #[arg(long = "space")]
+    pub space: Option<String>,
+}
+
+#[derive(Args)]
+pub struct SigningKeyRevokeArgs {
+    /// Id of the key to revoke
+    #[arg(long = "key-id")]
+    pub key_id: String,
+}
+
 #[derive(Args, Clone)]
 pub struct DownloadCard {
     /// Card space
@@ -85,7 +499,7 @@ pub struct DownloadCard {
     #[arg(long = "name")]
     pub name: Option<String>,
 
-    /// Card version
+    /// Card version, or a version alias such as @champion
     #[arg(long = "version")]
     pub version: Option<String>,
 
@@ -211,3 +625,18 @@ pub struct UiArgs {
     #[arg(long = "version")]
     pub version: Option<String>,
 }
+
+#[derive(Args)]
+pub struct AuditExportArgs {
+    /// Start of the export window (RFC 3339, e.g. 2025-01-01T00:00:00Z)
+    #[arg(long = "start-time")]
+    pub start_time: DateTime<Utc>,
+
+    /// End of the export window (RFC 3339, e.g. 2025-02-01T00:00:00Z)
+    #[arg(long = "end-time")]
+    pub end_time: DateTime<Utc>,
+
+    /// File to write the JSON Lines export to. Defaults to stdout
+    #[arg(long = "output")]
+    pub output: Option<PathBuf>,
+}
@@ -2,5 +2,6 @@ pub mod arg;
 pub mod commands;
 
 pub use commands::{
-    Cli, Commands, GenerateCommands, GetCommands, InstallCommands, ListCommands, LOGO_TEXT,
+    Cli, Commands, GenerateCommands, GetCommands, ImportCommands, InstallCommands, ListCommands,
+    LOGO_TEXT,
 };
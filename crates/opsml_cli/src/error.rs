@@ -1,6 +1,8 @@
 use opsml_cards::error::CardError;
 use opsml_crypt::error::CryptError;
+use opsml_experiment::error::ExperimentError;
 use opsml_registry::error::RegistryError;
+use opsml_sql::error::SqlError;
 use opsml_storage::storage::error::StorageError;
 use opsml_toml::error::PyProjectTomlError;
 use opsml_types::error::TypeError;
@@ -35,6 +37,9 @@ pub enum CliError {
     #[error(transparent)]
     RegistryError(#[from] RegistryError),
 
+    #[error(transparent)]
+    ExperimentError(#[from] ExperimentError),
+
     #[error("Failed to load service card JSON file")]
     LoadServiceCardError(#[source] CardError),
 
@@ -76,6 +81,57 @@ pub enum CliError {
 
     #[error(transparent)]
     SerdeJsonError(#[from] serde_json::Error),
+
+    #[error("Audit chain broken at event {0}: {1}")]
+    AuditChainBroken(i32, String),
+
+    #[error("Invalid lineage format: {0}. Expected mermaid or dot")]
+    InvalidLineageFormat(String),
+
+    #[error("{0} artifact(s) of card {1} do not match its manifest")]
+    ArtifactVerificationFailed(usize, String),
+
+    #[error("Card {0} has no artifact manifest")]
+    MissingArtifactManifest(String),
+
+    #[error("No cards matched the export query")]
+    NoCardsToExport,
+
+    #[error("Failed to read or write bundle archive")]
+    BundleArchiveError(#[source] zip::result::ZipError),
+
+    #[error("Bundle artifact keys are password protected. Set OPSML_BUNDLE_PASSWORD to import it")]
+    BundlePasswordRequired,
+
+    #[error("Set OPSML_BUNDLE_PASSWORD to protect the artifact keys in the bundle, or pass --insecure-plaintext-keys to store them unprotected")]
+    BundleKeysUnprotected,
+
+    #[error("Provide --path with the bundle to import, or import from mlflow or tensorboard")]
+    MissingImportPath,
+
+    #[error("{0} card(s) in the bundle already exist in the registry. Use --skip-existing to import the rest")]
+    ImportConflicts(usize),
+
+    #[error(transparent)]
+    DecodeError(#[from] base64::DecodeError),
+
+    #[error(transparent)]
+    SqlError(#[from] SqlError),
+
+    #[error("--from and --to must be different databases")]
+    SameDatabase,
+
+    #[error("Row counts differ for {0} table(s). Re-run the migration to resume")]
+    RowCountMismatch(usize),
+
+    #[error("{0} must be set to rotate the encryption secret")]
+    MissingEncryptionSecret(String),
+
+    #[error("The old and new keys must be different")]
+    SameEncryptionSecret,
+
+    #[error("Signing key file {0} already exists")]
+    SigningKeyFileExists(String),
 }
 
 impl From<CliError> for PyErr {
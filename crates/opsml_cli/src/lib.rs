@@ -2,8 +2,15 @@ pub mod actions;
 pub mod cli;
 pub mod error;
 
-use crate::actions::{download_card, list_cards};
-use crate::cli::{Cli, Commands, GenerateCommands, GetCommands, InstallCommands, ListCommands};
+use crate::actions::{
+    create_signing_key, diff_experiment_environments, download_card, export_audit_events,
+    export_bundle, import_bundle, import_mlflow, import_tensorboard, list_audit_events, list_cards,
+    list_signing_keys, migrate_database, print_card_lineage, revoke_signing_key,
+    rotate_encryption_secret, search_cards, sync_experiments, verify_audit_chain, verify_card,
+};
+use crate::cli::{
+    Cli, Commands, GenerateCommands, GetCommands, ImportCommands, InstallCommands, ListCommands,
+};
 use actions::download::download_service;
 pub use actions::{
     demo::run_python_code,
@@ -16,7 +23,9 @@ pub use actions::{
 use anyhow::Context;
 use clap::Parser;
 pub use cli::arg::ScouterArgs;
-use cli::commands::{ScouterCommands, UiCommands};
+use cli::commands::{
+    AuditCommands, DbCommands, ExperimentCommands, ScouterCommands, SigningKeyCommands, UiCommands,
+};
 use opsml_colors::Colorize;
 use opsml_types::RegistryType;
 
@@ -53,6 +62,26 @@ pub fn run_cli(args: Vec<String>) -> anyhow::Result<()> {
             ListCommands::Prompt(args) => {
                 list_cards(args, RegistryType::Prompt).context("Failed to list PromptCards")
             }
+            ListCommands::AuditEvents(args) => {
+                list_audit_events(args).context("Failed to list audit events")
+            }
+        },
+        Some(Commands::Search(args)) => search_cards(args).context("Failed to search cards"),
+        Some(Commands::Lineage(args)) => {
+            print_card_lineage(args).context("Failed to get card lineage")
+        }
+        Some(Commands::Verify(args)) => {
+            verify_card(args).context("Failed to verify card artifacts")
+        }
+        Some(Commands::Export(args)) => export_bundle(args).context("Failed to export cards"),
+        Some(Commands::Import(args)) => match &args.source {
+            Some(ImportCommands::Mlflow(args)) => {
+                import_mlflow(args).context("Failed to import MLflow runs")
+            }
+            Some(ImportCommands::Tensorboard(args)) => {
+                import_tensorboard(args).context("Failed to import TensorBoard runs")
+            }
+            None => import_bundle(args).context("Failed to import cards"),
         },
         Some(Commands::Get { command }) => match command {
             GetCommands::Model(args) => {
@@ -109,6 +138,55 @@ pub fn run_cli(args: Vec<String>) -> anyhow::Result<()> {
             }
         },
 
+        Some(Commands::Audit { command }) => match command {
+            AuditCommands::Verify => {
+                verify_audit_chain().context("Failed to verify audit chain")?;
+                Ok(())
+            }
+            AuditCommands::Export(args) => {
+                export_audit_events(args).context("Failed to export audit events")?;
+                Ok(())
+            }
+        },
+
+        Some(Commands::Db { command }) => match command {
+            DbCommands::Migrate(args) => {
+                migrate_database(args).context("Failed to migrate database")?;
+                Ok(())
+            }
+            DbCommands::RotateKey(args) => {
+                rotate_encryption_secret(args).context("Failed to rotate encryption secret")?;
+                Ok(())
+            }
+        },
+
+        Some(Commands::SigningKey { command }) => match command {
+            SigningKeyCommands::Create(args) => {
+                create_signing_key(args).context("Failed to create signing key")?;
+                Ok(())
+            }
+            SigningKeyCommands::List(args) => {
+                list_signing_keys(args).context("Failed to list signing keys")?;
+                Ok(())
+            }
+            SigningKeyCommands::Revoke(args) => {
+                revoke_signing_key(args).context("Failed to revoke signing key")?;
+                Ok(())
+            }
+        },
+
+        Some(Commands::Experiment { command }) => match command {
+            ExperimentCommands::Sync(args) => {
+                sync_experiments(args).context("Failed to sync experiments")?;
+                Ok(())
+            }
+            ExperimentCommands::EnvDiff(args) => {
+                diff_experiment_environments(args)
+                    .context("Failed to diff experiment environments")?;
+                Ok(())
+            }
+        },
+
         Some(Commands::Ui { command }) => match command {
             // Start commands can be added here
             UiCommands::Start(args) => {
@@ -88,6 +88,16 @@ impl OpsmlApiClient {
         Ok(api_client)
     }
 
+    /// Create a client that authenticates with a pre-issued API token.
+    /// API tokens are long-lived, so no login request is made.
+    pub fn with_api_token(url: String, client: &Client, api_token: &str) -> Self {
+        Self {
+            client: client.clone(),
+            base_path: url,
+            auth_token: Arc::new(RwLock::new(api_token.to_string())),
+        }
+    }
+
     #[instrument(skip_all)]
     fn refresh_token(&self) -> Result<(), ApiClientError> {
         let url = format!("{}/{}", self.base_path, Routes::AuthLogin.as_str());
@@ -304,6 +314,11 @@ pub fn build_api_client(settings: &OpsmlStorageSettings) -> Result<OpsmlApiClien
         "{}/{}",
         settings.api_settings.base_url, settings.api_settings.opsml_dir
     );
+
+    if let Some(api_token) = settings.api_settings.api_token.as_deref() {
+        return Ok(OpsmlApiClient::with_api_token(url, &client, api_token));
+    }
+
     OpsmlApiClient::new(url, &client)
 }
 
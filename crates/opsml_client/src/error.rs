@@ -32,6 +32,9 @@ pub enum ApiClientError {
 
     #[error("{0}")]
     ServerError(String),
+
+    #[error("Request rejected with status {0}: {1}")]
+    RequestRejected(u16, String),
 }
 
 #[derive(Error, Debug)]
@@ -66,3 +69,15 @@ pub enum RegistryError {
     #[error("Failed to insert parameters")]
     InsertParameterError,
 }
+
+impl RegistryError {
+    /// Whether resending the same request may succeed. Requests rejected with a client error
+    /// are rejected again, unless the rejection was an expired login, timeout or rate limit
+    pub fn is_retryable(&self) -> bool {
+        !matches!(
+            self,
+            RegistryError::ApiClientError(ApiClientError::RequestRejected(status, _))
+                if !matches!(status, 401 | 408 | 429)
+        )
+    }
+}
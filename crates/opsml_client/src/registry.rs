@@ -7,8 +7,10 @@ use opsml_types::{
     contracts::*,
     Alive, IntegratedService, RegistryMode, RegistryType,
 };
+use reqwest::blocking::Response;
 use scouter_client::{ProfileRequest, ProfileStatusRequest, ScouterServerError};
 use serde::Deserialize;
+use std::io::Write;
 use std::sync::Arc;
 use tracing::error;
 use tracing::instrument;
@@ -19,6 +21,17 @@ pub struct ErrorResponse {
     error: String,
 }
 
+/// Returns the response unless the server rejected the request with a client error (4xx)
+fn reject_client_error(response: Response) -> Result<Response, RegistryError> {
+    let status = response.status();
+    if !status.is_client_error() {
+        return Ok(response);
+    }
+
+    let error_text = response.text().map_err(RegistryError::RequestError)?;
+    Err(ApiClientError::RequestRejected(status.as_u16(), error_text).into())
+}
+
 // TODO: Add trait for client and server registry
 #[derive(Debug, Clone)]
 pub struct ClientRegistry {
@@ -62,6 +75,12 @@ impl ClientRegistry {
                 error!("Failed to list cards {}", e);
             })?;
 
+        // a version alias that does not exist is reported by the server
+        if response.status() != 200 {
+            let error_text = response.text().map_err(RegistryError::RequestError)?;
+            return Err(ApiClientError::ServerError(error_text).into());
+        }
+
         response
             .json::<Vec<CardRecord>>()
             .map_err(RegistryError::RequestError)
@@ -131,6 +150,57 @@ impl ClientRegistry {
         }
     }
 
+    #[instrument(skip_all)]
+    pub fn import_card(
+        &self,
+        card: CardRecord,
+        encryption_key: Vec<u8>,
+        has_manifest: bool,
+    ) -> Result<ImportCardResponse, RegistryError> {
+        let import_request = ImportCardRequest {
+            registry_type: self.registry_type.clone(),
+            card,
+            encryption_key,
+            has_manifest,
+        };
+
+        let body = serde_json::to_value(import_request).inspect_err(|e| {
+            error!("Failed to serialize import request {}", e);
+        })?;
+
+        let response = self
+            .api_client
+            .request(
+                Routes::CardImport,
+                RequestType::Post,
+                Some(body),
+                None,
+                None,
+            )
+            .inspect_err(|e| {
+                error!("Failed to import card {}", e);
+            })?;
+
+        // check if 403 forbidden and get error message
+        if response.status() == 403 {
+            let error = response
+                .json::<ErrorResponse>()
+                .map_err(RegistryError::RequestError)?;
+
+            return Err(ApiClientError::ForbiddenError(error.error).into());
+        }
+
+        // conflicts with existing cards are reported by the server
+        if response.status() != 200 {
+            let error_text = response.text().map_err(RegistryError::RequestError)?;
+            return Err(ApiClientError::ServerError(error_text).into());
+        }
+
+        response
+            .json::<ImportCardResponse>()
+            .map_err(RegistryError::RequestError)
+    }
+
     #[instrument(skip_all)]
     pub fn update_card(&self, card: &CardRecord) -> Result<(), RegistryError> {
         let update_request = UpdateCardRequest {
@@ -138,16 +208,393 @@ impl ClientRegistry {
             registry_type: self.registry_type.clone(),
         };
 
-        // serialize card to json
-        let body = serde_json::to_value(update_request)?;
+        // serialize card to json
+        let body = serde_json::to_value(update_request)?;
+
+        let response = self.api_client.request(
+            Routes::CardUpdate,
+            RequestType::Post,
+            Some(body),
+            None,
+            None,
+        )?;
+
+        // check if 403 forbidden and get error message
+        if response.status() == 403 {
+            let error = response
+                .json::<ErrorResponse>()
+                .map_err(RegistryError::RequestError)?;
+
+            return Err(ApiClientError::ForbiddenError(error.error).into());
+        }
+
+        let updated = response
+            .json::<UpdateCardResponse>()
+            .map_err(RegistryError::RequestError)?;
+
+        if updated.updated {
+            Ok(())
+        } else {
+            Err(RegistryError::UpdateCardError)
+        }
+    }
+
+    #[instrument(skip_all)]
+    pub fn delete_card(&self, delete_request: DeleteCardRequest) -> Result<(), RegistryError> {
+        let query_string = serde_qs::to_string(&delete_request)?;
+
+        let response = self
+            .api_client
+            .request(
+                Routes::CardDelete,
+                RequestType::Delete,
+                None,
+                Some(query_string),
+                None,
+            )
+            .inspect_err(|e| {
+                error!("Request failed {}", e);
+            })?;
+
+        // check if 403 forbidden and get error message
+        if response.status() == 403 {
+            let error = response
+                .json::<ErrorResponse>()
+                .map_err(RegistryError::RequestError)?;
+
+            return Err(ApiClientError::ForbiddenError(error.error).into());
+        }
+
+        if response.status() != 200 {
+            let error_text = response.text().map_err(RegistryError::RequestError)?;
+            return Err(ApiClientError::ServerError(error_text).into());
+        }
+
+        let deleted = response
+            .json::<UidResponse>()
+            .map_err(RegistryError::RequestError)?;
+
+        if !deleted.exists {
+            Ok(())
+        } else {
+            Err(RegistryError::DeleteCardError)
+        }
+    }
+
+    #[instrument(skip_all)]
+    pub fn restore_card(&self, restore_request: &UidRequest) -> Result<(), RegistryError> {
+        let body = serde_json::to_value(restore_request)?;
+
+        let response = self
+            .api_client
+            .request(
+                Routes::CardRestore,
+                RequestType::Put,
+                Some(body),
+                None,
+                None,
+            )
+            .inspect_err(|e| {
+                error!("Failed to restore card {}", e);
+            })?;
+
+        // check if 403 forbidden and get error message
+        if response.status() == 403 {
+            let error = response
+                .json::<ErrorResponse>()
+                .map_err(RegistryError::RequestError)?;
+
+            return Err(ApiClientError::ForbiddenError(error.error).into());
+        }
+
+        if response.status() != 200 {
+            let error_text = response.text().map_err(RegistryError::RequestError)?;
+            return Err(ApiClientError::ServerError(error_text).into());
+        }
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    pub fn list_deleted_cards(
+        &self,
+        trash_query: &DeletedCardQuery,
+    ) -> Result<Vec<DeletedCard>, RegistryError> {
+        let query_string = serde_qs::to_string(trash_query)?;
+
+        let response = self
+            .api_client
+            .request(
+                Routes::CardTrash,
+                RequestType::Get,
+                None,
+                Some(query_string),
+                None,
+            )
+            .inspect_err(|e| {
+                error!("Failed to list deleted cards {}", e);
+            })?;
+
+        // check if 403 forbidden and get error message
+        if response.status() == 403 {
+            let error = response
+                .json::<ErrorResponse>()
+                .map_err(RegistryError::RequestError)?;
+
+            return Err(ApiClientError::ForbiddenError(error.error).into());
+        }
+
+        if response.status() != 200 {
+            let error_text = response.text().map_err(RegistryError::RequestError)?;
+            return Err(ApiClientError::ServerError(error_text).into());
+        }
+
+        response
+            .json::<Vec<DeletedCard>>()
+            .map_err(RegistryError::RequestError)
+    }
+
+    #[instrument(skip_all)]
+    pub fn search_cards(
+        &self,
+        search_query: &CardSearchQuery,
+    ) -> Result<Vec<CardSearchResult>, RegistryError> {
+        let query_string = serde_qs::to_string(search_query)?;
+
+        let response = self
+            .api_client
+            .request(
+                Routes::CardSearch,
+                RequestType::Get,
+                None,
+                Some(query_string),
+                None,
+            )
+            .inspect_err(|e| {
+                error!("Failed to search cards {}", e);
+            })?;
+
+        // check if 403 forbidden and get error message
+        if response.status() == 403 {
+            let error = response
+                .json::<ErrorResponse>()
+                .map_err(RegistryError::RequestError)?;
+
+            return Err(ApiClientError::ForbiddenError(error.error).into());
+        }
+
+        if response.status() != 200 {
+            let error_text = response.text().map_err(RegistryError::RequestError)?;
+            return Err(ApiClientError::ServerError(error_text).into());
+        }
+
+        response
+            .json::<Vec<CardSearchResult>>()
+            .map_err(RegistryError::RequestError)
+    }
+
+    #[instrument(skip_all)]
+    pub fn get_card_lineage(
+        &self,
+        lineage_request: &CardLineageRequest,
+    ) -> Result<CardLineage, RegistryError> {
+        let query_string = serde_qs::to_string(lineage_request)?;
+
+        let response = self
+            .api_client
+            .request(
+                Routes::CardLineage,
+                RequestType::Get,
+                None,
+                Some(query_string),
+                None,
+            )
+            .inspect_err(|e| {
+                error!("Failed to get card lineage {}", e);
+            })?;
+
+        // check if 403 forbidden and get error message
+        if response.status() == 403 {
+            let error = response
+                .json::<ErrorResponse>()
+                .map_err(RegistryError::RequestError)?;
+
+            return Err(ApiClientError::ForbiddenError(error.error).into());
+        }
+
+        if response.status() != 200 {
+            let error_text = response.text().map_err(RegistryError::RequestError)?;
+            return Err(ApiClientError::ServerError(error_text).into());
+        }
+
+        response
+            .json::<CardLineage>()
+            .map_err(RegistryError::RequestError)
+    }
+
+    #[instrument(skip_all)]
+    pub fn verify_card_artifacts(
+        &self,
+        verify_request: &VerifyArtifactsRequest,
+    ) -> Result<ArtifactIntegrityReport, RegistryError> {
+        let query_string = serde_qs::to_string(verify_request)?;
+
+        let response = self
+            .api_client
+            .request(
+                Routes::CardVerify,
+                RequestType::Get,
+                None,
+                Some(query_string),
+                None,
+            )
+            .inspect_err(|e| {
+                error!("Failed to verify card artifacts {}", e);
+            })?;
+
+        // check if 403 forbidden and get error message
+        if response.status() == 403 {
+            let error = response
+                .json::<ErrorResponse>()
+                .map_err(RegistryError::RequestError)?;
+
+            return Err(ApiClientError::ForbiddenError(error.error).into());
+        }
+
+        if response.status() != 200 {
+            let error_text = response.text().map_err(RegistryError::RequestError)?;
+            return Err(ApiClientError::ServerError(error_text).into());
+        }
+
+        response
+            .json::<ArtifactIntegrityReport>()
+            .map_err(RegistryError::RequestError)
+    }
+
+    #[instrument(skip_all)]
+    pub fn update_card_stage(
+        &self,
+        stage_request: &CardStageRequest,
+    ) -> Result<CardStageResponse, RegistryError> {
+        let body = serde_json::to_value(stage_request)?;
+
+        let response = self
+            .api_client
+            .request(Routes::CardStage, RequestType::Put, Some(body), None, None)
+            .inspect_err(|e| {
+                error!("Failed to update card stage {}", e);
+            })?;
+
+        // check if 403 forbidden and get error message
+        if response.status() == 403 {
+            let error = response
+                .json::<ErrorResponse>()
+                .map_err(RegistryError::RequestError)?;
+
+            return Err(ApiClientError::ForbiddenError(error.error).into());
+        }
+
+        if response.status() != 200 {
+            let error_text = response.text().map_err(RegistryError::RequestError)?;
+            return Err(ApiClientError::ServerError(error_text).into());
+        }
+
+        response
+            .json::<CardStageResponse>()
+            .map_err(RegistryError::RequestError)
+    }
+
+    #[instrument(skip_all)]
+    pub fn set_version_alias(
+        &self,
+        alias_request: &VersionAliasRequest,
+    ) -> Result<VersionAlias, RegistryError> {
+        let body = serde_json::to_value(alias_request)?;
+
+        let response = self
+            .api_client
+            .request(Routes::CardAlias, RequestType::Put, Some(body), None, None)
+            .inspect_err(|e| {
+                error!("Failed to set version alias {}", e);
+            })?;
+
+        // check if 403 forbidden and get error message
+        if response.status() == 403 {
+            let error = response
+                .json::<ErrorResponse>()
+                .map_err(RegistryError::RequestError)?;
+
+            return Err(ApiClientError::ForbiddenError(error.error).into());
+        }
+
+        if response.status() != 200 {
+            let error_text = response.text().map_err(RegistryError::RequestError)?;
+            return Err(ApiClientError::ServerError(error_text).into());
+        }
+
+        response
+            .json::<VersionAlias>()
+            .map_err(RegistryError::RequestError)
+    }
+
+    #[instrument(skip_all)]
+    pub fn get_version_aliases(
+        &self,
+        alias_query: &VersionAliasQuery,
+    ) -> Result<Vec<VersionAlias>, RegistryError> {
+        let query_string = serde_qs::to_string(alias_query)?;
+
+        let response = self
+            .api_client
+            .request(
+                Routes::CardAlias,
+                RequestType::Get,
+                None,
+                Some(query_string),
+                None,
+            )
+            .inspect_err(|e| {
+                error!("Failed to get version aliases {}", e);
+            })?;
+
+        // check if 403 forbidden and get error message
+        if response.status() == 403 {
+            let error = response
+                .json::<ErrorResponse>()
+                .map_err(RegistryError::RequestError)?;
+
+            return Err(ApiClientError::ForbiddenError(error.error).into());
+        }
+
+        if response.status() != 200 {
+            let error_text = response.text().map_err(RegistryError::RequestError)?;
+            return Err(ApiClientError::ServerError(error_text).into());
+        }
+
+        response
+            .json::<Vec<VersionAlias>>()
+            .map_err(RegistryError::RequestError)
+    }
+
+    #[instrument(skip_all)]
+    pub fn delete_version_alias(
+        &self,
+        alias_query: &VersionAliasQuery,
+    ) -> Result<(), RegistryError> {
+        let query_string = serde_qs::to_string(alias_query)?;
 
-        let response = self.api_client.request(
-            Routes::CardUpdate,
-            RequestType::Post,
-            Some(body),
-            None,
-            None,
-        )?;
+        let response = self
+            .api_client
+            .request(
+                Routes::CardAlias,
+                RequestType::Delete,
+                None,
+                Some(query_string),
+                None,
+            )
+            .inspect_err(|e| {
+                error!("Failed to delete version alias {}", e);
+            })?;
 
         // check if 403 forbidden and get error message
         if response.status() == 403 {
@@ -158,32 +605,32 @@ impl ClientRegistry {
             return Err(ApiClientError::ForbiddenError(error.error).into());
         }
 
-        let updated = response
-            .json::<UpdateCardResponse>()
-            .map_err(RegistryError::RequestError)?;
-
-        if updated.updated {
-            Ok(())
-        } else {
-            Err(RegistryError::UpdateCardError)
+        if response.status() != 200 {
+            let error_text = response.text().map_err(RegistryError::RequestError)?;
+            return Err(ApiClientError::ServerError(error_text).into());
         }
+
+        Ok(())
     }
 
     #[instrument(skip_all)]
-    pub fn delete_card(&self, delete_request: DeleteCardRequest) -> Result<(), RegistryError> {
-        let query_string = serde_qs::to_string(&delete_request)?;
+    pub fn get_version_alias_history(
+        &self,
+        alias_query: &VersionAliasQuery,
+    ) -> Result<Vec<VersionAliasHistory>, RegistryError> {
+        let query_string = serde_qs::to_string(alias_query)?;
 
         let response = self
             .api_client
             .request(
-                Routes::CardDelete,
-                RequestType::Delete,
+                Routes::CardAliasHistory,
+                RequestType::Get,
                 None,
                 Some(query_string),
                 None,
             )
             .inspect_err(|e| {
-                error!("Request failed {}", e);
+                error!("Failed to get version alias history {}", e);
             })?;
 
         // check if 403 forbidden and get error message
@@ -195,15 +642,14 @@ impl ClientRegistry {
             return Err(ApiClientError::ForbiddenError(error.error).into());
         }
 
-        let deleted = response
-            .json::<UidResponse>()
-            .map_err(RegistryError::RequestError)?;
-
-        if !deleted.exists {
-            Ok(())
-        } else {
-            Err(RegistryError::DeleteCardError)
+        if response.status() != 200 {
+            let error_text = response.text().map_err(RegistryError::RequestError)?;
+            return Err(ApiClientError::ServerError(error_text).into());
         }
+
+        response
+            .json::<Vec<VersionAliasHistory>>()
+            .map_err(RegistryError::RequestError)
     }
 
     #[instrument(skip_all)]
@@ -223,6 +669,12 @@ impl ClientRegistry {
                 error!("Failed to get artifact key {}", e);
             })?;
 
+        // a version alias that does not exist is reported by the server
+        if response.status() != 200 {
+            let error_text = response.text().map_err(RegistryError::RequestError)?;
+            return Err(ApiClientError::ServerError(error_text).into());
+        }
+
         response
             .json::<ArtifactKey>()
             .map_err(RegistryError::RequestError)
@@ -310,6 +762,8 @@ impl ClientRegistry {
                 error!("Failed to insert hardware metrics {}", e);
             })?;
 
+        let response = reject_client_error(response)?;
+
         let inserted = response
             .json::<HardwareMetricResponse>()
             .map_err(RegistryError::RequestError)?;
@@ -361,6 +815,8 @@ impl ClientRegistry {
                 error!("Failed to insert metrics {}", e);
             })?;
 
+        let response = reject_client_error(response)?;
+
         let inserted = response
             .json::<MetricResponse>()
             .map_err(RegistryError::RequestError)?;
@@ -393,6 +849,32 @@ impl ClientRegistry {
             .map_err(RegistryError::RequestError)
     }
 
+    pub fn query_metrics(&self, query: &MetricQuery) -> Result<MetricPage, RegistryError> {
+        let body = serde_json::to_value(query)?;
+
+        let response = self
+            .api_client
+            .request(
+                Routes::ExperimentMetricQuery,
+                RequestType::Post,
+                Some(body),
+                None,
+                None,
+            )
+            .inspect_err(|e| {
+                error!("Failed to query metrics {}", e);
+            })?;
+
+        if response.status() != 200 {
+            let error_text = response.text().map_err(RegistryError::RequestError)?;
+            return Err(ApiClientError::ServerError(error_text).into());
+        }
+
+        response
+            .json::<MetricPage>()
+            .map_err(RegistryError::RequestError)
+    }
+
     pub fn insert_parameters(&self, parameters: &ParameterRequest) -> Result<(), RegistryError> {
         let body = serde_json::to_value(parameters)?;
 
@@ -409,6 +891,8 @@ impl ClientRegistry {
                 error!("Failed to insert parameters {}", e);
             })?;
 
+        let response = reject_client_error(response)?;
+
         let inserted = response.json::<ParameterResponse>()?;
 
         if inserted.success {
@@ -442,6 +926,35 @@ impl ClientRegistry {
             .map_err(RegistryError::RequestError)
     }
 
+    pub fn compare_experiments(
+        &self,
+        comparison_request: &ExperimentComparisonRequest,
+    ) -> Result<ExperimentComparison, RegistryError> {
+        let body = serde_json::to_value(comparison_request)?;
+
+        let response = self
+            .api_client
+            .request(
+                Routes::ExperimentCompare,
+                RequestType::Post,
+                Some(body),
+                None,
+                None,
+            )
+            .inspect_err(|e| {
+                error!("Failed to compare experiments {}", e);
+            })?;
+
+        if response.status() != 200 {
+            let error_text = response.text().map_err(RegistryError::RequestError)?;
+            return Err(ApiClientError::ServerError(error_text).into());
+        }
+
+        response
+            .json::<ExperimentComparison>()
+            .map_err(RegistryError::RequestError)
+    }
+
     #[instrument(skip_all)]
     pub fn check_service_health(&self, service: IntegratedService) -> Result<bool, RegistryError> {
         let route = match service {
@@ -537,4 +1050,193 @@ impl ClientRegistry {
 
         Ok(())
     }
+
+    #[instrument(skip_all)]
+    pub fn get_audit_events(
+        &self,
+        args: &AuditEventQueryArgs,
+    ) -> Result<AuditEventPage, RegistryError> {
+        let query_string = serde_qs::to_string(args)?;
+
+        let response = self
+            .api_client
+            .request(
+                Routes::AuditEvents,
+                RequestType::Get,
+                None,
+                Some(query_string),
+                None,
+            )
+            .inspect_err(|e| {
+                error!("Failed to get audit events {}", e);
+            })?;
+
+        if response.status() != 200 {
+            let error_text = response.text().map_err(RegistryError::RequestError)?;
+            return Err(ApiClientError::ServerError(error_text).into());
+        }
+
+        response
+            .json::<AuditEventPage>()
+            .map_err(RegistryError::RequestError)
+    }
+
+    #[instrument(skip_all)]
+    pub fn verify_audit_chain(&self) -> Result<AuditChainReport, RegistryError> {
+        let response = self
+            .api_client
+            .request(Routes::AuditVerify, RequestType::Get, None, None, None)
+            .inspect_err(|e| {
+                error!("Failed to verify audit chain {}", e);
+            })?;
+
+        if response.status() != 200 {
+            let error_text = response.text().map_err(RegistryError::RequestError)?;
+            return Err(ApiClientError::ServerError(error_text).into());
+        }
+
+        response
+            .json::<AuditChainReport>()
+            .map_err(RegistryError::RequestError)
+    }
+
+    #[instrument(skip_all)]
+    pub fn export_audit_events(
+        &self,
+        request: &AuditExportRequest,
+        writer: &mut dyn Write,
+    ) -> Result<(), RegistryError> {
+        let query_string = serde_qs::to_string(request)?;
+
+        let mut response = self
+            .api_client
+            .request(
+                Routes::AuditExport,
+                RequestType::Get,
+                None,
+                Some(query_string),
+                None,
+            )
+            .inspect_err(|e| {
+                error!("Failed to export audit events {}", e);
+            })?;
+
+        if response.status() != 200 {
+            let error_text = response.text().map_err(RegistryError::RequestError)?;
+            return Err(ApiClientError::ServerError(error_text).into());
+        }
+
+        response
+            .copy_to(writer)
+            .map_err(RegistryError::RequestError)?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    pub fn get_signing_keys(
+        &self,
+        query: &SigningKeyQuery,
+    ) -> Result<Vec<SpaceSigningKey>, RegistryError> {
+        let query_string = serde_qs::to_string(query)?;
+
+        let response = self
+            .api_client
+            .request(
+                Routes::SigningKey,
+                RequestType::Get,
+                None,
+                Some(query_string),
+                None,
+            )
+            .inspect_err(|e| {
+                error!("Failed to get signing keys {}", e);
+            })?;
+
+        if response.status() != 200 {
+            let error_text = response.text().map_err(RegistryError::RequestError)?;
+            return Err(ApiClientError::ServerError(error_text).into());
+        }
+
+        response
+            .json::<SigningKeyListResponse>()
+            .map(|response| response.keys)
+            .map_err(RegistryError::RequestError)
+    }
+
+    #[instrument(skip_all)]
+    pub fn create_signing_key(
+        &self,
+        request: &CreateSigningKeyRequest,
+    ) -> Result<SpaceSigningKey, RegistryError> {
+        let body = serde_json::to_value(request)?;
+
+        let response = self
+            .api_client
+            .request(
+                Routes::SigningKey,
+                RequestType::Post,
+                Some(body),
+                None,
+                None,
+            )
+            .inspect_err(|e| {
+                error!("Failed to register signing key {}", e);
+            })?;
+
+        // check if 403 forbidden and get error message
+        if response.status() == 403 {
+            let error = response
+                .json::<ErrorResponse>()
+                .map_err(RegistryError::RequestError)?;
+
+            return Err(ApiClientError::ForbiddenError(error.error).into());
+        }
+
+        if response.status() != 200 {
+            let error_text = response.text().map_err(RegistryError::RequestError)?;
+            return Err(ApiClientError::ServerError(error_text).into());
+        }
+
+        response
+            .json::<SpaceSigningKey>()
+            .map_err(RegistryError::RequestError)
+    }
+
+    #[instrument(skip_all)]
+    pub fn revoke_signing_key(
+        &self,
+        request: &RevokeSigningKeyRequest,
+    ) -> Result<(), RegistryError> {
+        let query_string = serde_qs::to_string(request)?;
+
+        let response = self
+            .api_client
+            .request(
+                Routes::SigningKey,
+                RequestType::Delete,
+                None,
+                Some(query_string),
+                None,
+            )
+            .inspect_err(|e| {
+                error!("Failed to revoke signing key {}", e);
+            })?;
+
+        // check if 403 forbidden and get error message
+        if response.status() == 403 {
+            let error = response
+                .json::<ErrorResponse>()
+                .map_err(RegistryError::RequestError)?;
+
+            return Err(ApiClientError::ForbiddenError(error.error).into());
+        }
+
+        if response.status() != 204 {
+            let error_text = response.text().map_err(RegistryError::RequestError)?;
+            return Err(ApiClientError::ServerError(error_text).into());
+        }
+
+        Ok(())
+    }
 }
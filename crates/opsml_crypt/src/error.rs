@@ -13,6 +13,9 @@ pub enum CryptError {
     #[error("Failed to decrypt file: {0}")]
     DecryptError(String),
 
+    #[error("Unsupported encrypted file format version: {0}")]
+    UnsupportedFormatVersion(u8),
+
     #[error(transparent)]
     UtilError(#[from] UtilError),
 
@@ -30,4 +33,16 @@ pub enum CryptError {
 
     #[error("Failed to decrypt key using AES-256-GCM")]
     DecryptKeyError,
+
+    #[error("Key provider error: {0}")]
+    KeyProviderError(String),
+
+    #[error("No key with label {0} in the key file")]
+    UnknownKeyLabel(String),
+
+    #[error("Invalid signing key: {0}")]
+    SigningKeyError(String),
+
+    #[error("Signature verification failed")]
+    InvalidSignature,
 }
@@ -1,22 +1,300 @@
 use crate::error::CryptError;
+use aes_gcm::aead::Payload;
 use aes_gcm::Nonce;
 use aes_gcm::{
-    aead::{Aead, AeadCore, KeyInit, OsRng},
+    aead::{Aead, KeyInit},
     Aes256Gcm,
     Key, // Or `Aes128Gcm`
 };
 
 use opsml_utils::FileUtils;
+use rand::{rngs::OsRng, TryRngCore};
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use sha2::{Digest, Sha256};
 use std::fs::{self, File};
-use std::io::{BufReader, BufWriter, Read, Write};
+use std::io::{BufRead, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
 use std::path::Path;
 use tracing::{debug, instrument};
 
 static CHUNK_SIZE: usize = 1024 * 1024 * 16; // 16 MiB chunk size
 const BUFFER_SIZE: usize = 1024 * 1024 * 2; // 2 MiB buffer size
 
-pub fn encrypt_file(input_path: &Path, key_bytes: &[u8]) -> Result<(), CryptError> {
+/// Marks files written in the streaming format. Files without it were written in the legacy
+/// format of independent chunks with random nonces
+const STREAM_MAGIC: &[u8; 8] = b"OPSMLENC";
+const STREAM_VERSION: u8 = 1;
+const NONCE_PREFIX_SIZE: usize = 7;
+const HEADER_SIZE: usize = STREAM_MAGIC.len() + 1 + 4 + NONCE_PREFIX_SIZE;
+const NONCE_SIZE: usize = 12;
+const TAG_SIZE: usize = 16;
+
+/// Largest chunk size accepted when reading a header
+const MAX_CHUNK_SIZE: usize = 1024 * 1024 * 64; // 64 MiB
+
+/// Header of the streaming format: `magic | version | chunk size (u32 LE) | nonce prefix`.
+///
+/// Every chunk except the last holds exactly `chunk_size` bytes of plaintext, so chunks can be
+/// located without reading the file. Chunk `i` is encrypted with the nonce
+/// `nonce prefix | i (u32 BE) | last flag` and authenticates the header, `i` (u64 BE) and the last
+/// flag as associated data. Reordered, duplicated or dropped chunks fail to decrypt
+#[derive(Debug, Clone, PartialEq)]
+struct StreamHeader {
+    chunk_size: u32,
+    nonce_prefix: [u8; NONCE_PREFIX_SIZE],
+}
+
+impl StreamHeader {
+    fn new(chunk_size: usize) -> Result<Self, CryptError> {
+        let chunk_size = u32::try_from(chunk_size)
+            .map_err(|_| CryptError::EncryptError(format!("Invalid chunk size: {chunk_size}")))?;
+
+        let mut nonce_prefix = [0u8; NONCE_PREFIX_SIZE];
+        OsRng
+            .try_fill_bytes(&mut nonce_prefix)
+            .map_err(|e| CryptError::EncryptError(e.to_string()))?;
+
+        Ok(Self {
+            chunk_size,
+            nonce_prefix,
+        })
+    }
+
+    fn to_bytes(&self) -> [u8; HEADER_SIZE] {
+        let mut bytes = [0u8; HEADER_SIZE];
+        bytes[..8].copy_from_slice(STREAM_MAGIC);
+        bytes[8] = STREAM_VERSION;
+        bytes[9..13].copy_from_slice(&self.chunk_size.to_le_bytes());
+        bytes[13..].copy_from_slice(&self.nonce_prefix);
+        bytes
+    }
+
+    /// Parses the start of an encrypted file. None when the file is in the legacy format
+    fn from_bytes(bytes: &[u8]) -> Result<Option<Self>, CryptError> {
+        if bytes.len() < HEADER_SIZE {
+            // a legacy file this short is truncated as well, so it is never read as legacy
+            let prefix_len = bytes.len().min(STREAM_MAGIC.len());
+            if !bytes.is_empty() && bytes[..prefix_len] == STREAM_MAGIC[..prefix_len] {
+                return Err(CryptError::DecryptError(format!(
+                    "Header is truncated: {} of {HEADER_SIZE} bytes",
+                    bytes.len()
+                )));
+            }
+            return Ok(None);
+        }
+
+        if &bytes[..8] != STREAM_MAGIC {
+            return Ok(None);
+        }
+
+        if bytes[8] != STREAM_VERSION {
+            return Err(CryptError::UnsupportedFormatVersion(bytes[8]));
+        }
+
+        let chunk_size = u32::from_le_bytes([bytes[9], bytes[10], bytes[11], bytes[12]]);
+        if chunk_size == 0 || chunk_size as usize > MAX_CHUNK_SIZE {
+            return Err(CryptError::DecryptError(format!(
+                "Invalid chunk size: {chunk_size} bytes"
+            )));
+        }
+
+        let mut nonce_prefix = [0u8; NONCE_PREFIX_SIZE];
+        nonce_prefix.copy_from_slice(&bytes[13..HEADER_SIZE]);
+
+        Ok(Some(Self {
+            chunk_size,
+            nonce_prefix,
+        }))
+    }
+
+    fn encrypted_chunk_size(&self) -> usize {
+        self.chunk_size as usize + TAG_SIZE
+    }
+
+    fn nonce(&self, index: u64, last: bool) -> Result<[u8; NONCE_SIZE], CryptError> {
+        let counter = u32::try_from(index)
+            .map_err(|_| CryptError::EncryptError("File has too many chunks".to_string()))?;
+
+        let mut nonce = [0u8; NONCE_SIZE];
+        nonce[..NONCE_PREFIX_SIZE].copy_from_slice(&self.nonce_prefix);
+        nonce[NONCE_PREFIX_SIZE..NONCE_SIZE - 1].copy_from_slice(&counter.to_be_bytes());
+        nonce[NONCE_SIZE - 1] = last as u8;
+        Ok(nonce)
+    }
+
+    fn aad(&self, index: u64, last: bool) -> Vec<u8> {
+        let mut aad = self.to_bytes().to_vec();
+        aad.extend_from_slice(&index.to_be_bytes());
+        aad.push(last as u8);
+        aad
+    }
+
+    fn encrypt_chunk(
+        &self,
+        cipher: &Aes256Gcm,
+        index: u64,
+        last: bool,
+        chunk: &[u8],
+    ) -> Result<Vec<u8>, CryptError> {
+        let nonce = self.nonce(index, last)?;
+        let aad = self.aad(index, last);
+
+        cipher
+            .encrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: chunk,
+                    aad: &aad,
+                },
+            )
+            .map_err(|e| CryptError::EncryptError(e.to_string()))
+    }
+
+    fn decrypt_chunk(
+        &self,
+        cipher: &Aes256Gcm,
+        index: u64,
+        last: bool,
+        chunk: &[u8],
+    ) -> Result<Vec<u8>, CryptError> {
+        let nonce = self.nonce(index, last)?;
+        let aad = self.aad(index, last);
+
+        cipher
+            .decrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: chunk,
+                    aad: &aad,
+                },
+            )
+            .map_err(|_| {
+                CryptError::DecryptError(format!(
+                    "Chunk {index} failed authentication. The file was modified, reordered or truncated"
+                ))
+            })
+    }
+}
+
+/// Reads until `buffer` is full or the reader is exhausted
+fn read_full(reader: &mut impl Read, buffer: &mut [u8]) -> Result<usize, CryptError> {
+    let mut read_bytes = 0;
+
+    while read_bytes < buffer.len() {
+        match reader.read(&mut buffer[read_bytes..]) {
+            Ok(0) => break,
+            Ok(n) => read_bytes += n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(CryptError::IoError(e)),
+        }
+    }
+
+    Ok(read_bytes)
+}
+
+fn encrypt_stream(
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+    cipher: &Aes256Gcm,
+    chunk_size: usize,
+) -> Result<(), CryptError> {
+    let header = StreamHeader::new(chunk_size)?;
+    output.write_all(&header.to_bytes())?;
+
+    let mut buffer = vec![0u8; chunk_size];
+    let mut index = 0;
+
+    // an empty file still gets a last chunk, so dropping every chunk is detected
+    loop {
+        let read_bytes = read_full(input, &mut buffer)?;
+        let last = read_bytes < chunk_size || input.fill_buf()?.is_empty();
+
+        output.write_all(&header.encrypt_chunk(cipher, index, last, &buffer[..read_bytes])?)?;
+
+        if last {
+            return Ok(());
+        }
+        index += 1;
+    }
+}
+
+fn decrypt_stream(
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+    cipher: &Aes256Gcm,
+    header: &StreamHeader,
+) -> Result<(), CryptError> {
+    let mut buffer = vec![0u8; header.encrypted_chunk_size()];
+    let mut index = 0;
+
+    loop {
+        let read_bytes = read_full(input, &mut buffer)?;
+        let last = read_bytes < buffer.len() || input.fill_buf()?.is_empty();
+
+        if read_bytes < TAG_SIZE {
+            return Err(CryptError::DecryptError(format!(
+                "Chunk {index} is truncated"
+            )));
+        }
+
+        output.write_all(&header.decrypt_chunk(cipher, index, last, &buffer[..read_bytes])?)?;
+
+        if last {
+            return Ok(());
+        }
+        index += 1;
+    }
+}
+
+/// Reads the nonce of the next legacy chunk. None at the end of the file, which is only valid
+/// between chunks
+fn read_legacy_nonce(input: &mut impl Read) -> Result<Option<[u8; NONCE_SIZE]>, CryptError> {
+    let mut nonce = [0u8; NONCE_SIZE];
+
+    match read_full(input, &mut nonce)? {
+        0 => Ok(None),
+        NONCE_SIZE => Ok(Some(nonce)),
+        read_bytes => Err(CryptError::DecryptError(format!(
+            "Chunk nonce is truncated: {read_bytes} of {NONCE_SIZE} bytes"
+        ))),
+    }
+}
+
+/// Reads the legacy format: `nonce | ciphertext length (u32 LE) | ciphertext` per chunk
+fn decrypt_legacy(
+    input: &mut impl Read,
+    output: &mut impl Write,
+    cipher: &Aes256Gcm,
+) -> Result<(), CryptError> {
+    let mut len_buf = [0u8; 4]; // Use u32 instead of u64 for length
+
+    while let Some(nonce_buf) = read_legacy_nonce(input)? {
+        input.read_exact(&mut len_buf)?;
+        let ct_len = u32::from_le_bytes(len_buf) as usize;
+
+        if ct_len > CHUNK_SIZE + TAG_SIZE {
+            return Err(CryptError::DecryptError(format!(
+                "Invalid ciphertext length: {ct_len} bytes"
+            )));
+        }
+        let mut ct_buf = vec![0u8; ct_len];
+        input.read_exact(&mut ct_buf)?;
+
+        let nonce = Nonce::from_slice(&nonce_buf);
+        let decrypted = cipher
+            .decrypt(nonce, ct_buf.as_ref())
+            .map_err(|e| CryptError::DecryptError(e.to_string()))?;
+
+        output.write_all(&decrypted)?;
+    }
+
+    Ok(())
+}
+
+fn encrypt_file_with_chunk_size(
+    input_path: &Path,
+    key_bytes: &[u8],
+    chunk_size: usize,
+) -> Result<(), CryptError> {
     let key = Key::<Aes256Gcm>::from_slice(key_bytes);
     let cipher = Aes256Gcm::new(key);
 
@@ -27,31 +305,21 @@ pub fn encrypt_file(input_path: &Path, key_bytes: &[u8]) -> Result<(), CryptErro
     let output_file = File::create(&temp_output_path)?;
     let mut output = BufWriter::with_capacity(BUFFER_SIZE, output_file);
 
-    let mut buffer = vec![0u8; CHUNK_SIZE]; // 16MiB chunk
-
-    loop {
-        let read_bytes = input.read(&mut buffer)?;
-        if read_bytes == 0 {
-            break;
-        }
-        let chunk = &buffer[..read_bytes];
-
-        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
-        let encrypted = cipher
-            .encrypt(&nonce, chunk)
-            .map_err(|e| CryptError::EncryptError(e.to_string()))?;
+    encrypt_stream(&mut input, &mut output, &cipher, chunk_size)?;
 
-        // Write nonce length, nonce, ciphertext length, ciphertext
-        output.write_all(nonce.as_slice())?;
-        output.write_all(&(encrypted.len() as u32).to_le_bytes())?;
-        output.write_all(&encrypted)?;
-    }
     output.flush()?;
     fs::rename(temp_output_path, input_path)?;
 
     Ok(())
 }
 
+/// Encrypt a file in place with AES-256-GCM, using the streaming format described on
+/// `StreamHeader`
+pub fn encrypt_file(input_path: &Path, key_bytes: &[u8]) -> Result<(), CryptError> {
+    encrypt_file_with_chunk_size(input_path, key_bytes, CHUNK_SIZE)
+}
+
+/// Decrypt a file in place. Files in the streaming format and in the legacy format are supported
 pub fn decrypt_file(input_path: &Path, key_bytes: &[u8]) -> Result<(), CryptError> {
     let key = Key::<Aes256Gcm>::from_slice(key_bytes);
     let cipher = Aes256Gcm::new(key);
@@ -62,40 +330,156 @@ pub fn decrypt_file(input_path: &Path, key_bytes: &[u8]) -> Result<(), CryptErro
     let output_file = File::create(&temp_output_path)?;
     let mut output = BufWriter::with_capacity(BUFFER_SIZE, output_file);
 
-    let mut nonce_buf = [0u8; 12]; // AES-GCM nonce is always 12 bytes
-    let mut len_buf = [0u8; 4]; // Use u32 instead of u64 for length
+    let mut header_buf = [0u8; HEADER_SIZE];
+    let header_len = read_full(&mut input, &mut header_buf)?;
 
-    loop {
-        // Read nonce
-        match input.read_exact(&mut nonce_buf) {
-            Ok(_) => {}
-            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => break,
-            Err(e) => return Err(CryptError::IoError(e)),
+    match StreamHeader::from_bytes(&header_buf[..header_len])? {
+        Some(header) => decrypt_stream(&mut input, &mut output, &cipher, &header)?,
+        None => {
+            // the bytes read while looking for a header belong to the first legacy chunk
+            let mut input = (&header_buf[..header_len]).chain(input);
+            decrypt_legacy(&mut input, &mut output, &cipher)?
         }
+    }
+
+    output.flush()?;
+    fs::rename(temp_output_path, input_path)?;
+
+    Ok(())
+}
+
+/// Copies the part of `chunk`, starting at plaintext offset `chunk_start`, that lies in `start..end`
+fn extend_with_range(range: &mut Vec<u8>, chunk: &[u8], chunk_start: u64, start: u64, end: u64) {
+    let chunk_end = chunk_start + chunk.len() as u64;
+    let from = start.clamp(chunk_start, chunk_end) - chunk_start;
+    let to = end.clamp(chunk_start, chunk_end) - chunk_start;
+
+    range.extend_from_slice(&chunk[from as usize..to as usize]);
+}
+
+fn decrypt_stream_range(
+    input: &mut File,
+    cipher: &Aes256Gcm,
+    header: &StreamHeader,
+    start: u64,
+    end: u64,
+) -> Result<Vec<u8>, CryptError> {
+    let chunk_size = header.chunk_size as u64;
+    let encrypted_chunk_size = header.encrypted_chunk_size() as u64;
+
+    let body_size = input
+        .metadata()?
+        .len()
+        .checked_sub(HEADER_SIZE as u64)
+        .filter(|size| *size > 0)
+        .ok_or_else(|| CryptError::DecryptError("File has no chunks".to_string()))?;
+    let last_index = body_size.div_ceil(encrypted_chunk_size) - 1;
+
+    let mut range = Vec::new();
+    let first_index = start / chunk_size;
+    if first_index > last_index {
+        return Ok(range);
+    }
+
+    let mut buffer = vec![0u8; header.encrypted_chunk_size()];
+    for index in first_index..=last_index.min((end - 1) / chunk_size) {
+        let offset = index * encrypted_chunk_size;
+        let size = encrypted_chunk_size.min(body_size - offset) as usize;
+
+        input.seek(SeekFrom::Start(HEADER_SIZE as u64 + offset))?;
+        input.read_exact(&mut buffer[..size])?;
+
+        if size < TAG_SIZE {
+            return Err(CryptError::DecryptError(format!(
+                "Chunk {index} is truncated"
+            )));
+        }
+
+        let chunk = header.decrypt_chunk(cipher, index, index == last_index, &buffer[..size])?;
+        extend_with_range(&mut range, &chunk, index * chunk_size, start, end);
+    }
+
+    Ok(range)
+}
+
+/// Legacy chunks hold their length, so chunks before the range are skipped without decrypting them
+fn decrypt_legacy_range(
+    input: &mut File,
+    cipher: &Aes256Gcm,
+    start: u64,
+    end: u64,
+) -> Result<Vec<u8>, CryptError> {
+    let mut range = Vec::new();
+    let mut len_buf = [0u8; 4];
+    let mut chunk_start = 0;
+
+    input.seek(SeekFrom::Start(0))?;
+    while chunk_start < end {
+        let Some(nonce_buf) = read_legacy_nonce(input)? else {
+            break;
+        };
 
         input.read_exact(&mut len_buf)?;
         let ct_len = u32::from_le_bytes(len_buf) as usize;
 
-        if ct_len > CHUNK_SIZE + 16 {
-            // +16 for GCM tag
+        if !(TAG_SIZE..=CHUNK_SIZE + TAG_SIZE).contains(&ct_len) {
             return Err(CryptError::DecryptError(format!(
                 "Invalid ciphertext length: {ct_len} bytes"
             )));
         }
-        let mut ct_buf = vec![0u8; ct_len];
-        input.read_exact(&mut ct_buf)?;
 
-        let nonce = Nonce::from_slice(&nonce_buf);
-        let decrypted = cipher
-            .decrypt(nonce, ct_buf.as_ref())
-            .map_err(|e| CryptError::DecryptError(e.to_string()))?;
+        let chunk_end = chunk_start + (ct_len - TAG_SIZE) as u64;
+        if chunk_end <= start {
+            input.seek(SeekFrom::Current(ct_len as i64))?;
+        } else {
+            let mut ct_buf = vec![0u8; ct_len];
+            input.read_exact(&mut ct_buf)?;
+
+            let chunk = cipher
+                .decrypt(Nonce::from_slice(&nonce_buf), ct_buf.as_ref())
+                .map_err(|e| CryptError::DecryptError(e.to_string()))?;
+            extend_with_range(&mut range, &chunk, chunk_start, start, end);
+        }
 
-        output.write_all(&decrypted)?;
+        chunk_start = chunk_end;
     }
-    output.flush()?;
-    fs::rename(temp_output_path, input_path)?;
 
-    Ok(())
+    Ok(range)
+}
+
+/// Decrypt part of an encrypted file without decrypting the rest of it. Only the chunks holding
+/// the range are read
+///
+/// # Arguments
+/// * `input_path` - A path to the encrypted file
+/// * `key_bytes` - A byte slice containing the key the file was encrypted with
+/// * `offset` - Offset of the range in the decrypted file
+/// * `length` - Number of bytes to decrypt. The range is shortened when it passes the end of the file
+///
+/// # Returns
+/// A Result containing the decrypted bytes or a CryptError
+pub fn decrypt_range(
+    input_path: &Path,
+    key_bytes: &[u8],
+    offset: u64,
+    length: u64,
+) -> Result<Vec<u8>, CryptError> {
+    let key = Key::<Aes256Gcm>::from_slice(key_bytes);
+    let cipher = Aes256Gcm::new(key);
+
+    if length == 0 {
+        return Ok(Vec::new());
+    }
+    let end = offset.saturating_add(length);
+
+    let mut input = File::open(input_path)?;
+    let mut header_buf = [0u8; HEADER_SIZE];
+    let header_len = read_full(&mut input, &mut header_buf)?;
+
+    match StreamHeader::from_bytes(&header_buf[..header_len])? {
+        Some(header) => decrypt_stream_range(&mut input, &cipher, &header, offset, end),
+        None => decrypt_legacy_range(&mut input, &cipher, offset, end),
+    }
 }
 
 /// Encrypt all files in a directory
@@ -155,6 +539,37 @@ pub fn decrypt_directory(input_path: &Path, key_bytes: &[u8]) -> Result<(), Cryp
     Ok(())
 }
 
+/// Compute the SHA-256 digest of a file
+///
+/// # Arguments
+/// * `input_path` - A path to the file to hash
+///
+/// # Returns
+/// A Result containing the lowercase hex digest and the size of the file in bytes
+pub fn sha256_file(input_path: &Path) -> Result<(String, u64), CryptError> {
+    let mut reader = BufReader::with_capacity(BUFFER_SIZE, File::open(input_path)?);
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0; BUFFER_SIZE];
+    let mut size = 0;
+
+    loop {
+        let bytes_read = reader.read(&mut buffer)?;
+        if bytes_read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..bytes_read]);
+        size += bytes_read as u64;
+    }
+
+    let digest = hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+
+    Ok((digest, size))
+}
+
 /// Tests
 #[cfg(test)]
 mod tests {
@@ -257,4 +672,221 @@ mod tests {
         assert_eq!(file_buffer, decrypted_file_buffer);
         assert_eq!(file_buffer2, decrypted_file_buffer2);
     }
+
+    fn test_key() -> [u8; 32] {
+        let master_key =
+            derive_master_key(b"password", &generate_salt().unwrap(), Some(2)).unwrap();
+        derive_encryption_key(&master_key, &generate_salt().unwrap(), b"info").unwrap()
+    }
+
+    fn random_bytes(size: usize) -> Vec<u8> {
+        (0..size).map(|_| rand::rng().random::<u8>()).collect()
+    }
+
+    /// Writes `content` the way files were encrypted before the streaming format
+    fn write_legacy_file(path: &Path, key_bytes: &[u8], content: &[u8], chunk_size: usize) {
+        use aes_gcm::aead::{AeadCore, OsRng};
+
+        let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key_bytes));
+        let mut output = Vec::new();
+
+        for chunk in content.chunks(chunk_size) {
+            let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+            let ciphertext = cipher.encrypt(&nonce, chunk).unwrap();
+
+            output.extend_from_slice(&nonce);
+            output.extend_from_slice(&(ciphertext.len() as u32).to_le_bytes());
+            output.extend_from_slice(&ciphertext);
+        }
+
+        fs::write(path, output).unwrap();
+    }
+
+    #[test]
+    fn test_stream_round_trip() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file_path = temp_dir.path().join("test.bin");
+        let key = test_key();
+        let chunk_size = 64;
+
+        for size in [0, 1, chunk_size, chunk_size * 2, chunk_size * 2 + 5] {
+            let content = random_bytes(size);
+            fs::write(&file_path, &content).unwrap();
+
+            encrypt_file_with_chunk_size(&file_path, &key, chunk_size).unwrap();
+            let encrypted = fs::read(&file_path).unwrap();
+            assert_eq!(&encrypted[..8], STREAM_MAGIC);
+
+            // an empty file is still written as one (empty) last chunk
+            let chunks = size.div_ceil(chunk_size).max(1);
+            assert_eq!(encrypted.len(), HEADER_SIZE + size + chunks * TAG_SIZE);
+
+            decrypt_file(&file_path, &key).unwrap();
+            assert_eq!(fs::read(&file_path).unwrap(), content);
+        }
+    }
+
+    #[test]
+    fn test_stream_detects_tampering() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file_path = temp_dir.path().join("test.bin");
+        let key = test_key();
+        let chunk_size = 64;
+        let encrypted_chunk_size = chunk_size + TAG_SIZE;
+
+        fs::write(&file_path, random_bytes(chunk_size * 3)).unwrap();
+        encrypt_file_with_chunk_size(&file_path, &key, chunk_size).unwrap();
+        let encrypted = fs::read(&file_path).unwrap();
+
+        let chunk = |index: usize| {
+            let start = HEADER_SIZE + index * encrypted_chunk_size;
+            encrypted[start..(start + encrypted_chunk_size).min(encrypted.len())].to_vec()
+        };
+        let header = encrypted[..HEADER_SIZE].to_vec();
+
+        let tampered = vec![
+            // reordered chunks
+            [header.clone(), chunk(1), chunk(0), chunk(2)].concat(),
+            // duplicated chunk
+            [header.clone(), chunk(0), chunk(0), chunk(1), chunk(2)].concat(),
+            // truncated at a chunk boundary
+            [header.clone(), chunk(0), chunk(1)].concat(),
+            // truncated inside a chunk
+            encrypted[..encrypted.len() - 4].to_vec(),
+        ];
+
+        for content in tampered {
+            fs::write(&file_path, content).unwrap();
+            assert!(decrypt_file(&file_path, &key).is_err());
+        }
+
+        // unknown versions are rejected rather than read as the legacy format
+        let mut content = encrypted.clone();
+        content[8] = STREAM_VERSION + 1;
+        fs::write(&file_path, content).unwrap();
+        assert!(matches!(
+            decrypt_file(&file_path, &key),
+            Err(CryptError::UnsupportedFormatVersion(2))
+        ));
+    }
+
+    #[test]
+    fn test_decrypt_range() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file_path = temp_dir.path().join("test.bin");
+        let legacy_path = temp_dir.path().join("legacy.bin");
+        let key = test_key();
+        let chunk_size = 64;
+
+        let content = random_bytes(chunk_size * 3 + 10);
+        fs::write(&file_path, &content).unwrap();
+        encrypt_file_with_chunk_size(&file_path, &key, chunk_size).unwrap();
+        write_legacy_file(&legacy_path, &key, &content, chunk_size);
+
+        let ranges = [
+            (0, 10),
+            (10, 54),
+            (60, 10),
+            (64, 64),
+            (100, 100),
+            (190, 100),
+            (500, 10),
+            (5, 0),
+        ];
+
+        for path in [&file_path, &legacy_path] {
+            for (offset, length) in ranges {
+                let start = (offset as usize).min(content.len());
+                let end = (offset + length).min(content.len() as u64) as usize;
+
+                assert_eq!(
+                    decrypt_range(path, &key, offset, length).unwrap(),
+                    content[start..end]
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_decrypt_legacy_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file_path = temp_dir.path().join("test.bin");
+        let key = test_key();
+
+        for size in [0, 1, 1000] {
+            let content = random_bytes(size);
+            write_legacy_file(&file_path, &key, &content, 64);
+
+            decrypt_file(&file_path, &key).unwrap();
+            assert_eq!(fs::read(&file_path).unwrap(), content);
+        }
+    }
+
+    #[test]
+    fn test_decrypt_truncated_header() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file_path = temp_dir.path().join("test.bin");
+        let key = test_key();
+
+        fs::write(&file_path, random_bytes(100)).unwrap();
+        encrypt_file_with_chunk_size(&file_path, &key, 64).unwrap();
+        let encrypted = fs::read(&file_path).unwrap();
+
+        // cut inside the magic and inside the rest of the header
+        for len in [3, 8, HEADER_SIZE - 1] {
+            fs::write(&file_path, &encrypted[..len]).unwrap();
+
+            assert!(matches!(
+                decrypt_file(&file_path, &key),
+                Err(CryptError::DecryptError(_))
+            ));
+            assert!(matches!(
+                decrypt_range(&file_path, &key, 0, 10),
+                Err(CryptError::DecryptError(_))
+            ));
+            assert_eq!(fs::read(&file_path).unwrap(), &encrypted[..len]);
+        }
+    }
+
+    #[test]
+    fn test_decrypt_legacy_truncated_nonce() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file_path = temp_dir.path().join("test.bin");
+        let key = test_key();
+        let chunk_size = 64;
+
+        let content = random_bytes(chunk_size * 2);
+        write_legacy_file(&file_path, &key, &content, chunk_size);
+        let encrypted = fs::read(&file_path).unwrap();
+
+        // cut inside the nonce of the second chunk
+        let first_chunk_len = NONCE_SIZE + 4 + chunk_size + TAG_SIZE;
+        let truncated = &encrypted[..first_chunk_len + NONCE_SIZE / 2];
+        fs::write(&file_path, truncated).unwrap();
+
+        assert!(matches!(
+            decrypt_range(&file_path, &key, 0, content.len() as u64),
+            Err(CryptError::DecryptError(_))
+        ));
+        assert!(matches!(
+            decrypt_file(&file_path, &key),
+            Err(CryptError::DecryptError(_))
+        ));
+        assert_eq!(fs::read(&file_path).unwrap(), truncated);
+    }
+
+    #[test]
+    fn test_sha256_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file_path = temp_dir.path().join("test.txt");
+        fs::write(&file_path, b"abc").unwrap();
+
+        let (digest, size) = sha256_file(&file_path).unwrap();
+
+        assert_eq!(
+            digest,
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(size, 3);
+    }
 }
@@ -77,6 +77,16 @@ pub fn generate_salt() -> Result<[u8; 16], CryptError> {
     Ok(salt)
 }
 
+/// Generate a random 32-byte artifact encryption key.
+/// Used by clients that encrypt artifacts before a registry has created a key for them (offline experiments)
+pub fn generate_key() -> Result<[u8; 32], CryptError> {
+    let mut key = [0u8; 32];
+    RandOsRng
+        .try_fill_bytes(&mut key)
+        .map_err(|_| CryptError::GenerateSaltError)?;
+    Ok(key)
+}
+
 /// Encrypt a key using AES-256-GCM
 /// This us used to encrypt the encryption key for storing in databases. This is typically run on the server-side.
 ///
@@ -140,6 +150,51 @@ pub fn decrypt_key(master_key: &[u8], encrypted_key: &[u8]) -> Result<Vec<u8>, C
     Ok(key)
 }
 
+/// Encrypt a key with a key derived from a master key, e.g. to store it in a database.
+/// Re-wrapping the result under a new master key leaves the wrapped key unchanged
+///
+/// # Arguments
+/// * `master_key` - A byte slice containing the master key
+/// * `salt` - A byte slice used as salt when deriving the wrapping key
+/// * `info` - A byte slice naming what the wrapping key is used for
+/// * `key` - A byte slice containing the key to wrap
+///
+/// # Returns
+/// A vector containing the nonce and the encrypted key
+pub fn wrap_key(
+    master_key: &[u8],
+    salt: &[u8],
+    info: &[u8],
+    key: &[u8],
+) -> Result<Vec<u8>, CryptError> {
+    let wrapping_key = derive_encryption_key(master_key, salt, info)?;
+    encrypted_key(&wrapping_key, key)
+}
+
+/// Decrypt a key encrypted with `wrap_key`
+///
+/// # Arguments
+/// * `master_key` - A byte slice containing the master key
+/// * `salt` - The salt the key was wrapped with
+/// * `info` - The info the key was wrapped with
+/// * `wrapped_key` - A byte slice containing the nonce and the encrypted key
+///
+/// # Returns
+/// A vector containing the decrypted key
+pub fn unwrap_key(
+    master_key: &[u8],
+    salt: &[u8],
+    info: &[u8],
+    wrapped_key: &[u8],
+) -> Result<Vec<u8>, CryptError> {
+    if wrapped_key.len() <= 12 {
+        return Err(CryptError::DecryptKeyError);
+    }
+
+    let wrapping_key = derive_encryption_key(master_key, salt, info)?;
+    decrypt_key(&wrapping_key, wrapped_key)
+}
+
 pub fn generate_code_verifier() -> String {
     rand::rng()
         .sample_iter(&Alphanumeric)
@@ -191,4 +246,23 @@ mod tests {
 
         assert_eq!(derived_key, decrypted_key.as_slice());
     }
+
+    #[test]
+    fn test_wrap_key() {
+        let old_master = generate_key().unwrap();
+        let new_master = generate_key().unwrap();
+        let key = generate_key().unwrap();
+
+        let wrapped = wrap_key(&old_master, b"uid", b"info", &key).unwrap();
+        assert_eq!(
+            unwrap_key(&old_master, b"uid", b"info", &wrapped).unwrap(),
+            key
+        );
+
+        // the salt, info and master key all have to match
+        assert!(unwrap_key(&new_master, b"uid", b"info", &wrapped).is_err());
+        assert!(unwrap_key(&old_master, b"other", b"info", &wrapped).is_err());
+        assert!(unwrap_key(&old_master, b"uid", b"other", &wrapped).is_err());
+        assert!(unwrap_key(&old_master, b"uid", b"info", &wrapped[..8]).is_err());
+    }
 }
@@ -1,6 +1,10 @@
 pub mod error;
 pub mod file;
 pub mod key;
+pub mod provider;
+pub mod signing;
 
 pub use file::*;
 pub use key::*;
+pub use provider::*;
+pub use signing::*;
@@ -0,0 +1,417 @@
+use crate::error::CryptError;
+use crate::key::{derive_encryption_key, unwrap_key, wrap_key};
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use async_trait::async_trait;
+use base64::prelude::*;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::path::Path;
+
+/// Magic bytes at the start of a key wrapped by `FileKeyProvider`
+const FILE_KEY_MAGIC: &[u8; 8] = b"OPSMLKEK";
+const NONCE_SIZE: usize = 12;
+
+/// What a wrapped key protects. The context is bound to the wrapped key, so a key wrapped for
+/// one resource cannot be unwrapped for another
+#[derive(Debug, Clone, Copy)]
+pub struct KeyContext<'a> {
+    /// What the key is used for, e.g. `artifact_key` or `webhook`
+    pub purpose: &'a str,
+    /// Uid of the resource the key belongs to
+    pub id: &'a str,
+}
+
+/// Holds the key-encryption key that wraps the keys the server stores, such as artifact keys and
+/// webhook signing secrets.
+///
+/// The key-encryption key never leaves the provider, so it can live in a key file, an HSM or a
+/// cloud KMS. A wrapped key must carry whatever the provider needs to unwrap it (e.g. the id of
+/// the key it was wrapped with), so providers backed by several keys can unwrap keys wrapped
+/// before the newest key was added
+#[async_trait]
+pub trait KeyProvider: Debug + Send + Sync {
+    /// Identifies the key new keys are wrapped with, e.g. a key label or KMS key id.
+    /// Never contains key material
+    fn key_id(&self) -> String;
+
+    /// Encrypts a key for storage
+    async fn wrap(&self, context: KeyContext<'_>, key: &[u8]) -> Result<Vec<u8>, CryptError>;
+
+    /// Decrypts a key encrypted with `wrap` for the same context
+    async fn unwrap(
+        &self,
+        context: KeyContext<'_>,
+        wrapped_key: &[u8],
+    ) -> Result<Vec<u8>, CryptError>;
+
+    /// Whether `wrapped_key` carries the format of keys wrapped by this provider. Stored keys
+    /// without it predate key wrapping. Providers whose wrapped keys cannot be told apart from
+    /// older keys return false
+    fn is_provider_format(&self, _wrapped_key: &[u8]) -> bool {
+        false
+    }
+}
+
+/// Wraps keys with keys derived from a secret, e.g. OPSML_ENCRYPT_SECRET.
+/// The context purpose is used as HKDF info and the context id as salt
+pub struct LocalKeyProvider {
+    secret: Vec<u8>,
+}
+
+impl LocalKeyProvider {
+    pub fn new(secret: &[u8]) -> Self {
+        LocalKeyProvider {
+            secret: secret.to_vec(),
+        }
+    }
+}
+
+impl Debug for LocalKeyProvider {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "LocalKeyProvider {{ key_id: {} }}", self.key_id())
+    }
+}
+
+#[async_trait]
+impl KeyProvider for LocalKeyProvider {
+    /// A fingerprint derived from the secret, so rotations can be told apart in the audit log
+    fn key_id(&self) -> String {
+        let fingerprint: String = derive_encryption_key(&self.secret, b"", b"key_id")
+            .map(|key| key[..8].iter().map(|b| format!("{b:02x}")).collect())
+            .unwrap_or_default();
+
+        format!("local:{fingerprint}")
+    }
+
+    async fn wrap(&self, context: KeyContext<'_>, key: &[u8]) -> Result<Vec<u8>, CryptError> {
+        wrap_key(
+            &self.secret,
+            context.id.as_bytes(),
+            context.purpose.as_bytes(),
+            key,
+        )
+    }
+
+    async fn unwrap(
+        &self,
+        context: KeyContext<'_>,
+        wrapped_key: &[u8],
+    ) -> Result<Vec<u8>, CryptError> {
+        unwrap_key(
+            &self.secret,
+            context.id.as_bytes(),
+            context.purpose.as_bytes(),
+            wrapped_key,
+        )
+    }
+}
+
+#[derive(Deserialize)]
+struct KeyFile {
+    keys: Vec<KeyFileEntry>,
+}
+
+#[derive(Deserialize)]
+struct KeyFileEntry {
+    label: String,
+    /// Base64 encoded 32-byte key
+    key: String,
+}
+
+/// Wraps keys with AES-256-GCM keys read from a key file, addressed by label like the key
+/// objects of a PKCS#11 token.
+///
+/// The key file is JSON of the form `{"keys": [{"label": "kek-1", "key": "<base64 32 bytes>"}]}`.
+/// New keys are wrapped with the key of the configured label, and the label is stored with each
+/// wrapped key, so keys wrapped with a retired key can still be unwrapped while it stays in the
+/// file. The label can be omitted when the file holds a single key.
+///
+/// Wrapped keys are `OPSMLKEK | label length (u8) | label | nonce | ciphertext`, with the header
+/// and the context authenticated as associated data.
+/// Also used as a local stand-in for KMS providers in tests
+pub struct FileKeyProvider {
+    keys: HashMap<String, [u8; 32]>,
+    label: Option<String>,
+}
+
+impl FileKeyProvider {
+    /// Creates a provider from labeled keys
+    ///
+    /// # Arguments
+    /// * `keys` - Keys by label
+    /// * `label` - Label of the key used to wrap new keys. Defaults to the only key
+    pub fn new(keys: HashMap<String, [u8; 32]>, label: Option<&str>) -> Result<Self, CryptError> {
+        let label = match label {
+            Some(label) if !keys.contains_key(label) => {
+                return Err(CryptError::UnknownKeyLabel(label.to_string()))
+            }
+            Some(label) => Some(label.to_string()),
+            None if keys.len() == 1 => keys.keys().next().cloned(),
+            None => None,
+        };
+
+        Ok(FileKeyProvider { keys, label })
+    }
+
+    /// Reads the keys of a key file
+    ///
+    /// # Arguments
+    /// * `path` - Path to the key file
+    /// * `label` - Label of the key used to wrap new keys. Defaults to the only key in the file
+    pub fn from_file(path: &Path, label: Option<&str>) -> Result<Self, CryptError> {
+        let key_file: KeyFile = serde_json::from_str(&std::fs::read_to_string(path)?)
+            .map_err(|e| CryptError::KeyProviderError(format!("invalid key file: {e}")))?;
+
+        let mut keys = HashMap::new();
+        for entry in key_file.keys {
+            if entry.label.is_empty() || entry.label.len() > u8::MAX as usize {
+                return Err(CryptError::KeyProviderError(format!(
+                    "key label must be 1 to 255 bytes: {}",
+                    entry.label
+                )));
+            }
+
+            let key: [u8; 32] = BASE64_STANDARD
+                .decode(&entry.key)
+                .ok()
+                .and_then(|key| key.try_into().ok())
+                .ok_or_else(|| {
+                    CryptError::KeyProviderError(format!(
+                        "key {} is not a base64 encoded 32-byte key",
+                        entry.label
+                    ))
+                })?;
+
+            keys.insert(entry.label, key);
+        }
+
+        Self::new(keys, label)
+    }
+
+    fn cipher(&self, label: &str) -> Result<Aes256Gcm, CryptError> {
+        let key = self
+            .keys
+            .get(label)
+            .ok_or_else(|| CryptError::UnknownKeyLabel(label.to_string()))?;
+
+        Ok(Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key)))
+    }
+}
+
+impl Debug for FileKeyProvider {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut labels: Vec<&String> = self.keys.keys().collect();
+        labels.sort();
+
+        write!(
+            f,
+            "FileKeyProvider {{ label: {:?}, keys: {:?} }}",
+            self.label, labels
+        )
+    }
+}
+
+/// Header of a key wrapped by `FileKeyProvider`
+fn file_key_header(label: &str) -> Vec<u8> {
+    [
+        FILE_KEY_MAGIC.as_slice(),
+        &[label.len() as u8],
+        label.as_bytes(),
+    ]
+    .concat()
+}
+
+fn file_key_aad(header: &[u8], context: KeyContext<'_>) -> Vec<u8> {
+    [
+        header,
+        context.purpose.as_bytes(),
+        &[0],
+        context.id.as_bytes(),
+    ]
+    .concat()
+}
+
+#[async_trait]
+impl KeyProvider for FileKeyProvider {
+    fn key_id(&self) -> String {
+        format!("file:{}", self.label.as_deref().unwrap_or_default())
+    }
+
+    fn is_provider_format(&self, wrapped_key: &[u8]) -> bool {
+        wrapped_key.starts_with(FILE_KEY_MAGIC)
+    }
+
+    async fn wrap(&self, context: KeyContext<'_>, key: &[u8]) -> Result<Vec<u8>, CryptError> {
+        let label = self.label.as_deref().ok_or_else(|| {
+            CryptError::KeyProviderError(
+                "a key label is required when the key file holds several keys".to_string(),
+            )
+        })?;
+
+        let header = file_key_header(label);
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher(label)?
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: key,
+                    aad: &file_key_aad(&header, context),
+                },
+            )
+            .map_err(|_| CryptError::EncryptKeyError)?;
+
+        Ok([header.as_slice(), nonce.as_slice(), &ciphertext].concat())
+    }
+
+    async fn unwrap(
+        &self,
+        context: KeyContext<'_>,
+        wrapped_key: &[u8],
+    ) -> Result<Vec<u8>, CryptError> {
+        let label_len = match wrapped_key.strip_prefix(FILE_KEY_MAGIC.as_slice()) {
+            Some([len, ..]) => *len as usize,
+            _ => return Err(CryptError::DecryptKeyError),
+        };
+
+        let header_len = FILE_KEY_MAGIC.len() + 1 + label_len;
+        if wrapped_key.len() <= header_len + NONCE_SIZE {
+            return Err(CryptError::DecryptKeyError);
+        }
+
+        let (header, rest) = wrapped_key.split_at(header_len);
+        let label = std::str::from_utf8(&header[FILE_KEY_MAGIC.len() + 1..])
+            .map_err(|_| CryptError::DecryptKeyError)?;
+        let (nonce, ciphertext) = rest.split_at(NONCE_SIZE);
+
+        self.cipher(label)?
+            .decrypt(
+                Nonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: &file_key_aad(header, context),
+                },
+            )
+            .map_err(|_| CryptError::DecryptKeyError)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::key::generate_key;
+
+    const CONTEXT: KeyContext = KeyContext {
+        purpose: "artifact_key",
+        id: "uid",
+    };
+
+    fn file_provider(labels: &[&str], label: Option<&str>) -> FileKeyProvider {
+        let keys = labels
+            .iter()
+            .map(|label| (label.to_string(), generate_key().unwrap()))
+            .collect();
+
+        FileKeyProvider::new(keys, label).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_local_key_provider() {
+        let secret = generate_key().unwrap();
+        let provider = LocalKeyProvider::new(&secret);
+        let key = generate_key().unwrap();
+
+        let wrapped = provider.wrap(CONTEXT, &key).await.unwrap();
+        assert_eq!(provider.unwrap(CONTEXT, &wrapped).await.unwrap(), key);
+
+        // compatible with keys wrapped with wrap_key
+        assert_eq!(
+            unwrap_key(&secret, b"uid", b"artifact_key", &wrapped).unwrap(),
+            key
+        );
+
+        let other = KeyContext {
+            purpose: "artifact_key",
+            id: "other",
+        };
+        assert!(provider.unwrap(other, &wrapped).await.is_err());
+        assert_ne!(
+            provider.key_id(),
+            LocalKeyProvider::new(&generate_key().unwrap()).key_id()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_file_key_provider() {
+        let key = generate_key().unwrap();
+        let provider = file_provider(&["kek-1"], None);
+        assert_eq!(provider.key_id(), "file:kek-1");
+
+        let wrapped = provider.wrap(CONTEXT, &key).await.unwrap();
+        assert_eq!(provider.unwrap(CONTEXT, &wrapped).await.unwrap(), key);
+
+        // the context is authenticated
+        let other = KeyContext {
+            purpose: "webhook",
+            id: "uid",
+        };
+        assert!(provider.unwrap(other, &wrapped).await.is_err());
+
+        // keys wrapped with another provider are rejected
+        let local = LocalKeyProvider::new(&generate_key().unwrap());
+        let local_wrapped = local.wrap(CONTEXT, &key).await.unwrap();
+        assert!(provider.unwrap(CONTEXT, &local_wrapped).await.is_err());
+        assert!(local.unwrap(CONTEXT, &wrapped).await.is_err());
+
+        assert!(provider.is_provider_format(&wrapped));
+        assert!(!provider.is_provider_format(&local_wrapped));
+        assert!(!local.is_provider_format(&local_wrapped));
+    }
+
+    #[tokio::test]
+    async fn test_file_key_provider_labels() {
+        let key = generate_key().unwrap();
+        let old = file_provider(&["kek-1"], None);
+        let wrapped = old.wrap(CONTEXT, &key).await.unwrap();
+
+        // a new key is added to the file and used to wrap new keys
+        let mut keys = old.keys.clone();
+        keys.insert("kek-2".to_string(), generate_key().unwrap());
+        let provider = FileKeyProvider::new(keys.clone(), Some("kek-2")).unwrap();
+
+        assert_eq!(provider.unwrap(CONTEXT, &wrapped).await.unwrap(), key);
+        let rewrapped = provider.wrap(CONTEXT, &key).await.unwrap();
+        assert!(old.unwrap(CONTEXT, &rewrapped).await.is_err());
+
+        // several keys and no label can only unwrap
+        let unlabeled = FileKeyProvider::new(keys.clone(), None).unwrap();
+        assert!(unlabeled.wrap(CONTEXT, &key).await.is_err());
+        assert_eq!(unlabeled.unwrap(CONTEXT, &rewrapped).await.unwrap(), key);
+
+        assert!(FileKeyProvider::new(keys, Some("kek-3")).is_err());
+    }
+
+    #[test]
+    fn test_file_key_provider_from_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("keys.json");
+        let key = BASE64_STANDARD.encode(generate_key().unwrap());
+
+        std::fs::write(
+            &path,
+            format!(r#"{{"keys": [{{"label": "kek-1", "key": "{key}"}}]}}"#),
+        )
+        .unwrap();
+        let provider = FileKeyProvider::from_file(&path, None).unwrap();
+        assert_eq!(provider.key_id(), "file:kek-1");
+
+        std::fs::write(
+            &path,
+            r#"{"keys": [{"label": "kek-1", "key": "c2hvcnQ="}]}"#,
+        )
+        .unwrap();
+        assert!(FileKeyProvider::from_file(&path, None).is_err());
+    }
+}
@@ -0,0 +1,111 @@
+use crate::error::CryptError;
+use ring::rand::SystemRandom;
+use ring::signature::{Ed25519KeyPair, KeyPair, UnparsedPublicKey, ED25519};
+use sha2::{Digest, Sha256};
+use std::fmt::{Debug, Formatter};
+
+/// Ed25519 key used to sign card manifests.
+///
+/// The private key is kept as a PKCS#8 document so it can be written to and read back from
+/// a signer key file. Only the public key is ever sent to the registry.
+pub struct SigningKey {
+    pkcs8: Vec<u8>,
+    pair: Ed25519KeyPair,
+}
+
+impl SigningKey {
+    /// Generate a new random signing key
+    pub fn generate() -> Result<Self, CryptError> {
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new())
+            .map_err(|e| CryptError::SigningKeyError(e.to_string()))?;
+        Self::from_pkcs8(pkcs8.as_ref())
+    }
+
+    /// Load a signing key from a PKCS#8 document
+    pub fn from_pkcs8(pkcs8: &[u8]) -> Result<Self, CryptError> {
+        let pair = Ed25519KeyPair::from_pkcs8(pkcs8)
+            .map_err(|e| CryptError::SigningKeyError(e.to_string()))?;
+        Ok(Self {
+            pkcs8: pkcs8.to_vec(),
+            pair,
+        })
+    }
+
+    pub fn to_pkcs8(&self) -> &[u8] {
+        &self.pkcs8
+    }
+
+    /// Raw 32-byte Ed25519 public key
+    pub fn public_key(&self) -> Vec<u8> {
+        self.pair.public_key().as_ref().to_vec()
+    }
+
+    pub fn key_id(&self) -> String {
+        signing_key_id(self.pair.public_key().as_ref())
+    }
+
+    pub fn sign(&self, message: &[u8]) -> Vec<u8> {
+        self.pair.sign(message).as_ref().to_vec()
+    }
+}
+
+impl Debug for SigningKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SigningKey")
+            .field("key_id", &self.key_id())
+            .finish()
+    }
+}
+
+/// Identifier of a signing key, derived from its public key so the registry and the signer
+/// always agree on it
+pub fn signing_key_id(public_key: &[u8]) -> String {
+    let digest = Sha256::digest(public_key);
+    let key_id: String = digest.iter().take(8).map(|b| format!("{b:02x}")).collect();
+    key_id
+}
+
+/// Verify an Ed25519 signature over `message`
+///
+/// # Arguments
+/// * `public_key` - Raw 32-byte Ed25519 public key
+/// * `message` - The signed message
+/// * `signature` - The signature to check
+pub fn verify_signature(
+    public_key: &[u8],
+    message: &[u8],
+    signature: &[u8],
+) -> Result<(), CryptError> {
+    UnparsedPublicKey::new(&ED25519, public_key)
+        .verify(message, signature)
+        .map_err(|_| CryptError::InvalidSignature)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_and_verify() {
+        let key = SigningKey::generate().unwrap();
+        let signature = key.sign(b"manifest");
+
+        verify_signature(&key.public_key(), b"manifest", &signature).unwrap();
+        assert!(verify_signature(&key.public_key(), b"tampered", &signature).is_err());
+
+        let other = SigningKey::generate().unwrap();
+        assert!(verify_signature(&other.public_key(), b"manifest", &signature).is_err());
+        assert_ne!(key.key_id(), other.key_id());
+    }
+
+    #[test]
+    fn test_signing_key_roundtrip() {
+        let key = SigningKey::generate().unwrap();
+        let loaded = SigningKey::from_pkcs8(key.to_pkcs8()).unwrap();
+
+        assert_eq!(key.public_key(), loaded.public_key());
+        assert_eq!(key.key_id(), loaded.key_id());
+        assert_eq!(key.key_id().len(), 16);
+        assert!(SigningKey::from_pkcs8(b"not a key").is_err());
+    }
+}
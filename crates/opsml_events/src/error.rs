@@ -5,4 +5,7 @@ use thiserror::Error;
 pub enum EventError {
     #[error("Failed to log event")]
     LogEventError(#[source] SqlError),
+
+    #[error("Failed to serialize event")]
+    SerializeError(#[source] serde_json::Error),
 }
@@ -1,6 +1,6 @@
 use futures::Stream;
 use tokio::sync::broadcast;
-use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream};
 use tokio_stream::StreamExt;
 
 use opsml_types::contracts::{AuditEvent, SpaceNameEvent};
@@ -11,8 +11,10 @@ use opsml_sql::{base::SqlClient, enums::client::SqlClientEnum};
 use tracing::{debug, instrument};
 
 use crate::types::Event;
-use std::sync::Arc;
-use tracing::error;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::{error, warn};
 
 #[instrument(skip_all)]
 pub async fn log_audit_event(
@@ -47,9 +49,34 @@ pub async fn insert_space_name_record(
     Ok(())
 }
 
+/// Number of published events kept in memory so subscribers can resume after a disconnect
+const EVENT_HISTORY_CAPACITY: usize = 1000;
+
+/// An event with its position on the bus
+#[derive(Debug, Clone)]
+pub struct SequencedEvent {
+    pub id: u64,
+    pub event: Event,
+}
+
+/// Buffered events replayed to a subscriber that resumes after its last received event
+#[derive(Debug, Default)]
+pub struct EventReplay {
+    pub events: Vec<SequencedEvent>,
+    /// Oldest event id still available, set if events published after the last received
+    /// event were already dropped from the history
+    pub gap: Option<u64>,
+}
+
 #[derive(Clone)]
 pub struct EventBus {
-    tx: broadcast::Sender<Event>,
+    tx: broadcast::Sender<SequencedEvent>,
+    history: Arc<Mutex<EventHistory>>,
+}
+
+struct EventHistory {
+    next_id: u64,
+    events: VecDeque<SequencedEvent>,
 }
 
 impl EventBus {
@@ -57,17 +84,140 @@ impl EventBus {
     pub fn new(capacity: usize) -> Self {
         debug!("Creating EventBus with capacity: {}", capacity);
         let (tx, _) = broadcast::channel(capacity);
-        Self { tx }
+
+        // ids start at the creation time so they keep increasing across server restarts
+        let next_id = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_micros() as u64)
+            .unwrap_or_default();
+
+        Self {
+            tx,
+            history: Arc::new(Mutex::new(EventHistory {
+                next_id,
+                events: VecDeque::with_capacity(EVENT_HISTORY_CAPACITY),
+            })),
+        }
     }
 
     #[instrument(skip_all)]
     pub fn publish(&self, event: Event) {
         debug!("Publishing event: {:?}", event);
-        let _ = self.tx.send(event);
+        let mut history = self.history.lock().unwrap_or_else(|e| e.into_inner());
+
+        let sequenced = SequencedEvent {
+            id: history.next_id,
+            event,
+        };
+        history.next_id += 1;
+
+        if history.events.len() == EVENT_HISTORY_CAPACITY {
+            history.events.pop_front();
+        }
+        history.events.push_back(sequenced.clone());
+
+        // send while holding the lock so history and live subscribers see the same order
+        let _ = self.tx.send(sequenced);
     }
 
     pub fn subscribe(&self) -> impl Stream<Item = Event> {
         let rx = self.tx.subscribe();
-        BroadcastStream::new(rx).filter_map(|result| result.ok())
+        BroadcastStream::new(rx).filter_map(|result| result.ok().map(|e| e.event))
+    }
+
+    /// Subscribes to the bus and returns the buffered events published after `last_event_id`.
+    ///
+    /// Events in the returned history are not repeated on the stream. If `last_event_id` is
+    /// older than the buffered history, all buffered events are returned and the replay reports
+    /// the gap. The stream ends if the subscriber falls so far behind that events are dropped,
+    /// so it can resubscribe from the last event it received instead of silently missing events.
+    pub fn subscribe_from(
+        &self,
+        last_event_id: Option<u64>,
+    ) -> (EventReplay, impl Stream<Item = SequencedEvent>) {
+        let history = self.history.lock().unwrap_or_else(|e| e.into_inner());
+        let rx = self.tx.subscribe();
+
+        let replay = match last_event_id {
+            Some(last_id) => {
+                let first_id = history
+                    .events
+                    .front()
+                    .map_or(history.next_id, |event| event.id);
+
+                EventReplay {
+                    events: history
+                        .events
+                        .iter()
+                        .filter(|e| e.id > last_id)
+                        .cloned()
+                        .collect(),
+                    gap: (first_id.saturating_sub(1) > last_id).then_some(first_id),
+                }
+            }
+            None => EventReplay::default(),
+        };
+
+        let stream = BroadcastStream::new(rx).map_while(|result| match result {
+            Ok(event) => Some(event),
+            Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+                warn!("Event subscriber lagged behind by {skipped} events, ending its stream");
+                None
+            }
+        });
+        (replay, stream)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_lagged_subscriber_stream_ends() {
+        let bus = EventBus::new(2);
+        let (_, mut live) = bus.subscribe_from(None);
+
+        for _ in 0..5 {
+            bus.publish(Event::Audit(AuditEvent::default()));
+        }
+
+        // the oldest events were dropped, so the stream ends instead of skipping them
+        assert!(live.next().await.is_none());
+
+        // resubscribing replays the history after the last received event
+        let (replay, _) = bus.subscribe_from(Some(0));
+        assert_eq!(replay.events.len(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_replay_reports_gap() {
+        let bus = EventBus::new(2);
+
+        for _ in 0..EVENT_HISTORY_CAPACITY + 2 {
+            bus.publish(Event::Audit(AuditEvent::default()));
+        }
+
+        let (replay, _) = bus.subscribe_from(None);
+        assert!(replay.events.is_empty());
+        assert!(replay.gap.is_none());
+
+        let history = bus.history.lock().unwrap();
+        let first_id = history.events.front().unwrap().id;
+        drop(history);
+
+        // the two oldest events were dropped from the history
+        let (replay, _) = bus.subscribe_from(Some(first_id - 3));
+        assert_eq!(replay.events.len(), EVENT_HISTORY_CAPACITY);
+        assert_eq!(replay.gap, Some(first_id));
+
+        // resuming right before the oldest buffered event misses nothing
+        let (replay, _) = bus.subscribe_from(Some(first_id - 1));
+        assert_eq!(replay.events.len(), EVENT_HISTORY_CAPACITY);
+        assert!(replay.gap.is_none());
+
+        let (replay, _) = bus.subscribe_from(Some(first_id + 10));
+        assert_eq!(replay.events.len(), EVENT_HISTORY_CAPACITY - 11);
+        assert!(replay.gap.is_none());
     }
 }
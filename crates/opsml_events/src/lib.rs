@@ -2,5 +2,5 @@ pub mod error;
 pub mod event;
 pub mod types;
 
-pub use event::EventBus;
+pub use event::{EventBus, EventReplay, SequencedEvent};
 pub use types::{create_audit_event, AuditContext, Event};
@@ -1,7 +1,12 @@
+use crate::error::EventError;
 use axum::http::HeaderMap;
 use headers::UserAgent;
+use opsml_sql::schemas::schema::OutboxEvent;
+use serde::{Deserialize, Serialize};
 
-use opsml_types::contracts::{AuditEvent, AuditStatus, Operation, ResourceType, SpaceNameEvent};
+use opsml_types::contracts::{
+    AuditEvent, AuditStatus, CardEvent, Operation, ResourceType, SpaceEvent, SpaceNameEvent,
+};
 use opsml_types::RegistryType;
 
 use std::net::SocketAddr;
@@ -14,6 +19,7 @@ pub struct AuditContext {
     pub operation: Operation,
     pub registry_type: Option<RegistryType>,
     pub access_location: Option<String>,
+    pub space: Option<String>,
 }
 
 pub fn create_audit_event(
@@ -45,12 +51,38 @@ pub fn create_audit_event(
         metadata: context.metadata,
         registry_type: context.registry_type,
         route,
+        space: context.space,
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "event", rename_all = "snake_case")]
 pub enum Event {
     Audit(AuditEvent),
     SpaceName(SpaceNameEvent),
+    Card(CardEvent),
+    Space(SpaceEvent),
     // Add other events as needed
 }
+
+impl Event {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Event::Audit(_) => "audit",
+            Event::SpaceName(_) => "space_name",
+            Event::Card(_) => "card",
+            Event::Space(_) => "space",
+        }
+    }
+
+    /// Encodes the event as an outbox row
+    pub fn to_outbox(&self) -> Result<OutboxEvent, EventError> {
+        let payload = serde_json::to_string(self).map_err(EventError::SerializeError)?;
+        Ok(OutboxEvent::new(self.kind(), payload))
+    }
+
+    /// Decodes an event from an outbox row
+    pub fn from_outbox(record: &OutboxEvent) -> Result<Self, EventError> {
+        serde_json::from_str(&record.payload).map_err(EventError::SerializeError)
+    }
+}
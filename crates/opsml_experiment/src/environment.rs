@@ -0,0 +1,52 @@
+use crate::error::ExperimentError;
+use opsml_cards::ExperimentCard;
+use opsml_registry::base::OpsmlRegistry;
+use opsml_storage::download_artifacts;
+use opsml_types::cards::{EnvironmentDiff, EnvironmentSnapshot};
+use opsml_types::{RegistryType, SaveName, Suffix};
+use pyo3::prelude::*;
+use std::path::PathBuf;
+use tempfile::TempDir;
+use tracing::instrument;
+
+/// Loads the environment recorded on the card of an experiment
+#[instrument(skip_all)]
+pub fn load_experiment_environment(
+    registry: &OpsmlRegistry,
+    experiment_uid: &str,
+) -> Result<EnvironmentSnapshot, ExperimentError> {
+    let key = registry.get_artifact_key(experiment_uid, &RegistryType::Experiment)?;
+
+    let card_file = PathBuf::from(SaveName::Card).with_extension(Suffix::Json);
+    let tmp_dir = TempDir::new()?;
+    let lpath = tmp_dir.path().join(&card_file);
+
+    download_artifacts(&key, &lpath, &key.storage_path().join(&card_file), false)?;
+
+    let card = ExperimentCard::model_validate_json(std::fs::read_to_string(lpath)?)?;
+
+    card.environment
+        .ok_or_else(|| ExperimentError::MissingEnvironment(experiment_uid.to_string()))
+}
+
+/// Differences between the environments of two experiments
+pub fn diff_environments(
+    left_uid: &str,
+    right_uid: &str,
+) -> Result<EnvironmentDiff, ExperimentError> {
+    let registry = OpsmlRegistry::new(RegistryType::Experiment)?;
+
+    let left = load_experiment_environment(&registry, left_uid)?;
+    let right = load_experiment_environment(&registry, right_uid)?;
+
+    Ok(EnvironmentDiff::new(left_uid, &left, right_uid, &right))
+}
+
+#[pyfunction]
+#[pyo3(signature = (left_uid, right_uid))]
+pub fn diff_experiment_environments(
+    left_uid: &str,
+    right_uid: &str,
+) -> Result<EnvironmentDiff, ExperimentError> {
+    diff_environments(left_uid, right_uid)
+}
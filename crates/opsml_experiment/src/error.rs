@@ -1,8 +1,10 @@
 use opsml_cards::error::CardError;
 use opsml_crypt::error::CryptError;
 use opsml_registry::error::RegistryError;
+use opsml_semver::error::VersionError;
 use opsml_storage::storage::error::StorageError;
 use opsml_types::error::{PyTypeError, TypeError};
+use opsml_utils::error::UtilError;
 use pyo3::exceptions::PyRuntimeError;
 use pyo3::PyErr;
 use thiserror::Error;
@@ -43,6 +45,12 @@ pub enum ExperimentError {
     #[error("Failed to insert parameter")]
     InsertParameterError(#[source] RegistryError),
 
+    #[error("Metric queue has been stopped")]
+    MetricQueueStopped,
+
+    #[error(transparent)]
+    SerdeError(#[from] serde_json::Error),
+
     #[error("Path does not exist")]
     PathNotExistError,
 
@@ -54,6 +62,36 @@ pub enum ExperimentError {
 
     #[error(transparent)]
     StripPrefixError(#[from] std::path::StripPrefixError),
+
+    #[error(transparent)]
+    VersionError(#[from] VersionError),
+
+    #[error(transparent)]
+    UtilError(#[from] UtilError),
+
+    #[error(transparent)]
+    DecodeError(#[from] base64::DecodeError),
+
+    #[error("{0} is not available for offline experiments")]
+    OfflineNotSupported(&'static str),
+
+    #[error("No offline experiments found in {0}")]
+    NoOfflineRuns(String),
+
+    #[error("Offline experiment {0} exists on the server but could not be loaded")]
+    OfflineRunNotRegistered(String),
+
+    #[error("Invalid sweep: {0}")]
+    InvalidSweep(String),
+
+    #[error("Experiment {0} has no recorded environment")]
+    MissingEnvironment(String),
+
+    #[error("No {0} runs found in {1}")]
+    NoRunsToImport(&'static str, String),
+
+    #[error("Invalid TensorBoard event file {0}: {1}")]
+    InvalidEventFile(String, String),
 }
 
 impl From<ExperimentError> for PyErr {
@@ -1,5 +1,8 @@
 use crate::error::ExperimentError;
-use crate::HardwareQueue;
+use crate::{
+    extract_search_space, run_sweep, spill_dir, HardwareQueue, MetricQueue, MetricTarget,
+    OfflineRun, SpilledBatch, SweepConfig,
+};
 use chrono::{DateTime, Utc};
 
 use opsml_cards::ExperimentCard;
@@ -7,19 +10,25 @@ use opsml_crypt::{decrypt_directory, encrypt_directory};
 use opsml_registry::base::OpsmlRegistry;
 use opsml_registry::CardRegistries;
 use opsml_semver::VersionType;
-use opsml_storage::storage_client;
+use opsml_storage::{record_artifacts, storage_client};
 use opsml_types::cards::{Metrics, Parameters};
 use opsml_types::contracts::{
-    ArtifactKey, GetMetricRequest, GetParameterRequest, MetricRequest, ParameterRequest,
+    ArtifactKey, ExperimentComparison, ExperimentComparisonRequest, GetMetricRequest,
+    GetParameterRequest, MetricQuery, MetricRequest, ParameterRequest,
 };
 use opsml_types::RegistryType;
 use opsml_types::{
-    cards::experiment::{Metric, Parameter},
+    cards::experiment::{Metric, Parameter, ParameterValue, Sweep, SweepStrategy, SweepTrial},
+    cards::EnvironmentSnapshot,
     SaveName,
 };
+use opsml_utils::get_utc_datetime;
+use pyo3::exceptions::PyKeyboardInterrupt;
+use pyo3::types::PyDict;
 use pyo3::{prelude::*, IntoPyObjectExt};
-use std::path::PathBuf;
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 use tempfile::TempDir;
 use tracing::{debug, error, instrument, warn};
 use walkdir::WalkDir;
@@ -42,6 +51,24 @@ fn get_py_filename(py: Python) -> Result<PathBuf, ExperimentError> {
     Ok(PathBuf::from(filename.to_string()))
 }
 
+/// Puts artifacts into the registry storage, or into the run directory of an offline experiment.
+/// Artifacts put into the registry storage are added to the manifest of the experiment
+fn put_artifacts(
+    offline: Option<&OfflineRun>,
+    artifact_key: &ArtifactKey,
+    lpath: &Path,
+    rpath: &Path,
+    recursive: bool,
+) -> Result<(), ExperimentError> {
+    match offline {
+        Some(run) => run.put(lpath, rpath, recursive),
+        None => {
+            storage_client()?.put(lpath, rpath, recursive)?;
+            Ok(record_artifacts(artifact_key, lpath, rpath)?)
+        }
+    }
+}
+
 /// Extract the code related to the experiment
 ///
 /// # Arguments
@@ -51,6 +78,7 @@ fn get_py_filename(py: Python) -> Result<PathBuf, ExperimentError> {
 /// * `fs` - The file system storage
 /// * `registries` - The registries
 /// * `rt` - The tokio runtime
+/// * `offline` - The run directory of an offline experiment
 ///
 /// # Returns
 /// * `None`
@@ -62,6 +90,7 @@ fn extract_code(
     py: Python<'_>,
     code_dir: Option<PathBuf>,
     artifact_key: &ArtifactKey,
+    offline: Option<&OfflineRun>,
 ) -> Result<(), ExperimentError> {
     // Attempt to get file
     let (lpath, is_directory) = match code_dir {
@@ -113,7 +142,7 @@ fn extract_code(
     encrypt_directory(temp_dir_path, &encryption_key)?;
 
     // Upload the file or directory to the storage
-    storage_client()?.put(temp_dir_path, &rpath, true)?;
+    put_artifacts(offline, artifact_key, temp_dir_path, &rpath, true)?;
 
     // Decrypt the file or directory (this is done to ensure the file is not encrypted in the code directory)
     decrypt_directory(temp_dir_path, &encryption_key)?;
@@ -127,10 +156,15 @@ fn extract_code(
 #[pyclass]
 pub struct Experiment {
     pub experiment: PyObject,
-    pub registries: CardRegistries,
+    /// Card registries. Not set for offline experiments
+    pub registries: Option<CardRegistries>,
     pub hardware_queue: Option<HardwareQueue>,
+    pub metric_queue: Option<MetricQueue>,
+    offline: Option<OfflineRun>,
     uid: String,
     artifact_key: ArtifactKey,
+    /// Last logged value of each metric, used to rank sweep trials
+    metric_values: Mutex<HashMap<String, f64>>,
 }
 
 impl Experiment {
@@ -157,32 +191,100 @@ impl Experiment {
         let mut experiment: ExperimentCard = experiment.extract(py)?;
         experiment.set_artifact_key(artifact_key.clone());
 
+        Self {
+            experiment: experiment.into_py_any(py)?,
+            registries: Some(registries),
+            hardware_queue: None,
+            metric_queue: None,
+            offline: None,
+            uid: experiment_uid,
+            artifact_key,
+            metric_values: Mutex::default(),
+        }
+        .start(py, log_hardware, code_dir)
+    }
+
+    /// Create an experiment that is recorded in `offline_dir` without contacting the server.
+    /// The experiment is registered later with `opsml experiment sync`
+    #[allow(clippy::too_many_arguments)]
+    #[instrument(skip_all)]
+    pub fn new_offline(
+        py: Python,
+        offline_dir: &Path,
+        space: Option<&str>,
+        name: Option<&str>,
+        subexperiment: bool,
+        environment: Option<EnvironmentSnapshot>,
+        log_hardware: bool,
+        code_dir: Option<PathBuf>,
+    ) -> Result<Self, ExperimentError> {
+        let mut experiment = ExperimentCard::new(py, space, name, None, None, None)?;
+        experiment.subexperiment = subexperiment;
+        experiment.environment = environment;
+
+        let (run, artifact_key) = OfflineRun::create(offline_dir, &mut experiment)?;
+
+        Self {
+            uid: experiment.uid.clone(),
+            experiment: experiment.into_py_any(py)?,
+            registries: None,
+            hardware_queue: None,
+            metric_queue: None,
+            offline: Some(run),
+            artifact_key,
+            metric_values: Mutex::default(),
+        }
+        .start(py, log_hardware, code_dir)
+    }
+
+    /// Extracts the experiment code and starts the metric and hardware queues
+    fn start(
+        mut self,
+        py: Python,
+        log_hardware: bool,
+        code_dir: Option<PathBuf>,
+    ) -> Result<Self, ExperimentError> {
         // extract code
-        match extract_code(py, code_dir, &artifact_key) {
+        match extract_code(py, code_dir, &self.artifact_key, self.offline.as_ref()) {
             Ok(_) => debug!("Code extracted successfully"),
             Err(e) => warn!("Failed to extract code: {e}"),
         };
 
         // start hardware queue if log_hardware is true
-        let hardware_queue = match log_hardware {
-            true => {
-                // clone the experiment registry
-                let registry = registries.experiment.registry.clone();
-                let arc_reg = Arc::new(registry);
-                let hardware_queue = HardwareQueue::start(arc_reg, experiment_uid.clone())?;
-                Some(hardware_queue)
-            }
+        if log_hardware {
+            let target = match &self.offline {
+                Some(run) => MetricTarget::Offline(run.hardware_file()),
+                None => self.registry_target()?,
+            };
+            self.hardware_queue = Some(HardwareQueue::start(target, self.uid.clone())?);
+        }
 
-            false => None,
+        let target = match &self.offline {
+            Some(run) => MetricTarget::Offline(run.metrics_file()),
+            None => self.registry_target()?,
         };
+        self.metric_queue = Some(MetricQueue::start(target, self.uid.clone(), &spill_dir())?);
 
-        Ok(Self {
-            experiment: experiment.into_py_any(py)?,
-            registries,
-            hardware_queue,
-            uid: experiment_uid,
-            artifact_key,
-        })
+        Ok(self)
+    }
+
+    /// Card registries of the experiment. Offline experiments have no registries
+    fn registries(&mut self) -> Result<&mut CardRegistries, ExperimentError> {
+        self.registries
+            .as_mut()
+            .ok_or(ExperimentError::OfflineNotSupported("Registry access"))
+    }
+
+    /// The experiment registry. Offline experiments have no registries
+    fn registry(&self) -> Result<&OpsmlRegistry, ExperimentError> {
+        self.registries
+            .as_ref()
+            .map(|registries| &registries.experiment.registry)
+            .ok_or(ExperimentError::OfflineNotSupported("Registry access"))
+    }
+
+    fn registry_target(&self) -> Result<MetricTarget, ExperimentError> {
+        Ok(MetricTarget::Registry(Arc::new(self.registry()?.clone())))
     }
 
     /// Create an experiment
@@ -194,8 +296,7 @@ impl Experiment {
     /// * `code_dir` - The directory containing the code
     /// * `registries` - The registries
     /// * `subexperiment` - Whether the experiment is a subexperiment
-    /// * `fs` - The file system storage
-    /// * `rt` - The tokio runtime
+    /// * `environment` - The environment captured when the experiment started
     ///
     /// # Returns
     /// * `Bound<PyAny>` - The experiment card
@@ -210,9 +311,10 @@ impl Experiment {
         name: Option<&str>,
         registries: &mut CardRegistries,
         subexperiment: bool,
+        environment: Option<EnvironmentSnapshot>,
     ) -> Result<(Bound<'py, PyAny>, String), ExperimentError> {
         debug!("Initializing experiment");
-        let experiment = Self::initialize_experiment(py, space, name, subexperiment)?;
+        let experiment = Self::initialize_experiment(py, space, name, subexperiment, environment)?;
 
         debug!("Registering experiment");
         let uid = Self::register_experiment(&experiment, registries)?;
@@ -228,6 +330,7 @@ impl Experiment {
     /// * `space` - The space URL
     /// * `name` - The name of the experiment
     /// * `subexperiment` - Whether the experiment is a subexperiment
+    /// * `environment` - The environment captured when the experiment started
     ///
     /// # Returns
     ///
@@ -241,9 +344,11 @@ impl Experiment {
         space: Option<&str>,
         name: Option<&str>,
         subexperiment: bool,
+        environment: Option<EnvironmentSnapshot>,
     ) -> Result<Bound<'py, PyAny>, ExperimentError> {
         let mut card = ExperimentCard::new(py, space, name, None, None, None)?;
         card.subexperiment = subexperiment;
+        card.environment = environment;
 
         let experiment = Py::new(py, card)?.into_bound_py_any(py)?;
 
@@ -294,17 +399,68 @@ impl Experiment {
     /// # Errors
     ///
     /// * `OpsmlError` - Error adding the subexperiment
-    fn add_subexperiment_experiment<'py>(
-        slf: &PyRefMut<'py, Self>,
-        py: Python<'py>,
+    fn add_subexperiment_experiment(
+        &self,
+        py: Python<'_>,
         subexperiment: &Experiment,
     ) -> Result<(), ExperimentError> {
         let subexperiment_uid = subexperiment.uid.clone();
-        slf.experiment
+        self.experiment
             .call_method1(py, "add_subexperiment_experiment", (&subexperiment_uid,))?;
         Ok(())
     }
 
+    /// Create a child experiment and add it to the subexperiments of this experiment.
+    /// Children of an offline experiment are recorded next to it. Children run in the same
+    /// process, so they share the environment of this experiment
+    fn create_child(
+        &mut self,
+        py: Python<'_>,
+        space: Option<&str>,
+        name: Option<&str>,
+        code_dir: Option<PathBuf>,
+        log_hardware: bool,
+    ) -> Result<Experiment, ExperimentError> {
+        let environment = self
+            .experiment
+            .getattr(py, "environment")?
+            .extract::<Option<EnvironmentSnapshot>>(py)?;
+
+        let experiment = match &self.offline {
+            Some(run) => {
+                let offline_dir = run.offline_dir().to_path_buf();
+                Experiment::new_offline(
+                    py,
+                    &offline_dir,
+                    space,
+                    name,
+                    true,
+                    environment,
+                    log_hardware,
+                    code_dir,
+                )?
+            }
+            None => {
+                let mut registries = self.registries()?.clone();
+                let (card, uid) = Experiment::create_experiment(
+                    py,
+                    space,
+                    name,
+                    &mut registries,
+                    true,
+                    environment,
+                )?;
+
+                Experiment::new(py, card.unbind(), registries, log_hardware, code_dir, uid)?
+            }
+        };
+
+        debug!("Adding subexperiment to experiment");
+        self.add_subexperiment_experiment(py, &experiment)?;
+
+        Ok(experiment)
+    }
+
     /// Load an existing experiment
     ///
     /// # Arguments
@@ -340,6 +496,154 @@ impl Experiment {
         }
         Ok(())
     }
+
+    /// Sends the buffered metrics and parameters and stops buffering. Metrics logged afterwards
+    /// are sent immediately
+    fn stop_metric_queue(&mut self, py: Python<'_>) {
+        if let Some(mut queue) = self.metric_queue.take() {
+            py.allow_threads(move || queue.stop());
+        }
+    }
+
+    /// Runs a single sweep trial as a child experiment. The objective receives the trial
+    /// experiment and its parameters. A returned value is logged as the sweep metric, otherwise
+    /// the last value the trial logged for the metric is used
+    #[allow(clippy::too_many_arguments)]
+    fn run_trial(
+        slf: &Bound<'_, Self>,
+        objective: &Bound<'_, PyAny>,
+        space: &str,
+        name: &str,
+        config: &SweepConfig,
+        budget_parameter: &str,
+        parameters: &[Parameter],
+        budget: Option<i64>,
+    ) -> Result<SweepTrial, ExperimentError> {
+        let py = slf.py();
+
+        let mut trial_parameters = parameters.to_vec();
+        if let Some(budget) = budget {
+            trial_parameters.push(Parameter {
+                name: budget_parameter.to_string(),
+                value: ParameterValue::Int(budget),
+            });
+        }
+
+        let trial = slf
+            .borrow_mut()
+            .create_child(py, Some(space), Some(name), None, false)?;
+        let trial = Bound::new(py, trial)?;
+        let experiment_uid = trial.borrow().uid.clone();
+
+        let kwargs = PyDict::new(py);
+        for parameter in trial_parameters.iter() {
+            kwargs.set_item(&parameter.name, parameter.value.to_py(py)?)?;
+        }
+        trial.borrow().log_parameters(trial_parameters)?;
+
+        // a return value that is not a number fails the trial, like an exception in the objective
+        let result = objective
+            .call1((&trial, kwargs))
+            .and_then(|value| match value.is_none() {
+                true => Ok(None),
+                false => value.extract::<f64>().map(Some),
+            });
+
+        let value = match result {
+            Ok(value) => {
+                if let Some(value) = value {
+                    trial
+                        .borrow()
+                        .log_metric(config.metric.clone(), value, None, None, None)?;
+                }
+
+                // exiting updates the trial card, the same as leaving a `with` block
+                trial.call_method1("__exit__", (py.None(), py.None(), py.None()))?;
+
+                trial
+                    .borrow()
+                    .metric_values
+                    .lock()
+                    .ok()
+                    .and_then(|values| values.get(&config.metric).copied())
+            }
+            Err(e) => {
+                if e.is_instance_of::<PyKeyboardInterrupt>(py) {
+                    return Err(e.into());
+                }
+
+                error!("Sweep trial {experiment_uid} failed: {e}");
+                let mut trial = trial.borrow_mut();
+                trial.stop_metric_queue(py);
+                trial.stop_queue()?;
+                None
+            }
+        };
+
+        debug!("Sweep trial {experiment_uid} finished with {value:?}");
+
+        Ok(SweepTrial {
+            experiment_uid,
+            parameters: parameters.to_vec(),
+            value,
+            budget,
+        })
+    }
+
+    fn insert_metrics(&self, mut metrics: Vec<Metric>) -> Result<(), ExperimentError> {
+        // offline metrics reach the server much later, so the logging time is recorded here
+        if self.offline.is_some() {
+            for metric in metrics.iter_mut() {
+                metric.created_at.get_or_insert_with(get_utc_datetime);
+            }
+        }
+
+        if let Ok(mut values) = self.metric_values.lock() {
+            for metric in metrics.iter() {
+                values.insert(metric.name.clone(), metric.value);
+            }
+        }
+
+        if let Some(queue) = &self.metric_queue {
+            return queue.log_metrics(metrics);
+        }
+
+        let metric_request = MetricRequest {
+            experiment_uid: self.uid.clone(),
+            metrics,
+        };
+
+        if let Some(run) = &self.offline {
+            return run
+                .metrics_file()
+                .append(&[SpilledBatch::Metrics(metric_request)]);
+        }
+
+        self.registry()?
+            .insert_metrics(&metric_request)
+            .map_err(ExperimentError::InsertMetricError)
+    }
+
+    fn insert_parameters(&self, parameters: Vec<Parameter>) -> Result<(), ExperimentError> {
+        if let Some(queue) = &self.metric_queue {
+            return queue.log_parameters(parameters);
+        }
+
+        let param_request = ParameterRequest {
+            experiment_uid: self.uid.clone(),
+            parameters,
+        };
+
+        if let Some(run) = &self.offline {
+            return run
+                .metrics_file()
+                .append(&[SpilledBatch::Parameters(param_request)]);
+        }
+
+        self.registry()?
+            .insert_parameters(&param_request)
+            .map_err(ExperimentError::InsertParameterError)
+    }
 }
 
 #[pymethods]
@@ -373,37 +677,34 @@ impl Experiment {
         experiment_uid: Option<&str>,
     ) -> Result<Bound<'py, Experiment>, ExperimentError> {
         debug!("Starting experiment");
-        let registries = &mut slf.registries;
+
         let experiment = match experiment_uid {
             Some(uid) => {
-                let card = Experiment::load_experiment(py, uid, registries)?;
-                Experiment::new(
+                if slf.offline.is_some() {
+                    return Err(ExperimentError::OfflineNotSupported(
+                        "Loading an existing experiment",
+                    ));
+                }
+
+                let mut registries = slf.registries()?.clone();
+                let card = Experiment::load_experiment(py, uid, &mut registries)?;
+                let experiment = Experiment::new(
                     py,
                     card.unbind(),
-                    slf.registries.clone(),
+                    registries,
                     false,
                     code_dir, // we can always revisit, but it doesn't make sense to log hardware for a completed experiment
                     uid.to_string(),
-                )?
-            }
-            None => {
-                let (card, uid) = Experiment::create_experiment(py, space, name, registries, true)?;
+                )?;
 
-                Experiment::new(
-                    py,
-                    card.unbind(),
-                    slf.registries.clone(),
-                    log_hardware,
-                    code_dir,
-                    uid,
-                )?
+                // Add the new experiment's UID to the parent experiment's experimentcard_uids
+                debug!("Adding subexperiment to experiment");
+                slf.add_subexperiment_experiment(py, &experiment)?;
+                experiment
             }
+            None => slf.create_child(py, space, name, code_dir, log_hardware)?,
         };
 
-        // Add the new experiment's UID to the parent experiment's experimentcard_uids
-        debug!("Adding subexperiment to experiment");
-        Self::add_subexperiment_experiment(&slf, py, &experiment)?;
-
         debug!("Starting experiment");
 
         Ok(Py::new(py, experiment)?.bind(py).clone())
@@ -422,6 +723,9 @@ impl Experiment {
         exc_value: Option<PyObject>,
         traceback: Option<PyObject>,
     ) -> Result<bool, ExperimentError> {
+        debug!("Flushing metric queue");
+        slf.stop_metric_queue(py);
+
         if let (Some(exc_type), Some(exc_value), Some(traceback)) = (exc_type, exc_value, traceback)
         {
             error!(
@@ -438,7 +742,13 @@ impl Experiment {
             let exp = experiment.bind(py);
 
             // Update experiment card using the cloned reference
-            slf.registries.experiment.update_card(exp)?;
+            match &slf.offline {
+                Some(run) => {
+                    let mut card = exp.extract::<PyRefMut<ExperimentCard>>()?;
+                    run.save_card(&mut card)?
+                }
+                None => slf.registries()?.experiment.update_card(exp)?,
+            }
 
             debug!("Stopping hardware queue");
             slf.stop_queue()?;
@@ -448,6 +758,7 @@ impl Experiment {
         Ok(false) // Return false to propagate exceptions
     }
 
+    /// Log a metric. Metrics are buffered and sent in batches from a background thread
     #[pyo3(signature = (name, value, step = None, timestamp = None, created_at = None))]
     pub fn log_metric(
         &self,
@@ -457,39 +768,17 @@ impl Experiment {
         timestamp: Option<i64>,
         created_at: Option<DateTime<Utc>>,
     ) -> Result<(), ExperimentError> {
-        let registry = &self.registries.experiment.registry;
-
-        let metric_request = MetricRequest {
-            experiment_uid: self.uid.clone(),
-            metrics: vec![Metric {
-                name,
-                value,
-                step,
-                timestamp,
-                created_at,
-            }],
-        };
-
-        registry
-            .insert_metrics(&metric_request)
-            .map_err(ExperimentError::InsertMetricError)?;
-
-        Ok(())
+        self.insert_metrics(vec![Metric {
+            name,
+            value,
+            step,
+            timestamp,
+            created_at,
+        }])
     }
 
     pub fn log_metrics(&self, metrics: Vec<Metric>) -> Result<(), ExperimentError> {
-        let registry = &self.registries.experiment.registry;
-
-        let metric_request = MetricRequest {
-            experiment_uid: self.uid.clone(),
-            metrics,
-        };
-
-        registry
-            .insert_metrics(&metric_request)
-            .map_err(ExperimentError::InsertMetricError)?;
-
-        Ok(())
+        self.insert_metrics(metrics)
     }
 
     #[pyo3(signature = (name, value))]
@@ -498,33 +787,20 @@ impl Experiment {
         name: String,
         value: Bound<'_, PyAny>,
     ) -> Result<(), ExperimentError> {
-        let registry = &self.registries.experiment.registry;
-
-        let param_request = ParameterRequest {
-            experiment_uid: self.uid.clone(),
-            parameters: vec![Parameter::new(name, value)?],
-        };
-
-        registry
-            .insert_parameters(&param_request)
-            .map_err(ExperimentError::InsertParameterError)?;
-
-        Ok(())
+        self.insert_parameters(vec![Parameter::new(name, value)?])
     }
 
     pub fn log_parameters(&self, parameters: Vec<Parameter>) -> Result<(), ExperimentError> {
-        let registry = &self.registries.experiment.registry;
-
-        let param_request = ParameterRequest {
-            experiment_uid: self.uid.clone(),
-            parameters,
-        };
-
-        registry
-            .insert_parameters(&param_request)
-            .map_err(ExperimentError::InsertParameterError)?;
+        self.insert_parameters(parameters)
+    }
 
-        Ok(())
+    /// Send the buffered metrics and parameters to the server. Batches the server does not
+    /// accept are spilled to disk and resent on the next flush
+    pub fn flush(&self, py: Python<'_>) -> Result<(), ExperimentError> {
+        match &self.metric_queue {
+            Some(queue) => py.allow_threads(|| queue.flush()),
+            None => Ok(()),
+        }
     }
 
     pub fn log_artifact(&self, path: PathBuf) -> Result<(), ExperimentError> {
@@ -557,7 +833,13 @@ impl Experiment {
         let encryption_key = self.artifact_key.get_decrypt_key()?;
         encrypt_directory(&path, &encryption_key)?;
 
-        storage_client()?.put(&path, &rpath, false)?;
+        put_artifacts(
+            self.offline.as_ref(),
+            &self.artifact_key,
+            &path,
+            &rpath,
+            false,
+        )?;
 
         decrypt_directory(&path, &encryption_key)?;
 
@@ -570,13 +852,83 @@ impl Experiment {
 
         let rpath = self.artifact_key.storage_path().join(SaveName::Artifacts);
 
-        storage_client()?.put(&path, &rpath, true)?;
+        put_artifacts(
+            self.offline.as_ref(),
+            &self.artifact_key,
+            &path,
+            &rpath,
+            true,
+        )?;
 
         decrypt_directory(&path, &encryption_key)?;
 
         Ok(())
     }
 
+    /// Run a hyperparameter sweep. Every trial runs `objective` in a child experiment and the
+    /// best trial is picked by `metric`. The sweep is recorded on the card of this experiment,
+    /// replacing an earlier sweep
+    #[pyo3(signature = (objective, search_space, metric, strategy = SweepStrategy::Grid, maximize = false, num_trials = None, seed = None, min_budget = 1, max_budget = None, reduction_factor = 3, budget_parameter = "budget", name = None))]
+    #[allow(clippy::too_many_arguments)]
+    #[instrument(skip_all)]
+    pub fn sweep(
+        slf: &Bound<'_, Self>,
+        objective: &Bound<'_, PyAny>,
+        search_space: &Bound<'_, PyDict>,
+        metric: &str,
+        strategy: SweepStrategy,
+        maximize: bool,
+        num_trials: Option<usize>,
+        seed: Option<u64>,
+        min_budget: i64,
+        max_budget: Option<i64>,
+        reduction_factor: usize,
+        budget_parameter: &str,
+        name: Option<&str>,
+    ) -> Result<Sweep, ExperimentError> {
+        let py = slf.py();
+
+        let search_space = extract_search_space(search_space)?;
+        let config = SweepConfig {
+            strategy,
+            metric: metric.to_string(),
+            maximize,
+            num_trials,
+            seed,
+            min_budget,
+            max_budget,
+            reduction_factor,
+        };
+
+        let experiment = slf.borrow().experiment.clone_ref(py);
+        let experiment = experiment.bind(py);
+        let space = experiment.getattr("space")?.extract::<String>()?;
+        let name = match name {
+            Some(name) => name.to_string(),
+            None => format!("{}-trial", experiment.getattr("name")?.extract::<String>()?),
+        };
+
+        let sweep = run_sweep(search_space, &config, |parameters, budget| {
+            Self::run_trial(
+                slf,
+                objective,
+                &space,
+                &name,
+                &config,
+                budget_parameter,
+                parameters,
+                budget,
+            )
+        })?;
+
+        // saved with the card when the experiment exits
+        experiment
+            .extract::<PyRefMut<ExperimentCard>>()?
+            .set_sweep(sweep.clone());
+
+        Ok(sweep)
+    }
+
     #[getter]
     pub fn card<'py>(&self, py: Python<'py>) -> Result<Bound<'py, PyAny>, ExperimentError> {
         Ok(self.experiment.bind(py).clone())
@@ -592,6 +944,11 @@ impl Experiment {
         save_kwargs: Option<&Bound<'_, PyAny>>,
     ) -> Result<(), ExperimentError> {
         let py = card.py();
+
+        if self.offline.is_some() {
+            return Err(ExperimentError::OfflineNotSupported("register_card"));
+        }
+
         // get registry type of card
         let registry_type = card.getattr("registry_type")?.extract::<RegistryType>()?;
 
@@ -603,7 +960,7 @@ impl Experiment {
 
         match registry_type {
             RegistryType::Data => {
-                self.registries.data.register_card(
+                self.registries()?.data.register_card(
                     card,
                     version_type,
                     pre_tag,
@@ -618,7 +975,7 @@ impl Experiment {
                     .call_method1("add_datacard_uid", (datacard_uid,))?;
             }
             RegistryType::Model => {
-                self.registries.model.register_card(
+                self.registries()?.model.register_card(
                     card,
                     version_type,
                     pre_tag,
@@ -634,7 +991,7 @@ impl Experiment {
             }
 
             RegistryType::Prompt => {
-                self.registries.prompt.register_card(
+                self.registries()?.prompt.register_card(
                     card,
                     version_type,
                     pre_tag,
@@ -650,7 +1007,7 @@ impl Experiment {
             }
 
             RegistryType::Service => {
-                self.registries.service.register_card(
+                self.registries()?.service.register_card(
                     card,
                     version_type,
                     pre_tag,
@@ -685,6 +1042,10 @@ impl Experiment {
 /// * `code_dir` - The directory containing the code
 /// * `log_hardware` - Whether to log hardware metrics. Will log hardware metrics every 30 seconds
 /// * `experiment_uid` - The experiment UID
+/// * `offline_dir` - Record the experiment in this directory instead of registering it. Offline
+///   experiments are registered later with `opsml experiment sync <offline_dir>`
+/// * `env_vars` - Environment variables recorded with the environment of the experiment. Names
+///   ending in `*` match every variable starting with the name
 ///
 /// # Returns
 /// * `Bound<Experiment>` - The experiment
@@ -692,8 +1053,9 @@ impl Experiment {
 /// # Errors
 /// * `ExperimentError` - Error starting the experiment
 #[pyfunction]
-#[pyo3(signature = (space=None, name=None, code_dir=None, log_hardware=false, experiment_uid=None))]
+#[pyo3(signature = (space=None, name=None, code_dir=None, log_hardware=false, experiment_uid=None, offline_dir=None, env_vars=None))]
 #[instrument(skip_all)]
+#[allow(clippy::too_many_arguments)]
 pub fn start_experiment<'py>(
     py: Python<'py>,
     space: Option<&str>,
@@ -701,9 +1063,41 @@ pub fn start_experiment<'py>(
     code_dir: Option<PathBuf>,
     log_hardware: bool,
     experiment_uid: Option<&str>,
+    offline_dir: Option<PathBuf>,
+    env_vars: Option<Vec<String>>,
 ) -> Result<Bound<'py, Experiment>, ExperimentError> {
     debug!("Initializing experiment");
 
+    // loaded experiments keep the environment they were created with
+    let environment = match experiment_uid {
+        Some(_) => None,
+        None => Some(EnvironmentSnapshot::capture(
+            py,
+            &env_vars.unwrap_or_default(),
+        )),
+    };
+
+    if let Some(offline_dir) = offline_dir {
+        if experiment_uid.is_some() {
+            return Err(ExperimentError::OfflineNotSupported(
+                "Loading an existing experiment",
+            ));
+        }
+
+        let experiment = Experiment::new_offline(
+            py,
+            &offline_dir,
+            space,
+            name,
+            false,
+            environment,
+            log_hardware,
+            code_dir,
+        )?;
+
+        return Ok(Py::new(py, experiment)?.bind(py).clone());
+    }
+
     // runtime should be shared across all registries and all child experiments to prevent deadlocks
 
     let mut registries = CardRegistries::new()?;
@@ -722,8 +1116,14 @@ pub fn start_experiment<'py>(
             )?
         }
         None => {
-            let (experiment, uid) =
-                Experiment::create_experiment(py, space, name, &mut registries, false)?;
+            let (experiment, uid) = Experiment::create_experiment(
+                py,
+                space,
+                name,
+                &mut registries,
+                false,
+                environment,
+            )?;
 
             Experiment::new(
                 py,
@@ -740,18 +1140,56 @@ pub fn start_experiment<'py>(
 }
 
 #[pyfunction]
-#[pyo3(signature = (experiment_uid, names = None))]
+#[pyo3(signature = (experiment_uid, names = None, start_step = None, end_step = None, start_timestamp = None, end_timestamp = None, max_points = None))]
+#[allow(clippy::too_many_arguments)]
 pub fn get_experiment_metrics(
     experiment_uid: &str,
     names: Option<Vec<String>>,
+    start_step: Option<i32>,
+    end_step: Option<i32>,
+    start_timestamp: Option<i64>,
+    end_timestamp: Option<i64>,
+    max_points: Option<i64>,
 ) -> Result<Metrics, ExperimentError> {
-    let metric_request = GetMetricRequest {
+    let registry = OpsmlRegistry::new(RegistryType::Experiment)?;
+
+    let mut query = MetricQuery {
         experiment_uid: experiment_uid.to_string(),
         names: names.unwrap_or_default(),
+        start_step,
+        end_step,
+        start_timestamp,
+        end_timestamp,
+        max_points,
+        ..Default::default()
     };
 
-    let registry = OpsmlRegistry::new(RegistryType::Experiment)?;
-    let metrics = registry.get_metrics(&metric_request)?;
+    // plain requests keep working against servers without metric queries
+    if start_step.is_none()
+        && end_step.is_none()
+        && start_timestamp.is_none()
+        && end_timestamp.is_none()
+        && max_points.is_none()
+    {
+        let metric_request = GetMetricRequest {
+            experiment_uid: query.experiment_uid,
+            names: query.names,
+        };
+        let metrics = registry.get_metrics(&metric_request)?;
+
+        return Ok(Metrics { metrics });
+    }
+
+    let mut metrics = Vec::new();
+    loop {
+        let page = registry.query_metrics(&query)?;
+        metrics.extend(page.metrics);
+
+        match page.next_cursor {
+            Some(cursor) => query.cursor = Some(cursor),
+            None => break,
+        }
+    }
 
     Ok(Metrics { metrics })
 }
@@ -773,3 +1211,23 @@ pub fn get_experiment_parameters(
 
     Ok(Parameters { parameters })
 }
+
+#[pyfunction]
+#[pyo3(signature = (experiment_uids, metrics = None, parameters = None, maximize = None))]
+pub fn compare_experiments(
+    experiment_uids: Vec<String>,
+    metrics: Option<Vec<String>>,
+    parameters: Option<Vec<String>>,
+    maximize: Option<Vec<String>>,
+) -> Result<ExperimentComparison, ExperimentError> {
+    let comparison_request = ExperimentComparisonRequest {
+        experiment_uids,
+        metric_names: metrics.unwrap_or_default(),
+        parameter_names: parameters.unwrap_or_default(),
+        maximize: maximize.unwrap_or_default(),
+    };
+
+    let registry = OpsmlRegistry::new(RegistryType::Experiment)?;
+
+    Ok(registry.compare_experiments(&comparison_request)?)
+}
@@ -1,16 +1,15 @@
 use crate::error::ExperimentError;
+use crate::metric_queue::{MetricTarget, SpilledBatch};
 use chrono::Utc;
-use opsml_registry::base::OpsmlRegistry;
 use opsml_state::app_state;
 use opsml_types::{cards::HardwareMetricLogger, contracts::HardwareMetricRequest};
-use std::sync::Arc;
 use tokio::sync::watch;
 use tokio::time::{self, Duration};
 use tracing::Instrument;
 use tracing::{debug, error, info_span};
 
 async fn insert_metrics(
-    registry: Arc<OpsmlRegistry>,
+    target: &MetricTarget,
     hw_logger: &mut HardwareMetricLogger,
     experiment_uid: &str,
 ) -> Result<(), ExperimentError> {
@@ -21,17 +20,20 @@ async fn insert_metrics(
         experiment_uid: experiment_uid.to_string(),
         metrics: metrics.clone(),
     };
-    registry.insert_hardware_metrics(request).await?;
+
+    match target {
+        MetricTarget::Registry(registry) => registry.insert_hardware_metrics(request).await?,
+        MetricTarget::Offline(file) => file.append(&[SpilledBatch::HardwareMetrics(request)])?,
+    }
 
     Ok(())
 }
 
 fn start_background_task(
-    registry: Arc<OpsmlRegistry>,
+    target: MetricTarget,
     mut stop_rx: watch::Receiver<()>,
     experiment_uid: String,
 ) -> Result<(), ExperimentError> {
-    let registry = registry.clone();
     let state = app_state();
     let mut last_inserted = Utc::now().naive_utc();
     let mut hw_logger = HardwareMetricLogger::new();
@@ -48,7 +50,7 @@ fn start_background_task(
                     let elapsed = now - last_inserted;
 
                     if elapsed.num_seconds() >= 30 {
-                        let inserted = insert_metrics(registry.clone(), &mut hw_logger, &experiment_uid).await;
+                        let inserted = insert_metrics(&target, &mut hw_logger, &experiment_uid).await;
 
                         if let Err(e) = inserted {
                             error!("Error inserting metrics: {:?}", e);
@@ -77,12 +79,9 @@ pub struct HardwareQueue {
 }
 
 impl HardwareQueue {
-    pub fn start(
-        registry: Arc<OpsmlRegistry>,
-        experiment_uid: String,
-    ) -> Result<Self, ExperimentError> {
+    pub fn start(target: MetricTarget, experiment_uid: String) -> Result<Self, ExperimentError> {
         let (stop_tx, stop_rx) = watch::channel(());
-        start_background_task(registry, stop_rx, experiment_uid)?;
+        start_background_task(target, stop_rx, experiment_uid)?;
 
         Ok(Self { stop_tx })
     }
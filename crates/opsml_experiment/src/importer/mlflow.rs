@@ -0,0 +1,374 @@
+use super::{
+    parse_parameter_value, register_imported_runs, to_card_name, ImportedExperiment, ImportedRun,
+};
+use crate::error::ExperimentError;
+use chrono::{DateTime, Utc};
+use opsml_types::cards::experiment::{Metric, Parameter};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tracing::{debug, instrument, warn};
+use walkdir::WalkDir;
+
+/// Metadata file of MLflow experiments and runs
+const MLFLOW_META_FILE: &str = "meta.yaml";
+
+/// Tags MLflow sets on every run. They are not imported, except for the run name
+const MLFLOW_SYSTEM_TAG_PREFIX: &str = "mlflow.";
+const MLFLOW_RUN_NAME_TAG: &str = "mlflow.runName";
+
+/// Reads a flat `meta.yaml` file. MLflow only writes `key: value` lines to these files
+fn read_meta(path: &Path) -> Result<HashMap<String, String>, ExperimentError> {
+    let content = std::fs::read_to_string(path)?;
+
+    Ok(content
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .filter(|(key, _)| !key.starts_with(' '))
+        .map(|(key, value)| {
+            let value = value.trim();
+            let value = match value.len() >= 2
+                && ((value.starts_with('\'') && value.ends_with('\''))
+                    || (value.starts_with('"') && value.ends_with('"')))
+            {
+                true => value[1..value.len() - 1].replace("''", "'"),
+                false => value.to_string(),
+            };
+            (key.trim().to_string(), value)
+        })
+        .collect())
+}
+
+fn is_deleted(meta: &HashMap<String, String>) -> bool {
+    meta.get("lifecycle_stage")
+        .is_some_and(|stage| stage == "deleted")
+}
+
+fn millis_to_datetime(millis: i64) -> Option<DateTime<Utc>> {
+    DateTime::from_timestamp_millis(millis)
+}
+
+/// Reads every file below `dir` as a key and value. Keys containing `/` are stored in nested
+/// directories
+fn read_key_files(dir: &Path) -> Result<Vec<(String, String)>, ExperimentError> {
+    let mut entries = Vec::new();
+    if !dir.is_dir() {
+        return Ok(entries);
+    }
+
+    for entry in WalkDir::new(dir).sort_by_file_name() {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+
+        let key = entry
+            .path()
+            .strip_prefix(dir)?
+            .to_string_lossy()
+            .replace('\\', "/");
+        entries.push((key, std::fs::read_to_string(entry.path())?));
+    }
+
+    Ok(entries)
+}
+
+/// Metric files hold one `<timestamp> <value> <step>` line per logged value. Files written by
+/// old MLflow versions have no step
+fn read_metrics(dir: &Path) -> Result<Vec<Metric>, ExperimentError> {
+    let mut metrics = Vec::new();
+
+    for (name, content) in read_key_files(dir)? {
+        for line in content.lines().filter(|line| !line.trim().is_empty()) {
+            let mut fields = line.split_whitespace();
+            let timestamp = fields.next().and_then(|value| value.parse::<i64>().ok());
+            let value = fields.next().and_then(|value| value.parse::<f64>().ok());
+            let step = fields.next().map(|value| {
+                value
+                    .parse::<i64>()
+                    .ok()
+                    .and_then(|step| i32::try_from(step).ok())
+            });
+
+            let Some(value) = value.filter(|value| value.is_finite()) else {
+                warn!("Skipping metric {name} with unsupported value: {line}");
+                continue;
+            };
+
+            // steps are stored as 32 bit integers
+            let step = match step {
+                None => None,
+                Some(Some(step)) => Some(step),
+                Some(None) => {
+                    warn!("Skipping metric {name} with unsupported step: {line}");
+                    continue;
+                }
+            };
+
+            metrics.push(Metric {
+                name: name.clone(),
+                value,
+                step,
+                timestamp,
+                created_at: timestamp.and_then(millis_to_datetime),
+            });
+        }
+    }
+
+    Ok(metrics)
+}
+
+/// Reads a single MLflow run directory
+fn read_run(dir: &Path, space: &str) -> Result<Option<ImportedRun>, ExperimentError> {
+    let meta = read_meta(&dir.join(MLFLOW_META_FILE))?;
+    let Some(run_id) = meta.get("run_id").or_else(|| meta.get("run_uuid")).cloned() else {
+        return Ok(None);
+    };
+
+    if is_deleted(&meta) {
+        debug!("Skipping deleted MLflow run {run_id}");
+        return Ok(None);
+    }
+
+    let mut run_name = meta
+        .get("run_name")
+        .cloned()
+        .filter(|name| !name.is_empty());
+    let mut tags = Vec::new();
+    for (key, value) in read_key_files(&dir.join("tags"))? {
+        if key == MLFLOW_RUN_NAME_TAG {
+            run_name = run_name.or(Some(value));
+        } else if !key.starts_with(MLFLOW_SYSTEM_TAG_PREFIX) && !value.contains('\n') {
+            tags.push(format!("{key}:{value}"));
+        }
+    }
+
+    let parameters = read_key_files(&dir.join("params"))?
+        .into_iter()
+        .map(|(name, value)| Parameter {
+            name,
+            value: parse_parameter_value(&value),
+        })
+        .collect();
+
+    let artifacts_dir = match dir.join("artifacts") {
+        local if local.is_dir() => Some(local),
+        _ => meta
+            .get("artifact_uri")
+            .and_then(|uri| uri.strip_prefix("file://"))
+            .map(PathBuf::from)
+            .filter(|path| path.is_dir()),
+    };
+
+    let mut artifacts = Vec::new();
+    match artifacts_dir {
+        Some(artifacts_dir) => {
+            for entry in WalkDir::new(&artifacts_dir) {
+                let entry = entry?;
+                if entry.file_type().is_file() {
+                    let rpath = entry.path().strip_prefix(&artifacts_dir)?.to_path_buf();
+                    artifacts.push((entry.path().to_path_buf(), rpath));
+                }
+            }
+        }
+        None => {
+            if let Some(uri) = meta.get("artifact_uri") {
+                warn!("Artifacts of MLflow run {run_id} are not stored locally ({uri}), skipping them");
+            }
+        }
+    }
+
+    let created_at = meta
+        .get("start_time")
+        .and_then(|time| time.parse::<i64>().ok())
+        .and_then(millis_to_datetime)
+        .unwrap_or_default();
+
+    Ok(Some(ImportedRun {
+        space: space.to_string(),
+        name: to_card_name(run_name.as_deref().unwrap_or(&run_id), "run"),
+        created_at,
+        tags,
+        metrics: read_metrics(&dir.join("metrics"))?,
+        parameters,
+        artifacts,
+        source_tag: Some(format!("mlflow_run_id:{run_id}")),
+    }))
+}
+
+/// Space of the runs of an MLflow experiment directory, named after the experiment. None when the
+/// experiment was deleted
+fn experiment_space(dir: &Path) -> Result<Option<String>, ExperimentError> {
+    let meta = read_meta(&dir.join(MLFLOW_META_FILE))?;
+    if is_deleted(&meta) {
+        return Ok(None);
+    }
+
+    let name = meta
+        .get("name")
+        .or_else(|| meta.get("experiment_id"))
+        .cloned()
+        .unwrap_or_default();
+
+    Ok(Some(to_card_name(&name, "default")))
+}
+
+fn read_experiment(dir: &Path) -> Result<Vec<ImportedRun>, ExperimentError> {
+    let Some(space) = experiment_space(dir)? else {
+        return Ok(Vec::new());
+    };
+
+    let mut runs = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let run_dir = entry?.path();
+        if run_dir.join(MLFLOW_META_FILE).is_file() {
+            runs.extend(read_run(&run_dir, &space)?);
+        }
+    }
+
+    Ok(runs)
+}
+
+/// Reads MLflow runs from `path`, which is an `mlruns` directory, an experiment directory or a
+/// single run directory
+pub fn read_mlflow_runs(path: &Path) -> Result<Vec<ImportedRun>, ExperimentError> {
+    let meta_path = path.join(MLFLOW_META_FILE);
+
+    let runs = match meta_path.is_file() {
+        true => match read_meta(&meta_path)?.contains_key("run_id") {
+            // runs are stored inside their experiment directory
+            true => match experiment_space(path.parent().unwrap_or(path))? {
+                Some(space) => read_run(path, &space)?.into_iter().collect(),
+                None => Vec::new(),
+            },
+            false => read_experiment(path)?,
+        },
+        false => {
+            let mut runs = Vec::new();
+            for entry in std::fs::read_dir(path)? {
+                let experiment_dir = entry?.path();
+                if experiment_dir.join(MLFLOW_META_FILE).is_file() {
+                    runs.extend(read_experiment(&experiment_dir)?);
+                }
+            }
+            runs
+        }
+    };
+
+    if runs.is_empty() {
+        return Err(ExperimentError::NoRunsToImport(
+            "MLflow",
+            path.display().to_string(),
+        ));
+    }
+
+    Ok(runs)
+}
+
+/// Imports local MLflow runs. Every run becomes an experiment in the space named after its MLflow
+/// experiment, with its metrics, parameters, tags and locally stored artifacts.
+///
+/// Runs imported earlier are skipped, so an interrupted import can be re-run
+#[instrument(skip_all)]
+pub fn import_mlflow_runs(path: &Path) -> Result<Vec<ImportedExperiment>, ExperimentError> {
+    register_imported_runs(read_mlflow_runs(path)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opsml_types::cards::experiment::ParameterValue;
+    use tempfile::TempDir;
+
+    fn write(path: &Path, content: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    /// An `mlruns` tree with one active and one deleted run
+    fn mlruns() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        let experiment = dir.path().join("1");
+
+        write(
+            &experiment.join(MLFLOW_META_FILE),
+            "artifact_location: file:///tmp/mlruns/1\nexperiment_id: '1'\nlifecycle_stage: active\nname: 'Churn Model'\n",
+        );
+
+        let run = experiment.join("abc123");
+        write(
+            &run.join(MLFLOW_META_FILE),
+            "artifact_uri: file:///tmp/mlruns/1/abc123/artifacts\nexperiment_id: '1'\nlifecycle_stage: active\nrun_id: abc123\nrun_name: 'it''s a run'\nstart_time: 1700000000000\ntags: []\n",
+        );
+        write(
+            &run.join("metrics/loss"),
+            "1700000000000 0.5 0\n1700000001000 0.25 1\n",
+        );
+        write(&run.join("metrics/legacy"), "1700000002000 3.0\n");
+        write(
+            &run.join("metrics/overflow"),
+            "1700000003000 1.0 3000000000\n",
+        );
+        write(&run.join("params/lr"), "0.01");
+        write(&run.join("tags/team"), "ml");
+        write(&run.join("tags/mlflow.user"), "someone");
+        write(&run.join("artifacts/model/weights.bin"), "weights");
+
+        let deleted = experiment.join("def456");
+        write(
+            &deleted.join(MLFLOW_META_FILE),
+            "experiment_id: '1'\nlifecycle_stage: deleted\nrun_id: def456\nrun_name: deleted-run\n",
+        );
+        write(&deleted.join("metrics/loss"), "1700000000000 0.5 0\n");
+
+        dir
+    }
+
+    #[test]
+    fn test_read_mlflow_runs() {
+        let dir = mlruns();
+        let runs = read_mlflow_runs(dir.path()).unwrap();
+        assert_eq!(runs.len(), 1);
+
+        let run = &runs[0];
+        assert_eq!(run.space, "churn-model");
+        assert_eq!(run.name, "it-s-a-run");
+        assert_eq!(run.source_tag.as_deref(), Some("mlflow_run_id:abc123"));
+        assert_eq!(run.created_at.timestamp_millis(), 1700000000000);
+        assert_eq!(run.tags, vec!["team:ml".to_string()]);
+
+        assert_eq!(run.parameters.len(), 1);
+        assert_eq!(run.parameters[0].name, "lr");
+        assert!(matches!(run.parameters[0].value, ParameterValue::Float(value) if value == 0.01));
+
+        // metrics without a step are kept, steps outside of the i32 range are skipped
+        let metrics: Vec<(&str, f64, Option<i32>)> = run
+            .metrics
+            .iter()
+            .map(|metric| (metric.name.as_str(), metric.value, metric.step))
+            .collect();
+        assert_eq!(
+            metrics,
+            vec![
+                ("legacy", 3.0, None),
+                ("loss", 0.5, Some(0)),
+                ("loss", 0.25, Some(1)),
+            ]
+        );
+        assert_eq!(run.metrics[1].timestamp, Some(1700000000000));
+
+        assert_eq!(run.artifacts.len(), 1);
+        assert_eq!(run.artifacts[0].1, PathBuf::from("model/weights.bin"));
+    }
+
+    #[test]
+    fn test_read_mlflow_run_directory() {
+        let dir = mlruns();
+
+        let runs = read_mlflow_runs(&dir.path().join("1/abc123")).unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].space, "churn-model");
+
+        let result = read_mlflow_runs(&dir.path().join("1/def456"));
+        assert!(matches!(result, Err(ExperimentError::NoRunsToImport(..))));
+    }
+}
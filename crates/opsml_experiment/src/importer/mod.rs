@@ -0,0 +1,202 @@
+pub mod mlflow;
+pub mod tensorboard;
+
+pub use mlflow::*;
+pub use tensorboard::*;
+
+use crate::error::ExperimentError;
+use crate::metric_queue::METRIC_BATCH_SIZE;
+use crate::offline::next_version;
+use chrono::{DateTime, Utc};
+use opsml_cards::ExperimentCard;
+use opsml_crypt::{encrypt_directory, generate_key};
+use opsml_registry::base::OpsmlRegistry;
+use opsml_storage::{add_card_manifest, storage_client};
+use opsml_types::cards::experiment::{Metric, Parameter, ParameterValue};
+use opsml_types::contracts::{CardQueryArgs, MetricRequest, ParameterRequest};
+use opsml_types::{RegistryType, SaveName};
+use opsml_utils::create_uuid7;
+use std::path::PathBuf;
+use tempfile::TempDir;
+use tracing::{debug, instrument};
+
+/// Longest name accepted for a card
+const MAX_NAME_LENGTH: usize = 53;
+
+/// A run read from the logs of another tracking tool
+#[derive(Debug, Default)]
+pub struct ImportedRun {
+    pub space: String,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    pub tags: Vec<String>,
+    pub metrics: Vec<Metric>,
+    pub parameters: Vec<Parameter>,
+
+    /// Files to store as artifacts of the experiment, with their path in the artifact directory
+    pub artifacts: Vec<(PathBuf, PathBuf)>,
+
+    /// Tag identifying the source run. Runs already registered with this tag are skipped
+    pub source_tag: Option<String>,
+}
+
+/// Result of importing a run
+#[derive(Debug)]
+pub struct ImportedExperiment {
+    pub uid: String,
+    pub space: String,
+    pub name: String,
+    pub version: String,
+    pub metrics: usize,
+    pub parameters: usize,
+    pub artifacts: usize,
+
+    /// The run was imported by an earlier call
+    pub skipped: bool,
+}
+
+/// Converts a name used by another tool into a valid space or card name. Characters other than
+/// lowercase letters, digits and dashes are replaced by dashes
+pub fn to_card_name(name: &str, fallback: &str) -> String {
+    let mut cleaned = String::with_capacity(name.len());
+
+    for c in name.trim().to_lowercase().chars() {
+        match c.is_ascii_alphanumeric() {
+            true => cleaned.push(c),
+            false if !cleaned.ends_with('-') && !cleaned.is_empty() => cleaned.push('-'),
+            false => {}
+        }
+    }
+
+    cleaned.truncate(MAX_NAME_LENGTH);
+    let cleaned = cleaned.trim_end_matches('-');
+
+    match cleaned.is_empty() {
+        true => fallback.to_string(),
+        false => cleaned.to_string(),
+    }
+}
+
+/// Parameters of other tools are stored as text. Numbers are converted back to numbers
+pub fn parse_parameter_value(value: &str) -> ParameterValue {
+    if let Ok(value) = value.parse::<i64>() {
+        ParameterValue::Int(value)
+    } else if let Some(value) = value.parse::<f64>().ok().filter(|value| value.is_finite()) {
+        ParameterValue::Float(value)
+    } else {
+        ParameterValue::Str(value.to_string())
+    }
+}
+
+fn find_imported(
+    registry: &OpsmlRegistry,
+    run: &ImportedRun,
+) -> Result<Option<ImportedExperiment>, ExperimentError> {
+    let Some(source_tag) = &run.source_tag else {
+        return Ok(None);
+    };
+
+    let existing = registry
+        .list_cards(CardQueryArgs {
+            space: Some(run.space.clone()),
+            tags: Some(vec![source_tag.clone()]),
+            limit: Some(1),
+            registry_type: RegistryType::Experiment,
+            ..Default::default()
+        })?
+        .into_iter()
+        .next();
+
+    Ok(existing.map(|card| ImportedExperiment {
+        uid: card.uid().to_string(),
+        space: card.space().to_string(),
+        name: card.name().to_string(),
+        version: card.version().to_string(),
+        metrics: 0,
+        parameters: 0,
+        artifacts: 0,
+        skipped: true,
+    }))
+}
+
+/// Registers an imported run as an experiment. The card keeps the start time of the run and
+/// receives the next minor version of its space and name
+#[instrument(skip_all)]
+pub fn register_imported_run(
+    registry: &OpsmlRegistry,
+    run: ImportedRun,
+) -> Result<ImportedExperiment, ExperimentError> {
+    if let Some(existing) = find_imported(registry, &run)? {
+        debug!("Run {}/{} was already imported", run.space, run.name);
+        return Ok(existing);
+    }
+
+    let mut tags = run.tags;
+    tags.extend(run.source_tag);
+
+    let mut card = ExperimentCard::imported(&run.space, &run.name, tags, run.created_at)?;
+    card.uid = create_uuid7();
+    card.version = next_version(registry, &card.space, &card.name)?;
+
+    let encryption_key = generate_key()?;
+    let key = registry
+        .import_card(card.get_registry_card()?, encryption_key.to_vec(), true)?
+        .key;
+    card.set_artifact_key(key.clone());
+
+    let tmp_dir = TempDir::new()?;
+    card.save(tmp_dir.path().to_path_buf())?;
+
+    // source files are copied so the logs of the other tool are never encrypted in place
+    let artifacts_dir = tmp_dir.path().join(SaveName::Artifacts);
+    for (source, rpath) in &run.artifacts {
+        let lpath = artifacts_dir.join(rpath);
+        if let Some(parent) = lpath.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::copy(source, &lpath)?;
+    }
+
+    encrypt_directory(tmp_dir.path(), &key.get_decrypt_key()?)?;
+    add_card_manifest(&key, tmp_dir.path())?;
+    storage_client()?.put(tmp_dir.path(), &key.storage_path(), true)?;
+
+    for metrics in run.metrics.chunks(METRIC_BATCH_SIZE) {
+        registry.insert_metrics(&MetricRequest {
+            experiment_uid: card.uid.clone(),
+            metrics: metrics.to_vec(),
+        })?;
+    }
+
+    if !run.parameters.is_empty() {
+        registry.insert_parameters(&ParameterRequest {
+            experiment_uid: card.uid.clone(),
+            parameters: run.parameters.clone(),
+        })?;
+    }
+
+    Ok(ImportedExperiment {
+        uid: card.uid,
+        space: card.space,
+        name: card.name,
+        version: card.version,
+        metrics: run.metrics.len(),
+        parameters: run.parameters.len(),
+        artifacts: run.artifacts.len(),
+        skipped: false,
+    })
+}
+
+/// Registers runs in order of their start time, so versions of the same name follow the order
+/// the runs were recorded in
+fn register_imported_runs(
+    mut runs: Vec<ImportedRun>,
+) -> Result<Vec<ImportedExperiment>, ExperimentError> {
+    runs.sort_by_key(|run| run.created_at);
+
+    let registry = OpsmlRegistry::new(RegistryType::Experiment)?;
+
+    runs.into_iter()
+        .map(|run| register_imported_run(&registry, run))
+        .collect()
+}
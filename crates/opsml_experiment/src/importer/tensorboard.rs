@@ -0,0 +1,685 @@
+use super::{register_imported_runs, to_card_name, ImportedExperiment, ImportedRun};
+use crate::error::ExperimentError;
+use chrono::DateTime;
+use crc::{Crc, CRC_32_ISCSI};
+use opsml_types::cards::experiment::{Metric, Parameter, ParameterValue};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use tracing::{debug, instrument, warn};
+use walkdir::WalkDir;
+
+/// TensorBoard event files contain this marker in their name
+const EVENT_FILE_MARKER: &str = "tfevents";
+
+const SCALARS_PLUGIN: &str = "scalars";
+const HPARAMS_PLUGIN: &str = "hparams";
+
+const CRC32C: Crc<u32> = Crc::<u32>::new(&CRC_32_ISCSI);
+
+/// TFRecord checksums are masked crc32c values
+fn masked_crc(data: &[u8]) -> u32 {
+    let crc = CRC32C.checksum(data);
+    crc.rotate_right(15).wrapping_add(0xa282_ead8)
+}
+
+fn read_u32(bytes: &[u8]) -> u32 {
+    u32::from_le_bytes(bytes[..4].try_into().unwrap_or_default())
+}
+
+fn read_u64(bytes: &[u8]) -> u64 {
+    u64::from_le_bytes(bytes[..8].try_into().unwrap_or_default())
+}
+
+/// Splits a TFRecord file into its records. Each record is stored as
+/// `length (u64) | masked crc of length (u32) | data | masked crc of data (u32)`.
+///
+/// A truncated last record, left by a writer that was still running, is ignored
+fn read_records(path: &Path) -> Result<Vec<Vec<u8>>, ExperimentError> {
+    let bytes = std::fs::read(path)?;
+    let invalid = |reason: &str| {
+        ExperimentError::InvalidEventFile(path.display().to_string(), reason.to_string())
+    };
+
+    let mut records = Vec::new();
+    let mut pos = 0;
+
+    while pos < bytes.len() {
+        let Some(header) = bytes.get(pos..pos + 12) else {
+            warn!("Ignoring truncated record at the end of {}", path.display());
+            break;
+        };
+
+        if masked_crc(&header[..8]) != read_u32(&header[8..]) {
+            return Err(invalid("record length checksum mismatch"));
+        }
+
+        let length = usize::try_from(read_u64(header)).map_err(|_| invalid("record too large"))?;
+        let start = pos + 12;
+        let Some(data) = length
+            .checked_add(4)
+            .and_then(|size| start.checked_add(size))
+            .and_then(|end| bytes.get(start..end))
+        else {
+            warn!("Ignoring truncated record at the end of {}", path.display());
+            break;
+        };
+
+        let (data, crc) = data.split_at(length);
+        if masked_crc(data) != read_u32(crc) {
+            return Err(invalid("record data checksum mismatch"));
+        }
+
+        records.push(data.to_vec());
+        pos = start + length + 4;
+    }
+
+    Ok(records)
+}
+
+/// Value of a protobuf field, by wire type
+enum WireValue<'a> {
+    Varint(u64),
+    Fixed64(u64),
+    Bytes(&'a [u8]),
+    Fixed32(u32),
+}
+
+/// Minimal protobuf reader, enough to decode the messages of event files
+struct ProtoReader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ProtoReader<'a> {
+    fn new(buf: &'a [u8]) -> Self {
+        Self { buf, pos: 0 }
+    }
+
+    fn varint(&mut self) -> Option<u64> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = *self.buf.get(self.pos)?;
+            self.pos += 1;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Some(value);
+            }
+        }
+        None
+    }
+
+    fn take(&mut self, length: usize) -> Option<&'a [u8]> {
+        let bytes = self.buf.get(self.pos..self.pos.checked_add(length)?)?;
+        self.pos += length;
+        Some(bytes)
+    }
+}
+
+/// Yields the field numbers and values of a message. Stops at the end of the message, or when the
+/// message is malformed
+impl<'a> Iterator for ProtoReader<'a> {
+    type Item = (u64, WireValue<'a>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos >= self.buf.len() {
+            return None;
+        }
+
+        let key = self.varint()?;
+        let value = match key & 0x7 {
+            0 => WireValue::Varint(self.varint()?),
+            1 => WireValue::Fixed64(read_u64(self.take(8)?)),
+            2 => {
+                let length = usize::try_from(self.varint()?).ok()?;
+                WireValue::Bytes(self.take(length)?)
+            }
+            5 => WireValue::Fixed32(read_u32(self.take(4)?)),
+            _ => return None,
+        };
+
+        Some((key >> 3, value))
+    }
+}
+
+fn as_string(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes).into_owned()
+}
+
+/// Values of a repeated numeric field, which is either packed or written once per value
+fn repeated_values(value: &WireValue, size: usize, decode: fn(&[u8]) -> f64) -> Vec<f64> {
+    match value {
+        WireValue::Bytes(bytes) if size > 0 => bytes.chunks_exact(size).map(decode).collect(),
+        WireValue::Bytes(bytes) => {
+            let mut reader = ProtoReader::new(bytes);
+            std::iter::from_fn(|| reader.varint())
+                .map(|value| value as i64 as f64)
+                .collect()
+        }
+        WireValue::Fixed32(value) => vec![decode(&value.to_le_bytes())],
+        WireValue::Fixed64(value) => vec![decode(&value.to_le_bytes())],
+        WireValue::Varint(value) => vec![*value as i64 as f64],
+    }
+}
+
+fn decode_f32(bytes: &[u8]) -> f64 {
+    f64::from(f32::from_le_bytes(bytes.try_into().unwrap_or_default()))
+}
+
+fn decode_f64(bytes: &[u8]) -> f64 {
+    f64::from_le_bytes(bytes.try_into().unwrap_or_default())
+}
+
+fn decode_i32(bytes: &[u8]) -> f64 {
+    f64::from(i32::from_le_bytes(bytes.try_into().unwrap_or_default()))
+}
+
+fn decode_i64(bytes: &[u8]) -> f64 {
+    i64::from_le_bytes(bytes.try_into().unwrap_or_default()) as f64
+}
+
+/// Value of a scalar `TensorProto`. Tensors with more than one value are not scalars
+fn tensor_scalar(tensor: &[u8]) -> Option<f64> {
+    let mut dtype = 0;
+    let mut content = None;
+    let mut values = Vec::new();
+
+    for (field, value) in ProtoReader::new(tensor) {
+        match (field, &value) {
+            (1, WireValue::Varint(value)) => dtype = *value,
+            (4, WireValue::Bytes(bytes)) => content = Some(*bytes),
+            (5, _) => values.extend(repeated_values(&value, 4, decode_f32)),
+            (6, _) => values.extend(repeated_values(&value, 8, decode_f64)),
+            (7, _) | (10, _) => values.extend(repeated_values(&value, 0, decode_i64)),
+            _ => {}
+        }
+    }
+
+    // DT_FLOAT, DT_DOUBLE, DT_INT32 and DT_INT64
+    if let Some(content) = content {
+        values = match dtype {
+            1 => content.chunks_exact(4).map(decode_f32).collect(),
+            2 => content.chunks_exact(8).map(decode_f64).collect(),
+            3 => content.chunks_exact(4).map(decode_i32).collect(),
+            9 => content.chunks_exact(8).map(decode_i64).collect(),
+            _ => return None,
+        };
+    }
+
+    match values.as_slice() {
+        [value] => Some(*value),
+        _ => None,
+    }
+}
+
+/// Plugin name and content of a `SummaryMetadata`
+fn plugin_data(metadata: &[u8]) -> Option<(String, &[u8])> {
+    ProtoReader::new(metadata).find_map(|(field, value)| match (field, value) {
+        (1, WireValue::Bytes(plugin_data)) => {
+            let mut name = None;
+            let mut content: &[u8] = &[];
+            for (field, value) in ProtoReader::new(plugin_data) {
+                match (field, value) {
+                    (1, WireValue::Bytes(bytes)) => name = Some(as_string(bytes)),
+                    (2, WireValue::Bytes(bytes)) => content = bytes,
+                    _ => {}
+                }
+            }
+            name.map(|name| (name, content))
+        }
+        _ => None,
+    })
+}
+
+/// Hyperparameters of the `session_start_info` of the hparams plugin. Values are
+/// `google.protobuf.Value` messages
+fn hparams(content: &[u8]) -> Vec<Parameter> {
+    let mut parameters = Vec::new();
+
+    for (field, value) in ProtoReader::new(content) {
+        let (3, WireValue::Bytes(session_start_info)) = (field, value) else {
+            continue;
+        };
+
+        for (field, value) in ProtoReader::new(session_start_info) {
+            let (1, WireValue::Bytes(entry)) = (field, value) else {
+                continue;
+            };
+
+            let mut name = None;
+            let mut parameter_value = None;
+            for (field, value) in ProtoReader::new(entry) {
+                match (field, value) {
+                    (1, WireValue::Bytes(bytes)) => name = Some(as_string(bytes)),
+                    (2, WireValue::Bytes(bytes)) => {
+                        parameter_value =
+                            ProtoReader::new(bytes).find_map(|(field, value)| {
+                                match (field, value) {
+                                    (2, WireValue::Fixed64(bits)) => {
+                                        Some(ParameterValue::Float(f64::from_bits(bits)))
+                                    }
+                                    (3, WireValue::Bytes(bytes)) => {
+                                        Some(ParameterValue::Str(as_string(bytes)))
+                                    }
+                                    (4, WireValue::Varint(value)) => {
+                                        Some(ParameterValue::Str((value != 0).to_string()))
+                                    }
+                                    _ => None,
+                                }
+                            })
+                    }
+                    _ => {}
+                }
+            }
+
+            if let (Some(name), Some(value)) = (name, parameter_value) {
+                parameters.push(Parameter { name, value });
+            }
+        }
+    }
+
+    parameters
+}
+
+/// Scalars and hyperparameters of the events of a run
+#[derive(Default)]
+struct EventReader {
+    /// Metadata is only written with the first value of a tag
+    plugins: HashMap<String, String>,
+    metrics: Vec<Metric>,
+    parameters: BTreeMap<String, ParameterValue>,
+    first_wall_time: Option<f64>,
+}
+
+impl EventReader {
+    fn read_event(&mut self, event: &[u8]) {
+        let mut wall_time = None;
+        let mut step = None;
+        let mut summary = None;
+
+        for (field, value) in ProtoReader::new(event) {
+            match (field, value) {
+                (1, WireValue::Fixed64(bits)) => wall_time = Some(f64::from_bits(bits)),
+                (2, WireValue::Varint(value)) => step = Some(value as i64),
+                (5, WireValue::Bytes(bytes)) => summary = Some(bytes),
+                _ => {}
+            }
+        }
+
+        if let Some(wall_time) = wall_time {
+            self.first_wall_time =
+                Some(self.first_wall_time.map_or(wall_time, |t| t.min(wall_time)));
+        }
+
+        let Some(summary) = summary else {
+            return;
+        };
+
+        for (field, value) in ProtoReader::new(summary) {
+            if let (1, WireValue::Bytes(value)) = (field, value) {
+                self.read_summary_value(value, wall_time, step);
+            }
+        }
+    }
+
+    fn read_summary_value(&mut self, value: &[u8], wall_time: Option<f64>, step: Option<i64>) {
+        let mut tag = None;
+        let mut simple_value = None;
+        let mut tensor = None;
+        let mut metadata = None;
+
+        for (field, value) in ProtoReader::new(value) {
+            match (field, value) {
+                (1, WireValue::Bytes(bytes)) => tag = Some(as_string(bytes)),
+                (2, WireValue::Fixed32(bits)) => {
+                    simple_value = Some(f64::from(f32::from_bits(bits)))
+                }
+                (8, WireValue::Bytes(bytes)) => tensor = Some(bytes),
+                (9, WireValue::Bytes(bytes)) => metadata = Some(bytes),
+                _ => {}
+            }
+        }
+
+        let Some(tag) = tag else {
+            return;
+        };
+
+        if let Some((plugin, content)) = metadata.and_then(plugin_data) {
+            if plugin == HPARAMS_PLUGIN {
+                self.parameters.extend(
+                    hparams(content)
+                        .into_iter()
+                        .map(|parameter| (parameter.name, parameter.value)),
+                );
+            }
+            self.plugins.insert(tag.clone(), plugin);
+        }
+
+        let value = match simple_value {
+            Some(value) => Some(value),
+            None => match self.plugins.get(&tag).map(String::as_str) {
+                Some(SCALARS_PLUGIN) => tensor.and_then(tensor_scalar),
+                _ => None,
+            },
+        };
+
+        let Some(value) = value.filter(|value| value.is_finite()) else {
+            return;
+        };
+
+        // steps are stored as 32 bit integers
+        let Ok(step) = step.map(i32::try_from).transpose() else {
+            warn!("Skipping value of {tag} with unsupported step {step:?}");
+            return;
+        };
+
+        let timestamp = wall_time.map(|wall_time| (wall_time * 1000.0) as i64);
+        self.metrics.push(Metric {
+            name: tag,
+            value,
+            step,
+            timestamp,
+            created_at: timestamp.and_then(DateTime::from_timestamp_millis),
+        });
+    }
+}
+
+/// Directories of `logdir` holding event files, with their event files
+fn find_runs(logdir: &Path) -> Result<BTreeMap<PathBuf, Vec<PathBuf>>, ExperimentError> {
+    let mut runs: BTreeMap<PathBuf, Vec<PathBuf>> = BTreeMap::new();
+
+    for entry in WalkDir::new(logdir).sort_by_file_name() {
+        let entry = entry?;
+        let is_event_file = entry.file_type().is_file()
+            && entry
+                .file_name()
+                .to_string_lossy()
+                .contains(EVENT_FILE_MARKER);
+
+        if is_event_file {
+            let run_dir = entry.path().parent().unwrap_or(logdir).to_path_buf();
+            runs.entry(run_dir)
+                .or_default()
+                .push(entry.path().to_path_buf());
+        }
+    }
+
+    Ok(runs)
+}
+
+/// Reads TensorBoard runs from `logdir`. Every directory holding event files is a run, named
+/// after its path relative to `logdir`
+pub fn read_tensorboard_runs(
+    logdir: &Path,
+    space: &str,
+) -> Result<Vec<ImportedRun>, ExperimentError> {
+    let space = to_card_name(space, "default");
+    let mut runs = Vec::new();
+
+    for (run_dir, event_files) in find_runs(logdir)? {
+        let relative = run_dir.strip_prefix(logdir)?.to_string_lossy().into_owned();
+        let name = match relative.is_empty() {
+            true => logdir
+                .canonicalize()?
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            false => relative,
+        };
+
+        let mut reader = EventReader::default();
+        let mut artifacts = Vec::new();
+        for event_file in event_files {
+            for record in read_records(&event_file)? {
+                reader.read_event(&record);
+            }
+
+            let rpath = event_file.strip_prefix(&run_dir)?.to_path_buf();
+            artifacts.push((event_file, rpath));
+        }
+
+        debug!(
+            "Read {} scalars from TensorBoard run {}",
+            reader.metrics.len(),
+            run_dir.display()
+        );
+
+        runs.push(ImportedRun {
+            space: space.clone(),
+            name: to_card_name(&name, "run"),
+            created_at: reader
+                .first_wall_time
+                .and_then(|time| DateTime::from_timestamp_millis((time * 1000.0) as i64))
+                .unwrap_or_default(),
+            tags: Vec::new(),
+            metrics: reader.metrics,
+            parameters: reader
+                .parameters
+                .into_iter()
+                .map(|(name, value)| Parameter { name, value })
+                .collect(),
+            artifacts,
+            source_tag: None,
+        });
+    }
+
+    if runs.is_empty() {
+        return Err(ExperimentError::NoRunsToImport(
+            "TensorBoard",
+            logdir.display().to_string(),
+        ));
+    }
+
+    Ok(runs)
+}
+
+/// Imports TensorBoard runs into `space`. Scalars become metrics, hparams become parameters and
+/// the event files are stored as artifacts of the experiment
+#[instrument(skip_all)]
+pub fn import_tensorboard_runs(
+    logdir: &Path,
+    space: &str,
+) -> Result<Vec<ImportedExperiment>, ExperimentError> {
+    register_imported_runs(read_tensorboard_runs(logdir, space)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn varint(mut value: u64) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        while value >= 0x80 {
+            bytes.push((value as u8) | 0x80);
+            value >>= 7;
+        }
+        bytes.push(value as u8);
+        bytes
+    }
+
+    fn field_varint(field: u64, value: u64) -> Vec<u8> {
+        [varint(field << 3), varint(value)].concat()
+    }
+
+    fn field_fixed64(field: u64, value: u64) -> Vec<u8> {
+        [varint((field << 3) | 1), value.to_le_bytes().to_vec()].concat()
+    }
+
+    fn field_fixed32(field: u64, value: u32) -> Vec<u8> {
+        [varint((field << 3) | 5), value.to_le_bytes().to_vec()].concat()
+    }
+
+    fn field_bytes(field: u64, value: &[u8]) -> Vec<u8> {
+        [
+            varint((field << 3) | 2),
+            varint(value.len() as u64),
+            value.to_vec(),
+        ]
+        .concat()
+    }
+
+    fn record(data: &[u8]) -> Vec<u8> {
+        let length = (data.len() as u64).to_le_bytes();
+        [
+            length.to_vec(),
+            masked_crc(&length).to_le_bytes().to_vec(),
+            data.to_vec(),
+            masked_crc(data).to_le_bytes().to_vec(),
+        ]
+        .concat()
+    }
+
+    fn event(wall_time: f64, step: u64, value: &[u8]) -> Vec<u8> {
+        let summary = field_bytes(1, value);
+        [
+            field_fixed64(1, wall_time.to_bits()),
+            field_varint(2, step),
+            field_bytes(5, &summary),
+        ]
+        .concat()
+    }
+
+    fn plugin_metadata(plugin: &str, content: &[u8]) -> Vec<u8> {
+        let plugin_data = [field_bytes(1, plugin.as_bytes()), field_bytes(2, content)].concat();
+        field_bytes(9, &field_bytes(1, &plugin_data))
+    }
+
+    fn simple_value(tag: &str, value: f32) -> Vec<u8> {
+        [
+            field_bytes(1, tag.as_bytes()),
+            field_fixed32(2, value.to_bits()),
+        ]
+        .concat()
+    }
+
+    /// Scalar written by `tf.summary.scalar`, as a float tensor of the scalars plugin
+    fn tensor_value(tag: &str, value: f32, with_metadata: bool) -> Vec<u8> {
+        let tensor = [field_varint(1, 1), field_bytes(4, &value.to_le_bytes())].concat();
+        let metadata = match with_metadata {
+            true => plugin_metadata(SCALARS_PLUGIN, &[]),
+            false => Vec::new(),
+        };
+        [
+            field_bytes(1, tag.as_bytes()),
+            field_bytes(8, &tensor),
+            metadata,
+        ]
+        .concat()
+    }
+
+    fn hparams_value(name: &str, value: f64) -> Vec<u8> {
+        let number = field_fixed64(2, value.to_bits());
+        let entry = [field_bytes(1, name.as_bytes()), field_bytes(2, &number)].concat();
+        let session_start_info = field_bytes(1, &entry);
+        let content = field_bytes(3, &session_start_info);
+        [
+            field_bytes(1, b"_hparams_/session_start_info"),
+            plugin_metadata(HPARAMS_PLUGIN, &content),
+        ]
+        .concat()
+    }
+
+    fn write_events(dir: &Path, records: &[Vec<u8>]) -> PathBuf {
+        let run_dir = dir.join("run-a");
+        std::fs::create_dir_all(&run_dir).unwrap();
+        let path = run_dir.join("events.out.tfevents.1700000000.host");
+        std::fs::write(&path, records.concat()).unwrap();
+        path
+    }
+
+    fn events() -> Vec<Vec<u8>> {
+        vec![
+            record(&event(
+                1700000000.0,
+                0,
+                &hparams_value("learning_rate", 0.5),
+            )),
+            record(&event(1700000001.0, 1, &simple_value("loss", 0.5))),
+            record(&event(
+                1700000002.0,
+                1,
+                &tensor_value("accuracy", 0.75, true),
+            )),
+            record(&event(
+                1700000003.0,
+                2,
+                &tensor_value("accuracy", 0.5, false),
+            )),
+            record(&event(
+                1700000004.0,
+                3_000_000_000,
+                &simple_value("loss", 0.25),
+            )),
+        ]
+    }
+
+    #[test]
+    fn test_read_tensorboard_runs() {
+        let dir = TempDir::new().unwrap();
+        write_events(dir.path(), &events());
+
+        let runs = read_tensorboard_runs(dir.path(), "My Space").unwrap();
+        assert_eq!(runs.len(), 1);
+
+        let run = &runs[0];
+        assert_eq!(run.space, "my-space");
+        assert_eq!(run.name, "run-a");
+        assert_eq!(run.created_at.timestamp(), 1700000000);
+        assert_eq!(run.artifacts.len(), 1);
+
+        assert_eq!(run.parameters.len(), 1);
+        assert_eq!(run.parameters[0].name, "learning_rate");
+        assert!(matches!(run.parameters[0].value, ParameterValue::Float(value) if value == 0.5));
+
+        // the value logged at a step outside of the i32 range is skipped
+        let metrics: Vec<(&str, f64, Option<i32>)> = run
+            .metrics
+            .iter()
+            .map(|metric| (metric.name.as_str(), metric.value, metric.step))
+            .collect();
+        assert_eq!(
+            metrics,
+            vec![
+                ("loss", 0.5, Some(1)),
+                ("accuracy", 0.75, Some(1)),
+                ("accuracy", 0.5, Some(2))
+            ]
+        );
+        assert_eq!(run.metrics[0].timestamp, Some(1700000001000));
+    }
+
+    #[test]
+    fn test_read_truncated_record() {
+        let dir = TempDir::new().unwrap();
+        let mut records = events();
+        let last = record(&event(1700000005.0, 4, &simple_value("loss", 0.1)));
+        records.push(last[..last.len() - 6].to_vec());
+        let path = write_events(dir.path(), &records);
+
+        assert_eq!(read_records(&path).unwrap().len(), 5);
+
+        // a header cut short is ignored as well
+        records.pop();
+        records.push(last[..6].to_vec());
+        let path = write_events(dir.path(), &records);
+        assert_eq!(read_records(&path).unwrap().len(), 5);
+    }
+
+    #[test]
+    fn test_read_invalid_checksum() {
+        let dir = TempDir::new().unwrap();
+        let mut records = events();
+        let corrupted = records[1].len() - 5;
+        records[1][corrupted] ^= 0xff;
+        write_events(dir.path(), &records);
+
+        let result = read_tensorboard_runs(dir.path(), "space");
+        assert!(matches!(result, Err(ExperimentError::InvalidEventFile(..))));
+
+        // a record length too large for the file is a truncated record, not a valid one
+        let mut header = u64::MAX.to_le_bytes().to_vec();
+        header.extend(masked_crc(&u64::MAX.to_le_bytes()).to_le_bytes());
+        let path = write_events(dir.path(), &[records[0].clone(), header]);
+        assert_eq!(read_records(&path).unwrap().len(), 1);
+    }
+}
@@ -1,6 +1,16 @@
+pub mod environment;
 pub mod error;
 pub mod experiment;
 pub mod hardware_queue;
+pub mod importer;
+pub mod metric_queue;
+pub mod offline;
+pub mod sweep;
 
+pub use environment::*;
 pub use experiment::*;
 pub use hardware_queue::*;
+pub use importer::*;
+pub use metric_queue::*;
+pub use offline::*;
+pub use sweep::*;
@@ -0,0 +1,695 @@
+use crate::error::ExperimentError;
+use opsml_registry::base::OpsmlRegistry;
+use opsml_registry::error::RegistryError;
+use opsml_state::app_state;
+use opsml_types::cards::experiment::{Metric, Parameter};
+use opsml_types::contracts::{HardwareMetricRequest, MetricRequest, ParameterRequest};
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions, TryLockError};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+use tracing::{debug, error, info, warn};
+
+/// Number of buffered metrics that triggers a send
+pub const METRIC_BATCH_SIZE: usize = 500;
+
+/// Buffered metrics and parameters are sent at least this often
+pub const METRIC_FLUSH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Directory holding batches that could not be sent, resolved by [`spill_dir`]
+const SPILL_DIR: &str = "opsml/spill";
+
+enum QueueMessage {
+    Metrics(Vec<Metric>),
+    Parameters(Vec<Parameter>),
+    Flush(Sender<()>),
+}
+
+/// A batch that could not be sent, stored as one json line of the spill file.
+/// Offline experiments store every batch this way until they are synced
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SpilledBatch {
+    Metrics(MetricRequest),
+    Parameters(ParameterRequest),
+    HardwareMetrics(HardwareMetricRequest),
+}
+
+/// Directory holding batches that could not be sent. Batches are kept in the user's cache
+/// directory so they are found again regardless of the working directory of the run
+pub fn spill_dir() -> PathBuf {
+    dirs::cache_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join(SPILL_DIR)
+}
+
+/// Sends a single batch to the registry
+pub fn send_batch(registry: &OpsmlRegistry, batch: &SpilledBatch) -> Result<(), RegistryError> {
+    match batch {
+        SpilledBatch::Metrics(request) => registry.insert_metrics(request),
+        SpilledBatch::Parameters(request) => registry.insert_parameters(request),
+        SpilledBatch::HardwareMetrics(request) => {
+            app_state().block_on(registry.insert_hardware_metrics(request.clone()))
+        }
+    }
+}
+
+/// Destination of the batches logged during an experiment
+#[derive(Clone)]
+pub enum MetricTarget {
+    /// Batches are sent to the registry
+    Registry(Arc<OpsmlRegistry>),
+
+    /// Batches are appended to a file of an offline run and sent with `opsml experiment sync`
+    Offline(SpillFile),
+}
+
+/// Batches of an experiment waiting to be resent, in logging order
+#[derive(Clone)]
+pub struct SpillFile {
+    path: PathBuf,
+}
+
+impl SpillFile {
+    pub fn new(dir: &Path, experiment_uid: &str) -> Self {
+        Self {
+            path: dir.join(format!("{experiment_uid}.jsonl")),
+        }
+    }
+
+    pub fn from_path(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn read(&self) -> Result<Vec<SpilledBatch>, ExperimentError> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let file = std::fs::File::open(&self.path)?;
+        let mut batches = Vec::new();
+        for line in BufReader::new(file).lines() {
+            let line = line?;
+            if !line.trim().is_empty() {
+                batches.push(serde_json::from_str(&line)?);
+            }
+        }
+
+        Ok(batches)
+    }
+
+    /// Replaces the content of the spill file. The file is removed when there is nothing to resend
+    pub fn write(&self, batches: &[SpilledBatch]) -> Result<(), ExperimentError> {
+        if batches.is_empty() {
+            if self.path.exists() {
+                std::fs::remove_file(&self.path)?;
+            }
+            return Ok(());
+        }
+
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let tmp_path = self.path.with_extension("jsonl.tmp");
+        let mut file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&tmp_path)?;
+
+        for batch in batches {
+            writeln!(file, "{}", serde_json::to_string(batch)?)?;
+        }
+        file.sync_all()?;
+        std::fs::rename(&tmp_path, &self.path)?;
+
+        Ok(())
+    }
+
+    /// Locks the spill file so its batches are not resent by another process at the same time.
+    /// None if it is locked by a running experiment
+    fn try_lock(&self) -> Result<Option<SpillLock>, ExperimentError> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let path = self.path.with_extension("lock");
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&path)?;
+
+        match file.try_lock() {
+            Ok(()) => Ok(Some(SpillLock { _file: file, path })),
+            Err(TryLockError::WouldBlock) => Ok(None),
+            Err(TryLockError::Error(e)) => Err(e.into()),
+        }
+    }
+
+    /// Appends batches to the end of the file
+    pub fn append(&self, batches: &[SpilledBatch]) -> Result<(), ExperimentError> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+
+        for batch in batches {
+            writeln!(file, "{}", serde_json::to_string(batch)?)?;
+        }
+        file.sync_data()?;
+
+        Ok(())
+    }
+}
+
+/// Exclusive lock on a spill file, released when dropped
+struct SpillLock {
+    _file: File,
+    path: PathBuf,
+}
+
+impl SpillLock {
+    /// Releases the lock and removes the lock file once there is nothing left to resend
+    fn release(self, spill: &SpillFile) {
+        if !spill.path().exists() {
+            if let Err(e) = std::fs::remove_file(&self.path) {
+                debug!("Failed to remove {}: {e}", self.path.display());
+            }
+        }
+    }
+}
+
+/// Sends batches in order with `send` and returns the batches that still have to be resent.
+/// Everything after the first failed batch is kept so the server receives batches in order.
+/// Batches the server rejects as invalid are dropped, since resending them cannot succeed
+fn send_in_order(
+    batches: Vec<SpilledBatch>,
+    mut send: impl FnMut(&SpilledBatch) -> Result<(), RegistryError>,
+) -> Vec<SpilledBatch> {
+    let total = batches.len();
+    let mut batches = batches.into_iter();
+    let mut sent = 0;
+    let mut unsent = Vec::new();
+
+    for batch in batches.by_ref() {
+        match send(&batch) {
+            Ok(()) => sent += 1,
+            Err(e) if e.is_retryable() => {
+                warn!("Failed to send metrics, spilling to disk: {e}");
+                unsent.push(batch);
+                break;
+            }
+            // resending a batch the server rejected fails again and blocks the batches after it
+            Err(e) => error!("Server rejected metric batch, dropping it: {e}"),
+        }
+    }
+    unsent.extend(batches);
+
+    debug!("Sent {sent} of {total} metric batches");
+    unsent
+}
+
+/// Spill files in `dir` of experiments other than `experiment_uid`
+fn leftover_spill_files(dir: &Path, experiment_uid: &str) -> Vec<SpillFile> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut paths = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.extension().is_some_and(|ext| ext == "jsonl")
+                && path.file_stem().is_some_and(|stem| stem != experiment_uid)
+        })
+        .collect::<Vec<_>>();
+    paths.sort();
+
+    paths.into_iter().map(SpillFile::from_path).collect()
+}
+
+/// Resends the batches of experiments whose queue stopped before the server could receive them.
+/// Spill files of experiments that are still running are locked and left to their own queue.
+/// Stops at the first file that cannot be sent completely, as the server is still unavailable
+fn resend_leftover_spills(
+    dir: &Path,
+    experiment_uid: &str,
+    mut send: impl FnMut(&SpilledBatch) -> Result<(), RegistryError>,
+) {
+    for spill in leftover_spill_files(dir, experiment_uid) {
+        let lock = match spill.try_lock() {
+            Ok(Some(lock)) => lock,
+            Ok(None) => continue,
+            Err(e) => {
+                warn!("Failed to lock {}: {e}", spill.path().display());
+                continue;
+            }
+        };
+
+        let batches = match spill.read() {
+            Ok(batches) => batches,
+            Err(e) => {
+                error!(
+                    "Failed to read spilled metrics from {}: {e}",
+                    spill.path().display()
+                );
+                continue;
+            }
+        };
+
+        if !batches.is_empty() {
+            info!(
+                "Resending {} metric batches left in {}",
+                batches.len(),
+                spill.path().display()
+            );
+        }
+
+        let unsent = send_in_order(batches, &mut send);
+        if let Err(e) = spill.write(&unsent) {
+            error!("Failed to spill metrics to {}: {e}", spill.path().display());
+        }
+        lock.release(&spill);
+
+        if !unsent.is_empty() {
+            break;
+        }
+    }
+}
+
+struct MetricWorker {
+    target: MetricTarget,
+    experiment_uid: String,
+    metrics: Vec<Metric>,
+    parameters: Vec<Parameter>,
+    spill: SpillFile,
+    spill_lock: Option<SpillLock>,
+}
+
+impl MetricWorker {
+    /// Sends previously spilled batches followed by the buffered metrics and parameters.
+    /// Offline experiments append the buffered batches to the run directory
+    fn flush(&mut self) {
+        match &self.target {
+            MetricTarget::Registry(registry) => {
+                let registry = registry.clone();
+                self.send_batches(|batch| send_batch(&registry, batch));
+            }
+            MetricTarget::Offline(file) => {
+                let file = file.clone();
+                let batches = self.take_batches();
+                if batches.is_empty() {
+                    return;
+                }
+
+                if let Err(e) = file.append(&batches) {
+                    error!("Failed to write metrics to {}: {e}", file.path().display());
+                }
+            }
+        }
+    }
+
+    /// Sends spilled and buffered batches in order with `send`, see [`send_in_order`]
+    fn send_batches(&mut self, send: impl FnMut(&SpilledBatch) -> Result<(), RegistryError>) {
+        let mut batches = match self.spill.read() {
+            Ok(batches) => batches,
+            Err(e) => {
+                error!("Failed to read spilled metrics: {e}");
+                return;
+            }
+        };
+        batches.extend(self.take_batches());
+
+        if batches.is_empty() {
+            return;
+        }
+
+        let unsent = send_in_order(batches, send);
+        if let Err(e) = self.spill.write(&unsent) {
+            error!(
+                "Failed to spill metrics to {}: {e}",
+                self.spill.path().display()
+            );
+        }
+    }
+
+    /// Flushes a last time and tells the user where unsent batches are kept
+    fn stop(&mut self) {
+        self.flush();
+
+        if let MetricTarget::Registry(_) = self.target {
+            match self.spill.read() {
+                Ok(batches) if !batches.is_empty() => warn!(
+                    "{} metric batches of experiment {} could not be sent and are kept in {}. They are resent when the next experiment starts",
+                    batches.len(),
+                    self.experiment_uid,
+                    self.spill.path().display()
+                ),
+                Ok(_) => {}
+                Err(e) => error!("Failed to read spilled metrics: {e}"),
+            }
+        }
+
+        if let Some(lock) = self.spill_lock.take() {
+            lock.release(&self.spill);
+        }
+    }
+
+    /// Takes the buffered parameters and metrics, parameters first
+    fn take_batches(&mut self) -> Vec<SpilledBatch> {
+        let mut batches = Vec::new();
+
+        if !self.parameters.is_empty() {
+            batches.push(SpilledBatch::Parameters(ParameterRequest {
+                experiment_uid: self.experiment_uid.clone(),
+                parameters: std::mem::take(&mut self.parameters),
+            }));
+        }
+
+        if !self.metrics.is_empty() {
+            batches.push(SpilledBatch::Metrics(MetricRequest {
+                experiment_uid: self.experiment_uid.clone(),
+                metrics: std::mem::take(&mut self.metrics),
+            }));
+        }
+
+        batches
+    }
+}
+
+/// Buffers metrics and parameters of an experiment and sends them in batches from a background
+/// thread. A thread is used instead of a runtime task because registry requests are blocking.
+///
+/// Batches that fail to send are written to a spill file and resent on the next flush.
+/// Spill files left behind by stopped experiments are resent when a queue starts.
+/// For offline experiments every batch is appended to the run directory instead.
+pub struct MetricQueue {
+    tx: Option<Sender<QueueMessage>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl MetricQueue {
+    pub fn start(
+        target: MetricTarget,
+        experiment_uid: String,
+        spill_dir: &Path,
+    ) -> Result<Self, ExperimentError> {
+        let (tx, rx) = mpsc::channel();
+        let spill = SpillFile::new(spill_dir, &experiment_uid);
+
+        let spill_lock = match target {
+            MetricTarget::Registry(_) => spill.try_lock()?,
+            MetricTarget::Offline(_) => None,
+        };
+
+        let mut worker = MetricWorker {
+            target,
+            spill,
+            spill_lock,
+            experiment_uid,
+            metrics: Vec::new(),
+            parameters: Vec::new(),
+        };
+        let spill_dir = spill_dir.to_path_buf();
+
+        let handle = std::thread::Builder::new()
+            .name("opsml-metric-queue".to_string())
+            .spawn(move || {
+                if let MetricTarget::Registry(registry) = &worker.target {
+                    resend_leftover_spills(&spill_dir, &worker.experiment_uid, |batch| {
+                        send_batch(registry, batch)
+                    });
+                }
+
+                let mut last_flush = Instant::now();
+
+                loop {
+                    let timeout = METRIC_FLUSH_INTERVAL.saturating_sub(last_flush.elapsed());
+
+                    match rx.recv_timeout(timeout) {
+                        Ok(QueueMessage::Metrics(metrics)) => {
+                            worker.metrics.extend(metrics);
+                            if worker.metrics.len() < METRIC_BATCH_SIZE {
+                                continue;
+                            }
+                        }
+                        Ok(QueueMessage::Parameters(parameters)) => {
+                            worker.parameters.extend(parameters);
+                            continue;
+                        }
+                        Ok(QueueMessage::Flush(done)) => {
+                            worker.flush();
+                            last_flush = Instant::now();
+                            let _ = done.send(());
+                            continue;
+                        }
+                        Err(RecvTimeoutError::Timeout) => {}
+                        Err(RecvTimeoutError::Disconnected) => {
+                            debug!("Stopping metric queue");
+                            worker.stop();
+                            break;
+                        }
+                    }
+
+                    worker.flush();
+                    last_flush = Instant::now();
+                }
+            })?;
+
+        Ok(Self {
+            tx: Some(tx),
+            handle: Some(handle),
+        })
+    }
+
+    fn enqueue(&self, message: QueueMessage) -> Result<(), ExperimentError> {
+        self.tx
+            .as_ref()
+            .and_then(|tx| tx.send(message).ok())
+            .ok_or(ExperimentError::MetricQueueStopped)
+    }
+
+    pub fn log_metrics(&self, metrics: Vec<Metric>) -> Result<(), ExperimentError> {
+        self.enqueue(QueueMessage::Metrics(metrics))
+    }
+
+    pub fn log_parameters(&self, parameters: Vec<Parameter>) -> Result<(), ExperimentError> {
+        self.enqueue(QueueMessage::Parameters(parameters))
+    }
+
+    /// Sends everything logged so far and waits until the send completes or spills
+    pub fn flush(&self) -> Result<(), ExperimentError> {
+        let (done_tx, done_rx) = mpsc::channel();
+        self.enqueue(QueueMessage::Flush(done_tx))?;
+        done_rx
+            .recv()
+            .map_err(|_| ExperimentError::MetricQueueStopped)
+    }
+
+    /// Sends everything logged so far and stops the background thread
+    pub fn stop(&mut self) {
+        // dropping the sender disconnects the channel, which flushes and ends the thread
+        self.tx.take();
+
+        if let Some(handle) = self.handle.take() {
+            if handle.join().is_err() {
+                error!("Metric queue thread panicked");
+            }
+        }
+    }
+}
+
+impl Drop for MetricQueue {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opsml_client::error::{ApiClientError, RegistryError as ApiRegistryError};
+    use tempfile::TempDir;
+
+    fn rejected(status: u16) -> RegistryError {
+        RegistryError::ApiRegistryError(ApiRegistryError::ApiClientError(
+            ApiClientError::RequestRejected(status, "rejected".to_string()),
+        ))
+    }
+
+    fn unavailable() -> RegistryError {
+        RegistryError::IoError(std::io::Error::other("connection refused"))
+    }
+
+    fn worker(dir: &Path) -> MetricWorker {
+        MetricWorker {
+            target: MetricTarget::Offline(SpillFile::from_path(dir.join("offline.jsonl"))),
+            experiment_uid: "experiment".to_string(),
+            metrics: Vec::new(),
+            parameters: Vec::new(),
+            spill: SpillFile::new(dir, "experiment"),
+            spill_lock: None,
+        }
+    }
+
+    fn log(worker: &mut MetricWorker, step: i32) {
+        worker.metrics.push(Metric {
+            name: "loss".to_string(),
+            value: f64::from(step),
+            step: Some(step),
+            timestamp: None,
+            created_at: None,
+        });
+    }
+
+    fn steps(batch: &SpilledBatch) -> Vec<i32> {
+        match batch {
+            SpilledBatch::Metrics(request) => request
+                .metrics
+                .iter()
+                .filter_map(|metric| metric.step)
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_flush_spills_and_resends_in_order() {
+        let dir = TempDir::new().unwrap();
+        let mut worker = worker(dir.path());
+
+        // 1. A retryable failure spills the batch
+        log(&mut worker, 0);
+        worker.send_batches(|_| Err(rejected(429)));
+        let spilled = worker.spill.read().unwrap();
+        assert_eq!(spilled.iter().map(steps).collect::<Vec<_>>(), vec![vec![0]]);
+
+        // 2. While the server is unavailable, new batches are spilled after the older ones
+        log(&mut worker, 1);
+        worker.send_batches(|_| Err(unavailable()));
+        let spilled = worker.spill.read().unwrap();
+        assert_eq!(
+            spilled.iter().map(steps).collect::<Vec<_>>(),
+            vec![vec![0], vec![1]]
+        );
+
+        // 3. Spilled batches are resent first, in logging order
+        log(&mut worker, 2);
+        let mut sent = Vec::new();
+        worker.send_batches(|batch| {
+            sent.push(steps(batch));
+            Ok(())
+        });
+        assert_eq!(sent, vec![vec![0], vec![1], vec![2]]);
+        assert!(!worker.spill.path().exists());
+    }
+
+    #[test]
+    fn test_flush_stops_at_first_retryable_failure() {
+        let dir = TempDir::new().unwrap();
+        let mut worker = worker(dir.path());
+
+        log(&mut worker, 0);
+        worker.send_batches(|_| Err(unavailable()));
+        log(&mut worker, 1);
+        worker.send_batches(|_| Err(rejected(408)));
+        log(&mut worker, 2);
+
+        // batches after a failed batch are not sent, so the server never receives them out of order
+        let mut attempts = Vec::new();
+        worker.send_batches(|batch| {
+            attempts.push(steps(batch));
+            match steps(batch).as_slice() {
+                [1] => Err(rejected(401)),
+                _ => Ok(()),
+            }
+        });
+        assert_eq!(attempts, vec![vec![0], vec![1]]);
+
+        let spilled = worker.spill.read().unwrap();
+        assert_eq!(
+            spilled.iter().map(steps).collect::<Vec<_>>(),
+            vec![vec![1], vec![2]]
+        );
+    }
+
+    #[test]
+    fn test_flush_drops_rejected_batches() {
+        let dir = TempDir::new().unwrap();
+        let mut worker = worker(dir.path());
+
+        log(&mut worker, 0);
+        worker.send_batches(|_| Err(rejected(429)));
+        log(&mut worker, 1);
+
+        // the spilled batch is rejected as invalid and dropped without blocking the next batch
+        let mut sent = Vec::new();
+        worker.send_batches(|batch| match steps(batch).as_slice() {
+            [0] => Err(rejected(422)),
+            steps => {
+                sent.push(steps.to_vec());
+                Ok(())
+            }
+        });
+        assert_eq!(sent, vec![vec![1]]);
+        assert!(worker.spill.read().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_resend_leftover_spills_after_restart() {
+        let dir = TempDir::new().unwrap();
+
+        // 1. Experiments stopped while the server was unavailable
+        for (uid, step) in [("stopped_a", 0), ("stopped_b", 1)] {
+            let mut worker = worker(dir.path());
+            worker.experiment_uid = uid.to_string();
+            worker.spill = SpillFile::new(dir.path(), uid);
+            log(&mut worker, step);
+            worker.send_batches(|_| Err(unavailable()));
+            worker.stop();
+            assert!(worker.spill.path().exists());
+        }
+
+        // a running experiment holds the lock of its spill file
+        let running = SpillFile::new(dir.path(), "running");
+        let mut worker = worker(dir.path());
+        log(&mut worker, 2);
+        worker.spill = running.clone();
+        worker.send_batches(|_| Err(unavailable()));
+        let _lock = running.try_lock().unwrap().unwrap();
+        assert!(running.try_lock().unwrap().is_none());
+
+        // 2. While the server is still unavailable, nothing is lost
+        resend_leftover_spills(dir.path(), "restarted", |_| Err(unavailable()));
+        assert!(SpillFile::new(dir.path(), "stopped_a").path().exists());
+        assert!(SpillFile::new(dir.path(), "stopped_b").path().exists());
+
+        // 3. A new experiment resends the batches of stopped experiments, except running ones
+        let mut sent = Vec::new();
+        resend_leftover_spills(dir.path(), "restarted", |batch| {
+            sent.push(steps(batch));
+            Ok(())
+        });
+        assert_eq!(sent, vec![vec![0], vec![1]]);
+
+        for uid in ["stopped_a", "stopped_b"] {
+            let spill = SpillFile::new(dir.path(), uid);
+            assert!(!spill.path().exists());
+            assert!(!spill.path().with_extension("lock").exists());
+        }
+        assert_eq!(running.read().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,568 @@
+use crate::error::ExperimentError;
+use crate::metric_queue::{send_batch, SpillFile, SpilledBatch};
+use base64::prelude::*;
+use chrono::{DateTime, Utc};
+use opsml_cards::ExperimentCard;
+use opsml_crypt::{encrypt_directory, encrypted_key, generate_key};
+use opsml_registry::base::OpsmlRegistry;
+use opsml_semver::{VersionArgs, VersionType, VersionValidator};
+use opsml_settings::config::OpsmlStorageSettings;
+use opsml_state::app_state;
+use opsml_storage::storage::filesystem::FileSystem;
+use opsml_storage::storage::local::client::LocalFSStorageClient;
+use opsml_storage::{add_card_manifest, record_artifacts, storage_client};
+use opsml_types::contracts::{
+    ArtifactKey, CardQueryArgs, GetHardwareMetricRequest, GetMetricRequest, GetParameterRequest,
+};
+use opsml_types::{RegistryType, SaveName, Suffix};
+use opsml_utils::{create_uuid7, get_utc_datetime, uid_to_byte_key};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+use tracing::{debug, instrument};
+
+/// Description of an offline run, written when the run starts
+pub const OFFLINE_RUN_FILE: &str = "run.json";
+
+/// Metric and parameter batches of an offline run, in logging order
+pub const OFFLINE_METRICS_FILE: &str = "metrics.jsonl";
+
+/// Hardware metrics of an offline run
+pub const OFFLINE_HARDWARE_FILE: &str = "hardware.jsonl";
+
+/// Progress of `opsml experiment sync` for a run
+pub const OFFLINE_SYNC_FILE: &str = "sync.json";
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct OfflineManifest {
+    pub uid: String,
+    pub space: String,
+    pub name: String,
+    /// base64 encoded artifact encryption key
+    pub encryption_key: String,
+    pub created_at: DateTime<Utc>,
+    pub opsml_version: String,
+}
+
+/// Tracks what has already been registered so an interrupted sync can be resumed
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct SyncState {
+    pub version: Option<String>,
+    pub artifacts_synced: bool,
+    pub metric_batches: usize,
+    pub hardware_batches: usize,
+    /// The next batch was being sent when the sync stopped, so the server may already have it
+    #[serde(default)]
+    pub batch_in_flight: bool,
+    pub synced: bool,
+}
+
+/// An experiment recorded without access to an OpsML server.
+///
+/// Every run is stored in its own directory, named after the experiment uid:
+///
+/// ```text
+/// <offline_dir>/<uid>/
+///     run.json        # uid and artifact encryption key
+///     card.json       # ExperimentCard
+///     metrics.jsonl   # metric and parameter batches
+///     hardware.jsonl  # hardware metrics
+///     artifacts/      # encrypted artifacts, using the local storage layout
+/// ```
+pub struct OfflineRun {
+    dir: PathBuf,
+    storage: LocalFSStorageClient,
+}
+
+impl OfflineRun {
+    /// Creates the run directory for a new experiment. A uid and an artifact encryption key are
+    /// generated locally and kept when the run is synced
+    #[instrument(skip_all)]
+    pub fn create(
+        offline_dir: &Path,
+        card: &mut ExperimentCard,
+    ) -> Result<(Self, ArtifactKey), ExperimentError> {
+        let uid = create_uuid7();
+        let encryption_key = generate_key()?;
+
+        let dir = offline_dir.join(&uid);
+        std::fs::create_dir_all(&dir)?;
+
+        let manifest = OfflineManifest {
+            uid: uid.clone(),
+            space: card.space.clone(),
+            name: card.name.clone(),
+            encryption_key: BASE64_STANDARD.encode(encryption_key),
+            created_at: get_utc_datetime(),
+            opsml_version: card.opsml_version.clone(),
+        };
+        std::fs::write(
+            dir.join(OFFLINE_RUN_FILE),
+            serde_json::to_string_pretty(&manifest)?,
+        )?;
+
+        // artifacts are stored under the run directory, mirroring the registry storage path
+        let artifact_key = ArtifactKey {
+            uid: uid.clone(),
+            space: card.space.clone(),
+            registry_type: RegistryType::Experiment,
+            encrypted_key: encrypted_key(&uid_to_byte_key(&uid)?, &encryption_key)?,
+            storage_key: String::new(),
+            has_manifest: true,
+        };
+
+        card.uid = uid;
+        card.set_artifact_key(artifact_key.clone());
+
+        let run = Self::open(dir)?;
+        run.save_card(card)?;
+
+        debug!("Created offline run at {}", run.dir.display());
+
+        Ok((run, artifact_key))
+    }
+
+    fn open(dir: PathBuf) -> Result<Self, ExperimentError> {
+        let settings = OpsmlStorageSettings::new(&dir.to_string_lossy());
+        let storage = app_state().block_on(LocalFSStorageClient::new(&settings));
+
+        Ok(Self { dir, storage })
+    }
+
+    /// Directory holding the runs of all offline experiments
+    pub fn offline_dir(&self) -> &Path {
+        self.dir.parent().unwrap_or(&self.dir)
+    }
+
+    pub fn metrics_file(&self) -> SpillFile {
+        SpillFile::from_path(self.dir.join(OFFLINE_METRICS_FILE))
+    }
+
+    pub fn hardware_file(&self) -> SpillFile {
+        SpillFile::from_path(self.dir.join(OFFLINE_HARDWARE_FILE))
+    }
+
+    pub fn save_card(&self, card: &mut ExperimentCard) -> Result<(), ExperimentError> {
+        Ok(card.save(self.dir.clone())?)
+    }
+
+    /// Stores artifacts in the run directory. `rpath` is relative to the run directory
+    pub fn put(&self, lpath: &Path, rpath: &Path, recursive: bool) -> Result<(), ExperimentError> {
+        app_state().block_on(self.storage.put(lpath, rpath, recursive))?;
+        Ok(())
+    }
+}
+
+/// Result of syncing an offline run
+#[derive(Debug)]
+pub struct SyncedExperiment {
+    pub uid: String,
+    pub space: String,
+    pub name: String,
+    pub version: String,
+    /// The run was synced by an earlier call
+    pub skipped: bool,
+}
+
+fn read_json<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<T, ExperimentError> {
+    Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+}
+
+fn write_state(dir: &Path, state: &SyncState) -> Result<(), ExperimentError> {
+    let path = dir.join(OFFLINE_SYNC_FILE);
+    let tmp_path = path.with_extension("json.tmp");
+    std::fs::write(&tmp_path, serde_json::to_string_pretty(state)?)?;
+    std::fs::rename(&tmp_path, &path)?;
+    Ok(())
+}
+
+/// Next minor version of an experiment, following the versioning used when registering
+pub(crate) fn next_version(
+    registry: &OpsmlRegistry,
+    space: &str,
+    name: &str,
+) -> Result<String, ExperimentError> {
+    // cards are listed from the highest version down
+    let latest = registry
+        .list_cards(CardQueryArgs {
+            space: Some(space.to_string()),
+            name: Some(name.to_string()),
+            limit: Some(1),
+            registry_type: RegistryType::Experiment,
+            ..Default::default()
+        })?
+        .first()
+        .map(|card| card.version().to_string());
+
+    match latest {
+        Some(version) => Ok(VersionValidator::bump_version(&VersionArgs {
+            version,
+            version_type: VersionType::Minor,
+            pre: None,
+            build: None,
+        })?
+        .to_string()),
+        None => Ok("0.1.0".to_string()),
+    }
+}
+
+/// Registers the card of a run, or looks it up when an earlier sync already registered it.
+/// The card version is set to the registered version
+fn register_run(
+    registry: &OpsmlRegistry,
+    manifest: &OfflineManifest,
+    card: &mut ExperimentCard,
+) -> Result<ArtifactKey, ExperimentError> {
+    if registry.check_card_uid(&manifest.uid)? {
+        let version = registry
+            .list_cards(CardQueryArgs {
+                uid: Some(manifest.uid.clone()),
+                registry_type: RegistryType::Experiment,
+                ..Default::default()
+            })?
+            .first()
+            .map(|card| card.version().to_string())
+            .ok_or(ExperimentError::OfflineRunNotRegistered(
+                manifest.uid.clone(),
+            ))?;
+
+        card.version = version;
+        return Ok(registry.get_artifact_key(&manifest.uid, &RegistryType::Experiment)?);
+    }
+
+    card.version = next_version(registry, &card.space, &card.name)?;
+
+    let encryption_key = BASE64_STANDARD.decode(&manifest.encryption_key)?;
+    let response = registry.import_card(card.get_registry_card()?, encryption_key, true)?;
+
+    Ok(response.key)
+}
+
+/// Whether the server already stored a batch. A batch is inserted in a single statement, so it
+/// is stored either whole or not at all. Metrics are matched on name, step and timestamp,
+/// parameters on name and hardware metrics on their creation time
+fn batch_was_sent(registry: &OpsmlRegistry, batch: &SpilledBatch) -> Result<bool, ExperimentError> {
+    let sent = match batch {
+        SpilledBatch::Metrics(request) => {
+            let names = request
+                .metrics
+                .iter()
+                .map(|metric| metric.name.clone())
+                .collect::<BTreeSet<_>>();
+            let stored = registry
+                .get_metrics(&GetMetricRequest::new(
+                    request.experiment_uid.clone(),
+                    Some(names.into_iter().collect()),
+                ))?
+                .into_iter()
+                .map(|metric| (metric.name, metric.step, metric.timestamp))
+                .collect::<HashSet<_>>();
+
+            request.metrics.iter().all(|metric| {
+                stored.contains(&(metric.name.clone(), metric.step, metric.timestamp))
+            })
+        }
+        SpilledBatch::Parameters(request) => {
+            let names = request
+                .parameters
+                .iter()
+                .map(|parameter| parameter.name.clone())
+                .collect::<BTreeSet<_>>();
+            let stored = registry
+                .get_parameters(&GetParameterRequest::new(
+                    request.experiment_uid.clone(),
+                    Some(names.iter().cloned().collect()),
+                ))?
+                .into_iter()
+                .map(|parameter| parameter.name)
+                .collect::<BTreeSet<_>>();
+
+            names.is_subset(&stored)
+        }
+        SpilledBatch::HardwareMetrics(request) => registry
+            .get_hardware_metrics(&GetHardwareMetricRequest {
+                experiment_uid: request.experiment_uid.clone(),
+            })?
+            .iter()
+            .any(|metrics| {
+                metrics.created_at.timestamp_micros()
+                    == request.metrics.created_at.timestamp_micros()
+            }),
+    };
+
+    Ok(sent)
+}
+
+/// Sends the batches of a file that have not been sent yet.
+///
+/// A batch is marked in flight before it is sent and counted once the server accepted it. When a
+/// sync stops in between, the in-flight batch is only resent if the server does not have it, so
+/// resuming does not insert its metrics twice
+fn replay_batches(
+    registry: &OpsmlRegistry,
+    dir: &Path,
+    file: SpillFile,
+    state: &mut SyncState,
+    hardware: bool,
+) -> Result<(), ExperimentError> {
+    let batches = file.read()?;
+    let sent = match hardware {
+        true => state.hardware_batches,
+        false => state.metric_batches,
+    };
+
+    for batch in batches.iter().skip(sent) {
+        if !(state.batch_in_flight && batch_was_sent(registry, batch)?) {
+            state.batch_in_flight = true;
+            write_state(dir, state)?;
+            send_batch(registry, batch)?;
+        }
+
+        match hardware {
+            true => state.hardware_batches += 1,
+            false => state.metric_batches += 1,
+        }
+        state.batch_in_flight = false;
+        write_state(dir, state)?;
+    }
+
+    Ok(())
+}
+
+/// Registers an offline run against the server
+#[instrument(skip_all)]
+fn sync_run(registry: &OpsmlRegistry, dir: &Path) -> Result<SyncedExperiment, ExperimentError> {
+    let manifest: OfflineManifest = read_json(&dir.join(OFFLINE_RUN_FILE))?;
+    let sync_path = dir.join(OFFLINE_SYNC_FILE);
+    let mut state: SyncState = match sync_path.exists() {
+        true => read_json(&sync_path)?,
+        false => SyncState::default(),
+    };
+
+    let card_path = dir.join(SaveName::Card).with_extension(Suffix::Json);
+    let mut card = ExperimentCard::model_validate_json(std::fs::read_to_string(card_path)?)?;
+
+    if state.synced {
+        return Ok(SyncedExperiment {
+            uid: manifest.uid,
+            space: card.space,
+            name: card.name,
+            version: state.version.unwrap_or_default(),
+            skipped: true,
+        });
+    }
+
+    let key = register_run(registry, &manifest, &mut card)?;
+    state.version = Some(card.version.clone());
+    write_state(dir, &state)?;
+
+    // the card is saved with its registered version, encrypted like any registered card
+    let encryption_key = key.get_decrypt_key()?;
+    let tmp_dir = TempDir::new()?;
+    card.save(tmp_dir.path().to_path_buf())?;
+    encrypt_directory(tmp_dir.path(), &encryption_key)?;
+    add_card_manifest(&key, tmp_dir.path())?;
+    storage_client()?.put(tmp_dir.path(), &key.storage_path(), true)?;
+
+    // artifacts were encrypted with the run key when they were logged
+    let artifacts_dir = dir.join(SaveName::Artifacts);
+    if !state.artifacts_synced && artifacts_dir.exists() {
+        let rpath = key.storage_path().join(SaveName::Artifacts);
+        storage_client()?.put(&artifacts_dir, &rpath, true)?;
+        record_artifacts(&key, &artifacts_dir, &rpath)?;
+    }
+    state.artifacts_synced = true;
+    write_state(dir, &state)?;
+
+    let metrics_file = SpillFile::from_path(dir.join(OFFLINE_METRICS_FILE));
+    replay_batches(registry, dir, metrics_file, &mut state, false)?;
+
+    let hardware_file = SpillFile::from_path(dir.join(OFFLINE_HARDWARE_FILE));
+    replay_batches(registry, dir, hardware_file, &mut state, true)?;
+
+    state.synced = true;
+    write_state(dir, &state)?;
+
+    Ok(SyncedExperiment {
+        uid: manifest.uid,
+        space: card.space,
+        name: card.name,
+        version: card.version,
+        skipped: false,
+    })
+}
+
+/// Registers offline experiments against the server. Cards keep their uid and creation time,
+/// and metrics are sent in the order they were logged.
+///
+/// `path` is either a single run directory or the offline directory holding several runs.
+/// Runs are synced in creation order and runs that were already synced are skipped, so an
+/// interrupted sync can be re-run.
+pub fn sync_offline_experiments(path: &Path) -> Result<Vec<SyncedExperiment>, ExperimentError> {
+    let mut run_dirs = match path.join(OFFLINE_RUN_FILE).exists() {
+        true => vec![path.to_path_buf()],
+        false => std::fs::read_dir(path)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|dir| dir.join(OFFLINE_RUN_FILE).exists())
+            .collect(),
+    };
+
+    if run_dirs.is_empty() {
+        return Err(ExperimentError::NoOfflineRuns(path.display().to_string()));
+    }
+
+    // run directories are named after uuid7 uids, which sort by creation time
+    run_dirs.sort();
+
+    let registry = OpsmlRegistry::new(RegistryType::Experiment)?;
+
+    run_dirs
+        .iter()
+        .map(|dir| sync_run(&registry, dir))
+        .collect()
+}
+
+#[cfg(all(test, feature = "server"))]
+mod tests {
+    use super::*;
+    use opsml_types::cards::experiment::Metric;
+    use opsml_types::contracts::MetricRequest;
+    use pyo3::Python;
+
+    fn metric_batch(uid: &str, steps: std::ops::Range<i32>) -> SpilledBatch {
+        SpilledBatch::Metrics(MetricRequest {
+            experiment_uid: uid.to_string(),
+            metrics: steps
+                .map(|step| Metric {
+                    name: "loss".to_string(),
+                    value: f64::from(step),
+                    step: Some(step),
+                    timestamp: None,
+                    created_at: DateTime::from_timestamp(1_700_000_000 + i64::from(step), 0),
+                })
+                .collect(),
+        })
+    }
+
+    #[test]
+    fn test_sync_resumes_after_interruption() {
+        let dir = TempDir::new().unwrap();
+        std::env::set_var(
+            "OPSML_TRACKING_URI",
+            format!("sqlite://{}/opsml.db", dir.path().display()),
+        );
+        std::env::set_var("OPSML_STORAGE_URI", dir.path().join("opsml_registries"));
+
+        pyo3::prepare_freethreaded_python();
+        let mut card = Python::with_gil(|py| {
+            ExperimentCard::new(py, Some("offline"), Some("sync"), None, None, None)
+        })
+        .unwrap();
+
+        // 1. Record an offline run
+        let offline_dir = dir.path().join("offline");
+        let (run, _) = OfflineRun::create(&offline_dir, &mut card).unwrap();
+        run.metrics_file()
+            .append(&[metric_batch(&card.uid, 0..3), metric_batch(&card.uid, 3..6)])
+            .unwrap();
+
+        // 2. The first sync stops right after the card is registered, before its progress is saved
+        let run_dir = offline_dir.join(&card.uid);
+        let manifest: OfflineManifest = read_json(&run_dir.join(OFFLINE_RUN_FILE)).unwrap();
+        let registry = OpsmlRegistry::new(RegistryType::Experiment).unwrap();
+        let card_path = run_dir.join(SaveName::Card).with_extension(Suffix::Json);
+        let mut registered =
+            ExperimentCard::model_validate_json(std::fs::read_to_string(card_path).unwrap())
+                .unwrap();
+        register_run(&registry, &manifest, &mut registered).unwrap();
+        assert_eq!(registered.version, "0.1.0");
+        assert!(!run_dir.join(OFFLINE_SYNC_FILE).exists());
+
+        // 3. The re-run keeps the registered card instead of registering a new version
+        let synced = sync_offline_experiments(&offline_dir).unwrap();
+        assert_eq!(synced.len(), 1);
+        assert_eq!(synced[0].uid, card.uid);
+        assert_eq!(synced[0].version, "0.1.0");
+        assert!(!synced[0].skipped);
+
+        let cards = registry
+            .list_cards(CardQueryArgs {
+                space: Some(card.space.clone()),
+                name: Some(card.name.clone()),
+                registry_type: RegistryType::Experiment,
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(cards.len(), 1);
+        assert_eq!(cards[0].version(), "0.1.0");
+        // sqlite stores card timestamps with second precision
+        assert_eq!(
+            cards[0].created_at().timestamp(),
+            card.created_at.timestamp()
+        );
+
+        let metrics = registry
+            .get_metrics(&GetMetricRequest::new(card.uid.clone(), None))
+            .unwrap();
+        let steps: Vec<Option<i32>> = metrics.iter().map(|metric| metric.step).collect();
+        assert_eq!(steps, (0..6).map(Some).collect::<Vec<_>>());
+        assert_eq!(
+            metrics[5].created_at,
+            DateTime::from_timestamp(1_700_000_005, 0)
+        );
+
+        // 4. Syncing again skips the run without sending its metrics twice
+        let synced = sync_offline_experiments(&offline_dir).unwrap();
+        assert!(synced[0].skipped);
+        let metrics = registry
+            .get_metrics(&GetMetricRequest::new(card.uid.clone(), None))
+            .unwrap();
+        assert_eq!(metrics.len(), 6);
+
+        // 5. A sync stopped while a batch was in flight resends it only when the server does not have it
+        for stored in [true, false] {
+            let mut card = Python::with_gil(|py| {
+                ExperimentCard::new(py, Some("offline"), Some("replay"), None, None, None)
+            })
+            .unwrap();
+            let (run, _) = OfflineRun::create(&offline_dir, &mut card).unwrap();
+            let batches = [
+                metric_batch(&card.uid, 0..3),
+                metric_batch(&card.uid, 3..6),
+                metric_batch(&card.uid, 6..9),
+            ];
+            run.metrics_file().append(&batches).unwrap();
+
+            let run_dir = offline_dir.join(&card.uid);
+            let manifest: OfflineManifest = read_json(&run_dir.join(OFFLINE_RUN_FILE)).unwrap();
+            register_run(&registry, &manifest, &mut card).unwrap();
+
+            // the first batch was counted and the second was in flight when the sync stopped
+            send_batch(&registry, &batches[0]).unwrap();
+            if stored {
+                send_batch(&registry, &batches[1]).unwrap();
+            }
+            let state = SyncState {
+                version: Some(card.version.clone()),
+                metric_batches: 1,
+                batch_in_flight: true,
+                ..Default::default()
+            };
+            write_state(&run_dir, &state).unwrap();
+
+            sync_offline_experiments(&run_dir).unwrap();
+
+            let metrics = registry
+                .get_metrics(&GetMetricRequest::new(card.uid.clone(), None))
+                .unwrap();
+            let steps: Vec<Option<i32>> = metrics.iter().map(|metric| metric.step).collect();
+            assert_eq!(steps, (0..9).map(Some).collect::<Vec<_>>());
+
+            let state: SyncState = read_json(&run_dir.join(OFFLINE_SYNC_FILE)).unwrap();
+            assert_eq!(state.metric_batches, 3);
+            assert!(!state.batch_in_flight);
+            assert!(state.synced);
+        }
+    }
+}
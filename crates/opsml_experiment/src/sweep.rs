@@ -0,0 +1,354 @@
+use crate::error::ExperimentError;
+use opsml_types::cards::experiment::{
+    Parameter, ParameterValue, SearchDimension, Sweep, SweepStrategy, SweepTrial,
+};
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use tracing::{debug, warn};
+
+/// Number of trials of random and successive halving sweeps when `num_trials` is not set
+pub const DEFAULT_SWEEP_TRIALS: usize = 10;
+
+/// A parameter taking one of a fixed set of values
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct Choice {
+    pub values: Vec<ParameterValue>,
+}
+
+#[pymethods]
+impl Choice {
+    #[new]
+    pub fn new(values: Vec<Bound<'_, PyAny>>) -> Result<Self, ExperimentError> {
+        let values = values
+            .into_iter()
+            .map(ParameterValue::from_any)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { values })
+    }
+}
+
+/// A float parameter sampled between `low` and `high`
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct Uniform {
+    #[pyo3(get)]
+    pub low: f64,
+
+    #[pyo3(get)]
+    pub high: f64,
+
+    #[pyo3(get)]
+    pub log: bool,
+}
+
+#[pymethods]
+impl Uniform {
+    #[new]
+    #[pyo3(signature = (low, high, log = false))]
+    pub fn new(low: f64, high: f64, log: bool) -> Result<Self, ExperimentError> {
+        if low >= high {
+            return Err(ExperimentError::InvalidSweep(format!(
+                "Uniform low ({low}) must be lower than high ({high})"
+            )));
+        }
+
+        if log && low <= 0.0 {
+            return Err(ExperimentError::InvalidSweep(format!(
+                "Uniform low ({low}) must be positive when sampling on a log scale"
+            )));
+        }
+
+        Ok(Self { low, high, log })
+    }
+}
+
+/// An integer parameter sampled between `low` and `high`, both inclusive
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct IntUniform {
+    #[pyo3(get)]
+    pub low: i64,
+
+    #[pyo3(get)]
+    pub high: i64,
+}
+
+#[pymethods]
+impl IntUniform {
+    #[new]
+    pub fn new(low: i64, high: i64) -> Result<Self, ExperimentError> {
+        if low > high {
+            return Err(ExperimentError::InvalidSweep(format!(
+                "IntUniform low ({low}) must not be higher than high ({high})"
+            )));
+        }
+
+        Ok(Self { low, high })
+    }
+}
+
+/// Converts a python search space. Values are either a list of choices or one of
+/// `Choice`, `Uniform` and `IntUniform`
+pub fn extract_search_space(
+    search_space: &Bound<'_, PyDict>,
+) -> Result<BTreeMap<String, SearchDimension>, ExperimentError> {
+    let mut dimensions = BTreeMap::new();
+
+    for (key, value) in search_space.iter() {
+        let name = key.extract::<String>()?;
+
+        let dimension = if let Ok(choice) = value.extract::<Choice>() {
+            SearchDimension::Choice {
+                values: choice.values,
+            }
+        } else if let Ok(values) = value.downcast::<PyList>() {
+            SearchDimension::Choice {
+                values: Choice::new(values.iter().collect())?.values,
+            }
+        } else if let Ok(uniform) = value.extract::<Uniform>() {
+            SearchDimension::Uniform {
+                low: uniform.low,
+                high: uniform.high,
+                log: uniform.log,
+            }
+        } else if let Ok(uniform) = value.extract::<IntUniform>() {
+            SearchDimension::IntUniform {
+                low: uniform.low,
+                high: uniform.high,
+            }
+        } else {
+            return Err(ExperimentError::InvalidSweep(format!(
+                "Unsupported search space value for {name}. Use a list, Choice, Uniform or IntUniform"
+            )));
+        };
+
+        if matches!(&dimension, SearchDimension::Choice { values } if values.is_empty()) {
+            return Err(ExperimentError::InvalidSweep(format!(
+                "No values to choose from for {name}"
+            )));
+        }
+
+        dimensions.insert(name, dimension);
+    }
+
+    if dimensions.is_empty() {
+        return Err(ExperimentError::InvalidSweep(
+            "Search space is empty".to_string(),
+        ));
+    }
+
+    Ok(dimensions)
+}
+
+/// Every combination of the choice values, in search space order
+fn grid_configs(
+    search_space: &BTreeMap<String, SearchDimension>,
+) -> Result<Vec<Vec<Parameter>>, ExperimentError> {
+    let mut configs: Vec<Vec<Parameter>> = vec![Vec::new()];
+
+    for (name, dimension) in search_space {
+        let SearchDimension::Choice { values } = dimension else {
+            return Err(ExperimentError::InvalidSweep(format!(
+                "Grid sweeps only accept choices, {name} is a range"
+            )));
+        };
+
+        configs = configs
+            .into_iter()
+            .flat_map(|config| {
+                values.iter().map(move |value| {
+                    let mut config = config.clone();
+                    config.push(Parameter {
+                        name: name.clone(),
+                        value: value.clone(),
+                    });
+                    config
+                })
+            })
+            .collect();
+    }
+
+    Ok(configs)
+}
+
+fn sample_config(
+    search_space: &BTreeMap<String, SearchDimension>,
+    rng: &mut StdRng,
+) -> Vec<Parameter> {
+    search_space
+        .iter()
+        .map(|(name, dimension)| {
+            let value = match dimension {
+                SearchDimension::Choice { values } => {
+                    values[rng.random_range(0..values.len())].clone()
+                }
+                SearchDimension::Uniform { low, high, log } => match log {
+                    true => ParameterValue::Float(rng.random_range(low.ln()..=high.ln()).exp()),
+                    false => ParameterValue::Float(rng.random_range(*low..=*high)),
+                },
+                SearchDimension::IntUniform { low, high } => {
+                    ParameterValue::Int(rng.random_range(*low..=*high))
+                }
+            };
+
+            Parameter {
+                name: name.clone(),
+                value,
+            }
+        })
+        .collect()
+}
+
+/// Orders trials best first. Trials without a value come last
+fn compare_trials(a: &SweepTrial, b: &SweepTrial, maximize: bool) -> Ordering {
+    match (a.value, b.value) {
+        (Some(a), Some(b)) => {
+            let ordering = a.partial_cmp(&b).unwrap_or(Ordering::Equal);
+            match maximize {
+                true => ordering.reverse(),
+                false => ordering,
+            }
+        }
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+fn best_trial(trials: &[SweepTrial], maximize: bool) -> Option<&SweepTrial> {
+    trials
+        .iter()
+        .filter(|trial| trial.value.is_some())
+        .min_by(|a, b| compare_trials(a, b, maximize))
+}
+
+/// Settings of a sweep
+#[derive(Debug, Clone)]
+pub struct SweepConfig {
+    pub strategy: SweepStrategy,
+    pub metric: String,
+    pub maximize: bool,
+
+    /// Number of sampled configurations. Limits the number of grid combinations when set
+    pub num_trials: Option<usize>,
+    pub seed: Option<u64>,
+
+    /// Budget of the first successive halving round
+    pub min_budget: i64,
+
+    /// Successive halving stops before a round would exceed this budget
+    pub max_budget: Option<i64>,
+
+    /// Successive halving keeps 1 / `reduction_factor` of the configurations and multiplies
+    /// the budget by `reduction_factor` after each round
+    pub reduction_factor: usize,
+}
+
+/// Runs a sweep. `run_trial` runs a single configuration, with a budget for successive halving,
+/// and returns the finished trial.
+///
+/// The best trial is picked from all trials, or from the last round of successive halving since
+/// earlier rounds ran with a smaller budget
+pub fn run_sweep<F>(
+    search_space: BTreeMap<String, SearchDimension>,
+    config: &SweepConfig,
+    mut run_trial: F,
+) -> Result<Sweep, ExperimentError>
+where
+    F: FnMut(&[Parameter], Option<i64>) -> Result<SweepTrial, ExperimentError>,
+{
+    let mut rng = match config.seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_os_rng(),
+    };
+    let num_trials = config.num_trials.unwrap_or(DEFAULT_SWEEP_TRIALS);
+
+    let mut trials = Vec::new();
+    let best = match config.strategy {
+        SweepStrategy::Grid => {
+            let mut configs = grid_configs(&search_space)?;
+            if let Some(num_trials) = config.num_trials {
+                configs.truncate(num_trials);
+            }
+
+            for parameters in &configs {
+                trials.push(run_trial(parameters, None)?);
+            }
+
+            best_trial(&trials, config.maximize).cloned()
+        }
+        SweepStrategy::Random => {
+            for _ in 0..num_trials {
+                let parameters = sample_config(&search_space, &mut rng);
+                trials.push(run_trial(&parameters, None)?);
+            }
+
+            best_trial(&trials, config.maximize).cloned()
+        }
+        SweepStrategy::SuccessiveHalving => {
+            if config.reduction_factor < 2 || config.min_budget < 1 {
+                return Err(ExperimentError::InvalidSweep(
+                    "Successive halving needs a reduction factor of at least 2 and a positive min budget"
+                        .to_string(),
+                ));
+            }
+
+            let mut configs: Vec<Vec<Parameter>> = (0..num_trials)
+                .map(|_| sample_config(&search_space, &mut rng))
+                .collect();
+            let mut budget = config.min_budget;
+
+            loop {
+                debug!(
+                    "Running {} sweep configurations with budget {budget}",
+                    configs.len()
+                );
+
+                let mut round = Vec::with_capacity(configs.len());
+                for parameters in &configs {
+                    round.push(run_trial(parameters, Some(budget))?);
+                }
+                trials.extend(round.iter().cloned());
+
+                let next_budget = budget.saturating_mul(config.reduction_factor as i64);
+                if configs.len() <= 1 || config.max_budget.is_some_and(|max| next_budget > max) {
+                    break best_trial(&round, config.maximize).cloned();
+                }
+
+                let mut ranked: Vec<usize> = (0..round.len()).collect();
+                ranked.sort_by(|a, b| compare_trials(&round[*a], &round[*b], config.maximize));
+
+                let keep = (configs.len() / config.reduction_factor).max(1);
+                configs = ranked
+                    .into_iter()
+                    .take(keep)
+                    .map(|index| configs[index].clone())
+                    .collect();
+                budget = next_budget;
+            }
+        }
+    };
+
+    if best.is_none() {
+        warn!(
+            "No sweep trial logged metric {}, a best trial could not be picked",
+            config.metric
+        );
+    }
+
+    Ok(Sweep {
+        search_space,
+        strategy: config.strategy.clone(),
+        metric: config.metric.clone(),
+        maximize: config.maximize,
+        best_uid: best.as_ref().map(|trial| trial.experiment_uid.clone()),
+        best_value: best.and_then(|trial| trial.value),
+        trials,
+    })
+}
@@ -4,20 +4,29 @@ use opsml_semver::VersionType;
 use opsml_settings::config::OpsmlMode;
 use opsml_settings::ScouterSettings;
 use opsml_state::{app_state, get_api_client};
+use opsml_storage::{load_artifact_manifest, load_manifest_signature};
 use opsml_types::contracts::{
-    CardQueryArgs, CardRecord, CreateCardResponse, GetMetricRequest, MetricRequest,
+    ArtifactIntegrityReport, AuditChainReport, AuditEventPage, AuditEventQueryArgs,
+    AuditExportRequest, CardLineage, CardLineageRequest, CardQueryArgs, CardRecord,
+    CardSearchQuery, CardSearchResult, CardStageRequest, CardStageResponse, CreateCardResponse,
+    CreateSigningKeyRequest, DeletedCard, DeletedCardQuery, ExperimentComparison,
+    ExperimentComparisonRequest, GetMetricRequest, ImportCardResponse, MetricPage, MetricQuery,
+    MetricRequest, RevokeSigningKeyRequest, SignaturePolicy, SignatureStatus, SigningKeyQuery,
+    SpaceSigningKey, VerifyArtifactsRequest, VersionAlias, VersionAliasHistory, VersionAliasQuery,
+    VersionAliasRequest,
 };
 use opsml_types::*;
 use opsml_types::{
     cards::{HardwareMetrics, Metric, Parameter},
     contracts::{
         ArtifactKey, DeleteCardRequest, GetHardwareMetricRequest, GetParameterRequest,
-        HardwareMetricRequest, ParameterRequest,
+        HardwareMetricRequest, ParameterRequest, UidRequest,
     },
 };
 use scouter_client::ScouterClient;
 use scouter_client::{ProfileRequest, ProfileStatusRequest};
-use tracing::{error, info, instrument};
+use std::io::Write;
+use tracing::{debug, error, info, instrument, warn};
 
 pub fn setup_scouter_client(
     settings: &ScouterSettings,
@@ -68,6 +77,10 @@ impl OpsmlRegistry {
                         error!("Failed to get storage settings: {e}");
                     })?;
 
+                    let key_provider = config.key_provider().inspect_err(|e| {
+                        error!("Failed to create key provider: {e}");
+                    })?;
+
                     let db_settings = config.database_settings.clone();
 
                     // check if scouter is enabled
@@ -78,6 +91,7 @@ impl OpsmlRegistry {
                         crate::server::registry::server_logic::ServerRegistry::new(
                             registry_type,
                             settings,
+                            key_provider,
                             db_settings,
                             scouter_client,
                         )
@@ -152,6 +166,27 @@ impl OpsmlRegistry {
         }
     }
 
+    /// Recreates a card exported from another registry, keeping its uid and version.
+    /// `has_manifest` records whether the artifacts of the card include a manifest
+    pub fn import_card(
+        &self,
+        card: CardRecord,
+        encryption_key: Vec<u8>,
+        has_manifest: bool,
+    ) -> Result<ImportCardResponse, RegistryError> {
+        match self {
+            Self::ClientRegistry(client_registry) => {
+                Ok(client_registry.import_card(card, encryption_key, has_manifest)?)
+            }
+            #[cfg(feature = "server")]
+            Self::ServerRegistry(server_registry) => app_state().block_on(async {
+                server_registry
+                    .import_card(card, &encryption_key, has_manifest)
+                    .await
+            }),
+        }
+    }
+
     pub fn get_artifact_key(
         &self,
         uid: &str,
@@ -182,6 +217,7 @@ impl OpsmlRegistry {
                         registry_type: key.registry_type,
                         encrypted_key: key.encrypted_key,
                         storage_key: key.storage_key,
+                        has_manifest: key.has_manifest,
                     })
                 })
             }
@@ -200,6 +236,180 @@ impl OpsmlRegistry {
         }
     }
 
+    pub fn restore_card(&self, restore_request: &UidRequest) -> Result<(), RegistryError> {
+        match self {
+            Self::ClientRegistry(client_registry) => {
+                Ok(client_registry.restore_card(restore_request)?)
+            }
+            #[cfg(feature = "server")]
+            Self::ServerRegistry(server_registry) => {
+                app_state().block_on(async { server_registry.restore_card(restore_request).await })
+            }
+        }
+    }
+
+    pub fn list_deleted_cards(
+        &self,
+        trash_query: &DeletedCardQuery,
+    ) -> Result<Vec<DeletedCard>, RegistryError> {
+        match self {
+            Self::ClientRegistry(client_registry) => {
+                Ok(client_registry.list_deleted_cards(trash_query)?)
+            }
+            #[cfg(feature = "server")]
+            Self::ServerRegistry(server_registry) => app_state()
+                .block_on(async { server_registry.list_deleted_cards(trash_query).await }),
+        }
+    }
+
+    /// Full-text search across card names, spaces, tags, README content and metadata
+    pub fn search_cards(
+        &self,
+        search_query: &CardSearchQuery,
+    ) -> Result<Vec<CardSearchResult>, RegistryError> {
+        match self {
+            Self::ClientRegistry(client_registry) => {
+                Ok(client_registry.search_cards(search_query)?)
+            }
+            #[cfg(feature = "server")]
+            Self::ServerRegistry(server_registry) => {
+                app_state().block_on(async { server_registry.search_cards(search_query).await })
+            }
+        }
+    }
+
+    pub fn get_card_lineage(
+        &self,
+        lineage_request: &CardLineageRequest,
+    ) -> Result<CardLineage, RegistryError> {
+        match self {
+            Self::ClientRegistry(client_registry) => {
+                Ok(client_registry.get_card_lineage(lineage_request)?)
+            }
+            #[cfg(feature = "server")]
+            Self::ServerRegistry(server_registry) => app_state()
+                .block_on(async { server_registry.get_card_lineage(lineage_request).await }),
+        }
+    }
+
+    /// Re-hashes the stored artifacts of a card and compares them with the card manifest
+    pub fn verify_card_artifacts(
+        &self,
+        verify_request: &VerifyArtifactsRequest,
+    ) -> Result<ArtifactIntegrityReport, RegistryError> {
+        match self {
+            Self::ClientRegistry(client_registry) => {
+                Ok(client_registry.verify_card_artifacts(verify_request)?)
+            }
+            #[cfg(feature = "server")]
+            Self::ServerRegistry(_) => {
+                let key =
+                    self.get_artifact_key(&verify_request.uid, &verify_request.registry_type)?;
+                Ok(opsml_storage::verify_card_artifacts(&key)?)
+            }
+        }
+    }
+
+    pub fn get_signing_keys(
+        &self,
+        query: &SigningKeyQuery,
+    ) -> Result<Vec<SpaceSigningKey>, RegistryError> {
+        match self {
+            Self::ClientRegistry(client_registry) => Ok(client_registry.get_signing_keys(query)?),
+            #[cfg(feature = "server")]
+            Self::ServerRegistry(server_registry) => {
+                app_state().block_on(async { server_registry.get_signing_keys(query).await })
+            }
+        }
+    }
+
+    pub fn create_signing_key(
+        &self,
+        request: &CreateSigningKeyRequest,
+    ) -> Result<SpaceSigningKey, RegistryError> {
+        match self {
+            Self::ClientRegistry(client_registry) => {
+                Ok(client_registry.create_signing_key(request)?)
+            }
+            #[cfg(feature = "server")]
+            Self::ServerRegistry(server_registry) => {
+                app_state().block_on(async { server_registry.create_signing_key(request).await })
+            }
+        }
+    }
+
+    pub fn revoke_signing_key(
+        &self,
+        request: &RevokeSigningKeyRequest,
+    ) -> Result<(), RegistryError> {
+        match self {
+            Self::ClientRegistry(client_registry) => {
+                Ok(client_registry.revoke_signing_key(request)?)
+            }
+            #[cfg(feature = "server")]
+            Self::ServerRegistry(server_registry) => {
+                app_state().block_on(async { server_registry.revoke_signing_key(request).await })
+            }
+        }
+    }
+
+    /// Checks the manifest signature of a card against the signing keys of the card space.
+    /// Keys are only fetched for signed cards
+    pub fn card_signature_status(
+        &self,
+        key: &ArtifactKey,
+    ) -> Result<SignatureStatus, RegistryError> {
+        let Some(signature) = load_manifest_signature(key)? else {
+            return Ok(SignatureStatus::Unsigned);
+        };
+
+        let Some(manifest) = load_artifact_manifest(key)? else {
+            return Ok(SignatureStatus::Invalid(
+                "the signed manifest is missing".to_string(),
+            ));
+        };
+
+        let keys = self.get_signing_keys(&SigningKeyQuery {
+            space: Some(key.space.clone()),
+        })?;
+
+        Ok(manifest.verify_signature(key, &signature, &keys)?)
+    }
+
+    /// Applies the configured signature policy to a card before it is loaded or downloaded
+    pub fn check_card_signature(&self, key: &ArtifactKey) -> Result<(), RegistryError> {
+        let policy = app_state()
+            .config()?
+            .signing_settings
+            .signature_policy
+            .clone();
+
+        if policy == SignaturePolicy::Off {
+            return Ok(());
+        }
+
+        match self.card_signature_status(key)? {
+            SignatureStatus::Valid(key_id) => {
+                debug!("Card {} is signed by key {key_id}", key.uid);
+            }
+            SignatureStatus::Unsigned if policy == SignaturePolicy::Require => {
+                return Err(RegistryError::UnsignedCardError(key.uid.clone()));
+            }
+            SignatureStatus::Unsigned => debug!("Card {} is not signed", key.uid),
+            status @ SignatureStatus::Invalid(_) => {
+                if policy == SignaturePolicy::Require {
+                    return Err(RegistryError::CardSignatureError(
+                        key.uid.clone(),
+                        status.to_string(),
+                    ));
+                }
+                warn!("Card {} has an {status}", key.uid);
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn update_card(&self, card: &CardRecord) -> Result<(), RegistryError> {
         match self {
             Self::ClientRegistry(client_registry) => Ok(client_registry.update_card(card)?),
@@ -210,6 +420,77 @@ impl OpsmlRegistry {
         }
     }
 
+    /// Moves a model or service card version to a new stage
+    pub fn update_card_stage(
+        &self,
+        stage_request: &CardStageRequest,
+    ) -> Result<CardStageResponse, RegistryError> {
+        match self {
+            Self::ClientRegistry(client_registry) => {
+                Ok(client_registry.update_card_stage(stage_request)?)
+            }
+            #[cfg(feature = "server")]
+            Self::ServerRegistry(server_registry) => app_state()
+                .block_on(async { server_registry.update_card_stage(stage_request).await }),
+        }
+    }
+
+    pub fn set_version_alias(
+        &self,
+        alias_request: &VersionAliasRequest,
+    ) -> Result<VersionAlias, RegistryError> {
+        match self {
+            Self::ClientRegistry(client_registry) => {
+                Ok(client_registry.set_version_alias(alias_request)?)
+            }
+            #[cfg(feature = "server")]
+            Self::ServerRegistry(server_registry) => app_state()
+                .block_on(async { server_registry.set_version_alias(alias_request).await }),
+        }
+    }
+
+    pub fn get_version_aliases(
+        &self,
+        alias_query: &VersionAliasQuery,
+    ) -> Result<Vec<VersionAlias>, RegistryError> {
+        match self {
+            Self::ClientRegistry(client_registry) => {
+                Ok(client_registry.get_version_aliases(alias_query)?)
+            }
+            #[cfg(feature = "server")]
+            Self::ServerRegistry(server_registry) => app_state()
+                .block_on(async { server_registry.get_version_aliases(alias_query).await }),
+        }
+    }
+
+    pub fn delete_version_alias(
+        &self,
+        alias_query: &VersionAliasQuery,
+    ) -> Result<(), RegistryError> {
+        match self {
+            Self::ClientRegistry(client_registry) => {
+                Ok(client_registry.delete_version_alias(alias_query)?)
+            }
+            #[cfg(feature = "server")]
+            Self::ServerRegistry(server_registry) => app_state()
+                .block_on(async { server_registry.delete_version_alias(alias_query).await }),
+        }
+    }
+
+    pub fn get_version_alias_history(
+        &self,
+        alias_query: &VersionAliasQuery,
+    ) -> Result<Vec<VersionAliasHistory>, RegistryError> {
+        match self {
+            Self::ClientRegistry(client_registry) => {
+                Ok(client_registry.get_version_alias_history(alias_query)?)
+            }
+            #[cfg(feature = "server")]
+            Self::ServerRegistry(server_registry) => app_state()
+                .block_on(async { server_registry.get_version_alias_history(alias_query).await }),
+        }
+    }
+
     pub async fn insert_hardware_metrics(
         &self,
         metrics: HardwareMetricRequest,
@@ -218,14 +499,14 @@ impl OpsmlRegistry {
             Self::ClientRegistry(client_registry) => {
                 // Clone the client_registry to avoid lifetime issues
                 let client_registry = client_registry.clone();
-                let _ = app_state()
+                app_state()
                     .runtime
                     .spawn_blocking(move || client_registry.insert_hardware_metrics(&metrics))
                     .await
                     .map_err(|e| {
                         error!("Failed to insert hardware metrics: {e}");
                         RegistryError::from(e)
-                    })?;
+                    })??;
                 Ok(())
             }
             #[cfg(feature = "server")]
@@ -282,6 +563,16 @@ impl OpsmlRegistry {
         }
     }
 
+    pub fn query_metrics(&self, query: &MetricQuery) -> Result<MetricPage, RegistryError> {
+        match self {
+            Self::ClientRegistry(client_registry) => Ok(client_registry.query_metrics(query)?),
+            #[cfg(feature = "server")]
+            Self::ServerRegistry(server_registry) => {
+                app_state().block_on(async { server_registry.query_metrics(query).await })
+            }
+        }
+    }
+
     pub fn get_parameters(
         &self,
         parameters: &GetParameterRequest,
@@ -297,6 +588,23 @@ impl OpsmlRegistry {
         }
     }
 
+    pub fn compare_experiments(
+        &self,
+        comparison_request: &ExperimentComparisonRequest,
+    ) -> Result<ExperimentComparison, RegistryError> {
+        match self {
+            Self::ClientRegistry(client_registry) => {
+                Ok(client_registry.compare_experiments(comparison_request)?)
+            }
+            #[cfg(feature = "server")]
+            Self::ServerRegistry(server_registry) => app_state().block_on(async {
+                server_registry
+                    .compare_experiments(comparison_request)
+                    .await
+            }),
+        }
+    }
+
     /// Inserts a scouter profile into the registry when opsml is integrated with scouter
     ///
     /// # Arguments
@@ -348,4 +656,45 @@ impl OpsmlRegistry {
             }
         }
     }
+
+    /// Queries audit events with filters, newest first (requires admin permissions in client mode)
+    pub fn get_audit_events(
+        &self,
+        args: &AuditEventQueryArgs,
+    ) -> Result<AuditEventPage, RegistryError> {
+        match self {
+            Self::ClientRegistry(client_registry) => Ok(client_registry.get_audit_events(args)?),
+            #[cfg(feature = "server")]
+            Self::ServerRegistry(server_registry) => {
+                app_state().block_on(async { server_registry.get_audit_events(args).await })
+            }
+        }
+    }
+
+    /// Verifies the audit event hash chain (requires admin permissions in client mode)
+    pub fn verify_audit_chain(&self) -> Result<AuditChainReport, RegistryError> {
+        match self {
+            Self::ClientRegistry(client_registry) => Ok(client_registry.verify_audit_chain()?),
+            #[cfg(feature = "server")]
+            Self::ServerRegistry(server_registry) => {
+                app_state().block_on(async { server_registry.verify_audit_chain().await })
+            }
+        }
+    }
+
+    /// Writes audit events for a time range as JSON Lines (requires admin permissions in client mode)
+    pub fn export_audit_events(
+        &self,
+        request: &AuditExportRequest,
+        writer: &mut dyn Write,
+    ) -> Result<(), RegistryError> {
+        match self {
+            Self::ClientRegistry(client_registry) => {
+                Ok(client_registry.export_audit_events(request, writer)?)
+            }
+            #[cfg(feature = "server")]
+            Self::ServerRegistry(server_registry) => app_state()
+                .block_on(async { server_registry.export_audit_events(request, writer).await }),
+        }
+    }
 }
@@ -62,6 +62,15 @@ pub enum RegistryError {
     #[error(transparent)]
     StorageError(#[from] StorageError),
 
+    #[error("Invalid signing key: {0}")]
+    InvalidSigningKey(String),
+
+    #[error("Card {0} is not signed and the signature policy requires signed cards")]
+    UnsignedCardError(String),
+
+    #[error("Card {0} has an {1}")]
+    CardSignatureError(String, String),
+
     #[error("At least one of uid, name, space, version must be provided")]
     MissingArgsError,
 
@@ -115,6 +124,16 @@ pub enum RegistryError {
     ScouterClientNotFoundError,
 }
 
+impl RegistryError {
+    /// Whether resending the same request may succeed
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            RegistryError::ApiRegistryError(e) => e.is_retryable(),
+            _ => true,
+        }
+    }
+}
+
 impl From<RegistryError> for PyErr {
     fn from(err: RegistryError) -> PyErr {
         let msg = err.to_string();
@@ -111,7 +111,7 @@ impl CardRegistry {
     }
 
     #[allow(clippy::too_many_arguments)]
-    #[pyo3(signature = (uid=None, space=None, name=None,  version=None, max_date=None, tags=None,  sort_by_timestamp=None, limit=25))]
+    #[pyo3(signature = (uid=None, space=None, name=None,  version=None, max_date=None, tags=None,  sort_by_timestamp=None, limit=25, stage=None))]
     #[instrument(skip_all)]
     pub fn list_cards(
         &self,
@@ -123,10 +123,11 @@ impl CardRegistry {
         tags: Option<Vec<String>>,
         sort_by_timestamp: Option<bool>,
         limit: i32,
+        stage: Option<CardStage>,
     ) -> Result<CardList, RegistryError> {
         debug!(
-            "Listing cards - {:?} - {:?} - {:?} - {:?} - {:?} - {:?} - {:?} - {:?}",
-            uid, name, space, version, max_date, tags, limit, sort_by_timestamp
+            "Listing cards - {:?} - {:?} - {:?} - {:?} - {:?} - {:?} - {:?} - {:?} - {:?}",
+            uid, name, space, version, max_date, tags, limit, sort_by_timestamp, stage
         );
 
         let name = name.map(|name| clean_string(&name)).transpose()?;
@@ -143,6 +144,7 @@ impl CardRegistry {
             limit: Some(limit),
             sort_by_timestamp,
             registry_type: self.registry_type.clone(),
+            stage,
         };
 
         let cards = self.registry.list_cards(query_args)?;
@@ -207,6 +209,8 @@ impl CardRegistry {
             ..Default::default()
         })?;
 
+        self.registry.check_card_signature(&key)?;
+
         let card = download_card(py, key, interface)?;
 
         //
@@ -224,6 +228,207 @@ impl CardRegistry {
         Self::_delete_card(&mut self.registry, card, &self.registry_type)
     }
 
+    /// Restore a deleted card from the trash
+    #[pyo3(signature = (uid))]
+    #[instrument(skip_all)]
+    pub fn restore_card(&self, uid: String) -> Result<(), RegistryError> {
+        debug!("Restoring card {}", uid);
+
+        self.registry.restore_card(&UidRequest {
+            uid,
+            registry_type: self.registry_type.clone(),
+        })
+    }
+
+    /// List the deleted cards of the registry that have not been purged yet
+    #[pyo3(signature = (space=None))]
+    #[instrument(skip_all)]
+    pub fn list_deleted_cards(
+        &self,
+        space: Option<String>,
+    ) -> Result<Vec<DeletedCard>, RegistryError> {
+        self.registry.list_deleted_cards(&DeletedCardQuery {
+            registry_type: self.registry_type.clone(),
+            space,
+        })
+    }
+
+    /// Full-text search of the registry across card names, spaces, tags, README content
+    /// and metadata such as the interface type and the username that registered the card
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - Words to search for. Every word must match, words are matched as prefixes
+    /// * `space` - Only search cards in this space
+    /// * `limit` - Maximum number of results
+    #[pyo3(signature = (query, space=None, limit=None))]
+    #[instrument(skip_all)]
+    pub fn search(
+        &self,
+        query: String,
+        space: Option<String>,
+        limit: Option<i32>,
+    ) -> Result<Vec<CardSearchResult>, RegistryError> {
+        debug!("Searching cards for {}", query);
+
+        self.registry.search_cards(&CardSearchQuery {
+            query,
+            registry_type: Some(self.registry_type.clone()),
+            space,
+            limit,
+        })
+    }
+
+    /// Move a model or service card version to a new stage
+    ///
+    /// # Arguments
+    ///
+    /// * `uid` - The uid of the card version
+    /// * `stage` - The new stage
+    /// * `archive_existing` - Archive other versions of the card that hold the stage
+    #[pyo3(signature = (uid, stage, archive_existing=false))]
+    #[instrument(skip_all)]
+    pub fn transition_stage(
+        &self,
+        uid: String,
+        stage: CardStage,
+        archive_existing: bool,
+    ) -> Result<CardStageResponse, RegistryError> {
+        debug!("Moving card {} to stage {}", uid, stage);
+
+        self.registry.update_card_stage(&CardStageRequest {
+            uid,
+            registry_type: self.registry_type.clone(),
+            stage,
+            archive_existing,
+        })
+    }
+
+    /// Point a version alias such as `champion` at a card version. Cards can then be loaded
+    /// with `version="@champion"`
+    ///
+    /// # Arguments
+    ///
+    /// * `space` - The space of the card
+    /// * `name` - The name of the card
+    /// * `alias` - The alias name without the `@` prefix
+    /// * `version` - The exact version the alias points to
+    #[pyo3(signature = (space, name, alias, version))]
+    #[instrument(skip_all)]
+    pub fn set_version_alias(
+        &self,
+        space: String,
+        name: String,
+        alias: String,
+        version: String,
+    ) -> Result<VersionAlias, RegistryError> {
+        debug!(
+            "Pointing alias {} of {}/{} at {}",
+            alias, space, name, version
+        );
+
+        self.registry.set_version_alias(&VersionAliasRequest {
+            space,
+            name,
+            registry_type: self.registry_type.clone(),
+            alias,
+            version,
+        })
+    }
+
+    /// Delete a version alias
+    #[pyo3(signature = (space, name, alias))]
+    #[instrument(skip_all)]
+    pub fn delete_version_alias(
+        &self,
+        space: String,
+        name: String,
+        alias: String,
+    ) -> Result<(), RegistryError> {
+        debug!("Deleting alias {} of {}/{}", alias, space, name);
+
+        self.registry.delete_version_alias(&VersionAliasQuery {
+            space,
+            name,
+            registry_type: self.registry_type.clone(),
+            alias: Some(alias),
+        })
+    }
+
+    /// List the version aliases of a card
+    #[pyo3(signature = (space, name))]
+    #[instrument(skip_all)]
+    pub fn list_version_aliases(
+        &self,
+        space: String,
+        name: String,
+    ) -> Result<Vec<VersionAlias>, RegistryError> {
+        self.registry.get_version_aliases(&VersionAliasQuery {
+            space,
+            name,
+            registry_type: self.registry_type.clone(),
+            alias: None,
+        })
+    }
+
+    /// Get the history of the version aliases of a card, newest first
+    ///
+    /// # Arguments
+    ///
+    /// * `space` - The space of the card
+    /// * `name` - The name of the card
+    /// * `alias` - Only return the history of this alias
+    #[pyo3(signature = (space, name, alias=None))]
+    #[instrument(skip_all)]
+    pub fn get_version_alias_history(
+        &self,
+        space: String,
+        name: String,
+        alias: Option<String>,
+    ) -> Result<Vec<VersionAliasHistory>, RegistryError> {
+        self.registry.get_version_alias_history(&VersionAliasQuery {
+            space,
+            name,
+            registry_type: self.registry_type.clone(),
+            alias,
+        })
+    }
+
+    /// Compare the parameters and metrics of several experiments. Metric series are aligned by
+    /// step and summarized per experiment
+    ///
+    /// # Arguments
+    ///
+    /// * `experiment_uids` - The experiments to compare
+    /// * `metrics` - Only compare these metrics. All metrics are compared when not provided
+    /// * `parameters` - Only compare these parameters. All parameters are compared when not provided
+    /// * `maximize` - Metrics where a higher value is better. Lower is better for all other metrics
+    #[pyo3(signature = (experiment_uids, metrics=None, parameters=None, maximize=None))]
+    #[instrument(skip_all)]
+    pub fn compare_experiments(
+        &self,
+        experiment_uids: Vec<String>,
+        metrics: Option<Vec<String>>,
+        parameters: Option<Vec<String>>,
+        maximize: Option<Vec<String>>,
+    ) -> Result<ExperimentComparison, RegistryError> {
+        if self.registry_type != RegistryType::Experiment {
+            return Err(RegistryError::RegistryTypeNotSupported(
+                self.registry_type.clone(),
+            ));
+        }
+
+        debug!("Comparing {} experiments", experiment_uids.len());
+
+        self.registry
+            .compare_experiments(&ExperimentComparisonRequest {
+                experiment_uids,
+                metric_names: metrics.unwrap_or_default(),
+                parameter_names: parameters.unwrap_or_default(),
+                maximize: maximize.unwrap_or_default(),
+            })
+    }
+
     #[pyo3(signature = (card))]
     #[instrument(skip_all)]
     pub fn update_card(&mut self, card: &Bound<'_, PyAny>) -> Result<(), RegistryError> {
@@ -3,17 +3,26 @@ pub mod server_logic {
     // We implement 2 versions of the registry, one for rust compatibility and one for python compatibility
 
     use crate::error::RegistryError;
-    use opsml_crypt::{derive_encryption_key, encrypted_key, generate_salt};
+    use base64::prelude::*;
+    use opsml_crypt::{encrypted_key, generate_key, signing_key_id, KeyProvider};
     use opsml_semver::error::VersionError;
     use opsml_semver::{VersionArgs, VersionType, VersionValidator};
     use opsml_settings::config::{DatabaseSettings, OpsmlStorageSettings};
 
+    use futures::StreamExt;
     use opsml_sql::{
+        alias::{build_version_alias, resolve_version_alias},
+        audit::{export_audit_events, verify_audit_chain},
         base::SqlClient,
+        comparison::compare_experiments,
         enums::client::{get_sql_client, SqlClientEnum},
+        error::SqlError,
+        import::insert_imported_card,
+        lineage::build_card_lineage,
+        metric::get_metric_page,
         schemas::*,
+        stage::plan_stage_transition,
     };
-    use opsml_storage::StorageClientEnum;
     use opsml_types::{
         cards::{
             CPUMetrics, CardTable, HardwareMetrics, MemoryMetrics, Metric, NetworkRates, Parameter,
@@ -21,13 +30,18 @@ pub mod server_logic {
         contracts::*,
         *,
     };
-    use opsml_utils::{get_utc_datetime, uid_to_byte_key};
+    use opsml_utils::uid_to_byte_key;
     use pyo3::prelude::*;
     use scouter_client::ScouterClient;
     use scouter_client::{ProfileRequest, ProfileStatusRequest};
     use semver::Version;
     use sqlx::types::Json as SqlxJson;
-    use tracing::info;
+    use std::io::Write;
+    use std::pin::pin;
+    use std::sync::Arc;
+
+    /// Maximum number of version alias history entries returned
+    const VERSION_ALIAS_HISTORY_LIMIT: i32 = 100;
 
     #[derive(Debug, Clone)]
     pub struct ServerRegistry {
@@ -36,12 +50,14 @@ pub mod server_logic {
         pub registry_type: RegistryType,
         pub table_name: CardTable,
         pub storage_settings: OpsmlStorageSettings,
+        pub key_provider: Arc<dyn KeyProvider>,
     }
 
     impl ServerRegistry {
         pub async fn new(
             registry_type: RegistryType,
             storage_settings: OpsmlStorageSettings,
+            key_provider: Arc<dyn KeyProvider>,
             database_settings: DatabaseSettings,
             scouter_client: Option<ScouterClient>,
         ) -> Result<Self, RegistryError> {
@@ -53,6 +69,7 @@ pub mod server_logic {
                 table_name,
                 registry_type,
                 storage_settings,
+                key_provider,
                 scouter_client,
             })
         }
@@ -69,6 +86,7 @@ pub mod server_logic {
             &self,
             args: CardQueryArgs,
         ) -> Result<Vec<CardRecord>, RegistryError> {
+            let args = resolve_version_alias(&self.sql_client, &args).await?;
             let cards = self.sql_client.query_cards(&self.table_name, &args).await?;
 
             match cards {
@@ -142,17 +160,33 @@ pub mod server_logic {
             registry_type: &str,
             storage_key: &str,
         ) -> Result<ArtifactKey, RegistryError> {
-            let salt = generate_salt()?;
+            let artifact_encryption_key = generate_key()?;
 
-            let derived_key = derive_encryption_key(
-                &self.storage_settings.encryption_key,
-                &salt,
-                registry_type.as_bytes(),
-            )?;
+            self.store_artifact_key(
+                &artifact_encryption_key,
+                uid,
+                space,
+                registry_type,
+                storage_key,
+                true,
+            )
+            .await
+        }
 
+        /// Stores an existing artifact encryption key for a card. `has_manifest` records whether
+        /// the artifacts of the card include a manifest
+        async fn store_artifact_key(
+            &self,
+            artifact_encryption_key: &[u8],
+            uid: &str,
+            space: &str,
+            registry_type: &str,
+            storage_key: &str,
+            has_manifest: bool,
+        ) -> Result<ArtifactKey, RegistryError> {
             let uid_key = uid_to_byte_key(uid)?;
 
-            let encrypted_key = encrypted_key(&uid_key, &derived_key)?;
+            let encrypted_key = encrypted_key(&uid_key, artifact_encryption_key)?;
 
             let artifact_key = ArtifactKey {
                 uid: uid.to_string(),
@@ -160,9 +194,12 @@ pub mod server_logic {
                 registry_type: RegistryType::from_string(registry_type)?,
                 encrypted_key,
                 storage_key: storage_key.to_string(),
+                has_manifest,
             };
 
-            self.sql_client.insert_artifact_key(&artifact_key).await?;
+            self.sql_client
+                .insert_artifact_key(&artifact_key.seal(self.key_provider.as_ref()).await?)
+                .await?;
 
             Ok(artifact_key)
         }
@@ -280,7 +317,9 @@ pub mod server_logic {
                 }
             };
 
-            self.sql_client.insert_card(&self.table_name, &card).await?;
+            self.sql_client
+                .insert_card(&self.table_name, &card, &[])
+                .await?;
 
             let key = self
                 .create_artifact_key(
@@ -304,11 +343,39 @@ pub mod server_logic {
                     registry_type: key.registry_type,
                     encrypted_key: key.encrypted_key,
                     storage_key: key.storage_key,
+                    has_manifest: key.has_manifest,
                 },
             };
             Ok(response)
         }
 
+        pub async fn import_card(
+            &self,
+            card: CardRecord,
+            encryption_key: &[u8],
+            has_manifest: bool,
+        ) -> Result<ImportCardResponse, RegistryError> {
+            let card = ServerCard::from_card(card)?;
+
+            insert_imported_card(&self.sql_client, &self.table_name, &card, &[]).await?;
+
+            let key = self
+                .store_artifact_key(
+                    encryption_key,
+                    card.uid(),
+                    &card.space(),
+                    &card.registry_type(),
+                    &card.uri(),
+                    has_manifest,
+                )
+                .await?;
+
+            Ok(ImportCardResponse {
+                imported: true,
+                key,
+            })
+        }
+
         pub async fn update_card(&self, card: &CardRecord) -> Result<(), RegistryError> {
             let card = card.clone();
             let card = match card {
@@ -365,6 +432,7 @@ pub mod server_logic {
                         task_type: client_card.task_type,
                         username: client_card.username,
                         opsml_version: client_card.opsml_version,
+                        stage: client_card.stage.to_string(),
                     };
                     ServerCard::Model(server_card)
                 }
@@ -468,56 +536,248 @@ pub mod server_logic {
                         cards: SqlxJson(client_card.cards),
                         username: client_card.username,
                         opsml_version: client_card.opsml_version,
+                        stage: client_card.stage.to_string(),
                     };
                     ServerCard::Service(server_card)
                 }
             };
 
-            self.sql_client.update_card(&self.table_name, &card).await?;
+            self.sql_client
+                .update_card(&self.table_name, &card, &[])
+                .await?;
 
             Ok(())
         }
 
+        /// Moves the card to the trash. Artifacts are removed when an opsml server purges the trash
         pub async fn delete_card(
             &self,
             delete_request: DeleteCardRequest,
         ) -> Result<(), RegistryError> {
-            // get key
-            let key = self
-                .get_key(&CardQueryArgs {
-                    uid: Some(delete_request.uid.to_string()),
-                    ..Default::default()
-                })
+            self.sql_client
+                .soft_delete_card(
+                    &self.table_name,
+                    &delete_request.uid,
+                    &self.storage_settings.api_settings.username,
+                    &[],
+                )
                 .await?;
 
-            // get storage client and delete artifacts
-            let storage_client = StorageClientEnum::new(&self.storage_settings).await?;
-
-            // Delete saved artifacts if they exist
-            if storage_client.find(&key.storage_path()).await?.is_empty() {
-                info!("No files found at storage path. Skipping artifact deletion");
-            } else {
-                storage_client.rm(&key.storage_path(), true).await?;
-            }
+            Ok(())
+        }
 
-            // Delete the artifact key
+        pub async fn restore_card(
+            &self,
+            restore_request: &UidRequest,
+        ) -> Result<(), RegistryError> {
             self.sql_client
-                .delete_artifact_key(&delete_request.uid, &key.registry_type.to_string())
+                .restore_card(&self.table_name, &restore_request.uid, &[])
+                .await?;
+
+            Ok(())
+        }
+
+        pub async fn list_deleted_cards(
+            &self,
+            trash_query: &DeletedCardQuery,
+        ) -> Result<Vec<DeletedCard>, RegistryError> {
+            let records = self
+                .sql_client
+                .get_deleted_cards(&self.table_name, trash_query.space.as_deref(), None)
                 .await?;
 
-            // Delete the card from registry
+            Ok(records
+                .into_iter()
+                .map(|record| record.into_deleted_card(&trash_query.registry_type))
+                .collect())
+        }
+
+        pub async fn search_cards(
+            &self,
+            search_query: &CardSearchQuery,
+        ) -> Result<Vec<CardSearchResult>, RegistryError> {
+            let records = self.sql_client.search_cards(search_query, None).await?;
+
+            Ok(records
+                .into_iter()
+                .map(|record| record.into_search_result())
+                .collect::<Result<Vec<_>, _>>()?)
+        }
+
+        pub async fn get_card_lineage(
+            &self,
+            lineage_request: &CardLineageRequest,
+        ) -> Result<CardLineage, RegistryError> {
+            Ok(build_card_lineage(&self.sql_client, lineage_request).await?)
+        }
+
+        pub async fn update_card_stage(
+            &self,
+            stage_request: &CardStageRequest,
+        ) -> Result<CardStageResponse, RegistryError> {
+            let transition = plan_stage_transition(
+                &self.sql_client,
+                &self.table_name,
+                &stage_request.uid,
+                &stage_request.stage,
+                stage_request.archive_existing,
+            )
+            .await?;
+
             self.sql_client
-                .delete_card(&self.table_name, &delete_request.uid)
+                .update_card_stages(
+                    &self.table_name,
+                    &transition,
+                    stage_request.archive_existing,
+                    &[],
+                )
                 .await?;
 
-            Ok(())
+            Ok(transition)
         }
 
         pub async fn get_key(&self, args: &CardQueryArgs) -> Result<ArtifactKey, RegistryError> {
-            Ok(self
+            let args = resolve_version_alias(&self.sql_client, args).await?;
+            let key = self
                 .sql_client
-                .get_card_key_for_loading(&self.table_name, args)
-                .await?)
+                .get_card_key_for_loading(&self.table_name, &args)
+                .await?;
+
+            Ok(key.unseal(self.key_provider.as_ref()).await?)
+        }
+
+        pub async fn set_version_alias(
+            &self,
+            alias_request: &VersionAliasRequest,
+        ) -> Result<VersionAlias, RegistryError> {
+            let record = build_version_alias(
+                &self.sql_client,
+                &alias_request.registry_type,
+                &alias_request.space,
+                &alias_request.name,
+                &alias_request.alias,
+                &alias_request.version,
+                &self.storage_settings.api_settings.username,
+            )
+            .await?;
+
+            self.sql_client.set_version_alias(&record).await?;
+
+            Ok(VersionAlias::try_from(record)?)
+        }
+
+        pub async fn get_version_aliases(
+            &self,
+            alias_query: &VersionAliasQuery,
+        ) -> Result<Vec<VersionAlias>, RegistryError> {
+            let registry_type = alias_query.registry_type.to_string();
+            let records = match &alias_query.alias {
+                Some(alias) => self
+                    .sql_client
+                    .get_version_alias(&registry_type, &alias_query.space, &alias_query.name, alias)
+                    .await?
+                    .into_iter()
+                    .collect(),
+                None => {
+                    self.sql_client
+                        .get_version_aliases(&registry_type, &alias_query.space, &alias_query.name)
+                        .await?
+                }
+            };
+
+            Ok(records
+                .into_iter()
+                .map(VersionAlias::try_from)
+                .collect::<Result<Vec<_>, _>>()?)
+        }
+
+        pub async fn delete_version_alias(
+            &self,
+            alias_query: &VersionAliasQuery,
+        ) -> Result<(), RegistryError> {
+            let alias = alias_query.alias.as_deref().unwrap_or_default();
+            let deleted = self
+                .sql_client
+                .delete_version_alias(
+                    &alias_query.registry_type.to_string(),
+                    &alias_query.space,
+                    &alias_query.name,
+                    alias,
+                    &self.storage_settings.api_settings.username,
+                )
+                .await?;
+
+            if !deleted {
+                return Err(SqlError::VersionAliasNotFound(alias_query.get_resource_id()).into());
+            }
+
+            Ok(())
+        }
+
+        pub async fn get_version_alias_history(
+            &self,
+            alias_query: &VersionAliasQuery,
+        ) -> Result<Vec<VersionAliasHistory>, RegistryError> {
+            let history = self
+                .sql_client
+                .get_version_alias_history(
+                    &alias_query.registry_type.to_string(),
+                    &alias_query.space,
+                    &alias_query.name,
+                    alias_query.alias.as_deref(),
+                    VERSION_ALIAS_HISTORY_LIMIT,
+                )
+                .await?;
+
+            Ok(history.into_iter().map(Into::into).collect())
+        }
+
+        pub async fn get_signing_keys(
+            &self,
+            query: &SigningKeyQuery,
+        ) -> Result<Vec<SpaceSigningKey>, RegistryError> {
+            let keys = self
+                .sql_client
+                .get_signing_keys(query.space.as_deref())
+                .await?;
+
+            Ok(keys.into_iter().map(Into::into).collect())
+        }
+
+        pub async fn create_signing_key(
+            &self,
+            request: &CreateSigningKeyRequest,
+        ) -> Result<SpaceSigningKey, RegistryError> {
+            let public_key = BASE64_STANDARD
+                .decode(&request.public_key)
+                .map_err(|e| RegistryError::InvalidSigningKey(e.to_string()))?;
+            let key_id = signing_key_id(&public_key);
+
+            let keys = self.sql_client.get_signing_keys(None).await?;
+            if keys.iter().any(|key| key.key_id == key_id) {
+                return Err(SqlError::SigningKeyExists(key_id).into());
+            }
+
+            let record = SigningKeyRecord::new(
+                &key_id,
+                &request.space,
+                &request.public_key,
+                &self.storage_settings.api_settings.username,
+            );
+            self.sql_client.insert_signing_key(&record).await?;
+
+            Ok(record.into())
+        }
+
+        pub async fn revoke_signing_key(
+            &self,
+            request: &RevokeSigningKeyRequest,
+        ) -> Result<(), RegistryError> {
+            if !self.sql_client.revoke_signing_key(&request.key_id).await? {
+                return Err(SqlError::SigningKeyNotFound(request.key_id.clone()).into());
+            }
+
+            Ok(())
         }
 
         pub async fn check_uid_exists(&self, uid: &str) -> Result<bool, RegistryError> {
@@ -537,18 +797,16 @@ pub mod server_logic {
                 .get_artifact_key(uid, &registry_type.to_string())
                 .await?;
 
-            Ok(key)
+            Ok(key.unseal(self.key_provider.as_ref()).await?)
         }
 
         pub async fn insert_hardware_metrics(
             &self,
             metrics: &HardwareMetricRequest,
         ) -> Result<(), RegistryError> {
-            let created_at = get_utc_datetime();
-
             let record = HardwareMetricsRecord {
                 experiment_uid: metrics.experiment_uid.clone(),
-                created_at: created_at.clone(),
+                created_at: metrics.metrics.created_at,
                 cpu_percent_utilization: metrics.metrics.cpu.cpu_percent_utilization,
                 cpu_percent_per_core: SqlxJson(metrics.metrics.cpu.cpu_percent_per_core.clone()),
                 free_memory: metrics.metrics.memory.free_memory,
@@ -607,6 +865,7 @@ pub mod server_logic {
                         m.value,
                         m.step,
                         m.timestamp,
+                        m.created_at,
                     )
                 })
                 .collect::<Vec<_>>();
@@ -659,6 +918,13 @@ pub mod server_logic {
                 .await?)
         }
 
+        pub async fn query_metrics(
+            &self,
+            query: &MetricQuery,
+        ) -> Result<MetricPage, RegistryError> {
+            Ok(get_metric_page(&self.sql_client, query).await?)
+        }
+
         pub async fn get_parameters(
             &self,
             parameters: &GetParameterRequest,
@@ -679,6 +945,57 @@ pub mod server_logic {
             Ok(params)
         }
 
+        pub async fn compare_experiments(
+            &self,
+            comparison_request: &ExperimentComparisonRequest,
+        ) -> Result<ExperimentComparison, RegistryError> {
+            Ok(compare_experiments(&self.sql_client, comparison_request).await?)
+        }
+
+        pub async fn get_audit_events(
+            &self,
+            args: &AuditEventQueryArgs,
+        ) -> Result<AuditEventPage, RegistryError> {
+            let limit = args.limit.unwrap_or(50);
+            let records = self.sql_client.get_audit_events(args).await?;
+
+            let next_cursor = match records.last() {
+                Some(last) if records.len() == limit as usize => Some(last.id),
+                _ => None,
+            };
+
+            Ok(AuditEventPage {
+                events: records
+                    .into_iter()
+                    .map(AuditEventClientRecord::from)
+                    .collect(),
+                next_cursor,
+            })
+        }
+
+        pub async fn verify_audit_chain(&self) -> Result<AuditChainReport, RegistryError> {
+            Ok(verify_audit_chain(&self.sql_client, 1000).await?)
+        }
+
+        pub async fn export_audit_events(
+            &self,
+            request: &AuditExportRequest,
+            writer: &mut dyn Write,
+        ) -> Result<(), RegistryError> {
+            let mut stream = pin!(export_audit_events(
+                self.sql_client.clone(),
+                request.start_time,
+                request.end_time,
+                1000,
+            ));
+
+            while let Some(chunk) = stream.next().await {
+                writer.write_all(chunk?.as_bytes())?;
+            }
+
+            Ok(())
+        }
+
         pub fn check_service_health(
             &self,
             service: IntegratedService,
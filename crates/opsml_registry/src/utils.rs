@@ -5,8 +5,8 @@ use opsml_cards::{
 };
 
 use crate::error::RegistryError;
-use opsml_crypt::{decrypt_directory, encrypt_directory};
-use opsml_storage::storage_client;
+use opsml_crypt::encrypt_directory;
+use opsml_storage::{add_card_manifest, download_artifacts, storage_client};
 use opsml_types::contracts::*;
 use opsml_types::*;
 use pyo3::prelude::*;
@@ -241,10 +241,6 @@ pub fn download_card<'py>(
     key: ArtifactKey,
     interface: Option<&Bound<'py, PyAny>>,
 ) -> Result<Bound<'py, PyAny>, RegistryError> {
-    let decryption_key = key.get_decrypt_key().inspect_err(|e| {
-        error!("Failed to get decryption key: {e}");
-    })?;
-
     let tmp_dir = TempDir::new()?;
 
     let tmp_path = tmp_dir.keep();
@@ -255,8 +251,7 @@ pub fn download_card<'py>(
     // add Card.json to tmp_path and rpath
     let lpath = tmp_path.join(SaveName::Card).with_extension(Suffix::Json);
 
-    storage_client()?.get(&lpath, &rpath, false)?;
-    decrypt_directory(&tmp_path, &decryption_key)?;
+    download_artifacts(&key, &lpath, &rpath, false)?;
 
     let json_string = std::fs::read_to_string(&lpath).inspect_err(|e| {
         error!("Failed to read card json: {e}");
@@ -285,7 +280,8 @@ pub fn download_card<'py>(
 /// Using a runtime, this method with
 /// (1) create an artifact key to be used to encrypt data
 /// (2) save the card to a temporary directory (with encryption)
-/// (3) Transfer all files in the temporary directory to the storage system
+/// (3) record the SHA-256 digests of the encrypted files in the card manifest
+/// (4) Transfer all files in the temporary directory to the storage system
 ///
 /// # Arguments
 /// * `py` - Python interpreter
@@ -302,6 +298,8 @@ pub fn upload_card_artifacts(path: PathBuf, key: &ArtifactKey) -> Result<(), Reg
     encrypt_directory(&path, &encryption_key)?;
     debug!("Encrypted card artifacts");
 
+    add_card_manifest(key, &path)?;
+
     storage_client()?.put(&path, &key.storage_path(), true)?;
     debug!("Saved card artifacts to storage");
 
@@ -1,19 +1,24 @@
-use crate::core::audit::AuditEventHandler;
+use crate::core::outbox::OutboxDispatcher;
 use crate::core::router::create_router;
 use crate::core::setup::{initialize_default_user, setup_components};
 use crate::core::state::AppState;
+use crate::core::trash::TrashPurger;
+use crate::core::webhook::handler::WebhookResumer;
 use anyhow::Ok;
 use anyhow::Result;
 use axum::Router;
 use opsml_auth::auth::AuthManager;
 use opsml_events::EventBus;
 use std::sync::Arc;
+use tokio::sync::Notify;
 use tracing::{info, warn};
 
 pub async fn create_app() -> Result<Router> {
     // setup components (config, logging, storage client)
     let (config, storage_client, sql_client, scouter_client) = setup_components().await?;
     let storage_settings = config.storage_settings()?;
+    let key_provider = config.key_provider()?;
+    info!("Using key provider {}", key_provider.key_id());
 
     // Setup basic auth and sso
     let auth_manager = AuthManager::new(
@@ -30,13 +35,23 @@ pub async fn create_app() -> Result<Router> {
         auth_manager,
         config,
         storage_settings,
+        key_provider,
         scouter_client,
         event_bus: EventBus::new(100),
+        outbox_notify: Arc::new(Notify::new()),
     });
 
-    // Initialize the event bus
-    let event_handler = AuditEventHandler::new(app_state.clone());
-    event_handler.start().await;
+    // Deliver outbox events to the audit log, webhooks and the event bus
+    let outbox_dispatcher = OutboxDispatcher::new(app_state.clone());
+    outbox_dispatcher.start().await;
+
+    // Resume webhook deliveries whose retries were interrupted by a restart
+    let webhook_resumer = WebhookResumer::new(app_state.clone());
+    webhook_resumer.start().await;
+
+    // Purge deleted cards once they have been in the trash for the retention period
+    let trash_purger = TrashPurger::new(app_state.clone());
+    trash_purger.start().await;
 
     // Initialize default user if none exists
     if let Err(e) = initialize_default_user(&app_state.sql_client, &app_state.scouter_client).await
@@ -1,3 +1,2 @@
-pub mod handler;
+pub mod route;
 pub mod schema;
-pub use handler::AuditEventHandler;
@@ -0,0 +1,134 @@
+use crate::core::error::{internal_server_error, OpsmlServerError};
+use crate::core::state::AppState;
+use anyhow::{Context, Result};
+use axum::{
+    body::Body,
+    extract::{Query, State},
+    http::{header::CONTENT_TYPE, StatusCode},
+    response::{IntoResponse, Response},
+    routing::get,
+    Extension, Json, Router,
+};
+use opsml_auth::permission::UserPermissions;
+use opsml_sql::audit::{export_audit_events, verify_audit_chain};
+use opsml_sql::base::SqlClient;
+use opsml_types::contracts::{
+    AuditChainReport, AuditEventClientRecord, AuditEventPage, AuditEventQueryArgs,
+    AuditExportRequest,
+};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::Arc;
+use tokio_stream::StreamExt;
+use tracing::{error, instrument};
+
+/// Number of audit events loaded per query when walking the chain
+const AUDIT_BATCH_SIZE: i32 = 1000;
+
+/// Verify the audit event hash chain (admin only)
+#[instrument(skip_all)]
+async fn verify_audit_events(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+) -> Result<Json<AuditChainReport>, (StatusCode, Json<OpsmlServerError>)> {
+    if !perms.group_permissions.contains(&"admin".to_string()) {
+        return OpsmlServerError::need_admin_permission().into_response(StatusCode::FORBIDDEN);
+    }
+
+    let report = verify_audit_chain(state.sql_client.as_ref(), AUDIT_BATCH_SIZE)
+        .await
+        .map_err(|e| {
+            error!("Failed to verify audit chain: {e}");
+            internal_server_error(e, "Failed to verify audit chain")
+        })?;
+
+    Ok(Json(report))
+}
+
+/// Query audit events with filters, newest first (admin only)
+#[instrument(skip_all)]
+async fn get_audit_events(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Query(params): Query<AuditEventQueryArgs>,
+) -> Result<Json<AuditEventPage>, (StatusCode, Json<OpsmlServerError>)> {
+    if !perms.group_permissions.contains(&"admin".to_string()) {
+        return OpsmlServerError::need_admin_permission().into_response(StatusCode::FORBIDDEN);
+    }
+
+    let limit = params.limit.unwrap_or(50).clamp(1, AUDIT_BATCH_SIZE);
+    let args = AuditEventQueryArgs {
+        limit: Some(limit),
+        ..params
+    };
+
+    let records = state
+        .sql_client
+        .get_audit_events(&args)
+        .await
+        .map_err(|e| {
+            error!("Failed to get audit events: {e}");
+            internal_server_error(e, "Failed to get audit events")
+        })?;
+
+    // a full page means there may be older events left
+    let next_cursor = match records.last() {
+        Some(last) if records.len() == limit as usize => Some(last.id),
+        _ => None,
+    };
+
+    Ok(Json(AuditEventPage {
+        events: records
+            .into_iter()
+            .map(AuditEventClientRecord::from)
+            .collect(),
+        next_cursor,
+    }))
+}
+
+/// Export audit events for a time range as JSON Lines (admin only)
+#[instrument(skip_all)]
+async fn export_audit_event_log(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Query(params): Query<AuditExportRequest>,
+) -> Result<Response, (StatusCode, Json<OpsmlServerError>)> {
+    if !perms.group_permissions.contains(&"admin".to_string()) {
+        return OpsmlServerError::need_admin_permission().into_response(StatusCode::FORBIDDEN);
+    }
+
+    // events are streamed batch by batch. A failure after the first batch aborts the response
+    let stream = export_audit_events(
+        state.sql_client.as_ref().clone(),
+        params.start_time,
+        params.end_time,
+        AUDIT_BATCH_SIZE,
+    )
+    .map(|chunk| chunk.inspect_err(|e| error!("Failed to export audit events: {e}")));
+
+    Ok((
+        [(CONTENT_TYPE, "application/x-ndjson")],
+        Body::from_stream(stream),
+    )
+        .into_response())
+}
+
+pub async fn get_audit_router(prefix: &str) -> Result<Router<Arc<AppState>>> {
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        Router::new()
+            .route(&format!("{prefix}/audit/events"), get(get_audit_events))
+            .route(&format!("{prefix}/audit/verify"), get(verify_audit_events))
+            .route(
+                &format!("{prefix}/audit/export"),
+                get(export_audit_event_log),
+            )
+    }));
+
+    match result {
+        Ok(router) => Ok(router),
+        Err(_) => {
+            error!("Failed to create audit router");
+            Err(anyhow::anyhow!("Failed to create audit router"))
+                .context("Panic occurred while creating the router")
+        }
+    }
+}
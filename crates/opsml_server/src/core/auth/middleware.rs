@@ -11,6 +11,7 @@ use axum::{
 };
 use axum_extra::extract::cookie::CookieJar;
 use opsml_auth::permission::UserPermissions;
+use opsml_auth::util::{hash_api_token, is_api_token};
 use opsml_sql::base::SqlClient;
 use serde::Serialize;
 use std::sync::Arc;
@@ -22,6 +23,52 @@ pub struct ErrorResponse {
     pub message: String,
 }
 
+fn unauthorized(message: &str) -> (StatusCode, Json<AuthError>) {
+    (
+        StatusCode::UNAUTHORIZED,
+        Json(AuthError {
+            error: "Unauthorized".to_string(),
+            message: message.to_string(),
+        }),
+    )
+}
+
+/// Validates an API token and resolves the effective permissions of the token owner
+async fn validate_api_token(
+    state: &AppState,
+    token: &str,
+) -> Result<UserPermissions, (StatusCode, Json<AuthError>)> {
+    let api_token = state
+        .sql_client
+        .get_api_token(&hash_api_token(token))
+        .await
+        .map_err(|e| {
+            error!("Failed to get api token: {e}");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(AuthError {
+                    error: "Server Error".to_string(),
+                    message: "Failed to validate api token".to_string(),
+                }),
+            )
+        })?
+        .ok_or_else(|| unauthorized("Invalid api token"))?;
+
+    if !api_token.is_active() {
+        return Err(unauthorized("Api token is revoked or expired"));
+    }
+
+    let user = get_user(&state.sql_client, &api_token.username, None)
+        .await
+        .map_err(|_| unauthorized("User not found"))?;
+
+    if !user.active {
+        return Err(unauthorized("User is not active"));
+    }
+
+    Ok(UserPermissions::from_api_token(&user, &api_token.scopes))
+}
+
 pub async fn auth_api_middleware(
     cookie_jar: CookieJar,
     State(state): State<Arc<AppState>>,
@@ -56,6 +103,13 @@ pub async fn auth_api_middleware(
         )
     })?;
 
+    // api tokens are looked up by hash and bypass the jwt/refresh flow
+    if is_api_token(&access_token) {
+        let auth_middleware = validate_api_token(&state, &access_token).await?;
+        req.extensions_mut().insert(auth_middleware);
+        return Ok(next.run(req).await.into_response());
+    }
+
     // validate the access token (this will also check if the token is expired)
     let auth_middleware = match state.auth_manager.validate_jwt(&access_token) {
         Ok(claims) => {
@@ -175,5 +229,5 @@ pub async fn auth_api_middleware(
     // add the auth middleware to the request extensions
     req.extensions_mut().insert(auth_middleware);
 
-    Ok(next.run(req).await)
+    Ok(next.run(req).await.into_response())
 }
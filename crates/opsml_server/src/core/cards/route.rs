@@ -1,10 +1,11 @@
 use crate::core::cards::schema::{
     CreateReadeMe, QueryPageResponse, ReadeMe, RegistryStatsResponse, VersionPageResponse,
 };
-use crate::core::cards::utils::{cleanup_artifacts, get_next_version, insert_card_into_db};
+use crate::core::cards::utils::{get_next_version, insert_card_into_db};
 use crate::core::error::{internal_server_error, OpsmlServerError};
 use crate::core::files::utils::{
     create_and_store_encrypted_file, create_artifact_key, download_artifact, get_artifact_key,
+    get_unsealed_artifact_key, store_artifact_key,
 };
 use crate::core::state::AppState;
 use anyhow::{Context, Result};
@@ -17,9 +18,14 @@ use axum::{
 };
 use opsml_auth::permission::UserPermissions;
 use opsml_crypt::decrypt_directory;
-use opsml_events::AuditContext;
+use opsml_events::{AuditContext, Event};
+use opsml_sql::alias::{build_version_alias, resolve_version_alias};
 use opsml_sql::base::SqlClient;
+use opsml_sql::error::SqlError;
+use opsml_sql::import::insert_imported_card;
+use opsml_sql::lineage::build_card_lineage;
 use opsml_sql::schemas::*;
+use opsml_sql::stage::plan_stage_transition;
 use opsml_types::{cards::*, contracts::*};
 use opsml_types::{SaveName, Suffix};
 
@@ -27,6 +33,10 @@ use std::panic::{catch_unwind, AssertUnwindSafe};
 use std::sync::Arc;
 use tempfile::tempdir;
 use tracing::{debug, error, info, instrument};
+
+/// Maximum number of version alias history entries returned
+const VERSION_ALIAS_HISTORY_LIMIT: i32 = 100;
+
 /// Route for checking if a card UID exists
 #[axum::debug_handler]
 pub async fn check_card_uid(
@@ -95,56 +105,100 @@ pub async fn get_space_record(
 #[instrument(skip_all)]
 pub async fn create_space_record(
     State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
     Json(space_request): Json<CrudSpaceRequest>,
 ) -> Result<Json<CrudSpaceResponse>, (StatusCode, Json<OpsmlServerError>)> {
     let record = SpaceRecord {
         space: space_request.space,
         description: space_request.description.unwrap_or_default(),
     };
+
+    let space_event = Event::Space(SpaceEvent::new(
+        SpaceEventType::SpaceCreated,
+        &record.space,
+        Some(record.description.clone()),
+        &perms.username,
+    ))
+    .to_outbox()
+    .map_err(|e| {
+        error!("Failed to create space event: {e}");
+        internal_server_error(e, "Failed to create space event")
+    })?;
+
     state
         .sql_client
-        .insert_space_record(&record)
+        .insert_space_record(&record, &[space_event])
         .await
         .map_err(|e| {
             error!("Failed to create space record: {e}");
             internal_server_error(e, "Failed to create space record")
         })?;
+
     Ok(Json(CrudSpaceResponse { success: true }))
 }
 
 #[instrument(skip_all)]
 pub async fn update_space_record(
     State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
     Json(space_request): Json<CrudSpaceRequest>,
 ) -> Result<Json<CrudSpaceResponse>, (StatusCode, Json<OpsmlServerError>)> {
     let record = SpaceRecord {
         space: space_request.space,
         description: space_request.description.unwrap_or_default(),
     };
+
+    let space_event = Event::Space(SpaceEvent::new(
+        SpaceEventType::SpaceUpdated,
+        &record.space,
+        Some(record.description.clone()),
+        &perms.username,
+    ))
+    .to_outbox()
+    .map_err(|e| {
+        error!("Failed to create space event: {e}");
+        internal_server_error(e, "Failed to create space event")
+    })?;
+
     state
         .sql_client
-        .update_space_record(&record)
+        .update_space_record(&record, &[space_event])
         .await
         .map_err(|e| {
             error!("Failed to update space record: {e}");
             internal_server_error(e, "Failed to update space record")
         })?;
+
     Ok(Json(CrudSpaceResponse { success: true }))
 }
 
 #[instrument(skip_all)]
 pub async fn delete_space_record(
     State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
     Query(space_request): Query<CrudSpaceRequest>,
 ) -> Result<Json<CrudSpaceResponse>, (StatusCode, Json<OpsmlServerError>)> {
+    let space_event = Event::Space(SpaceEvent::new(
+        SpaceEventType::SpaceDeleted,
+        &space_request.space,
+        None,
+        &perms.username,
+    ))
+    .to_outbox()
+    .map_err(|e| {
+        error!("Failed to create space event: {e}");
+        internal_server_error(e, "Failed to create space event")
+    })?;
+
     state
         .sql_client
-        .delete_space_record(&space_request.space)
+        .delete_space_record(&space_request.space, &[space_event])
         .await
         .map_err(|e| {
             error!("Failed to delete space record: {e}");
             internal_server_error(e, "Failed to delete space record")
         })?;
+
     Ok(Json(CrudSpaceResponse { success: true }))
 }
 
@@ -219,6 +273,29 @@ pub async fn get_version_page(
     Ok(Json(VersionPageResponse { summaries }))
 }
 
+/// Resolves a version alias (e.g. `@champion`) in a card query to the uid it points to
+async fn resolve_card_query(
+    state: &AppState,
+    params: &CardQueryArgs,
+) -> Result<CardQueryArgs, (StatusCode, Json<OpsmlServerError>)> {
+    resolve_version_alias(state.sql_client.as_ref(), params)
+        .await
+        .map_err(|e| match e {
+            SqlError::VersionAliasNotFound(_) => (
+                StatusCode::NOT_FOUND,
+                Json(OpsmlServerError::new(e.to_string())),
+            ),
+            SqlError::VersionAliasMissingCard(_) => (
+                StatusCode::BAD_REQUEST,
+                Json(OpsmlServerError::new(e.to_string())),
+            ),
+            _ => {
+                error!("Failed to resolve version alias: {e}");
+                internal_server_error(e, "Failed to resolve version alias")
+            }
+        })
+}
+
 pub async fn list_cards(
     State(state): State<Arc<AppState>>,
     Query(params): Query<CardQueryArgs>,
@@ -229,10 +306,11 @@ pub async fn list_cards(
     );
 
     let table = CardTable::from_registry_type(&params.registry_type);
+    let query = resolve_card_query(&state, &params).await?;
 
     let cards = state
         .sql_client
-        .query_cards(&table, &params)
+        .query_cards(&table, &query)
         .await
         .map_err(|e| {
             error!("Failed to get unique space names: {e}");
@@ -273,6 +351,7 @@ pub async fn list_cards(
         registry_type: Some(params.registry_type.clone()),
         operation: Operation::List,
         access_location: None,
+        space: params.space.clone(),
     };
 
     response.extensions_mut().insert(audit_context);
@@ -317,6 +396,8 @@ pub async fn create_card(
         card_request.card.clone(),
         version.clone(),
         &table,
+        &card_request.registry_type,
+        &perms.username,
     )
     .await
     .map_err(|e| {
@@ -327,11 +408,12 @@ pub async fn create_card(
     // (3) ------- Create the artifact key for card artifact encryption
     let key = create_artifact_key(
         &state.sql_client,
-        &state.storage_settings.encryption_key,
+        state.key_provider.as_ref(),
         &uid,
         &space,
         &registry_type,
         &card_uri,
+        true,
     )
     .await
     .map_err(|e| {
@@ -352,7 +434,7 @@ pub async fn create_card(
     })
     .into_response();
 
-    // (4) ------- Create audit and space stats events
+    // (4) ------- Create audit event
     let audit_context = AuditContext {
         resource_id: uid.clone(),
         resource_type: ResourceType::Database,
@@ -360,21 +442,120 @@ pub async fn create_card(
         registry_type: Some(card_request.registry_type.clone()),
         operation: Operation::Create,
         access_location: None,
+        space: Some(card_request.card.space().to_string()),
     };
 
-    // (5) ------- Create space name registry event
-    let space_name = SpaceNameEvent {
-        space: card_request.card.space().to_string(),
-        name: card_request.card.name().to_string(),
-        registry_type: card_request.registry_type.clone(),
-    };
+    response.extensions_mut().insert(audit_context);
 
-    {
-        let extensions = response.extensions_mut();
-        extensions.insert(audit_context);
-        extensions.insert(space_name);
+    Ok(response)
+}
+
+/// Import a card exported from another registry. The uid and version are kept and existing cards are never overwritten
+#[instrument(skip_all)]
+pub async fn import_card(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Json(import_request): Json<ImportCardRequest>,
+) -> Result<Response, (StatusCode, Json<OpsmlServerError>)> {
+    let table = CardTable::from_registry_type(&import_request.registry_type);
+
+    if !perms.has_write_permission(import_request.card.space()) {
+        return OpsmlServerError::permission_denied().into_response(StatusCode::FORBIDDEN);
     }
 
+    info!(
+        "Importing card: {}/{}/{} - registry: {:?}",
+        &import_request.card.space(),
+        &import_request.card.name(),
+        &import_request.card.version(),
+        &import_request.registry_type
+    );
+
+    let card = ServerCard::from_card(import_request.card.clone()).map_err(|e| {
+        error!("Failed to convert card: {e}");
+        internal_server_error(e, "Failed to convert card")
+    })?;
+
+    let events = [
+        Event::SpaceName(SpaceNameEvent {
+            space: card.space(),
+            name: card.name(),
+            registry_type: import_request.registry_type.clone(),
+        }),
+        Event::Card(
+            CardEvent::new(
+                CardEventType::CardRegistered,
+                import_request.registry_type.clone(),
+                &card.space(),
+                &card.name(),
+                &perms.username,
+            )
+            .with_version(&card.version())
+            .with_uid(card.uid()),
+        ),
+    ]
+    .iter()
+    .map(Event::to_outbox)
+    .collect::<Result<Vec<_>, _>>()
+    .map_err(|e| {
+        error!("Failed to create card event: {e}");
+        internal_server_error(e, "Failed to create card event")
+    })?;
+
+    insert_imported_card(state.sql_client.as_ref(), &table, &card, &events)
+        .await
+        .map_err(|e| match e {
+            SqlError::ImportConflict(_) => (
+                StatusCode::CONFLICT,
+                Json(OpsmlServerError::new(e.to_string())),
+            ),
+            SqlError::InvalidCardUid(_) => (
+                StatusCode::BAD_REQUEST,
+                Json(OpsmlServerError::new(e.to_string())),
+            ),
+            _ => {
+                error!("Failed to import card: {e}");
+                internal_server_error(e, "Failed to import card")
+            }
+        })?;
+
+    // the artifacts were encrypted with the key of the exporting registry, so it is stored as is
+    let key = store_artifact_key(
+        &state.sql_client,
+        state.key_provider.as_ref(),
+        &import_request.encryption_key,
+        card.uid(),
+        &card.space(),
+        &card.registry_type(),
+        &card.uri(),
+        import_request.has_manifest,
+    )
+    .await
+    .map_err(|e| {
+        error!("Failed to store artifact key: {e}");
+        internal_server_error(e, "Failed to store artifact key")
+    })?;
+
+    debug!("Card imported successfully");
+
+    let mut response = Json(ImportCardResponse {
+        imported: true,
+        key,
+    })
+    .into_response();
+
+    let audit_context = AuditContext {
+        resource_id: card.uid().to_string(),
+        resource_type: ResourceType::Database,
+        metadata: import_request.get_metadata(),
+        registry_type: Some(import_request.registry_type.clone()),
+        operation: Operation::Create,
+        access_location: None,
+        space: Some(card.space()),
+    };
+
+    response.extensions_mut().insert(audit_context);
+
     Ok(response)
 }
 
@@ -382,6 +563,7 @@ pub async fn create_card(
 #[instrument(skip_all)]
 pub async fn update_card(
     State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
     Json(card_request): Json<UpdateCardRequest>,
 ) -> Result<Response, (StatusCode, Json<OpsmlServerError>)> {
     info!(
@@ -398,9 +580,26 @@ pub async fn update_card(
         internal_server_error(e, "Failed to convert card")
     })?;
 
+    let card_event = Event::Card(
+        CardEvent::new(
+            CardEventType::CardUpdated,
+            card_request.registry_type.clone(),
+            card_request.card.space(),
+            card_request.card.name(),
+            &perms.username,
+        )
+        .with_version(card_request.card.version())
+        .with_uid(card.uid()),
+    )
+    .to_outbox()
+    .map_err(|e| {
+        error!("Failed to create card event: {e}");
+        internal_server_error(e, "Failed to create card event")
+    })?;
+
     state
         .sql_client
-        .update_card(&table, &card)
+        .update_card(&table, &card, &[card_event])
         .await
         .map_err(|e| {
             error!("Failed to update card: {e}");
@@ -417,6 +616,7 @@ pub async fn update_card(
         registry_type: Some(card_request.registry_type.clone()),
         operation: Operation::Update,
         access_location: None,
+        space: Some(card_request.card.space().to_string()),
     };
 
     response.extensions_mut().insert(audit_context);
@@ -424,6 +624,7 @@ pub async fn update_card(
     Ok(response)
 }
 
+/// Move a card to the trash. Deleted cards can be restored until they are purged
 #[instrument(skip_all)]
 pub async fn delete_card(
     State(state): State<Arc<AppState>>,
@@ -438,28 +639,55 @@ pub async fn delete_card(
 
     let table = CardTable::from_registry_type(&params.registry_type);
 
-    // delete the artifact key and the artifact itself
-    cleanup_artifacts(
-        &state.storage_client,
-        &state.sql_client,
-        params.uid.clone(),
-        params.registry_type.clone(),
-        &table,
+    // look up the card name for the lifecycle event, which is committed with the delete
+    let (space, name) = state
+        .sql_client
+        .query_cards(
+            &table,
+            &CardQueryArgs {
+                uid: Some(params.uid.clone()),
+                registry_type: params.registry_type.clone(),
+                ..Default::default()
+            },
+        )
+        .await
+        .map_err(|e| {
+            error!("Failed to get card: {e}");
+            internal_server_error(e, "Failed to get card")
+        })?
+        .first_space_name()
+        .unwrap_or_else(|| (params.space.clone(), String::new()));
+
+    let card_event = Event::Card(
+        CardEvent::new(
+            CardEventType::CardDeleted,
+            params.registry_type.clone(),
+            &space,
+            &name,
+            &perms.username,
+        )
+        .with_uid(&params.uid),
     )
-    .await
+    .to_outbox()
     .map_err(|e| {
-        error!("Failed to cleanup artifacts: {e}");
-        internal_server_error(e, "Failed to cleanup artifacts")
+        error!("Failed to create card event: {e}");
+        internal_server_error(e, "Failed to create card event")
     })?;
 
-    // delete card
+    // move the card to the trash. Artifacts are kept until the card is purged
     let (space, name) = state
         .sql_client
-        .delete_card(&table, &params.uid)
+        .soft_delete_card(&table, &params.uid, &perms.username, &[card_event])
         .await
-        .map_err(|e| {
-            error!("Failed to delete card: {e}");
-            internal_server_error(e, "Failed to delete card")
+        .map_err(|e| match e {
+            SqlError::CardNotFound(_) => (
+                StatusCode::NOT_FOUND,
+                Json(OpsmlServerError::card_not_found()),
+            ),
+            _ => {
+                error!("Failed to delete card: {e}");
+                internal_server_error(e, "Failed to delete card")
+            }
         })?;
 
     let mut response = Json(UidResponse { exists: false }).into_response();
@@ -471,6 +699,7 @@ pub async fn delete_card(
         registry_type: Some(params.registry_type.clone()),
         operation: Operation::Delete,
         access_location: None,
+        space: Some(params.space.clone()),
     };
 
     response.extensions_mut().insert(audit_context);
@@ -513,6 +742,8 @@ pub async fn load_card(
     Query(params): Query<CardQueryArgs>,
 ) -> Result<Json<ArtifactKey>, (StatusCode, Json<OpsmlServerError>)> {
     let table = CardTable::from_registry_type(&params.registry_type);
+    let params = resolve_card_query(&state, &params).await?;
+
     let key = state
         .sql_client
         .get_card_key_for_loading(&table, &params)
@@ -520,6 +751,12 @@ pub async fn load_card(
         .map_err(|e| {
             error!("Failed to get card key for loading: {e}");
             internal_server_error(e, "Failed to get card key for loading")
+        })?
+        .unseal(state.key_provider.as_ref())
+        .await
+        .map_err(|e| {
+            error!("Failed to unseal artifact key: {e}");
+            internal_server_error(e, "Failed to get card key for loading")
         })?;
 
     Ok(Json(key))
@@ -532,6 +769,7 @@ pub async fn get_card(
     Query(params): Query<CardQueryArgs>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, Json<OpsmlServerError>)> {
     let table = CardTable::from_registry_type(&params.registry_type);
+    let params = resolve_card_query(&state, &params).await?;
 
     let key = state
         .sql_client
@@ -540,6 +778,12 @@ pub async fn get_card(
         .map_err(|e| {
             error!("Failed to get card key for loading: {e}");
             internal_server_error(e, "Failed to get card key for loading")
+        })?
+        .unseal(state.key_provider.as_ref())
+        .await
+        .map_err(|e| {
+            error!("Failed to unseal artifact key: {e}");
+            internal_server_error(e, "Failed to get card key for loading")
         })?;
 
     if !perms.has_read_permission(&key.space) {
@@ -636,6 +880,7 @@ pub async fn get_readme(
     match download_artifact(
         state.storage_client.clone(),
         state.sql_client.clone(),
+        state.key_provider.as_ref(),
         &lpath,
         &rpath,
         &params.registry_type.to_string(),
@@ -681,7 +926,7 @@ pub async fn create_readme(
     // check if artifact key exists before creating a new key
     let key = get_artifact_key(
         &state.sql_client,
-        &state.storage_settings.encryption_key,
+        state.key_provider.as_ref(),
         &req.registry_type.to_string(),
         &req.space,
         &readme_path,
@@ -703,7 +948,18 @@ pub async fn create_readme(
     .await;
 
     match result {
-        Ok(uploaded) => Ok(Json(uploaded)),
+        Ok(uploaded) => {
+            // README content is searchable. A failed index update does not fail the upload
+            if let Err(e) = state
+                .sql_client
+                .update_search_readme(&req.registry_type, &req.space, &req.name, &req.readme)
+                .await
+            {
+                error!("Failed to index readme: {e}");
+            }
+
+            Ok(Json(uploaded))
+        }
         Err(e) => Ok(Json(UploadResponse {
             uploaded: false,
             message: format!("Failed to upload readme: {e}"),
@@ -711,6 +967,555 @@ pub async fn create_readme(
     }
 }
 
+/// Move a model or service card version to a new stage
+///
+/// When `archive_existing` is set, other versions of the card that hold the stage are archived
+/// in the same transaction. A card event is emitted for every version whose stage changed.
+#[instrument(skip_all)]
+pub async fn update_card_stage(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Json(params): Json<CardStageRequest>,
+) -> Result<Response, (StatusCode, Json<OpsmlServerError>)> {
+    info!("Moving card {} to stage {}", &params.uid, &params.stage);
+
+    let table = CardTable::from_registry_type(&params.registry_type);
+
+    let transition = plan_stage_transition(
+        state.sql_client.as_ref(),
+        &table,
+        &params.uid,
+        &params.stage,
+        params.archive_existing,
+    )
+    .await
+    .map_err(|e| match e {
+        SqlError::StageNotSupported => (
+            StatusCode::BAD_REQUEST,
+            Json(OpsmlServerError::new(e.to_string())),
+        ),
+        SqlError::CardNotFound(_) => (
+            StatusCode::NOT_FOUND,
+            Json(OpsmlServerError::card_not_found()),
+        ),
+        _ => {
+            error!("Failed to get card stage: {e}");
+            internal_server_error(e, "Failed to get card stage")
+        }
+    })?;
+
+    if !perms.has_write_permission(&transition.space) {
+        return OpsmlServerError::permission_denied().into_response(StatusCode::FORBIDDEN);
+    }
+
+    let events = std::iter::once(&transition.change)
+        .chain(transition.archived.iter())
+        .map(|change| {
+            Event::Card(
+                CardEvent::new(
+                    CardEventType::CardStageChanged,
+                    params.registry_type.clone(),
+                    &transition.space,
+                    &transition.name,
+                    &perms.username,
+                )
+                .with_uid(&change.uid)
+                .with_version(&change.version)
+                .with_details(serde_json::json!({
+                    "previous_stage": change.previous_stage,
+                    "stage": change.stage,
+                })),
+            )
+            .to_outbox()
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| {
+            error!("Failed to create card event: {e}");
+            internal_server_error(e, "Failed to create card event")
+        })?;
+
+    state
+        .sql_client
+        .update_card_stages(&table, &transition, params.archive_existing, &events)
+        .await
+        .map_err(|e| match e {
+            SqlError::StageConflict(_) => (
+                StatusCode::CONFLICT,
+                Json(OpsmlServerError::new(e.to_string())),
+            ),
+            _ => {
+                error!("Failed to update card stage: {e}");
+                internal_server_error(e, "Failed to update card stage")
+            }
+        })?;
+
+    let audit_context = AuditContext {
+        resource_id: params.uid.clone(),
+        resource_type: ResourceType::Database,
+        metadata: serde_json::to_string(&transition).unwrap_or_else(|_| params.get_metadata()),
+        registry_type: Some(params.registry_type.clone()),
+        operation: Operation::Update,
+        access_location: None,
+        space: Some(transition.space.clone()),
+    };
+
+    let mut response = Json(transition).into_response();
+    response.extensions_mut().insert(audit_context);
+
+    Ok(response)
+}
+
+/// Point a version alias at a card version, creating the alias if needed
+#[instrument(skip_all)]
+pub async fn set_version_alias(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Json(params): Json<VersionAliasRequest>,
+) -> Result<Response, (StatusCode, Json<OpsmlServerError>)> {
+    info!(
+        "Pointing alias {} of {}/{} at version {}",
+        &params.alias, &params.space, &params.name, &params.version
+    );
+
+    if !perms.has_write_permission(&params.space) {
+        return OpsmlServerError::permission_denied().into_response(StatusCode::FORBIDDEN);
+    }
+
+    let record = build_version_alias(
+        state.sql_client.as_ref(),
+        &params.registry_type,
+        &params.space,
+        &params.name,
+        &params.alias,
+        &params.version,
+        &perms.username,
+    )
+    .await
+    .map_err(|e| match e {
+        SqlError::TypeError(_) => (
+            StatusCode::BAD_REQUEST,
+            Json(OpsmlServerError::new(e.to_string())),
+        ),
+        SqlError::CardNotFound(_) => (
+            StatusCode::NOT_FOUND,
+            Json(OpsmlServerError::card_not_found()),
+        ),
+        _ => {
+            error!("Failed to get card version: {e}");
+            internal_server_error(e, "Failed to get card version")
+        }
+    })?;
+
+    state
+        .sql_client
+        .set_version_alias(&record)
+        .await
+        .map_err(|e| {
+            error!("Failed to set version alias: {e}");
+            internal_server_error(e, "Failed to set version alias")
+        })?;
+
+    let alias = VersionAlias::try_from(record).map_err(|e| {
+        error!("Failed to convert version alias: {e}");
+        internal_server_error(e, "Failed to convert version alias")
+    })?;
+
+    let audit_context = AuditContext {
+        resource_id: params.get_resource_id(),
+        resource_type: ResourceType::Database,
+        metadata: params.get_metadata(),
+        registry_type: Some(params.registry_type.clone()),
+        operation: Operation::Update,
+        access_location: None,
+        space: Some(params.space.clone()),
+    };
+
+    let mut response = Json(alias).into_response();
+    response.extensions_mut().insert(audit_context);
+
+    Ok(response)
+}
+
+/// List the version aliases of a card, or a single alias when `alias` is set
+#[instrument(skip_all)]
+pub async fn get_version_aliases(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Query(params): Query<VersionAliasQuery>,
+) -> Result<Json<Vec<VersionAlias>>, (StatusCode, Json<OpsmlServerError>)> {
+    if !perms.has_read_permission(&params.space) {
+        return OpsmlServerError::permission_denied().into_response(StatusCode::FORBIDDEN);
+    }
+
+    let registry_type = params.registry_type.to_string();
+    let records = match &params.alias {
+        Some(alias) => state
+            .sql_client
+            .get_version_alias(&registry_type, &params.space, &params.name, alias)
+            .await
+            .map(|record| record.into_iter().collect()),
+        None => {
+            state
+                .sql_client
+                .get_version_aliases(&registry_type, &params.space, &params.name)
+                .await
+        }
+    }
+    .map_err(|e| {
+        error!("Failed to get version aliases: {e}");
+        internal_server_error(e, "Failed to get version aliases")
+    })?;
+
+    let aliases = records
+        .into_iter()
+        .map(VersionAlias::try_from)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| {
+            error!("Failed to convert version aliases: {e}");
+            internal_server_error(e, "Failed to convert version aliases")
+        })?;
+
+    Ok(Json(aliases))
+}
+
+/// Delete a version alias
+#[instrument(skip_all)]
+pub async fn delete_version_alias(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Query(params): Query<VersionAliasQuery>,
+) -> Result<Response, (StatusCode, Json<OpsmlServerError>)> {
+    if !perms.has_delete_permission(&params.space) {
+        return OpsmlServerError::permission_denied().into_response(StatusCode::FORBIDDEN);
+    }
+
+    let Some(alias) = &params.alias else {
+        return OpsmlServerError::new("Alias is required".to_string())
+            .into_response(StatusCode::BAD_REQUEST);
+    };
+
+    info!(
+        "Deleting alias {} of {}/{}",
+        alias, &params.space, &params.name
+    );
+
+    let deleted = state
+        .sql_client
+        .delete_version_alias(
+            &params.registry_type.to_string(),
+            &params.space,
+            &params.name,
+            alias,
+            &perms.username,
+        )
+        .await
+        .map_err(|e| {
+            error!("Failed to delete version alias: {e}");
+            internal_server_error(e, "Failed to delete version alias")
+        })?;
+
+    if !deleted {
+        return OpsmlServerError::new(format!("Version alias not found: {alias}"))
+            .into_response(StatusCode::NOT_FOUND);
+    }
+
+    let audit_context = AuditContext {
+        resource_id: params.get_resource_id(),
+        resource_type: ResourceType::Database,
+        metadata: params.get_metadata(),
+        registry_type: Some(params.registry_type.clone()),
+        operation: Operation::Delete,
+        access_location: None,
+        space: Some(params.space.clone()),
+    };
+
+    let mut response = StatusCode::OK.into_response();
+    response.extensions_mut().insert(audit_context);
+
+    Ok(response)
+}
+
+/// Get the history of the version aliases of a card, newest first
+#[instrument(skip_all)]
+pub async fn get_version_alias_history(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Query(params): Query<VersionAliasQuery>,
+) -> Result<Json<Vec<VersionAliasHistory>>, (StatusCode, Json<OpsmlServerError>)> {
+    if !perms.has_read_permission(&params.space) {
+        return OpsmlServerError::permission_denied().into_response(StatusCode::FORBIDDEN);
+    }
+
+    let history = state
+        .sql_client
+        .get_version_alias_history(
+            &params.registry_type.to_string(),
+            &params.space,
+            &params.name,
+            params.alias.as_deref(),
+            VERSION_ALIAS_HISTORY_LIMIT,
+        )
+        .await
+        .map_err(|e| {
+            error!("Failed to get version alias history: {e}");
+            internal_server_error(e, "Failed to get version alias history")
+        })?;
+
+    Ok(Json(history.into_iter().map(Into::into).collect()))
+}
+
+/// Restore a card from the trash
+#[instrument(skip_all)]
+pub async fn restore_card(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Json(params): Json<UidRequest>,
+) -> Result<Response, (StatusCode, Json<OpsmlServerError>)> {
+    info!("Restoring card: {}", &params.uid);
+
+    let table = CardTable::from_registry_type(&params.registry_type);
+
+    // the artifact key is kept while the card is in the trash
+    let key = state
+        .sql_client
+        .get_artifact_key(&params.uid, &params.registry_type.to_string())
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(OpsmlServerError::card_not_found()),
+            )
+        })?;
+
+    if !perms.has_delete_permission(&key.space) {
+        return OpsmlServerError::permission_denied().into_response(StatusCode::FORBIDDEN);
+    }
+
+    let deleted = state
+        .sql_client
+        .get_deleted_cards(&table, Some(&key.space), None)
+        .await
+        .map_err(|e| {
+            error!("Failed to get deleted cards: {e}");
+            internal_server_error(e, "Failed to get deleted cards")
+        })?
+        .into_iter()
+        .find(|card| card.uid == params.uid)
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(OpsmlServerError::card_not_found()),
+            )
+        })?;
+
+    // the space name record is removed when the last version of a card is deleted
+    let events = [
+        Event::Card(
+            CardEvent::new(
+                CardEventType::CardRestored,
+                params.registry_type.clone(),
+                &deleted.space,
+                &deleted.name,
+                &perms.username,
+            )
+            .with_uid(&deleted.uid)
+            .with_version(&deleted.version),
+        ),
+        Event::SpaceName(SpaceNameEvent {
+            space: deleted.space.clone(),
+            name: deleted.name.clone(),
+            registry_type: params.registry_type.clone(),
+        }),
+    ]
+    .iter()
+    .map(Event::to_outbox)
+    .collect::<Result<Vec<_>, _>>()
+    .map_err(|e| {
+        error!("Failed to create card event: {e}");
+        internal_server_error(e, "Failed to create card event")
+    })?;
+
+    state
+        .sql_client
+        .restore_card(&table, &params.uid, &events)
+        .await
+        .map_err(|e| {
+            error!("Failed to restore card: {e}");
+            internal_server_error(e, "Failed to restore card")
+        })?;
+
+    let audit_context = AuditContext {
+        resource_id: params.uid.clone(),
+        resource_type: ResourceType::Database,
+        metadata: params.get_metadata(),
+        registry_type: Some(params.registry_type.clone()),
+        operation: Operation::Update,
+        access_location: None,
+        space: Some(deleted.space.clone()),
+    };
+
+    let mut response = Json(UidResponse { exists: true }).into_response();
+    response.extensions_mut().insert(audit_context);
+
+    Ok(response)
+}
+
+/// List the deleted cards of a registry that have not been purged yet
+#[instrument(skip_all)]
+pub async fn list_deleted_cards(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Query(params): Query<DeletedCardQuery>,
+) -> Result<Json<Vec<DeletedCard>>, (StatusCode, Json<OpsmlServerError>)> {
+    let table = CardTable::from_registry_type(&params.registry_type);
+
+    let records = state
+        .sql_client
+        .get_deleted_cards(&table, params.space.as_deref(), None)
+        .await
+        .map_err(|e| {
+            error!("Failed to get deleted cards: {e}");
+            internal_server_error(e, "Failed to get deleted cards")
+        })?;
+
+    let cards = records
+        .into_iter()
+        .filter(|record| perms.has_read_permission(&record.space))
+        .map(|record| record.into_deleted_card(&params.registry_type))
+        .collect();
+
+    Ok(Json(cards))
+}
+
+/// Full-text search across card names, spaces, tags, README content and metadata.
+/// Only cards in spaces the user can read are returned
+#[instrument(skip_all)]
+pub async fn search_cards(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Query(params): Query<CardSearchQuery>,
+) -> Result<Json<Vec<CardSearchResult>>, (StatusCode, Json<OpsmlServerError>)> {
+    if let Some(space) = &params.space {
+        if !perms.has_read_permission(space) {
+            return OpsmlServerError::permission_denied().into_response(StatusCode::FORBIDDEN);
+        }
+    }
+
+    // restrict the search to readable spaces so the limit is filled with permitted cards
+    let spaces = perms.readable_spaces();
+
+    let records = state
+        .sql_client
+        .search_cards(&params, spaces.as_deref())
+        .await
+        .map_err(|e| match e {
+            SqlError::EmptySearchQuery => (
+                StatusCode::BAD_REQUEST,
+                Json(OpsmlServerError::new(e.to_string())),
+            ),
+            _ => {
+                error!("Failed to search cards: {e}");
+                internal_server_error(e, "Failed to search cards")
+            }
+        })?;
+
+    let results = records
+        .into_iter()
+        .map(|record| record.into_search_result())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| {
+            error!("Failed to parse search results: {e}");
+            internal_server_error(e, "Failed to parse search results")
+        })?;
+
+    Ok(Json(results))
+}
+
+/// Walk card references upstream and downstream from a card across all registries.
+/// Cards in spaces the user cannot read are left out of the graph
+#[instrument(skip_all)]
+pub async fn get_card_lineage(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Query(params): Query<CardLineageRequest>,
+) -> Result<Json<CardLineage>, (StatusCode, Json<OpsmlServerError>)> {
+    let mut lineage = build_card_lineage(state.sql_client.as_ref(), &params)
+        .await
+        .map_err(|e| match e {
+            SqlError::CardNotFound(_) => (
+                StatusCode::NOT_FOUND,
+                Json(OpsmlServerError::card_not_found()),
+            ),
+            _ => {
+                error!("Failed to get card lineage: {e}");
+                internal_server_error(e, "Failed to get card lineage")
+            }
+        })?;
+
+    // the requested card is always the first node
+    if !perms.has_read_permission(&lineage.nodes[0].space) {
+        return OpsmlServerError::permission_denied().into_response(StatusCode::FORBIDDEN);
+    }
+
+    lineage.retain_nodes(|node| perms.has_read_permission(&node.space));
+
+    Ok(Json(lineage))
+}
+
+/// Re-hashes the stored artifacts of a card and compares them with the card manifest
+#[instrument(skip_all)]
+pub async fn verify_card_artifacts(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Query(params): Query<VerifyArtifactsRequest>,
+) -> Result<Json<ArtifactIntegrityReport>, (StatusCode, Json<OpsmlServerError>)> {
+    let key = get_unsealed_artifact_key(
+        &state.sql_client,
+        state.key_provider.as_ref(),
+        &params.uid,
+        &params.registry_type.to_string(),
+    )
+    .await
+    .map_err(|e| {
+        error!("Failed to get artifact key: {e}");
+        internal_server_error(e, "Failed to get artifact key")
+    })?;
+
+    if !perms.has_read_permission(&key.space) {
+        return OpsmlServerError::permission_denied().into_response(StatusCode::FORBIDDEN);
+    }
+
+    let tmp_dir = tempdir().map_err(|e| {
+        error!("Failed to create temp dir: {e}");
+        internal_server_error(e, "Failed to create temp dir")
+    })?;
+
+    // artifacts are hashed as stored, so they are never decrypted
+    state
+        .storage_client
+        .get(tmp_dir.path(), &key.storage_path(), true)
+        .await
+        .map_err(|e| {
+            error!("Failed to get card artifacts: {e}");
+            internal_server_error(e, "Failed to get card artifacts")
+        })?;
+
+    let report = ArtifactIntegrityReport::from_directory(&key, tmp_dir.path()).map_err(|e| {
+        error!("Failed to verify card artifacts: {e}");
+        internal_server_error(e, "Failed to verify card artifacts")
+    })?;
+
+    if !report.mismatches.is_empty() {
+        info!(
+            "Artifacts of card {} do not match their manifest: {} mismatches",
+            report.uid,
+            report.mismatches.len()
+        );
+    }
+
+    Ok(Json(report))
+}
+
 pub async fn get_card_router(prefix: &str) -> Result<Router<Arc<AppState>>> {
     let result = catch_unwind(AssertUnwindSafe(|| {
         Router::new()
@@ -739,9 +1544,26 @@ pub async fn get_card_router(prefix: &str) -> Result<Router<Arc<AppState>>> {
             )
             .route(&format!("{prefix}/card/list"), get(list_cards))
             .route(&format!("{prefix}/card/create"), post(create_card))
+            .route(&format!("{prefix}/card/import"), post(import_card))
             .route(&format!("{prefix}/card/load"), get(load_card))
             .route(&format!("{prefix}/card/update"), post(update_card))
             .route(&format!("{prefix}/card/delete"), delete(delete_card))
+            .route(&format!("{prefix}/card/restore"), put(restore_card))
+            .route(&format!("{prefix}/card/trash"), get(list_deleted_cards))
+            .route(&format!("{prefix}/card/search"), get(search_cards))
+            .route(&format!("{prefix}/card/lineage"), get(get_card_lineage))
+            .route(&format!("{prefix}/card/verify"), get(verify_card_artifacts))
+            .route(&format!("{prefix}/card/stage"), put(update_card_stage))
+            .route(&format!("{prefix}/card/alias"), get(get_version_aliases))
+            .route(&format!("{prefix}/card/alias"), put(set_version_alias))
+            .route(
+                &format!("{prefix}/card/alias"),
+                delete(delete_version_alias),
+            )
+            .route(
+                &format!("{prefix}/card/alias/history"),
+                get(get_version_alias_history),
+            )
     }));
 
     match result {
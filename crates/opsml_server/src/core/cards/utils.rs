@@ -1,15 +1,17 @@
 use crate::core::cards::schema::InsertCardResponse;
 use crate::core::error::ServerError;
+use opsml_events::Event;
 use opsml_semver::{VersionArgs, VersionValidator};
 use opsml_sql::base::SqlClient;
 use opsml_sql::enums::client::SqlClientEnum;
+use opsml_sql::error::SqlError;
 use opsml_sql::schemas::*;
 use opsml_storage::StorageClientEnum;
 use opsml_types::cards::CardTable;
 use opsml_types::{contracts::*, RegistryType};
 use semver::Version;
 use std::sync::Arc;
-use tracing::{error, instrument};
+use tracing::{debug, error, instrument};
 
 #[instrument(skip_all)]
 pub async fn get_next_version(
@@ -68,6 +70,8 @@ pub async fn insert_card_into_db(
     card: CardRecord,
     version: Version,
     table: &CardTable,
+    registry_type: &RegistryType,
+    username: &str,
 ) -> Result<InsertCardResponse, ServerError> {
     // match on registry type
     let card = match card {
@@ -163,7 +167,31 @@ pub async fn insert_card_into_db(
             ServerCard::Service(server_card)
         }
     };
-    sql_client.insert_card(table, &card).await?;
+
+    // space name and lifecycle events are committed in the same transaction as the card
+    let events = [
+        Event::SpaceName(SpaceNameEvent {
+            space: card.space(),
+            name: card.name(),
+            registry_type: registry_type.clone(),
+        }),
+        Event::Card(
+            CardEvent::new(
+                CardEventType::CardRegistered,
+                registry_type.clone(),
+                &card.space(),
+                &card.name(),
+                username,
+            )
+            .with_version(&card.version())
+            .with_uid(card.uid()),
+        ),
+    ]
+    .iter()
+    .map(Event::to_outbox)
+    .collect::<Result<Vec<_>, _>>()?;
+
+    sql_client.insert_card(table, &card, &events).await?;
 
     Ok((
         card.uid().to_string(),
@@ -175,42 +203,43 @@ pub async fn insert_card_into_db(
     ))
 }
 
+/// Removes the stored artifacts of a card. The artifact key row is deleted with the card row,
+/// so a missing key or storage path means the artifacts were already removed.
 #[instrument(skip_all)]
 pub async fn cleanup_artifacts(
     storage_client: &Arc<StorageClientEnum>,
     sql_client: &Arc<SqlClientEnum>,
-    uid: String,
-    registry_type: RegistryType,
-    table: &CardTable,
+    uid: &str,
+    registry_type: &RegistryType,
 ) -> Result<(), ServerError> {
-    // get artifact key
-    let key = sql_client
-        .get_card_key_for_loading(
-            table,
-            &CardQueryArgs {
-                uid: Some(uid.clone()),
-                registry_type: registry_type.clone(),
-                ..Default::default()
-            },
-        )
+    // get artifact key (cards in the trash are hidden from card queries, so look up the key directly)
+    let key = match sql_client
+        .get_artifact_key(uid, &registry_type.to_string())
         .await
-        .inspect_err(|e| {
+    {
+        Ok(key) => key,
+        Err(SqlError::SqlxError(sqlx::Error::RowNotFound)) => {
+            debug!("No artifact key found for card {uid}, skipping artifact cleanup");
+            return Ok(());
+        }
+        Err(e) => {
             error!("Failed to get artifact key: {e}");
-        })?;
+            return Err(e.into());
+        }
+    };
+
+    let storage_path = key.storage_path();
+    if !storage_client.exists(&storage_path).await? {
+        debug!("Artifacts for card {uid} already removed");
+        return Ok(());
+    }
 
     storage_client
-        .rm(&key.storage_path(), true)
+        .rm(&storage_path, true)
         .await
         .inspect_err(|e| {
             error!("Failed to remove artifact: {e}");
         })?;
 
-    sql_client
-        .delete_artifact_key(&uid, &registry_type.to_string())
-        .await
-        .inspect_err(|e| {
-            error!("Failed to delete artifact key: {e}");
-        })?;
-
     Ok(())
 }
@@ -2,6 +2,7 @@ use axum::http::StatusCode;
 use axum::Json;
 use opsml_auth::error::AuthError;
 use opsml_crypt::error::CryptError;
+use opsml_events::error::EventError;
 use opsml_semver::error::VersionError;
 use opsml_sql::error::SqlError;
 use opsml_storage::storage::error::StorageError;
@@ -191,6 +192,83 @@ impl OpsmlServerError {
         }
     }
 
+    pub fn api_token_already_exists() -> Self {
+        error!("API token already exists");
+        OpsmlServerError {
+            error: "API token with this name already exists".to_string(),
+        }
+    }
+
+    pub fn api_token_not_found() -> Self {
+        error!("API token not found");
+        OpsmlServerError {
+            error: "API token not found".to_string(),
+        }
+    }
+
+    pub fn invalid_token_scope(scope: &str) -> Self {
+        error!("Invalid token scope: {scope}");
+        OpsmlServerError {
+            error: format!("Token scope not permitted for user: {scope}"),
+        }
+    }
+
+    pub fn invalid_token_expiry(max_days: i64) -> Self {
+        error!("Invalid token expiry");
+        OpsmlServerError {
+            error: format!("Token expiry must be between 1 and {max_days} days"),
+        }
+    }
+
+    pub fn webhook_not_found() -> Self {
+        error!("Webhook not found");
+        OpsmlServerError {
+            error: "Webhook not found".to_string(),
+        }
+    }
+
+    pub fn webhook_delivery_not_found() -> Self {
+        error!("Webhook delivery not found");
+        OpsmlServerError {
+            error: "Webhook delivery not found".to_string(),
+        }
+    }
+
+    pub fn invalid_webhook(reason: &str) -> Self {
+        error!("Invalid webhook: {reason}");
+        OpsmlServerError {
+            error: format!("Invalid webhook: {reason}"),
+        }
+    }
+
+    pub fn signing_key_not_found() -> Self {
+        error!("Signing key not found");
+        OpsmlServerError {
+            error: "Signing key not found".to_string(),
+        }
+    }
+
+    pub fn signing_key_exists() -> Self {
+        error!("Signing key already registered");
+        OpsmlServerError {
+            error: "Signing key already registered".to_string(),
+        }
+    }
+
+    pub fn invalid_signing_key(reason: &str) -> Self {
+        error!("Invalid signing key: {reason}");
+        OpsmlServerError {
+            error: format!("Invalid signing key: {reason}"),
+        }
+    }
+
+    pub fn card_not_found() -> Self {
+        error!("Card not found");
+        OpsmlServerError {
+            error: "Card not found".to_string(),
+        }
+    }
+
     pub fn into_response<T>(
         self,
         code: StatusCode,
@@ -258,4 +336,13 @@ pub enum ServerError {
 
     #[error(transparent)]
     StripPrefixError(#[from] std::path::StripPrefixError),
+
+    #[error("Failed to sign webhook payload: {0}")]
+    WebhookSignatureError(String),
+
+    #[error(transparent)]
+    SerdeError(#[from] serde_json::Error),
+
+    #[error(transparent)]
+    EventError(#[from] EventError),
 }
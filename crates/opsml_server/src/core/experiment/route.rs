@@ -11,9 +11,11 @@ use axum::{
 };
 
 use opsml_sql::base::SqlClient;
+use opsml_sql::comparison::compare_experiments;
+use opsml_sql::error::SqlError;
+use opsml_sql::metric::get_metric_page;
 use opsml_sql::schemas::schema::{HardwareMetricsRecord, MetricRecord, ParameterRecord};
 use opsml_types::{cards::*, contracts::*};
-use opsml_utils::utils::get_utc_datetime;
 use sqlx::types::Json as SqlxJson;
 use std::sync::Arc;
 use std::{
@@ -36,6 +38,7 @@ pub async fn insert_metrics(
                 m.value,
                 m.step,
                 m.timestamp,
+                m.created_at,
             )
         })
         .collect::<Vec<_>>();
@@ -81,6 +84,26 @@ pub async fn get_metrics(
     Ok(Json(metrics))
 }
 
+pub async fn query_metrics(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<MetricQuery>,
+) -> Result<Json<MetricPage>, (StatusCode, Json<OpsmlServerError>)> {
+    let page = get_metric_page(state.sql_client.as_ref(), &req)
+        .await
+        .map_err(|e| match e {
+            SqlError::InvalidMaxPoints(_) => (
+                StatusCode::BAD_REQUEST,
+                Json(OpsmlServerError::new(e.to_string())),
+            ),
+            _ => {
+                error!("Failed to query metrics: {e}");
+                internal_server_error(e, "Failed to query metrics")
+            }
+        })?;
+
+    Ok(Json(page))
+}
+
 pub async fn get_grouped_metrics(
     State(state): State<Arc<AppState>>,
 
@@ -208,11 +231,10 @@ pub async fn insert_hardware_metrics(
     State(state): State<Arc<AppState>>,
     Json(req): Json<HardwareMetricRequest>,
 ) -> Result<Json<HardwareMetricResponse>, (StatusCode, Json<OpsmlServerError>)> {
-    let created_at = get_utc_datetime();
-
+    // keep the time the metrics were collected, hardware metrics may be synced long after
     let record = HardwareMetricsRecord {
         experiment_uid: req.experiment_uid.clone(),
-        created_at,
+        created_at: req.metrics.created_at,
         cpu_percent_utilization: req.metrics.cpu.cpu_percent_utilization,
         cpu_percent_per_core: SqlxJson(req.metrics.cpu.cpu_percent_per_core.clone()),
         free_memory: req.metrics.memory.free_memory,
@@ -275,6 +297,26 @@ pub async fn get_hardware_metrics(
     Ok(Json(metrics))
 }
 
+pub async fn compare_experiment_runs(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<ExperimentComparisonRequest>,
+) -> Result<Json<ExperimentComparison>, (StatusCode, Json<OpsmlServerError>)> {
+    let comparison = compare_experiments(state.sql_client.as_ref(), &req)
+        .await
+        .map_err(|e| match e {
+            SqlError::InvalidExperimentComparison(_) => (
+                StatusCode::BAD_REQUEST,
+                Json(OpsmlServerError::new(e.to_string())),
+            ),
+            _ => {
+                error!("Failed to compare experiments: {e}");
+                internal_server_error(e, "Failed to compare experiments")
+            }
+        })?;
+
+    Ok(Json(comparison))
+}
+
 pub async fn get_experiment_router(prefix: &str) -> Result<Router<Arc<AppState>>> {
     let result = catch_unwind(AssertUnwindSafe(|| {
         Router::new()
@@ -282,6 +324,10 @@ pub async fn get_experiment_router(prefix: &str) -> Result<Router<Arc<AppState>>
                 &format!("{prefix}/experiment/metrics"),
                 put(insert_metrics).post(get_metrics),
             )
+            .route(
+                &format!("{prefix}/experiment/metrics/query"),
+                post(query_metrics),
+            )
             .route(
                 &format!("{prefix}/experiment/metrics/grouped"),
                 post(get_grouped_metrics),
@@ -294,6 +340,10 @@ pub async fn get_experiment_router(prefix: &str) -> Result<Router<Arc<AppState>>
                 &format!("{prefix}/experiment/parameters"),
                 put(insert_parameters).post(get_parameter),
             )
+            .route(
+                &format!("{prefix}/experiment/compare"),
+                post(compare_experiment_runs),
+            )
             .route(
                 &format!("{prefix}/experiment/hardware/metrics"),
                 put(insert_hardware_metrics).get(get_hardware_metrics),
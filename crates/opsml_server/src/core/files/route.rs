@@ -1,6 +1,6 @@
 use crate::core::error::internal_server_error;
 use crate::core::error::OpsmlServerError;
-use crate::core::files::utils::download_artifact;
+use crate::core::files::utils::{download_artifact, get_unsealed_artifact_key};
 use crate::core::state::AppState;
 use axum::extract::DefaultBodyLimit;
 use axum::extract::Multipart;
@@ -16,7 +16,6 @@ use axum::{
 };
 use headers::HeaderMap;
 use opsml_auth::permission::UserPermissions;
-use opsml_sql::base::SqlClient;
 use opsml_types::{contracts::*, StorageType, MAX_FILE_SIZE};
 
 use tokio::fs::File;
@@ -441,6 +440,7 @@ pub async fn get_file_for_ui(
     download_artifact(
         state.storage_client.clone(),
         state.sql_client.clone(),
+        state.key_provider.as_ref(),
         &lpath,
         &file.name,
         &req.registry_type.to_string(),
@@ -570,14 +570,17 @@ pub async fn get_artifact_key(
     Query(req): Query<ArtifactKeyRequest>,
 ) -> Result<Json<ArtifactKey>, (StatusCode, Json<OpsmlServerError>)> {
     debug!("Getting artifact key for: {:?}", req);
-    let key = state
-        .sql_client
-        .get_artifact_key(&req.uid, &req.registry_type.to_string())
-        .await
-        .map_err(|e| {
-            error!("Failed to get artifact key: {e}");
-            internal_server_error(e, "Failed to get artifact key")
-        })?;
+    let key = get_unsealed_artifact_key(
+        &state.sql_client,
+        state.key_provider.as_ref(),
+        &req.uid,
+        &req.registry_type.to_string(),
+    )
+    .await
+    .map_err(|e| {
+        error!("Failed to get artifact key: {e}");
+        internal_server_error(e, "Failed to get artifact key")
+    })?;
 
     Ok(Json(key))
 }
@@ -2,7 +2,8 @@ use crate::core::error::ServerError;
 use anyhow::Result;
 use opsml_crypt::{
     decrypt_directory, decrypt_file, encrypt_directory,
-    key::{derive_encryption_key, encrypted_key, generate_salt},
+    key::{encrypted_key, generate_key},
+    KeyProvider,
 };
 use opsml_sql::base::SqlClient;
 use opsml_sql::enums::client::SqlClientEnum;
@@ -23,24 +24,52 @@ use uuid::Uuid;
 #[instrument(skip_all)]
 pub async fn create_artifact_key(
     sql_client: &SqlClientEnum,
-    encryption_key: &[u8],
+    key_provider: &dyn KeyProvider,
     uid: &str,
     space: &str,
     registry_type: &str,
     storage_key: &str,
+    has_manifest: bool,
 ) -> Result<ArtifactKey, ServerError> {
     debug!(
         "Creating artifact key for: {:?} and path {:?}",
         uid, storage_key
     );
-    let salt = generate_salt()?;
+    let artifact_encryption_key = generate_key()?;
+
+    store_artifact_key(
+        sql_client,
+        key_provider,
+        &artifact_encryption_key,
+        uid,
+        space,
+        registry_type,
+        storage_key,
+        has_manifest,
+    )
+    .await
+}
 
-    // create derived key
-    let derived_key = derive_encryption_key(encryption_key, &salt, registry_type.as_bytes())?;
+/// Stores an existing artifact encryption key for a card, e.g. the key of a card imported
+/// from another registry. The key is stored wrapped by the server key provider and returned
+/// in the form sent to clients. `has_manifest` records whether the artifacts of the card
+/// include a manifest
+#[instrument(skip_all)]
+#[allow(clippy::too_many_arguments)]
+pub async fn store_artifact_key(
+    sql_client: &SqlClientEnum,
+    key_provider: &dyn KeyProvider,
+    artifact_encryption_key: &[u8],
+    uid: &str,
+    space: &str,
+    registry_type: &str,
+    storage_key: &str,
+    has_manifest: bool,
+) -> Result<ArtifactKey, ServerError> {
     let uid_key = uid_to_byte_key(uid)?;
 
     // encrypt key before sending
-    let encrypted_key = encrypted_key(&uid_key, &derived_key)?;
+    let encrypted_key = encrypted_key(&uid_key, artifact_encryption_key)?;
 
     // spawn a task to insert the key into the database
 
@@ -50,11 +79,12 @@ pub async fn create_artifact_key(
         registry_type: RegistryType::from_string(registry_type)?,
         encrypted_key,
         storage_key: storage_key.to_string(),
+        has_manifest,
     };
 
     // Spawn task and add to managed set
     sql_client
-        .insert_artifact_key(&artifact_key)
+        .insert_artifact_key(&artifact_key.seal(key_provider).await?)
         .await
         .inspect_err(|e| {
             error!("Failed to insert artifact key: {e}");
@@ -63,6 +93,20 @@ pub async fn create_artifact_key(
     Ok(artifact_key)
 }
 
+/// Gets the artifact key of a card, unwrapped by the server key provider into the form
+/// returned to clients
+#[instrument(skip_all)]
+pub async fn get_unsealed_artifact_key(
+    sql_client: &SqlClientEnum,
+    key_provider: &dyn KeyProvider,
+    uid: &str,
+    registry_type: &str,
+) -> Result<ArtifactKey, ServerError> {
+    let key = sql_client.get_artifact_key(uid, registry_type).await?;
+
+    Ok(key.unseal(key_provider).await?)
+}
+
 pub async fn create_and_store_encrypted_file(
     storage_client: Arc<StorageClientEnum>,
     content: &str,
@@ -111,6 +155,7 @@ pub async fn create_and_store_encrypted_file(
 pub async fn download_artifact(
     storage_client: Arc<StorageClientEnum>,
     sql_client: Arc<SqlClientEnum>,
+    key_provider: &dyn KeyProvider,
     lpath: &Path,
     rpath: &str,
     registry_type: &str,
@@ -138,7 +183,7 @@ pub async fn download_artifact(
         return Err(ServerError::ArtifactKeyNotFound);
     }
 
-    let key = key.unwrap();
+    let key = key.unwrap().unseal(key_provider).await?;
     let rpath = PathBuf::from(rpath);
 
     // Check if file exists in storage
@@ -177,13 +222,13 @@ pub async fn download_artifact(
 pub async fn download_artifacts(
     storage_client: Arc<StorageClientEnum>,
     sql_client: Arc<SqlClientEnum>,
+    key_provider: &dyn KeyProvider,
     lpath: &Path,
     rpath: &Path,
     registry_type: &str,
     uid: Option<&str>,
 ) -> Result<DownloadResponse, ServerError> {
-    let key = sql_client
-        .get_artifact_key(uid.unwrap(), registry_type)
+    let key = get_unsealed_artifact_key(&sql_client, key_provider, uid.unwrap(), registry_type)
         .await
         .inspect_err(|e| {
             error!("Failed to get artifact key: {e}");
@@ -222,7 +267,7 @@ pub async fn download_artifacts(
 
 pub async fn get_artifact_key(
     sql_client: &SqlClientEnum,
-    encryption_key: &[u8],
+    key_provider: &dyn KeyProvider,
     registry_type: &str,
     space: &str,
     storage_key: &str,
@@ -233,16 +278,18 @@ pub async fn get_artifact_key(
         .inspect_err(|e| {
             error!("Failed to get artifact key: {e}");
         })? {
-        Some(key) => Ok(key),
+        Some(key) => Ok(key.unseal(key_provider).await?),
         None => {
+            // files stored by path, such as readmes, are not recorded in a card manifest
             let uid = Uuid::new_v4().to_string();
             create_artifact_key(
                 sql_client,
-                encryption_key,
+                key_provider,
                 &uid,
                 space,
                 registry_type,
                 storage_key,
+                false,
             )
             .await
         }
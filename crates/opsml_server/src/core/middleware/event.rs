@@ -1,4 +1,5 @@
 use crate::core::audit::schema::AuditError;
+use crate::core::outbox::enqueue_events;
 use crate::core::state::AppState;
 use axum::http::StatusCode;
 use axum::middleware::Next;
@@ -14,10 +15,17 @@ use axum_extra::TypedHeader;
 use headers::UserAgent;
 use opsml_events::create_audit_event;
 use opsml_events::{AuditContext, Event};
-use opsml_types::contracts::SpaceNameEvent;
+use opsml_types::contracts::{CardEvent, SpaceNameEvent};
 use std::net::SocketAddr;
 use std::sync::Arc;
+use tracing::error;
 
+/// Writes the events attached to a response to the event outbox.
+///
+/// Audit events are built from the request and response, so they are written after the
+/// handler has committed its change and are not part of its transaction. If the server stops
+/// between the two writes the audit event is lost. Events that must not be lost are written
+/// by the handlers in the same transaction as the change instead.
 pub async fn event_middleware(
     State(state): State<Arc<AppState>>,
     ConnectInfo(addr): ConnectInfo<SocketAddr>,
@@ -31,19 +39,33 @@ pub async fn event_middleware(
     // Process the request
     let mut response = next.run(request).await;
 
+    let mut events = Vec::new();
+
     // Handle audit events
     if let Some(ctx) = response.extensions().get::<AuditContext>().cloned() {
         let audit_event =
             create_audit_event(addr, agent.clone(), headers.clone(), path.clone(), ctx);
-        state.event_bus.publish(Event::Audit(audit_event));
+        events.push(Event::Audit(audit_event));
         response.extensions_mut().remove::<AuditContext>();
     }
 
     // Handle space name events
     if let Some(event) = response.extensions().get::<SpaceNameEvent>().cloned() {
-        state.event_bus.publish(Event::SpaceName(event));
+        events.push(Event::SpaceName(event));
         response.extensions_mut().remove::<SpaceNameEvent>();
     }
 
+    // Handle card lifecycle events
+    if let Some(event) = response.extensions().get::<CardEvent>().cloned() {
+        events.push(Event::Card(event));
+        response.extensions_mut().remove::<CardEvent>();
+    }
+
+    if !events.is_empty() {
+        if let Err(e) = enqueue_events(&state, &events).await {
+            error!("Failed to write events to outbox: {e}");
+        }
+    }
+
     Ok(response)
 }
@@ -8,11 +8,16 @@ pub mod experiment;
 pub mod files;
 pub mod health;
 pub mod middleware;
+pub mod outbox;
 pub mod router;
 pub mod scouter;
 pub mod settings;
 pub mod setup;
 pub mod shutdown;
+pub mod signing;
 pub mod state;
+pub mod stream;
+pub mod trash;
 pub mod ui;
 pub mod user;
+pub mod webhook;
@@ -0,0 +1,148 @@
+use crate::core::error::ServerError;
+use crate::core::state::AppState;
+use crate::core::webhook::handler::dispatch_card_event;
+use opsml_events::{
+    event::{insert_space_name_record, log_audit_event},
+    Event,
+};
+use opsml_sql::base::SqlClient;
+use opsml_utils::utils::{create_uuid7, get_utc_datetime};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task;
+use tokio::time::MissedTickBehavior;
+use tracing::{debug, error, info, instrument};
+
+/// Maximum number of outbox events processed per batch
+const OUTBOX_BATCH_SIZE: i32 = 100;
+
+/// How often the outbox is checked when the dispatcher has not been woken
+const OUTBOX_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How long claimed events are reserved for a dispatcher before another may claim them
+const OUTBOX_LEASE: chrono::Duration = chrono::Duration::minutes(5);
+
+/// Number of times an event is handled before it is parked
+pub const OUTBOX_MAX_ATTEMPTS: i32 = 5;
+
+/// Writes events to the outbox and wakes the [`OutboxDispatcher`] to deliver them.
+///
+/// This is a separate write, so it is only used for events that are not tied to a database
+/// change, such as audit events and changes made in other services
+#[instrument(skip_all)]
+pub async fn enqueue_events(state: &AppState, events: &[Event]) -> Result<(), ServerError> {
+    let records = events
+        .iter()
+        .map(Event::to_outbox)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    state.sql_client.insert_outbox_events(&records).await?;
+    state.outbox_notify.notify_one();
+
+    Ok(())
+}
+
+/// Delivers outbox events to the audit log, space name registry and webhooks, then
+/// publishes them on the event bus for live subscribers.
+///
+/// Delivery is at least once. Each dispatcher claims a batch of events with a lease, so
+/// server replicas never dispatch the same events concurrently, and deletes each event once
+/// it has been handled. If a handler fails, the batch stops at that event and the remaining
+/// claims are released so they are retried on the next pass. An event that fails
+/// [`OUTBOX_MAX_ATTEMPTS`] times is parked in the outbox with its last error, so it no longer
+/// holds back the events behind it. Events claimed by a server that stopped are claimed again
+/// once their lease expires.
+pub struct OutboxDispatcher {
+    state: Arc<AppState>,
+    claimer: String,
+}
+
+impl OutboxDispatcher {
+    pub fn new(state: Arc<AppState>) -> Self {
+        Self {
+            state,
+            claimer: create_uuid7(),
+        }
+    }
+
+    pub async fn start(self) {
+        info!("Starting event outbox dispatcher");
+        task::spawn(async move {
+            let mut interval = tokio::time::interval(OUTBOX_POLL_INTERVAL);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {}
+                    _ = self.state.outbox_notify.notified() => {}
+                }
+
+                // drain full batches before waiting for the next tick
+                loop {
+                    match self.process_batch().await {
+                        Ok(processed) if processed == OUTBOX_BATCH_SIZE as usize => continue,
+                        Ok(_) => break,
+                        Err(e) => {
+                            error!("Failed to process event outbox: {e}");
+                            break;
+                        }
+                    }
+                }
+            }
+        });
+    }
+
+    /// Processes the next batch of pending events and returns the number of events claimed
+    async fn process_batch(&self) -> Result<usize, ServerError> {
+        let sql_client = &self.state.sql_client;
+
+        let records = sql_client
+            .claim_outbox_events(
+                &self.claimer,
+                get_utc_datetime() + OUTBOX_LEASE,
+                OUTBOX_BATCH_SIZE,
+            )
+            .await?;
+
+        for record in &records {
+            match Event::from_outbox(record) {
+                Ok(event) => {
+                    if let Err(e) = self.handle_event(event).await {
+                        error!("Failed to handle outbox event {}: {e}", record.id);
+                        sql_client
+                            .fail_outbox_event(record.id, &e.to_string(), OUTBOX_MAX_ATTEMPTS)
+                            .await?;
+                        sql_client.release_outbox_events(&self.claimer).await?;
+                        return Ok(0);
+                    }
+                }
+                // an event that cannot be decoded will never succeed, so it is dropped
+                Err(e) => error!("Dropping invalid outbox event {}: {e}", record.id),
+            }
+            sql_client.delete_outbox_event(record.id).await?;
+        }
+
+        if let Some(last) = records.last() {
+            debug!("Processed outbox events up to {}", last.id);
+        }
+
+        Ok(records.len())
+    }
+
+    async fn handle_event(&self, event: Event) -> Result<(), ServerError> {
+        let sql_client = self.state.sql_client.clone();
+
+        match &event {
+            Event::Audit(record) => log_audit_event(record.clone(), sql_client).await?,
+            Event::SpaceName(record) => {
+                insert_space_name_record(record.clone(), sql_client).await?
+            }
+            Event::Card(record) => dispatch_card_event(&self.state, record.clone()).await?,
+            Event::Space(_) => {}
+        }
+
+        self.state.event_bus.publish(event);
+
+        Ok(())
+    }
+}
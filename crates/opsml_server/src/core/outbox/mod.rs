@@ -0,0 +1,2 @@
+pub mod handler;
+pub use handler::{enqueue_events, OutboxDispatcher};
@@ -1,3 +1,4 @@
+use crate::core::audit::route::get_audit_router;
 use crate::core::auth::middleware::auth_api_middleware;
 use crate::core::auth::route::get_auth_router;
 use crate::core::cards::route::get_card_router;
@@ -9,9 +10,12 @@ use crate::core::middleware::event::event_middleware;
 use crate::core::middleware::metrics::track_metrics;
 use crate::core::scouter::route::get_scouter_router;
 use crate::core::settings::route::get_settings_router;
+use crate::core::signing::route::get_signing_router;
 use crate::core::state::AppState;
+use crate::core::stream::route::get_stream_router;
 use crate::core::ui::get_ui_router;
 use crate::core::user::route::get_user_router;
+use crate::core::webhook::route::get_webhook_router;
 use anyhow::Result;
 use axum::http::{
     header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE},
@@ -44,6 +48,10 @@ pub async fn create_router(app_state: Arc<AppState>) -> Result<Router> {
     let auth_routes = get_auth_router(ROUTE_PREFIX).await?;
     let user_routes = get_user_router(ROUTE_PREFIX).await?;
     let scouter_routes = get_scouter_router(ROUTE_PREFIX).await?;
+    let audit_routes = get_audit_router(ROUTE_PREFIX).await?;
+    let webhook_routes = get_webhook_router(ROUTE_PREFIX).await?;
+    let signing_routes = get_signing_router(ROUTE_PREFIX).await?;
+    let stream_routes = get_stream_router(ROUTE_PREFIX).await?;
     let ui_routes = get_ui_router().await?;
 
     // merge all the routes except the auth routes
@@ -55,6 +63,10 @@ pub async fn create_router(app_state: Arc<AppState>) -> Result<Router> {
         .merge(run_routes)
         .merge(user_routes)
         .merge(scouter_routes)
+        .merge(audit_routes)
+        .merge(webhook_routes)
+        .merge(signing_routes)
+        .merge(stream_routes)
         .route_layer(middleware::from_fn_with_state(
             // Audit middleware occurs last.
             //Audit middleware passes the request to the request handler
@@ -1,5 +1,6 @@
 use crate::core::error::{internal_server_error, OpsmlServerError};
-use crate::core::files::utils::download_artifacts;
+use crate::core::files::utils::{download_artifacts, get_unsealed_artifact_key};
+use crate::core::outbox::enqueue_events;
 use crate::core::scouter;
 
 use crate::core::scouter::types::DriftProfileResult;
@@ -14,11 +15,10 @@ use axum::{
     Extension, Json, Router,
 };
 use opsml_auth::permission::UserPermissions;
-use opsml_events::AuditContext;
-use opsml_sql::base::SqlClient;
+use opsml_events::{AuditContext, Event};
 use opsml_types::api::RequestType;
-use opsml_types::contracts::Operation;
 use opsml_types::contracts::ResourceType;
+use opsml_types::contracts::{CardEvent, CardEventType, Operation};
 use opsml_types::contracts::{DriftProfileRequest, UpdateProfileRequest};
 use opsml_types::{Alive, RegistryType};
 use reqwest::Response;
@@ -103,6 +103,7 @@ pub async fn insert_drift_profile(
         registry_type: None,
         operation: Operation::Create,
         access_location: None,
+        space: Some(body.space.clone()),
     };
 
     response.extensions_mut().insert(audit_context);
@@ -123,14 +124,17 @@ pub async fn update_drift_profile(
         return OpsmlServerError::permission_denied().into_response(StatusCode::FORBIDDEN);
     }
 
-    let artifact_key = state
-        .sql_client
-        .get_artifact_key(&req.uid, &RegistryType::Model.to_string())
-        .await
-        .map_err(|e| {
-            error!("Failed to get artifact key: {e}");
-            internal_server_error(e, "Failed to get artifact key")
-        })?;
+    let artifact_key = get_unsealed_artifact_key(
+        &state.sql_client,
+        state.key_provider.as_ref(),
+        &req.uid,
+        &RegistryType::Model.to_string(),
+    )
+    .await
+    .map_err(|e| {
+        error!("Failed to get artifact key: {e}");
+        internal_server_error(e, "Failed to get artifact key")
+    })?;
 
     let drift_path = artifact_key.storage_path().join(&req.profile_uri);
 
@@ -213,6 +217,7 @@ pub async fn update_drift_profile(
         registry_type: None,
         operation: Operation::Update,
         access_location: Some(req.profile_uri.clone()),
+        space: Some(req.request.space.clone()),
     };
 
     response.extensions_mut().insert(audit_context);
@@ -281,11 +286,32 @@ pub async fn update_drift_profile_status(
         registry_type: None,
         operation: Operation::Update,
         access_location: None,
+        space: Some(body.space.clone()),
     };
 
     response.extensions_mut().insert(audit_context);
 
-    parse_scouter_response(response).await
+    let scouter_response = parse_scouter_response(response).await?;
+
+    // the scouter response is not returned through the event middleware, so write to the outbox directly
+    let card_event = CardEvent::new(
+        CardEventType::DriftProfileStatusChanged,
+        RegistryType::Model,
+        &body.space,
+        &body.name,
+        &perms.username,
+    )
+    .with_version(&body.version)
+    .with_details(serde_json::json!({
+        "active": body.active,
+        "drift_type": body.drift_type.as_ref().map(|dt| dt.to_string()),
+        "deactivate_others": body.deactivate_others,
+    }));
+    if let Err(e) = enqueue_events(&data, &[Event::Card(card_event)]).await {
+        error!("Failed to write drift profile event: {e}");
+    }
+
+    Ok(scouter_response)
 }
 
 #[instrument(skip(data, params))]
@@ -432,14 +458,17 @@ pub async fn get_drift_profiles_for_ui(
     Json(req): Json<DriftProfileRequest>,
 ) -> DriftProfileResult {
     // get artifact key for the given uid
-    let artifact_key = state
-        .sql_client
-        .get_artifact_key(&req.uid, &RegistryType::Model.to_string())
-        .await
-        .map_err(|e| {
-            error!("Failed to get artifact key: {e}");
-            internal_server_error(e, "Failed to get artifact key")
-        })?;
+    let artifact_key = get_unsealed_artifact_key(
+        &state.sql_client,
+        state.key_provider.as_ref(),
+        &req.uid,
+        &RegistryType::Model.to_string(),
+    )
+    .await
+    .map_err(|e| {
+        error!("Failed to get artifact key: {e}");
+        internal_server_error(e, "Failed to get artifact key")
+    })?;
 
     if !perms.has_read_permission(&artifact_key.space) {
         return OpsmlServerError::permission_denied().into_response(StatusCode::FORBIDDEN);
@@ -475,6 +504,7 @@ pub async fn get_drift_profiles_for_ui(
     download_artifacts(
         state.storage_client.clone(),
         state.sql_client.clone(),
+        state.key_provider.as_ref(),
         &dest_path,
         source_path,
         &RegistryType::Model.to_string(),
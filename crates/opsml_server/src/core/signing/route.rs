@@ -0,0 +1,159 @@
+use crate::core::error::{internal_server_error, OpsmlServerError};
+use crate::core::state::AppState;
+use anyhow::{Context, Result};
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    routing::post,
+    Extension, Json, Router,
+};
+use base64::prelude::*;
+use opsml_auth::permission::UserPermissions;
+use opsml_crypt::signing_key_id;
+use opsml_sql::base::SqlClient;
+use opsml_sql::schemas::schema::SigningKeyRecord;
+use opsml_types::contracts::{
+    CreateSigningKeyRequest, RevokeSigningKeyRequest, SigningKeyListResponse, SigningKeyQuery,
+    SpaceSigningKey,
+};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::Arc;
+use tracing::{error, info, instrument};
+
+/// Length of a raw Ed25519 public key
+const PUBLIC_KEY_LENGTH: usize = 32;
+
+/// Register the public key of a space signing key (admin only).
+/// The key id is derived from the public key, so it matches the id signers put in signatures
+#[instrument(skip_all)]
+async fn create_signing_key(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Json(request): Json<CreateSigningKeyRequest>,
+) -> Result<Json<SpaceSigningKey>, (StatusCode, Json<OpsmlServerError>)> {
+    if !perms.group_permissions.contains(&"admin".to_string()) {
+        return OpsmlServerError::need_admin_permission().into_response(StatusCode::FORBIDDEN);
+    }
+
+    if request.space.trim().is_empty() {
+        return OpsmlServerError::invalid_signing_key("space must not be empty")
+            .into_response(StatusCode::BAD_REQUEST);
+    }
+
+    let public_key = match BASE64_STANDARD.decode(&request.public_key) {
+        Ok(key) if key.len() == PUBLIC_KEY_LENGTH => key,
+        _ => {
+            return OpsmlServerError::invalid_signing_key(
+                "public_key must be a base64 encoded Ed25519 public key",
+            )
+            .into_response(StatusCode::BAD_REQUEST)
+        }
+    };
+
+    let key_id = signing_key_id(&public_key);
+
+    let keys = state.sql_client.get_signing_keys(None).await.map_err(|e| {
+        error!("Failed to get signing keys: {e}");
+        internal_server_error(e, "Failed to get signing keys")
+    })?;
+
+    if keys.iter().any(|key| key.key_id == key_id) {
+        return OpsmlServerError::signing_key_exists().into_response(StatusCode::CONFLICT);
+    }
+
+    let record = SigningKeyRecord::new(
+        &key_id,
+        &request.space,
+        &request.public_key,
+        &perms.username,
+    );
+
+    state
+        .sql_client
+        .insert_signing_key(&record)
+        .await
+        .map_err(|e| {
+            error!("Failed to insert signing key: {e}");
+            internal_server_error(e, "Failed to register signing key")
+        })?;
+
+    info!(
+        "Signing key {} registered for space {} by {}",
+        record.key_id, record.space, perms.username
+    );
+
+    Ok(Json(SpaceSigningKey::from(record)))
+}
+
+/// List registered signing keys, including revoked keys. Public keys are not secret, so any
+/// authenticated user can list them to verify card signatures
+#[instrument(skip_all)]
+async fn list_signing_keys(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<SigningKeyQuery>,
+) -> Result<Json<SigningKeyListResponse>, (StatusCode, Json<OpsmlServerError>)> {
+    let keys = state
+        .sql_client
+        .get_signing_keys(params.space.as_deref())
+        .await
+        .map_err(|e| {
+            error!("Failed to get signing keys: {e}");
+            internal_server_error(e, "Failed to get signing keys")
+        })?;
+
+    Ok(Json(SigningKeyListResponse {
+        keys: keys.into_iter().map(SpaceSigningKey::from).collect(),
+    }))
+}
+
+/// Revoke a signing key (admin only). Cards signed with a revoked key no longer verify
+#[instrument(skip_all)]
+async fn revoke_signing_key(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Query(params): Query<RevokeSigningKeyRequest>,
+) -> Result<StatusCode, (StatusCode, Json<OpsmlServerError>)> {
+    if !perms.group_permissions.contains(&"admin".to_string()) {
+        return OpsmlServerError::need_admin_permission().into_response(StatusCode::FORBIDDEN);
+    }
+
+    let revoked = state
+        .sql_client
+        .revoke_signing_key(&params.key_id)
+        .await
+        .map_err(|e| {
+            error!("Failed to revoke signing key: {e}");
+            internal_server_error(e, "Failed to revoke signing key")
+        })?;
+
+    if !revoked {
+        return OpsmlServerError::signing_key_not_found().into_response(StatusCode::NOT_FOUND);
+    }
+
+    info!(
+        "Signing key {} revoked by {}",
+        params.key_id, perms.username
+    );
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn get_signing_router(prefix: &str) -> Result<Router<Arc<AppState>>> {
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        Router::new().route(
+            &format!("{prefix}/signing_key"),
+            post(create_signing_key)
+                .get(list_signing_keys)
+                .delete(revoke_signing_key),
+        )
+    }));
+
+    match result {
+        Ok(router) => Ok(router),
+        Err(_) => {
+            error!("Failed to create signing key router");
+            Err(anyhow::anyhow!("Failed to create signing key router"))
+                .context("Panic occurred while creating the router")
+        }
+    }
+}
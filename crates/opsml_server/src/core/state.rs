@@ -2,12 +2,14 @@ use crate::core::error::ServerError;
 use crate::core::scouter::client::ScouterApiClient;
 use opsml_auth::auth::AuthManager;
 use opsml_auth::permission::UserPermissions;
+use opsml_crypt::KeyProvider;
 use opsml_events::EventBus;
 use opsml_settings::config::{OpsmlConfig, OpsmlStorageSettings};
 use opsml_sql::base::SqlClient;
 use opsml_sql::enums::client::SqlClientEnum;
 use opsml_storage::storage::enums::client::StorageClientEnum;
 use std::sync::Arc;
+use tokio::sync::Notify;
 use tracing::error;
 
 pub struct AppState {
@@ -16,8 +18,12 @@ pub struct AppState {
     pub auth_manager: AuthManager,
     pub config: OpsmlConfig,
     pub storage_settings: OpsmlStorageSettings,
+    /// Wraps the artifact keys and webhook secrets stored in the database
+    pub key_provider: Arc<dyn KeyProvider>,
     pub scouter_client: ScouterApiClient,
     pub event_bus: EventBus,
+    /// Wakes the outbox dispatcher when new events are written
+    pub outbox_notify: Arc<Notify>,
 }
 
 impl AppState {
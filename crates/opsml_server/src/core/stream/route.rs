@@ -0,0 +1,143 @@
+use crate::core::state::AppState;
+use anyhow::{Context, Result};
+use axum::{
+    extract::{Query, State},
+    http::HeaderMap,
+    response::sse::{Event as SseEvent, KeepAlive, Sse},
+    routing::get,
+    Extension, Router,
+};
+use opsml_auth::permission::UserPermissions;
+use opsml_events::{Event, SequencedEvent};
+use opsml_types::contracts::{EventStreamQuery, EventStreamReset, RegistryEvent};
+use std::convert::Infallible;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::Arc;
+use tokio_stream::{Stream, StreamExt};
+use tracing::{debug, error, instrument, warn};
+
+const LAST_EVENT_ID_HEADER: &str = "Last-Event-ID";
+
+/// Converts a bus event into a stream event if it passes the caller's filters and read permissions
+fn to_sse_event(
+    sequenced: SequencedEvent,
+    params: &EventStreamQuery,
+    perms: &UserPermissions,
+) -> Option<SseEvent> {
+    let event = match sequenced.event {
+        Event::Card(event) => RegistryEvent::Card(event),
+        Event::Space(event) => RegistryEvent::Space(event),
+        _ => return None,
+    };
+
+    if !perms.has_read_permission(event.space()) {
+        return None;
+    }
+
+    if params.space.as_deref().is_some_and(|s| s != event.space()) {
+        return None;
+    }
+
+    // space events are not tied to a registry and pass the registry filter
+    if let (Some(filter), Some(registry_type)) = (&params.registry_type, event.registry_type()) {
+        if filter != registry_type {
+            return None;
+        }
+    }
+
+    match SseEvent::default()
+        .id(sequenced.id.to_string())
+        .event(event.event_type())
+        .json_data(&event)
+    {
+        Ok(sse_event) => Some(sse_event),
+        Err(e) => {
+            error!("Failed to serialize stream event: {e}");
+            None
+        }
+    }
+}
+
+/// Tells a resuming client that events after its last event were dropped from the history.
+/// The event id points right before the oldest buffered event, so a reconnect does not report
+/// the same gap again
+fn to_reset_event(last_event_id: u64, first_event_id: u64) -> Option<SseEvent> {
+    let reset = EventStreamReset {
+        last_event_id,
+        first_event_id,
+    };
+
+    match SseEvent::default()
+        .id((first_event_id - 1).to_string())
+        .event(EventStreamReset::EVENT_TYPE)
+        .json_data(&reset)
+    {
+        Ok(sse_event) => Some(sse_event),
+        Err(e) => {
+            error!("Failed to serialize stream reset event: {e}");
+            None
+        }
+    }
+}
+
+/// Stream card and space events as Server-Sent Events
+///
+/// Events the caller cannot read are skipped. Clients resume after a disconnect by sending
+/// the id of the last received event, either as the `Last-Event-ID` header or the
+/// `last_event_id` query parameter. If that event is older than the buffered history, a `reset`
+/// event is sent first since events were missed. The stream is closed if the client falls too
+/// far behind, so it reconnects and replays the events it missed from history.
+#[instrument(skip_all)]
+async fn stream_events(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Query(params): Query<EventStreamQuery>,
+    headers: HeaderMap,
+) -> Sse<impl Stream<Item = Result<SseEvent, Infallible>>> {
+    let last_event_id = headers
+        .get(LAST_EVENT_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok())
+        .or(params.last_event_id);
+
+    debug!(
+        "Starting event stream for {} (resume from {:?})",
+        perms.username, last_event_id
+    );
+
+    let (replay, live) = state.event_bus.subscribe_from(last_event_id);
+
+    let reset = match (last_event_id, replay.gap) {
+        (Some(last_id), Some(first_id)) => {
+            warn!(
+                "Events after {} are no longer buffered for {}, sending reset",
+                last_id, perms.username
+            );
+            to_reset_event(last_id, first_id)
+        }
+        _ => None,
+    };
+
+    let events = tokio_stream::iter(replay.events)
+        .chain(live)
+        .filter_map(move |sequenced| to_sse_event(sequenced, &params, &perms));
+
+    let stream = tokio_stream::iter(reset).chain(events).map(Ok);
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+pub async fn get_stream_router(prefix: &str) -> Result<Router<Arc<AppState>>> {
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        Router::new().route(&format!("{prefix}/events/stream"), get(stream_events))
+    }));
+
+    match result {
+        Ok(router) => Ok(router),
+        Err(_) => {
+            error!("Failed to create stream router");
+            Err(anyhow::anyhow!("Failed to create stream router"))
+                .context("Panic occurred while creating the router")
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use crate::core::cards::utils::cleanup_artifacts;
+use crate::core::error::ServerError;
+use crate::core::state::AppState;
+use chrono::Duration as ChronoDuration;
+use opsml_events::Event;
+use opsml_sql::base::SqlClient;
+use opsml_sql::enums::client::SqlClientEnum;
+use opsml_sql::schemas::DeletedCardRecord;
+use opsml_storage::StorageClientEnum;
+use opsml_types::cards::CardTable;
+use opsml_types::contracts::{CardEvent, CardEventType};
+use opsml_types::RegistryType;
+use opsml_utils::get_utc_datetime;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task;
+use tokio::time::MissedTickBehavior;
+use tracing::{error, info, instrument};
+
+/// How often the trash is checked for cards past the retention period
+const TRASH_PURGE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Registries whose deleted cards are purged
+const PURGED_REGISTRIES: [RegistryType; 6] = [
+    RegistryType::Data,
+    RegistryType::Model,
+    RegistryType::Experiment,
+    RegistryType::Audit,
+    RegistryType::Prompt,
+    RegistryType::Service,
+];
+
+/// Permanently removes cards that have been in the trash for longer than `retention_days`.
+///
+/// For each card the stored artifacts are removed first, then the card row and its artifact
+/// key are deleted in one transaction. A card that fails to purge is logged and left in the
+/// trash, so it is retried on the next pass without blocking the other cards.
+///
+/// # Returns
+///
+/// * `usize` - The number of purged cards
+#[instrument(skip_all)]
+pub async fn purge_expired_cards(
+    sql_client: &Arc<SqlClientEnum>,
+    storage_client: &Arc<StorageClientEnum>,
+    retention_days: i64,
+) -> Result<usize, ServerError> {
+    let cutoff = get_utc_datetime() - ChronoDuration::days(retention_days);
+    let mut purged = 0;
+
+    for registry_type in PURGED_REGISTRIES {
+        let table = CardTable::from_registry_type(&registry_type);
+        let expired = sql_client
+            .get_deleted_cards(&table, None, Some(cutoff))
+            .await?;
+
+        for card in expired {
+            match purge_card(sql_client, storage_client, &table, &registry_type, &card).await {
+                Ok(()) => purged += 1,
+                Err(e) => error!("Failed to purge card {}: {e}", card.uid),
+            }
+        }
+    }
+
+    Ok(purged)
+}
+
+async fn purge_card(
+    sql_client: &Arc<SqlClientEnum>,
+    storage_client: &Arc<StorageClientEnum>,
+    table: &CardTable,
+    registry_type: &RegistryType,
+    card: &DeletedCardRecord,
+) -> Result<(), ServerError> {
+    cleanup_artifacts(storage_client, sql_client, &card.uid, registry_type).await?;
+
+    let event = Event::Card(
+        CardEvent::new(
+            CardEventType::CardPurged,
+            registry_type.clone(),
+            &card.space,
+            &card.name,
+            &card.deleted_by,
+        )
+        .with_uid(&card.uid)
+        .with_version(&card.version),
+    )
+    .to_outbox()?;
+
+    sql_client.delete_card(table, &card.uid, &[event]).await?;
+
+    Ok(())
+}
+
+/// Periodically purges cards that have been in the trash for longer than the configured
+/// retention period (`OPSML_TRASH_RETENTION_DAYS`)
+pub struct TrashPurger {
+    state: Arc<AppState>,
+}
+
+impl TrashPurger {
+    pub fn new(state: Arc<AppState>) -> Self {
+        Self { state }
+    }
+
+    pub async fn start(self) {
+        info!(
+            "Starting trash purger with a retention of {} days",
+            self.state.config.trash_retention_days
+        );
+        task::spawn(async move {
+            let mut interval = tokio::time::interval(TRASH_PURGE_INTERVAL);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+            loop {
+                interval.tick().await;
+
+                match purge_expired_cards(
+                    &self.state.sql_client,
+                    &self.state.storage_client,
+                    self.state.config.trash_retention_days,
+                )
+                .await
+                {
+                    Ok(0) => {}
+                    Ok(purged) => {
+                        info!("Purged {purged} cards from the trash");
+                        self.state.outbox_notify.notify_one();
+                    }
+                    Err(e) => error!("Failed to purge trash: {e}"),
+                }
+            }
+        });
+    }
+}
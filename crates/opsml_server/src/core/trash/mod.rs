@@ -0,0 +1,2 @@
+pub mod handler;
+pub use handler::{purge_expired_cards, TrashPurger};
@@ -2,8 +2,9 @@ use crate::core::error::{internal_server_error, OpsmlServerError};
 use crate::core::scouter;
 use crate::core::state::AppState;
 use crate::core::user::schema::{
-    CreateUserRequest, CreateUserResponse, CreateUserUiResponse, RecoveryResetRequest,
-    UpdateUserRequest, UserListResponse, UserResponse,
+    ApiTokenListResponse, ApiTokenResponse, CreateApiTokenRequest, CreateApiTokenResponse,
+    CreateServiceAccountRequest, CreateUserRequest, CreateUserResponse, CreateUserUiResponse,
+    RecoveryResetRequest, UpdateUserRequest, UserListResponse, UserResponse,
 };
 use crate::core::user::utils::get_user as get_user_from_db;
 use anyhow::{Context, Result};
@@ -16,10 +17,11 @@ use axum::{
 };
 
 use opsml_auth::permission::UserPermissions;
-use opsml_auth::util::generate_recovery_codes_with_hashes;
+use opsml_auth::util::{generate_api_token, generate_recovery_codes_with_hashes};
 use opsml_sql::base::SqlClient;
-use opsml_sql::schemas::schema::User;
+use opsml_sql::schemas::schema::{ApiToken, User};
 use opsml_types::RequestType;
+use opsml_utils::utils::get_utc_datetime;
 use password_auth::generate_hash;
 use std::panic::{catch_unwind, AssertUnwindSafe};
 use std::sync::Arc;
@@ -27,6 +29,9 @@ use tracing::{error, info, instrument};
 
 use super::schema::ResetPasswordResponse;
 
+/// Longest lifetime an API token can be created with
+const MAX_TOKEN_EXPIRY_DAYS: i64 = 3650;
+
 /// Create a new user via SDK.
 #[instrument(skip_all)]
 async fn create_user(
@@ -384,6 +389,189 @@ async fn reset_password_with_recovery(
     })))
 }
 
+/// Create a non-human service account
+///
+/// Service accounts cannot log in with a password and authenticate exclusively with API tokens.
+/// Requires admin permissions
+#[instrument(skip_all)]
+async fn create_service_account(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Json(create_req): Json<CreateServiceAccountRequest>,
+) -> Result<Json<UserResponse>, (StatusCode, Json<OpsmlServerError>)> {
+    if !perms.group_permissions.contains(&"admin".to_string()) {
+        return OpsmlServerError::need_admin_permission().into_response(StatusCode::FORBIDDEN);
+    }
+
+    if let Ok(Some(_)) = state.sql_client.get_user(&create_req.username, None).await {
+        return OpsmlServerError::user_already_exists().into_response(StatusCode::CONFLICT);
+    }
+
+    let user = User::new_service_account(
+        &create_req.username,
+        create_req.email,
+        create_req.permissions,
+        create_req.group_permissions,
+    );
+
+    if let Err(e) = state.sql_client.insert_user(&user).await {
+        error!("Failed to create service account: {e}");
+        return Err(internal_server_error(e, "Failed to create service account"));
+    }
+
+    info!("Service account {} created successfully", user.username);
+
+    Ok(Json(UserResponse::from(user)))
+}
+
+/// Create a named API token for a user
+///
+/// The raw token is only returned in this response. Users can create tokens for themselves,
+/// while admins can create tokens for any user (including service accounts).
+#[instrument(skip_all)]
+async fn create_api_token(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Path(username): Path<String>,
+    Json(create_req): Json<CreateApiTokenRequest>,
+) -> Result<Json<CreateApiTokenResponse>, (StatusCode, Json<OpsmlServerError>)> {
+    let is_admin = perms.group_permissions.contains(&"admin".to_string());
+    let is_self = perms.username == username;
+
+    if !is_admin && !is_self {
+        return OpsmlServerError::permission_denied().into_response(StatusCode::FORBIDDEN);
+    }
+
+    let user = get_user_from_db(&state.sql_client, &username, None).await?;
+
+    // scopes can only be a subset of what both the token owner and the caller are allowed to do,
+    // so a narrowly scoped token cannot mint a broader one
+    let owner_perms = UserPermissions::from_user_permissions(&user);
+    let is_allowed = |scope: &String| match scope.as_str() {
+        "admin" => user.group_permissions.contains(scope) && is_admin,
+        _ => owner_perms.grants_scope(scope) && perms.grants_scope(scope),
+    };
+
+    if let Some(scope) = create_req.scopes.iter().find(|scope| !is_allowed(scope)) {
+        return OpsmlServerError::invalid_token_scope(scope).into_response(StatusCode::BAD_REQUEST);
+    }
+
+    let existing = state
+        .sql_client
+        .get_api_tokens(&username)
+        .await
+        .map_err(|e| {
+            error!("Failed to get api tokens: {e}");
+            internal_server_error(e, "Failed to get api tokens")
+        })?;
+
+    if existing
+        .iter()
+        .any(|token| token.name == create_req.name && token.is_active())
+    {
+        return OpsmlServerError::api_token_already_exists().into_response(StatusCode::CONFLICT);
+    }
+
+    let expires_at = match create_req.expires_in_days {
+        Some(days) => {
+            if !(1..=MAX_TOKEN_EXPIRY_DAYS).contains(&days) {
+                return OpsmlServerError::invalid_token_expiry(MAX_TOKEN_EXPIRY_DAYS)
+                    .into_response(StatusCode::BAD_REQUEST);
+            }
+
+            match get_utc_datetime().checked_add_signed(chrono::Duration::days(days)) {
+                Some(expires_at) => Some(expires_at),
+                None => {
+                    return OpsmlServerError::invalid_token_expiry(MAX_TOKEN_EXPIRY_DAYS)
+                        .into_response(StatusCode::BAD_REQUEST)
+                }
+            }
+        }
+        None => None,
+    };
+
+    let (raw_token, hashed_token) = generate_api_token();
+    let token = ApiToken::new(
+        &username,
+        &create_req.name,
+        hashed_token,
+        create_req.scopes,
+        expires_at,
+    );
+
+    if let Err(e) = state.sql_client.insert_api_token(&token).await {
+        error!("Failed to create api token: {e}");
+        return Err(internal_server_error(e, "Failed to create api token"));
+    }
+
+    info!("API token {} created for user {}", token.name, username);
+
+    Ok(Json(CreateApiTokenResponse::new(
+        raw_token,
+        ApiTokenResponse::from(token),
+    )))
+}
+
+/// List API tokens for a user (hashed values are never returned)
+#[instrument(skip_all)]
+async fn list_api_tokens(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Path(username): Path<String>,
+) -> Result<Json<ApiTokenListResponse>, (StatusCode, Json<OpsmlServerError>)> {
+    let is_admin = perms.group_permissions.contains(&"admin".to_string());
+    let is_self = perms.username == username;
+
+    if !is_admin && !is_self {
+        return OpsmlServerError::permission_denied().into_response(StatusCode::FORBIDDEN);
+    }
+
+    let tokens = state
+        .sql_client
+        .get_api_tokens(&username)
+        .await
+        .map_err(|e| {
+            error!("Failed to list api tokens: {e}");
+            internal_server_error(e, "Failed to list api tokens")
+        })?;
+
+    Ok(Json(ApiTokenListResponse {
+        tokens: tokens.into_iter().map(ApiTokenResponse::from).collect(),
+    }))
+}
+
+/// Revoke an API token by name
+#[instrument(skip_all)]
+async fn revoke_api_token(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Path((username, name)): Path<(String, String)>,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<OpsmlServerError>)> {
+    let is_admin = perms.group_permissions.contains(&"admin".to_string());
+    let is_self = perms.username == username;
+
+    if !is_admin && !is_self {
+        return OpsmlServerError::permission_denied().into_response(StatusCode::FORBIDDEN);
+    }
+
+    let revoked = state
+        .sql_client
+        .revoke_api_token(&username, &name)
+        .await
+        .map_err(|e| {
+            error!("Failed to revoke api token: {e}");
+            internal_server_error(e, "Failed to revoke api token")
+        })?;
+
+    if !revoked {
+        return OpsmlServerError::api_token_not_found().into_response(StatusCode::NOT_FOUND);
+    }
+
+    info!("API token {} revoked for user {}", name, username);
+
+    Ok(Json(serde_json::json!({"success": true})))
+}
+
 pub async fn get_user_router(prefix: &str) -> Result<Router<Arc<AppState>>> {
     let result = catch_unwind(AssertUnwindSafe(|| {
         Router::new()
@@ -400,6 +588,22 @@ pub async fn get_user_router(prefix: &str) -> Result<Router<Arc<AppState>>> {
             .route(&format!("{prefix}/user/{{username}}"), get(get_user))
             .route(&format!("{prefix}/user/{{username}}"), put(update_user))
             .route(&format!("{prefix}/user/{{username}}"), delete(delete_user))
+            .route(
+                &format!("{prefix}/service-account"),
+                post(create_service_account),
+            )
+            .route(
+                &format!("{prefix}/user/{{username}}/tokens"),
+                post(create_api_token),
+            )
+            .route(
+                &format!("{prefix}/user/{{username}}/tokens"),
+                get(list_api_tokens),
+            )
+            .route(
+                &format!("{prefix}/user/{{username}}/tokens/{{name}}"),
+                delete(revoke_api_token),
+            )
     }));
 
     match result {
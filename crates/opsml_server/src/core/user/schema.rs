@@ -1,4 +1,5 @@
-use opsml_sql::schemas::schema::User;
+use chrono::{DateTime, Utc};
+use opsml_sql::schemas::schema::{ApiToken, User};
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -88,3 +89,64 @@ impl From<User> for UserResponse {
         }
     }
 }
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CreateServiceAccountRequest {
+    pub username: String,
+    pub email: Option<String>,
+    pub permissions: Option<Vec<String>>,
+    pub group_permissions: Option<Vec<String>>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CreateApiTokenRequest {
+    pub name: String,
+    pub scopes: Vec<String>,
+    pub expires_in_days: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ApiTokenResponse {
+    pub name: String,
+    pub username: String,
+    pub scopes: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub revoked: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CreateApiTokenResponse {
+    pub token: String,
+    pub info: ApiTokenResponse,
+    pub message: String,
+}
+
+impl CreateApiTokenResponse {
+    pub fn new(token: String, info: ApiTokenResponse) -> Self {
+        Self {
+            token,
+            info,
+            message: "Save this token securely. It cannot be shown again!".to_string(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ApiTokenListResponse {
+    pub tokens: Vec<ApiTokenResponse>,
+}
+
+// Convert ApiToken to ApiTokenResponse (strips hashed token)
+impl From<ApiToken> for ApiTokenResponse {
+    fn from(token: ApiToken) -> Self {
+        ApiTokenResponse {
+            name: token.name,
+            username: token.username,
+            scopes: token.scopes.0,
+            created_at: token.created_at,
+            expires_at: token.expires_at,
+            revoked: token.revoked,
+        }
+    }
+}
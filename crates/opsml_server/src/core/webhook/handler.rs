@@ -0,0 +1,287 @@
+use crate::core::error::ServerError;
+use crate::core::state::AppState;
+use hmac::{Hmac, Mac};
+use opsml_crypt::KeyProvider;
+use opsml_sql::base::SqlClient;
+use opsml_sql::enums::client::SqlClientEnum;
+use opsml_sql::schemas::schema::{
+    Webhook, WebhookDelivery, WEBHOOK_DELIVERY_FAILED, WEBHOOK_DELIVERY_SUCCESS,
+};
+use opsml_types::contracts::CardEvent;
+use opsml_utils::utils::get_utc_datetime;
+use sha2::Sha256;
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
+use tokio::task;
+use tokio::time::MissedTickBehavior;
+use tracing::{debug, error, info, instrument, warn};
+
+/// Maximum number of delivery attempts before a delivery is marked as failed
+pub const WEBHOOK_MAX_ATTEMPTS: i32 = 5;
+
+/// Delay before the first retry. Doubled after each failed attempt
+const WEBHOOK_INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How often pending deliveries left behind by a stopped server are checked
+const WEBHOOK_RESUME_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Pending deliveries not updated for this long are no longer being delivered. Deliveries in
+/// progress are updated after every attempt, which is far more often
+const WEBHOOK_STALE_AFTER: chrono::Duration = chrono::Duration::minutes(5);
+
+/// Maximum number of stale deliveries resumed per check
+const WEBHOOK_RESUME_BATCH_SIZE: i32 = 100;
+
+pub const SIGNATURE_HEADER: &str = "X-Opsml-Signature";
+pub const TIMESTAMP_HEADER: &str = "X-Opsml-Timestamp";
+pub const EVENT_HEADER: &str = "X-Opsml-Event";
+pub const DELIVERY_HEADER: &str = "X-Opsml-Delivery";
+
+static HTTP_CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
+    reqwest::Client::builder()
+        .timeout(WEBHOOK_TIMEOUT)
+        .build()
+        .unwrap_or_default()
+});
+
+/// Encrypts a webhook signing secret for storage
+pub async fn encrypt_webhook_secret(
+    key_provider: &dyn KeyProvider,
+    webhook_uid: &str,
+    secret: &str,
+) -> Result<Vec<u8>, ServerError> {
+    Ok(Webhook::encrypt_secret(key_provider, webhook_uid, secret.as_bytes()).await?)
+}
+
+/// Decrypts a stored webhook signing secret
+pub async fn decrypt_webhook_secret(
+    key_provider: &dyn KeyProvider,
+    webhook: &Webhook,
+) -> Result<Vec<u8>, ServerError> {
+    Ok(webhook.decrypt_secret(key_provider).await?)
+}
+
+/// Computes the hex encoded HMAC-SHA256 signature of a delivery.
+///
+/// The signed message is `{timestamp}.{payload}` so receivers can reject replayed requests
+/// by checking the timestamp header.
+pub fn sign_payload(secret: &[u8], timestamp: i64, payload: &str) -> Result<String, ServerError> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret)
+        .map_err(|e| ServerError::WebhookSignatureError(e.to_string()))?;
+    mac.update(format!("{timestamp}.{payload}").as_bytes());
+
+    Ok(mac
+        .finalize()
+        .into_bytes()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect())
+}
+
+/// Sends a single signed request to the webhook endpoint
+///
+/// # Returns
+/// * `(Option<i32>, Option<String>)` - The response status, if any, and an error message on failure
+async fn send_delivery(
+    webhook: &Webhook,
+    delivery: &WebhookDelivery,
+    secret: &[u8],
+) -> (Option<i32>, Option<String>) {
+    let timestamp = get_utc_datetime().timestamp();
+    let signature = match sign_payload(secret, timestamp, &delivery.payload) {
+        Ok(signature) => signature,
+        Err(e) => return (None, Some(e.to_string())),
+    };
+
+    let response = HTTP_CLIENT
+        .post(&webhook.url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header(SIGNATURE_HEADER, format!("sha256={signature}"))
+        .header(TIMESTAMP_HEADER, timestamp.to_string())
+        .header(EVENT_HEADER, &delivery.event_type)
+        .header(DELIVERY_HEADER, &delivery.uid)
+        .body(delivery.payload.clone())
+        .send()
+        .await;
+
+    match response {
+        Ok(response) if response.status().is_success() => {
+            (Some(response.status().as_u16() as i32), None)
+        }
+        Ok(response) => {
+            let status = response.status();
+            (
+                Some(status.as_u16() as i32),
+                Some(format!("Endpoint returned {status}")),
+            )
+        }
+        Err(e) => (None, Some(e.to_string())),
+    }
+}
+
+/// Delivers a pending delivery, retrying with exponential backoff.
+///
+/// The delivery record is updated after every attempt so the delivery log reflects progress.
+#[instrument(skip_all, fields(webhook = %webhook.uid, delivery = %delivery.uid))]
+pub async fn deliver_webhook(
+    sql_client: Arc<SqlClientEnum>,
+    key_provider: Arc<dyn KeyProvider>,
+    webhook: Webhook,
+    mut delivery: WebhookDelivery,
+) -> Result<WebhookDelivery, ServerError> {
+    let secret = decrypt_webhook_secret(key_provider.as_ref(), &webhook).await?;
+    let mut backoff = WEBHOOK_INITIAL_BACKOFF;
+
+    loop {
+        let (response_status, error_message) = send_delivery(&webhook, &delivery, &secret).await;
+
+        delivery.attempts += 1;
+        delivery.response_status = response_status;
+        delivery.updated_at = get_utc_datetime();
+
+        let done = match error_message {
+            None => {
+                delivery.status = WEBHOOK_DELIVERY_SUCCESS.to_string();
+                delivery.error_message = None;
+                true
+            }
+            Some(message) => {
+                warn!(
+                    "Webhook delivery attempt {} failed: {message}",
+                    delivery.attempts
+                );
+                delivery.error_message = Some(message);
+                if delivery.attempts >= WEBHOOK_MAX_ATTEMPTS {
+                    delivery.status = WEBHOOK_DELIVERY_FAILED.to_string();
+                    true
+                } else {
+                    false
+                }
+            }
+        };
+
+        sql_client.update_webhook_delivery(&delivery).await?;
+
+        if done {
+            return Ok(delivery);
+        }
+
+        tokio::time::sleep(backoff).await;
+        backoff *= 2;
+    }
+}
+
+/// Delivers a recorded delivery in the background
+fn spawn_delivery(state: &Arc<AppState>, webhook: Webhook, delivery: WebhookDelivery) {
+    let sql_client = state.sql_client.clone();
+    let key_provider = state.key_provider.clone();
+
+    task::spawn(async move {
+        if let Err(e) = deliver_webhook(sql_client, key_provider, webhook, delivery).await {
+            error!("Failed to deliver webhook: {e}");
+        }
+    });
+}
+
+/// Records a delivery in the delivery log and delivers it in the background
+pub async fn enqueue_delivery(
+    state: &Arc<AppState>,
+    webhook: Webhook,
+    delivery: WebhookDelivery,
+) -> Result<(), ServerError> {
+    state.sql_client.insert_webhook_delivery(&delivery).await?;
+    spawn_delivery(state, webhook, delivery);
+
+    Ok(())
+}
+
+/// Resumes pending deliveries that are no longer being delivered, such as deliveries whose
+/// retries were interrupted by a server restart. Attempts already made count towards
+/// [`WEBHOOK_MAX_ATTEMPTS`]. Deliveries of deleted webhooks are marked as failed.
+///
+/// # Returns
+///
+/// * `usize` - The number of resumed deliveries
+#[instrument(skip_all)]
+pub async fn resume_stale_deliveries(state: &Arc<AppState>) -> Result<usize, ServerError> {
+    let deliveries = state
+        .sql_client
+        .claim_stale_webhook_deliveries(
+            get_utc_datetime() - WEBHOOK_STALE_AFTER,
+            WEBHOOK_RESUME_BATCH_SIZE,
+        )
+        .await?;
+
+    let mut resumed = 0;
+    for mut delivery in deliveries {
+        match state.sql_client.get_webhook(&delivery.webhook_uid).await? {
+            Some(webhook) => {
+                debug!("Resuming webhook delivery {}", delivery.uid);
+                spawn_delivery(state, webhook, delivery);
+                resumed += 1;
+            }
+            None => {
+                delivery.status = WEBHOOK_DELIVERY_FAILED.to_string();
+                delivery.error_message = Some("Webhook was deleted".to_string());
+                state.sql_client.update_webhook_delivery(&delivery).await?;
+            }
+        }
+    }
+
+    Ok(resumed)
+}
+
+/// Periodically resumes webhook deliveries left pending by a server that stopped while they
+/// were being retried. The first check runs at startup
+pub struct WebhookResumer {
+    state: Arc<AppState>,
+}
+
+impl WebhookResumer {
+    pub fn new(state: Arc<AppState>) -> Self {
+        Self { state }
+    }
+
+    pub async fn start(self) {
+        info!("Starting webhook delivery resumer");
+        task::spawn(async move {
+            let mut interval = tokio::time::interval(WEBHOOK_RESUME_INTERVAL);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+            loop {
+                interval.tick().await;
+
+                match resume_stale_deliveries(&self.state).await {
+                    Ok(0) => {}
+                    Ok(resumed) => info!("Resumed {resumed} pending webhook deliveries"),
+                    Err(e) => error!("Failed to resume webhook deliveries: {e}"),
+                }
+            }
+        });
+    }
+}
+
+/// Creates a delivery for every webhook subscribed to the event
+#[instrument(skip_all)]
+pub async fn dispatch_card_event(
+    state: &Arc<AppState>,
+    event: CardEvent,
+) -> Result<(), ServerError> {
+    let event_type = event.event_type.to_string();
+    let webhooks = state.sql_client.get_active_webhooks(&event.space).await?;
+
+    let payload = serde_json::to_string(&event)?;
+
+    for webhook in webhooks
+        .into_iter()
+        .filter(|webhook| webhook.matches(&event.space, &event_type))
+    {
+        debug!("Dispatching {event_type} to webhook {}", webhook.uid);
+        let delivery = WebhookDelivery::new(&webhook.uid, &event_type, payload.clone());
+        enqueue_delivery(state, webhook, delivery).await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,3 @@
+pub mod handler;
+pub mod route;
+pub mod schema;
@@ -0,0 +1,236 @@
+use crate::core::error::{internal_server_error, OpsmlServerError};
+use crate::core::state::AppState;
+use crate::core::webhook::handler::{encrypt_webhook_secret, enqueue_delivery};
+use crate::core::webhook::schema::{
+    CreateWebhookRequest, CreateWebhookResponse, WebhookDeliveryListResponse, WebhookDeliveryQuery,
+    WebhookListResponse, WebhookResponse,
+};
+use anyhow::{Context, Result};
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::{delete, get, post},
+    Extension, Json, Router,
+};
+use opsml_auth::permission::UserPermissions;
+use opsml_sql::base::SqlClient;
+use opsml_sql::schemas::schema::{Webhook, WebhookDelivery};
+use opsml_types::contracts::CardEventType;
+use rand::distr::Alphanumeric;
+use rand::Rng;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::Arc;
+use tracing::{error, info, instrument};
+
+/// Maximum number of deliveries returned per query
+const MAX_DELIVERY_LIMIT: i32 = 500;
+
+fn generate_webhook_secret() -> String {
+    rand::rng()
+        .sample_iter(&Alphanumeric)
+        .take(40)
+        .map(char::from)
+        .collect()
+}
+
+/// Register a webhook endpoint (admin only)
+#[instrument(skip_all)]
+async fn create_webhook(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Json(request): Json<CreateWebhookRequest>,
+) -> Result<Json<CreateWebhookResponse>, (StatusCode, Json<OpsmlServerError>)> {
+    if !perms.group_permissions.contains(&"admin".to_string()) {
+        return OpsmlServerError::need_admin_permission().into_response(StatusCode::FORBIDDEN);
+    }
+
+    if !(request.url.starts_with("http://") || request.url.starts_with("https://")) {
+        return OpsmlServerError::invalid_webhook("url must use http or https")
+            .into_response(StatusCode::BAD_REQUEST);
+    }
+
+    let mut event_types = Vec::with_capacity(request.event_types.len());
+    for event_type in &request.event_types {
+        match CardEventType::from_string(event_type) {
+            Ok(event_type) => event_types.push(event_type.to_string()),
+            Err(e) => {
+                return OpsmlServerError::invalid_webhook(&e.to_string())
+                    .into_response(StatusCode::BAD_REQUEST)
+            }
+        }
+    }
+
+    let secret = request.secret.unwrap_or_else(generate_webhook_secret);
+
+    let mut webhook = Webhook::new(
+        &request.url,
+        request.space,
+        event_types,
+        vec![],
+        &perms.username,
+    );
+
+    webhook.encrypted_secret =
+        encrypt_webhook_secret(state.key_provider.as_ref(), &webhook.uid, &secret)
+            .await
+            .map_err(|e| internal_server_error(e, "Failed to encrypt webhook secret"))?;
+
+    state
+        .sql_client
+        .insert_webhook(&webhook)
+        .await
+        .map_err(|e| {
+            error!("Failed to insert webhook: {e}");
+            internal_server_error(e, "Failed to create webhook")
+        })?;
+
+    info!("Webhook {} registered by {}", webhook.uid, perms.username);
+
+    Ok(Json(CreateWebhookResponse::new(
+        secret,
+        WebhookResponse::from(webhook),
+    )))
+}
+
+/// List all webhook endpoints (admin only)
+#[instrument(skip_all)]
+async fn list_webhooks(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+) -> Result<Json<WebhookListResponse>, (StatusCode, Json<OpsmlServerError>)> {
+    if !perms.group_permissions.contains(&"admin".to_string()) {
+        return OpsmlServerError::need_admin_permission().into_response(StatusCode::FORBIDDEN);
+    }
+
+    let webhooks = state.sql_client.get_webhooks().await.map_err(|e| {
+        error!("Failed to get webhooks: {e}");
+        internal_server_error(e, "Failed to get webhooks")
+    })?;
+
+    Ok(Json(WebhookListResponse {
+        webhooks: webhooks.into_iter().map(WebhookResponse::from).collect(),
+    }))
+}
+
+/// Delete a webhook endpoint (admin only)
+#[instrument(skip_all)]
+async fn delete_webhook(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Path(uid): Path<String>,
+) -> Result<StatusCode, (StatusCode, Json<OpsmlServerError>)> {
+    if !perms.group_permissions.contains(&"admin".to_string()) {
+        return OpsmlServerError::need_admin_permission().into_response(StatusCode::FORBIDDEN);
+    }
+
+    let deleted = state.sql_client.delete_webhook(&uid).await.map_err(|e| {
+        error!("Failed to delete webhook: {e}");
+        internal_server_error(e, "Failed to delete webhook")
+    })?;
+
+    if !deleted {
+        return OpsmlServerError::webhook_not_found().into_response(StatusCode::NOT_FOUND);
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// List recent deliveries for a webhook, newest first (admin only)
+#[instrument(skip_all)]
+async fn list_webhook_deliveries(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Path(uid): Path<String>,
+    Query(params): Query<WebhookDeliveryQuery>,
+) -> Result<Json<WebhookDeliveryListResponse>, (StatusCode, Json<OpsmlServerError>)> {
+    if !perms.group_permissions.contains(&"admin".to_string()) {
+        return OpsmlServerError::need_admin_permission().into_response(StatusCode::FORBIDDEN);
+    }
+
+    let limit = params.limit.unwrap_or(50).clamp(1, MAX_DELIVERY_LIMIT);
+
+    let deliveries = state
+        .sql_client
+        .get_webhook_deliveries(&uid, limit)
+        .await
+        .map_err(|e| {
+            error!("Failed to get webhook deliveries: {e}");
+            internal_server_error(e, "Failed to get webhook deliveries")
+        })?;
+
+    Ok(Json(WebhookDeliveryListResponse { deliveries }))
+}
+
+/// Replay a previous delivery as a new delivery (admin only)
+#[instrument(skip_all)]
+async fn replay_webhook_delivery(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Path(uid): Path<String>,
+) -> Result<Json<WebhookDelivery>, (StatusCode, Json<OpsmlServerError>)> {
+    if !perms.group_permissions.contains(&"admin".to_string()) {
+        return OpsmlServerError::need_admin_permission().into_response(StatusCode::FORBIDDEN);
+    }
+
+    let previous = match state.sql_client.get_webhook_delivery(&uid).await {
+        Ok(Some(delivery)) => delivery,
+        Ok(None) => {
+            return OpsmlServerError::webhook_delivery_not_found()
+                .into_response(StatusCode::NOT_FOUND)
+        }
+        Err(e) => {
+            error!("Failed to get webhook delivery: {e}");
+            return Err(internal_server_error(e, "Failed to get webhook delivery"));
+        }
+    };
+
+    let webhook = match state.sql_client.get_webhook(&previous.webhook_uid).await {
+        Ok(Some(webhook)) => webhook,
+        Ok(None) => {
+            return OpsmlServerError::webhook_not_found().into_response(StatusCode::NOT_FOUND)
+        }
+        Err(e) => {
+            error!("Failed to get webhook: {e}");
+            return Err(internal_server_error(e, "Failed to get webhook"));
+        }
+    };
+
+    let delivery = WebhookDelivery::new(&webhook.uid, &previous.event_type, previous.payload);
+
+    enqueue_delivery(&state, webhook, delivery.clone())
+        .await
+        .map_err(|e| {
+            error!("Failed to replay webhook delivery: {e}");
+            internal_server_error(e, "Failed to replay webhook delivery")
+        })?;
+
+    Ok(Json(delivery))
+}
+
+pub async fn get_webhook_router(prefix: &str) -> Result<Router<Arc<AppState>>> {
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        Router::new()
+            .route(
+                &format!("{prefix}/webhook"),
+                post(create_webhook).get(list_webhooks),
+            )
+            .route(&format!("{prefix}/webhook/{{uid}}"), delete(delete_webhook))
+            .route(
+                &format!("{prefix}/webhook/{{uid}}/deliveries"),
+                get(list_webhook_deliveries),
+            )
+            .route(
+                &format!("{prefix}/webhook/delivery/{{uid}}/replay"),
+                post(replay_webhook_delivery),
+            )
+    }));
+
+    match result {
+        Ok(router) => Ok(router),
+        Err(_) => {
+            error!("Failed to create webhook router");
+            Err(anyhow::anyhow!("Failed to create webhook router"))
+                .context("Panic occurred while creating the router")
+        }
+    }
+}
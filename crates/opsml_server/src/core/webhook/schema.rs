@@ -0,0 +1,73 @@
+use chrono::{DateTime, Utc};
+use opsml_sql::schemas::schema::{Webhook, WebhookDelivery};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CreateWebhookRequest {
+    pub url: String,
+    /// Only deliver events for this space. Deliver events for all spaces if not set
+    pub space: Option<String>,
+    /// Event types to deliver. Deliver all event types if empty
+    #[serde(default)]
+    pub event_types: Vec<String>,
+    /// Secret used to sign deliveries. A random secret is generated if not set
+    pub secret: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct WebhookResponse {
+    pub uid: String,
+    pub created_at: DateTime<Utc>,
+    pub url: String,
+    pub space: Option<String>,
+    pub event_types: Vec<String>,
+    pub active: bool,
+    pub created_by: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CreateWebhookResponse {
+    pub secret: String,
+    pub info: WebhookResponse,
+    pub message: String,
+}
+
+impl CreateWebhookResponse {
+    pub fn new(secret: String, info: WebhookResponse) -> Self {
+        Self {
+            secret,
+            info,
+            message: "Save this secret securely. It is used to verify delivery signatures and cannot be shown again!".to_string(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct WebhookListResponse {
+    pub webhooks: Vec<WebhookResponse>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct WebhookDeliveryQuery {
+    pub limit: Option<i32>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct WebhookDeliveryListResponse {
+    pub deliveries: Vec<WebhookDelivery>,
+}
+
+// Convert Webhook to WebhookResponse (strips encrypted secret)
+impl From<Webhook> for WebhookResponse {
+    fn from(webhook: Webhook) -> Self {
+        WebhookResponse {
+            uid: webhook.uid,
+            created_at: webhook.created_at,
+            url: webhook.url,
+            space: webhook.space,
+            event_types: webhook.event_types.0,
+            active: webhook.active,
+            created_by: webhook.created_by,
+        }
+    }
+}
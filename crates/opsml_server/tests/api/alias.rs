@@ -0,0 +1,173 @@
+use crate::common::TestHelper;
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    response::Response,
+};
+use http_body_util::BodyExt; // for `collect`
+use opsml_types::contracts::{
+    ArtifactKey, CardQueryArgs, CardRecord, VersionAlias, VersionAliasHistory, VersionAliasQuery,
+    VersionAliasRequest,
+};
+use opsml_types::RegistryType;
+
+async fn set_alias(helper: &TestHelper, alias: &str, version: &str) -> Response {
+    let alias_request = VersionAliasRequest {
+        space: helper.space.clone(),
+        name: helper.name.clone(),
+        registry_type: RegistryType::Model,
+        alias: alias.to_string(),
+        version: version.to_string(),
+    };
+
+    let request = Request::builder()
+        .uri("/opsml/api/card/alias")
+        .method("PUT")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(serde_json::to_string(&alias_request).unwrap()))
+        .unwrap();
+
+    helper.send_oneshot(request).await
+}
+
+fn alias_query(helper: &TestHelper, alias: Option<&str>) -> String {
+    let query = VersionAliasQuery {
+        space: helper.space.clone(),
+        name: helper.name.clone(),
+        registry_type: RegistryType::Model,
+        alias: alias.map(String::from),
+    };
+    serde_qs::to_string(&query).unwrap()
+}
+
+async fn load_card(helper: &TestHelper, version: &str) -> Response {
+    let args = CardQueryArgs {
+        space: Some(helper.space.clone()),
+        name: Some(helper.name.clone()),
+        version: Some(version.to_string()),
+        registry_type: RegistryType::Model,
+        ..Default::default()
+    };
+    let query_string = serde_qs::to_string(&args).unwrap();
+
+    let request = Request::builder()
+        .uri(format!("/opsml/api/card/load?{query_string}"))
+        .method("GET")
+        .body(Body::empty())
+        .unwrap();
+
+    helper.send_oneshot(request).await
+}
+
+#[tokio::test]
+async fn test_opsml_server_version_alias() {
+    let mut helper = TestHelper::new(None).await;
+
+    helper.create_modelcard().await;
+    let first_uid = helper.key.uid.clone();
+
+    helper.version = "1.1.0".to_string();
+    helper.create_modelcard().await;
+    let second_uid = helper.key.uid.clone();
+
+    // 1. Point the alias at the first version
+    let response = set_alias(&helper, "champion", "1.0.0").await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let alias: VersionAlias = serde_json::from_slice(&body).unwrap();
+    assert_eq!(alias.uid, first_uid);
+
+    // 2. Load the card through the alias
+    let response = load_card(&helper, "@champion").await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let key: ArtifactKey = serde_json::from_slice(&body).unwrap();
+    assert_eq!(key.uid, first_uid);
+
+    // 3. Move the alias and list cards through it
+    let response = set_alias(&helper, "champion", "1.1.0").await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let args = CardQueryArgs {
+        space: Some(helper.space.clone()),
+        name: Some(helper.name.clone()),
+        version: Some("@champion".to_string()),
+        registry_type: RegistryType::Model,
+        ..Default::default()
+    };
+    let query_string = serde_qs::to_string(&args).unwrap();
+
+    let request = Request::builder()
+        .uri(format!("/opsml/api/card/list?{query_string}"))
+        .method("GET")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let cards: Vec<CardRecord> = serde_json::from_slice(&body).unwrap();
+    assert_eq!(cards.len(), 1);
+    assert_eq!(cards[0].uid(), second_uid);
+
+    // 4. List aliases
+    let request = Request::builder()
+        .uri(format!(
+            "/opsml/api/card/alias?{}",
+            alias_query(&helper, None)
+        ))
+        .method("GET")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let aliases: Vec<VersionAlias> = serde_json::from_slice(&body).unwrap();
+    assert_eq!(aliases.len(), 1);
+    assert_eq!(aliases[0].version, "1.1.0");
+
+    // 5. Delete the alias
+    let request = Request::builder()
+        .uri(format!(
+            "/opsml/api/card/alias?{}",
+            alias_query(&helper, Some("champion"))
+        ))
+        .method("DELETE")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = load_card(&helper, "@champion").await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    // 6. Every move is recorded in the history
+    let request = Request::builder()
+        .uri(format!(
+            "/opsml/api/card/alias/history?{}",
+            alias_query(&helper, Some("champion"))
+        ))
+        .method("GET")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let history: Vec<VersionAliasHistory> = serde_json::from_slice(&body).unwrap();
+    assert_eq!(history.len(), 3);
+    assert_eq!(history[0].version, None);
+    assert_eq!(history[1].previous_version, Some("1.0.0".to_string()));
+
+    // 7. Unknown versions and invalid alias names are rejected
+    let response = set_alias(&helper, "champion", "9.9.9").await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = set_alias(&helper, "Champion!", "1.0.0").await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    helper.cleanup();
+}
@@ -0,0 +1,148 @@
+use crate::common::TestHelper;
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use chrono::{Duration, Utc};
+use http_body_util::BodyExt; // for `collect`
+use opsml_types::contracts::{
+    AuditChainReport, AuditEventPage, AuditEventQueryArgs, AuditExportRequest, Operation,
+};
+use opsml_types::RegistryType;
+
+#[tokio::test]
+async fn test_opsml_server_audit_chain() {
+    let mut helper = TestHelper::new(None).await;
+
+    // card registration publishes audit events
+    helper.create_modelcard().await;
+    helper.create_datacard().await;
+
+    // audit events are written asynchronously by the outbox dispatcher
+    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+
+    // 1. Verify the chain
+    let request = Request::builder()
+        .uri("/opsml/api/audit/verify")
+        .method("GET")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let report: AuditChainReport = serde_json::from_slice(&body).unwrap();
+    assert!(report.verified);
+    assert!(report.events_checked >= 2);
+    assert!(report.first_break.is_none());
+
+    // 2. Export the chain as JSON Lines
+    let export_req = AuditExportRequest {
+        start_time: Utc::now() - Duration::hours(1),
+        end_time: Utc::now() + Duration::hours(1),
+    };
+    let query_string = serde_qs::to_string(&export_req).unwrap();
+
+    let request = Request::builder()
+        .uri(format!("/opsml/api/audit/export?{query_string}"))
+        .method("GET")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let export = String::from_utf8(body.to_vec()).unwrap();
+    let events = export
+        .lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+        .collect::<Vec<_>>();
+
+    assert_eq!(events.len() as i64, report.events_checked);
+    assert_eq!(
+        events.last().unwrap()["hash"].as_str(),
+        report.last_hash.as_deref()
+    );
+
+    helper.cleanup();
+}
+
+#[tokio::test]
+async fn test_opsml_server_audit_events() {
+    let mut helper = TestHelper::new(None).await;
+
+    helper.create_modelcard().await;
+    let model_uid = helper.key.uid.clone();
+    helper.create_datacard().await;
+
+    // audit events are written asynchronously by the outbox dispatcher
+    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+
+    // 1. Filter by space, operation and registry type
+    let args = AuditEventQueryArgs {
+        space: Some(helper.space.clone()),
+        operation: Some(Operation::Create),
+        registry_type: Some(RegistryType::Model),
+        ..Default::default()
+    };
+    let query_string = serde_qs::to_string(&args).unwrap();
+
+    let request = Request::builder()
+        .uri(format!("/opsml/api/audit/events?{query_string}"))
+        .method("GET")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let page: AuditEventPage = serde_json::from_slice(&body).unwrap();
+    assert_eq!(page.events.len(), 1);
+    assert_eq!(page.events[0].registry_type.as_deref(), Some("model"));
+    assert_eq!(page.events[0].space.as_deref(), Some("space"));
+    assert_eq!(page.events[0].resource_id, model_uid);
+    assert!(page.next_cursor.is_none());
+
+    // 2. Paginate through all create events
+    let args = AuditEventQueryArgs {
+        operation: Some(Operation::Create),
+        limit: Some(1),
+        ..Default::default()
+    };
+    let query_string = serde_qs::to_string(&args).unwrap();
+
+    let request = Request::builder()
+        .uri(format!("/opsml/api/audit/events?{query_string}"))
+        .method("GET")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let page: AuditEventPage = serde_json::from_slice(&body).unwrap();
+    assert_eq!(page.events.len(), 1);
+    assert_eq!(page.events[0].registry_type.as_deref(), Some("data"));
+
+    let args = AuditEventQueryArgs {
+        cursor: page.next_cursor,
+        ..args
+    };
+    let query_string = serde_qs::to_string(&args).unwrap();
+
+    let request = Request::builder()
+        .uri(format!("/opsml/api/audit/events?{query_string}"))
+        .method("GET")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let page: AuditEventPage = serde_json::from_slice(&body).unwrap();
+    assert_eq!(page.events.len(), 1);
+    assert_eq!(page.events[0].registry_type.as_deref(), Some("model"));
+
+    helper.cleanup();
+}
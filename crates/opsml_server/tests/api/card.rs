@@ -254,6 +254,7 @@ async fn test_opsml_server_card_list_cards() {
         limit: None,
         sort_by_timestamp: None,
         registry_type: RegistryType::Data,
+        stage: None,
     };
 
     let query_string = serde_qs::to_string(&args).unwrap();
@@ -282,6 +283,7 @@ async fn test_opsml_server_card_list_cards() {
         limit: None,
         sort_by_timestamp: None,
         registry_type: RegistryType::Model,
+        stage: None,
     };
 
     let query_string = serde_qs::to_string(&args).unwrap();
@@ -496,9 +498,10 @@ async fn test_opsml_server_card_modelcard_crud() {
     let key_from_server: ArtifactKey =
         serde_json::from_slice(&response.into_body().collect().await.unwrap().to_bytes()).unwrap();
 
+    // keys are re-wrapped with a new nonce for every response
     assert_eq!(
-        create_response.key.encrypted_key,
-        key_from_server.encrypted_key
+        create_response.key.get_decrypt_key().unwrap(),
+        key_from_server.get_decrypt_key().unwrap()
     );
 
     // get card by uid
@@ -549,6 +552,7 @@ async fn test_opsml_server_card_modelcard_crud() {
             tags: card.tags,
             username: std::env::var("OPSML_USERNAME").unwrap_or_else(|_| "guest".to_string()),
             opsml_version: card.opsml_version,
+            stage: card.stage,
         }),
     };
 
@@ -938,6 +942,7 @@ async fn test_opsml_server_card_service_card_crud() {
             cards: card.cards,
             username: std::env::var("OPSML_USERNAME").unwrap_or_else(|_| "guest".to_string()),
             opsml_version: card.opsml_version,
+            stage: card.stage,
         }),
     };
 
@@ -1165,6 +1170,7 @@ async fn test_opsml_server_card_get_card() {
         limit: None,
         sort_by_timestamp: None,
         registry_type: RegistryType::Model,
+        stage: None,
     };
     //
     let query_string = serde_qs::to_string(&params).unwrap();
@@ -1225,6 +1231,7 @@ async fn test_opsml_server_card_get_readme() {
         limit: None,
         sort_by_timestamp: None,
         registry_type: RegistryType::Model,
+        stage: None,
     };
     //
     let query_string = serde_qs::to_string(&params).unwrap();
@@ -341,3 +341,214 @@ async fn test_opsml_server_grouped_experiment_metrics() {
     assert_eq!(metrics.len(), 2);
     assert_eq!(metrics["metric1"].len(), 2);
 }
+
+#[tokio::test]
+async fn test_opsml_server_compare_experiments() {
+    let helper = TestHelper::new(None).await;
+    let experiment_uid1 = "550e8400-e29b-41d4-a716-446655440001".to_string();
+    let experiment_uid2 = "550e8400-e29b-41d4-a716-446655440002".to_string();
+
+    let runs = [
+        (experiment_uid1.clone(), vec![0.9, 0.4, 0.6], 0.1),
+        (experiment_uid2.clone(), vec![0.8, 0.7], 0.01),
+    ];
+
+    for (experiment_uid, losses, lr) in runs {
+        let request = MetricRequest {
+            experiment_uid: experiment_uid.clone(),
+            metrics: losses
+                .into_iter()
+                .enumerate()
+                .map(|(step, value)| Metric {
+                    name: "loss".to_string(),
+                    value,
+                    step: Some(step as i32),
+                    ..Default::default()
+                })
+                .collect(),
+        };
+
+        let request = Request::builder()
+            .uri("/opsml/api/experiment/metrics")
+            .method("PUT")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(serde_json::to_string(&request).unwrap()))
+            .unwrap();
+
+        let response = helper.send_oneshot(request).await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let request = ParameterRequest {
+            experiment_uid,
+            parameters: vec![
+                Parameter {
+                    name: "lr".to_string(),
+                    value: ParameterValue::Float(lr),
+                },
+                Parameter {
+                    name: "batch_size".to_string(),
+                    value: ParameterValue::Int(32),
+                },
+            ],
+        };
+
+        let request = Request::builder()
+            .uri("/opsml/api/experiment/parameters")
+            .method("PUT")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(serde_json::to_string(&request).unwrap()))
+            .unwrap();
+
+        let response = helper.send_oneshot(request).await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    let body = ExperimentComparisonRequest {
+        experiment_uids: vec![experiment_uid1.clone(), experiment_uid2.clone()],
+        ..Default::default()
+    };
+
+    let request = Request::builder()
+        .uri("/opsml/api/experiment/compare")
+        .method("POST")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(serde_json::to_string(&body).unwrap()))
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let comparison: ExperimentComparison = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(
+        comparison.experiment_uids,
+        vec![experiment_uid1, experiment_uid2]
+    );
+
+    let loss = &comparison.metrics[0];
+    assert_eq!(loss.steps, vec![0, 1, 2]);
+    assert_eq!(loss.runs[1].values, vec![Some(0.8), Some(0.7), None]);
+
+    let summary = loss.runs[0].summary.as_ref().unwrap();
+    assert_eq!(summary.best, 0.4);
+    assert_eq!(summary.best_step, 1);
+    assert_eq!(summary.last, 0.6);
+
+    // parameters are sorted by name
+    assert_eq!(comparison.parameters[0].name, "batch_size");
+    assert!(!comparison.parameters[0].differs());
+    assert!(comparison.parameters[1].differs());
+
+    // comparing no experiments is rejected
+    let request = Request::builder()
+        .uri("/opsml/api/experiment/compare")
+        .method("POST")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(
+            serde_json::to_string(&ExperimentComparisonRequest::default()).unwrap(),
+        ))
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    helper.cleanup();
+}
+
+#[tokio::test]
+async fn test_opsml_server_query_metrics() {
+    let helper = TestHelper::new(None).await;
+    let experiment_uid = "550e8400-e29b-41d4-a716-446655440003".to_string();
+
+    let request = MetricRequest {
+        experiment_uid: experiment_uid.clone(),
+        metrics: (0..50)
+            .map(|step| Metric {
+                name: "loss".to_string(),
+                value: (50 - step) as f64,
+                step: Some(step),
+                ..Default::default()
+            })
+            .collect(),
+    };
+
+    let request = Request::builder()
+        .uri("/opsml/api/experiment/metrics")
+        .method("PUT")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(serde_json::to_string(&request).unwrap()))
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // first page of a step range
+    let body = MetricQuery {
+        experiment_uid: experiment_uid.clone(),
+        start_step: Some(10),
+        limit: Some(20),
+        ..Default::default()
+    };
+
+    let request = Request::builder()
+        .uri("/opsml/api/experiment/metrics/query")
+        .method("POST")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(serde_json::to_string(&body).unwrap()))
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let page: MetricPage = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(page.metrics.len(), 20);
+    assert_eq!(page.metrics[0].step, Some(10));
+    assert!(page.next_cursor.is_some());
+
+    // downsampled to the budget
+    let body = MetricQuery {
+        experiment_uid: experiment_uid.clone(),
+        max_points: Some(10),
+        ..Default::default()
+    };
+
+    let request = Request::builder()
+        .uri("/opsml/api/experiment/metrics/query")
+        .method("POST")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(serde_json::to_string(&body).unwrap()))
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let page: MetricPage = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(page.metrics.len(), 10);
+    assert!(page.next_cursor.is_none());
+
+    // a budget outside 1..=MAX_METRIC_POINTS is rejected
+    for max_points in [0, -1, MAX_METRIC_POINTS + 1] {
+        let body = MetricQuery {
+            experiment_uid: experiment_uid.clone(),
+            max_points: Some(max_points),
+            ..Default::default()
+        };
+
+        let request = Request::builder()
+            .uri("/opsml/api/experiment/metrics/query")
+            .method("POST")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(serde_json::to_string(&body).unwrap()))
+            .unwrap();
+
+        let response = helper.send_oneshot(request).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    helper.cleanup();
+}
@@ -0,0 +1,158 @@
+use crate::common::TestHelper;
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    response::Response,
+};
+use http_body_util::BodyExt; // for `collect`
+use opsml_crypt::encrypt_file;
+use opsml_storage::{download_artifacts, storage::error::StorageError};
+use opsml_types::contracts::{
+    CardRecord, ImportCardRequest, ImportCardResponse, ModelCardClientRecord, UidResponse,
+};
+use opsml_types::RegistryType;
+use opsml_utils::create_uuid7;
+
+async fn import_card(helper: &TestHelper, import_request: &ImportCardRequest) -> Response {
+    let request = Request::builder()
+        .uri("/opsml/api/card/import")
+        .method("POST")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(serde_json::to_string(import_request).unwrap()))
+        .unwrap();
+
+    helper.send_oneshot(request).await
+}
+
+#[tokio::test]
+async fn test_opsml_server_card_import() {
+    let helper = TestHelper::new(None).await;
+
+    let uid = create_uuid7();
+    let encryption_key = vec![7u8; 32];
+    let import_request = ImportCardRequest {
+        registry_type: RegistryType::Model,
+        card: CardRecord::Model(ModelCardClientRecord {
+            uid: uid.clone(),
+            name: helper.name.clone(),
+            space: helper.space.clone(),
+            version: "4.2.0".to_string(),
+            ..ModelCardClientRecord::default()
+        }),
+        encryption_key: encryption_key.clone(),
+        has_manifest: true,
+    };
+
+    // 1. The card keeps its uid, version and artifact encryption key
+    let response = import_card(&helper, &import_request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let import_response: ImportCardResponse = serde_json::from_slice(&body).unwrap();
+    assert!(import_response.imported);
+    assert_eq!(import_response.key.uid, uid);
+    assert!(import_response.key.storage_key.ends_with("v4.2.0"));
+    assert_eq!(
+        import_response.key.get_decrypt_key().unwrap(),
+        encryption_key
+    );
+
+    let request = Request::builder()
+        .uri(format!("/opsml/api/card?uid={uid}&registry_type=model"))
+        .method("GET")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let uid_response: UidResponse = serde_json::from_slice(&body).unwrap();
+    assert!(uid_response.exists);
+
+    // 2. Importing the same card again is a conflict
+    let response = import_card(&helper, &import_request).await;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    // 3. So is a different card with the same space, name and version
+    let mut duplicate_version = import_request.clone();
+    if let CardRecord::Model(card) = &mut duplicate_version.card {
+        card.uid = create_uuid7();
+    }
+
+    let response = import_card(&helper, &duplicate_version).await;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    // 4. Uids must be valid uuids
+    let mut invalid_uid = import_request.clone();
+    if let CardRecord::Model(card) = &mut invalid_uid.card {
+        card.uid = "not-a-uuid".to_string();
+        card.version = "4.3.0".to_string();
+    }
+
+    let response = import_card(&helper, &invalid_uid).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    helper.cleanup();
+}
+
+#[tokio::test]
+async fn test_opsml_server_card_import_without_manifest() {
+    let helper = TestHelper::new(None).await;
+
+    let encryption_key = vec![9u8; 32];
+    let import_request = |version: &str, has_manifest: bool| ImportCardRequest {
+        registry_type: RegistryType::Model,
+        card: CardRecord::Model(ModelCardClientRecord {
+            uid: create_uuid7(),
+            name: helper.name.clone(),
+            space: helper.space.clone(),
+            version: version.to_string(),
+            ..ModelCardClientRecord::default()
+        }),
+        encryption_key: encryption_key.clone(),
+        has_manifest,
+    };
+
+    for (version, has_manifest) in [("5.0.0", false), ("5.1.0", true)] {
+        let response = import_card(&helper, &import_request(version, has_manifest)).await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let key = serde_json::from_slice::<ImportCardResponse>(&body)
+            .unwrap()
+            .key;
+        assert_eq!(key.has_manifest, has_manifest);
+
+        // the bundle artifacts are copied to the storage path of the card without a manifest
+        let storage_dir = std::env::current_dir()
+            .unwrap()
+            .join("opsml_registries")
+            .join(key.storage_path());
+        std::fs::create_dir_all(&storage_dir).unwrap();
+        let path = storage_dir.join("card.json");
+        std::fs::write(&path, "{}").unwrap();
+        encrypt_file(&path, &encryption_key).unwrap();
+
+        let download_dir = tempfile::tempdir().unwrap();
+        let lpath = download_dir.path().to_path_buf();
+        let result = tokio::task::spawn_blocking(move || {
+            download_artifacts(&key, &lpath, &key.storage_path(), true)
+        })
+        .await
+        .unwrap();
+
+        // 1. Cards imported without a manifest download without verification
+        // 2. Cards that should have a manifest fail when it is missing
+        match has_manifest {
+            false => {
+                result.unwrap();
+                assert_eq!(
+                    std::fs::read_to_string(download_dir.path().join("card.json")).unwrap(),
+                    "{}"
+                );
+            }
+            true => assert!(matches!(result, Err(StorageError::MissingManifestError(_)))),
+        }
+    }
+
+    helper.cleanup();
+}
@@ -0,0 +1,140 @@
+use crate::common::TestHelper;
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    response::Response,
+};
+use http_body_util::BodyExt; // for `collect`
+use opsml_semver::VersionType;
+use opsml_types::contracts::{
+    CardLineage, CardLineageRequest, CardRecord, CardVersionRequest, CreateCardRequest,
+    CreateCardResponse, LineageDirection, LineageEdge, ModelCardClientRecord,
+};
+use opsml_types::RegistryType;
+
+async fn get_lineage(helper: &TestHelper, lineage_request: &CardLineageRequest) -> Response {
+    let query_string = serde_qs::to_string(lineage_request).unwrap();
+
+    let request = Request::builder()
+        .uri(format!("/opsml/api/card/lineage?{query_string}"))
+        .method("GET")
+        .body(Body::empty())
+        .unwrap();
+
+    helper.send_oneshot(request).await
+}
+
+#[tokio::test]
+async fn test_opsml_server_card_lineage() {
+    let mut helper = TestHelper::new(None).await;
+
+    helper.create_datacard().await;
+    let datacard_uid = helper.key.uid.clone();
+
+    // a model trained on the datacard
+    let card_request = CreateCardRequest {
+        card: CardRecord::Model(ModelCardClientRecord {
+            name: helper.name.clone(),
+            space: helper.space.clone(),
+            version: helper.version.clone(),
+            datacard_uid: Some(datacard_uid.clone()),
+            ..ModelCardClientRecord::default()
+        }),
+        registry_type: RegistryType::Model,
+        version_request: CardVersionRequest {
+            name: helper.name.clone(),
+            space: helper.space.clone(),
+            version: Some(helper.version.clone()),
+            version_type: VersionType::Minor,
+            pre_tag: None,
+            build_tag: None,
+        },
+    };
+
+    let request = Request::builder()
+        .uri("/opsml/api/card/create")
+        .method("POST")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(serde_json::to_string(&card_request).unwrap()))
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let create_response: CreateCardResponse = serde_json::from_slice(&body).unwrap();
+    let modelcard_uid = create_response.key.uid;
+
+    // 1. Walking downstream from the datacard reaches the model
+    let response = get_lineage(
+        &helper,
+        &CardLineageRequest {
+            uid: datacard_uid.clone(),
+            direction: Some(LineageDirection::Downstream),
+            ..Default::default()
+        },
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let lineage: CardLineage = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(lineage.root_uid, datacard_uid);
+    assert_eq!(lineage.nodes.len(), 2);
+    assert_eq!(lineage.nodes[0].uid, datacard_uid);
+    assert_eq!(lineage.nodes[1].uid, modelcard_uid);
+    assert_eq!(lineage.nodes[1].registry_type, RegistryType::Model);
+    assert_eq!(
+        lineage.edges,
+        vec![LineageEdge {
+            source_uid: datacard_uid.clone(),
+            target_uid: modelcard_uid.clone(),
+        }]
+    );
+
+    // 2. The datacard is upstream of the model
+    let response = get_lineage(
+        &helper,
+        &CardLineageRequest {
+            uid: modelcard_uid.clone(),
+            direction: Some(LineageDirection::Upstream),
+            depth: Some(1),
+        },
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let lineage: CardLineage = serde_json::from_slice(&body).unwrap();
+    assert_eq!(lineage.nodes.len(), 2);
+    assert_eq!(lineage.edges.len(), 1);
+
+    // nothing depends on the model
+    let response = get_lineage(
+        &helper,
+        &CardLineageRequest {
+            uid: modelcard_uid,
+            direction: Some(LineageDirection::Downstream),
+            ..Default::default()
+        },
+    )
+    .await;
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let lineage: CardLineage = serde_json::from_slice(&body).unwrap();
+    assert_eq!(lineage.nodes.len(), 1);
+    assert!(lineage.edges.is_empty());
+
+    // 3. Unknown cards are not found
+    let response = get_lineage(
+        &helper,
+        &CardLineageRequest {
+            uid: "missing".to_string(),
+            ..Default::default()
+        },
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    helper.cleanup();
+}
@@ -1,6 +1,18 @@
+pub mod alias;
+pub mod audit;
 pub mod card;
 pub mod experiment;
 pub mod files;
+pub mod import;
+pub mod lineage;
 pub mod login;
+pub mod outbox;
 pub mod scouter;
+pub mod search;
+pub mod signing;
+pub mod stage;
+pub mod stream;
+pub mod trash;
 pub mod user;
+pub mod verify;
+pub mod webhook;
@@ -0,0 +1,120 @@
+use crate::common::{get_connection_uri, TestHelper};
+use opsml_events::Event;
+use opsml_settings::config::DatabaseSettings;
+use opsml_sql::base::SqlClient;
+use opsml_sql::enums::client::SqlClientEnum;
+use opsml_types::contracts::{AuditEvent, AuditEventQueryArgs};
+use opsml_types::SqlType;
+
+#[tokio::test]
+async fn test_opsml_server_event_outbox() {
+    let mut helper = TestHelper::new(None).await;
+
+    let config = DatabaseSettings {
+        connection_uri: get_connection_uri(),
+        max_connections: 1,
+        sql_type: SqlType::Sqlite,
+    };
+    let client = SqlClientEnum::new(&config).await.unwrap();
+
+    // 1. An event left in the outbox (e.g. by a server that stopped before delivering it)
+    let pending = Event::Audit(AuditEvent {
+        resource_id: "pending_outbox_event".to_string(),
+        ..Default::default()
+    });
+    client
+        .insert_outbox_events(&[pending.to_outbox().unwrap()])
+        .await
+        .unwrap();
+
+    // 2. Card events are written to the outbox with the card
+    helper.create_modelcard().await;
+
+    tokio::time::sleep(std::time::Duration::from_millis(1000)).await;
+
+    // 3. All events were delivered and removed from the outbox
+    assert!(client.get_outbox_events(0, 100).await.unwrap().is_empty());
+
+    let delivered = client
+        .get_audit_events(&AuditEventQueryArgs {
+            resource_id: Some("pending_outbox_event".to_string()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(delivered.len(), 1);
+
+    let card_audit = client
+        .get_audit_events(&AuditEventQueryArgs {
+            resource_id: Some(helper.key.uid.clone()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(card_audit.len(), 1);
+
+    helper.cleanup();
+}
+
+#[tokio::test]
+async fn test_opsml_server_outbox_parks_failing_events() {
+    let helper = TestHelper::new(None).await;
+
+    let config = DatabaseSettings {
+        connection_uri: get_connection_uri(),
+        max_connections: 1,
+        sql_type: SqlType::Sqlite,
+    };
+    let client = SqlClientEnum::new(&config).await.unwrap();
+
+    // 1. An event whose handler always fails, followed by an event that can be delivered
+    client
+        .query(
+            "CREATE TRIGGER reject_poison_event BEFORE INSERT ON opsml_audit_event
+            WHEN NEW.resource_id = 'poison_outbox_event'
+            BEGIN SELECT RAISE(ABORT, 'poison event'); END;",
+        )
+        .await;
+
+    let events = ["poison_outbox_event", "healthy_outbox_event"]
+        .iter()
+        .map(|resource_id| {
+            Event::Audit(AuditEvent {
+                resource_id: resource_id.to_string(),
+                ..Default::default()
+            })
+            .to_outbox()
+            .unwrap()
+        })
+        .collect::<Vec<_>>();
+    client.insert_outbox_events(&events).await.unwrap();
+
+    // 2. The failing event is parked after its last attempt and the event behind it is delivered
+    let healthy_query = AuditEventQueryArgs {
+        resource_id: Some("healthy_outbox_event".to_string()),
+        ..Default::default()
+    };
+    let mut delivered = Vec::new();
+    for _ in 0..30 {
+        tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+        delivered = client.get_audit_events(&healthy_query).await.unwrap();
+        if !delivered.is_empty() {
+            break;
+        }
+    }
+    assert_eq!(delivered.len(), 1);
+
+    // 3. The parked event stays in the outbox for inspection but is no longer claimed
+    let remaining = client.get_outbox_events(0, 100).await.unwrap();
+    assert_eq!(remaining.len(), 1);
+    assert!(remaining[0].payload.contains("poison_outbox_event"));
+
+    let lease = opsml_utils::utils::get_utc_datetime() + chrono::Duration::minutes(5);
+    assert!(client
+        .claim_outbox_events("test", lease, 100)
+        .await
+        .unwrap()
+        .is_empty());
+
+    helper.cleanup();
+}
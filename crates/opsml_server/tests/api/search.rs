@@ -0,0 +1,82 @@
+use crate::common::TestHelper;
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    response::Response,
+};
+use http_body_util::BodyExt; // for `collect`
+use opsml_server::core::cards::schema::CreateReadeMe;
+use opsml_types::contracts::{CardSearchQuery, CardSearchResult};
+use opsml_types::RegistryType;
+
+async fn search(helper: &TestHelper, query: &str) -> Response {
+    let search_query = CardSearchQuery {
+        query: query.to_string(),
+        registry_type: Some(RegistryType::Model),
+        ..Default::default()
+    };
+    let query_string = serde_qs::to_string(&search_query).unwrap();
+
+    let request = Request::builder()
+        .uri(format!("/opsml/api/card/search?{query_string}"))
+        .method("GET")
+        .body(Body::empty())
+        .unwrap();
+
+    helper.send_oneshot(request).await
+}
+
+async fn search_results(helper: &TestHelper, query: &str) -> Vec<CardSearchResult> {
+    let response = search(helper, query).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    serde_json::from_slice(&body).unwrap()
+}
+
+#[tokio::test]
+async fn test_opsml_server_card_search() {
+    let mut helper = TestHelper::new(None).await;
+
+    helper.create_modelcard().await;
+
+    // 1. Cards are searchable by name and tags as soon as they are created
+    let results = search_results(&helper, &helper.name).await;
+    assert!(results.iter().any(|result| result.uid == helper.key.uid));
+
+    let results = search_results(&helper, "test").await;
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].uid, helper.key.uid);
+    assert_eq!(results[0].tags, vec!["test".to_string()]);
+
+    // 2. README content is searchable once it is saved
+    assert!(search_results(&helper, "fraudulent").await.is_empty());
+
+    let create_readme = CreateReadeMe {
+        space: helper.space.clone(),
+        name: helper.name.clone(),
+        registry_type: RegistryType::Model,
+        readme: "Flags fraudulent card transactions".to_string(),
+    };
+
+    let request = Request::builder()
+        .uri("/opsml/api/card/readme")
+        .method("POST")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(serde_json::to_string(&create_readme).unwrap()))
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let results = search_results(&helper, "fraudulent transactions").await;
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].uid, helper.key.uid);
+    assert_eq!(results[0].version, helper.version);
+
+    // 3. A query without any words is rejected
+    let response = search(&helper, "--").await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    helper.cleanup();
+}
@@ -0,0 +1,98 @@
+use crate::common::TestHelper;
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use base64::prelude::*;
+use http_body_util::BodyExt; // for `collect`
+use opsml_crypt::SigningKey;
+use opsml_types::contracts::{CreateSigningKeyRequest, SigningKeyListResponse, SpaceSigningKey};
+
+fn create_request(request: &CreateSigningKeyRequest) -> Request<Body> {
+    Request::builder()
+        .uri("/opsml/api/signing_key")
+        .method("POST")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(serde_json::to_string(request).unwrap()))
+        .unwrap()
+}
+
+async fn list_keys(helper: &TestHelper, space: &str) -> Vec<SpaceSigningKey> {
+    let request = Request::builder()
+        .uri(format!("/opsml/api/signing_key?space={space}"))
+        .method("GET")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let keys: SigningKeyListResponse = serde_json::from_slice(&body).unwrap();
+    keys.keys
+}
+
+#[tokio::test]
+async fn test_opsml_server_signing_keys() {
+    let helper = TestHelper::new(None).await;
+    let signing_key = SigningKey::generate().unwrap();
+
+    // 1. Register a public key for the test space
+    let key_request = CreateSigningKeyRequest {
+        space: helper.space.clone(),
+        public_key: BASE64_STANDARD.encode(signing_key.public_key()),
+    };
+
+    let response = helper.send_oneshot(create_request(&key_request)).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let created: SpaceSigningKey = serde_json::from_slice(&body).unwrap();
+
+    // the server derives the key id from the public key
+    assert_eq!(created.key_id, signing_key.key_id());
+    assert_eq!(created.space, helper.space);
+    assert!(created.active);
+
+    // a key can only be registered once
+    let response = helper.send_oneshot(create_request(&key_request)).await;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    // malformed public keys are rejected
+    let invalid_request = CreateSigningKeyRequest {
+        public_key: BASE64_STANDARD.encode(b"too short"),
+        ..key_request.clone()
+    };
+    let response = helper.send_oneshot(create_request(&invalid_request)).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // 2. List the keys of the space
+    let keys = list_keys(&helper, &helper.space).await;
+    assert_eq!(keys.len(), 1);
+    assert_eq!(
+        keys[0].public_key_bytes().unwrap(),
+        signing_key.public_key()
+    );
+    assert!(list_keys(&helper, "other-space").await.is_empty());
+
+    // 3. Revoke the key. Revoked keys are still listed
+    let revoke_request = || {
+        Request::builder()
+            .uri(format!("/opsml/api/signing_key?key_id={}", created.key_id))
+            .method("DELETE")
+            .body(Body::empty())
+            .unwrap()
+    };
+
+    let response = helper.send_oneshot(revoke_request()).await;
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let response = helper.send_oneshot(revoke_request()).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let keys = list_keys(&helper, &helper.space).await;
+    assert_eq!(keys.len(), 1);
+    assert!(!keys[0].active);
+
+    helper.cleanup();
+}
@@ -0,0 +1,130 @@
+use crate::common::TestHelper;
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    response::Response,
+};
+use http_body_util::BodyExt; // for `collect`
+use opsml_types::contracts::{
+    AuditEventPage, AuditEventQueryArgs, CardQueryArgs, CardRecord, CardStageRequest,
+    CardStageResponse, Operation,
+};
+use opsml_types::{CardStage, RegistryType};
+
+async fn update_stage(helper: &TestHelper, stage_request: &CardStageRequest) -> Response {
+    let request = Request::builder()
+        .uri("/opsml/api/card/stage")
+        .method("PUT")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(serde_json::to_string(stage_request).unwrap()))
+        .unwrap();
+
+    helper.send_oneshot(request).await
+}
+
+async fn list_model_cards(helper: &TestHelper, stage: CardStage) -> Vec<CardRecord> {
+    let args = CardQueryArgs {
+        space: Some(helper.space.clone()),
+        name: Some(helper.name.clone()),
+        registry_type: RegistryType::Model,
+        stage: Some(stage),
+        ..Default::default()
+    };
+    let query_string = serde_qs::to_string(&args).unwrap();
+
+    let request = Request::builder()
+        .uri(format!("/opsml/api/card/list?{query_string}"))
+        .method("GET")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    serde_json::from_slice(&body).unwrap()
+}
+
+#[tokio::test]
+async fn test_opsml_server_card_stage() {
+    let mut helper = TestHelper::new(None).await;
+
+    helper.create_modelcard().await;
+    let first_uid = helper.key.uid.clone();
+
+    helper.version = "1.1.0".to_string();
+    helper.create_modelcard().await;
+    let second_uid = helper.key.uid.clone();
+
+    // 1. Promote the first version to production
+    let mut stage_request = CardStageRequest {
+        uid: first_uid.clone(),
+        registry_type: RegistryType::Model,
+        stage: CardStage::Production,
+        archive_existing: false,
+    };
+    let response = update_stage(&helper, &stage_request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // 2. Promote the second version and archive the previous holder
+    stage_request.uid = second_uid.clone();
+    stage_request.archive_existing = true;
+
+    let response = update_stage(&helper, &stage_request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let stage_response: CardStageResponse = serde_json::from_slice(&body).unwrap();
+    assert_eq!(stage_response.change.previous_stage, CardStage::Unassigned);
+    assert_eq!(stage_response.archived.len(), 1);
+    assert_eq!(stage_response.archived[0].uid, first_uid);
+
+    // 3. list_cards filters by stage
+    let production = list_model_cards(&helper, CardStage::Production).await;
+    assert_eq!(production.len(), 1);
+    assert_eq!(production[0].uid(), second_uid);
+    assert_eq!(production[0].stage(), Some(CardStage::Production));
+
+    let archived = list_model_cards(&helper, CardStage::Archived).await;
+    assert_eq!(archived.len(), 1);
+    assert_eq!(archived[0].uid(), first_uid);
+
+    // 4. Each transition is audited
+    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+
+    let args = AuditEventQueryArgs {
+        operation: Some(Operation::Update),
+        registry_type: Some(RegistryType::Model),
+        ..Default::default()
+    };
+    let query_string = serde_qs::to_string(&args).unwrap();
+
+    let request = Request::builder()
+        .uri(format!("/opsml/api/audit/events?{query_string}"))
+        .method("GET")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let page: AuditEventPage = serde_json::from_slice(&body).unwrap();
+    assert_eq!(page.events.len(), 2);
+
+    // 5. Stages are only supported for model and service cards
+    helper.create_datacard().await;
+    let data_request = CardStageRequest {
+        uid: helper.key.uid.clone(),
+        registry_type: RegistryType::Data,
+        stage: CardStage::Staging,
+        archive_existing: false,
+    };
+    let response = update_stage(&helper, &data_request).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // unknown versions are not found
+    stage_request.uid = helper.key.uid.clone();
+    let response = update_stage(&helper, &stage_request).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    helper.cleanup();
+}
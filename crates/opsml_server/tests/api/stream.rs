@@ -0,0 +1,157 @@
+use crate::common::TestHelper;
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use http_body_util::BodyExt; // for `frame`
+use opsml_types::contracts::{EventStreamQuery, EventStreamReset, RegistryEvent};
+use opsml_types::RegistryType;
+use std::time::Duration;
+
+/// A single parsed Server-Sent Event
+struct StreamMessage {
+    id: String,
+    event: String,
+    data: String,
+}
+
+impl StreamMessage {
+    fn registry_event(&self) -> RegistryEvent {
+        serde_json::from_str(&self.data).unwrap()
+    }
+}
+
+/// Reads the next event from an SSE body. Returns None if no event arrives before the timeout
+async fn next_message(body: &mut Body, timeout: Duration) -> Option<StreamMessage> {
+    let frame = tokio::time::timeout(timeout, body.frame())
+        .await
+        .ok()??
+        .unwrap();
+    let text = String::from_utf8(frame.into_data().unwrap().to_vec()).unwrap();
+
+    let field = |name: &str| {
+        text.lines()
+            .find_map(|line| line.strip_prefix(&format!("{name}:")))
+            .map(|value| value.trim().to_string())
+            .unwrap()
+    };
+
+    Some(StreamMessage {
+        id: field("id"),
+        event: field("event"),
+        data: field("data"),
+    })
+}
+
+async fn open_stream(
+    helper: &TestHelper,
+    params: &EventStreamQuery,
+    last_id: Option<&str>,
+) -> Body {
+    let query_string = serde_qs::to_string(params).unwrap();
+
+    let mut request = Request::builder()
+        .uri(format!("/opsml/api/events/stream?{query_string}"))
+        .method("GET");
+
+    if let Some(last_id) = last_id {
+        request = request.header("Last-Event-ID", last_id);
+    }
+
+    let response = helper
+        .send_oneshot(request.body(Body::empty()).unwrap())
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()["content-type"].to_str().unwrap(),
+        "text/event-stream"
+    );
+
+    response.into_body()
+}
+
+#[tokio::test]
+async fn test_opsml_server_event_stream() {
+    let mut helper = TestHelper::new(None).await;
+
+    helper.create_modelcard().await;
+    let model_uid = helper.key.uid.clone();
+
+    // 1. Resume from the start of the buffered history, filtered to the model registry
+    let params = EventStreamQuery {
+        registry_type: Some(RegistryType::Model),
+        last_event_id: Some(0),
+        ..Default::default()
+    };
+    let mut body = open_stream(&helper, &params, None).await;
+
+    // events before the buffered history are unknown, so the client is told to reset first
+    let message = next_message(&mut body, Duration::from_secs(2))
+        .await
+        .unwrap();
+    assert_eq!(message.event, EventStreamReset::EVENT_TYPE);
+    let reset: EventStreamReset = serde_json::from_str(&message.data).unwrap();
+    assert_eq!(reset.last_event_id, 0);
+    assert_eq!(message.id, (reset.first_event_id - 1).to_string());
+
+    let message = next_message(&mut body, Duration::from_secs(2))
+        .await
+        .unwrap();
+    assert_eq!(message.event, "card_registered");
+    match message.registry_event() {
+        RegistryEvent::Card(event) => {
+            assert_eq!(event.uid.as_deref(), Some(model_uid.as_str()));
+            assert_eq!(event.registry_type, RegistryType::Model);
+        }
+        RegistryEvent::Space(_) => panic!("Expected card event"),
+    }
+    let model_event_id = message.id;
+
+    // 2. Live events are filtered by registry type
+    helper.create_datacard().await;
+    assert!(next_message(&mut body, Duration::from_millis(500))
+        .await
+        .is_none());
+
+    // 3. Resuming after the model event with Last-Event-ID replays only the data card
+    let mut body = open_stream(
+        &helper,
+        &EventStreamQuery::default(),
+        Some(model_event_id.as_str()),
+    )
+    .await;
+
+    let message = next_message(&mut body, Duration::from_secs(2))
+        .await
+        .unwrap();
+    assert_eq!(message.event, "card_registered");
+    assert!(message.id.parse::<u64>().unwrap() > model_event_id.parse::<u64>().unwrap());
+    match message.registry_event() {
+        RegistryEvent::Card(event) => assert_eq!(event.registry_type, RegistryType::Data),
+        RegistryEvent::Space(_) => panic!("Expected card event"),
+    }
+
+    // 4. Events outside the requested space are skipped
+    let params = EventStreamQuery {
+        space: Some("other_space".to_string()),
+        last_event_id: Some(0),
+        ..Default::default()
+    };
+    let mut body = open_stream(&helper, &params, None).await;
+    let message = next_message(&mut body, Duration::from_secs(2))
+        .await
+        .unwrap();
+    assert_eq!(message.event, EventStreamReset::EVENT_TYPE);
+    assert!(next_message(&mut body, Duration::from_millis(500))
+        .await
+        .is_none());
+
+    // 5. Resuming from the reset event replays the history without another reset
+    let mut body = open_stream(&helper, &EventStreamQuery::default(), Some(&message.id)).await;
+    let message = next_message(&mut body, Duration::from_secs(2))
+        .await
+        .unwrap();
+    assert_ne!(message.event, EventStreamReset::EVENT_TYPE);
+
+    helper.cleanup();
+}
@@ -0,0 +1,176 @@
+use crate::common::{get_connection_uri, TestHelper};
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    response::Response,
+};
+use http_body_util::BodyExt; // for `collect`
+use opsml_server::core::trash::purge_expired_cards;
+use opsml_settings::config::{DatabaseSettings, OpsmlConfig};
+use opsml_sql::base::SqlClient;
+use opsml_sql::enums::client::SqlClientEnum;
+use opsml_storage::StorageClientEnum;
+use opsml_types::contracts::{
+    CardQueryArgs, CardRecord, DeleteCardRequest, DeletedCard, DeletedCardQuery, UidRequest,
+    UidResponse,
+};
+use opsml_types::{RegistryType, SqlType};
+use std::sync::Arc;
+
+async fn delete_card(helper: &TestHelper) -> Response {
+    let delete_args = DeleteCardRequest {
+        uid: helper.key.uid.clone(),
+        space: helper.space.clone(),
+        registry_type: RegistryType::Model,
+    };
+    let query_string = serde_qs::to_string(&delete_args).unwrap();
+
+    let request = Request::builder()
+        .uri(format!("/opsml/api/card/delete?{query_string}"))
+        .method("DELETE")
+        .body(Body::empty())
+        .unwrap();
+
+    helper.send_oneshot(request).await
+}
+
+async fn list_cards(helper: &TestHelper) -> Vec<CardRecord> {
+    let args = CardQueryArgs {
+        space: Some(helper.space.clone()),
+        name: Some(helper.name.clone()),
+        registry_type: RegistryType::Model,
+        ..Default::default()
+    };
+    let query_string = serde_qs::to_string(&args).unwrap();
+
+    let request = Request::builder()
+        .uri(format!("/opsml/api/card/list?{query_string}"))
+        .method("GET")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    serde_json::from_slice(&body).unwrap()
+}
+
+async fn list_deleted_cards(helper: &TestHelper) -> Vec<DeletedCard> {
+    let query = DeletedCardQuery {
+        registry_type: RegistryType::Model,
+        space: Some(helper.space.clone()),
+    };
+    let query_string = serde_qs::to_string(&query).unwrap();
+
+    let request = Request::builder()
+        .uri(format!("/opsml/api/card/trash?{query_string}"))
+        .method("GET")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    serde_json::from_slice(&body).unwrap()
+}
+
+async fn restore_card(helper: &TestHelper) -> Response {
+    let restore_request = UidRequest {
+        uid: helper.key.uid.clone(),
+        registry_type: RegistryType::Model,
+    };
+
+    let request = Request::builder()
+        .uri("/opsml/api/card/restore")
+        .method("PUT")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(serde_json::to_string(&restore_request).unwrap()))
+        .unwrap();
+
+    helper.send_oneshot(request).await
+}
+
+#[tokio::test]
+async fn test_opsml_server_card_trash() {
+    let mut helper = TestHelper::new(None).await;
+
+    helper.create_modelcard().await;
+    let artifact_path = helper.create_files();
+
+    // 1. Deleting a card moves it to the trash and keeps its artifacts
+    let response = delete_card(&helper).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let delete_response: UidResponse = serde_json::from_slice(&body).unwrap();
+    assert!(!delete_response.exists);
+
+    assert!(list_cards(&helper).await.is_empty());
+    assert!(std::path::Path::new(&artifact_path)
+        .join("file.json")
+        .exists());
+
+    let deleted = list_deleted_cards(&helper).await;
+    assert_eq!(deleted.len(), 1);
+    assert_eq!(deleted[0].uid, helper.key.uid);
+    assert_eq!(deleted[0].version, helper.version);
+
+    // a card can only be deleted once
+    let response = delete_card(&helper).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    // 2. Restoring the card makes it visible again
+    let response = restore_card(&helper).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let cards = list_cards(&helper).await;
+    assert_eq!(cards.len(), 1);
+    assert_eq!(cards[0].uid(), helper.key.uid);
+    assert!(list_deleted_cards(&helper).await.is_empty());
+
+    // a card that is not in the trash cannot be restored
+    let response = restore_card(&helper).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    // 3. Purging removes the card and its artifacts once the retention period has passed
+    let response = delete_card(&helper).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let sql_client = Arc::new(
+        SqlClientEnum::new(&DatabaseSettings {
+            connection_uri: get_connection_uri(),
+            max_connections: 1,
+            sql_type: SqlType::Sqlite,
+        })
+        .await
+        .unwrap(),
+    );
+    let storage_settings = OpsmlConfig::default().storage_settings().unwrap();
+    let storage_client = Arc::new(StorageClientEnum::new(&storage_settings).await.unwrap());
+
+    // cards are kept while they are within the retention period
+    let purged = purge_expired_cards(&sql_client, &storage_client, 30)
+        .await
+        .unwrap();
+    assert_eq!(purged, 0);
+    assert_eq!(list_deleted_cards(&helper).await.len(), 1);
+
+    let purged = purge_expired_cards(&sql_client, &storage_client, 0)
+        .await
+        .unwrap();
+    assert_eq!(purged, 1);
+    assert!(list_deleted_cards(&helper).await.is_empty());
+    assert!(!std::path::Path::new(&artifact_path)
+        .join("file.json")
+        .exists());
+
+    // the artifact key is deleted with the card
+    assert!(sql_client
+        .get_artifact_key(&helper.key.uid, &RegistryType::Model.to_string())
+        .await
+        .is_err());
+
+    helper.cleanup();
+}
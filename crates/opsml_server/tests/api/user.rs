@@ -280,3 +280,233 @@ async fn test_opsml_login_logout() {
 
     assert!(logout_response.logged_out);
 }
+
+#[tokio::test]
+async fn test_opsml_server_api_tokens() {
+    let helper = TestHelper::new(None).await;
+
+    // 1. Create a service account (admin only)
+    let create_req = CreateServiceAccountRequest {
+        username: "ci_bot".to_string(),
+        email: None,
+        permissions: Some(vec!["read:all".to_string()]),
+        group_permissions: Some(vec!["user".to_string()]),
+    };
+
+    let request = Request::builder()
+        .uri("/opsml/api/service-account")
+        .method("POST")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(serde_json::to_string(&create_req).unwrap()))
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let user_response: UserResponse = serde_json::from_slice(&body).unwrap();
+    assert_eq!(user_response.role, "service");
+
+    // service accounts cannot log in with a password
+    let request = Request::builder()
+        .uri("/opsml/api/auth/login")
+        .method("GET")
+        .header("Username", "ci_bot")
+        .header("Password", "[redacted]")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // 2. Scopes outside of the user's permissions are rejected
+    let token_req = CreateApiTokenRequest {
+        name: "ci".to_string(),
+        scopes: vec!["write:all".to_string()],
+        expires_in_days: Some(30),
+    };
+
+    let request = Request::builder()
+        .uri("/opsml/api/user/ci_bot/tokens")
+        .method("POST")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(serde_json::to_string(&token_req).unwrap()))
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // 3. Create a valid token
+    let token_req = CreateApiTokenRequest {
+        name: "ci".to_string(),
+        scopes: vec!["read:all".to_string()],
+        expires_in_days: Some(30),
+    };
+
+    let request = Request::builder()
+        .uri("/opsml/api/user/ci_bot/tokens")
+        .method("POST")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(serde_json::to_string(&token_req).unwrap()))
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let token_response: CreateApiTokenResponse = serde_json::from_slice(&body).unwrap();
+    assert!(token_response.token.starts_with("opsml_pat_"));
+    assert_eq!(token_response.info.scopes, vec!["read:all".to_string()]);
+
+    // duplicate active names are rejected
+    let request = Request::builder()
+        .uri("/opsml/api/user/ci_bot/tokens")
+        .method("POST")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(serde_json::to_string(&token_req).unwrap()))
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    // 4. The token can be used to authenticate as the service account
+    let request = Request::builder()
+        .uri("/opsml/api/user/ci_bot")
+        .method("GET")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = helper
+        .send_oneshot_with_token(request, &token_response.token)
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // service account tokens do not carry admin permissions
+    let request = Request::builder()
+        .uri("/opsml/api/user")
+        .method("GET")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = helper
+        .send_oneshot_with_token(request, &token_response.token)
+        .await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // expiry must be within the allowed range
+    for days in [0, -1, i64::MAX] {
+        let token_req = CreateApiTokenRequest {
+            name: "expiry".to_string(),
+            scopes: vec!["read:all".to_string()],
+            expires_in_days: Some(days),
+        };
+
+        let request = Request::builder()
+            .uri("/opsml/api/user/admin/tokens")
+            .method("POST")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(serde_json::to_string(&token_req).unwrap()))
+            .unwrap();
+
+        let response = helper.send_oneshot(request).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    // a narrowly scoped token cannot mint a token with broader scopes
+    let token_req = CreateApiTokenRequest {
+        name: "narrow".to_string(),
+        scopes: vec!["read:all".to_string()],
+        expires_in_days: Some(1),
+    };
+
+    let request = Request::builder()
+        .uri("/opsml/api/user/admin/tokens")
+        .method("POST")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(serde_json::to_string(&token_req).unwrap()))
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let narrow_token: CreateApiTokenResponse = serde_json::from_slice(&body).unwrap();
+
+    for scope in ["write:all", "admin"] {
+        let token_req = CreateApiTokenRequest {
+            name: "escalated".to_string(),
+            scopes: vec![scope.to_string()],
+            expires_in_days: Some(1),
+        };
+
+        let request = Request::builder()
+            .uri("/opsml/api/user/admin/tokens")
+            .method("POST")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(serde_json::to_string(&token_req).unwrap()))
+            .unwrap();
+
+        let response = helper
+            .send_oneshot_with_token(request, &narrow_token.token)
+            .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    // `read:all` covers the scopes of individual spaces
+    let token_req = CreateApiTokenRequest {
+        name: "space".to_string(),
+        scopes: vec!["read:space_a".to_string()],
+        expires_in_days: Some(1),
+    };
+
+    let request = Request::builder()
+        .uri("/opsml/api/user/admin/tokens")
+        .method("POST")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(serde_json::to_string(&token_req).unwrap()))
+        .unwrap();
+
+    let response = helper
+        .send_oneshot_with_token(request, &narrow_token.token)
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // 5. List tokens
+    let request = Request::builder()
+        .uri("/opsml/api/user/ci_bot/tokens")
+        .method("GET")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let list_response: ApiTokenListResponse = serde_json::from_slice(&body).unwrap();
+    assert_eq!(list_response.tokens.len(), 1);
+    assert!(!list_response.tokens[0].revoked);
+
+    // 6. Revoke the token
+    let request = Request::builder()
+        .uri("/opsml/api/user/ci_bot/tokens/ci")
+        .method("DELETE")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // revoked tokens are rejected
+    let request = Request::builder()
+        .uri("/opsml/api/user/ci_bot")
+        .method("GET")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = helper
+        .send_oneshot_with_token(request, &token_response.token)
+        .await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    helper.cleanup();
+}
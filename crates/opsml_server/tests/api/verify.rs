@@ -0,0 +1,79 @@
+use crate::common::TestHelper;
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use http_body_util::BodyExt; // for `collect`
+use opsml_crypt::encrypt_file;
+use opsml_types::contracts::{
+    ArtifactIntegrityReport, ArtifactManifest, MismatchKind, VerifyArtifactsRequest,
+};
+use opsml_types::RegistryType;
+use std::path::{Path, PathBuf};
+
+async fn verify_artifacts(helper: &TestHelper) -> ArtifactIntegrityReport {
+    let query_string = serde_qs::to_string(&VerifyArtifactsRequest {
+        uid: helper.key.uid.clone(),
+        registry_type: RegistryType::Model,
+    })
+    .unwrap();
+
+    let request = Request::builder()
+        .uri(format!("/opsml/api/card/verify?{query_string}"))
+        .method("GET")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    serde_json::from_slice(&body).unwrap()
+}
+
+#[tokio::test]
+async fn test_opsml_server_verify_card_artifacts() {
+    let mut helper = TestHelper::new(None).await;
+
+    helper.create_modelcard().await;
+    let path = PathBuf::from(helper.create_files());
+
+    // 1. Cards saved without a manifest cannot be checked
+    let report = verify_artifacts(&helper).await;
+    assert!(!report.has_manifest);
+    assert!(!report.is_valid());
+
+    // 2. Stored files match the manifest
+    let mut manifest = ArtifactManifest::default();
+    manifest.add_files(&path, Path::new("")).unwrap();
+
+    let manifest_path = path.join(ArtifactManifest::path());
+    std::fs::write(&manifest_path, serde_json::to_string(&manifest).unwrap()).unwrap();
+    encrypt_file(&manifest_path, &helper.key.get_decrypt_key().unwrap()).unwrap();
+
+    let report = verify_artifacts(&helper).await;
+    assert!(report.is_valid());
+    assert_eq!(report.files_checked, 2);
+
+    // 3. Modified and unknown files are reported
+    std::fs::write(path.join("file.png"), "tampered").unwrap();
+    std::fs::write(path.join("extra.txt"), "extra").unwrap();
+
+    let report = verify_artifacts(&helper).await;
+    assert!(!report.is_valid());
+
+    let mismatches = report
+        .mismatches
+        .iter()
+        .map(|mismatch| (mismatch.path.as_str(), mismatch.kind.clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        mismatches,
+        vec![
+            ("file.png", MismatchKind::Modified),
+            ("extra.txt", MismatchKind::Untracked),
+        ]
+    );
+
+    helper.cleanup();
+}
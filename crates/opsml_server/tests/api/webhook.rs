@@ -0,0 +1,156 @@
+use crate::common::TestHelper;
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use http_body_util::BodyExt; // for `collect`
+use opsml_server::core::webhook::handler::{
+    sign_payload, EVENT_HEADER, SIGNATURE_HEADER, TIMESTAMP_HEADER,
+};
+use opsml_server::core::webhook::schema::{
+    CreateWebhookRequest, CreateWebhookResponse, WebhookDeliveryListResponse, WebhookListResponse,
+};
+use opsml_sql::schemas::schema::WebhookDelivery;
+
+const WEBHOOK_SECRET: &str = "test-webhook-secret";
+
+async fn get_deliveries(helper: &TestHelper, webhook_uid: &str) -> Vec<WebhookDelivery> {
+    let request = Request::builder()
+        .uri(format!("/opsml/api/webhook/{webhook_uid}/deliveries"))
+        .method("GET")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let deliveries: WebhookDeliveryListResponse = serde_json::from_slice(&body).unwrap();
+    deliveries.deliveries
+}
+
+#[tokio::test]
+async fn test_opsml_server_webhook() {
+    let mut helper = TestHelper::new(None).await;
+
+    // endpoint only accepts correctly signed card_registered events
+    let mut endpoint = mockito::Server::new_async().await;
+    let mock = endpoint
+        .mock("POST", "/hook")
+        .match_header(EVENT_HEADER, "card_registered")
+        .match_request(|request| {
+            let timestamp = request.header(TIMESTAMP_HEADER)[0]
+                .to_str()
+                .unwrap()
+                .parse::<i64>()
+                .unwrap();
+            let body = request.utf8_lossy_body().unwrap();
+            let expected = sign_payload(WEBHOOK_SECRET.as_bytes(), timestamp, &body).unwrap();
+
+            request.header(SIGNATURE_HEADER)[0].to_str().unwrap() == format!("sha256={expected}")
+        })
+        .with_status(200)
+        .expect(2)
+        .create_async()
+        .await;
+
+    // 1. Register a webhook for card registrations in the test space
+    let webhook_request = CreateWebhookRequest {
+        url: format!("{}/hook", endpoint.url()),
+        space: Some(helper.space.clone()),
+        event_types: vec!["card_registered".to_string()],
+        secret: Some(WEBHOOK_SECRET.to_string()),
+    };
+
+    let request = Request::builder()
+        .uri("/opsml/api/webhook")
+        .method("POST")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(serde_json::to_string(&webhook_request).unwrap()))
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let created: CreateWebhookResponse = serde_json::from_slice(&body).unwrap();
+    assert_eq!(created.secret, WEBHOOK_SECRET);
+    let webhook_uid = created.info.uid.clone();
+
+    // invalid event types are rejected
+    let invalid_request = CreateWebhookRequest {
+        event_types: vec!["card_exploded".to_string()],
+        ..webhook_request
+    };
+
+    let request = Request::builder()
+        .uri("/opsml/api/webhook")
+        .method("POST")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(serde_json::to_string(&invalid_request).unwrap()))
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // 2. Registering a card delivers a signed event
+    helper.create_modelcard().await;
+
+    // deliveries are created asynchronously by the outbox dispatcher
+    tokio::time::sleep(std::time::Duration::from_millis(1000)).await;
+
+    let deliveries = get_deliveries(&helper, &webhook_uid).await;
+    assert_eq!(deliveries.len(), 1);
+    assert_eq!(deliveries[0].status, "success");
+    assert_eq!(deliveries[0].attempts, 1);
+    assert_eq!(deliveries[0].response_status, Some(200));
+
+    let payload: serde_json::Value = serde_json::from_str(&deliveries[0].payload).unwrap();
+    assert_eq!(payload["event_type"], "card_registered");
+    assert_eq!(payload["uid"], helper.key.uid.as_str());
+
+    // 3. Replay the delivery
+    let request = Request::builder()
+        .uri(format!(
+            "/opsml/api/webhook/delivery/{}/replay",
+            deliveries[0].uid
+        ))
+        .method("POST")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    tokio::time::sleep(std::time::Duration::from_millis(1000)).await;
+
+    let deliveries = get_deliveries(&helper, &webhook_uid).await;
+    assert_eq!(deliveries.len(), 2);
+    assert!(deliveries.iter().all(|d| d.status == "success"));
+    mock.assert_async().await;
+
+    // 4. List and delete the webhook
+    let request = Request::builder()
+        .uri("/opsml/api/webhook")
+        .method("GET")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let webhooks: WebhookListResponse = serde_json::from_slice(&body).unwrap();
+    assert_eq!(webhooks.webhooks.len(), 1);
+
+    let request = Request::builder()
+        .uri(format!("/opsml/api/webhook/{webhook_uid}"))
+        .method("DELETE")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    helper.cleanup();
+}
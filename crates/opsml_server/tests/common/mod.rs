@@ -42,7 +42,7 @@ fn cleanup() {
     }
 }
 
-fn get_connection_uri() -> String {
+pub fn get_connection_uri() -> String {
     let mut current_dir = env::current_dir().expect("Failed to get current directory");
     current_dir.push("opsml.db");
 
@@ -476,6 +476,7 @@ impl TestHelper {
                 registry_type: RegistryType::Data,
                 encrypted_key: vec![],
                 storage_key: "".to_string(),
+                has_manifest: true,
             },
             server: scouter_server,
             sso_server: mock_sso_server,
@@ -528,6 +529,23 @@ impl TestHelper {
             .unwrap()
     }
 
+    /// Send a request authenticated with the provided bearer token instead of the helper's JWT
+    pub async fn send_oneshot_with_token(
+        &self,
+        mut request: Request<Body>,
+        token: &str,
+    ) -> Response<Body> {
+        request.headers_mut().insert(
+            header::AUTHORIZATION,
+            format!("Bearer {token}").parse().unwrap(),
+        );
+        request
+            .headers_mut()
+            .insert(header::USER_AGENT, "opsml-test".parse().unwrap());
+
+        self.app.clone().oneshot(request).await.unwrap()
+    }
+
     pub fn cleanup(&self) {
         cleanup();
     }
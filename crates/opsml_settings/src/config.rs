@@ -1,5 +1,7 @@
 use crate::error::SettingsError;
 use base64::prelude::*;
+use opsml_crypt::{FileKeyProvider, KeyProvider, LocalKeyProvider};
+use opsml_types::contracts::{SignaturePolicy, SignerKeyFile};
 use opsml_types::{SqlType, StorageType};
 use rusty_logging::logger::{LoggingConfig, WriteLevel};
 use rusty_logging::LogLevel;
@@ -8,8 +10,15 @@ use std::default::Default;
 use std::env;
 use std::path::PathBuf;
 use std::str::FromStr;
+use std::sync::Arc;
 use tracing::warn;
 
+/// Days a deleted card stays in the trash when `OPSML_TRASH_RETENTION_DAYS` is unset or invalid
+const DEFAULT_TRASH_RETENTION_DAYS: i64 = 30;
+
+/// Longest supported trash retention (100 years)
+const MAX_TRASH_RETENTION_DAYS: i64 = 36_500;
+
 #[derive(Debug, Clone, Serialize, PartialEq)]
 pub enum OpsmlMode {
     Client,
@@ -24,6 +33,7 @@ pub struct ApiSettings {
     pub password: String,
     pub prod_token: Option<String>,
     pub use_sso: bool,
+    pub api_token: Option<String>,
 }
 
 /// StorageSettings for used with all storage clients
@@ -52,6 +62,7 @@ impl OpsmlStorageSettings {
                 password: "guest".to_string(),
                 use_sso: false,
                 prod_token: None,
+                api_token: None,
             },
             storage_type: StorageType::Local,
         }
@@ -66,6 +77,17 @@ pub struct DatabaseSettings {
     pub sql_type: SqlType,
 }
 
+impl DatabaseSettings {
+    /// Settings for connecting to a database other than the configured tracking uri
+    pub fn from_uri(connection_uri: &str, max_connections: u32) -> Self {
+        DatabaseSettings {
+            connection_uri: connection_uri.to_string(),
+            max_connections,
+            sql_type: OpsmlConfig::get_sql_type(connection_uri),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default, Serialize)]
 pub struct AuthSettings {
     pub jwt_secret: String,
@@ -75,6 +97,7 @@ pub struct AuthSettings {
     pub prod_token: Option<String>,
     pub scouter_secret: String,
     pub use_sso: bool,
+    pub api_token: Option<String>,
 }
 
 #[derive(Debug, Clone, Default, Serialize)]
@@ -91,6 +114,25 @@ impl ScouterSettings {
     }
 }
 
+/// Where the key that wraps stored artifact keys and webhook secrets lives
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct KeyProviderSettings {
+    /// `local` to derive it from OPSML_ENCRYPT_SECRET, or `file` to read it from a key file
+    pub provider: String,
+    pub key_file: Option<PathBuf>,
+    /// Label of the key in the key file used to wrap new keys
+    pub key_label: Option<String>,
+}
+
+/// Card manifest signing and signature enforcement
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SigningSettings {
+    /// Signer key file used to sign the manifests of registered cards
+    pub signing_key: Option<PathBuf>,
+    /// How signatures are enforced when cards are loaded
+    pub signature_policy: SignaturePolicy,
+}
+
 /// OpsmlConfig for use with both server and client implementations
 /// OpsmlConfig is the main primary configuration struct for the Opsml system
 /// Based on provided env variables, it will be used to determine if opsml is running in client or server mode.
@@ -105,10 +147,14 @@ pub struct OpsmlConfig {
     pub opsml_registry_path: String,
     pub scouter_settings: ScouterSettings,
     pub auth_settings: AuthSettings,
+    pub key_provider_settings: KeyProviderSettings,
+    pub signing_settings: SigningSettings,
     pub database_settings: DatabaseSettings,
     pub logging_config: LoggingConfig,
     pub mode: OpsmlMode,
     pub base_path: PathBuf,
+    /// Days a deleted card stays in the trash before its artifacts are purged
+    pub trash_retention_days: i64,
 }
 
 impl Default for OpsmlConfig {
@@ -176,6 +222,27 @@ impl Default for OpsmlConfig {
                 .parse()
                 .unwrap_or(false),
             prod_token: env::var("OPSML_PROD_TOKEN").ok(),
+            api_token: env::var("OPSML_API_TOKEN").ok(),
+        };
+
+        let key_provider_settings = KeyProviderSettings {
+            provider: env::var("OPSML_KEY_PROVIDER").unwrap_or_else(|_| "local".to_string()),
+            key_file: env::var("OPSML_KEY_FILE").ok().map(PathBuf::from),
+            key_label: env::var("OPSML_KEY_LABEL").ok(),
+        };
+
+        // an unknown policy is enforced strictly rather than silently weakened
+        let signature_policy = match env::var("OPSML_SIGNATURE_POLICY") {
+            Ok(policy) => SignaturePolicy::from_string(&policy).unwrap_or_else(|e| {
+                warn!("{e}, enforcing the require policy");
+                SignaturePolicy::Require
+            }),
+            Err(_) => SignaturePolicy::default(),
+        };
+
+        let signing_settings = SigningSettings {
+            signing_key: env::var("OPSML_SIGNING_KEY").ok().map(PathBuf::from),
+            signature_policy,
         };
 
         // set database settings
@@ -228,9 +295,32 @@ impl Default for OpsmlConfig {
             database_settings,
             scouter_settings,
             auth_settings,
+            key_provider_settings,
+            signing_settings,
             mode,
             logging_config,
             base_path,
+            trash_retention_days: parse_trash_retention_days(
+                env::var("OPSML_TRASH_RETENTION_DAYS").ok().as_deref(),
+            ),
+        }
+    }
+}
+
+/// Parses the trash retention, falling back to the default for values that are not a number
+/// of days between 0 and [`MAX_TRASH_RETENTION_DAYS`]
+fn parse_trash_retention_days(value: Option<&str>) -> i64 {
+    let Some(value) = value else {
+        return DEFAULT_TRASH_RETENTION_DAYS;
+    };
+
+    match value.parse::<i64>() {
+        Ok(days) if (0..=MAX_TRASH_RETENTION_DAYS).contains(&days) => days,
+        _ => {
+            warn!(
+                "Invalid OPSML_TRASH_RETENTION_DAYS {value}, expected 0 to {MAX_TRASH_RETENTION_DAYS} days. Using {DEFAULT_TRASH_RETENTION_DAYS} days"
+            );
+            DEFAULT_TRASH_RETENTION_DAYS
         }
     }
 }
@@ -334,12 +424,44 @@ impl OpsmlConfig {
                 password: self.auth_settings.password.clone(),
                 use_sso: self.auth_settings.use_sso,
                 prod_token: self.auth_settings.prod_token.clone(),
+                api_token: self.auth_settings.api_token.clone(),
             },
         })
     }
 }
 
 impl OpsmlConfig {
+    /// Creates the key provider that wraps artifact keys and webhook secrets stored by the server
+    pub fn key_provider(&self) -> Result<Arc<dyn KeyProvider>, SettingsError> {
+        let settings = &self.key_provider_settings;
+
+        match settings.provider.as_str() {
+            "local" => Ok(Arc::new(LocalKeyProvider::new(
+                &BASE64_STANDARD.decode(&self.auth_settings.jwt_secret)?,
+            ))),
+            "file" => {
+                let key_file = settings
+                    .key_file
+                    .as_ref()
+                    .ok_or(SettingsError::MissingKeyFile)?;
+
+                Ok(Arc::new(FileKeyProvider::from_file(
+                    key_file,
+                    settings.key_label.as_deref(),
+                )?))
+            }
+            provider => Err(SettingsError::UnknownKeyProvider(provider.to_string())),
+        }
+    }
+
+    /// Signer key file used to sign card manifests, if one is configured
+    pub fn signer(&self) -> Result<Option<SignerKeyFile>, SettingsError> {
+        match &self.signing_settings.signing_key {
+            Some(path) => Ok(Some(SignerKeyFile::from_file(path)?)),
+            None => Ok(None),
+        }
+    }
+
     /// Create a new OpsmlConfig instance
     ///
     /// # Returns
@@ -489,7 +611,80 @@ mod tests {
         assert_eq!(opsml_config.auth_settings.username, "guest");
         assert_eq!(opsml_config.auth_settings.password, "guest");
         assert_eq!(opsml_config.scouter_settings.server_uri, "");
+        assert_eq!(opsml_config.key_provider_settings.provider, "local");
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_parse_trash_retention_days() {
+        assert_eq!(parse_trash_retention_days(None), 30);
+        assert_eq!(parse_trash_retention_days(Some("0")), 0);
+        assert_eq!(parse_trash_retention_days(Some("7")), 7);
+        assert_eq!(parse_trash_retention_days(Some("-1")), 30);
+        assert_eq!(parse_trash_retention_days(Some("abc")), 30);
+        assert_eq!(parse_trash_retention_days(Some(&i64::MAX.to_string())), 30);
+    }
+
+    #[test]
+    fn test_key_provider() {
+        let opsml_config = OpsmlConfig::default();
+        assert!(opsml_config
+            .key_provider()
+            .unwrap()
+            .key_id()
+            .starts_with("local:"));
+
+        let opsml_config = OpsmlConfig {
+            key_provider_settings: KeyProviderSettings {
+                provider: "file".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert!(matches!(
+            opsml_config.key_provider(),
+            Err(SettingsError::MissingKeyFile)
+        ));
+
+        let opsml_config = OpsmlConfig {
+            key_provider_settings: KeyProviderSettings {
+                provider: "kms".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert!(opsml_config.key_provider().is_err());
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_signer() {
+        let opsml_config = OpsmlConfig::default();
+        assert_eq!(
+            opsml_config.signing_settings.signature_policy,
+            SignaturePolicy::Warn
+        );
+        assert!(opsml_config.signer().unwrap().is_none());
+
+        let key = opsml_crypt::SigningKey::generate().unwrap();
+        let path = env::temp_dir().join(format!("opsml_signer_{}.json", key.key_id()));
+        SignerKeyFile::new("space", &key).write(&path).unwrap();
+
+        let opsml_config = OpsmlConfig {
+            signing_settings: SigningSettings {
+                signing_key: Some(path.clone()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let signer = opsml_config.signer().unwrap().unwrap();
+
+        assert_eq!(signer.space, "space");
+        assert_eq!(signer.signing_key().unwrap().key_id(), key.key_id());
 
+        std::fs::remove_file(path).unwrap();
         cleanup();
     }
 }
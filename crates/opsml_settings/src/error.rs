@@ -6,4 +6,16 @@ use tracing::error;
 pub enum SettingsError {
     #[error("Failed to decode base64: {0}")]
     Base64DecodeError(#[from] DecodeError),
+
+    #[error(transparent)]
+    CryptError(#[from] opsml_crypt::error::CryptError),
+
+    #[error(transparent)]
+    TypeError(#[from] opsml_types::error::TypeError),
+
+    #[error("Unknown key provider: {0}. Expected local or file")]
+    UnknownKeyProvider(String),
+
+    #[error("OPSML_KEY_FILE must be set to use the file key provider")]
+    MissingKeyFile,
 }
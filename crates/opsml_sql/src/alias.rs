@@ -0,0 +1,95 @@
+use crate::base::SqlClient;
+use crate::error::SqlError;
+use crate::schemas::schema::VersionAliasRecord;
+use opsml_types::cards::CardTable;
+use opsml_types::contracts::{parse_version_alias, validate_alias_name, CardQueryArgs};
+use opsml_types::RegistryType;
+use tracing::instrument;
+
+/// Replaces a version alias (e.g. `@champion`) in the query with the uid the alias points to.
+///
+/// Queries without an alias are returned unchanged.
+///
+/// # Arguments
+///
+/// * `client` - The sql client
+/// * `args` - The card query. `space` and `name` are required when `version` is an alias
+///
+/// # Returns
+///
+/// * `CardQueryArgs` - The query with the alias resolved to a uid
+#[instrument(skip_all)]
+pub async fn resolve_version_alias(
+    client: &impl SqlClient,
+    args: &CardQueryArgs,
+) -> Result<CardQueryArgs, SqlError> {
+    let Some(alias) = args.version.as_deref().and_then(parse_version_alias) else {
+        return Ok(args.clone());
+    };
+
+    let (Some(space), Some(name)) = (&args.space, &args.name) else {
+        return Err(SqlError::VersionAliasMissingCard(alias.to_string()));
+    };
+
+    let record = client
+        .get_version_alias(&args.registry_type.to_string(), space, name, alias)
+        .await?
+        .ok_or_else(|| SqlError::VersionAliasNotFound(format!("{space}/{name}@{alias}")))?;
+
+    Ok(CardQueryArgs {
+        uid: Some(record.uid),
+        version: None,
+        ..args.clone()
+    })
+}
+
+/// Builds the alias record for pointing `alias` at an exact card version.
+///
+/// Nothing is written; store the result with `SqlClient::set_version_alias`.
+///
+/// # Arguments
+///
+/// * `client` - The sql client
+/// * `registry_type` - The registry of the card
+/// * `space` - The space of the card
+/// * `name` - The name of the card
+/// * `alias` - The alias name without the `@` prefix
+/// * `version` - The exact version the alias points to
+/// * `username` - The user moving the alias
+#[instrument(skip_all)]
+pub async fn build_version_alias(
+    client: &impl SqlClient,
+    registry_type: &RegistryType,
+    space: &str,
+    name: &str,
+    alias: &str,
+    version: &str,
+    username: &str,
+) -> Result<VersionAliasRecord, SqlError> {
+    validate_alias_name(alias)?;
+
+    let table = CardTable::from_registry_type(registry_type);
+    let args = CardQueryArgs {
+        space: Some(space.to_string()),
+        name: Some(name.to_string()),
+        version: Some(version.to_string()),
+        registry_type: registry_type.clone(),
+        ..Default::default()
+    };
+
+    let uid = client
+        .query_cards(&table, &args)
+        .await?
+        .find_version(version)
+        .ok_or_else(|| SqlError::CardNotFound(format!("{space}/{name}/v{version}")))?;
+
+    Ok(VersionAliasRecord::new(
+        registry_type,
+        space,
+        name,
+        alias,
+        &uid,
+        version,
+        username,
+    ))
+}
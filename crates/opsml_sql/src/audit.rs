@@ -0,0 +1,218 @@
+use crate::base::SqlClient;
+use crate::error::SqlError;
+use crate::schemas::schema::{AuditChainHeadRecord, AuditEventRecord};
+use chrono::{DateTime, Utc};
+use futures::stream::{try_unfold, Stream};
+use opsml_types::contracts::{AuditChainBreak, AuditChainReport};
+use opsml_utils::utils::get_utc_datetime;
+use tracing::{error, instrument};
+
+/// Incrementally verifies the audit event hash chain.
+///
+/// Events must be passed in insertion (id) order. Events written before hash chaining was enabled
+/// are counted as unchained as long as they precede the first chained event. When the recorded
+/// chain head is given, the chain must reach it, so events deleted from the end are detected.
+#[derive(Debug, Default)]
+pub struct AuditChainVerifier {
+    report: AuditChainReport,
+    chain_started: bool,
+    expected_prev_hash: String,
+    head: Option<AuditChainHeadRecord>,
+    head_reached: bool,
+}
+
+impl AuditChainVerifier {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Verifier that also checks the chain reaches `head`. Read the head before walking the
+    /// events so events written during verification are not reported as missing.
+    /// A head at sequence 0 is a seeded head that no chained event has reached yet
+    pub fn with_head(head: Option<AuditChainHeadRecord>) -> Self {
+        Self {
+            head: head.filter(|head| head.seq > 0),
+            ..Self::default()
+        }
+    }
+
+    /// Checks the next event in the chain
+    ///
+    /// # Returns
+    /// * `bool` - false once the chain is broken. Further calls are ignored
+    pub fn check(&mut self, record: &AuditEventRecord) -> bool {
+        if self.report.first_break.is_some() {
+            return false;
+        }
+
+        self.report.events_checked += 1;
+        self.report.last_id = Some(record.id);
+
+        let hash = match &record.hash {
+            Some(hash) => hash,
+            None if !self.chain_started => {
+                self.report.unchained_events += 1;
+                return true;
+            }
+            None => return self.fail(record, "Event is missing its hash"),
+        };
+
+        self.chain_started = true;
+
+        let prev_hash = record.prev_hash.as_deref().unwrap_or_default();
+        if prev_hash != self.expected_prev_hash {
+            return self.fail(
+                record,
+                "Previous hash does not match the preceding event (event deleted or inserted)",
+            );
+        }
+
+        match (record.seq, self.report.last_seq) {
+            (Some(seq), Some(last_seq)) if seq != last_seq + 1 => {
+                return self.fail(
+                    record,
+                    "Sequence number does not follow the preceding event (event deleted or inserted)",
+                );
+            }
+            (None, Some(_)) => return self.fail(record, "Event is missing its sequence number"),
+            _ => {}
+        }
+
+        if record.compute_hash() != *hash {
+            return self.fail(
+                record,
+                "Event hash does not match its contents (event modified)",
+            );
+        }
+
+        if let (Some(head), Some(seq)) = (&self.head, record.seq) {
+            if head.seq == seq {
+                if head.hash != *hash {
+                    return self.fail(record, "Event does not match the recorded chain head");
+                }
+                self.head_reached = true;
+            }
+        }
+
+        self.expected_prev_hash = hash.clone();
+        self.report.last_hash = Some(hash.clone());
+        self.report.last_seq = record.seq.or(self.report.last_seq);
+
+        true
+    }
+
+    fn fail(&mut self, record: &AuditEventRecord, reason: &str) -> bool {
+        self.fail_at(record.id, record.created_at, reason)
+    }
+
+    fn fail_at(&mut self, id: i32, created_at: DateTime<Utc>, reason: &str) -> bool {
+        error!("Audit chain broken at event {}: {}", id, reason);
+        self.report.first_break = Some(AuditChainBreak {
+            id,
+            created_at,
+            reason: reason.to_string(),
+        });
+        false
+    }
+
+    pub fn finish(mut self) -> AuditChainReport {
+        if self.report.first_break.is_none() {
+            let last_id = self.report.last_id.unwrap_or_default();
+
+            match self.head.take() {
+                Some(head) if !self.head_reached => {
+                    let reason = format!(
+                        "Audit chain ends at sequence {} but the recorded head is at sequence {} (events deleted from the end)",
+                        self.report.last_seq.unwrap_or_default(),
+                        head.seq
+                    );
+                    self.fail_at(last_id, head.updated_at, &reason);
+                }
+                None if self.report.last_seq.is_some() => {
+                    self.fail_at(
+                        last_id,
+                        get_utc_datetime(),
+                        "Audit chain head is missing (head record deleted)",
+                    );
+                }
+                _ => {}
+            }
+        }
+
+        self.report.verified = self.report.first_break.is_none();
+        self.report
+    }
+}
+
+/// Walks the full audit event table in batches and verifies the hash chain
+///
+/// # Arguments
+/// * `client` - The sql client
+/// * `batch_size` - Number of events to load per query
+///
+/// # Returns
+/// * `AuditChainReport` - The verification report, including the first break if any
+#[instrument(skip_all)]
+pub async fn verify_audit_chain(
+    client: &impl SqlClient,
+    batch_size: i32,
+) -> Result<AuditChainReport, SqlError> {
+    // read the head first, events written while walking the chain are past it
+    let head = client.get_audit_chain_head().await?;
+    let mut verifier = AuditChainVerifier::with_head(head);
+    let mut after_id = 0;
+
+    loop {
+        let records = client.get_audit_event_chain(after_id, batch_size).await?;
+
+        let Some(last) = records.last() else {
+            break;
+        };
+        after_id = last.id;
+
+        if !records.iter().all(|record| verifier.check(record)) {
+            break;
+        }
+    }
+
+    Ok(verifier.finish())
+}
+
+/// Exports all audit events created within a time range as JSON Lines
+///
+/// Each line contains the full event, including its hash chain fields, so the export can be
+/// verified offline. Events are loaded one batch at a time, so the export is never held in memory.
+///
+/// # Arguments
+/// * `client` - The sql client
+/// * `start_time` - Start of the time range (inclusive)
+/// * `end_time` - End of the time range (inclusive)
+/// * `batch_size` - Number of events to load per query
+///
+/// # Returns
+/// * `Stream` - One chunk of JSON Lines per batch, in insertion order
+pub fn export_audit_events<C: SqlClient>(
+    client: C,
+    start_time: DateTime<Utc>,
+    end_time: DateTime<Utc>,
+    batch_size: i32,
+) -> impl Stream<Item = Result<String, SqlError>> {
+    try_unfold((client, 0), move |(client, after_id)| async move {
+        let records = client
+            .get_audit_events_by_time(&start_time, &end_time, after_id, batch_size)
+            .await?;
+
+        let Some(last) = records.last() else {
+            return Ok(None);
+        };
+        let after_id = last.id;
+
+        let mut chunk = String::new();
+        for record in &records {
+            chunk.push_str(&serde_json::to_string(record)?);
+            chunk.push('\n');
+        }
+
+        Ok(Some((chunk, (client, after_id))))
+    })
+}
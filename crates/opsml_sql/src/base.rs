@@ -1,17 +1,55 @@
 use crate::error::SqlError;
 use crate::schemas::schema::{
-    CardResults, CardSummary, HardwareMetricsRecord, MetricRecord, ParameterRecord, QueryStats,
-    ServerCard, User, VersionSummary,
+    ApiToken, AuditChainHeadRecord, AuditEventRecord, CardLineageEdge, CardLineageNodeRecord,
+    CardResults, CardSearchRecord, CardStageRecord, CardSummary, DeletedCardRecord,
+    HardwareMetricsRecord, MetricRecord, OutboxEvent, ParameterRecord, QueryStats, ServerCard,
+    SigningKeyRecord, User, VersionAliasHistoryRecord, VersionAliasRecord, VersionSummary, Webhook,
+    WebhookDelivery,
 };
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use opsml_semver::VersionParser;
 use opsml_settings::config::DatabaseSettings;
 use opsml_types::{
     cards::CardTable,
-    contracts::{ArtifactKey, AuditEvent, CardQueryArgs, SpaceNameEvent, SpaceRecord, SpaceStats},
-    RegistryType,
+    contracts::{
+        ArtifactKey, AuditEvent, AuditEventQueryArgs, CardQueryArgs, CardSearchQuery,
+        CardStageResponse, MetricQuery, SpaceNameEvent, SpaceRecord, SpaceStats,
+    },
+    CardStage, RegistryType,
 };
 
+/// Range filters of a metric page query that are set, as a comparison and the value to bind.
+/// Backends append the filters and bind the values in this order
+pub fn metric_page_filters(query: &MetricQuery) -> Vec<(&'static str, i64)> {
+    [
+        ("step >=", query.start_step.map(i64::from)),
+        ("step <=", query.end_step.map(i64::from)),
+        ("timestamp >=", query.start_timestamp),
+        ("timestamp <=", query.end_timestamp),
+        ("idx >", query.cursor),
+    ]
+    .into_iter()
+    .filter_map(|(filter, value)| value.map(|value| (filter, value)))
+    .collect()
+}
+
+/// Adds a stage filter to a card query. Only model and service cards have a stage
+pub fn add_stage_filter(
+    builder: &mut String,
+    table: &CardTable,
+    stage: &CardStage,
+) -> Result<(), SqlError> {
+    match table {
+        CardTable::Model | CardTable::Service => {
+            // stage is an enum, so the value is safe to inline
+            builder.push_str(format!(" AND stage = '{stage}'").as_str());
+            Ok(())
+        }
+        _ => Err(SqlError::StageNotSupported),
+    }
+}
+
 pub fn add_version_bounds(builder: &mut String, version: &str) -> Result<(), SqlError> {
     let version_bounds = VersionParser::get_version_to_search(version)?;
 
@@ -75,8 +113,22 @@ pub trait SqlClient: Sized {
         query_args: &CardQueryArgs,
     ) -> Result<CardResults, SqlError>;
 
-    async fn insert_card(&self, table: &CardTable, card: &ServerCard) -> Result<(), SqlError>;
-    async fn update_card(&self, table: &CardTable, card: &ServerCard) -> Result<(), SqlError>;
+    /// Insert a card. `events` are written to the event outbox in the same transaction
+    async fn insert_card(
+        &self,
+        table: &CardTable,
+        card: &ServerCard,
+        events: &[OutboxEvent],
+    ) -> Result<(), SqlError>;
+
+    /// Update a card. `events` are written to the event outbox in the same transaction
+    async fn update_card(
+        &self,
+        table: &CardTable,
+        card: &ServerCard,
+        events: &[OutboxEvent],
+    ) -> Result<(), SqlError>;
+
     async fn get_unique_space_names(&self, table: &CardTable) -> Result<Vec<String>, SqlError>;
     async fn query_stats(
         &self,
@@ -94,8 +146,134 @@ pub trait SqlClient: Sized {
         table: &CardTable,
     ) -> Result<Vec<CardSummary>, SqlError>;
 
-    async fn delete_card(&self, table: &CardTable, uid: &str)
-        -> Result<(String, String), SqlError>;
+    /// Delete a card and its artifact key. `events` are written to the event outbox in the
+    /// same transaction
+    ///
+    /// # Returns
+    ///
+    /// * `(String, String)` - The space and name of the deleted card
+    async fn delete_card(
+        &self,
+        table: &CardTable,
+        uid: &str,
+        events: &[OutboxEvent],
+    ) -> Result<(String, String), SqlError>;
+
+    /// Move a card to the trash. The card is hidden from queries until it is restored or purged.
+    /// `events` are written to the event outbox in the same transaction
+    ///
+    /// # Returns
+    ///
+    /// * `(String, String)` - The space and name of the deleted card
+    async fn soft_delete_card(
+        &self,
+        table: &CardTable,
+        uid: &str,
+        username: &str,
+        events: &[OutboxEvent],
+    ) -> Result<(String, String), SqlError>;
+
+    /// Restore a card from the trash. `events` are written to the event outbox in the same transaction
+    ///
+    /// # Returns
+    ///
+    /// * `(String, String)` - The space and name of the restored card
+    async fn restore_card(
+        &self,
+        table: &CardTable,
+        uid: &str,
+        events: &[OutboxEvent],
+    ) -> Result<(String, String), SqlError>;
+
+    /// Get the cards in the trash, most recently deleted first
+    ///
+    /// # Arguments
+    ///
+    /// * `space` - Only return cards in this space
+    /// * `deleted_before` - Only return cards deleted at or before this time
+    async fn get_deleted_cards(
+        &self,
+        table: &CardTable,
+        space: Option<&str>,
+        deleted_before: Option<DateTime<Utc>>,
+    ) -> Result<Vec<DeletedCardRecord>, SqlError>;
+
+    /// Save the README content of a card to the search index. The README is shared by all
+    /// versions of the card
+    async fn update_search_readme(
+        &self,
+        registry_type: &RegistryType,
+        space: &str,
+        name: &str,
+        readme: &str,
+    ) -> Result<(), SqlError>;
+
+    /// Full-text search across card names, spaces, tags, README content and metadata.
+    /// Cards are indexed when they are inserted or updated and deleted cards are never returned
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The search query
+    /// * `spaces` - Only return cards in these spaces. `None` searches all spaces
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<CardSearchRecord>` - Matching card versions, most relevant first
+    async fn search_cards(
+        &self,
+        query: &CardSearchQuery,
+        spaces: Option<&[String]>,
+    ) -> Result<Vec<CardSearchRecord>, SqlError>;
+
+    /// Walk the lineage graph from a card across all registries. Edges are followed up to
+    /// `upstream_depth` hops towards the cards it depends on and up to `downstream_depth` hops
+    /// towards the cards that depend on it
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<CardLineageEdge>` - Edges traversed from the card
+    async fn get_lineage_edges(
+        &self,
+        uid: &str,
+        upstream_depth: i32,
+        downstream_depth: i32,
+    ) -> Result<Vec<CardLineageEdge>, SqlError>;
+
+    /// Get the cards reached by `get_lineage_edges`, including the card itself.
+    /// Cards that have been purged are not returned
+    async fn get_lineage_nodes(
+        &self,
+        uid: &str,
+        upstream_depth: i32,
+        downstream_depth: i32,
+    ) -> Result<Vec<CardLineageNodeRecord>, SqlError>;
+
+    /// Get the stage of a model or service card version
+    async fn get_card_stage(
+        &self,
+        table: &CardTable,
+        uid: &str,
+    ) -> Result<Option<CardStageRecord>, SqlError>;
+
+    /// Get all versions of a card that are currently in a stage
+    async fn get_stage_holders(
+        &self,
+        table: &CardTable,
+        space: &str,
+        name: &str,
+        stage: &str,
+    ) -> Result<Vec<CardStageRecord>, SqlError>;
+
+    /// Apply a planned stage transition. The versions of the card are locked and read inside the
+    /// write transaction, and nothing is written if they no longer match the plan.
+    /// `events` are written to the event outbox in the same transaction
+    async fn update_card_stages(
+        &self,
+        table: &CardTable,
+        transition: &CardStageResponse,
+        archive_existing: bool,
+        events: &[OutboxEvent],
+    ) -> Result<(), SqlError>;
 
     /// Insert run metric
     ///
@@ -143,6 +321,21 @@ pub trait SqlClient: Sized {
         names: &'life2 [String],
     ) -> Result<Vec<MetricRecord>, SqlError>;
 
+    /// Get a page of run metrics in logging order, filtered by step and timestamp range and
+    /// downsampled when the query sets `max_points`
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The experiment, metric names, ranges and page to get
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<MetricRecord>` - The metrics of the page
+    async fn get_experiment_metric_page(
+        &self,
+        query: &MetricQuery,
+    ) -> Result<Vec<MetricRecord>, SqlError>;
+
     /// Get run metric names
     ///
     /// # Arguments
@@ -275,8 +468,41 @@ pub trait SqlClient: Sized {
     /// * `Result<(), SqlError>` - The result of the operation
     async fn update_artifact_key(&self, key: &ArtifactKey) -> Result<(), SqlError>;
 
+    /// Get a page of artifact keys ordered by uid and registry type, used to walk every key
+    ///
+    /// # Arguments
+    ///
+    /// * `after_uid` - The uid of the last key of the previous page. Empty for the first page
+    /// * `after_registry_type` - The registry type of the last key of the previous page
+    /// * `limit` - The maximum number of keys to return
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<ArtifactKey>` - The artifact keys
+    async fn get_artifact_key_page(
+        &self,
+        after_uid: &str,
+        after_registry_type: &str,
+        limit: i64,
+    ) -> Result<Vec<ArtifactKey>, SqlError>;
+
+    /// Replace the encrypted key of artifact keys in a single transaction. Unlike
+    /// `update_artifact_key`, `created_at` is left unchanged
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - The artifact keys
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), SqlError>` - The result of the operation
+    async fn rewrap_artifact_keys(&self, keys: &[ArtifactKey]) -> Result<(), SqlError>;
+
     /// Insert audit event
     ///
+    /// The event is chained to the previously inserted event by storing the previous row's hash
+    /// along with a hash of the event itself (see `AuditEventRecord::compute_hash`)
+    ///
     /// # Arguments
     /// * `event` - The audit event
     ///
@@ -284,6 +510,56 @@ pub trait SqlClient: Sized {
     /// * `Result<(), SqlError>` - The result of the operation
     async fn insert_audit_event(&self, event: AuditEvent) -> Result<(), SqlError>;
 
+    /// Get the latest sequence number and hash of the audit hash chain
+    ///
+    /// # Returns
+    /// * `Option<AuditChainHeadRecord>` - None if no chained event has been written yet
+    async fn get_audit_chain_head(&self) -> Result<Option<AuditChainHeadRecord>, SqlError>;
+
+    /// Get a batch of audit events in insertion order, used to walk the audit hash chain
+    ///
+    /// # Arguments
+    /// * `after_id` - Only return events with an id greater than this value
+    /// * `limit` - The maximum number of events to return
+    ///
+    /// # Returns
+    /// * `Vec<AuditEventRecord>` - The audit events ordered by id
+    async fn get_audit_event_chain(
+        &self,
+        after_id: i32,
+        limit: i32,
+    ) -> Result<Vec<AuditEventRecord>, SqlError>;
+
+    /// Get a batch of audit events created within a time range, ordered by id
+    ///
+    /// # Arguments
+    /// * `start_time` - Start of the time range (inclusive)
+    /// * `end_time` - End of the time range (inclusive)
+    /// * `after_id` - Only return events with an id greater than this value
+    /// * `limit` - The maximum number of events to return
+    ///
+    /// # Returns
+    /// * `Vec<AuditEventRecord>` - The audit events ordered by id
+    async fn get_audit_events_by_time(
+        &self,
+        start_time: &DateTime<Utc>,
+        end_time: &DateTime<Utc>,
+        after_id: i32,
+        limit: i32,
+    ) -> Result<Vec<AuditEventRecord>, SqlError>;
+
+    /// Query audit events with optional filters, newest first
+    ///
+    /// # Arguments
+    /// * `args` - The audit event filters and pagination cursor
+    ///
+    /// # Returns
+    /// * `Vec<AuditEventRecord>` - The matching audit events ordered by id descending
+    async fn get_audit_events(
+        &self,
+        args: &AuditEventQueryArgs,
+    ) -> Result<Vec<AuditEventRecord>, SqlError>;
+
     /// Queries the a card registry for a card version and returns
     /// the artifact keys for loading the card on the client side
     ///
@@ -344,6 +620,291 @@ pub trait SqlClient: Sized {
     /// * `Result<bool, SqlError>` - True if the user is the last admin
     async fn is_last_admin(&self, username: &str) -> Result<bool, SqlError>;
 
+    /// Insert a hashed API token
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - The api token record
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), SqlError>` - The result of the operation
+    async fn insert_api_token(&self, token: &ApiToken) -> Result<(), SqlError>;
+
+    /// Get an API token by its hash
+    ///
+    /// # Arguments
+    ///
+    /// * `hashed_token` - The hash of the raw token
+    ///
+    /// # Returns
+    ///
+    /// * `Option<ApiToken>` - The api token record, if found
+    async fn get_api_token(&self, hashed_token: &str) -> Result<Option<ApiToken>, SqlError>;
+
+    /// Get all API tokens for a user
+    ///
+    /// # Arguments
+    ///
+    /// * `username` - The owner of the tokens
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<ApiToken>` - The api token records
+    async fn get_api_tokens(&self, username: &str) -> Result<Vec<ApiToken>, SqlError>;
+
+    /// Revoke an active API token by name
+    ///
+    /// # Arguments
+    ///
+    /// * `username` - The owner of the token
+    /// * `name` - The name of the token
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - True if a token was revoked
+    async fn revoke_api_token(&self, username: &str, name: &str) -> Result<bool, SqlError>;
+
+    /// Insert a webhook endpoint
+    ///
+    /// # Arguments
+    ///
+    /// * `webhook` - The webhook record
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), SqlError>` - The result of the operation
+    async fn insert_webhook(&self, webhook: &Webhook) -> Result<(), SqlError>;
+
+    /// Get a webhook by uid
+    ///
+    /// # Arguments
+    ///
+    /// * `uid` - The uid of the webhook
+    ///
+    /// # Returns
+    ///
+    /// * `Option<Webhook>` - The webhook record, if found
+    async fn get_webhook(&self, uid: &str) -> Result<Option<Webhook>, SqlError>;
+
+    /// Get all registered webhooks, newest first
+    async fn get_webhooks(&self) -> Result<Vec<Webhook>, SqlError>;
+
+    /// Get active webhooks that subscribe to a space, including webhooks registered for all spaces
+    ///
+    /// # Arguments
+    ///
+    /// * `space` - The space of the event
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<Webhook>` - The webhook records. Event types are not filtered
+    async fn get_active_webhooks(&self, space: &str) -> Result<Vec<Webhook>, SqlError>;
+
+    /// Delete a webhook by uid
+    ///
+    /// # Arguments
+    ///
+    /// * `uid` - The uid of the webhook
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - True if a webhook was deleted
+    async fn delete_webhook(&self, uid: &str) -> Result<bool, SqlError>;
+
+    /// Replace the encrypted secret of webhooks in a single transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `webhooks` - The webhooks
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), SqlError>` - The result of the operation
+    async fn update_webhook_secrets(&self, webhooks: &[Webhook]) -> Result<(), SqlError>;
+
+    /// Insert a webhook delivery into the delivery log
+    async fn insert_webhook_delivery(&self, delivery: &WebhookDelivery) -> Result<(), SqlError>;
+
+    /// Update the status, attempts and response of a webhook delivery
+    async fn update_webhook_delivery(&self, delivery: &WebhookDelivery) -> Result<(), SqlError>;
+
+    /// Get a webhook delivery by uid
+    ///
+    /// # Arguments
+    ///
+    /// * `uid` - The uid of the delivery
+    ///
+    /// # Returns
+    ///
+    /// * `Option<WebhookDelivery>` - The delivery record, if found
+    async fn get_webhook_delivery(&self, uid: &str) -> Result<Option<WebhookDelivery>, SqlError>;
+
+    /// Get the most recent deliveries for a webhook, newest first
+    ///
+    /// # Arguments
+    ///
+    /// * `webhook_uid` - The uid of the webhook
+    /// * `limit` - Maximum number of deliveries to return
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<WebhookDelivery>` - The delivery records
+    async fn get_webhook_deliveries(
+        &self,
+        webhook_uid: &str,
+        limit: i32,
+    ) -> Result<Vec<WebhookDelivery>, SqlError>;
+
+    /// Claim pending deliveries that have not been updated since `stale_before`, oldest first.
+    /// A delivery in progress is updated after every attempt, so a stale delivery was left
+    /// behind by a server that stopped. Claiming sets `updated_at` to now, so concurrent
+    /// servers never resume the same delivery.
+    ///
+    /// # Arguments
+    ///
+    /// * `stale_before` - Pending deliveries last updated before this time are claimed
+    /// * `limit` - Maximum number of deliveries to claim
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<WebhookDelivery>` - The claimed deliveries
+    async fn claim_stale_webhook_deliveries(
+        &self,
+        stale_before: DateTime<Utc>,
+        limit: i32,
+    ) -> Result<Vec<WebhookDelivery>, SqlError>;
+
+    /// Point a version alias at a card version and record the change in the alias history
+    ///
+    /// # Returns
+    ///
+    /// * `Option<VersionAliasRecord>` - The alias before it was moved, if it existed
+    async fn set_version_alias(
+        &self,
+        alias: &VersionAliasRecord,
+    ) -> Result<Option<VersionAliasRecord>, SqlError>;
+
+    /// Get a version alias
+    async fn get_version_alias(
+        &self,
+        registry_type: &str,
+        space: &str,
+        name: &str,
+        alias: &str,
+    ) -> Result<Option<VersionAliasRecord>, SqlError>;
+
+    /// Get all version aliases of a card
+    async fn get_version_aliases(
+        &self,
+        registry_type: &str,
+        space: &str,
+        name: &str,
+    ) -> Result<Vec<VersionAliasRecord>, SqlError>;
+
+    /// Delete a version alias and record the deletion in the alias history
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Whether the alias existed
+    async fn delete_version_alias(
+        &self,
+        registry_type: &str,
+        space: &str,
+        name: &str,
+        alias: &str,
+        username: &str,
+    ) -> Result<bool, SqlError>;
+
+    /// Get the history of the version aliases of a card, newest first
+    ///
+    /// # Arguments
+    ///
+    /// * `alias` - Only return the history of this alias
+    /// * `limit` - Maximum number of entries to return
+    async fn get_version_alias_history(
+        &self,
+        registry_type: &str,
+        space: &str,
+        name: &str,
+        alias: Option<&str>,
+        limit: i32,
+    ) -> Result<Vec<VersionAliasHistoryRecord>, SqlError>;
+
+    /// Register the public key of a space signing key
+    async fn insert_signing_key(&self, key: &SigningKeyRecord) -> Result<(), SqlError>;
+
+    /// Get signing keys, including revoked keys
+    ///
+    /// # Arguments
+    ///
+    /// * `space` - Only return the keys of this space
+    async fn get_signing_keys(
+        &self,
+        space: Option<&str>,
+    ) -> Result<Vec<SigningKeyRecord>, SqlError>;
+
+    /// Revoke an active signing key
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - True if a key was revoked
+    async fn revoke_signing_key(&self, key_id: &str) -> Result<bool, SqlError>;
+
+    /// Write events to the event outbox in a single transaction
+    async fn insert_outbox_events(&self, events: &[OutboxEvent]) -> Result<(), SqlError>;
+
+    /// Get outbox events in insertion order
+    ///
+    /// # Arguments
+    ///
+    /// * `after_id` - Only return events with an id greater than this
+    /// * `limit` - Maximum number of events to return
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<OutboxEvent>` - The outbox events
+    async fn get_outbox_events(
+        &self,
+        after_id: i64,
+        limit: i32,
+    ) -> Result<Vec<OutboxEvent>, SqlError>;
+
+    /// Claim pending outbox events for a dispatcher. Events that are unclaimed or whose
+    /// lease has expired are leased to `claimer` until `claimed_until`, so concurrent
+    /// dispatchers never receive the same events.
+    ///
+    /// # Arguments
+    ///
+    /// * `claimer` - Unique name of the dispatcher claiming the events
+    /// * `claimed_until` - When the lease on the claimed events expires
+    /// * `limit` - Maximum number of events to claim
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<OutboxEvent>` - The claimed events in insertion order
+    async fn claim_outbox_events(
+        &self,
+        claimer: &str,
+        claimed_until: DateTime<Utc>,
+        limit: i32,
+    ) -> Result<Vec<OutboxEvent>, SqlError>;
+
+    /// Release every outbox event leased to `claimer` so it can be claimed again
+    async fn release_outbox_events(&self, claimer: &str) -> Result<(), SqlError>;
+
+    /// Record a failed delivery of an outbox event and release its claim. An event that has
+    /// failed `max_attempts` times is parked and is no longer claimed
+    async fn fail_outbox_event(
+        &self,
+        id: i64,
+        error: &str,
+        max_attempts: i32,
+    ) -> Result<(), SqlError>;
+
+    /// Delete a delivered outbox event
+    async fn delete_outbox_event(&self, id: i64) -> Result<(), SqlError>;
+
     async fn get_artifact_key_from_path(
         &self,
         storage_path: &str,
@@ -359,8 +920,12 @@ pub trait SqlClient: Sized {
         table: &CardTable,
     ) -> Result<Vec<VersionSummary>, SqlError>;
 
-    /// inserts record to `opsml_space`
-    async fn insert_space_record(&self, space: &SpaceRecord) -> Result<(), SqlError>;
+    /// inserts record to `opsml_space`. `events` are written to the event outbox in the same transaction
+    async fn insert_space_record(
+        &self,
+        space: &SpaceRecord,
+        events: &[OutboxEvent],
+    ) -> Result<(), SqlError>;
 
     /// inserts a space name record to `opsml_space_name` - this is done via the EventBus
     async fn insert_space_name_record(&self, event: &SpaceNameEvent) -> Result<(), SqlError>;
@@ -371,11 +936,21 @@ pub trait SqlClient: Sized {
     /// get a specific space record from `opsml_space`
     async fn get_space_record(&self, space: &str) -> Result<Option<SpaceRecord>, SqlError>;
 
-    /// Update a space name record in `opsml_space`
-    async fn update_space_record(&self, space: &SpaceRecord) -> Result<(), SqlError>;
+    /// Update a space name record in `opsml_space`. `events` are written to the event outbox in
+    /// the same transaction
+    async fn update_space_record(
+        &self,
+        space: &SpaceRecord,
+        events: &[OutboxEvent],
+    ) -> Result<(), SqlError>;
 
-    /// Delete a space record from `opsml_space`
-    async fn delete_space_record(&self, space: &str) -> Result<(), SqlError>;
+    /// Delete a space record from `opsml_space`. `events` are written to the event outbox in the
+    /// same transaction
+    async fn delete_space_record(
+        &self,
+        space: &str,
+        events: &[OutboxEvent],
+    ) -> Result<(), SqlError>;
 
     /// Delete a space name record from `opsml_space_name`
     async fn delete_space_name_record(
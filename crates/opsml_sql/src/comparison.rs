@@ -0,0 +1,80 @@
+use crate::base::SqlClient;
+use crate::error::SqlError;
+use opsml_types::cards::{Metric, Parameter};
+use opsml_types::contracts::{
+    ExperimentComparison, ExperimentComparisonRequest, MAX_COMPARED_EXPERIMENTS,
+};
+use std::collections::{HashMap, HashSet};
+use tracing::instrument;
+
+/// Loads the metrics and parameters of several experiments and aligns them for comparison.
+///
+/// Duplicate experiment uids are compared once, in the order they first appear.
+///
+/// # Arguments
+///
+/// * `client` - The sql client
+/// * `request` - The experiments, metric names and parameter names to compare
+///
+/// # Returns
+///
+/// * `ExperimentComparison` - Parameter tables and metric series aligned across experiments
+#[instrument(skip_all)]
+pub async fn compare_experiments(
+    client: &impl SqlClient,
+    request: &ExperimentComparisonRequest,
+) -> Result<ExperimentComparison, SqlError> {
+    let mut seen = HashSet::new();
+    let experiment_uids: Vec<String> = request
+        .experiment_uids
+        .iter()
+        .filter(|uid| seen.insert(uid.as_str()))
+        .cloned()
+        .collect();
+
+    if experiment_uids.is_empty() || experiment_uids.len() > MAX_COMPARED_EXPERIMENTS {
+        return Err(SqlError::InvalidExperimentComparison(
+            MAX_COMPARED_EXPERIMENTS,
+        ));
+    }
+
+    let mut metrics = HashMap::new();
+    let mut parameters = HashMap::new();
+
+    for uid in &experiment_uids {
+        let mut records = client
+            .get_experiment_metric(uid, &request.metric_names)
+            .await?;
+        records.sort_by_key(|record| record.idx);
+
+        let records = records
+            .into_iter()
+            .map(|m| Metric {
+                name: m.name,
+                value: m.value,
+                step: m.step,
+                timestamp: m.timestamp,
+                created_at: m.created_at,
+            })
+            .collect::<Vec<_>>();
+        metrics.insert(uid.clone(), records);
+
+        let records = client
+            .get_experiment_parameter(uid, &request.parameter_names)
+            .await?
+            .into_iter()
+            .map(|p| Parameter {
+                name: p.name,
+                value: p.value.0,
+            })
+            .collect::<Vec<_>>();
+        parameters.insert(uid.clone(), records);
+    }
+
+    Ok(ExperimentComparison::new(
+        experiment_uids,
+        metrics,
+        parameters,
+        &request.maximize,
+    ))
+}
@@ -3,16 +3,23 @@ use crate::error::SqlError;
 use crate::mysql::client::MySqlClient;
 use crate::postgres::client::PostgresClient;
 use crate::schemas::schema::{
-    CardResults, CardSummary, HardwareMetricsRecord, MetricRecord, ParameterRecord, QueryStats,
-    ServerCard, User,
+    ApiToken, AuditChainHeadRecord, AuditEventRecord, CardLineageEdge, CardLineageNodeRecord,
+    CardResults, CardSearchRecord, CardStageRecord, CardSummary, DeletedCardRecord,
+    HardwareMetricsRecord, MetricRecord, OutboxEvent, ParameterRecord, QueryStats, ServerCard,
+    SigningKeyRecord, User, VersionAliasHistoryRecord, VersionAliasRecord, Webhook,
+    WebhookDelivery,
 };
 use crate::schemas::VersionSummary;
 use crate::sqlite::client::SqliteClient;
 use anyhow::Context;
 use anyhow::Result as AnyhowResult;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use opsml_settings::config::DatabaseSettings;
-use opsml_types::contracts::{AuditEvent, SpaceNameEvent, SpaceRecord, SpaceStats};
+use opsml_types::contracts::{
+    AuditEvent, AuditEventQueryArgs, CardSearchQuery, CardStageResponse, MetricQuery,
+    SpaceNameEvent, SpaceRecord, SpaceStats,
+};
 use opsml_types::{
     RegistryType, SqlType,
     {
@@ -37,6 +44,14 @@ impl SqlClientEnum {
             SqlClientEnum::MySql(_) => "MySql".to_string(),
         }
     }
+
+    pub fn sql_type(&self) -> SqlType {
+        match self {
+            SqlClientEnum::Postgres(_) => SqlType::Postgres,
+            SqlClientEnum::Sqlite(_) => SqlType::Sqlite,
+            SqlClientEnum::MySql(_) => SqlType::MySql,
+        }
+    }
     pub async fn query(&self, sql: &str) {
         match self {
             SqlClientEnum::Postgres(client) => {
@@ -79,19 +94,29 @@ impl SqlClient for SqlClientEnum {
         }
     }
 
-    async fn insert_card(&self, table: &CardTable, card: &ServerCard) -> Result<(), SqlError> {
+    async fn insert_card(
+        &self,
+        table: &CardTable,
+        card: &ServerCard,
+        events: &[OutboxEvent],
+    ) -> Result<(), SqlError> {
         match self {
-            SqlClientEnum::Postgres(client) => client.insert_card(table, card).await,
-            SqlClientEnum::Sqlite(client) => client.insert_card(table, card).await,
-            SqlClientEnum::MySql(client) => client.insert_card(table, card).await,
+            SqlClientEnum::Postgres(client) => client.insert_card(table, card, events).await,
+            SqlClientEnum::Sqlite(client) => client.insert_card(table, card, events).await,
+            SqlClientEnum::MySql(client) => client.insert_card(table, card, events).await,
         }
     }
 
-    async fn update_card(&self, table: &CardTable, card: &ServerCard) -> Result<(), SqlError> {
+    async fn update_card(
+        &self,
+        table: &CardTable,
+        card: &ServerCard,
+        events: &[OutboxEvent],
+    ) -> Result<(), SqlError> {
         match self {
-            SqlClientEnum::Postgres(client) => client.update_card(table, card).await,
-            SqlClientEnum::Sqlite(client) => client.update_card(table, card).await,
-            SqlClientEnum::MySql(client) => client.update_card(table, card).await,
+            SqlClientEnum::Postgres(client) => client.update_card(table, card, events).await,
+            SqlClientEnum::Sqlite(client) => client.update_card(table, card, events).await,
+            SqlClientEnum::MySql(client) => client.update_card(table, card, events).await,
         }
     }
 
@@ -153,11 +178,210 @@ impl SqlClient for SqlClientEnum {
         &self,
         table: &CardTable,
         uid: &str,
+        events: &[OutboxEvent],
+    ) -> Result<(String, String), SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.delete_card(table, uid, events).await,
+            SqlClientEnum::Sqlite(client) => client.delete_card(table, uid, events).await,
+            SqlClientEnum::MySql(client) => client.delete_card(table, uid, events).await,
+        }
+    }
+
+    async fn soft_delete_card(
+        &self,
+        table: &CardTable,
+        uid: &str,
+        username: &str,
+        events: &[OutboxEvent],
+    ) -> Result<(String, String), SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => {
+                client.soft_delete_card(table, uid, username, events).await
+            }
+            SqlClientEnum::Sqlite(client) => {
+                client.soft_delete_card(table, uid, username, events).await
+            }
+            SqlClientEnum::MySql(client) => {
+                client.soft_delete_card(table, uid, username, events).await
+            }
+        }
+    }
+
+    async fn restore_card(
+        &self,
+        table: &CardTable,
+        uid: &str,
+        events: &[OutboxEvent],
     ) -> Result<(String, String), SqlError> {
         match self {
-            SqlClientEnum::Postgres(client) => client.delete_card(table, uid).await,
-            SqlClientEnum::Sqlite(client) => client.delete_card(table, uid).await,
-            SqlClientEnum::MySql(client) => client.delete_card(table, uid).await,
+            SqlClientEnum::Postgres(client) => client.restore_card(table, uid, events).await,
+            SqlClientEnum::Sqlite(client) => client.restore_card(table, uid, events).await,
+            SqlClientEnum::MySql(client) => client.restore_card(table, uid, events).await,
+        }
+    }
+
+    async fn get_deleted_cards(
+        &self,
+        table: &CardTable,
+        space: Option<&str>,
+        deleted_before: Option<DateTime<Utc>>,
+    ) -> Result<Vec<DeletedCardRecord>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => {
+                client.get_deleted_cards(table, space, deleted_before).await
+            }
+            SqlClientEnum::Sqlite(client) => {
+                client.get_deleted_cards(table, space, deleted_before).await
+            }
+            SqlClientEnum::MySql(client) => {
+                client.get_deleted_cards(table, space, deleted_before).await
+            }
+        }
+    }
+
+    async fn update_search_readme(
+        &self,
+        registry_type: &RegistryType,
+        space: &str,
+        name: &str,
+        readme: &str,
+    ) -> Result<(), SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => {
+                client
+                    .update_search_readme(registry_type, space, name, readme)
+                    .await
+            }
+            SqlClientEnum::Sqlite(client) => {
+                client
+                    .update_search_readme(registry_type, space, name, readme)
+                    .await
+            }
+            SqlClientEnum::MySql(client) => {
+                client
+                    .update_search_readme(registry_type, space, name, readme)
+                    .await
+            }
+        }
+    }
+
+    async fn search_cards(
+        &self,
+        query: &CardSearchQuery,
+        spaces: Option<&[String]>,
+    ) -> Result<Vec<CardSearchRecord>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.search_cards(query, spaces).await,
+            SqlClientEnum::Sqlite(client) => client.search_cards(query, spaces).await,
+            SqlClientEnum::MySql(client) => client.search_cards(query, spaces).await,
+        }
+    }
+
+    async fn get_lineage_edges(
+        &self,
+        uid: &str,
+        upstream_depth: i32,
+        downstream_depth: i32,
+    ) -> Result<Vec<CardLineageEdge>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => {
+                client
+                    .get_lineage_edges(uid, upstream_depth, downstream_depth)
+                    .await
+            }
+            SqlClientEnum::Sqlite(client) => {
+                client
+                    .get_lineage_edges(uid, upstream_depth, downstream_depth)
+                    .await
+            }
+            SqlClientEnum::MySql(client) => {
+                client
+                    .get_lineage_edges(uid, upstream_depth, downstream_depth)
+                    .await
+            }
+        }
+    }
+
+    async fn get_lineage_nodes(
+        &self,
+        uid: &str,
+        upstream_depth: i32,
+        downstream_depth: i32,
+    ) -> Result<Vec<CardLineageNodeRecord>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => {
+                client
+                    .get_lineage_nodes(uid, upstream_depth, downstream_depth)
+                    .await
+            }
+            SqlClientEnum::Sqlite(client) => {
+                client
+                    .get_lineage_nodes(uid, upstream_depth, downstream_depth)
+                    .await
+            }
+            SqlClientEnum::MySql(client) => {
+                client
+                    .get_lineage_nodes(uid, upstream_depth, downstream_depth)
+                    .await
+            }
+        }
+    }
+
+    async fn get_card_stage(
+        &self,
+        table: &CardTable,
+        uid: &str,
+    ) -> Result<Option<CardStageRecord>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.get_card_stage(table, uid).await,
+            SqlClientEnum::Sqlite(client) => client.get_card_stage(table, uid).await,
+            SqlClientEnum::MySql(client) => client.get_card_stage(table, uid).await,
+        }
+    }
+
+    async fn get_stage_holders(
+        &self,
+        table: &CardTable,
+        space: &str,
+        name: &str,
+        stage: &str,
+    ) -> Result<Vec<CardStageRecord>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => {
+                client.get_stage_holders(table, space, name, stage).await
+            }
+            SqlClientEnum::Sqlite(client) => {
+                client.get_stage_holders(table, space, name, stage).await
+            }
+            SqlClientEnum::MySql(client) => {
+                client.get_stage_holders(table, space, name, stage).await
+            }
+        }
+    }
+
+    async fn update_card_stages(
+        &self,
+        table: &CardTable,
+        transition: &CardStageResponse,
+        archive_existing: bool,
+        events: &[OutboxEvent],
+    ) -> Result<(), SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => {
+                client
+                    .update_card_stages(table, transition, archive_existing, events)
+                    .await
+            }
+            SqlClientEnum::Sqlite(client) => {
+                client
+                    .update_card_stages(table, transition, archive_existing, events)
+                    .await
+            }
+            SqlClientEnum::MySql(client) => {
+                client
+                    .update_card_stages(table, transition, archive_existing, events)
+                    .await
+            }
         }
     }
 
@@ -235,6 +459,17 @@ impl SqlClient for SqlClientEnum {
         }
     }
 
+    async fn get_experiment_metric_page(
+        &self,
+        query: &MetricQuery,
+    ) -> Result<Vec<MetricRecord>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.get_experiment_metric_page(query).await,
+            SqlClientEnum::Sqlite(client) => client.get_experiment_metric_page(query).await,
+            SqlClientEnum::MySql(client) => client.get_experiment_metric_page(query).await,
+        }
+    }
+
     async fn get_experiment_metric_names(&self, uid: &str) -> Result<Vec<String>, SqlError> {
         match self {
             SqlClientEnum::Postgres(client) => client.get_experiment_metric_names(uid).await,
@@ -337,6 +572,365 @@ impl SqlClient for SqlClientEnum {
         }
     }
 
+    async fn insert_api_token(&self, token: &ApiToken) -> Result<(), SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.insert_api_token(token).await,
+            SqlClientEnum::Sqlite(client) => client.insert_api_token(token).await,
+            SqlClientEnum::MySql(client) => client.insert_api_token(token).await,
+        }
+    }
+
+    async fn get_api_token(&self, hashed_token: &str) -> Result<Option<ApiToken>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.get_api_token(hashed_token).await,
+            SqlClientEnum::Sqlite(client) => client.get_api_token(hashed_token).await,
+            SqlClientEnum::MySql(client) => client.get_api_token(hashed_token).await,
+        }
+    }
+
+    async fn get_api_tokens(&self, username: &str) -> Result<Vec<ApiToken>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.get_api_tokens(username).await,
+            SqlClientEnum::Sqlite(client) => client.get_api_tokens(username).await,
+            SqlClientEnum::MySql(client) => client.get_api_tokens(username).await,
+        }
+    }
+
+    async fn revoke_api_token(&self, username: &str, name: &str) -> Result<bool, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.revoke_api_token(username, name).await,
+            SqlClientEnum::Sqlite(client) => client.revoke_api_token(username, name).await,
+            SqlClientEnum::MySql(client) => client.revoke_api_token(username, name).await,
+        }
+    }
+
+    async fn insert_webhook(&self, webhook: &Webhook) -> Result<(), SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.insert_webhook(webhook).await,
+            SqlClientEnum::Sqlite(client) => client.insert_webhook(webhook).await,
+            SqlClientEnum::MySql(client) => client.insert_webhook(webhook).await,
+        }
+    }
+
+    async fn get_webhook(&self, uid: &str) -> Result<Option<Webhook>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.get_webhook(uid).await,
+            SqlClientEnum::Sqlite(client) => client.get_webhook(uid).await,
+            SqlClientEnum::MySql(client) => client.get_webhook(uid).await,
+        }
+    }
+
+    async fn get_webhooks(&self) -> Result<Vec<Webhook>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.get_webhooks().await,
+            SqlClientEnum::Sqlite(client) => client.get_webhooks().await,
+            SqlClientEnum::MySql(client) => client.get_webhooks().await,
+        }
+    }
+
+    async fn get_active_webhooks(&self, space: &str) -> Result<Vec<Webhook>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.get_active_webhooks(space).await,
+            SqlClientEnum::Sqlite(client) => client.get_active_webhooks(space).await,
+            SqlClientEnum::MySql(client) => client.get_active_webhooks(space).await,
+        }
+    }
+
+    async fn delete_webhook(&self, uid: &str) -> Result<bool, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.delete_webhook(uid).await,
+            SqlClientEnum::Sqlite(client) => client.delete_webhook(uid).await,
+            SqlClientEnum::MySql(client) => client.delete_webhook(uid).await,
+        }
+    }
+
+    async fn update_webhook_secrets(&self, webhooks: &[Webhook]) -> Result<(), SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.update_webhook_secrets(webhooks).await,
+            SqlClientEnum::Sqlite(client) => client.update_webhook_secrets(webhooks).await,
+            SqlClientEnum::MySql(client) => client.update_webhook_secrets(webhooks).await,
+        }
+    }
+
+    async fn insert_webhook_delivery(&self, delivery: &WebhookDelivery) -> Result<(), SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.insert_webhook_delivery(delivery).await,
+            SqlClientEnum::Sqlite(client) => client.insert_webhook_delivery(delivery).await,
+            SqlClientEnum::MySql(client) => client.insert_webhook_delivery(delivery).await,
+        }
+    }
+
+    async fn update_webhook_delivery(&self, delivery: &WebhookDelivery) -> Result<(), SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.update_webhook_delivery(delivery).await,
+            SqlClientEnum::Sqlite(client) => client.update_webhook_delivery(delivery).await,
+            SqlClientEnum::MySql(client) => client.update_webhook_delivery(delivery).await,
+        }
+    }
+
+    async fn get_webhook_delivery(&self, uid: &str) -> Result<Option<WebhookDelivery>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.get_webhook_delivery(uid).await,
+            SqlClientEnum::Sqlite(client) => client.get_webhook_delivery(uid).await,
+            SqlClientEnum::MySql(client) => client.get_webhook_delivery(uid).await,
+        }
+    }
+
+    async fn get_webhook_deliveries(
+        &self,
+        webhook_uid: &str,
+        limit: i32,
+    ) -> Result<Vec<WebhookDelivery>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => {
+                client.get_webhook_deliveries(webhook_uid, limit).await
+            }
+            SqlClientEnum::Sqlite(client) => {
+                client.get_webhook_deliveries(webhook_uid, limit).await
+            }
+            SqlClientEnum::MySql(client) => client.get_webhook_deliveries(webhook_uid, limit).await,
+        }
+    }
+
+    async fn claim_stale_webhook_deliveries(
+        &self,
+        stale_before: DateTime<Utc>,
+        limit: i32,
+    ) -> Result<Vec<WebhookDelivery>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => {
+                client
+                    .claim_stale_webhook_deliveries(stale_before, limit)
+                    .await
+            }
+            SqlClientEnum::Sqlite(client) => {
+                client
+                    .claim_stale_webhook_deliveries(stale_before, limit)
+                    .await
+            }
+            SqlClientEnum::MySql(client) => {
+                client
+                    .claim_stale_webhook_deliveries(stale_before, limit)
+                    .await
+            }
+        }
+    }
+
+    async fn set_version_alias(
+        &self,
+        alias: &VersionAliasRecord,
+    ) -> Result<Option<VersionAliasRecord>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.set_version_alias(alias).await,
+            SqlClientEnum::Sqlite(client) => client.set_version_alias(alias).await,
+            SqlClientEnum::MySql(client) => client.set_version_alias(alias).await,
+        }
+    }
+
+    async fn get_version_alias(
+        &self,
+        registry_type: &str,
+        space: &str,
+        name: &str,
+        alias: &str,
+    ) -> Result<Option<VersionAliasRecord>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => {
+                client
+                    .get_version_alias(registry_type, space, name, alias)
+                    .await
+            }
+            SqlClientEnum::Sqlite(client) => {
+                client
+                    .get_version_alias(registry_type, space, name, alias)
+                    .await
+            }
+            SqlClientEnum::MySql(client) => {
+                client
+                    .get_version_alias(registry_type, space, name, alias)
+                    .await
+            }
+        }
+    }
+
+    async fn get_version_aliases(
+        &self,
+        registry_type: &str,
+        space: &str,
+        name: &str,
+    ) -> Result<Vec<VersionAliasRecord>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => {
+                client.get_version_aliases(registry_type, space, name).await
+            }
+            SqlClientEnum::Sqlite(client) => {
+                client.get_version_aliases(registry_type, space, name).await
+            }
+            SqlClientEnum::MySql(client) => {
+                client.get_version_aliases(registry_type, space, name).await
+            }
+        }
+    }
+
+    async fn delete_version_alias(
+        &self,
+        registry_type: &str,
+        space: &str,
+        name: &str,
+        alias: &str,
+        username: &str,
+    ) -> Result<bool, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => {
+                client
+                    .delete_version_alias(registry_type, space, name, alias, username)
+                    .await
+            }
+            SqlClientEnum::Sqlite(client) => {
+                client
+                    .delete_version_alias(registry_type, space, name, alias, username)
+                    .await
+            }
+            SqlClientEnum::MySql(client) => {
+                client
+                    .delete_version_alias(registry_type, space, name, alias, username)
+                    .await
+            }
+        }
+    }
+
+    async fn get_version_alias_history(
+        &self,
+        registry_type: &str,
+        space: &str,
+        name: &str,
+        alias: Option<&str>,
+        limit: i32,
+    ) -> Result<Vec<VersionAliasHistoryRecord>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => {
+                client
+                    .get_version_alias_history(registry_type, space, name, alias, limit)
+                    .await
+            }
+            SqlClientEnum::Sqlite(client) => {
+                client
+                    .get_version_alias_history(registry_type, space, name, alias, limit)
+                    .await
+            }
+            SqlClientEnum::MySql(client) => {
+                client
+                    .get_version_alias_history(registry_type, space, name, alias, limit)
+                    .await
+            }
+        }
+    }
+
+    async fn insert_signing_key(&self, key: &SigningKeyRecord) -> Result<(), SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.insert_signing_key(key).await,
+            SqlClientEnum::Sqlite(client) => client.insert_signing_key(key).await,
+            SqlClientEnum::MySql(client) => client.insert_signing_key(key).await,
+        }
+    }
+
+    async fn get_signing_keys(
+        &self,
+        space: Option<&str>,
+    ) -> Result<Vec<SigningKeyRecord>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.get_signing_keys(space).await,
+            SqlClientEnum::Sqlite(client) => client.get_signing_keys(space).await,
+            SqlClientEnum::MySql(client) => client.get_signing_keys(space).await,
+        }
+    }
+
+    async fn revoke_signing_key(&self, key_id: &str) -> Result<bool, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.revoke_signing_key(key_id).await,
+            SqlClientEnum::Sqlite(client) => client.revoke_signing_key(key_id).await,
+            SqlClientEnum::MySql(client) => client.revoke_signing_key(key_id).await,
+        }
+    }
+
+    async fn insert_outbox_events(&self, events: &[OutboxEvent]) -> Result<(), SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.insert_outbox_events(events).await,
+            SqlClientEnum::Sqlite(client) => client.insert_outbox_events(events).await,
+            SqlClientEnum::MySql(client) => client.insert_outbox_events(events).await,
+        }
+    }
+
+    async fn get_outbox_events(
+        &self,
+        after_id: i64,
+        limit: i32,
+    ) -> Result<Vec<OutboxEvent>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.get_outbox_events(after_id, limit).await,
+            SqlClientEnum::Sqlite(client) => client.get_outbox_events(after_id, limit).await,
+            SqlClientEnum::MySql(client) => client.get_outbox_events(after_id, limit).await,
+        }
+    }
+
+    async fn claim_outbox_events(
+        &self,
+        claimer: &str,
+        claimed_until: DateTime<Utc>,
+        limit: i32,
+    ) -> Result<Vec<OutboxEvent>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => {
+                client
+                    .claim_outbox_events(claimer, claimed_until, limit)
+                    .await
+            }
+            SqlClientEnum::Sqlite(client) => {
+                client
+                    .claim_outbox_events(claimer, claimed_until, limit)
+                    .await
+            }
+            SqlClientEnum::MySql(client) => {
+                client
+                    .claim_outbox_events(claimer, claimed_until, limit)
+                    .await
+            }
+        }
+    }
+
+    async fn release_outbox_events(&self, claimer: &str) -> Result<(), SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.release_outbox_events(claimer).await,
+            SqlClientEnum::Sqlite(client) => client.release_outbox_events(claimer).await,
+            SqlClientEnum::MySql(client) => client.release_outbox_events(claimer).await,
+        }
+    }
+
+    async fn fail_outbox_event(
+        &self,
+        id: i64,
+        error: &str,
+        max_attempts: i32,
+    ) -> Result<(), SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => {
+                client.fail_outbox_event(id, error, max_attempts).await
+            }
+            SqlClientEnum::Sqlite(client) => {
+                client.fail_outbox_event(id, error, max_attempts).await
+            }
+            SqlClientEnum::MySql(client) => client.fail_outbox_event(id, error, max_attempts).await,
+        }
+    }
+
+    async fn delete_outbox_event(&self, id: i64) -> Result<(), SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.delete_outbox_event(id).await,
+            SqlClientEnum::Sqlite(client) => client.delete_outbox_event(id).await,
+            SqlClientEnum::MySql(client) => client.delete_outbox_event(id).await,
+        }
+    }
+
     async fn insert_artifact_key(&self, key: &ArtifactKey) -> Result<(), SqlError> {
         debug!("Inserting artifact key");
         match self {
@@ -382,6 +976,39 @@ impl SqlClient for SqlClientEnum {
         }
     }
 
+    async fn get_artifact_key_page(
+        &self,
+        after_uid: &str,
+        after_registry_type: &str,
+        limit: i64,
+    ) -> Result<Vec<ArtifactKey>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => {
+                client
+                    .get_artifact_key_page(after_uid, after_registry_type, limit)
+                    .await
+            }
+            SqlClientEnum::Sqlite(client) => {
+                client
+                    .get_artifact_key_page(after_uid, after_registry_type, limit)
+                    .await
+            }
+            SqlClientEnum::MySql(client) => {
+                client
+                    .get_artifact_key_page(after_uid, after_registry_type, limit)
+                    .await
+            }
+        }
+    }
+
+    async fn rewrap_artifact_keys(&self, keys: &[ArtifactKey]) -> Result<(), SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.rewrap_artifact_keys(keys).await,
+            SqlClientEnum::Sqlite(client) => client.rewrap_artifact_keys(keys).await,
+            SqlClientEnum::MySql(client) => client.rewrap_artifact_keys(keys).await,
+        }
+    }
+
     async fn update_artifact_key(&self, key: &ArtifactKey) -> Result<(), SqlError> {
         match self {
             SqlClientEnum::Postgres(client) => client.update_artifact_key(key).await,
@@ -398,6 +1025,63 @@ impl SqlClient for SqlClientEnum {
         }
     }
 
+    async fn get_audit_chain_head(&self) -> Result<Option<AuditChainHeadRecord>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.get_audit_chain_head().await,
+            SqlClientEnum::Sqlite(client) => client.get_audit_chain_head().await,
+            SqlClientEnum::MySql(client) => client.get_audit_chain_head().await,
+        }
+    }
+
+    async fn get_audit_event_chain(
+        &self,
+        after_id: i32,
+        limit: i32,
+    ) -> Result<Vec<AuditEventRecord>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.get_audit_event_chain(after_id, limit).await,
+            SqlClientEnum::Sqlite(client) => client.get_audit_event_chain(after_id, limit).await,
+            SqlClientEnum::MySql(client) => client.get_audit_event_chain(after_id, limit).await,
+        }
+    }
+
+    async fn get_audit_events_by_time(
+        &self,
+        start_time: &DateTime<Utc>,
+        end_time: &DateTime<Utc>,
+        after_id: i32,
+        limit: i32,
+    ) -> Result<Vec<AuditEventRecord>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => {
+                client
+                    .get_audit_events_by_time(start_time, end_time, after_id, limit)
+                    .await
+            }
+            SqlClientEnum::Sqlite(client) => {
+                client
+                    .get_audit_events_by_time(start_time, end_time, after_id, limit)
+                    .await
+            }
+            SqlClientEnum::MySql(client) => {
+                client
+                    .get_audit_events_by_time(start_time, end_time, after_id, limit)
+                    .await
+            }
+        }
+    }
+
+    async fn get_audit_events(
+        &self,
+        args: &AuditEventQueryArgs,
+    ) -> Result<Vec<AuditEventRecord>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.get_audit_events(args).await,
+            SqlClientEnum::Sqlite(client) => client.get_audit_events(args).await,
+            SqlClientEnum::MySql(client) => client.get_audit_events(args).await,
+        }
+    }
+
     async fn get_card_key_for_loading(
         &self,
         table: &CardTable,
@@ -424,11 +1108,15 @@ impl SqlClient for SqlClientEnum {
         }
     }
 
-    async fn insert_space_record(&self, record: &SpaceRecord) -> Result<(), SqlError> {
+    async fn insert_space_record(
+        &self,
+        record: &SpaceRecord,
+        events: &[OutboxEvent],
+    ) -> Result<(), SqlError> {
         match self {
-            SqlClientEnum::Postgres(client) => client.insert_space_record(record).await,
-            SqlClientEnum::Sqlite(client) => client.insert_space_record(record).await,
-            SqlClientEnum::MySql(client) => client.insert_space_record(record).await,
+            SqlClientEnum::Postgres(client) => client.insert_space_record(record, events).await,
+            SqlClientEnum::Sqlite(client) => client.insert_space_record(record, events).await,
+            SqlClientEnum::MySql(client) => client.insert_space_record(record, events).await,
         }
     }
 
@@ -456,19 +1144,27 @@ impl SqlClient for SqlClientEnum {
         }
     }
 
-    async fn update_space_record(&self, record: &SpaceRecord) -> Result<(), SqlError> {
+    async fn update_space_record(
+        &self,
+        record: &SpaceRecord,
+        events: &[OutboxEvent],
+    ) -> Result<(), SqlError> {
         match self {
-            SqlClientEnum::Postgres(client) => client.update_space_record(record).await,
-            SqlClientEnum::Sqlite(client) => client.update_space_record(record).await,
-            SqlClientEnum::MySql(client) => client.update_space_record(record).await,
+            SqlClientEnum::Postgres(client) => client.update_space_record(record, events).await,
+            SqlClientEnum::Sqlite(client) => client.update_space_record(record, events).await,
+            SqlClientEnum::MySql(client) => client.update_space_record(record, events).await,
         }
     }
 
-    async fn delete_space_record(&self, space: &str) -> Result<(), SqlError> {
+    async fn delete_space_record(
+        &self,
+        space: &str,
+        events: &[OutboxEvent],
+    ) -> Result<(), SqlError> {
         match self {
-            SqlClientEnum::Postgres(client) => client.delete_space_record(space).await,
-            SqlClientEnum::Sqlite(client) => client.delete_space_record(space).await,
-            SqlClientEnum::MySql(client) => client.delete_space_record(space).await,
+            SqlClientEnum::Postgres(client) => client.delete_space_record(space, events).await,
+            SqlClientEnum::Sqlite(client) => client.delete_space_record(space, events).await,
+            SqlClientEnum::MySql(client) => client.delete_space_record(space, events).await,
         }
     }
 
@@ -745,7 +1441,10 @@ mod tests {
         let data_card = DataCardRecord::default();
         let card = ServerCard::Data(data_card.clone());
 
-        client.insert_card(&CardTable::Data, &card).await.unwrap();
+        client
+            .insert_card(&CardTable::Data, &card, &[])
+            .await
+            .unwrap();
 
         // check if the card was inserted
         let card_args = CardQueryArgs {
@@ -763,7 +1462,10 @@ mod tests {
         let model_card = ModelCardRecord::default();
         let card = ServerCard::Model(model_card.clone());
 
-        client.insert_card(&CardTable::Model, &card).await.unwrap();
+        client
+            .insert_card(&CardTable::Model, &card, &[])
+            .await
+            .unwrap();
 
         // check if the card was inserted
         let card_args = CardQueryArgs {
@@ -783,7 +1485,7 @@ mod tests {
         let card = ServerCard::Experiment(run_card.clone());
 
         client
-            .insert_card(&CardTable::Experiment, &card)
+            .insert_card(&CardTable::Experiment, &card, &[])
             .await
             .unwrap();
 
@@ -806,7 +1508,10 @@ mod tests {
         let audit_card = AuditCardRecord::default();
         let card = ServerCard::Audit(audit_card.clone());
 
-        client.insert_card(&CardTable::Audit, &card).await.unwrap();
+        client
+            .insert_card(&CardTable::Audit, &card, &[])
+            .await
+            .unwrap();
 
         // check if the card was inserted
 
@@ -833,7 +1538,10 @@ mod tests {
         let mut data_card = DataCardRecord::default();
         let card = ServerCard::Data(data_card.clone());
 
-        client.insert_card(&CardTable::Data, &card).await.unwrap();
+        client
+            .insert_card(&CardTable::Data, &card, &[])
+            .await
+            .unwrap();
 
         // check if the card was inserted
         let card_args = CardQueryArgs {
@@ -852,7 +1560,7 @@ mod tests {
         let updated_card = ServerCard::Data(data_card.clone());
 
         client
-            .update_card(&CardTable::Data, &updated_card)
+            .update_card(&CardTable::Data, &updated_card, &[])
             .await
             .unwrap();
 
@@ -871,7 +1579,10 @@ mod tests {
         let mut model_card = ModelCardRecord::default();
         let card = ServerCard::Model(model_card.clone());
 
-        client.insert_card(&CardTable::Model, &card).await.unwrap();
+        client
+            .insert_card(&CardTable::Model, &card, &[])
+            .await
+            .unwrap();
 
         // check if the card was inserted
         let card_args = CardQueryArgs {
@@ -890,7 +1601,7 @@ mod tests {
         let updated_card = ServerCard::Model(model_card.clone());
 
         client
-            .update_card(&CardTable::Model, &updated_card)
+            .update_card(&CardTable::Model, &updated_card, &[])
             .await
             .unwrap();
 
@@ -910,7 +1621,7 @@ mod tests {
         let card = ServerCard::Experiment(run_card.clone());
 
         client
-            .insert_card(&CardTable::Experiment, &card)
+            .insert_card(&CardTable::Experiment, &card, &[])
             .await
             .unwrap();
 
@@ -931,7 +1642,7 @@ mod tests {
         let updated_card = ServerCard::Experiment(run_card.clone());
 
         client
-            .update_card(&CardTable::Experiment, &updated_card)
+            .update_card(&CardTable::Experiment, &updated_card, &[])
             .await
             .unwrap();
 
@@ -950,7 +1661,10 @@ mod tests {
         let mut audit_card = AuditCardRecord::default();
         let card = ServerCard::Audit(audit_card.clone());
 
-        client.insert_card(&CardTable::Audit, &card).await.unwrap();
+        client
+            .insert_card(&CardTable::Audit, &card, &[])
+            .await
+            .unwrap();
 
         // check if the card was inserted
         let card_args = CardQueryArgs {
@@ -969,7 +1683,7 @@ mod tests {
         let updated_card = ServerCard::Audit(audit_card.clone());
 
         client
-            .update_card(&CardTable::Audit, &updated_card)
+            .update_card(&CardTable::Audit, &updated_card, &[])
             .await
             .unwrap();
 
@@ -1087,7 +1801,10 @@ mod tests {
         assert!(!uid.is_empty());
 
         // delete the card
-        client.delete_card(&CardTable::Data, &uid).await.unwrap();
+        client
+            .delete_card(&CardTable::Data, &uid, &[])
+            .await
+            .unwrap();
 
         // check if the card was deleted
         let args = CardQueryArgs {
@@ -1259,17 +1976,26 @@ mod tests {
             description: "Space description".to_string(),
         };
 
-        client.insert_space_record(&space_record).await.unwrap();
+        client
+            .insert_space_record(&space_record, &[])
+            .await
+            .unwrap();
 
         // insert datacard
         let data_card = DataCardRecord::default();
         let card = ServerCard::Data(data_card.clone());
-        client.insert_card(&CardTable::Data, &card).await.unwrap();
+        client
+            .insert_card(&CardTable::Data, &card, &[])
+            .await
+            .unwrap();
 
         // insert modelcard
         let model_card = ModelCardRecord::default();
         let card = ServerCard::Model(model_card.clone());
-        client.insert_card(&CardTable::Model, &card).await.unwrap();
+        client
+            .insert_card(&CardTable::Model, &card, &[])
+            .await
+            .unwrap();
 
         let space_event = SpaceNameEvent {
             space: data_card.space.clone(),
@@ -1298,7 +2024,10 @@ mod tests {
             ..Default::default()
         };
         let card = ServerCard::Model(model_card2.clone());
-        client.insert_card(&CardTable::Model, &card).await.unwrap();
+        client
+            .insert_card(&CardTable::Model, &card, &[])
+            .await
+            .unwrap();
 
         // update space stats again
         let space_event = SpaceNameEvent {
@@ -1321,7 +2050,7 @@ mod tests {
             description: "Updated Space description".to_string(),
         };
         client
-            .update_space_record(&updated_space_record)
+            .update_space_record(&updated_space_record, &[])
             .await
             .unwrap();
 
@@ -1336,7 +2065,7 @@ mod tests {
 
         // delete
         client
-            .delete_space_record(&model_card2.space)
+            .delete_space_record(&model_card2.space, &[])
             .await
             .unwrap();
 
@@ -1,3 +1,4 @@
+use opsml_crypt::error::CryptError;
 use opsml_semver::error::VersionError;
 use opsml_types::error::TypeError;
 use opsml_utils::error::UtilError;
@@ -17,6 +18,9 @@ pub enum SqlError {
     #[error(transparent)]
     TypeError(#[from] TypeError),
 
+    #[error(transparent)]
+    CryptError(#[from] CryptError),
+
     #[error(transparent)]
     SemverError(#[from] VersionError),
 
@@ -32,6 +36,45 @@ pub enum SqlError {
     #[error("Invalid card type")]
     InvalidCardType,
 
+    #[error("Stages are only supported for model and service cards")]
+    StageNotSupported,
+
+    #[error("Stage of card {0} changed while the transition was applied")]
+    StageConflict(String),
+
+    #[error("Card not found: {0}")]
+    CardNotFound(String),
+
+    #[error("Version alias not found: {0}")]
+    VersionAliasNotFound(String),
+
+    #[error("Signing key not found: {0}")]
+    SigningKeyNotFound(String),
+
+    #[error("Signing key already registered: {0}")]
+    SigningKeyExists(String),
+
+    #[error("Space and name are required to resolve version alias: {0}")]
+    VersionAliasMissingCard(String),
+
+    #[error("Search query must contain at least one word")]
+    EmptySearchQuery,
+
+    #[error("max_points must be between 1 and {0}")]
+    InvalidMaxPoints(i64),
+
+    #[error("Card already exists: {0}")]
+    ImportConflict(String),
+
+    #[error("Invalid card uid: {0}")]
+    InvalidCardUid(String),
+
+    #[error("Between 1 and {0} experiments can be compared")]
+    InvalidExperimentComparison(usize),
+
+    #[error("Key of {0} cannot be decrypted with the old or the new encryption secret")]
+    KeyRotationError(String),
+
     #[error(transparent)]
     SerdeError(#[from] serde_json::Error),
 }
@@ -0,0 +1,48 @@
+use crate::base::SqlClient;
+use crate::error::SqlError;
+use crate::schemas::schema::{OutboxEvent, ServerCard};
+use opsml_types::cards::CardTable;
+use tracing::instrument;
+
+/// Inserts a card exported from another registry, keeping its uid and version.
+///
+/// Existing cards are never overwritten. The import is rejected when the uid, or the
+/// space/name/version of the card, is already registered (including cards in the trash).
+///
+/// # Arguments
+///
+/// * `client` - The sql client
+/// * `table` - The card table
+/// * `card` - The card to insert
+/// * `events` - Events written to the outbox in the same transaction as the card
+#[instrument(skip_all)]
+pub async fn insert_imported_card(
+    client: &impl SqlClient,
+    table: &CardTable,
+    card: &ServerCard,
+    events: &[OutboxEvent],
+) -> Result<(), SqlError> {
+    // artifact keys are wrapped with a key derived from the uid, so it must be a valid uuid
+    if uuid::Uuid::parse_str(card.uid()).is_err() {
+        return Err(SqlError::InvalidCardUid(card.uid().to_string()));
+    }
+
+    let card_name = format!("{}/{} v{}", card.space(), card.name(), card.version());
+
+    if client.check_uid_exists(card.uid(), table).await? {
+        return Err(SqlError::ImportConflict(format!(
+            "{card_name} (uid {})",
+            card.uid()
+        )));
+    }
+
+    let versions = client
+        .get_versions(table, &card.space(), &card.name(), Some(card.version()))
+        .await?;
+
+    if versions.iter().any(|version| *version == card.version()) {
+        return Err(SqlError::ImportConflict(card_name));
+    }
+
+    client.insert_card(table, card, events).await
+}
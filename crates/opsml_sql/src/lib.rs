@@ -1,7 +1,16 @@
+pub mod alias;
+pub mod audit;
 pub mod base;
+pub mod comparison;
 pub mod enums;
 pub mod error;
+pub mod import;
+pub mod lineage;
+pub mod metric;
 pub mod mysql;
 pub mod postgres;
+pub mod rotation;
 pub mod schemas;
 pub mod sqlite;
+pub mod stage;
+pub mod transfer;
@@ -0,0 +1,68 @@
+use crate::base::SqlClient;
+use crate::error::SqlError;
+use opsml_types::contracts::{CardLineage, CardLineageRequest, LineageEdge};
+use std::collections::HashSet;
+use tracing::instrument;
+
+/// Walks the lineage graph from a card across all registries.
+///
+/// Edges that point to purged cards are dropped. The requested card is always the first node.
+///
+/// # Arguments
+///
+/// * `client` - The sql client
+/// * `request` - The card to start from, the depth and the direction to walk
+///
+/// # Returns
+///
+/// * `CardLineage` - The cards reached and the edges between them
+#[instrument(skip_all)]
+pub async fn build_card_lineage(
+    client: &impl SqlClient,
+    request: &CardLineageRequest,
+) -> Result<CardLineage, SqlError> {
+    let (upstream_depth, downstream_depth) = (request.upstream_depth(), request.downstream_depth());
+
+    let mut nodes = client
+        .get_lineage_nodes(&request.uid, upstream_depth, downstream_depth)
+        .await?
+        .into_iter()
+        .map(|record| record.into_lineage_node())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if !nodes.iter().any(|node| node.uid == request.uid) {
+        return Err(SqlError::CardNotFound(request.uid.clone()));
+    }
+
+    nodes.sort_by(|a, b| {
+        (b.uid == request.uid)
+            .cmp(&(a.uid == request.uid))
+            .then_with(|| {
+                a.registry_type
+                    .to_string()
+                    .cmp(&b.registry_type.to_string())
+            })
+            .then_with(|| (&a.space, &a.name, &a.version).cmp(&(&b.space, &b.name, &b.version)))
+    });
+
+    let uids: HashSet<&str> = nodes.iter().map(|node| node.uid.as_str()).collect();
+    let mut edges: Vec<LineageEdge> = client
+        .get_lineage_edges(&request.uid, upstream_depth, downstream_depth)
+        .await?
+        .into_iter()
+        .filter(|edge| {
+            uids.contains(edge.source_uid.as_str()) && uids.contains(edge.target_uid.as_str())
+        })
+        .map(|edge| LineageEdge {
+            source_uid: edge.source_uid,
+            target_uid: edge.target_uid,
+        })
+        .collect();
+    edges.sort_by(|a, b| (&a.source_uid, &a.target_uid).cmp(&(&b.source_uid, &b.target_uid)));
+
+    Ok(CardLineage {
+        root_uid: request.uid.clone(),
+        nodes,
+        edges,
+    })
+}
@@ -0,0 +1,68 @@
+use crate::base::SqlClient;
+use crate::error::SqlError;
+use opsml_types::cards::Metric;
+use opsml_types::contracts::{MetricPage, MetricQuery, MAX_METRIC_POINTS};
+use std::collections::HashMap;
+use tracing::instrument;
+
+/// Gets a page of metrics for an experiment.
+///
+/// A full page means more metrics may follow, so the index of its last metric is returned as the
+/// cursor of the next page. Downsampled queries always return a single page with at most
+/// `max_points` points per metric.
+///
+/// # Arguments
+///
+/// * `client` - The sql client
+/// * `query` - The experiment, metric names, ranges and page to get
+///
+/// # Returns
+///
+/// * `MetricPage` - The metrics and the cursor of the next page
+#[instrument(skip_all)]
+pub async fn get_metric_page(
+    client: &impl SqlClient,
+    query: &MetricQuery,
+) -> Result<MetricPage, SqlError> {
+    if query
+        .max_points
+        .is_some_and(|max_points| !(1..=MAX_METRIC_POINTS).contains(&max_points))
+    {
+        return Err(SqlError::InvalidMaxPoints(MAX_METRIC_POINTS));
+    }
+
+    let mut records = client.get_experiment_metric_page(query).await?;
+
+    // a bucket keeps up to two points, so a single bucket can exceed a budget of one
+    if let Some(max_points) = query.max_points {
+        let mut counts: HashMap<String, i64> = HashMap::new();
+        records.retain(|record| {
+            let count = counts.entry(record.name.clone()).or_default();
+            *count += 1;
+            *count <= max_points
+        });
+    }
+
+    let next_cursor = match query.max_points {
+        None if records.len() as i64 == query.limit() => {
+            records.last().and_then(|record| record.idx).map(i64::from)
+        }
+        _ => None,
+    };
+
+    let metrics = records
+        .into_iter()
+        .map(|m| Metric {
+            name: m.name,
+            value: m.value,
+            step: m.step,
+            timestamp: m.timestamp,
+            created_at: m.created_at,
+        })
+        .collect();
+
+    Ok(MetricPage {
+        metrics,
+        next_cursor,
+    })
+}
@@ -1,7 +1,10 @@
-use crate::base::add_version_bounds;
+use crate::base::{add_stage_filter, add_version_bounds, metric_page_filters};
 
 use crate::error::SqlError;
-use opsml_types::{cards::CardTable, contracts::CardQueryArgs};
+use opsml_types::{
+    cards::CardTable,
+    contracts::{CardQueryArgs, MetricQuery},
+};
 use opsml_utils::utils::is_valid_uuidv7;
 
 // user
@@ -52,9 +55,78 @@ const UPDATE_ARTIFACT_KEY_SQL: &str = include_str!("sql/artifact/update_artifact
 const GET_ARTIFACT_KEY_FROM_STORAGE_PATH_SQL: &str =
     include_str!("sql/artifact/get_artifact_key_from_storage_path.sql");
 const DELETE_ARTIFACT_KEY_SQL: &str = include_str!("sql/artifact/delete_artifact_key.sql");
+const DELETE_CARD_ARTIFACT_KEY_SQL: &str =
+    include_str!("sql/artifact/delete_card_artifact_key.sql");
+const GET_ARTIFACT_KEY_PAGE_SQL: &str = include_str!("sql/artifact/get_artifact_key_page.sql");
+const REWRAP_ARTIFACT_KEY_SQL: &str = include_str!("sql/artifact/rewrap_artifact_key.sql");
 
 // audit events
 const INSERT_AUDIT_EVENT_SQL: &str = include_str!("sql/audit/insert_audit_event.sql");
+const GET_LAST_AUDIT_EVENT_HASH_SQL: &str = include_str!("sql/audit/get_last_audit_event_hash.sql");
+const GET_AUDIT_EVENT_CHAIN_SQL: &str = include_str!("sql/audit/get_audit_event_chain.sql");
+const GET_AUDIT_EVENTS_BY_TIME_SQL: &str = include_str!("sql/audit/get_audit_events_by_time.sql");
+const GET_AUDIT_EVENTS_SQL: &str = include_str!("sql/audit/get_audit_events.sql");
+const GET_AUDIT_CHAIN_HEAD_SQL: &str = include_str!("sql/audit/get_audit_chain_head.sql");
+const UPSERT_AUDIT_CHAIN_HEAD_SQL: &str = include_str!("sql/audit/upsert_audit_chain_head.sql");
+
+// api tokens
+const INSERT_API_TOKEN_SQL: &str = include_str!("sql/token/insert_api_token.sql");
+const GET_API_TOKEN_SQL: &str = include_str!("sql/token/get_api_token.sql");
+const GET_API_TOKENS_SQL: &str = include_str!("sql/token/get_api_tokens.sql");
+const REVOKE_API_TOKEN_SQL: &str = include_str!("sql/token/revoke_api_token.sql");
+
+// webhooks
+const INSERT_WEBHOOK_SQL: &str = include_str!("sql/webhook/insert_webhook.sql");
+const GET_WEBHOOK_SQL: &str = include_str!("sql/webhook/get_webhook.sql");
+const GET_WEBHOOKS_SQL: &str = include_str!("sql/webhook/get_webhooks.sql");
+const GET_ACTIVE_WEBHOOKS_SQL: &str = include_str!("sql/webhook/get_active_webhooks.sql");
+const DELETE_WEBHOOK_SQL: &str = include_str!("sql/webhook/delete_webhook.sql");
+const UPDATE_WEBHOOK_SECRET_SQL: &str = include_str!("sql/webhook/update_webhook_secret.sql");
+const INSERT_WEBHOOK_DELIVERY_SQL: &str = include_str!("sql/webhook/insert_webhook_delivery.sql");
+const UPDATE_WEBHOOK_DELIVERY_SQL: &str = include_str!("sql/webhook/update_webhook_delivery.sql");
+const GET_WEBHOOK_DELIVERY_SQL: &str = include_str!("sql/webhook/get_webhook_delivery.sql");
+const GET_WEBHOOK_DELIVERIES_SQL: &str = include_str!("sql/webhook/get_webhook_deliveries.sql");
+const GET_STALE_WEBHOOK_DELIVERIES_SQL: &str =
+    include_str!("sql/webhook/get_stale_webhook_deliveries.sql");
+const CLAIM_WEBHOOK_DELIVERY_SQL: &str = include_str!("sql/webhook/claim_webhook_delivery.sql");
+
+// event outbox
+const INSERT_OUTBOX_EVENT_SQL: &str = include_str!("sql/outbox/insert_outbox_event.sql");
+const GET_OUTBOX_EVENTS_SQL: &str = include_str!("sql/outbox/get_outbox_events.sql");
+const GET_CLAIMABLE_OUTBOX_EVENTS_SQL: &str =
+    include_str!("sql/outbox/get_claimable_outbox_events.sql");
+const CLAIM_OUTBOX_EVENT_SQL: &str = include_str!("sql/outbox/claim_outbox_event.sql");
+const RELEASE_OUTBOX_EVENTS_SQL: &str = include_str!("sql/outbox/release_outbox_events.sql");
+const FAIL_OUTBOX_EVENT_SQL: &str = include_str!("sql/outbox/fail_outbox_event.sql");
+const DELETE_OUTBOX_EVENT_SQL: &str = include_str!("sql/outbox/delete_outbox_event.sql");
+
+// version aliases
+const GET_VERSION_ALIAS_SQL: &str = include_str!("sql/alias/get_version_alias.sql");
+const GET_VERSION_ALIASES_SQL: &str = include_str!("sql/alias/get_version_aliases.sql");
+const UPSERT_VERSION_ALIAS_SQL: &str = include_str!("sql/alias/upsert_version_alias.sql");
+const DELETE_VERSION_ALIAS_SQL: &str = include_str!("sql/alias/delete_version_alias.sql");
+const INSERT_VERSION_ALIAS_HISTORY_SQL: &str =
+    include_str!("sql/alias/insert_version_alias_history.sql");
+const GET_VERSION_ALIAS_HISTORY_SQL: &str = include_str!("sql/alias/get_version_alias_history.sql");
+
+// card search
+const GET_SEARCH_README_SQL: &str = include_str!("sql/search/get_search_readme.sql");
+const UPSERT_CARD_SEARCH_ENTRY_SQL: &str = include_str!("sql/search/upsert_card_search_entry.sql");
+const DELETE_CARD_SEARCH_ENTRY_SQL: &str = include_str!("sql/search/delete_card_search_entry.sql");
+const UPDATE_SEARCH_README_SQL: &str = include_str!("sql/search/update_search_readme.sql");
+const UPDATE_SEARCH_DELETED_SQL: &str = include_str!("sql/search/update_search_deleted.sql");
+const SEARCH_CARDS_SQL: &str = include_str!("sql/search/search_cards.sql");
+
+// card lineage
+const INSERT_LINEAGE_EDGE_SQL: &str = include_str!("sql/lineage/insert_lineage_edge.sql");
+const DELETE_LINEAGE_EDGES_SQL: &str = include_str!("sql/lineage/delete_lineage_edges.sql");
+const GET_LINEAGE_EDGES_SQL: &str = include_str!("sql/lineage/get_lineage_edges.sql");
+const GET_LINEAGE_NODES_SQL: &str = include_str!("sql/lineage/get_lineage_nodes.sql");
+
+// signing keys
+const INSERT_SIGNING_KEY_SQL: &str = include_str!("sql/signing/insert_signing_key.sql");
+const GET_SIGNING_KEYS_SQL: &str = include_str!("sql/signing/get_signing_keys.sql");
+const REVOKE_SIGNING_KEY_SQL: &str = include_str!("sql/signing/revoke_signing_key.sql");
 
 pub struct MySQLQueryHelper;
 
@@ -106,13 +178,14 @@ impl MySQLQueryHelper {
                 name, 
                 value,
                 step,
-                timestamp
+                timestamp,
+                created_at
             ) VALUES ",
             CardTable::Metrics
         );
 
         for i in 0..nbr_records {
-            query.push_str("(?, ?, ?, ?, ?) ");
+            query.push_str("(?, ?, ?, ?, ?, COALESCE(?, CURRENT_TIMESTAMP)) ");
 
             // add comma if not last record
             if i < nbr_records - 1 {
@@ -148,6 +221,43 @@ impl MySQLQueryHelper {
         (query, bindings)
     }
 
+    /// Builds the metric page query. Parameters are bound in order: experiment uid, names,
+    /// start step, end step, start timestamp, end timestamp and cursor when set, followed by
+    /// the number of buckets and max points when downsampling, or the page size otherwise
+    pub fn get_experiment_metric_page_query(query: &MetricQuery) -> String {
+        let (mut filtered, _) = Self::get_experiment_metric_query(&query.names);
+
+        for (filter, _) in metric_page_filters(query) {
+            filtered.push_str(&format!(" AND {filter} ?"));
+        }
+
+        if query.max_points.is_none() {
+            return format!("{filtered} ORDER BY idx LIMIT ?");
+        }
+
+        // the bucket count is bound twice, once per ranking
+        format!(
+            "WITH filtered AS (
+                SELECT
+                    experiment_uid, name, value, step, timestamp, created_at, idx,
+                    ROW_NUMBER() OVER (PARTITION BY name ORDER BY idx) - 1 AS pos,
+                    COUNT(*) OVER (PARTITION BY name) AS total
+                FROM ({filtered}) AS metrics
+            ),
+            ranked AS (
+                SELECT
+                    *,
+                    ROW_NUMBER() OVER (PARTITION BY name, pos * ? DIV total ORDER BY value ASC, idx) AS min_rank,
+                    ROW_NUMBER() OVER (PARTITION BY name, pos * ? DIV total ORDER BY value DESC, idx) AS max_rank
+                FROM filtered
+            )
+            SELECT experiment_uid, name, value, step, timestamp, created_at, idx
+            FROM ranked
+            WHERE total <= ? OR min_rank = 1 OR max_rank = 1
+            ORDER BY idx"
+        )
+    }
+
     pub fn get_query_page_query(table: &CardTable, sort_by: &str) -> String {
         let versions_cte = format!(
             "WITH versions AS (
@@ -158,6 +268,7 @@ impl MySQLQueryHelper {
                     ROW_NUMBER() OVER (PARTITION BY space, name ORDER BY created_at DESC) AS row_num
                 FROM {table}
                 WHERE 1=1
+                AND deleted_at IS NULL
                 AND (? IS NULL OR space = ?)
                 AND (? IS NULL OR name LIKE ? OR space LIKE ?)
             )"
@@ -173,6 +284,7 @@ impl MySQLQueryHelper {
                     MIN(created_at) AS created_at 
                 FROM {table}
                 WHERE 1=1
+                AND deleted_at IS NULL
                 AND (? IS NULL OR space = ?)
                 AND (? IS NULL OR name LIKE ? OR space LIKE ?)
                 GROUP BY space, name
@@ -234,7 +346,8 @@ impl MySQLQueryHelper {
                     created_at,
                     ROW_NUMBER() OVER (PARTITION BY space, name ORDER BY created_at DESC, major DESC, minor DESC, patch DESC) AS row_num
                 FROM {table}
-                WHERE space = ?
+                WHERE deleted_at IS NULL
+                AND space = ?
                 AND name = ?
             )"
         );
@@ -263,6 +376,7 @@ impl MySQLQueryHelper {
                     COALESCE(COUNT(DISTINCT space), 0) AS nbr_spaces
                 FROM {table}
                 WHERE 1=1
+                AND deleted_at IS NULL
                 AND (? IS NULL OR name LIKE ? OR space LIKE ?)
                 AND (? IS NULL OR space = ?)
                 "
@@ -309,6 +423,7 @@ impl MySQLQueryHelper {
             "
         SELECT * FROM {table}
         WHERE 1=1
+        AND deleted_at IS NULL
         AND (? IS NULL OR uid = ?)
         AND (? IS NULL OR name = ?)
         AND (? IS NULL OR space = ?)
@@ -327,6 +442,10 @@ impl MySQLQueryHelper {
                 add_version_bounds(&mut query, query_args.version.as_ref().unwrap())?;
             }
 
+            if let Some(stage) = &query_args.stage {
+                add_stage_filter(&mut query, table, stage)?;
+            }
+
             if query_args.tags.is_some() {
                 let tags = query_args.tags.as_ref().unwrap();
                 for tag in tags.iter() {
@@ -464,10 +583,210 @@ impl MySQLQueryHelper {
         GET_ARTIFACT_KEY_FROM_STORAGE_PATH_SQL.to_string()
     }
 
+    pub fn get_artifact_key_page_query() -> String {
+        GET_ARTIFACT_KEY_PAGE_SQL.to_string()
+    }
+
+    pub fn get_artifact_key_rewrap_query() -> String {
+        REWRAP_ARTIFACT_KEY_SQL.to_string()
+    }
+
     pub fn get_audit_event_insert_query() -> String {
         INSERT_AUDIT_EVENT_SQL.to_string()
     }
 
+    pub fn get_last_audit_event_hash_query() -> String {
+        GET_LAST_AUDIT_EVENT_HASH_SQL.to_string()
+    }
+
+    pub fn get_audit_chain_head_query() -> String {
+        GET_AUDIT_CHAIN_HEAD_SQL.to_string()
+    }
+
+    pub fn get_audit_chain_head_upsert_query() -> String {
+        UPSERT_AUDIT_CHAIN_HEAD_SQL.to_string()
+    }
+
+    pub fn get_audit_event_chain_query() -> String {
+        GET_AUDIT_EVENT_CHAIN_SQL.to_string()
+    }
+
+    pub fn get_audit_events_by_time_query() -> String {
+        GET_AUDIT_EVENTS_BY_TIME_SQL.to_string()
+    }
+
+    pub fn get_audit_events_query() -> String {
+        GET_AUDIT_EVENTS_SQL.to_string()
+    }
+
+    pub fn get_api_token_insert_query() -> String {
+        INSERT_API_TOKEN_SQL.to_string()
+    }
+
+    pub fn get_api_token_query() -> String {
+        GET_API_TOKEN_SQL.to_string()
+    }
+
+    pub fn get_api_tokens_query() -> String {
+        GET_API_TOKENS_SQL.to_string()
+    }
+
+    pub fn get_api_token_revoke_query() -> String {
+        REVOKE_API_TOKEN_SQL.to_string()
+    }
+
+    pub fn get_webhook_insert_query() -> String {
+        INSERT_WEBHOOK_SQL.to_string()
+    }
+
+    pub fn get_webhook_query() -> String {
+        GET_WEBHOOK_SQL.to_string()
+    }
+
+    pub fn get_webhooks_query() -> String {
+        GET_WEBHOOKS_SQL.to_string()
+    }
+
+    pub fn get_active_webhooks_query() -> String {
+        GET_ACTIVE_WEBHOOKS_SQL.to_string()
+    }
+
+    pub fn get_webhook_delete_query() -> String {
+        DELETE_WEBHOOK_SQL.to_string()
+    }
+
+    pub fn get_webhook_secret_update_query() -> String {
+        UPDATE_WEBHOOK_SECRET_SQL.to_string()
+    }
+
+    pub fn get_webhook_delivery_insert_query() -> String {
+        INSERT_WEBHOOK_DELIVERY_SQL.to_string()
+    }
+
+    pub fn get_webhook_delivery_update_query() -> String {
+        UPDATE_WEBHOOK_DELIVERY_SQL.to_string()
+    }
+
+    pub fn get_webhook_delivery_query() -> String {
+        GET_WEBHOOK_DELIVERY_SQL.to_string()
+    }
+
+    pub fn get_webhook_deliveries_query() -> String {
+        GET_WEBHOOK_DELIVERIES_SQL.to_string()
+    }
+
+    pub fn get_stale_webhook_deliveries_query() -> String {
+        GET_STALE_WEBHOOK_DELIVERIES_SQL.to_string()
+    }
+
+    pub fn get_webhook_delivery_claim_query() -> String {
+        CLAIM_WEBHOOK_DELIVERY_SQL.to_string()
+    }
+
+    pub fn get_outbox_event_insert_query() -> String {
+        INSERT_OUTBOX_EVENT_SQL.to_string()
+    }
+
+    pub fn get_outbox_events_query() -> String {
+        GET_OUTBOX_EVENTS_SQL.to_string()
+    }
+
+    pub fn get_claimable_outbox_events_query() -> String {
+        GET_CLAIMABLE_OUTBOX_EVENTS_SQL.to_string()
+    }
+
+    pub fn get_outbox_event_claim_query() -> String {
+        CLAIM_OUTBOX_EVENT_SQL.to_string()
+    }
+
+    pub fn get_outbox_events_release_query() -> String {
+        RELEASE_OUTBOX_EVENTS_SQL.to_string()
+    }
+
+    pub fn get_outbox_event_fail_query() -> String {
+        FAIL_OUTBOX_EVENT_SQL.to_string()
+    }
+
+    pub fn get_outbox_event_delete_query() -> String {
+        DELETE_OUTBOX_EVENT_SQL.to_string()
+    }
+
+    pub fn get_version_alias_query() -> String {
+        GET_VERSION_ALIAS_SQL.to_string()
+    }
+
+    pub fn get_version_aliases_query() -> String {
+        GET_VERSION_ALIASES_SQL.to_string()
+    }
+
+    pub fn get_version_alias_upsert_query() -> String {
+        UPSERT_VERSION_ALIAS_SQL.to_string()
+    }
+
+    pub fn get_version_alias_delete_query() -> String {
+        DELETE_VERSION_ALIAS_SQL.to_string()
+    }
+
+    pub fn get_version_alias_history_insert_query() -> String {
+        INSERT_VERSION_ALIAS_HISTORY_SQL.to_string()
+    }
+
+    pub fn get_version_alias_history_query() -> String {
+        GET_VERSION_ALIAS_HISTORY_SQL.to_string()
+    }
+
+    pub fn get_search_readme_query() -> String {
+        GET_SEARCH_README_SQL.to_string()
+    }
+
+    pub fn get_card_search_entry_upsert_query() -> String {
+        UPSERT_CARD_SEARCH_ENTRY_SQL.to_string()
+    }
+
+    pub fn get_card_search_entry_delete_query() -> String {
+        DELETE_CARD_SEARCH_ENTRY_SQL.to_string()
+    }
+
+    pub fn get_search_readme_update_query() -> String {
+        UPDATE_SEARCH_README_SQL.to_string()
+    }
+
+    pub fn get_search_deleted_update_query() -> String {
+        UPDATE_SEARCH_DELETED_SQL.to_string()
+    }
+
+    pub fn get_search_cards_query() -> String {
+        SEARCH_CARDS_SQL.to_string()
+    }
+
+    pub fn get_lineage_edge_insert_query() -> String {
+        INSERT_LINEAGE_EDGE_SQL.to_string()
+    }
+
+    pub fn get_lineage_edges_delete_query() -> String {
+        DELETE_LINEAGE_EDGES_SQL.to_string()
+    }
+
+    pub fn get_lineage_edges_query() -> String {
+        GET_LINEAGE_EDGES_SQL.to_string()
+    }
+
+    pub fn get_lineage_nodes_query() -> String {
+        GET_LINEAGE_NODES_SQL.to_string()
+    }
+
+    pub fn get_signing_key_insert_query() -> String {
+        INSERT_SIGNING_KEY_SQL.to_string()
+    }
+
+    pub fn get_signing_keys_query() -> String {
+        GET_SIGNING_KEYS_SQL.to_string()
+    }
+
+    pub fn get_signing_key_revoke_query() -> String {
+        REVOKE_SIGNING_KEY_SQL.to_string()
+    }
+
     pub fn get_load_card_query(
         table: &CardTable,
         query_args: &CardQueryArgs,
@@ -480,7 +799,7 @@ impl MySQLQueryHelper {
             "WITH query_cards AS (
                 {}
             )
-            SELECT a.uid, a.space, a.registry_type, a.encrypted_key, a.storage_key
+            SELECT a.uid, a.space, a.registry_type, a.encrypted_key, a.storage_key, a.has_manifest
             FROM {} as a
             INNER JOIN query_cards as b 
                 ON a.uid = b.uid;",
@@ -495,6 +814,10 @@ impl MySQLQueryHelper {
         DELETE_ARTIFACT_KEY_SQL.to_string()
     }
 
+    pub fn get_card_artifact_key_delete_query() -> String {
+        DELETE_CARD_ARTIFACT_KEY_SQL.to_string()
+    }
+
     pub fn get_all_space_stats_query() -> String {
         GET_ALL_SPACE_STATS_SQL.to_string()
     }
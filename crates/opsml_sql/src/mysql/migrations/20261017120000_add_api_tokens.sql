@@ -0,0 +1,12 @@
+-- Personal access tokens and service account tokens
+CREATE TABLE IF NOT EXISTS opsml_api_token (
+    id INT AUTO_INCREMENT PRIMARY KEY,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    username VARCHAR(255) NOT NULL,
+    name VARCHAR(255) NOT NULL,
+    hashed_token VARCHAR(64) NOT NULL UNIQUE,
+    scopes JSON NOT NULL DEFAULT ('[]'),
+    expires_at DATETIME,
+    revoked BOOLEAN NOT NULL DEFAULT FALSE,
+    INDEX idx_opsml_api_token_username (username)
+);
@@ -0,0 +1,3 @@
+-- Hash chain columns for tamper-evident audit events
+ALTER TABLE opsml_audit_event ADD COLUMN prev_hash VARCHAR(64);
+ALTER TABLE opsml_audit_event ADD COLUMN hash VARCHAR(64);
@@ -0,0 +1,3 @@
+-- Space the audited resource belongs to, used for filtering audit events
+ALTER TABLE opsml_audit_event ADD COLUMN space VARCHAR(255);
+CREATE INDEX idx_opsml_audit_event_space ON opsml_audit_event (space);
\ No newline at end of file
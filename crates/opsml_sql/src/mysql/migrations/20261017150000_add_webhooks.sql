@@ -0,0 +1,26 @@
+-- Webhook endpoints notified on card lifecycle events
+CREATE TABLE IF NOT EXISTS opsml_webhook (
+    uid VARCHAR(64) PRIMARY KEY,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    url VARCHAR(2048) NOT NULL,
+    space VARCHAR(255),
+    event_types JSON NOT NULL DEFAULT ('[]'),
+    encrypted_secret VARBINARY(255) NOT NULL,
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_by VARCHAR(255) NOT NULL
+);
+
+-- Delivery log for webhook requests
+CREATE TABLE IF NOT EXISTS opsml_webhook_delivery (
+    uid VARCHAR(64) PRIMARY KEY,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    webhook_uid VARCHAR(64) NOT NULL,
+    event_type VARCHAR(64) NOT NULL,
+    payload TEXT NOT NULL,
+    status VARCHAR(32) NOT NULL DEFAULT 'pending',
+    attempts INT NOT NULL DEFAULT 0,
+    response_status INT,
+    error_message TEXT,
+    INDEX idx_opsml_webhook_delivery_webhook_uid (webhook_uid, created_at)
+);
@@ -0,0 +1,14 @@
+-- Server events written in the same transaction as the change that produced them
+CREATE TABLE IF NOT EXISTS opsml_event_outbox (
+    id BIGINT AUTO_INCREMENT PRIMARY KEY,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    event_type VARCHAR(64) NOT NULL,
+    payload LONGTEXT NOT NULL
+);
+
+-- Last outbox event processed by each consumer
+CREATE TABLE IF NOT EXISTS opsml_event_offset (
+    consumer VARCHAR(255) PRIMARY KEY,
+    last_id BIGINT NOT NULL,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,27 @@
+-- Named pointers to card versions (e.g. champion), resolved with `@alias` in place of a version
+CREATE TABLE IF NOT EXISTS opsml_version_alias (
+    registry_type VARCHAR(32) NOT NULL,
+    space VARCHAR(255) NOT NULL,
+    name VARCHAR(255) NOT NULL,
+    alias VARCHAR(64) NOT NULL,
+    uid VARCHAR(64) NOT NULL,
+    version VARCHAR(255) NOT NULL,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_by VARCHAR(255) NOT NULL,
+    PRIMARY KEY (registry_type, space, name, alias)
+);
+
+-- Every move and deletion of a version alias
+CREATE TABLE IF NOT EXISTS opsml_version_alias_history (
+    id BIGINT AUTO_INCREMENT PRIMARY KEY,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    registry_type VARCHAR(32) NOT NULL,
+    space VARCHAR(255) NOT NULL,
+    name VARCHAR(255) NOT NULL,
+    alias VARCHAR(64) NOT NULL,
+    uid VARCHAR(64),
+    version VARCHAR(255),
+    previous_version VARCHAR(255),
+    username VARCHAR(255) NOT NULL,
+    INDEX idx_opsml_version_alias_history_card (registry_type, space, name, alias)
+);
\ No newline at end of file
@@ -0,0 +1,34 @@
+-- Full-text search index over card names, spaces, tags, metadata and README content
+CREATE TABLE IF NOT EXISTS opsml_card_search (
+    uid VARCHAR(64) PRIMARY KEY,
+    registry_type VARCHAR(32) NOT NULL,
+    space VARCHAR(255) NOT NULL,
+    name VARCHAR(255) NOT NULL,
+    version VARCHAR(255) NOT NULL,
+    tags TEXT NOT NULL,
+    metadata TEXT NOT NULL,
+    readme MEDIUMTEXT NOT NULL,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    deleted BOOLEAN NOT NULL DEFAULT FALSE,
+    INDEX idx_opsml_card_search_card (registry_type, space, name),
+    FULLTEXT INDEX idx_opsml_card_search_text (name, space, tags, metadata, readme)
+);
+
+-- Index existing cards. README content is indexed the next time a README is saved
+INSERT IGNORE INTO opsml_card_search (uid, registry_type, space, name, version, tags, metadata, readme, created_at, deleted)
+SELECT uid, 'data', space, name, version, COALESCE(CAST(tags AS CHAR), '[]'), CONCAT_WS(' ', username, interface_type, data_type), '', created_at, deleted_at IS NOT NULL FROM opsml_data_registry;
+
+INSERT IGNORE INTO opsml_card_search (uid, registry_type, space, name, version, tags, metadata, readme, created_at, deleted)
+SELECT uid, 'model', space, name, version, COALESCE(CAST(tags AS CHAR), '[]'), CONCAT_WS(' ', username, interface_type, data_type, model_type, task_type), '', created_at, deleted_at IS NOT NULL FROM opsml_model_registry;
+
+INSERT IGNORE INTO opsml_card_search (uid, registry_type, space, name, version, tags, metadata, readme, created_at, deleted)
+SELECT uid, 'experiment', space, name, version, COALESCE(CAST(tags AS CHAR), '[]'), CONCAT_WS(' ', username), '', created_at, deleted_at IS NOT NULL FROM opsml_experiment_registry;
+
+INSERT IGNORE INTO opsml_card_search (uid, registry_type, space, name, version, tags, metadata, readme, created_at, deleted)
+SELECT uid, 'audit', space, name, version, COALESCE(CAST(tags AS CHAR), '[]'), CONCAT_WS(' ', username), '', created_at, deleted_at IS NOT NULL FROM opsml_audit_registry;
+
+INSERT IGNORE INTO opsml_card_search (uid, registry_type, space, name, version, tags, metadata, readme, created_at, deleted)
+SELECT uid, 'prompt', space, name, version, COALESCE(CAST(tags AS CHAR), '[]'), CONCAT_WS(' ', username), '', created_at, deleted_at IS NOT NULL FROM opsml_prompt_registry;
+
+INSERT IGNORE INTO opsml_card_search (uid, registry_type, space, name, version, tags, metadata, readme, created_at, deleted)
+SELECT uid, 'service', space, name, version, '[]', CONCAT_WS(' ', username), '', created_at, deleted_at IS NOT NULL FROM opsml_service_registry;
\ No newline at end of file
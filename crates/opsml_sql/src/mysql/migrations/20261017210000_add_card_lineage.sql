@@ -0,0 +1,89 @@
+-- Lineage edges between cards. An edge points from an upstream card to a card that depends on it
+-- and belongs to the card whose record declared the reference
+CREATE TABLE IF NOT EXISTS opsml_card_lineage (
+    card_uid VARCHAR(64) NOT NULL,
+    source_uid VARCHAR(64) NOT NULL,
+    target_uid VARCHAR(64) NOT NULL,
+    PRIMARY KEY (card_uid, source_uid, target_uid),
+    INDEX idx_opsml_card_lineage_source (source_uid),
+    INDEX idx_opsml_card_lineage_target (target_uid)
+);
+
+-- Cards registered during an experiment
+INSERT IGNORE INTO opsml_card_lineage (card_uid, source_uid, target_uid)
+SELECT uid, experimentcard_uid, uid FROM opsml_data_registry WHERE experimentcard_uid IS NOT NULL AND experimentcard_uid != '';
+
+INSERT IGNORE INTO opsml_card_lineage (card_uid, source_uid, target_uid)
+SELECT uid, experimentcard_uid, uid FROM opsml_model_registry WHERE experimentcard_uid IS NOT NULL AND experimentcard_uid != '';
+
+INSERT IGNORE INTO opsml_card_lineage (card_uid, source_uid, target_uid)
+SELECT uid, experimentcard_uid, uid FROM opsml_prompt_registry WHERE experimentcard_uid IS NOT NULL AND experimentcard_uid != '';
+
+INSERT IGNORE INTO opsml_card_lineage (card_uid, source_uid, target_uid)
+SELECT experiment.uid, experiment.uid, card.value
+FROM opsml_experiment_registry AS experiment,
+JSON_TABLE(IF(JSON_TYPE(experiment.datacard_uids) = 'ARRAY', experiment.datacard_uids, JSON_ARRAY()), '$[*]' COLUMNS (value VARCHAR(64) PATH '$')) AS card
+WHERE card.value IS NOT NULL;
+
+INSERT IGNORE INTO opsml_card_lineage (card_uid, source_uid, target_uid)
+SELECT experiment.uid, experiment.uid, card.value
+FROM opsml_experiment_registry AS experiment,
+JSON_TABLE(IF(JSON_TYPE(experiment.modelcard_uids) = 'ARRAY', experiment.modelcard_uids, JSON_ARRAY()), '$[*]' COLUMNS (value VARCHAR(64) PATH '$')) AS card
+WHERE card.value IS NOT NULL;
+
+INSERT IGNORE INTO opsml_card_lineage (card_uid, source_uid, target_uid)
+SELECT experiment.uid, experiment.uid, card.value
+FROM opsml_experiment_registry AS experiment,
+JSON_TABLE(IF(JSON_TYPE(experiment.promptcard_uids) = 'ARRAY', experiment.promptcard_uids, JSON_ARRAY()), '$[*]' COLUMNS (value VARCHAR(64) PATH '$')) AS card
+WHERE card.value IS NOT NULL;
+
+INSERT IGNORE INTO opsml_card_lineage (card_uid, source_uid, target_uid)
+SELECT experiment.uid, experiment.uid, card.value
+FROM opsml_experiment_registry AS experiment,
+JSON_TABLE(IF(JSON_TYPE(experiment.service_card_uids) = 'ARRAY', experiment.service_card_uids, JSON_ARRAY()), '$[*]' COLUMNS (value VARCHAR(64) PATH '$')) AS card
+WHERE card.value IS NOT NULL;
+
+INSERT IGNORE INTO opsml_card_lineage (card_uid, source_uid, target_uid)
+SELECT experiment.uid, experiment.uid, card.value
+FROM opsml_experiment_registry AS experiment,
+JSON_TABLE(IF(JSON_TYPE(experiment.experimentcard_uids) = 'ARRAY', experiment.experimentcard_uids, JSON_ARRAY()), '$[*]' COLUMNS (value VARCHAR(64) PATH '$')) AS card
+WHERE card.value IS NOT NULL;
+
+-- Data used to train a model
+INSERT IGNORE INTO opsml_card_lineage (card_uid, source_uid, target_uid)
+SELECT uid, datacard_uid, uid FROM opsml_model_registry WHERE datacard_uid IS NOT NULL AND datacard_uid != '';
+
+-- Cards included in a service
+INSERT IGNORE INTO opsml_card_lineage (card_uid, source_uid, target_uid)
+SELECT service.uid, card.value, service.uid
+FROM opsml_service_registry AS service,
+JSON_TABLE(IF(JSON_TYPE(service.cards) = 'ARRAY', service.cards, JSON_ARRAY()), '$[*]' COLUMNS (value VARCHAR(64) PATH '$.uid')) AS card
+WHERE card.value IS NOT NULL;
+
+-- Cards covered by an audit
+INSERT IGNORE INTO opsml_card_lineage (card_uid, source_uid, target_uid)
+SELECT uid, uid, auditcard_uid FROM opsml_data_registry WHERE auditcard_uid IS NOT NULL AND auditcard_uid != '';
+
+INSERT IGNORE INTO opsml_card_lineage (card_uid, source_uid, target_uid)
+SELECT uid, uid, auditcard_uid FROM opsml_model_registry WHERE auditcard_uid IS NOT NULL AND auditcard_uid != '';
+
+INSERT IGNORE INTO opsml_card_lineage (card_uid, source_uid, target_uid)
+SELECT uid, uid, auditcard_uid FROM opsml_prompt_registry WHERE auditcard_uid IS NOT NULL AND auditcard_uid != '';
+
+INSERT IGNORE INTO opsml_card_lineage (card_uid, source_uid, target_uid)
+SELECT audit.uid, card.value, audit.uid
+FROM opsml_audit_registry AS audit,
+JSON_TABLE(IF(JSON_TYPE(audit.datacard_uids) = 'ARRAY', audit.datacard_uids, JSON_ARRAY()), '$[*]' COLUMNS (value VARCHAR(64) PATH '$')) AS card
+WHERE card.value IS NOT NULL;
+
+INSERT IGNORE INTO opsml_card_lineage (card_uid, source_uid, target_uid)
+SELECT audit.uid, card.value, audit.uid
+FROM opsml_audit_registry AS audit,
+JSON_TABLE(IF(JSON_TYPE(audit.modelcard_uids) = 'ARRAY', audit.modelcard_uids, JSON_ARRAY()), '$[*]' COLUMNS (value VARCHAR(64) PATH '$')) AS card
+WHERE card.value IS NOT NULL;
+
+INSERT IGNORE INTO opsml_card_lineage (card_uid, source_uid, target_uid)
+SELECT audit.uid, card.value, audit.uid
+FROM opsml_audit_registry AS audit,
+JSON_TABLE(IF(JSON_TYPE(audit.experimentcard_uids) = 'ARRAY', audit.experimentcard_uids, JSON_ARRAY()), '$[*]' COLUMNS (value VARCHAR(64) PATH '$')) AS card
+WHERE card.value IS NOT NULL;
\ No newline at end of file
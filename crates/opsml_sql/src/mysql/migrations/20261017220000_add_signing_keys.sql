@@ -0,0 +1,10 @@
+-- Public keys trusted to sign card manifests, per space
+CREATE TABLE IF NOT EXISTS opsml_signing_key (
+    key_id VARCHAR(64) PRIMARY KEY,
+    space VARCHAR(255) NOT NULL,
+    public_key VARCHAR(255) NOT NULL,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    created_by VARCHAR(255) NOT NULL,
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+    INDEX idx_opsml_signing_key_space (space)
+);
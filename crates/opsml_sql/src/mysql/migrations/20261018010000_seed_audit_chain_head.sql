@@ -0,0 +1,6 @@
+-- Writers lock the head row with SELECT ... FOR UPDATE. Seed it so there is always a row to lock,
+-- continuing from the last event written before the head was recorded.
+INSERT IGNORE INTO opsml_audit_chain_head (id, seq, hash)
+SELECT 1,
+    COALESCE((SELECT seq FROM opsml_audit_event ORDER BY id DESC LIMIT 1), 0),
+    COALESCE((SELECT hash FROM opsml_audit_event ORDER BY id DESC LIMIT 1), '');
@@ -0,0 +1 @@
+SELECT registry_type, space, name, alias, uid, version, updated_at, updated_by FROM opsml_version_alias WHERE registry_type = ? AND space = ? AND name = ? AND alias = ?;
\ No newline at end of file
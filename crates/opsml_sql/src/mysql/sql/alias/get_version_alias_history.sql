@@ -0,0 +1 @@
+SELECT id, created_at, registry_type, space, name, alias, uid, version, previous_version, username FROM opsml_version_alias_history WHERE registry_type = ? AND space = ? AND name = ? AND (? IS NULL OR alias = ?) ORDER BY id DESC LIMIT ?;
\ No newline at end of file
@@ -0,0 +1 @@
+INSERT INTO opsml_version_alias (registry_type, space, name, alias, uid, version, updated_at, updated_by) VALUES (?, ?, ?, ?, ?, ?, ?, ?) ON DUPLICATE KEY UPDATE uid = VALUES(uid), version = VALUES(version), updated_at = VALUES(updated_at), updated_by = VALUES(updated_by);
\ No newline at end of file
@@ -0,0 +1 @@
+DELETE FROM opsml_artifact_key WHERE uid = ?;
\ No newline at end of file
@@ -0,0 +1 @@
+SELECT uid, space, registry_type, encrypted_key, storage_key, has_manifest FROM opsml_artifact_key WHERE uid > ? OR (uid = ? AND registry_type > ?) ORDER BY uid, registry_type LIMIT ?;
\ No newline at end of file
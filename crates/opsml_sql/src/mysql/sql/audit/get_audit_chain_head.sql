@@ -0,0 +1 @@
+SELECT seq, hash, updated_at FROM opsml_audit_chain_head WHERE id = 1 FOR UPDATE;
\ No newline at end of file
@@ -0,0 +1,31 @@
+SELECT
+    id,
+    created_at,
+    username,
+    client_ip,
+    user_agent,
+    operation,
+    resource_type,
+    resource_id,
+    access_location,
+    status,
+    error_message,
+    metadata,
+    registry_type,
+    route,
+    prev_hash,
+    hash,
+    space,
+    seq
+FROM opsml_audit_event
+WHERE (? IS NULL OR username = ?)
+  AND (? IS NULL OR space = ?)
+  AND (? IS NULL OR registry_type = ?)
+  AND (? IS NULL OR operation = ?)
+  AND (? IS NULL OR status = ?)
+  AND (? IS NULL OR resource_id = ?)
+  AND (? IS NULL OR created_at >= ?)
+  AND (? IS NULL OR created_at <= ?)
+  AND (? IS NULL OR id < ?)
+ORDER BY id DESC
+LIMIT ?;
\ No newline at end of file
@@ -0,0 +1,25 @@
+SELECT
+    id,
+    created_at,
+    username,
+    client_ip,
+    user_agent,
+    operation,
+    resource_type,
+    resource_id,
+    access_location,
+    status,
+    error_message,
+    metadata,
+    registry_type,
+    route,
+    prev_hash,
+    hash,
+    space,
+    seq
+FROM opsml_audit_event
+WHERE created_at >= ?
+  AND created_at <= ?
+  AND id > ?
+ORDER BY id ASC
+LIMIT ?;
\ No newline at end of file
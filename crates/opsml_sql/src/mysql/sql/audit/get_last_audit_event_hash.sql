@@ -0,0 +1 @@
+SELECT seq, hash FROM opsml_audit_event ORDER BY id DESC LIMIT 1 FOR UPDATE;
\ No newline at end of file
@@ -1,4 +1,5 @@
 INSERT INTO opsml_audit_event (
+    created_at,
     username, 
     client_ip, 
     user_agent, 
@@ -10,5 +11,9 @@ INSERT INTO opsml_audit_event (
     error_message,
     metadata,
     registry_type,
-    route
-) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?);
\ No newline at end of file
+    route,
+    prev_hash,
+    hash,
+    space,
+    seq
+) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?);
\ No newline at end of file
@@ -0,0 +1,6 @@
+INSERT INTO opsml_audit_chain_head (id, seq, hash, updated_at)
+VALUES (1, ?, ?, ?)
+ON DUPLICATE KEY UPDATE
+    seq = VALUES(seq),
+    hash = VALUES(hash),
+    updated_at = VALUES(updated_at);
\ No newline at end of file
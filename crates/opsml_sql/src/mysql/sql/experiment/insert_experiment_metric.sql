@@ -3,5 +3,6 @@ INSERT INTO opsml_experiment_metric (
     name, 
     value,
     step,
-    timestamp
-) VALUES (?, ?, ?, ?, ?)
\ No newline at end of file
+    timestamp,
+    created_at
+) VALUES (?, ?, ?, ?, ?, COALESCE(?, CURRENT_TIMESTAMP))
\ No newline at end of file
@@ -0,0 +1,23 @@
+WITH RECURSIVE upstream(uid, depth) AS (
+    SELECT CAST(? AS CHAR(64)), 0
+    UNION
+    SELECT edge.source_uid, upstream.depth + 1
+    FROM opsml_card_lineage AS edge
+    INNER JOIN upstream ON edge.target_uid = upstream.uid
+    WHERE upstream.depth < ?
+),
+downstream(uid, depth) AS (
+    SELECT CAST(? AS CHAR(64)), 0
+    UNION
+    SELECT edge.target_uid, downstream.depth + 1
+    FROM opsml_card_lineage AS edge
+    INNER JOIN downstream ON edge.source_uid = downstream.uid
+    WHERE downstream.depth < ?
+)
+SELECT edge.source_uid, edge.target_uid
+FROM opsml_card_lineage AS edge
+INNER JOIN upstream ON edge.target_uid = upstream.uid AND upstream.depth < ?
+UNION
+SELECT edge.source_uid, edge.target_uid
+FROM opsml_card_lineage AS edge
+INNER JOIN downstream ON edge.source_uid = downstream.uid AND downstream.depth < ?;
\ No newline at end of file
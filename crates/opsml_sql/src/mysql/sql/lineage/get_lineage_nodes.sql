@@ -0,0 +1,19 @@
+WITH RECURSIVE upstream(uid, depth) AS (
+    SELECT CAST(? AS CHAR(64)), 0
+    UNION
+    SELECT edge.source_uid, upstream.depth + 1
+    FROM opsml_card_lineage AS edge
+    INNER JOIN upstream ON edge.target_uid = upstream.uid
+    WHERE upstream.depth < ?
+),
+downstream(uid, depth) AS (
+    SELECT CAST(? AS CHAR(64)), 0
+    UNION
+    SELECT edge.target_uid, downstream.depth + 1
+    FROM opsml_card_lineage AS edge
+    INNER JOIN downstream ON edge.source_uid = downstream.uid
+    WHERE downstream.depth < ?
+)
+SELECT card.uid, card.registry_type, card.space, card.name, card.version, card.deleted
+FROM opsml_card_search AS card
+WHERE card.uid IN (SELECT uid FROM upstream UNION SELECT uid FROM downstream);
\ No newline at end of file
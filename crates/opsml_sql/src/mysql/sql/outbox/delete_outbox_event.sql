@@ -0,0 +1 @@
+DELETE FROM opsml_event_outbox WHERE id = ?;
\ No newline at end of file
@@ -0,0 +1,7 @@
+UPDATE opsml_event_outbox
+SET parked_at = CASE WHEN attempts + 1 >= ? THEN ? ELSE NULL END,
+    attempts = attempts + 1,
+    last_error = ?,
+    claimed_by = NULL,
+    claimed_until = NULL
+WHERE id = ?;
\ No newline at end of file
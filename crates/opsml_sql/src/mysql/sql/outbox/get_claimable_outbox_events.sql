@@ -0,0 +1,7 @@
+SELECT id, created_at, event_type, payload
+FROM opsml_event_outbox
+WHERE parked_at IS NULL
+  AND (claimed_until IS NULL OR claimed_until < ?)
+ORDER BY id ASC
+LIMIT ?
+FOR UPDATE SKIP LOCKED;
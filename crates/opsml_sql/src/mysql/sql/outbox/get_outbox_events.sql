@@ -0,0 +1 @@
+SELECT id, created_at, event_type, payload FROM opsml_event_outbox WHERE id > ? ORDER BY id ASC LIMIT ?;
\ No newline at end of file
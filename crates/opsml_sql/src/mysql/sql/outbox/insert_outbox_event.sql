@@ -0,0 +1 @@
+INSERT INTO opsml_event_outbox (created_at, event_type, payload) VALUES (?, ?, ?);
\ No newline at end of file
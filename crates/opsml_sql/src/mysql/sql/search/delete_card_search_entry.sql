@@ -0,0 +1 @@
+DELETE FROM opsml_card_search WHERE uid = ?;
\ No newline at end of file
@@ -0,0 +1 @@
+SELECT readme FROM opsml_card_search WHERE registry_type = ? AND space = ? AND name = ? LIMIT 1;
\ No newline at end of file
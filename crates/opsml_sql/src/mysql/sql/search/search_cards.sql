@@ -0,0 +1 @@
+SELECT uid, registry_type, space, name, version, tags, created_at, MATCH (name, space, tags, metadata, readme) AGAINST (? IN BOOLEAN MODE) AS score FROM opsml_card_search WHERE MATCH (name, space, tags, metadata, readme) AGAINST (? IN BOOLEAN MODE) AND deleted = FALSE AND (? IS NULL OR registry_type = ?) AND (? IS NULL OR space = ?) AND (? IS NULL OR JSON_CONTAINS(?, JSON_QUOTE(space))) ORDER BY score DESC, created_at DESC LIMIT ?;
\ No newline at end of file
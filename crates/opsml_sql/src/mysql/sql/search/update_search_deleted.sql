@@ -0,0 +1 @@
+UPDATE opsml_card_search SET deleted = ? WHERE uid = ?;
\ No newline at end of file
@@ -0,0 +1 @@
+INSERT INTO opsml_card_search (uid, registry_type, space, name, version, tags, metadata, readme, created_at, deleted) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, FALSE) ON DUPLICATE KEY UPDATE registry_type = VALUES(registry_type), space = VALUES(space), name = VALUES(name), version = VALUES(version), tags = VALUES(tags), metadata = VALUES(metadata), readme = VALUES(readme), deleted = FALSE;
\ No newline at end of file
@@ -0,0 +1 @@
+SELECT key_id, space, public_key, created_at, created_by, active FROM opsml_signing_key WHERE (? IS NULL OR space = ?) ORDER BY space, created_at DESC;
\ No newline at end of file
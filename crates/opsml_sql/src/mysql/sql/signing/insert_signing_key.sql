@@ -0,0 +1 @@
+INSERT INTO opsml_signing_key (key_id, space, public_key, created_at, created_by, active) VALUES (?, ?, ?, ?, ?, ?);
\ No newline at end of file
@@ -0,0 +1 @@
+INSERT INTO opsml_api_token (username, name, hashed_token, scopes, expires_at) VALUES (?, ?, ?, ?, ?);
\ No newline at end of file
@@ -0,0 +1 @@
+UPDATE opsml_api_token SET revoked = TRUE WHERE username = ? AND name = ? AND revoked = FALSE;
\ No newline at end of file
@@ -0,0 +1,7 @@
+SELECT uid, created_at, updated_at, webhook_uid, event_type, payload, status, attempts, response_status, error_message
+FROM opsml_webhook_delivery
+WHERE status = ?
+  AND updated_at < ?
+ORDER BY created_at ASC
+LIMIT ?
+FOR UPDATE SKIP LOCKED;
\ No newline at end of file
@@ -0,0 +1 @@
+INSERT INTO opsml_webhook (uid, created_at, url, space, event_types, encrypted_secret, active, created_by) VALUES (?, ?, ?, ?, ?, ?, ?, ?);
\ No newline at end of file
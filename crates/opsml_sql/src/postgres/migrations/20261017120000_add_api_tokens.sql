@@ -0,0 +1,13 @@
+-- Personal access tokens and service account tokens
+CREATE TABLE IF NOT EXISTS opsml_api_token (
+    id SERIAL PRIMARY KEY,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    username TEXT NOT NULL,
+    name TEXT NOT NULL,
+    hashed_token TEXT NOT NULL UNIQUE,
+    scopes JSONB NOT NULL DEFAULT '[]',
+    expires_at TIMESTAMPTZ,
+    revoked BOOLEAN NOT NULL DEFAULT FALSE
+);
+
+CREATE INDEX IF NOT EXISTS idx_opsml_api_token_username ON opsml_api_token (username);
@@ -0,0 +1,3 @@
+-- Hash chain columns for tamper-evident audit events
+ALTER TABLE opsml_audit_event ADD COLUMN IF NOT EXISTS prev_hash TEXT;
+ALTER TABLE opsml_audit_event ADD COLUMN IF NOT EXISTS hash TEXT;
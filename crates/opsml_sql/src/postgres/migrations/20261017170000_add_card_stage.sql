@@ -0,0 +1,5 @@
+-- Lifecycle stage of model and service card versions
+ALTER TABLE opsml_model_registry ADD COLUMN IF NOT EXISTS stage TEXT NOT NULL DEFAULT 'unassigned';
+ALTER TABLE opsml_service_registry ADD COLUMN IF NOT EXISTS stage TEXT NOT NULL DEFAULT 'unassigned';
+CREATE INDEX IF NOT EXISTS idx_opsml_model_registry_stage ON opsml_model_registry (space, name, stage);
+CREATE INDEX IF NOT EXISTS idx_opsml_service_registry_stage ON opsml_service_registry (space, name, stage);
\ No newline at end of file
@@ -0,0 +1,28 @@
+-- Named pointers to card versions (e.g. champion), resolved with `@alias` in place of a version
+CREATE TABLE IF NOT EXISTS opsml_version_alias (
+    registry_type TEXT NOT NULL,
+    space TEXT NOT NULL,
+    name TEXT NOT NULL,
+    alias TEXT NOT NULL,
+    uid TEXT NOT NULL,
+    version TEXT NOT NULL,
+    updated_at TIMESTAMPTZ DEFAULT NOW(),
+    updated_by TEXT NOT NULL,
+    PRIMARY KEY (registry_type, space, name, alias)
+);
+
+-- Every move and deletion of a version alias
+CREATE TABLE IF NOT EXISTS opsml_version_alias_history (
+    id BIGSERIAL PRIMARY KEY,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    registry_type TEXT NOT NULL,
+    space TEXT NOT NULL,
+    name TEXT NOT NULL,
+    alias TEXT NOT NULL,
+    uid TEXT,
+    version TEXT,
+    previous_version TEXT,
+    username TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_opsml_version_alias_history_card ON opsml_version_alias_history (registry_type, space, name, alias);
\ No newline at end of file
@@ -0,0 +1,19 @@
+-- Soft delete: deleted cards stay in the trash until they are restored or purged
+ALTER TABLE opsml_data_registry ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
+ALTER TABLE opsml_data_registry ADD COLUMN IF NOT EXISTS deleted_by TEXT;
+ALTER TABLE opsml_model_registry ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
+ALTER TABLE opsml_model_registry ADD COLUMN IF NOT EXISTS deleted_by TEXT;
+ALTER TABLE opsml_experiment_registry ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
+ALTER TABLE opsml_experiment_registry ADD COLUMN IF NOT EXISTS deleted_by TEXT;
+ALTER TABLE opsml_audit_registry ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
+ALTER TABLE opsml_audit_registry ADD COLUMN IF NOT EXISTS deleted_by TEXT;
+ALTER TABLE opsml_prompt_registry ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
+ALTER TABLE opsml_prompt_registry ADD COLUMN IF NOT EXISTS deleted_by TEXT;
+ALTER TABLE opsml_service_registry ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
+ALTER TABLE opsml_service_registry ADD COLUMN IF NOT EXISTS deleted_by TEXT;
+CREATE INDEX IF NOT EXISTS idx_opsml_data_registry_deleted_at ON opsml_data_registry (deleted_at);
+CREATE INDEX IF NOT EXISTS idx_opsml_model_registry_deleted_at ON opsml_model_registry (deleted_at);
+CREATE INDEX IF NOT EXISTS idx_opsml_experiment_registry_deleted_at ON opsml_experiment_registry (deleted_at);
+CREATE INDEX IF NOT EXISTS idx_opsml_audit_registry_deleted_at ON opsml_audit_registry (deleted_at);
+CREATE INDEX IF NOT EXISTS idx_opsml_prompt_registry_deleted_at ON opsml_prompt_registry (deleted_at);
+CREATE INDEX IF NOT EXISTS idx_opsml_service_registry_deleted_at ON opsml_service_registry (deleted_at);
\ No newline at end of file
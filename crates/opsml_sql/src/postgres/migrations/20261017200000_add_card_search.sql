@@ -0,0 +1,48 @@
+-- Full-text search index over card names, spaces, tags, metadata and README content
+CREATE TABLE IF NOT EXISTS opsml_card_search (
+    uid TEXT PRIMARY KEY,
+    registry_type TEXT NOT NULL,
+    space TEXT NOT NULL,
+    name TEXT NOT NULL,
+    version TEXT NOT NULL,
+    tags TEXT NOT NULL DEFAULT '[]',
+    metadata TEXT NOT NULL DEFAULT '',
+    readme TEXT NOT NULL DEFAULT '',
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    deleted BOOLEAN NOT NULL DEFAULT FALSE,
+    document TSVECTOR GENERATED ALWAYS AS (
+        setweight(to_tsvector('simple', name), 'A') ||
+        setweight(to_tsvector('simple', space), 'A') ||
+        setweight(to_tsvector('simple', tags), 'B') ||
+        setweight(to_tsvector('simple', metadata), 'C') ||
+        setweight(to_tsvector('simple', readme), 'D')
+    ) STORED
+);
+
+CREATE INDEX IF NOT EXISTS idx_opsml_card_search_document ON opsml_card_search USING GIN (document);
+CREATE INDEX IF NOT EXISTS idx_opsml_card_search_card ON opsml_card_search (registry_type, space, name);
+
+-- Index existing cards. README content is indexed the next time a README is saved
+INSERT INTO opsml_card_search (uid, registry_type, space, name, version, tags, metadata, created_at, deleted)
+SELECT uid, 'data', space, name, version, COALESCE(tags::TEXT, '[]'), CONCAT_WS(' ', username, interface_type, data_type), created_at, deleted_at IS NOT NULL FROM opsml_data_registry
+ON CONFLICT (uid) DO NOTHING;
+
+INSERT INTO opsml_card_search (uid, registry_type, space, name, version, tags, metadata, created_at, deleted)
+SELECT uid, 'model', space, name, version, COALESCE(tags::TEXT, '[]'), CONCAT_WS(' ', username, interface_type, data_type, model_type, task_type), created_at, deleted_at IS NOT NULL FROM opsml_model_registry
+ON CONFLICT (uid) DO NOTHING;
+
+INSERT INTO opsml_card_search (uid, registry_type, space, name, version, tags, metadata, created_at, deleted)
+SELECT uid, 'experiment', space, name, version, COALESCE(tags::TEXT, '[]'), CONCAT_WS(' ', username), created_at, deleted_at IS NOT NULL FROM opsml_experiment_registry
+ON CONFLICT (uid) DO NOTHING;
+
+INSERT INTO opsml_card_search (uid, registry_type, space, name, version, tags, metadata, created_at, deleted)
+SELECT uid, 'audit', space, name, version, COALESCE(tags::TEXT, '[]'), CONCAT_WS(' ', username), created_at, deleted_at IS NOT NULL FROM opsml_audit_registry
+ON CONFLICT (uid) DO NOTHING;
+
+INSERT INTO opsml_card_search (uid, registry_type, space, name, version, tags, metadata, created_at, deleted)
+SELECT uid, 'prompt', space, name, version, COALESCE(tags::TEXT, '[]'), CONCAT_WS(' ', username), created_at, deleted_at IS NOT NULL FROM opsml_prompt_registry
+ON CONFLICT (uid) DO NOTHING;
+
+INSERT INTO opsml_card_search (uid, registry_type, space, name, version, tags, metadata, created_at, deleted)
+SELECT uid, 'service', space, name, version, '[]', CONCAT_WS(' ', username), created_at, deleted_at IS NOT NULL FROM opsml_service_registry
+ON CONFLICT (uid) DO NOTHING;
\ No newline at end of file
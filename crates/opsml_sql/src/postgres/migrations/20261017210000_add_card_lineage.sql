@@ -0,0 +1,99 @@
+-- Lineage edges between cards. An edge points from an upstream card to a card that depends on it
+-- and belongs to the card whose record declared the reference
+CREATE TABLE IF NOT EXISTS opsml_card_lineage (
+    card_uid TEXT NOT NULL,
+    source_uid TEXT NOT NULL,
+    target_uid TEXT NOT NULL,
+    PRIMARY KEY (card_uid, source_uid, target_uid)
+);
+
+CREATE INDEX IF NOT EXISTS idx_opsml_card_lineage_source ON opsml_card_lineage (source_uid);
+
+CREATE INDEX IF NOT EXISTS idx_opsml_card_lineage_target ON opsml_card_lineage (target_uid);
+
+-- Cards registered during an experiment
+INSERT INTO opsml_card_lineage (card_uid, source_uid, target_uid)
+SELECT uid, experimentcard_uid, uid FROM opsml_data_registry WHERE experimentcard_uid IS NOT NULL AND experimentcard_uid != ''
+ON CONFLICT DO NOTHING;
+
+INSERT INTO opsml_card_lineage (card_uid, source_uid, target_uid)
+SELECT uid, experimentcard_uid, uid FROM opsml_model_registry WHERE experimentcard_uid IS NOT NULL AND experimentcard_uid != ''
+ON CONFLICT DO NOTHING;
+
+INSERT INTO opsml_card_lineage (card_uid, source_uid, target_uid)
+SELECT uid, experimentcard_uid, uid FROM opsml_prompt_registry WHERE experimentcard_uid IS NOT NULL AND experimentcard_uid != ''
+ON CONFLICT DO NOTHING;
+
+INSERT INTO opsml_card_lineage (card_uid, source_uid, target_uid)
+SELECT experiment.uid, experiment.uid, card.value
+FROM opsml_experiment_registry AS experiment
+CROSS JOIN LATERAL jsonb_array_elements_text(CASE WHEN jsonb_typeof(experiment.datacard_uids) = 'array' THEN experiment.datacard_uids ELSE '[]'::JSONB END) AS card(value)
+ON CONFLICT DO NOTHING;
+
+INSERT INTO opsml_card_lineage (card_uid, source_uid, target_uid)
+SELECT experiment.uid, experiment.uid, card.value
+FROM opsml_experiment_registry AS experiment
+CROSS JOIN LATERAL jsonb_array_elements_text(CASE WHEN jsonb_typeof(experiment.modelcard_uids) = 'array' THEN experiment.modelcard_uids ELSE '[]'::JSONB END) AS card(value)
+ON CONFLICT DO NOTHING;
+
+INSERT INTO opsml_card_lineage (card_uid, source_uid, target_uid)
+SELECT experiment.uid, experiment.uid, card.value
+FROM opsml_experiment_registry AS experiment
+CROSS JOIN LATERAL jsonb_array_elements_text(CASE WHEN jsonb_typeof(experiment.promptcard_uids) = 'array' THEN experiment.promptcard_uids ELSE '[]'::JSONB END) AS card(value)
+ON CONFLICT DO NOTHING;
+
+INSERT INTO opsml_card_lineage (card_uid, source_uid, target_uid)
+SELECT experiment.uid, experiment.uid, card.value
+FROM opsml_experiment_registry AS experiment
+CROSS JOIN LATERAL jsonb_array_elements_text(CASE WHEN jsonb_typeof(experiment.service_card_uids) = 'array' THEN experiment.service_card_uids ELSE '[]'::JSONB END) AS card(value)
+ON CONFLICT DO NOTHING;
+
+INSERT INTO opsml_card_lineage (card_uid, source_uid, target_uid)
+SELECT experiment.uid, experiment.uid, card.value
+FROM opsml_experiment_registry AS experiment
+CROSS JOIN LATERAL jsonb_array_elements_text(CASE WHEN jsonb_typeof(experiment.experimentcard_uids) = 'array' THEN experiment.experimentcard_uids ELSE '[]'::JSONB END) AS card(value)
+ON CONFLICT DO NOTHING;
+
+-- Data used to train a model
+INSERT INTO opsml_card_lineage (card_uid, source_uid, target_uid)
+SELECT uid, datacard_uid, uid FROM opsml_model_registry WHERE datacard_uid IS NOT NULL AND datacard_uid != ''
+ON CONFLICT DO NOTHING;
+
+-- Cards included in a service
+INSERT INTO opsml_card_lineage (card_uid, source_uid, target_uid)
+SELECT service.uid, card.value ->> 'uid', service.uid
+FROM opsml_service_registry AS service
+CROSS JOIN LATERAL jsonb_array_elements(CASE WHEN jsonb_typeof(service.cards) = 'array' THEN service.cards ELSE '[]'::JSONB END) AS card(value)
+WHERE card.value ->> 'uid' IS NOT NULL
+ON CONFLICT DO NOTHING;
+
+-- Cards covered by an audit
+INSERT INTO opsml_card_lineage (card_uid, source_uid, target_uid)
+SELECT uid, uid, auditcard_uid FROM opsml_data_registry WHERE auditcard_uid IS NOT NULL AND auditcard_uid != ''
+ON CONFLICT DO NOTHING;
+
+INSERT INTO opsml_card_lineage (card_uid, source_uid, target_uid)
+SELECT uid, uid, auditcard_uid FROM opsml_model_registry WHERE auditcard_uid IS NOT NULL AND auditcard_uid != ''
+ON CONFLICT DO NOTHING;
+
+INSERT INTO opsml_card_lineage (card_uid, source_uid, target_uid)
+SELECT uid, uid, auditcard_uid FROM opsml_prompt_registry WHERE auditcard_uid IS NOT NULL AND auditcard_uid != ''
+ON CONFLICT DO NOTHING;
+
+INSERT INTO opsml_card_lineage (card_uid, source_uid, target_uid)
+SELECT audit.uid, card.value, audit.uid
+FROM opsml_audit_registry AS audit
+CROSS JOIN LATERAL jsonb_array_elements_text(CASE WHEN jsonb_typeof(audit.datacard_uids) = 'array' THEN audit.datacard_uids ELSE '[]'::JSONB END) AS card(value)
+ON CONFLICT DO NOTHING;
+
+INSERT INTO opsml_card_lineage (card_uid, source_uid, target_uid)
+SELECT audit.uid, card.value, audit.uid
+FROM opsml_audit_registry AS audit
+CROSS JOIN LATERAL jsonb_array_elements_text(CASE WHEN jsonb_typeof(audit.modelcard_uids) = 'array' THEN audit.modelcard_uids ELSE '[]'::JSONB END) AS card(value)
+ON CONFLICT DO NOTHING;
+
+INSERT INTO opsml_card_lineage (card_uid, source_uid, target_uid)
+SELECT audit.uid, card.value, audit.uid
+FROM opsml_audit_registry AS audit
+CROSS JOIN LATERAL jsonb_array_elements_text(CASE WHEN jsonb_typeof(audit.experimentcard_uids) = 'array' THEN audit.experimentcard_uids ELSE '[]'::JSONB END) AS card(value)
+ON CONFLICT DO NOTHING;
\ No newline at end of file
@@ -0,0 +1,10 @@
+-- Position of each event in the audit hash chain, covered by the event hash
+ALTER TABLE opsml_audit_event ADD COLUMN IF NOT EXISTS seq BIGINT;
+
+-- Latest sequence number and hash of the audit chain, used to detect events deleted from the end
+CREATE TABLE IF NOT EXISTS opsml_audit_chain_head (
+    id INTEGER PRIMARY KEY,
+    seq BIGINT NOT NULL,
+    hash TEXT NOT NULL,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,3 @@
+-- Metric pages are read by experiment and name, ordered by idx and optionally bounded by step
+CREATE INDEX IF NOT EXISTS idx_opsml_experiment_metric_name ON opsml_experiment_metric (experiment_uid, name, idx);
+CREATE INDEX IF NOT EXISTS idx_opsml_experiment_metric_step ON opsml_experiment_metric (experiment_uid, name, step);
\ No newline at end of file
@@ -0,0 +1 @@
+DELETE FROM opsml_version_alias WHERE registry_type = $1 AND space = $2 AND name = $3 AND alias = $4;
\ No newline at end of file
@@ -0,0 +1 @@
+SELECT id, created_at, registry_type, space, name, alias, uid, version, previous_version, username FROM opsml_version_alias_history WHERE registry_type = $1 AND space = $2 AND name = $3 AND ($4::TEXT IS NULL OR alias = $4) ORDER BY id DESC LIMIT $5;
\ No newline at end of file
@@ -0,0 +1 @@
+INSERT INTO opsml_version_alias_history (created_at, registry_type, space, name, alias, uid, version, previous_version, username) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9);
\ No newline at end of file
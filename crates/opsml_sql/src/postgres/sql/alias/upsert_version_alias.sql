@@ -0,0 +1 @@
+INSERT INTO opsml_version_alias (registry_type, space, name, alias, uid, version, updated_at, updated_by) VALUES ($1, $2, $3, $4, $5, $6, $7, $8) ON CONFLICT (registry_type, space, name, alias) DO UPDATE SET uid = EXCLUDED.uid, version = EXCLUDED.version, updated_at = EXCLUDED.updated_at, updated_by = EXCLUDED.updated_by;
\ No newline at end of file
@@ -0,0 +1 @@
+DELETE FROM opsml_artifact_key WHERE uid = $1;
\ No newline at end of file
@@ -0,0 +1 @@
+SELECT uid, space, registry_type, encrypted_key, storage_key, has_manifest FROM opsml_artifact_key WHERE uid > $1 OR (uid = $1 AND registry_type > $2) ORDER BY uid, registry_type LIMIT $3;
\ No newline at end of file
@@ -0,0 +1,25 @@
+SELECT
+    id,
+    created_at,
+    username,
+    client_ip,
+    user_agent,
+    operation,
+    resource_type,
+    resource_id,
+    access_location,
+    status,
+    error_message,
+    metadata,
+    registry_type,
+    route,
+    prev_hash,
+    hash,
+    space,
+    seq
+FROM opsml_audit_event
+WHERE created_at >= $1
+  AND created_at <= $2
+  AND id > $3
+ORDER BY id ASC
+LIMIT $4;
\ No newline at end of file
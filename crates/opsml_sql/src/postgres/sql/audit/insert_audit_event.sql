@@ -1,4 +1,5 @@
 INSERT INTO opsml_audit_event (
+    created_at,
     username, 
     client_ip, 
     user_agent, 
@@ -10,5 +11,9 @@ INSERT INTO opsml_audit_event (
     error_message,
     metadata,
     registry_type,
-    route
-) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12);
\ No newline at end of file
+    route,
+    prev_hash,
+    hash,
+    space,
+    seq
+) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17);
\ No newline at end of file
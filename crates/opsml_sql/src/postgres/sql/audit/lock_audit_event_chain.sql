@@ -0,0 +1 @@
+SELECT pg_advisory_xact_lock(hashtext('opsml_audit_event'));
\ No newline at end of file
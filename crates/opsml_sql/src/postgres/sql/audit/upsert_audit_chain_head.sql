@@ -0,0 +1,6 @@
+INSERT INTO opsml_audit_chain_head (id, seq, hash, updated_at)
+VALUES (1, $1, $2, $3)
+ON CONFLICT (id) DO UPDATE SET
+    seq = excluded.seq,
+    hash = excluded.hash,
+    updated_at = excluded.updated_at;
\ No newline at end of file
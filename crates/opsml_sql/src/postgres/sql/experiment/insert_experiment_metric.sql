@@ -3,5 +3,6 @@ INSERT INTO opsml_experiment_metric (
     name, 
     value,
     step,
-    timestamp
-) VALUES ($1, $2, $3, $4, $5)
\ No newline at end of file
+    timestamp,
+    created_at
+) VALUES ($1, $2, $3, $4, $5, COALESCE($6, CURRENT_TIMESTAMP))
\ No newline at end of file
@@ -0,0 +1,23 @@
+WITH RECURSIVE upstream(uid, depth) AS (
+    SELECT $1::TEXT, 0
+    UNION
+    SELECT edge.source_uid, upstream.depth + 1
+    FROM opsml_card_lineage AS edge
+    INNER JOIN upstream ON edge.target_uid = upstream.uid
+    WHERE upstream.depth < $2
+),
+downstream(uid, depth) AS (
+    SELECT $1::TEXT, 0
+    UNION
+    SELECT edge.target_uid, downstream.depth + 1
+    FROM opsml_card_lineage AS edge
+    INNER JOIN downstream ON edge.source_uid = downstream.uid
+    WHERE downstream.depth < $3
+)
+SELECT edge.source_uid, edge.target_uid
+FROM opsml_card_lineage AS edge
+INNER JOIN upstream ON edge.target_uid = upstream.uid AND upstream.depth < $2
+UNION
+SELECT edge.source_uid, edge.target_uid
+FROM opsml_card_lineage AS edge
+INNER JOIN downstream ON edge.source_uid = downstream.uid AND downstream.depth < $3;
\ No newline at end of file
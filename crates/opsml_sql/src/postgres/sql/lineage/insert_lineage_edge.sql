@@ -0,0 +1 @@
+INSERT INTO opsml_card_lineage (card_uid, source_uid, target_uid) VALUES ($1, $2, $3) ON CONFLICT DO NOTHING;
\ No newline at end of file
@@ -0,0 +1,18 @@
+WITH claimable AS MATERIALIZED (
+    SELECT id
+    FROM opsml_event_outbox
+    WHERE parked_at IS NULL
+      AND (claimed_until IS NULL OR claimed_until < $3)
+    ORDER BY id ASC
+    LIMIT $4
+    FOR UPDATE SKIP LOCKED
+)
+UPDATE opsml_event_outbox
+SET claimed_by = $1, claimed_until = $2
+FROM claimable
+WHERE opsml_event_outbox.id = claimable.id
+RETURNING
+    opsml_event_outbox.id,
+    opsml_event_outbox.created_at,
+    opsml_event_outbox.event_type,
+    opsml_event_outbox.payload;
@@ -0,0 +1 @@
+DELETE FROM opsml_event_outbox WHERE id = $1;
\ No newline at end of file
@@ -0,0 +1,7 @@
+UPDATE opsml_event_outbox
+SET parked_at = CASE WHEN attempts + 1 >= $1 THEN $2 ELSE NULL END,
+    attempts = attempts + 1,
+    last_error = $3,
+    claimed_by = NULL,
+    claimed_until = NULL
+WHERE id = $4;
\ No newline at end of file
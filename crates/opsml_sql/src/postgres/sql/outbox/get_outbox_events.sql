@@ -0,0 +1 @@
+SELECT id, created_at, event_type, payload FROM opsml_event_outbox WHERE id > $1 ORDER BY id ASC LIMIT $2;
\ No newline at end of file
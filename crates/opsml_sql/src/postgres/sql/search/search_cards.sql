@@ -0,0 +1 @@
+SELECT uid, registry_type, space, name, version, tags, created_at, ts_rank(document, query)::FLOAT8 AS score FROM opsml_card_search, to_tsquery('simple', $1) AS query WHERE document @@ query AND NOT deleted AND ($2::TEXT IS NULL OR registry_type = $2) AND ($3::TEXT IS NULL OR space = $3) AND ($4::TEXT[] IS NULL OR space = ANY($4)) ORDER BY score DESC, created_at DESC LIMIT $5;
\ No newline at end of file
@@ -0,0 +1 @@
+UPDATE opsml_card_search SET deleted = $1 WHERE uid = $2;
\ No newline at end of file
@@ -0,0 +1 @@
+UPDATE opsml_card_search SET readme = $1 WHERE registry_type = $2 AND space = $3 AND name = $4;
\ No newline at end of file
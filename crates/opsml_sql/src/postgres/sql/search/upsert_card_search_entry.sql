@@ -0,0 +1 @@
+INSERT INTO opsml_card_search (uid, registry_type, space, name, version, tags, metadata, readme, created_at, deleted) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, FALSE) ON CONFLICT (uid) DO UPDATE SET registry_type = EXCLUDED.registry_type, space = EXCLUDED.space, name = EXCLUDED.name, version = EXCLUDED.version, tags = EXCLUDED.tags, metadata = EXCLUDED.metadata, readme = EXCLUDED.readme, deleted = FALSE;
\ No newline at end of file
@@ -0,0 +1 @@
+SELECT key_id, space, public_key, created_at, created_by, active FROM opsml_signing_key WHERE ($1::TEXT IS NULL OR space = $1) ORDER BY space, created_at DESC;
\ No newline at end of file
@@ -0,0 +1 @@
+INSERT INTO opsml_signing_key (key_id, space, public_key, created_at, created_by, active) VALUES ($1, $2, $3, $4, $5, $6);
\ No newline at end of file
@@ -0,0 +1 @@
+UPDATE opsml_signing_key SET active = FALSE WHERE key_id = $1 AND active = TRUE;
\ No newline at end of file
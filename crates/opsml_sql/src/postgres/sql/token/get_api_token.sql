@@ -0,0 +1 @@
+SELECT id, created_at, username, name, hashed_token, scopes, expires_at, revoked FROM opsml_api_token WHERE hashed_token = $1;
\ No newline at end of file
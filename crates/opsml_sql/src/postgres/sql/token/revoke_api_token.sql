@@ -0,0 +1 @@
+UPDATE opsml_api_token SET revoked = TRUE WHERE username = $1 AND name = $2 AND revoked = FALSE;
\ No newline at end of file
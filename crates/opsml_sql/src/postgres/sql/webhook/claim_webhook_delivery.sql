@@ -0,0 +1 @@
+UPDATE opsml_webhook_delivery SET updated_at = $1 WHERE uid = $2;
\ No newline at end of file
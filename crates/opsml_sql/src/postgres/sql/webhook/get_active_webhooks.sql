@@ -0,0 +1 @@
+SELECT uid, created_at, url, space, event_types, encrypted_secret, active, created_by FROM opsml_webhook WHERE active = TRUE AND (space IS NULL OR space = $1);
\ No newline at end of file
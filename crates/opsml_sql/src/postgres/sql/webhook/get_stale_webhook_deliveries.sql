@@ -0,0 +1,7 @@
+SELECT uid, created_at, updated_at, webhook_uid, event_type, payload, status, attempts, response_status, error_message
+FROM opsml_webhook_delivery
+WHERE status = $1
+  AND updated_at < $2
+ORDER BY created_at ASC
+LIMIT $3
+FOR UPDATE SKIP LOCKED;
\ No newline at end of file
@@ -0,0 +1 @@
+SELECT uid, created_at, url, space, event_types, encrypted_secret, active, created_by FROM opsml_webhook WHERE uid = $1;
\ No newline at end of file
@@ -0,0 +1 @@
+SELECT uid, created_at, updated_at, webhook_uid, event_type, payload, status, attempts, response_status, error_message FROM opsml_webhook_delivery WHERE webhook_uid = $1 ORDER BY created_at DESC LIMIT $2;
\ No newline at end of file
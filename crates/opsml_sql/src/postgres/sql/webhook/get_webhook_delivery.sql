@@ -0,0 +1 @@
+SELECT uid, created_at, updated_at, webhook_uid, event_type, payload, status, attempts, response_status, error_message FROM opsml_webhook_delivery WHERE uid = $1;
\ No newline at end of file
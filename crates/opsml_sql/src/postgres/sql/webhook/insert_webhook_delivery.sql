@@ -0,0 +1 @@
+INSERT INTO opsml_webhook_delivery (uid, created_at, updated_at, webhook_uid, event_type, payload, status, attempts, response_status, error_message) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10);
\ No newline at end of file
@@ -0,0 +1 @@
+UPDATE opsml_webhook_delivery SET updated_at = $1, status = $2, attempts = $3, response_status = $4, error_message = $5 WHERE uid = $6;
\ No newline at end of file
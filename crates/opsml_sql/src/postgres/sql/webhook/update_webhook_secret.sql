@@ -0,0 +1 @@
+UPDATE opsml_webhook SET encrypted_secret = $1 WHERE uid = $2;
\ No newline at end of file
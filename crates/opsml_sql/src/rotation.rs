@@ -0,0 +1,315 @@
+use crate::base::SqlClient;
+use crate::enums::client::SqlClientEnum;
+use crate::error::SqlError;
+use crate::schemas::schema::Webhook;
+use opsml_crypt::KeyProvider;
+use opsml_types::contracts::ArtifactKey;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, instrument};
+
+/// How a stored key was re-wrapped during a rotation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RewrapOutcome {
+    /// Wrapped by the old key provider and re-wrapped by the new one
+    Rotated,
+    /// Stored before artifact keys were wrapped by a key provider and wrapped by the new one
+    Upgraded,
+    /// Already wrapped by the new key provider, e.g. by an interrupted rotation
+    AlreadyRotated,
+}
+
+/// Number of keys per outcome of a rotation
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct RotationCounts {
+    pub rotated: i64,
+    pub upgraded: i64,
+    pub already_rotated: i64,
+}
+
+impl RotationCounts {
+    fn add(&mut self, outcome: RewrapOutcome) {
+        match outcome {
+            RewrapOutcome::Rotated => self.rotated += 1,
+            RewrapOutcome::Upgraded => self.upgraded += 1,
+            RewrapOutcome::AlreadyRotated => self.already_rotated += 1,
+        }
+    }
+
+    pub fn merge(&mut self, other: &RotationCounts) {
+        self.rotated += other.rotated;
+        self.upgraded += other.upgraded;
+        self.already_rotated += other.already_rotated;
+    }
+
+    pub fn total(&self) -> i64 {
+        self.rotated + self.upgraded + self.already_rotated
+    }
+}
+
+/// A page of artifact keys re-wrapped by `rotate_artifact_key_batch`
+#[derive(Debug, Default)]
+pub struct ArtifactKeyBatch {
+    /// Uid and registry type of the last key in the batch. None when no keys were left
+    pub last_key: Option<(String, String)>,
+    pub counts: RotationCounts,
+}
+
+/// Re-wraps a stored artifact key from the old to the new key provider. The artifact
+/// encryption key itself is unchanged, so stored artifacts stay readable
+pub async fn rewrap_artifact_key(
+    key: &ArtifactKey,
+    old_provider: &dyn KeyProvider,
+    new_provider: &dyn KeyProvider,
+) -> Result<(ArtifactKey, RewrapOutcome), SqlError> {
+    if key.is_sealed_with(new_provider).await {
+        return Ok((key.clone(), RewrapOutcome::AlreadyRotated));
+    }
+
+    let outcome = match key.is_sealed_with(old_provider).await {
+        true => RewrapOutcome::Rotated,
+        false => RewrapOutcome::Upgraded,
+    };
+
+    let client_key = key
+        .unseal(old_provider)
+        .await
+        .map_err(|_| SqlError::KeyRotationError(format!("card {}", key.uid)))?;
+
+    Ok((client_key.seal(new_provider).await?, outcome))
+}
+
+/// Re-wraps the artifact keys following `after_uid` and `after_registry_type`. Keys of a batch
+/// are updated in a single transaction and nothing is written when `dry_run` is set
+///
+/// # Arguments
+/// * `client` - The database holding the keys
+/// * `old_provider` - The key provider the keys are wrapped by
+/// * `new_provider` - The key provider to wrap the keys with
+/// * `after_uid` - Uid of the last key of the previous batch. Empty for the first batch
+/// * `after_registry_type` - Registry type of the last key of the previous batch
+/// * `batch_size` - Maximum number of keys to re-wrap
+/// * `dry_run` - Check that every key can be re-wrapped without updating them
+#[instrument(skip_all)]
+pub async fn rotate_artifact_key_batch(
+    client: &SqlClientEnum,
+    old_provider: &dyn KeyProvider,
+    new_provider: &dyn KeyProvider,
+    after_uid: &str,
+    after_registry_type: &str,
+    batch_size: i64,
+    dry_run: bool,
+) -> Result<ArtifactKeyBatch, SqlError> {
+    let keys = client
+        .get_artifact_key_page(after_uid, after_registry_type, batch_size)
+        .await?;
+
+    let mut batch = ArtifactKeyBatch {
+        last_key: keys
+            .last()
+            .map(|key| (key.uid.clone(), key.registry_type.to_string())),
+        ..Default::default()
+    };
+
+    let mut rewrapped = Vec::new();
+    for key in &keys {
+        let (key, outcome) = rewrap_artifact_key(key, old_provider, new_provider).await?;
+        batch.counts.add(outcome);
+
+        if outcome != RewrapOutcome::AlreadyRotated {
+            rewrapped.push(key);
+        }
+    }
+
+    if !dry_run && !rewrapped.is_empty() {
+        client.rewrap_artifact_keys(&rewrapped).await?;
+    }
+
+    debug!("Re-wrapped {} artifact keys", rewrapped.len());
+
+    Ok(batch)
+}
+
+/// Re-encrypts every webhook signing secret from the old to the new key provider in a
+/// single transaction. Nothing is written when `dry_run` is set
+#[instrument(skip_all)]
+pub async fn rotate_webhook_secrets(
+    client: &SqlClientEnum,
+    old_provider: &dyn KeyProvider,
+    new_provider: &dyn KeyProvider,
+    dry_run: bool,
+) -> Result<RotationCounts, SqlError> {
+    let mut counts = RotationCounts::default();
+    let mut rotated = Vec::new();
+
+    for mut webhook in client.get_webhooks().await? {
+        if webhook.decrypt_secret(new_provider).await.is_ok() {
+            counts.add(RewrapOutcome::AlreadyRotated);
+            continue;
+        }
+
+        let secret = webhook
+            .decrypt_secret(old_provider)
+            .await
+            .map_err(|_| SqlError::KeyRotationError(format!("webhook {}", webhook.uid)))?;
+
+        webhook.encrypted_secret =
+            Webhook::encrypt_secret(new_provider, &webhook.uid, &secret).await?;
+        counts.add(RewrapOutcome::Rotated);
+        rotated.push(webhook);
+    }
+
+    if !dry_run && !rotated.is_empty() {
+        client.update_webhook_secrets(&rotated).await?;
+    }
+
+    Ok(counts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opsml_crypt::{encrypted_key, generate_key, FileKeyProvider, LocalKeyProvider};
+    use opsml_settings::config::DatabaseSettings;
+    use opsml_types::RegistryType;
+    use opsml_utils::{create_uuid7, uid_to_byte_key};
+    use std::collections::HashMap;
+
+    async fn sqlite_client(path: &str) -> SqlClientEnum {
+        let _ = std::fs::remove_file(path);
+        let uri = format!(
+            "sqlite://{}",
+            std::env::current_dir().unwrap().join(path).display()
+        );
+        SqlClientEnum::new(&DatabaseSettings::from_uri(&uri, 1))
+            .await
+            .unwrap()
+    }
+
+    /// An artifact key as returned to clients, wrapped with the card uid
+    fn client_key(uid: &str, artifact_key: &[u8]) -> ArtifactKey {
+        ArtifactKey {
+            uid: uid.to_string(),
+            space: "space".to_string(),
+            registry_type: RegistryType::Model,
+            encrypted_key: encrypted_key(&uid_to_byte_key(uid).unwrap(), artifact_key).unwrap(),
+            storage_key: format!("opsml_model_registry/space/{uid}"),
+            has_manifest: true,
+        }
+    }
+
+    async fn rotate_all(
+        client: &SqlClientEnum,
+        old: &dyn KeyProvider,
+        new: &dyn KeyProvider,
+        dry_run: bool,
+    ) -> RotationCounts {
+        let mut counts = RotationCounts::default();
+        let (mut uid, mut registry_type) = (String::new(), String::new());
+
+        loop {
+            let batch =
+                rotate_artifact_key_batch(client, old, new, &uid, &registry_type, 2, dry_run)
+                    .await
+                    .unwrap();
+            counts.merge(&batch.counts);
+
+            match batch.last_key {
+                Some(last_key) => (uid, registry_type) = last_key,
+                None => return counts,
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_key_rotation() {
+        let path = "key_rotation.db";
+        let client = sqlite_client(path).await;
+
+        // keys move from the encryption secret to a key file
+        let old_provider = LocalKeyProvider::new(&generate_key().unwrap());
+        let new_provider = FileKeyProvider::new(
+            HashMap::from([("kek-1".to_string(), generate_key().unwrap())]),
+            None,
+        )
+        .unwrap();
+        let artifact_key = generate_key().unwrap();
+
+        // three keys wrapped by the old provider and one stored before keys were sealed
+        let uids = (0..4).map(|_| create_uuid7()).collect::<Vec<_>>();
+        for uid in &uids[..3] {
+            let key = client_key(uid, &artifact_key)
+                .seal(&old_provider)
+                .await
+                .unwrap();
+            client.insert_artifact_key(&key).await.unwrap();
+        }
+        client
+            .insert_artifact_key(&client_key(&uids[3], &artifact_key))
+            .await
+            .unwrap();
+
+        let webhook_secret = Webhook::encrypt_secret(&old_provider, "hook", b"secret")
+            .await
+            .unwrap();
+        let mut webhook = Webhook::new("http://localhost", None, vec![], webhook_secret, "user");
+        webhook.uid = "hook".to_string();
+        client.insert_webhook(&webhook).await.unwrap();
+
+        // a dry run counts the keys without updating them
+        let counts = rotate_all(&client, &old_provider, &new_provider, true).await;
+        assert_eq!((counts.rotated, counts.upgraded), (3, 1));
+        let key = client
+            .get_artifact_key(&uids[0], &RegistryType::Model.to_string())
+            .await
+            .unwrap();
+        assert!(key.is_sealed_with(&old_provider).await);
+
+        let counts = rotate_all(&client, &old_provider, &new_provider, false).await;
+        assert_eq!((counts.rotated, counts.upgraded), (3, 1));
+
+        let counts = rotate_webhook_secrets(&client, &old_provider, &new_provider, false)
+            .await
+            .unwrap();
+        assert_eq!(counts.rotated, 1);
+
+        for uid in &uids {
+            let key = client
+                .get_artifact_key(uid, &RegistryType::Model.to_string())
+                .await
+                .unwrap();
+
+            assert!(key.is_sealed_with(&new_provider).await);
+            assert_eq!(
+                key.unseal(&new_provider)
+                    .await
+                    .unwrap()
+                    .get_decrypt_key()
+                    .unwrap(),
+                artifact_key
+            );
+        }
+
+        let webhook = client.get_webhook("hook").await.unwrap().unwrap();
+        assert_eq!(
+            webhook.decrypt_secret(&new_provider).await.unwrap(),
+            b"secret"
+        );
+
+        // running the rotation again, e.g. after an interruption, changes nothing
+        let counts = rotate_all(&client, &old_provider, &new_provider, false).await;
+        assert_eq!(counts.already_rotated, 4);
+
+        // keys that neither provider can decrypt stop the rotation
+        let other_provider = LocalKeyProvider::new(&generate_key().unwrap());
+        let key = client_key(&create_uuid7(), &artifact_key);
+        let key = ArtifactKey {
+            encrypted_key: key.seal(&other_provider).await.unwrap().encrypted_key,
+            ..key
+        };
+        assert!(rewrap_artifact_key(&key, &old_provider, &new_provider)
+            .await
+            .is_err());
+
+        let _ = std::fs::remove_file(path);
+    }
+}
@@ -1,17 +1,21 @@
 use crate::error::SqlError;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, SecondsFormat, SubsecRound, Utc};
+use opsml_crypt::{KeyContext, KeyProvider};
 use opsml_semver::error::VersionError;
 use opsml_types::cards::{CardTable, ParameterValue};
 use opsml_types::contracts::{
-    AuditCardClientRecord, CardEntry, CardRecord, DataCardClientRecord, ExperimentCardClientRecord,
-    ModelCardClientRecord, PromptCardClientRecord, ServiceCardClientRecord,
+    AuditCardClientRecord, AuditEvent, AuditEventClientRecord, CardEntry, CardRecord,
+    CardSearchResult, DataCardClientRecord, DeletedCard, ExperimentCardClientRecord, LineageNode,
+    ModelCardClientRecord, PromptCardClientRecord, ServiceCardClientRecord, SpaceSigningKey,
+    VersionAlias, VersionAliasHistory,
 };
-use opsml_types::{CommonKwargs, DataType, ModelType, RegistryType};
+use opsml_types::{CardStage, CommonKwargs, DataType, ModelType, RegistryType};
 use opsml_utils::create_uuid7;
 use opsml_utils::utils::get_utc_datetime;
 use semver::{BuildMetadata, Prerelease, Version};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use sha2::{Digest, Sha256};
 use sqlx::{prelude::FromRow, types::Json};
 use std::collections::HashMap;
 use std::env;
@@ -36,6 +40,7 @@ impl MetricRecord {
         value: f64,
         step: Option<i32>,
         timestamp: Option<i64>,
+        created_at: Option<DateTime<Utc>>,
     ) -> Self {
         MetricRecord {
             experiment_uid,
@@ -43,7 +48,7 @@ impl MetricRecord {
             value,
             step,
             timestamp,
-            created_at: None,
+            created_at,
             idx: None,
         }
     }
@@ -297,6 +302,7 @@ pub struct ModelCardRecord {
     pub task_type: String,
     pub opsml_version: String,
     pub username: String,
+    pub stage: String,
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -342,6 +348,7 @@ impl ModelCardRecord {
             task_type,
             opsml_version,
             username,
+            stage: CardStage::default().to_string(),
         }
     }
 
@@ -380,6 +387,7 @@ impl ModelCardRecord {
             task_type: client_card.task_type,
             opsml_version: client_card.opsml_version,
             username: client_card.username,
+            stage: client_card.stage.to_string(),
         })
     }
 }
@@ -408,6 +416,7 @@ impl Default for ModelCardRecord {
             task_type: CommonKwargs::Undefined.to_string(),
             opsml_version: opsml_version::version(),
             username: CommonKwargs::Undefined.to_string(),
+            stage: CardStage::default().to_string(),
         }
     }
 }
@@ -791,6 +800,7 @@ pub struct ServiceCardRecord {
     pub cards: Json<Vec<CardEntry>>,
     pub opsml_version: String,
     pub username: String,
+    pub stage: String,
 }
 
 impl ServiceCardRecord {
@@ -821,6 +831,7 @@ impl ServiceCardRecord {
             cards: Json(cards),
             opsml_version,
             username,
+            stage: CardStage::default().to_string(),
         }
     }
 
@@ -852,6 +863,7 @@ impl ServiceCardRecord {
             cards: Json(client_card.cards),
             opsml_version: client_card.opsml_version,
             username: client_card.username,
+            stage: client_card.stage.to_string(),
         })
     }
 }
@@ -873,10 +885,229 @@ impl Default for ServiceCardRecord {
             cards: Json(Vec::new()),
             opsml_version: opsml_version::version(),
             username: CommonKwargs::Undefined.to_string(),
+            stage: CardStage::default().to_string(),
+        }
+    }
+}
+
+/// Stage of a model or service card version
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CardStageRecord {
+    pub uid: String,
+    pub space: String,
+    pub name: String,
+    pub version: String,
+    pub stage: String,
+}
+
+/// A soft-deleted card version in the trash
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DeletedCardRecord {
+    pub uid: String,
+    pub space: String,
+    pub name: String,
+    pub version: String,
+    pub deleted_at: DateTime<Utc>,
+    pub deleted_by: String,
+}
+
+impl DeletedCardRecord {
+    pub fn into_deleted_card(self, registry_type: &RegistryType) -> DeletedCard {
+        DeletedCard {
+            uid: self.uid,
+            space: self.space,
+            name: self.name,
+            version: self.version,
+            registry_type: registry_type.clone(),
+            deleted_at: self.deleted_at,
+            deleted_by: self.deleted_by,
+        }
+    }
+}
+
+/// A card version in the full-text search index. README content is indexed per card name
+/// and is kept when a new version is indexed
+#[derive(Debug, Clone)]
+pub struct CardSearchEntry {
+    pub uid: String,
+    pub registry_type: String,
+    pub space: String,
+    pub name: String,
+    pub version: String,
+    /// Tags as a JSON array
+    pub tags: String,
+    /// Searchable metadata fields (username, interface, data and model types) separated by spaces
+    pub metadata: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl CardSearchEntry {
+    pub fn from_server_card(card: &ServerCard) -> Result<Self, SqlError> {
+        let (tags, metadata): (&[String], Vec<&str>) = match card {
+            ServerCard::Data(card) => (
+                &card.tags,
+                vec![&card.username, &card.interface_type, &card.data_type],
+            ),
+            ServerCard::Model(card) => (
+                &card.tags,
+                vec![
+                    &card.username,
+                    &card.interface_type,
+                    &card.data_type,
+                    &card.model_type,
+                    &card.task_type,
+                ],
+            ),
+            ServerCard::Experiment(card) => (&card.tags, vec![&card.username]),
+            ServerCard::Audit(card) => (&card.tags, vec![&card.username]),
+            ServerCard::Prompt(card) => (&card.tags, vec![&card.username]),
+            ServerCard::Service(card) => {
+                let mut metadata = vec![card.username.as_str()];
+                metadata.extend(card.cards.iter().map(|entry| entry.alias.as_str()));
+                (&[], metadata)
+            }
+        };
+
+        Ok(CardSearchEntry {
+            uid: card.uid().to_string(),
+            registry_type: card.registry_type(),
+            space: card.space(),
+            name: card.name(),
+            version: card.version(),
+            tags: serde_json::to_string(tags)?,
+            metadata: metadata.join(" "),
+            created_at: card.created_at(),
+        })
+    }
+}
+
+/// A search match as returned by the backend
+#[derive(Debug, Clone, FromRow)]
+pub struct CardSearchRecord {
+    pub uid: String,
+    pub registry_type: String,
+    pub space: String,
+    pub name: String,
+    pub version: String,
+    pub tags: String,
+    pub created_at: DateTime<Utc>,
+    pub score: f64,
+}
+
+impl CardSearchRecord {
+    pub fn into_search_result(self) -> Result<CardSearchResult, SqlError> {
+        Ok(CardSearchResult {
+            uid: self.uid,
+            registry_type: RegistryType::from_string(&self.registry_type)?,
+            space: self.space,
+            name: self.name,
+            version: self.version,
+            tags: serde_json::from_str(&self.tags)?,
+            created_at: self.created_at,
+            score: self.score,
+        })
+    }
+}
+
+/// A lineage edge declared by a card record. `source_uid` is upstream of `target_uid`
+#[derive(Debug, Clone, PartialEq, FromRow)]
+pub struct CardLineageEdge {
+    pub source_uid: String,
+    pub target_uid: String,
+}
+
+impl CardLineageEdge {
+    fn new(source_uid: &str, target_uid: &str) -> Self {
+        CardLineageEdge {
+            source_uid: source_uid.to_string(),
+            target_uid: target_uid.to_string(),
+        }
+    }
+
+    /// Edges for every card referenced by a card record. Cards registered during an experiment
+    /// are downstream of it, as are models trained on a datacard, services built from cards
+    /// and audits covering cards
+    pub fn from_server_card(card: &ServerCard) -> Vec<Self> {
+        let uid = card.uid();
+        let upstream = |uids: &[&Option<String>]| {
+            uids.iter()
+                .filter_map(|source| source.as_deref())
+                .filter(|source| !source.is_empty())
+                .map(|source| Self::new(source, uid))
+                .collect::<Vec<_>>()
+        };
+        let audit = |auditcard_uid: &Option<String>| {
+            auditcard_uid
+                .as_deref()
+                .filter(|target| !target.is_empty())
+                .map(|target| Self::new(uid, target))
+        };
+
+        match card {
+            ServerCard::Data(card) => {
+                let mut edges = upstream(&[&card.experimentcard_uid]);
+                edges.extend(audit(&card.auditcard_uid));
+                edges
+            }
+            ServerCard::Model(card) => {
+                let mut edges = upstream(&[&card.datacard_uid, &card.experimentcard_uid]);
+                edges.extend(audit(&card.auditcard_uid));
+                edges
+            }
+            ServerCard::Prompt(card) => {
+                let mut edges = upstream(&[&card.experimentcard_uid]);
+                edges.extend(audit(&card.auditcard_uid));
+                edges
+            }
+            ServerCard::Experiment(card) => card
+                .datacard_uids
+                .iter()
+                .chain(card.modelcard_uids.iter())
+                .chain(card.promptcard_uids.iter())
+                .chain(card.service_card_uids.iter())
+                .chain(card.experimentcard_uids.iter())
+                .map(|target| Self::new(uid, target))
+                .collect(),
+            ServerCard::Audit(card) => card
+                .datacard_uids
+                .iter()
+                .chain(card.modelcard_uids.iter())
+                .chain(card.experimentcard_uids.iter())
+                .map(|source| Self::new(source, uid))
+                .collect(),
+            ServerCard::Service(card) => card
+                .cards
+                .iter()
+                .map(|entry| Self::new(&entry.uid, uid))
+                .collect(),
         }
     }
 }
 
+/// A card reached while walking the lineage graph
+#[derive(Debug, Clone, FromRow)]
+pub struct CardLineageNodeRecord {
+    pub uid: String,
+    pub registry_type: String,
+    pub space: String,
+    pub name: String,
+    pub version: String,
+    pub deleted: bool,
+}
+
+impl CardLineageNodeRecord {
+    pub fn into_lineage_node(self) -> Result<LineageNode, SqlError> {
+        Ok(LineageNode {
+            uid: self.uid,
+            registry_type: RegistryType::from_string(&self.registry_type)?,
+            space: self.space,
+            name: self.name,
+            version: self.version,
+            deleted: self.deleted,
+        })
+    }
+}
+
 // create enum that takes vec of cards
 // TODO: There should also be a client side enum that matches this (don't want to install opsml_sql on client)
 #[derive(Debug, Serialize, Deserialize)]
@@ -890,6 +1121,50 @@ pub enum CardResults {
 }
 
 impl CardResults {
+    /// Space and name of the first card in the results
+    pub fn first_space_name(&self) -> Option<(String, String)> {
+        match self {
+            CardResults::Data(cards) => cards.first().map(|c| (c.space.clone(), c.name.clone())),
+            CardResults::Model(cards) => cards.first().map(|c| (c.space.clone(), c.name.clone())),
+            CardResults::Experiment(cards) => {
+                cards.first().map(|c| (c.space.clone(), c.name.clone()))
+            }
+            CardResults::Audit(cards) => cards.first().map(|c| (c.space.clone(), c.name.clone())),
+            CardResults::Prompt(cards) => cards.first().map(|c| (c.space.clone(), c.name.clone())),
+            CardResults::Service(cards) => cards.first().map(|c| (c.space.clone(), c.name.clone())),
+        }
+    }
+
+    /// Uid of the card with exactly this version
+    pub fn find_version(&self, version: &str) -> Option<String> {
+        match self {
+            CardResults::Data(cards) => cards
+                .iter()
+                .find(|c| c.version == version)
+                .map(|c| c.uid.clone()),
+            CardResults::Model(cards) => cards
+                .iter()
+                .find(|c| c.version == version)
+                .map(|c| c.uid.clone()),
+            CardResults::Experiment(cards) => cards
+                .iter()
+                .find(|c| c.version == version)
+                .map(|c| c.uid.clone()),
+            CardResults::Audit(cards) => cards
+                .iter()
+                .find(|c| c.version == version)
+                .map(|c| c.uid.clone()),
+            CardResults::Prompt(cards) => cards
+                .iter()
+                .find(|c| c.version == version)
+                .map(|c| c.uid.clone()),
+            CardResults::Service(cards) => cards
+                .iter()
+                .find(|c| c.version == version)
+                .map(|c| c.uid.clone()),
+        }
+    }
+
     pub fn len(&self) -> usize {
         match self {
             CardResults::Data(cards) => cards.len(),
@@ -1171,6 +1446,38 @@ impl User {
         }
     }
 
+    /// Convenience constructor for creating a non-human service account.
+    /// Service accounts cannot log in with a password and authenticate with API tokens only.
+    pub fn new_service_account(
+        username: &str,
+        email: Option<String>,
+        permissions: Option<Vec<String>>,
+        group_permissions: Option<Vec<String>>,
+    ) -> Self {
+        let created_at = get_utc_datetime();
+
+        User {
+            id: None,
+            created_at,
+            active: true,
+            username: username.to_string(),
+            password_hash: "[redacted]".to_string(),
+            hashed_recovery_codes: Vec::new(),
+            permissions: permissions.unwrap_or(vec!["read:all".to_string()]),
+            group_permissions: group_permissions.unwrap_or(vec!["user".to_string()]),
+            favorite_spaces: Vec::new(),
+            role: "service".to_string(),
+            refresh_token: None,
+            email: email.unwrap_or_else(|| format!("{username}@service-account")),
+            updated_at: created_at,
+            authentication_type: "service".to_string(),
+        }
+    }
+
+    pub fn is_service_account(&self) -> bool {
+        self.authentication_type == "service"
+    }
+
     pub fn serialize(&self) -> String {
         // convert to HashMap<String, Value>
         // redact password_hash and permissions
@@ -1223,3 +1530,445 @@ impl std::fmt::Debug for User {
             .finish()
     }
 }
+
+/// Hashed API token belonging to a user or service account.
+/// The raw token is only returned once at creation and is never stored.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ApiToken {
+    pub id: Option<i32>,
+    pub created_at: DateTime<Utc>,
+    pub username: String,
+    pub name: String,
+    pub hashed_token: String,
+    pub scopes: Json<Vec<String>>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub revoked: bool,
+}
+
+impl ApiToken {
+    pub fn new(
+        username: &str,
+        name: &str,
+        hashed_token: String,
+        scopes: Vec<String>,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Self {
+        ApiToken {
+            id: None,
+            created_at: get_utc_datetime(),
+            username: username.to_string(),
+            name: name.to_string(),
+            hashed_token,
+            scopes: Json(scopes),
+            expires_at,
+            revoked: false,
+        }
+    }
+
+    /// A token is usable if it has not been revoked and has not expired
+    pub fn is_active(&self) -> bool {
+        if self.revoked {
+            return false;
+        }
+
+        match self.expires_at {
+            Some(expires_at) => expires_at > get_utc_datetime(),
+            None => true,
+        }
+    }
+}
+
+/// Key provider context purpose of webhook signing secrets
+const WEBHOOK_SECRET_PURPOSE: &str = "webhook";
+
+/// HTTP endpoint that receives card lifecycle events.
+/// The signing secret is stored encrypted with the server encryption key.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Webhook {
+    pub uid: String,
+    pub created_at: DateTime<Utc>,
+    pub url: String,
+    /// Only deliver events for this space. None delivers events for all spaces
+    pub space: Option<String>,
+    /// Event types to deliver. An empty list delivers all event types
+    pub event_types: Json<Vec<String>>,
+    pub encrypted_secret: Vec<u8>,
+    pub active: bool,
+    pub created_by: String,
+}
+
+impl Webhook {
+    pub fn new(
+        url: &str,
+        space: Option<String>,
+        event_types: Vec<String>,
+        encrypted_secret: Vec<u8>,
+        created_by: &str,
+    ) -> Self {
+        Webhook {
+            uid: create_uuid7(),
+            created_at: get_utc_datetime(),
+            url: url.to_string(),
+            space,
+            event_types: Json(event_types),
+            encrypted_secret,
+            active: true,
+            created_by: created_by.to_string(),
+        }
+    }
+
+    /// Encrypts a webhook signing secret with the server key provider.
+    /// The webhook uid is bound to the encrypted secret
+    pub async fn encrypt_secret(
+        provider: &dyn KeyProvider,
+        uid: &str,
+        secret: &[u8],
+    ) -> Result<Vec<u8>, SqlError> {
+        let context = KeyContext {
+            purpose: WEBHOOK_SECRET_PURPOSE,
+            id: uid,
+        };
+
+        Ok(provider.wrap(context, secret).await?)
+    }
+
+    /// Decrypts the stored signing secret
+    pub async fn decrypt_secret(&self, provider: &dyn KeyProvider) -> Result<Vec<u8>, SqlError> {
+        let context = KeyContext {
+            purpose: WEBHOOK_SECRET_PURPOSE,
+            id: &self.uid,
+        };
+
+        Ok(provider.unwrap(context, &self.encrypted_secret).await?)
+    }
+
+    /// Whether an event in the given space and of the given type should be delivered
+    pub fn matches(&self, space: &str, event_type: &str) -> bool {
+        if !self.active {
+            return false;
+        }
+
+        let space_match = self.space.as_deref().is_none_or(|s| s == space);
+        let event_match =
+            self.event_types.is_empty() || self.event_types.iter().any(|e| e == event_type);
+
+        space_match && event_match
+    }
+}
+
+pub const WEBHOOK_DELIVERY_PENDING: &str = "pending";
+pub const WEBHOOK_DELIVERY_SUCCESS: &str = "success";
+pub const WEBHOOK_DELIVERY_FAILED: &str = "failed";
+
+/// A single event delivery to a webhook, including retries
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct WebhookDelivery {
+    pub uid: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub webhook_uid: String,
+    pub event_type: String,
+    /// JSON encoded event body sent to the endpoint
+    pub payload: String,
+    pub status: String,
+    pub attempts: i32,
+    pub response_status: Option<i32>,
+    pub error_message: Option<String>,
+}
+
+impl WebhookDelivery {
+    pub fn new(webhook_uid: &str, event_type: &str, payload: String) -> Self {
+        let now = get_utc_datetime();
+        WebhookDelivery {
+            uid: create_uuid7(),
+            created_at: now,
+            updated_at: now,
+            webhook_uid: webhook_uid.to_string(),
+            event_type: event_type.to_string(),
+            payload,
+            status: WEBHOOK_DELIVERY_PENDING.to_string(),
+            attempts: 0,
+            response_status: None,
+            error_message: None,
+        }
+    }
+}
+
+/// Server event stored in `opsml_event_outbox`.
+/// Written in the same transaction as the change that produced it and delivered at least once.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct OutboxEvent {
+    pub id: i64,
+    pub created_at: DateTime<Utc>,
+    pub event_type: String,
+    /// JSON encoded event
+    pub payload: String,
+}
+
+impl OutboxEvent {
+    pub fn new(event_type: &str, payload: String) -> Self {
+        OutboxEvent {
+            id: 0,
+            created_at: get_utc_datetime(),
+            event_type: event_type.to_string(),
+            payload,
+        }
+    }
+}
+
+/// Audit event row as stored in `opsml_audit_event`.
+///
+/// Each row carries the hash of the previous row (`prev_hash`) and its own hash, which is computed
+/// over `prev_hash`, the row's position in the chain (`seq`) and the row contents. Modifying,
+/// deleting or reordering rows breaks the chain. The latest `seq` and hash are also kept in
+/// `opsml_audit_chain_head`, so deleting rows from the end of the chain is detected as well.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AuditEventRecord {
+    pub id: i32,
+    pub created_at: DateTime<Utc>,
+    pub username: String,
+    pub client_ip: String,
+    pub user_agent: Option<String>,
+    pub operation: String,
+    pub resource_type: String,
+    pub resource_id: String,
+    pub access_location: Option<String>,
+    pub status: String,
+    pub error_message: Option<String>,
+    pub metadata: Option<String>,
+    pub registry_type: Option<String>,
+    pub route: Option<String>,
+    pub prev_hash: Option<String>,
+    pub hash: Option<String>,
+    pub space: Option<String>,
+    /// Position in the hash chain. Empty for rows written before sequence numbers were added
+    #[serde(default)]
+    pub seq: Option<i64>,
+}
+
+impl AuditEventRecord {
+    /// Creates a new chained record from an audit event and the head of the chain.
+    /// `created_at` is truncated to seconds so the hash is stable across all backends.
+    pub fn from_event(event: AuditEvent, prev_hash: String, seq: i64) -> Self {
+        let mut record = AuditEventRecord {
+            id: 0,
+            created_at: get_utc_datetime().trunc_subsecs(0),
+            username: event.username,
+            client_ip: event.client_ip,
+            user_agent: Some(event.user_agent),
+            operation: event.operation.to_string(),
+            resource_type: event.resource_type.to_string(),
+            resource_id: event.resource_id,
+            access_location: event.access_location,
+            status: event.status.to_string(),
+            error_message: event.error_message,
+            metadata: Some(event.metadata),
+            registry_type: event.registry_type.map(|r| r.to_string()),
+            route: Some(event.route),
+            prev_hash: Some(prev_hash),
+            hash: None,
+            space: event.space,
+            seq: Some(seq),
+        };
+
+        record.hash = Some(record.compute_hash());
+        record
+    }
+
+    /// Computes the SHA-256 hash of the record contents chained to `prev_hash`.
+    /// The row id is excluded as it is assigned by the database, `seq` is used instead.
+    pub fn compute_hash(&self) -> String {
+        let created_at = self
+            .created_at
+            .trunc_subsecs(0)
+            .to_rfc3339_opts(SecondsFormat::Secs, true);
+
+        let mut fields = vec![
+            self.prev_hash.as_deref().unwrap_or_default(),
+            &created_at,
+            &self.username,
+            &self.client_ip,
+            self.user_agent.as_deref().unwrap_or_default(),
+            &self.operation,
+            &self.resource_type,
+            &self.resource_id,
+            self.access_location.as_deref().unwrap_or_default(),
+            &self.status,
+            self.error_message.as_deref().unwrap_or_default(),
+            self.metadata.as_deref().unwrap_or_default(),
+            self.registry_type.as_deref().unwrap_or_default(),
+            self.route.as_deref().unwrap_or_default(),
+        ];
+
+        // space and seq were added after chaining, only hash them when present so earlier rows
+        // still verify. Rows with a seq always hash space so the field positions are fixed
+        let seq = self.seq.map(|seq| seq.to_string());
+        match &seq {
+            Some(seq) => {
+                fields.push(self.space.as_deref().unwrap_or_default());
+                fields.push(seq);
+            }
+            None => {
+                if let Some(space) = &self.space {
+                    fields.push(space);
+                }
+            }
+        }
+
+        // unit separator keeps field boundaries unambiguous
+        Sha256::digest(fields.join("\u{1f}").as_bytes())
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect()
+    }
+}
+
+impl From<AuditEventRecord> for AuditEventClientRecord {
+    fn from(record: AuditEventRecord) -> Self {
+        AuditEventClientRecord {
+            id: record.id,
+            created_at: record.created_at,
+            username: record.username,
+            client_ip: record.client_ip,
+            user_agent: record.user_agent,
+            operation: record.operation,
+            resource_type: record.resource_type,
+            resource_id: record.resource_id,
+            access_location: record.access_location,
+            status: record.status,
+            error_message: record.error_message,
+            metadata: record.metadata,
+            registry_type: record.registry_type,
+            route: record.route,
+            space: record.space,
+        }
+    }
+}
+
+/// Latest position of the audit hash chain, stored in `opsml_audit_chain_head`
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AuditChainHeadRecord {
+    pub seq: i64,
+    pub hash: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A version alias row in `opsml_version_alias`
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct VersionAliasRecord {
+    pub registry_type: String,
+    pub space: String,
+    pub name: String,
+    pub alias: String,
+    pub uid: String,
+    pub version: String,
+    pub updated_at: DateTime<Utc>,
+    pub updated_by: String,
+}
+
+impl VersionAliasRecord {
+    pub fn new(
+        registry_type: &RegistryType,
+        space: &str,
+        name: &str,
+        alias: &str,
+        uid: &str,
+        version: &str,
+        updated_by: &str,
+    ) -> Self {
+        VersionAliasRecord {
+            registry_type: registry_type.to_string(),
+            space: space.to_string(),
+            name: name.to_string(),
+            alias: alias.to_string(),
+            uid: uid.to_string(),
+            version: version.to_string(),
+            updated_at: get_utc_datetime(),
+            updated_by: updated_by.to_string(),
+        }
+    }
+}
+
+impl TryFrom<VersionAliasRecord> for VersionAlias {
+    type Error = SqlError;
+
+    fn try_from(record: VersionAliasRecord) -> Result<Self, Self::Error> {
+        Ok(VersionAlias {
+            registry_type: RegistryType::from_string(&record.registry_type)?,
+            space: record.space,
+            name: record.name,
+            alias: record.alias,
+            uid: record.uid,
+            version: record.version,
+            updated_at: record.updated_at,
+            updated_by: record.updated_by,
+        })
+    }
+}
+
+/// A row in `opsml_version_alias_history`. `uid` and `version` are None when the alias was deleted
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct VersionAliasHistoryRecord {
+    pub id: i64,
+    pub created_at: DateTime<Utc>,
+    pub registry_type: String,
+    pub space: String,
+    pub name: String,
+    pub alias: String,
+    pub uid: Option<String>,
+    pub version: Option<String>,
+    pub previous_version: Option<String>,
+    pub username: String,
+}
+
+impl From<VersionAliasHistoryRecord> for VersionAliasHistory {
+    fn from(record: VersionAliasHistoryRecord) -> Self {
+        VersionAliasHistory {
+            id: record.id,
+            created_at: record.created_at,
+            alias: record.alias,
+            uid: record.uid,
+            version: record.version,
+            previous_version: record.previous_version,
+            username: record.username,
+        }
+    }
+}
+
+/// Public key of a space signing key in `opsml_signing_key`
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SigningKeyRecord {
+    pub key_id: String,
+    pub space: String,
+    /// Base64 encoded raw Ed25519 public key
+    pub public_key: String,
+    pub created_at: DateTime<Utc>,
+    pub created_by: String,
+    pub active: bool,
+}
+
+impl SigningKeyRecord {
+    pub fn new(key_id: &str, space: &str, public_key: &str, created_by: &str) -> Self {
+        SigningKeyRecord {
+            key_id: key_id.to_string(),
+            space: space.to_string(),
+            public_key: public_key.to_string(),
+            created_at: get_utc_datetime(),
+            created_by: created_by.to_string(),
+            active: true,
+        }
+    }
+}
+
+impl From<SigningKeyRecord> for SpaceSigningKey {
+    fn from(record: SigningKeyRecord) -> Self {
+        SpaceSigningKey {
+            key_id: record.key_id,
+            space: record.space,
+            public_key: record.public_key,
+            created_at: record.created_at,
+            created_by: record.created_by,
+            active: record.active,
+        }
+    }
+}
@@ -8,6 +8,7 @@ use opsml_types::contracts::{
 };
 
 use super::ServiceCardRecord;
+use opsml_types::CardStage;
 
 pub fn convert_datacard(record: DataCardRecord) -> CardRecord {
     let card = DataCardClientRecord {
@@ -47,6 +48,7 @@ pub fn convert_modelcard(record: ModelCardRecord) -> CardRecord {
         task_type: record.task_type,
         username: record.username,
         opsml_version: record.opsml_version,
+        stage: CardStage::from_string(&record.stage).unwrap_or_default(),
     };
 
     CardRecord::Model(card)
@@ -122,6 +124,7 @@ pub fn convert_servicecard(record: ServiceCardRecord) -> CardRecord {
         username: record.username,
         cards: record.cards.0,
         opsml_version: record.opsml_version,
+        stage: CardStage::from_string(&record.stage).unwrap_or_default(),
     };
 
     CardRecord::Service(card)
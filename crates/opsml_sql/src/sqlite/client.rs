@@ -1,22 +1,32 @@
-use crate::base::SqlClient;
+use crate::base::{metric_page_filters, SqlClient};
 
 use crate::error::SqlError;
 use crate::schemas::schema::{
-    AuditCardRecord, CardResults, CardSummary, DataCardRecord, ExperimentCardRecord,
-    HardwareMetricsRecord, MetricRecord, ModelCardRecord, ParameterRecord, PromptCardRecord,
-    QueryStats, ServerCard, ServiceCardRecord, SqlSpaceRecord, User, VersionResult, VersionSummary,
+    ApiToken, AuditCardRecord, AuditChainHeadRecord, AuditEventRecord, CardLineageEdge,
+    CardLineageNodeRecord, CardResults, CardSearchEntry, CardSearchRecord, CardStageRecord,
+    CardSummary, DataCardRecord, DeletedCardRecord, ExperimentCardRecord, HardwareMetricsRecord,
+    MetricRecord, ModelCardRecord, OutboxEvent, ParameterRecord, PromptCardRecord, QueryStats,
+    ServerCard, ServiceCardRecord, SigningKeyRecord, SqlSpaceRecord, User,
+    VersionAliasHistoryRecord, VersionAliasRecord, VersionResult, VersionSummary, Webhook,
+    WebhookDelivery, WEBHOOK_DELIVERY_PENDING,
 };
+use crate::stage::{restored_stage, stage_updates, verify_stage_transition};
 
 use crate::sqlite::helper::SqliteQueryHelper;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use opsml_semver::VersionValidator;
 use opsml_settings::config::DatabaseSettings;
-use opsml_types::contracts::{ArtifactKey, AuditEvent, SpaceNameEvent, SpaceRecord, SpaceStats};
+use opsml_types::contracts::{
+    search_terms, ArtifactKey, AuditEvent, AuditEventQueryArgs, CardSearchQuery, CardStageResponse,
+    MetricQuery, SpaceNameEvent, SpaceRecord, SpaceStats,
+};
 use opsml_types::{cards::CardTable, contracts::CardQueryArgs, RegistryType};
+use opsml_utils::utils::get_utc_datetime;
 use semver::Version;
 use sqlx::{
     sqlite::{SqlitePoolOptions, SqliteRow},
-    FromRow, Pool, Row, Sqlite,
+    FromRow, Pool, Row, Sqlite, Transaction,
 };
 use tracing::{debug, error, info, instrument};
 
@@ -69,6 +79,87 @@ pub struct SqliteClient {
     pub pool: Pool<Sqlite>,
 }
 
+impl SqliteClient {
+    /// Writes events to the event outbox as part of an open transaction
+    async fn insert_outbox_events_tx(
+        tx: &mut Transaction<'_, Sqlite>,
+        events: &[OutboxEvent],
+    ) -> Result<(), SqlError> {
+        let query = SqliteQueryHelper::get_outbox_event_insert_query();
+
+        for event in events {
+            sqlx::query(&query)
+                .bind(event.created_at)
+                .bind(&event.event_type)
+                .bind(&event.payload)
+                .execute(&mut **tx)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Adds a card to the full-text search index as part of an open transaction.
+    /// The README indexed for other versions of the card is carried over
+    async fn index_card_tx(
+        tx: &mut Transaction<'_, Sqlite>,
+        card: &ServerCard,
+    ) -> Result<(), SqlError> {
+        let entry = CardSearchEntry::from_server_card(card)?;
+
+        let readme: Option<String> =
+            sqlx::query_scalar(&SqliteQueryHelper::get_search_readme_query())
+                .bind(&entry.registry_type)
+                .bind(&entry.space)
+                .bind(&entry.name)
+                .fetch_optional(&mut **tx)
+                .await?;
+
+        // FTS5 tables do not support upserts
+        sqlx::query(&SqliteQueryHelper::get_card_search_entry_delete_query())
+            .bind(&entry.uid)
+            .execute(&mut **tx)
+            .await?;
+
+        sqlx::query(&SqliteQueryHelper::get_card_search_entry_insert_query())
+            .bind(&entry.uid)
+            .bind(&entry.registry_type)
+            .bind(&entry.version)
+            .bind(entry.created_at)
+            .bind(&entry.name)
+            .bind(&entry.space)
+            .bind(&entry.tags)
+            .bind(&entry.metadata)
+            .bind(readme.unwrap_or_default())
+            .execute(&mut **tx)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Replaces the lineage edges declared by a card as part of an open transaction
+    async fn index_lineage_tx(
+        tx: &mut Transaction<'_, Sqlite>,
+        card: &ServerCard,
+    ) -> Result<(), SqlError> {
+        sqlx::query(&SqliteQueryHelper::get_lineage_edges_delete_query())
+            .bind(card.uid())
+            .execute(&mut **tx)
+            .await?;
+
+        for edge in CardLineageEdge::from_server_card(card) {
+            sqlx::query(&SqliteQueryHelper::get_lineage_edge_insert_query())
+                .bind(card.uid())
+                .bind(&edge.source_uid)
+                .bind(&edge.target_uid)
+                .execute(&mut **tx)
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
 #[async_trait]
 impl SqlClient for SqliteClient {
     async fn new(settings: &DatabaseSettings) -> Result<Self, SqlError> {
@@ -278,7 +369,14 @@ impl SqlClient for SqliteClient {
         }
     }
 
-    async fn insert_card(&self, table: &CardTable, card: &ServerCard) -> Result<(), SqlError> {
+    async fn insert_card(
+        &self,
+        table: &CardTable,
+        card: &ServerCard,
+        events: &[OutboxEvent],
+    ) -> Result<(), SqlError> {
+        let mut tx = self.pool.begin_with("BEGIN IMMEDIATE").await?;
+
         match table {
             CardTable::Data => match card {
                 ServerCard::Data(record) => {
@@ -301,9 +399,8 @@ impl SqlClient for SqliteClient {
                         .bind(&record.build_tag)
                         .bind(&record.username)
                         .bind(&record.opsml_version)
-                        .execute(&self.pool)
+                        .execute(&mut *tx)
                         .await?;
-                    Ok(())
                 }
                 _ => {
                     return Err(SqlError::InvalidCardType);
@@ -333,9 +430,8 @@ impl SqlClient for SqliteClient {
                         .bind(&record.build_tag)
                         .bind(&record.username)
                         .bind(&record.opsml_version)
-                        .execute(&self.pool)
+                        .execute(&mut *tx)
                         .await?;
-                    Ok(())
                 }
                 _ => {
                     return Err(SqlError::InvalidCardType);
@@ -363,9 +459,9 @@ impl SqlClient for SqliteClient {
                         .bind(&record.build_tag)
                         .bind(&record.username)
                         .bind(&record.opsml_version)
-                        .execute(&self.pool)
+                        .bind(record.created_at)
+                        .execute(&mut *tx)
                         .await?;
-                    Ok(())
                 }
                 _ => {
                     return Err(SqlError::InvalidCardType);
@@ -392,9 +488,8 @@ impl SqlClient for SqliteClient {
                         .bind(&record.build_tag)
                         .bind(&record.username)
                         .bind(&record.opsml_version)
-                        .execute(&self.pool)
+                        .execute(&mut *tx)
                         .await?;
-                    Ok(())
                 }
                 _ => {
                     return Err(SqlError::InvalidCardType);
@@ -420,9 +515,8 @@ impl SqlClient for SqliteClient {
                         .bind(&record.build_tag)
                         .bind(&record.username)
                         .bind(&record.opsml_version)
-                        .execute(&self.pool)
+                        .execute(&mut *tx)
                         .await?;
-                    Ok(())
                 }
 
                 _ => {
@@ -446,9 +540,8 @@ impl SqlClient for SqliteClient {
                         .bind(&record.cards)
                         .bind(&record.username)
                         .bind(&record.opsml_version)
-                        .execute(&self.pool)
+                        .execute(&mut *tx)
                         .await?;
-                    Ok(())
                 }
                 _ => {
                     return Err(SqlError::InvalidCardType);
@@ -459,9 +552,23 @@ impl SqlClient for SqliteClient {
                 return Err(SqlError::InvalidTableName);
             }
         }
+
+        Self::index_card_tx(&mut tx, card).await?;
+        Self::index_lineage_tx(&mut tx, card).await?;
+        Self::insert_outbox_events_tx(&mut tx, events).await?;
+        tx.commit().await?;
+
+        Ok(())
     }
 
-    async fn update_card(&self, table: &CardTable, card: &ServerCard) -> Result<(), SqlError> {
+    async fn update_card(
+        &self,
+        table: &CardTable,
+        card: &ServerCard,
+        events: &[OutboxEvent],
+    ) -> Result<(), SqlError> {
+        let mut tx = self.pool.begin_with("BEGIN IMMEDIATE").await?;
+
         match table {
             CardTable::Data => match card {
                 ServerCard::Data(record) => {
@@ -484,9 +591,8 @@ impl SqlClient for SqliteClient {
                         .bind(&record.username)
                         .bind(&record.opsml_version)
                         .bind(&record.uid)
-                        .execute(&self.pool)
+                        .execute(&mut *tx)
                         .await?;
-                    Ok(())
                 }
                 _ => {
                     return Err(SqlError::InvalidCardType);
@@ -516,9 +622,8 @@ impl SqlClient for SqliteClient {
                         .bind(&record.username)
                         .bind(&record.opsml_version)
                         .bind(&record.uid)
-                        .execute(&self.pool)
+                        .execute(&mut *tx)
                         .await?;
-                    Ok(())
                 }
                 _ => {
                     return Err(SqlError::InvalidCardType);
@@ -546,9 +651,8 @@ impl SqlClient for SqliteClient {
                         .bind(&record.username)
                         .bind(&record.opsml_version)
                         .bind(&record.uid)
-                        .execute(&self.pool)
+                        .execute(&mut *tx)
                         .await?;
-                    Ok(())
                 }
                 _ => {
                     return Err(SqlError::InvalidCardType);
@@ -575,9 +679,8 @@ impl SqlClient for SqliteClient {
                         .bind(&record.username)
                         .bind(&record.opsml_version)
                         .bind(&record.uid)
-                        .execute(&self.pool)
+                        .execute(&mut *tx)
                         .await?;
-                    Ok(())
                 }
                 _ => {
                     return Err(SqlError::InvalidCardType);
@@ -603,9 +706,8 @@ impl SqlClient for SqliteClient {
                         .bind(&record.username)
                         .bind(&record.opsml_version)
                         .bind(&record.uid)
-                        .execute(&self.pool)
+                        .execute(&mut *tx)
                         .await?;
-                    Ok(())
                 }
 
                 _ => {
@@ -628,9 +730,8 @@ impl SqlClient for SqliteClient {
                         .bind(&record.username)
                         .bind(&record.opsml_version)
                         .bind(&record.uid)
-                        .execute(&self.pool)
+                        .execute(&mut *tx)
                         .await?;
-                    Ok(())
                 }
                 _ => {
                     return Err(SqlError::InvalidCardType);
@@ -641,6 +742,13 @@ impl SqlClient for SqliteClient {
                 return Err(SqlError::InvalidTableName);
             }
         }
+
+        Self::index_card_tx(&mut tx, card).await?;
+        Self::index_lineage_tx(&mut tx, card).await?;
+        Self::insert_outbox_events_tx(&mut tx, events).await?;
+        tx.commit().await?;
+
+        Ok(())
     }
 
     /// Get unique space names
@@ -753,23 +861,327 @@ impl SqlClient for SqliteClient {
         &self,
         table: &CardTable,
         uid: &str,
+        events: &[OutboxEvent],
     ) -> Result<(String, String), SqlError> {
+        let mut tx = self.pool.begin_with("BEGIN IMMEDIATE").await?;
+
         // SQLite doesn't support RETURNING clause, so we need to do this in two steps
         let select_query = format!("SELECT space, name FROM {table} WHERE uid = ?");
         let (space, name): (String, String) = sqlx::query_as(&select_query)
             .bind(uid)
-            .fetch_one(&self.pool)
+            .fetch_one(&mut *tx)
             .await?;
 
         let delete_query = format!("DELETE FROM {table} WHERE uid = ?");
         sqlx::query(&delete_query)
             .bind(uid)
-            .execute(&self.pool)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query(&SqliteQueryHelper::get_card_search_entry_delete_query())
+            .bind(uid)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query(&SqliteQueryHelper::get_lineage_edges_delete_query())
+            .bind(uid)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query(&SqliteQueryHelper::get_card_artifact_key_delete_query())
+            .bind(uid)
+            .execute(&mut *tx)
+            .await?;
+
+        Self::insert_outbox_events_tx(&mut tx, events).await?;
+        tx.commit().await?;
+
+        Ok((space, name))
+    }
+
+    async fn soft_delete_card(
+        &self,
+        table: &CardTable,
+        uid: &str,
+        username: &str,
+        events: &[OutboxEvent],
+    ) -> Result<(String, String), SqlError> {
+        let mut tx = self.pool.begin_with("BEGIN IMMEDIATE").await?;
+
+        let select_query =
+            format!("SELECT space, name FROM {table} WHERE uid = ? AND deleted_at IS NULL");
+        let card: Option<(String, String)> = sqlx::query_as(&select_query)
+            .bind(uid)
+            .fetch_optional(&mut *tx)
+            .await?;
+
+        let Some((space, name)) = card else {
+            return Err(SqlError::CardNotFound(uid.to_string()));
+        };
+
+        let update_query =
+            format!("UPDATE {table} SET deleted_at = ?, deleted_by = ? WHERE uid = ?");
+        sqlx::query(&update_query)
+            .bind(get_utc_datetime())
+            .bind(username)
+            .bind(uid)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query(&SqliteQueryHelper::get_search_deleted_update_query())
+            .bind(true)
+            .bind(uid)
+            .execute(&mut *tx)
+            .await?;
+
+        Self::insert_outbox_events_tx(&mut tx, events).await?;
+        tx.commit().await?;
+
+        Ok((space, name))
+    }
+
+    async fn restore_card(
+        &self,
+        table: &CardTable,
+        uid: &str,
+        events: &[OutboxEvent],
+    ) -> Result<(String, String), SqlError> {
+        let mut tx = self.pool.begin_with("BEGIN IMMEDIATE").await?;
+
+        let select_query =
+            format!("SELECT space, name FROM {table} WHERE uid = ? AND deleted_at IS NOT NULL");
+        let card: Option<(String, String)> = sqlx::query_as(&select_query)
+            .bind(uid)
+            .fetch_optional(&mut *tx)
+            .await?;
+
+        let Some((space, name)) = card else {
+            return Err(SqlError::CardNotFound(uid.to_string()));
+        };
+
+        if matches!(table, CardTable::Model | CardTable::Service) {
+            // BEGIN IMMEDIATE holds the write lock, so the live stages cannot change before commit
+            let query = format!(
+                "SELECT uid, space, name, version, stage FROM {table} WHERE space = ? AND name = ? AND deleted_at IS NULL ORDER BY uid"
+            );
+            let versions: Vec<CardStageRecord> = sqlx::query_as(&query)
+                .bind(&space)
+                .bind(&name)
+                .fetch_all(&mut *tx)
+                .await?;
+
+            let stage_query = format!("SELECT stage FROM {table} WHERE uid = ?");
+            let stage: String = sqlx::query_scalar(&stage_query)
+                .bind(uid)
+                .fetch_one(&mut *tx)
+                .await?;
+
+            if let Some(stage) = restored_stage(&stage, &versions) {
+                let query = format!("UPDATE {table} SET stage = ? WHERE uid = ?");
+                sqlx::query(&query)
+                    .bind(stage.to_string())
+                    .bind(uid)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+        }
+
+        let update_query =
+            format!("UPDATE {table} SET deleted_at = NULL, deleted_by = NULL WHERE uid = ?");
+        sqlx::query(&update_query)
+            .bind(uid)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query(&SqliteQueryHelper::get_search_deleted_update_query())
+            .bind(false)
+            .bind(uid)
+            .execute(&mut *tx)
             .await?;
 
+        Self::insert_outbox_events_tx(&mut tx, events).await?;
+        tx.commit().await?;
+
         Ok((space, name))
     }
 
+    async fn get_deleted_cards(
+        &self,
+        table: &CardTable,
+        space: Option<&str>,
+        deleted_before: Option<DateTime<Utc>>,
+    ) -> Result<Vec<DeletedCardRecord>, SqlError> {
+        let query = format!(
+            "SELECT uid, space, name, version, deleted_at, deleted_by
+            FROM {table}
+            WHERE deleted_at IS NOT NULL
+            AND (?1 IS NULL OR space = ?1)
+            AND (?2 IS NULL OR deleted_at <= ?2)
+            ORDER BY deleted_at DESC"
+        );
+
+        let records: Vec<DeletedCardRecord> = sqlx::query_as(&query)
+            .bind(space)
+            .bind(deleted_before)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(records)
+    }
+
+    async fn update_search_readme(
+        &self,
+        registry_type: &RegistryType,
+        space: &str,
+        name: &str,
+        readme: &str,
+    ) -> Result<(), SqlError> {
+        sqlx::query(&SqliteQueryHelper::get_search_readme_update_query())
+            .bind(readme)
+            .bind(registry_type.to_string())
+            .bind(space)
+            .bind(name)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn search_cards(
+        &self,
+        query: &CardSearchQuery,
+        spaces: Option<&[String]>,
+    ) -> Result<Vec<CardSearchRecord>, SqlError> {
+        let terms = search_terms(&query.query);
+        if terms.is_empty() {
+            return Err(SqlError::EmptySearchQuery);
+        }
+
+        // every term must match as a prefix
+        let match_query = terms
+            .iter()
+            .map(|term| format!("\"{term}\"*"))
+            .collect::<Vec<_>>()
+            .join(" AND ");
+        let registry_type = query.registry_type.as_ref().map(|r| r.to_string());
+        let spaces = spaces.map(serde_json::to_string).transpose()?;
+
+        let records: Vec<CardSearchRecord> =
+            sqlx::query_as(&SqliteQueryHelper::get_search_cards_query())
+                .bind(&match_query)
+                .bind(registry_type)
+                .bind(&query.space)
+                .bind(spaces)
+                .bind(query.limit())
+                .fetch_all(&self.pool)
+                .await?;
+
+        Ok(records)
+    }
+
+    async fn get_lineage_edges(
+        &self,
+        uid: &str,
+        upstream_depth: i32,
+        downstream_depth: i32,
+    ) -> Result<Vec<CardLineageEdge>, SqlError> {
+        let edges: Vec<CardLineageEdge> =
+            sqlx::query_as(&SqliteQueryHelper::get_lineage_edges_query())
+                .bind(uid)
+                .bind(upstream_depth)
+                .bind(downstream_depth)
+                .fetch_all(&self.pool)
+                .await?;
+
+        Ok(edges)
+    }
+
+    async fn get_lineage_nodes(
+        &self,
+        uid: &str,
+        upstream_depth: i32,
+        downstream_depth: i32,
+    ) -> Result<Vec<CardLineageNodeRecord>, SqlError> {
+        let nodes: Vec<CardLineageNodeRecord> =
+            sqlx::query_as(&SqliteQueryHelper::get_lineage_nodes_query())
+                .bind(uid)
+                .bind(upstream_depth)
+                .bind(downstream_depth)
+                .fetch_all(&self.pool)
+                .await?;
+
+        Ok(nodes)
+    }
+
+    async fn get_card_stage(
+        &self,
+        table: &CardTable,
+        uid: &str,
+    ) -> Result<Option<CardStageRecord>, SqlError> {
+        let query = format!("SELECT uid, space, name, version, stage FROM {table} WHERE uid = ? AND deleted_at IS NULL");
+        let record: Option<CardStageRecord> = sqlx::query_as(&query)
+            .bind(uid)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(record)
+    }
+
+    async fn get_stage_holders(
+        &self,
+        table: &CardTable,
+        space: &str,
+        name: &str,
+        stage: &str,
+    ) -> Result<Vec<CardStageRecord>, SqlError> {
+        let query = format!(
+            "SELECT uid, space, name, version, stage FROM {table} WHERE space = ? AND name = ? AND stage = ? AND deleted_at IS NULL"
+        );
+        let records: Vec<CardStageRecord> = sqlx::query_as(&query)
+            .bind(space)
+            .bind(name)
+            .bind(stage)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(records)
+    }
+
+    async fn update_card_stages(
+        &self,
+        table: &CardTable,
+        transition: &CardStageResponse,
+        archive_existing: bool,
+        events: &[OutboxEvent],
+    ) -> Result<(), SqlError> {
+        let mut tx = self.pool.begin_with("BEGIN IMMEDIATE").await?;
+
+        // BEGIN IMMEDIATE takes the write lock, so the stages read below cannot change before commit
+        let query = format!(
+            "SELECT uid, space, name, version, stage FROM {table} WHERE space = ? AND name = ? AND deleted_at IS NULL ORDER BY uid"
+        );
+        let versions: Vec<CardStageRecord> = sqlx::query_as(&query)
+            .bind(&transition.space)
+            .bind(&transition.name)
+            .fetch_all(&mut *tx)
+            .await?;
+        verify_stage_transition(transition, &versions, archive_existing)?;
+
+        let query = format!("UPDATE {table} SET stage = ? WHERE uid = ? AND deleted_at IS NULL");
+        for update in stage_updates(transition) {
+            sqlx::query(&query)
+                .bind(update.stage)
+                .bind(update.uid)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        Self::insert_outbox_events_tx(&mut tx, events).await?;
+        tx.commit().await?;
+
+        Ok(())
+    }
+
     async fn insert_experiment_metric(&self, record: &MetricRecord) -> Result<(), SqlError> {
         let query = SqliteQueryHelper::get_experiment_metric_insert_query();
 
@@ -779,6 +1191,7 @@ impl SqlClient for SqliteClient {
             .bind(record.value)
             .bind(record.step)
             .bind(record.timestamp)
+            .bind(record.created_at)
             .execute(&self.pool)
             .await?;
 
@@ -799,7 +1212,8 @@ impl SqlClient for SqliteClient {
                 .bind(&r.name)
                 .bind(r.value)
                 .bind(r.step)
-                .bind(r.timestamp);
+                .bind(r.timestamp)
+                .bind(r.created_at);
         }
 
         query_builder.execute(&self.pool).await?;
@@ -824,6 +1238,32 @@ impl SqlClient for SqliteClient {
         Ok(records)
     }
 
+    async fn get_experiment_metric_page(
+        &self,
+        query: &MetricQuery,
+    ) -> Result<Vec<MetricRecord>, SqlError> {
+        let sql = SqliteQueryHelper::get_experiment_metric_page_query(query);
+        let mut query_builder =
+            sqlx::query_as::<sqlx::Sqlite, MetricRecord>(&sql).bind(&query.experiment_uid);
+
+        for name in &query.names {
+            query_builder = query_builder.bind(name);
+        }
+
+        for (_, value) in metric_page_filters(query) {
+            query_builder = query_builder.bind(value);
+        }
+
+        match (query.buckets(), query.max_points) {
+            (Some(buckets), Some(max_points)) => {
+                query_builder = query_builder.bind(buckets).bind(buckets).bind(max_points);
+            }
+            _ => query_builder = query_builder.bind(query.limit()),
+        }
+
+        Ok(query_builder.fetch_all(&self.pool).await?)
+    }
+
     async fn get_experiment_metric_names(&self, uid: &str) -> Result<Vec<String>, SqlError> {
         let query = format!(
             "SELECT DISTINCT name FROM {} WHERE experiment_uid = ?1",
@@ -1022,387 +1462,1910 @@ impl SqlClient for SqliteClient {
         Ok(())
     }
 
-    async fn insert_artifact_key(&self, key: &ArtifactKey) -> Result<(), SqlError> {
-        let query = SqliteQueryHelper::get_artifact_key_insert_query();
+    async fn insert_api_token(&self, token: &ApiToken) -> Result<(), SqlError> {
+        let query = SqliteQueryHelper::get_api_token_insert_query();
+
         sqlx::query(&query)
-            .bind(&key.uid)
-            .bind(&key.space)
-            .bind(key.registry_type.to_string())
-            .bind(key.encrypted_key.clone())
-            .bind(&key.storage_key)
+            .bind(&token.username)
+            .bind(&token.name)
+            .bind(&token.hashed_token)
+            .bind(&token.scopes)
+            .bind(token.expires_at)
             .execute(&self.pool)
             .await?;
 
         Ok(())
     }
 
-    async fn get_artifact_key(
-        &self,
-        uid: &str,
-        registry_type: &str,
-    ) -> Result<ArtifactKey, SqlError> {
-        let query = SqliteQueryHelper::get_artifact_key_select_query();
+    async fn get_api_token(&self, hashed_token: &str) -> Result<Option<ApiToken>, SqlError> {
+        let query = SqliteQueryHelper::get_api_token_query();
 
-        let key: (String, String, String, Vec<u8>, String) = sqlx::query_as(&query)
-            .bind(uid)
-            .bind(registry_type)
-            .fetch_one(&self.pool)
+        let token: Option<ApiToken> = sqlx::query_as(&query)
+            .bind(hashed_token)
+            .fetch_optional(&self.pool)
             .await?;
 
-        Ok(ArtifactKey {
-            uid: key.0,
-            space: key.1,
-            registry_type: RegistryType::from_string(&key.2)?,
-            encrypted_key: key.3,
-            storage_key: key.4,
-        })
+        Ok(token)
     }
 
-    async fn update_artifact_key(&self, key: &ArtifactKey) -> Result<(), SqlError> {
-        let query = SqliteQueryHelper::get_artifact_key_update_query();
-        sqlx::query(&query)
-            .bind(key.encrypted_key.clone())
-            .bind(&key.uid)
-            .bind(key.registry_type.to_string())
+    async fn get_api_tokens(&self, username: &str) -> Result<Vec<ApiToken>, SqlError> {
+        let query = SqliteQueryHelper::get_api_tokens_query();
+
+        let tokens: Vec<ApiToken> = sqlx::query_as(&query)
+            .bind(username)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(tokens)
+    }
+
+    async fn revoke_api_token(&self, username: &str, name: &str) -> Result<bool, SqlError> {
+        let query = SqliteQueryHelper::get_api_token_revoke_query();
+
+        let result = sqlx::query(&query)
+            .bind(username)
+            .bind(name)
             .execute(&self.pool)
             .await?;
 
-        Ok(())
+        Ok(result.rows_affected() > 0)
     }
 
-    async fn insert_audit_event(&self, event: AuditEvent) -> Result<(), SqlError> {
-        let query = SqliteQueryHelper::get_audit_event_insert_query();
+    async fn insert_webhook(&self, webhook: &Webhook) -> Result<(), SqlError> {
+        let query = SqliteQueryHelper::get_webhook_insert_query();
+
         sqlx::query(&query)
-            .bind(event.username)
-            .bind(event.client_ip)
-            .bind(event.user_agent)
-            .bind(event.operation.to_string())
-            .bind(event.resource_type.to_string())
-            .bind(event.resource_id)
-            .bind(event.access_location)
-            .bind(event.status.to_string())
-            .bind(event.error_message)
-            .bind(event.metadata)
-            .bind(event.registry_type.map(|r| r.to_string()))
-            .bind(event.route)
+            .bind(&webhook.uid)
+            .bind(webhook.created_at)
+            .bind(&webhook.url)
+            .bind(&webhook.space)
+            .bind(&webhook.event_types)
+            .bind(&webhook.encrypted_secret)
+            .bind(webhook.active)
+            .bind(&webhook.created_by)
             .execute(&self.pool)
             .await?;
 
         Ok(())
     }
 
-    async fn get_card_key_for_loading(
-        &self,
-        table: &CardTable,
-        query_args: &CardQueryArgs,
-    ) -> Result<ArtifactKey, SqlError> {
-        let query = SqliteQueryHelper::get_load_card_query(table, query_args)?;
+    async fn get_webhook(&self, uid: &str) -> Result<Option<Webhook>, SqlError> {
+        let query = SqliteQueryHelper::get_webhook_query();
 
-        let key: (String, String, String, Vec<u8>, String) = sqlx::query_as(&query)
-            .bind(query_args.uid.as_ref())
-            .bind(query_args.name.as_ref())
-            .bind(query_args.space.as_ref())
-            .bind(query_args.max_date.as_ref())
-            .bind(query_args.limit.unwrap_or(1))
-            .fetch_one(&self.pool)
+        let webhook: Option<Webhook> = sqlx::query_as(&query)
+            .bind(uid)
+            .fetch_optional(&self.pool)
             .await?;
 
-        Ok(ArtifactKey {
-            uid: key.0,
-            space: key.1,
-            registry_type: RegistryType::from_string(&key.2)?,
-            encrypted_key: key.3,
-            storage_key: key.4,
-        })
+        Ok(webhook)
     }
 
-    async fn get_artifact_key_from_path(
-        &self,
-        storage_path: &str,
-        registry_type: &str,
-    ) -> Result<Option<ArtifactKey>, SqlError> {
-        let query = SqliteQueryHelper::get_artifact_key_from_storage_path_query();
+    async fn get_webhooks(&self) -> Result<Vec<Webhook>, SqlError> {
+        let query = SqliteQueryHelper::get_webhooks_query();
 
-        let key: Option<(String, String, String, Vec<u8>, String)> = sqlx::query_as(&query)
-            .bind(storage_path)
-            .bind(registry_type)
-            .fetch_optional(&self.pool)
-            .await?;
+        let webhooks: Vec<Webhook> = sqlx::query_as(&query).fetch_all(&self.pool).await?;
 
-        return match key {
-            Some(k) => Ok(Some(ArtifactKey {
-                uid: k.0,
-                space: k.1,
-                registry_type: RegistryType::from_string(&k.2)?,
-                encrypted_key: k.3,
-                storage_key: k.4,
-            })),
-            None => Ok(None),
-        };
+        Ok(webhooks)
     }
 
-    async fn delete_artifact_key(&self, uid: &str, registry_type: &str) -> Result<(), SqlError> {
-        let query = SqliteQueryHelper::get_artifact_key_delete_query();
-        sqlx::query(&query)
-            .bind(uid)
-            .bind(registry_type)
-            .execute(&self.pool)
+    async fn get_active_webhooks(&self, space: &str) -> Result<Vec<Webhook>, SqlError> {
+        let query = SqliteQueryHelper::get_active_webhooks_query();
+
+        let webhooks: Vec<Webhook> = sqlx::query_as(&query)
+            .bind(space)
+            .fetch_all(&self.pool)
             .await?;
 
+        Ok(webhooks)
+    }
+
+    async fn delete_webhook(&self, uid: &str) -> Result<bool, SqlError> {
+        let query = SqliteQueryHelper::get_webhook_delete_query();
+
+        let result = sqlx::query(&query).bind(uid).execute(&self.pool).await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn update_webhook_secrets(&self, webhooks: &[Webhook]) -> Result<(), SqlError> {
+        let query = SqliteQueryHelper::get_webhook_secret_update_query();
+        let mut tx = self.pool.begin().await?;
+
+        for webhook in webhooks {
+            sqlx::query(&query)
+                .bind(&webhook.encrypted_secret)
+                .bind(&webhook.uid)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await?;
+
         Ok(())
     }
 
-    async fn insert_space_record(&self, space: &SpaceRecord) -> Result<(), SqlError> {
-        let query = SqliteQueryHelper::get_insert_space_record_query();
+    async fn insert_webhook_delivery(&self, delivery: &WebhookDelivery) -> Result<(), SqlError> {
+        let query = SqliteQueryHelper::get_webhook_delivery_insert_query();
+
         sqlx::query(&query)
-            .bind(&space.space)
-            .bind(&space.description)
+            .bind(&delivery.uid)
+            .bind(delivery.created_at)
+            .bind(delivery.updated_at)
+            .bind(&delivery.webhook_uid)
+            .bind(&delivery.event_type)
+            .bind(&delivery.payload)
+            .bind(&delivery.status)
+            .bind(delivery.attempts)
+            .bind(delivery.response_status)
+            .bind(&delivery.error_message)
             .execute(&self.pool)
             .await?;
 
         Ok(())
     }
 
-    async fn insert_space_name_record(&self, event: &SpaceNameEvent) -> Result<(), SqlError> {
-        let query = SqliteQueryHelper::get_insert_space_name_record_query();
+    async fn update_webhook_delivery(&self, delivery: &WebhookDelivery) -> Result<(), SqlError> {
+        let query = SqliteQueryHelper::get_webhook_delivery_update_query();
+
         sqlx::query(&query)
-            .bind(&event.space)
-            .bind(&event.name)
-            .bind(event.registry_type.to_string())
+            .bind(delivery.updated_at)
+            .bind(&delivery.status)
+            .bind(delivery.attempts)
+            .bind(delivery.response_status)
+            .bind(&delivery.error_message)
+            .bind(&delivery.uid)
             .execute(&self.pool)
             .await?;
 
         Ok(())
     }
 
-    async fn get_all_space_stats(&self) -> Result<Vec<SpaceStats>, SqlError> {
-        let query = SqliteQueryHelper::get_all_space_stats_query();
-        let spaces: Vec<SqlSpaceRecord> = sqlx::query_as(&query).fetch_all(&self.pool).await?;
+    async fn get_webhook_delivery(&self, uid: &str) -> Result<Option<WebhookDelivery>, SqlError> {
+        let query = SqliteQueryHelper::get_webhook_delivery_query();
 
-        Ok(spaces
-            .into_iter()
-            .map(|s| SpaceStats {
-                space: s.0,
-                model_count: s.1,
-                data_count: s.2,
-                prompt_count: s.3,
-                experiment_count: s.4,
-            })
-            .collect())
+        let delivery: Option<WebhookDelivery> = sqlx::query_as(&query)
+            .bind(uid)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(delivery)
     }
 
-    async fn get_space_record(&self, space: &str) -> Result<Option<SpaceRecord>, SqlError> {
-        let query = SqliteQueryHelper::get_space_record_query();
-        let record: Option<(String, String)> = sqlx::query_as(&query)
+    async fn get_webhook_deliveries(
+        &self,
+        webhook_uid: &str,
+        limit: i32,
+    ) -> Result<Vec<WebhookDelivery>, SqlError> {
+        let query = SqliteQueryHelper::get_webhook_deliveries_query();
+
+        let deliveries: Vec<WebhookDelivery> = sqlx::query_as(&query)
+            .bind(webhook_uid)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(deliveries)
+    }
+
+    async fn claim_stale_webhook_deliveries(
+        &self,
+        stale_before: DateTime<Utc>,
+        limit: i32,
+    ) -> Result<Vec<WebhookDelivery>, SqlError> {
+        let mut tx = self.pool.begin_with("BEGIN IMMEDIATE").await?;
+
+        let mut deliveries: Vec<WebhookDelivery> =
+            sqlx::query_as(&SqliteQueryHelper::get_stale_webhook_deliveries_query())
+                .bind(WEBHOOK_DELIVERY_PENDING)
+                .bind(stale_before)
+                .bind(limit)
+                .fetch_all(&mut *tx)
+                .await?;
+
+        let claim_query = SqliteQueryHelper::get_webhook_delivery_claim_query();
+        let claimed_at = get_utc_datetime();
+        for delivery in &mut deliveries {
+            sqlx::query(&claim_query)
+                .bind(claimed_at)
+                .bind(&delivery.uid)
+                .execute(&mut *tx)
+                .await?;
+            delivery.updated_at = claimed_at;
+        }
+
+        tx.commit().await?;
+
+        Ok(deliveries)
+    }
+
+    async fn set_version_alias(
+        &self,
+        alias: &VersionAliasRecord,
+    ) -> Result<Option<VersionAliasRecord>, SqlError> {
+        let mut tx = self.pool.begin_with("BEGIN IMMEDIATE").await?;
+
+        let previous: Option<VersionAliasRecord> =
+            sqlx::query_as(&SqliteQueryHelper::get_version_alias_query())
+                .bind(&alias.registry_type)
+                .bind(&alias.space)
+                .bind(&alias.name)
+                .bind(&alias.alias)
+                .fetch_optional(&mut *tx)
+                .await?;
+
+        sqlx::query(&SqliteQueryHelper::get_version_alias_upsert_query())
+            .bind(&alias.registry_type)
+            .bind(&alias.space)
+            .bind(&alias.name)
+            .bind(&alias.alias)
+            .bind(&alias.uid)
+            .bind(&alias.version)
+            .bind(alias.updated_at)
+            .bind(&alias.updated_by)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query(&SqliteQueryHelper::get_version_alias_history_insert_query())
+            .bind(alias.updated_at)
+            .bind(&alias.registry_type)
+            .bind(&alias.space)
+            .bind(&alias.name)
+            .bind(&alias.alias)
+            .bind(&alias.uid)
+            .bind(&alias.version)
+            .bind(previous.as_ref().map(|p| &p.version))
+            .bind(&alias.updated_by)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(previous)
+    }
+
+    async fn get_version_alias(
+        &self,
+        registry_type: &str,
+        space: &str,
+        name: &str,
+        alias: &str,
+    ) -> Result<Option<VersionAliasRecord>, SqlError> {
+        let query = SqliteQueryHelper::get_version_alias_query();
+
+        let record: Option<VersionAliasRecord> = sqlx::query_as(&query)
+            .bind(registry_type)
             .bind(space)
+            .bind(name)
+            .bind(alias)
             .fetch_optional(&self.pool)
             .await?;
 
-        Ok(record.map(|r| SpaceRecord {
-            space: r.0,
-            description: r.1,
-        }))
+        Ok(record)
     }
 
-    async fn update_space_record(&self, space: &SpaceRecord) -> Result<(), SqlError> {
-        let query = SqliteQueryHelper::get_update_space_record_query();
+    async fn get_version_aliases(
+        &self,
+        registry_type: &str,
+        space: &str,
+        name: &str,
+    ) -> Result<Vec<VersionAliasRecord>, SqlError> {
+        let query = SqliteQueryHelper::get_version_aliases_query();
+
+        let records: Vec<VersionAliasRecord> = sqlx::query_as(&query)
+            .bind(registry_type)
+            .bind(space)
+            .bind(name)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(records)
+    }
+
+    async fn delete_version_alias(
+        &self,
+        registry_type: &str,
+        space: &str,
+        name: &str,
+        alias: &str,
+        username: &str,
+    ) -> Result<bool, SqlError> {
+        let mut tx = self.pool.begin_with("BEGIN IMMEDIATE").await?;
+
+        let previous: Option<VersionAliasRecord> =
+            sqlx::query_as(&SqliteQueryHelper::get_version_alias_query())
+                .bind(registry_type)
+                .bind(space)
+                .bind(name)
+                .bind(alias)
+                .fetch_optional(&mut *tx)
+                .await?;
+
+        let Some(previous) = previous else {
+            return Ok(false);
+        };
+
+        sqlx::query(&SqliteQueryHelper::get_version_alias_delete_query())
+            .bind(registry_type)
+            .bind(space)
+            .bind(name)
+            .bind(alias)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query(&SqliteQueryHelper::get_version_alias_history_insert_query())
+            .bind(get_utc_datetime())
+            .bind(registry_type)
+            .bind(space)
+            .bind(name)
+            .bind(alias)
+            .bind(None::<String>)
+            .bind(None::<String>)
+            .bind(&previous.version)
+            .bind(username)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(true)
+    }
+
+    async fn get_version_alias_history(
+        &self,
+        registry_type: &str,
+        space: &str,
+        name: &str,
+        alias: Option<&str>,
+        limit: i32,
+    ) -> Result<Vec<VersionAliasHistoryRecord>, SqlError> {
+        let query = SqliteQueryHelper::get_version_alias_history_query();
+
+        let records: Vec<VersionAliasHistoryRecord> = sqlx::query_as(&query)
+            .bind(registry_type)
+            .bind(space)
+            .bind(name)
+            .bind(alias)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(records)
+    }
+
+    async fn insert_signing_key(&self, key: &SigningKeyRecord) -> Result<(), SqlError> {
+        let query = SqliteQueryHelper::get_signing_key_insert_query();
+
         sqlx::query(&query)
-            .bind(&space.description)
-            .bind(&space.space)
+            .bind(&key.key_id)
+            .bind(&key.space)
+            .bind(&key.public_key)
+            .bind(key.created_at)
+            .bind(&key.created_by)
+            .bind(key.active)
             .execute(&self.pool)
             .await?;
 
         Ok(())
     }
 
-    async fn delete_space_record(&self, space: &str) -> Result<(), SqlError> {
-        let query = SqliteQueryHelper::get_delete_space_record_query();
-        sqlx::query(&query).bind(space).execute(&self.pool).await?;
+    async fn get_signing_keys(
+        &self,
+        space: Option<&str>,
+    ) -> Result<Vec<SigningKeyRecord>, SqlError> {
+        let query = SqliteQueryHelper::get_signing_keys_query();
+
+        let keys: Vec<SigningKeyRecord> = sqlx::query_as(&query)
+            .bind(space)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(keys)
+    }
+
+    async fn revoke_signing_key(&self, key_id: &str) -> Result<bool, SqlError> {
+        let query = SqliteQueryHelper::get_signing_key_revoke_query();
+
+        let result = sqlx::query(&query).bind(key_id).execute(&self.pool).await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn insert_outbox_events(&self, events: &[OutboxEvent]) -> Result<(), SqlError> {
+        if events.is_empty() {
+            return Ok(());
+        }
+
+        let mut tx = self.pool.begin_with("BEGIN IMMEDIATE").await?;
+        Self::insert_outbox_events_tx(&mut tx, events).await?;
+        tx.commit().await?;
 
         Ok(())
     }
 
-    async fn delete_space_name_record(
+    async fn get_outbox_events(
         &self,
-        space: &str,
-        name: &str,
-        registry_type: &RegistryType,
+        after_id: i64,
+        limit: i32,
+    ) -> Result<Vec<OutboxEvent>, SqlError> {
+        let query = SqliteQueryHelper::get_outbox_events_query();
+
+        let events: Vec<OutboxEvent> = sqlx::query_as(&query)
+            .bind(after_id)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(events)
+    }
+
+    async fn claim_outbox_events(
+        &self,
+        claimer: &str,
+        claimed_until: DateTime<Utc>,
+        limit: i32,
+    ) -> Result<Vec<OutboxEvent>, SqlError> {
+        let mut tx = self.pool.begin_with("BEGIN IMMEDIATE").await?;
+
+        let events: Vec<OutboxEvent> =
+            sqlx::query_as(&SqliteQueryHelper::get_claimable_outbox_events_query())
+                .bind(get_utc_datetime())
+                .bind(limit)
+                .fetch_all(&mut *tx)
+                .await?;
+
+        let claim_query = SqliteQueryHelper::get_outbox_event_claim_query();
+        for event in &events {
+            sqlx::query(&claim_query)
+                .bind(claimer)
+                .bind(claimed_until)
+                .bind(event.id)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(events)
+    }
+
+    async fn release_outbox_events(&self, claimer: &str) -> Result<(), SqlError> {
+        let query = SqliteQueryHelper::get_outbox_events_release_query();
+
+        sqlx::query(&query)
+            .bind(claimer)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn fail_outbox_event(
+        &self,
+        id: i64,
+        error: &str,
+        max_attempts: i32,
     ) -> Result<(), SqlError> {
-        let query = SqliteQueryHelper::get_delete_space_name_record_query();
+        let query = SqliteQueryHelper::get_outbox_event_fail_query();
+
         sqlx::query(&query)
-            .bind(space)
-            .bind(name)
-            .bind(registry_type.to_string())
+            .bind(max_attempts)
+            .bind(get_utc_datetime())
+            .bind(error)
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn delete_outbox_event(&self, id: i64) -> Result<(), SqlError> {
+        let query = SqliteQueryHelper::get_outbox_event_delete_query();
+
+        sqlx::query(&query).bind(id).execute(&self.pool).await?;
+
+        Ok(())
+    }
+
+    async fn insert_artifact_key(&self, key: &ArtifactKey) -> Result<(), SqlError> {
+        let query = SqliteQueryHelper::get_artifact_key_insert_query();
+        sqlx::query(&query)
+            .bind(&key.uid)
+            .bind(&key.space)
+            .bind(key.registry_type.to_string())
+            .bind(key.encrypted_key.clone())
+            .bind(&key.storage_key)
+            .bind(key.has_manifest)
             .execute(&self.pool)
             .await?;
 
         Ok(())
     }
-}
 
-#[cfg(test)]
-mod tests {
+    async fn get_artifact_key(
+        &self,
+        uid: &str,
+        registry_type: &str,
+    ) -> Result<ArtifactKey, SqlError> {
+        let query = SqliteQueryHelper::get_artifact_key_select_query();
+
+        let key: (String, String, String, Vec<u8>, String, bool) = sqlx::query_as(&query)
+            .bind(uid)
+            .bind(registry_type)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(ArtifactKey {
+            uid: key.0,
+            space: key.1,
+            registry_type: RegistryType::from_string(&key.2)?,
+            encrypted_key: key.3,
+            storage_key: key.4,
+            has_manifest: key.5,
+        })
+    }
+
+    async fn get_artifact_key_page(
+        &self,
+        after_uid: &str,
+        after_registry_type: &str,
+        limit: i64,
+    ) -> Result<Vec<ArtifactKey>, SqlError> {
+        let query = SqliteQueryHelper::get_artifact_key_page_query();
+
+        let keys: Vec<(String, String, String, Vec<u8>, String, bool)> = sqlx::query_as(&query)
+            .bind(after_uid)
+            .bind(after_uid)
+            .bind(after_registry_type)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?;
+
+        keys.into_iter()
+            .map(|key| {
+                Ok(ArtifactKey {
+                    uid: key.0,
+                    space: key.1,
+                    registry_type: RegistryType::from_string(&key.2)?,
+                    encrypted_key: key.3,
+                    storage_key: key.4,
+                    has_manifest: key.5,
+                })
+            })
+            .collect()
+    }
+
+    async fn rewrap_artifact_keys(&self, keys: &[ArtifactKey]) -> Result<(), SqlError> {
+        let query = SqliteQueryHelper::get_artifact_key_rewrap_query();
+        let mut tx = self.pool.begin().await?;
+
+        for key in keys {
+            sqlx::query(&query)
+                .bind(&key.encrypted_key)
+                .bind(&key.uid)
+                .bind(key.registry_type.to_string())
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn update_artifact_key(&self, key: &ArtifactKey) -> Result<(), SqlError> {
+        let query = SqliteQueryHelper::get_artifact_key_update_query();
+        sqlx::query(&query)
+            .bind(key.encrypted_key.clone())
+            .bind(&key.uid)
+            .bind(key.registry_type.to_string())
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn insert_audit_event(&self, event: AuditEvent) -> Result<(), SqlError> {
+        // take the write lock up front so concurrent writers queue instead of failing on upgrade
+        let mut tx = self.pool.begin_with("BEGIN IMMEDIATE").await?;
+
+        let head: Option<AuditChainHeadRecord> =
+            sqlx::query_as(&SqliteQueryHelper::get_audit_chain_head_query())
+                .fetch_optional(&mut *tx)
+                .await?;
+
+        // chains written before the head was recorded continue from their last event
+        let (prev_seq, prev_hash) = match head {
+            Some(head) => (head.seq, head.hash),
+            None => {
+                let last: Option<(Option<i64>, Option<String>)> =
+                    sqlx::query_as(&SqliteQueryHelper::get_last_audit_event_hash_query())
+                        .fetch_optional(&mut *tx)
+                        .await?;
+                let (seq, hash) = last.unwrap_or_default();
+                (seq.unwrap_or_default(), hash.unwrap_or_default())
+            }
+        };
+
+        let record = AuditEventRecord::from_event(event, prev_hash, prev_seq + 1);
+
+        let query = SqliteQueryHelper::get_audit_event_insert_query();
+        sqlx::query(&query)
+            .bind(record.created_at)
+            .bind(&record.username)
+            .bind(&record.client_ip)
+            .bind(&record.user_agent)
+            .bind(&record.operation)
+            .bind(&record.resource_type)
+            .bind(&record.resource_id)
+            .bind(&record.access_location)
+            .bind(&record.status)
+            .bind(&record.error_message)
+            .bind(&record.metadata)
+            .bind(&record.registry_type)
+            .bind(&record.route)
+            .bind(&record.prev_hash)
+            .bind(&record.hash)
+            .bind(&record.space)
+            .bind(record.seq)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query(&SqliteQueryHelper::get_audit_chain_head_upsert_query())
+            .bind(record.seq)
+            .bind(&record.hash)
+            .bind(record.created_at)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn get_audit_chain_head(&self) -> Result<Option<AuditChainHeadRecord>, SqlError> {
+        let head: Option<AuditChainHeadRecord> =
+            sqlx::query_as(&SqliteQueryHelper::get_audit_chain_head_query())
+                .fetch_optional(&self.pool)
+                .await?;
+
+        Ok(head)
+    }
+
+    async fn get_audit_event_chain(
+        &self,
+        after_id: i32,
+        limit: i32,
+    ) -> Result<Vec<AuditEventRecord>, SqlError> {
+        let query = SqliteQueryHelper::get_audit_event_chain_query();
+
+        let records: Vec<AuditEventRecord> = sqlx::query_as(&query)
+            .bind(after_id)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(records)
+    }
+
+    async fn get_audit_events_by_time(
+        &self,
+        start_time: &DateTime<Utc>,
+        end_time: &DateTime<Utc>,
+        after_id: i32,
+        limit: i32,
+    ) -> Result<Vec<AuditEventRecord>, SqlError> {
+        let query = SqliteQueryHelper::get_audit_events_by_time_query();
+
+        let records: Vec<AuditEventRecord> = sqlx::query_as(&query)
+            .bind(start_time)
+            .bind(end_time)
+            .bind(after_id)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(records)
+    }
+
+    async fn get_audit_events(
+        &self,
+        args: &AuditEventQueryArgs,
+    ) -> Result<Vec<AuditEventRecord>, SqlError> {
+        let query = SqliteQueryHelper::get_audit_events_query();
+
+        let registry_type = args.registry_type.as_ref().map(|r| r.to_string());
+        let operation = args.operation.as_ref().map(|o| o.to_string());
+        let status = args.status.as_ref().map(|s| s.to_string());
+
+        let records: Vec<AuditEventRecord> = sqlx::query_as(&query)
+            .bind(args.username.as_ref())
+            .bind(args.space.as_ref())
+            .bind(registry_type.as_ref())
+            .bind(operation.as_ref())
+            .bind(status.as_ref())
+            .bind(args.resource_id.as_ref())
+            .bind(args.start_time.as_ref())
+            .bind(args.end_time.as_ref())
+            .bind(args.cursor)
+            .bind(args.limit.unwrap_or(50))
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(records)
+    }
+
+    async fn get_card_key_for_loading(
+        &self,
+        table: &CardTable,
+        query_args: &CardQueryArgs,
+    ) -> Result<ArtifactKey, SqlError> {
+        let query = SqliteQueryHelper::get_load_card_query(table, query_args)?;
+
+        let key: (String, String, String, Vec<u8>, String, bool) = sqlx::query_as(&query)
+            .bind(query_args.uid.as_ref())
+            .bind(query_args.name.as_ref())
+            .bind(query_args.space.as_ref())
+            .bind(query_args.max_date.as_ref())
+            .bind(query_args.limit.unwrap_or(1))
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(ArtifactKey {
+            uid: key.0,
+            space: key.1,
+            registry_type: RegistryType::from_string(&key.2)?,
+            encrypted_key: key.3,
+            storage_key: key.4,
+            has_manifest: key.5,
+        })
+    }
+
+    async fn get_artifact_key_from_path(
+        &self,
+        storage_path: &str,
+        registry_type: &str,
+    ) -> Result<Option<ArtifactKey>, SqlError> {
+        let query = SqliteQueryHelper::get_artifact_key_from_storage_path_query();
+
+        let key: Option<(String, String, String, Vec<u8>, String, bool)> = sqlx::query_as(&query)
+            .bind(storage_path)
+            .bind(registry_type)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        return match key {
+            Some(k) => Ok(Some(ArtifactKey {
+                uid: k.0,
+                space: k.1,
+                registry_type: RegistryType::from_string(&k.2)?,
+                encrypted_key: k.3,
+                storage_key: k.4,
+                has_manifest: k.5,
+            })),
+            None => Ok(None),
+        };
+    }
+
+    async fn delete_artifact_key(&self, uid: &str, registry_type: &str) -> Result<(), SqlError> {
+        let query = SqliteQueryHelper::get_artifact_key_delete_query();
+        sqlx::query(&query)
+            .bind(uid)
+            .bind(registry_type)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn insert_space_record(
+        &self,
+        space: &SpaceRecord,
+        events: &[OutboxEvent],
+    ) -> Result<(), SqlError> {
+        let query = SqliteQueryHelper::get_insert_space_record_query();
+
+        let mut tx = self.pool.begin_with("BEGIN IMMEDIATE").await?;
+        sqlx::query(&query)
+            .bind(&space.space)
+            .bind(&space.description)
+            .execute(&mut *tx)
+            .await?;
+
+        Self::insert_outbox_events_tx(&mut tx, events).await?;
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn insert_space_name_record(&self, event: &SpaceNameEvent) -> Result<(), SqlError> {
+        let query = SqliteQueryHelper::get_insert_space_name_record_query();
+        sqlx::query(&query)
+            .bind(&event.space)
+            .bind(&event.name)
+            .bind(event.registry_type.to_string())
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_all_space_stats(&self) -> Result<Vec<SpaceStats>, SqlError> {
+        let query = SqliteQueryHelper::get_all_space_stats_query();
+        let spaces: Vec<SqlSpaceRecord> = sqlx::query_as(&query).fetch_all(&self.pool).await?;
+
+        Ok(spaces
+            .into_iter()
+            .map(|s| SpaceStats {
+                space: s.0,
+                model_count: s.1,
+                data_count: s.2,
+                prompt_count: s.3,
+                experiment_count: s.4,
+            })
+            .collect())
+    }
+
+    async fn get_space_record(&self, space: &str) -> Result<Option<SpaceRecord>, SqlError> {
+        let query = SqliteQueryHelper::get_space_record_query();
+        let record: Option<(String, String)> = sqlx::query_as(&query)
+            .bind(space)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(record.map(|r| SpaceRecord {
+            space: r.0,
+            description: r.1,
+        }))
+    }
+
+    async fn update_space_record(
+        &self,
+        space: &SpaceRecord,
+        events: &[OutboxEvent],
+    ) -> Result<(), SqlError> {
+        let query = SqliteQueryHelper::get_update_space_record_query();
+
+        let mut tx = self.pool.begin_with("BEGIN IMMEDIATE").await?;
+        sqlx::query(&query)
+            .bind(&space.description)
+            .bind(&space.space)
+            .execute(&mut *tx)
+            .await?;
+
+        Self::insert_outbox_events_tx(&mut tx, events).await?;
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn delete_space_record(
+        &self,
+        space: &str,
+        events: &[OutboxEvent],
+    ) -> Result<(), SqlError> {
+        let query = SqliteQueryHelper::get_delete_space_record_query();
+
+        let mut tx = self.pool.begin_with("BEGIN IMMEDIATE").await?;
+        sqlx::query(&query).bind(space).execute(&mut *tx).await?;
+
+        Self::insert_outbox_events_tx(&mut tx, events).await?;
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn delete_space_name_record(
+        &self,
+        space: &str,
+        name: &str,
+        registry_type: &RegistryType,
+    ) -> Result<(), SqlError> {
+        let query = SqliteQueryHelper::get_delete_space_name_record_query();
+        sqlx::query(&query)
+            .bind(space)
+            .bind(name)
+            .bind(registry_type.to_string())
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::schemas::schema::WEBHOOK_DELIVERY_FAILED;
+    use crate::schemas::ServiceCardRecord;
+
+    use super::*;
+    use crate::alias::{build_version_alias, resolve_version_alias};
+    use crate::audit::verify_audit_chain;
+    use crate::metric::get_metric_page;
+    use crate::stage::plan_stage_transition;
+    use opsml_types::contracts::{AuditStatus, Operation, MAX_METRIC_POINTS};
+    use opsml_types::CardStage;
+
+    use opsml_types::{contracts::SpaceNameEvent, RegistryType, SqlType};
+    use opsml_utils::utils::get_utc_datetime;
+    use std::env;
+
+    async fn test_card_crud(
+        client: &SqliteClient,
+        table: &CardTable,
+        updated_name: &str,
+    ) -> Result<(), SqlError> {
+        // Create initial card
+        let card = match table {
+            CardTable::Data => ServerCard::Data(DataCardRecord::default()),
+            CardTable::Model => ServerCard::Model(ModelCardRecord::default()),
+            CardTable::Experiment => ServerCard::Experiment(ExperimentCardRecord::default()),
+            CardTable::Audit => ServerCard::Audit(AuditCardRecord::default()),
+            CardTable::Prompt => ServerCard::Prompt(PromptCardRecord::default()),
+            CardTable::Service => ServerCard::Service(ServiceCardRecord::default()),
+            _ => panic!("Invalid card type"),
+        };
+
+        // Get UID for queries
+        let uid = match &card {
+            ServerCard::Data(c) => c.uid.clone(),
+            ServerCard::Model(c) => c.uid.clone(),
+            ServerCard::Experiment(c) => c.uid.clone(),
+            ServerCard::Audit(c) => c.uid.clone(),
+            ServerCard::Prompt(c) => c.uid.clone(),
+            ServerCard::Service(c) => c.uid.clone(),
+        };
+
+        // Test Insert
+        client.insert_card(table, &card, &[]).await?;
+
+        // Verify Insert
+        let card_args = CardQueryArgs {
+            uid: Some(uid.clone()),
+            ..Default::default()
+        };
+        let results = client.query_cards(table, &card_args).await?;
+        assert_eq!(results.len(), 1);
+
+        // Create updated card with new name
+        let updated_card = match table {
+            CardTable::Data => {
+                let c = DataCardRecord {
+                    uid: uid.clone(),
+                    name: updated_name.to_string(),
+                    ..Default::default()
+                };
+                ServerCard::Data(c)
+            }
+            CardTable::Model => {
+                let c = ModelCardRecord {
+                    uid: uid.clone(),
+                    name: updated_name.to_string(),
+                    ..Default::default()
+                };
+
+                ServerCard::Model(c)
+            }
+            CardTable::Experiment => {
+                let c = ExperimentCardRecord {
+                    uid: uid.clone(),
+                    name: updated_name.to_string(),
+                    ..Default::default()
+                };
+                ServerCard::Experiment(c)
+            }
+            CardTable::Audit => {
+                let c = AuditCardRecord {
+                    uid: uid.clone(),
+                    name: updated_name.to_string(),
+                    ..Default::default()
+                };
+                ServerCard::Audit(c)
+            }
+            CardTable::Prompt => {
+                let c = PromptCardRecord {
+                    uid: uid.clone(),
+                    name: updated_name.to_string(),
+                    ..Default::default()
+                };
+                ServerCard::Prompt(c)
+            }
+
+            CardTable::Service => {
+                let c = ServiceCardRecord {
+                    uid: uid.clone(),
+                    name: updated_name.to_string(),
+                    ..Default::default()
+                };
+                ServerCard::Service(c)
+            }
+            _ => panic!("Invalid card type"),
+        };
+
+        // Test Update
+        client.update_card(table, &updated_card, &[]).await?;
+
+        // Verify Update
+        let updated_results = client.query_cards(table, &card_args).await?;
+        assert_eq!(updated_results.len(), 1);
+
+        // Verify updated name
+        match updated_results {
+            CardResults::Data(cards) => assert_eq!(cards[0].name, updated_name),
+            CardResults::Model(cards) => assert_eq!(cards[0].name, updated_name),
+            CardResults::Experiment(cards) => assert_eq!(cards[0].name, updated_name),
+            CardResults::Audit(cards) => assert_eq!(cards[0].name, updated_name),
+            CardResults::Prompt(cards) => assert_eq!(cards[0].name, updated_name),
+            CardResults::Service(cards) => assert_eq!(cards[0].name, updated_name),
+        }
+
+        // delete card
+        client.delete_card(table, &uid, &[]).await?;
+
+        // Verify Delete
+        let deleted_results = client.query_cards(table, &card_args).await?;
+        assert_eq!(deleted_results.len(), 0);
+
+        Ok(())
+    }
+
+    fn get_connection_uri() -> String {
+        let mut current_dir = env::current_dir().expect("Failed to get current directory");
+        current_dir.push("test.db");
+        format!(
+            "sqlite://{}",
+            current_dir
+                .to_str()
+                .expect("Failed to convert path to string")
+        )
+    }
+
+    pub fn cleanup() {
+        // delete ./test.db if exists
+        let mut current_dir = env::current_dir().expect("Failed to get current directory");
+        current_dir.push("test.db");
+        let _ = std::fs::remove_file(current_dir);
+    }
+
+    #[tokio::test]
+    async fn test_sqlite() {
+        let config = DatabaseSettings {
+            connection_uri: "sqlite::memory:".to_string(),
+            max_connections: 1,
+            sql_type: SqlType::Sqlite,
+        };
+
+        let _client = SqliteClient::new(&config).await;
+    }
+
+    // create test for non-memory sqlite
+
+    #[tokio::test]
+    async fn test_sqlite_versions() {
+        cleanup();
+
+        let config = DatabaseSettings {
+            connection_uri: get_connection_uri(),
+            max_connections: 1,
+            sql_type: SqlType::Sqlite,
+        };
+
+        let client = SqliteClient::new(&config).await.unwrap();
+
+        // Run the SQL script to populate the database
+        let script = std::fs::read_to_string("tests/populate_sqlite_test.sql").unwrap();
+        sqlx::query(&script).execute(&client.pool).await.unwrap();
+
+        // query all versions
+        // get versions (should return 1)
+        let versions = client
+            .get_versions(&CardTable::Data, "repo1", "Data1", None)
+            .await
+            .unwrap();
+        assert_eq!(versions.len(), 10);
+
+        // check star pattern
+        let versions = client
+            .get_versions(&CardTable::Data, "repo1", "Data1", Some("*".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(versions.len(), 10);
+
+        let versions = client
+            .get_versions(&CardTable::Data, "repo1", "Data1", Some("1.*".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(versions.len(), 4);
+
+        let versions = client
+            .get_versions(
+                &CardTable::Data,
+                "repo1",
+                "Data1",
+                Some("1.1.*".to_string()),
+            )
+            .await
+            .unwrap();
+        assert_eq!(versions.len(), 2);
+
+        // check tilde pattern
+        let versions = client
+            .get_versions(&CardTable::Data, "repo1", "Data1", Some("~1".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(versions.len(), 4);
+
+        // check tilde pattern
+        let versions = client
+            .get_versions(&CardTable::Data, "repo1", "Data1", Some("~1.1".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(versions.len(), 2);
+
+        // check tilde pattern
+        let versions = client
+            .get_versions(
+                &CardTable::Data,
+                "repo1",
+                "Data1",
+                Some("~1.1.1".to_string()),
+            )
+            .await
+            .unwrap();
+        assert_eq!(versions.len(), 1);
+
+        let versions = client
+            .get_versions(
+                &CardTable::Data,
+                "repo1",
+                "Data1",
+                Some("^2.0.0".to_string()),
+            )
+            .await
+            .unwrap();
+        assert_eq!(versions.len(), 2);
+
+        let versions = client
+            .get_versions(
+                &CardTable::Data,
+                "repo1",
+                "Data1",
+                Some("2.0.0".to_string()),
+            )
+            .await
+            .unwrap();
+        assert_eq!(versions.len(), 1);
+
+        let versions = client
+            .get_versions(&CardTable::Data, "repo1", "Data1", Some("2".to_string()))
+            .await
+            .unwrap();
+
+        assert_eq!(versions.len(), 4);
+
+        cleanup();
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_crud_cards() {
+        cleanup();
+
+        let config = DatabaseSettings {
+            connection_uri: get_connection_uri(),
+            max_connections: 1,
+            sql_type: SqlType::Sqlite,
+        };
+
+        let client = SqliteClient::new(&config).await.unwrap();
+
+        test_card_crud(&client, &CardTable::Data, "UpdatedDataName")
+            .await
+            .unwrap();
+        test_card_crud(&client, &CardTable::Model, "UpdatedModelName")
+            .await
+            .unwrap();
+        test_card_crud(&client, &CardTable::Experiment, "UpdatedRunName")
+            .await
+            .unwrap();
+        test_card_crud(&client, &CardTable::Audit, "UpdatedAuditName")
+            .await
+            .unwrap();
+        test_card_crud(&client, &CardTable::Prompt, "UpdatedPromptName")
+            .await
+            .unwrap();
+        test_card_crud(&client, &CardTable::Service, "UpdatedDeckName")
+            .await
+            .unwrap();
+
+        cleanup();
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_unique_repos() {
+        cleanup();
+
+        let config = DatabaseSettings {
+            connection_uri: get_connection_uri(),
+            max_connections: 1,
+            sql_type: SqlType::Sqlite,
+        };
+
+        let client = SqliteClient::new(&config).await.unwrap();
+
+        // Run the SQL script to populate the database
+        let script = std::fs::read_to_string("tests/populate_sqlite_test.sql").unwrap();
+        sqlx::query(&script).execute(&client.pool).await.unwrap();
+
+        // get unique space names
+        let repos = client
+            .get_unique_space_names(&CardTable::Model)
+            .await
+            .unwrap();
+
+        assert_eq!(repos.len(), 10);
+
+        cleanup();
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_query_stats() {
+        cleanup();
+
+        let config = DatabaseSettings {
+            connection_uri: get_connection_uri(),
+            max_connections: 1,
+            sql_type: SqlType::Sqlite,
+        };
+
+        let client = SqliteClient::new(&config).await.unwrap();
+
+        // Run the SQL script to populate the database
+        let script = std::fs::read_to_string("tests/populate_sqlite_test.sql").unwrap();
+        sqlx::query(&script).execute(&client.pool).await.unwrap();
+
+        // query stats
+        let stats = client
+            .query_stats(&CardTable::Model, None, None)
+            .await
+            .unwrap();
+
+        assert_eq!(stats.nbr_names, 10);
+        assert_eq!(stats.nbr_versions, 10);
+        assert_eq!(stats.nbr_spaces, 10);
+
+        // query stats with search term
+        let stats = client
+            .query_stats(&CardTable::Model, Some("Model1"), None)
+            .await
+            .unwrap();
+
+        assert_eq!(stats.nbr_names, 2); // for Model1 and Model10
+
+        let stats = client
+            .query_stats(&CardTable::Model, Some("Model1"), Some("repo1"))
+            .await
+            .unwrap();
+
+        assert_eq!(stats.nbr_names, 1); // for Model1
+
+        cleanup();
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_query_page() {
+        cleanup();
+
+        let config = DatabaseSettings {
+            connection_uri: get_connection_uri(),
+            max_connections: 1,
+            sql_type: SqlType::Sqlite,
+        };
+
+        let client = SqliteClient::new(&config).await.unwrap();
+
+        // Run the SQL script to populate the database
+        let script = std::fs::read_to_string("tests/populate_sqlite_test.sql").unwrap();
+        sqlx::query(&script).execute(&client.pool).await.unwrap();
+
+        // query page
+        let results = client
+            .query_page("name", 1, None, None, &CardTable::Data)
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 1);
+
+        // query page
+        let results = client
+            .query_page("name", 1, None, None, &CardTable::Model)
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 10);
+
+        // query page
+        let results = client
+            .query_page("name", 1, None, Some("repo3"), &CardTable::Model)
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 1);
+
+        cleanup();
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_version_page() {
+        cleanup();
+
+        let config = DatabaseSettings {
+            connection_uri: get_connection_uri(),
+            max_connections: 1,
+            sql_type: SqlType::Sqlite,
+        };
+
+        let client = SqliteClient::new(&config).await.unwrap();
+
+        // Run the SQL script to populate the database
+        let script = std::fs::read_to_string("tests/populate_sqlite_test.sql").unwrap();
+        sqlx::query(&script).execute(&client.pool).await.unwrap();
+
+        // query page
+        let results = client
+            .version_page(1, Some("repo1"), Some("Model1"), &CardTable::Model)
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 1);
+
+        cleanup();
+    }
+
+    // test run metric
+    #[tokio::test]
+    async fn test_sqlite_run_metric() {
+        cleanup();
+
+        let config = DatabaseSettings {
+            connection_uri: get_connection_uri(),
+            max_connections: 1,
+            sql_type: SqlType::Sqlite,
+        };
+
+        let client = SqliteClient::new(&config).await.unwrap();
+
+        // Run the SQL script to populate the database
+        let script = std::fs::read_to_string("tests/populate_sqlite_test.sql").unwrap();
+
+        sqlx::query(&script).execute(&client.pool).await.unwrap();
+
+        let uid = "550e8400-e29b-41d4-a716-446655440000".to_string();
+        let metric_names = vec!["metric1", "metric2", "metric3"];
+
+        for name in metric_names {
+            let metric = MetricRecord {
+                experiment_uid: uid.clone(),
+                name: name.to_string(),
+                value: 1.0,
+                ..Default::default()
+            };
+
+            client.insert_experiment_metric(&metric).await.unwrap();
+        }
+
+        let records = client
+            .get_experiment_metric(&uid, &Vec::new())
+            .await
+            .unwrap();
+        let names = client.get_experiment_metric_names(&uid).await.unwrap();
+
+        assert_eq!(records.len(), 3);
+
+        // assert names = "metric1"
+        assert_eq!(names.len(), 3);
+
+        // insert vec
+        let records = vec![
+            MetricRecord {
+                experiment_uid: uid.clone(),
+                name: "vec1".to_string(),
+                value: 1.0,
+                ..Default::default()
+            },
+            MetricRecord {
+                experiment_uid: uid.clone(),
+                name: "vec2".to_string(),
+                value: 1.0,
+                ..Default::default()
+            },
+        ];
+
+        client.insert_experiment_metrics(&records).await.unwrap();
+
+        let records = client
+            .get_experiment_metric(&uid, &Vec::new())
+            .await
+            .unwrap();
+
+        assert_eq!(records.len(), 5);
+
+        cleanup();
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_run_metric_page() {
+        cleanup();
+
+        let config = DatabaseSettings {
+            connection_uri: get_connection_uri(),
+            max_connections: 1,
+            sql_type: SqlType::Sqlite,
+        };
+
+        let client = SqliteClient::new(&config).await.unwrap();
+
+        let uid = "550e8400-e29b-41d4-a716-446655440123".to_string();
+        let mut records = (0..100)
+            .map(|step| MetricRecord {
+                experiment_uid: uid.clone(),
+                name: "loss".to_string(),
+                value: (step % 10) as f64,
+                step: Some(step),
+                timestamp: Some(step as i64 * 1000),
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+        records.extend((0..10).map(|step| MetricRecord {
+            experiment_uid: uid.clone(),
+            name: "accuracy".to_string(),
+            value: 1.0,
+            step: Some(step),
+            ..Default::default()
+        }));
+        client.insert_experiment_metrics(&records).await.unwrap();
+
+        let loss = vec!["loss".to_string()];
+
+        // step and timestamp ranges are inclusive
+        let page = get_metric_page(
+            &client,
+            &MetricQuery {
+                experiment_uid: uid.clone(),
+                names: loss.clone(),
+                start_step: Some(10),
+                end_step: Some(19),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(page.metrics.len(), 10);
+        assert_eq!(page.metrics[0].step, Some(10));
+
+        let page = get_metric_page(
+            &client,
+            &MetricQuery {
+                experiment_uid: uid.clone(),
+                start_timestamp: Some(5000),
+                end_timestamp: Some(9000),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(page.metrics.len(), 5);
+
+        // follow the cursor until the last page
+        let mut query = MetricQuery {
+            experiment_uid: uid.clone(),
+            names: loss.clone(),
+            limit: Some(30),
+            ..Default::default()
+        };
+        let mut steps = Vec::new();
+        loop {
+            let page = get_metric_page(&client, &query).await.unwrap();
+            steps.extend(page.metrics.iter().filter_map(|metric| metric.step));
+            match page.next_cursor {
+                Some(cursor) => query.cursor = Some(cursor),
+                None => break,
+            }
+        }
+        assert_eq!(steps, (0..100).collect::<Vec<_>>());
+
+        // each of the 5 buckets keeps its lowest and highest value. Metrics that fit the budget are kept whole
+        let page = get_metric_page(
+            &client,
+            &MetricQuery {
+                experiment_uid: uid.clone(),
+                max_points: Some(10),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        let (loss, accuracy): (Vec<_>, Vec<_>) = page
+            .metrics
+            .iter()
+            .partition(|metric| metric.name == "loss");
+        assert_eq!(loss.len(), 10);
+        assert_eq!(loss.iter().filter(|metric| metric.value == 0.0).count(), 5);
+        assert_eq!(loss.iter().filter(|metric| metric.value == 9.0).count(), 5);
+        assert_eq!(accuracy.len(), 10);
+        assert!(page.next_cursor.is_none());
+
+        // a budget of one point keeps one point per metric
+        let page = get_metric_page(
+            &client,
+            &MetricQuery {
+                experiment_uid: uid.clone(),
+                max_points: Some(1),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(page.metrics.len(), 2);
+
+        for max_points in [0, MAX_METRIC_POINTS + 1] {
+            let result = get_metric_page(
+                &client,
+                &MetricQuery {
+                    experiment_uid: uid.clone(),
+                    max_points: Some(max_points),
+                    ..Default::default()
+                },
+            )
+            .await;
+            assert!(matches!(result, Err(SqlError::InvalidMaxPoints(_))));
+        }
+
+        cleanup();
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_hardware_metric() {
+        cleanup();
+
+        let config = DatabaseSettings {
+            connection_uri: get_connection_uri(),
+            max_connections: 1,
+            sql_type: SqlType::Sqlite,
+        };
+
+        let client = SqliteClient::new(&config).await.unwrap();
+
+        // Run the SQL script to populate the database
+        let script = std::fs::read_to_string("tests/populate_sqlite_test.sql").unwrap();
+
+        sqlx::query(&script).execute(&client.pool).await.unwrap();
+
+        let uid = "550e8400-e29b-41d4-a716-446655440000".to_string();
+
+        // create a loop of 10
+
+        let metric = HardwareMetricsRecord {
+            experiment_uid: uid.clone(),
+            created_at: get_utc_datetime(),
+            ..Default::default()
+        };
+
+        client.insert_hardware_metrics(&metric).await.unwrap();
+        let records = client.get_hardware_metric(&uid).await.unwrap();
+
+        assert_eq!(records.len(), 1);
+
+        cleanup();
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_parameter() {
+        cleanup();
+
+        let config = DatabaseSettings {
+            connection_uri: get_connection_uri(),
+            max_connections: 1,
+            sql_type: SqlType::Sqlite,
+        };
+
+        let client = SqliteClient::new(&config).await.unwrap();
+
+        // Run the SQL script to populate the database
+        let script = std::fs::read_to_string("tests/populate_sqlite_test.sql").unwrap();
+
+        sqlx::query(&script).execute(&client.pool).await.unwrap();
+
+        let uid = "550e8400-e29b-41d4-a716-446655440000".to_string();
+        let mut params = vec![];
+
+        // create a loop of 10
+        for i in 0..10 {
+            let parameter = ParameterRecord {
+                experiment_uid: uid.clone(),
+                name: format!("param{i}"),
+                ..Default::default()
+            };
+
+            params.push(parameter.clone());
+        }
+
+        client.insert_experiment_parameters(&params).await.unwrap();
+        let records = client
+            .get_experiment_parameter(&uid, &Vec::new())
+            .await
+            .unwrap();
+
+        assert_eq!(records.len(), 10);
+
+        let param_records = client
+            .get_experiment_parameter(&uid, &["param1".to_string()])
+            .await
+            .unwrap();
+
+        assert_eq!(param_records.len(), 1);
+
+        cleanup();
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_user() {
+        cleanup();
+
+        let config = DatabaseSettings {
+            connection_uri: get_connection_uri(),
+            max_connections: 1,
+            sql_type: SqlType::Sqlite,
+        };
+
+        let client = SqliteClient::new(&config).await.unwrap();
+
+        let recovery_codes = vec!["recovery_code_1".to_string(), "recovery_code_2".to_string()];
+
+        let user = User::new(
+            "user".to_string(),
+            "pass".to_string(),
+            "email".to_string(),
+            recovery_codes,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+
+        let sso_user = User::new_from_sso("sso_user", "user@email.com");
+
+        client.insert_user(&user).await.unwrap();
+        client.insert_user(&sso_user).await.unwrap();
+
+        let mut user_to_update = client.get_user("user", None).await.unwrap().unwrap();
+
+        assert_eq!(user_to_update.username, "user");
+        assert_eq!(user_to_update.password_hash, "pass");
+        assert_eq!(user_to_update.group_permissions, vec!["user"]);
+        assert_eq!(user_to_update.email, "email");
+
+        // update user
+        user_to_update.active = false;
+        user_to_update.refresh_token = Some("token".to_string());
+
+        client.update_user(&user_to_update).await.unwrap();
+        let user = client.get_user("user", None).await.unwrap().unwrap();
+
+        assert!(!user.active);
+        assert_eq!(user.refresh_token.unwrap(), "token");
+
+        // get users
+        let users = client.get_users().await.unwrap();
+        assert_eq!(users.len(), 2);
+
+        let user = client
+            .get_user("sso_user", Some("sso"))
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(user.active);
+
+        // delete
+        client.delete_user("sso_user").await.unwrap();
+
+        // get last admin
+        let is_last_admin = client.is_last_admin("user").await.unwrap();
+        assert!(!is_last_admin);
+
+        // delete
+        client.delete_user("user").await.unwrap();
+
+        cleanup();
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_api_token() {
+        cleanup();
 
-    use crate::schemas::ServiceCardRecord;
+        let config = DatabaseSettings {
+            connection_uri: get_connection_uri(),
+            max_connections: 1,
+            sql_type: SqlType::Sqlite,
+        };
 
-    use super::*;
+        let client = SqliteClient::new(&config).await.unwrap();
 
-    use opsml_types::{contracts::SpaceNameEvent, RegistryType, SqlType};
-    use opsml_utils::utils::get_utc_datetime;
-    use std::env;
+        let service_account = User::new_service_account("ci_bot", None, None, None);
+        client.insert_user(&service_account).await.unwrap();
 
-    async fn test_card_crud(
-        client: &SqliteClient,
-        table: &CardTable,
-        updated_name: &str,
-    ) -> Result<(), SqlError> {
-        // Create initial card
-        let card = match table {
-            CardTable::Data => ServerCard::Data(DataCardRecord::default()),
-            CardTable::Model => ServerCard::Model(ModelCardRecord::default()),
-            CardTable::Experiment => ServerCard::Experiment(ExperimentCardRecord::default()),
-            CardTable::Audit => ServerCard::Audit(AuditCardRecord::default()),
-            CardTable::Prompt => ServerCard::Prompt(PromptCardRecord::default()),
-            CardTable::Service => ServerCard::Service(ServiceCardRecord::default()),
-            _ => panic!("Invalid card type"),
-        };
+        let token = ApiToken::new(
+            "ci_bot",
+            "ci",
+            "hashed_token".to_string(),
+            vec!["read:space".to_string(), "write:space".to_string()],
+            Some(get_utc_datetime() + chrono::Duration::days(30)),
+        );
+        client.insert_api_token(&token).await.unwrap();
 
-        // Get UID for queries
-        let uid = match &card {
-            ServerCard::Data(c) => c.uid.clone(),
-            ServerCard::Model(c) => c.uid.clone(),
-            ServerCard::Experiment(c) => c.uid.clone(),
-            ServerCard::Audit(c) => c.uid.clone(),
-            ServerCard::Prompt(c) => c.uid.clone(),
-            ServerCard::Service(c) => c.uid.clone(),
-        };
+        let stored = client.get_api_token("hashed_token").await.unwrap().unwrap();
+        assert_eq!(stored.username, "ci_bot");
+        assert_eq!(stored.scopes.len(), 2);
+        assert!(stored.is_active());
 
-        // Test Insert
-        client.insert_card(table, &card).await?;
+        let tokens = client.get_api_tokens("ci_bot").await.unwrap();
+        assert_eq!(tokens.len(), 1);
 
-        // Verify Insert
-        let card_args = CardQueryArgs {
-            uid: Some(uid.clone()),
-            ..Default::default()
-        };
-        let results = client.query_cards(table, &card_args).await?;
-        assert_eq!(results.len(), 1);
+        // revoke
+        assert!(client.revoke_api_token("ci_bot", "ci").await.unwrap());
+        assert!(!client.revoke_api_token("ci_bot", "ci").await.unwrap());
 
-        // Create updated card with new name
-        let updated_card = match table {
-            CardTable::Data => {
-                let c = DataCardRecord {
-                    uid: uid.clone(),
-                    name: updated_name.to_string(),
-                    ..Default::default()
-                };
-                ServerCard::Data(c)
-            }
-            CardTable::Model => {
-                let c = ModelCardRecord {
-                    uid: uid.clone(),
-                    name: updated_name.to_string(),
-                    ..Default::default()
-                };
+        let stored = client.get_api_token("hashed_token").await.unwrap().unwrap();
+        assert!(!stored.is_active());
 
-                ServerCard::Model(c)
-            }
-            CardTable::Experiment => {
-                let c = ExperimentCardRecord {
-                    uid: uid.clone(),
-                    name: updated_name.to_string(),
-                    ..Default::default()
-                };
-                ServerCard::Experiment(c)
-            }
-            CardTable::Audit => {
-                let c = AuditCardRecord {
-                    uid: uid.clone(),
-                    name: updated_name.to_string(),
-                    ..Default::default()
-                };
-                ServerCard::Audit(c)
-            }
-            CardTable::Prompt => {
-                let c = PromptCardRecord {
-                    uid: uid.clone(),
-                    name: updated_name.to_string(),
-                    ..Default::default()
-                };
-                ServerCard::Prompt(c)
-            }
+        let user = client
+            .get_user("ci_bot", Some("service"))
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(user.is_service_account());
 
-            CardTable::Service => {
-                let c = ServiceCardRecord {
-                    uid: uid.clone(),
-                    name: updated_name.to_string(),
-                    ..Default::default()
-                };
-                ServerCard::Service(c)
-            }
-            _ => panic!("Invalid card type"),
-        };
+        cleanup();
+    }
 
-        // Test Update
-        client.update_card(table, &updated_card).await?;
+    #[tokio::test]
+    async fn test_sqlite_webhook() {
+        cleanup();
 
-        // Verify Update
-        let updated_results = client.query_cards(table, &card_args).await?;
-        assert_eq!(updated_results.len(), 1);
+        let config = DatabaseSettings {
+            connection_uri: get_connection_uri(),
+            max_connections: 1,
+            sql_type: SqlType::Sqlite,
+        };
 
-        // Verify updated name
-        match updated_results {
-            CardResults::Data(cards) => assert_eq!(cards[0].name, updated_name),
-            CardResults::Model(cards) => assert_eq!(cards[0].name, updated_name),
-            CardResults::Experiment(cards) => assert_eq!(cards[0].name, updated_name),
-            CardResults::Audit(cards) => assert_eq!(cards[0].name, updated_name),
-            CardResults::Prompt(cards) => assert_eq!(cards[0].name, updated_name),
-            CardResults::Service(cards) => assert_eq!(cards[0].name, updated_name),
-        }
+        let client = SqliteClient::new(&config).await.unwrap();
 
-        // delete card
-        client.delete_card(table, &uid).await?;
+        let webhook = Webhook::new(
+            "http://localhost:9000/hook",
+            Some("space1".to_string()),
+            vec!["card_registered".to_string()],
+            vec![1, 2, 3, 4],
+            "admin",
+        );
+        client.insert_webhook(&webhook).await.unwrap();
 
-        // Verify Delete
-        let deleted_results = client.query_cards(table, &card_args).await?;
-        assert_eq!(deleted_results.len(), 0);
+        let global = Webhook::new(
+            "http://localhost:9000/all",
+            None,
+            vec![],
+            vec![5, 6],
+            "admin",
+        );
+        client.insert_webhook(&global).await.unwrap();
+
+        let stored = client.get_webhook(&webhook.uid).await.unwrap().unwrap();
+        assert_eq!(stored.encrypted_secret, vec![1, 2, 3, 4]);
+        assert!(stored.matches("space1", "card_registered"));
+        assert!(!stored.matches("space1", "card_deleted"));
+        assert!(!stored.matches("space2", "card_registered"));
+
+        assert_eq!(client.get_webhooks().await.unwrap().len(), 2);
+        assert_eq!(client.get_active_webhooks("space1").await.unwrap().len(), 2);
+        assert_eq!(client.get_active_webhooks("space2").await.unwrap().len(), 1);
+
+        // delivery log
+        let mut delivery = WebhookDelivery::new(&webhook.uid, "card_registered", "{}".to_string());
+        client.insert_webhook_delivery(&delivery).await.unwrap();
+
+        delivery.attempts = 2;
+        delivery.status = WEBHOOK_DELIVERY_FAILED.to_string();
+        delivery.response_status = Some(500);
+        delivery.error_message = Some("Internal Server Error".to_string());
+        delivery.updated_at = get_utc_datetime();
+        client.update_webhook_delivery(&delivery).await.unwrap();
+
+        let stored = client
+            .get_webhook_delivery(&delivery.uid)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.attempts, 2);
+        assert_eq!(stored.status, WEBHOOK_DELIVERY_FAILED);
+        assert_eq!(stored.response_status, Some(500));
 
-        Ok(())
-    }
+        let deliveries = client
+            .get_webhook_deliveries(&webhook.uid, 10)
+            .await
+            .unwrap();
+        assert_eq!(deliveries.len(), 1);
+
+        // pending deliveries left behind by a stopped server are claimed once
+        let mut stale = WebhookDelivery::new(&webhook.uid, "card_registered", "{}".to_string());
+        stale.updated_at = get_utc_datetime() - chrono::Duration::minutes(10);
+        client.insert_webhook_delivery(&stale).await.unwrap();
+        let active = WebhookDelivery::new(&webhook.uid, "card_registered", "{}".to_string());
+        client.insert_webhook_delivery(&active).await.unwrap();
+
+        let stale_before = get_utc_datetime() - chrono::Duration::minutes(5);
+        let claimed = client
+            .claim_stale_webhook_deliveries(stale_before, 10)
+            .await
+            .unwrap();
+        assert_eq!(claimed.len(), 1);
+        assert_eq!(claimed[0].uid, stale.uid);
+        assert!(client
+            .claim_stale_webhook_deliveries(stale_before, 10)
+            .await
+            .unwrap()
+            .is_empty());
 
-    fn get_connection_uri() -> String {
-        let mut current_dir = env::current_dir().expect("Failed to get current directory");
-        current_dir.push("test.db");
-        format!(
-            "sqlite://{}",
-            current_dir
-                .to_str()
-                .expect("Failed to convert path to string")
-        )
-    }
+        assert!(client.delete_webhook(&webhook.uid).await.unwrap());
+        assert!(!client.delete_webhook(&webhook.uid).await.unwrap());
+        assert!(client.get_webhook(&webhook.uid).await.unwrap().is_none());
 
-    pub fn cleanup() {
-        // delete ./test.db if exists
-        let mut current_dir = env::current_dir().expect("Failed to get current directory");
-        current_dir.push("test.db");
-        let _ = std::fs::remove_file(current_dir);
+        cleanup();
     }
 
     #[tokio::test]
-    async fn test_sqlite() {
+    async fn test_sqlite_signing_key() {
+        cleanup();
+
         let config = DatabaseSettings {
-            connection_uri: "sqlite::memory:".to_string(),
+            connection_uri: get_connection_uri(),
             max_connections: 1,
             sql_type: SqlType::Sqlite,
         };
 
-        let _client = SqliteClient::new(&config).await;
-    }
+        let client = SqliteClient::new(&config).await.unwrap();
 
-    // create test for non-memory sqlite
+        client
+            .insert_signing_key(&SigningKeyRecord::new(
+                "key1", "space1", "cHVibGlj", "admin",
+            ))
+            .await
+            .unwrap();
+        client
+            .insert_signing_key(&SigningKeyRecord::new(
+                "key2", "space2", "cHVibGlj", "admin",
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(client.get_signing_keys(None).await.unwrap().len(), 2);
+
+        let keys = client.get_signing_keys(Some("space1")).await.unwrap();
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].key_id, "key1");
+        assert!(keys[0].active);
+
+        // revoked keys are kept
+        assert!(client.revoke_signing_key("key1").await.unwrap());
+        assert!(!client.revoke_signing_key("key1").await.unwrap());
+
+        let keys = client.get_signing_keys(Some("space1")).await.unwrap();
+        assert_eq!(keys.len(), 1);
+        assert!(!keys[0].active);
+
+        cleanup();
+    }
 
     #[tokio::test]
-    async fn test_sqlite_versions() {
+    async fn test_sqlite_event_outbox() {
         cleanup();
 
         let config = DatabaseSettings {
@@ -1413,102 +3376,135 @@ mod tests {
 
         let client = SqliteClient::new(&config).await.unwrap();
 
-        // Run the SQL script to populate the database
-        let script = std::fs::read_to_string("tests/populate_sqlite_test.sql").unwrap();
-        sqlx::query(&script).execute(&client.pool).await.unwrap();
+        let data_card = DataCardRecord::default();
+        let card = ServerCard::Data(data_card.clone());
+        let events = vec![
+            OutboxEvent::new("space_name", "{\"id\":1}".to_string()),
+            OutboxEvent::new("card", "{\"id\":2}".to_string()),
+        ];
 
-        // query all versions
-        // get versions (should return 1)
-        let versions = client
-            .get_versions(&CardTable::Data, "repo1", "Data1", None)
+        // events are committed with the card
+        client
+            .insert_card(&CardTable::Data, &card, &events)
             .await
             .unwrap();
-        assert_eq!(versions.len(), 10);
 
-        // check star pattern
-        let versions = client
-            .get_versions(&CardTable::Data, "repo1", "Data1", Some("*".to_string()))
+        let pending = client.get_outbox_events(0, 10).await.unwrap();
+        assert_eq!(pending.len(), 2);
+        assert_eq!(pending[0].event_type, "space_name");
+        assert_eq!(pending[1].payload, "{\"id\":2}");
+        assert!(pending[0].id < pending[1].id);
+
+        // a failed card write rolls back its events
+        let result = client
+            .insert_card(&CardTable::Data, &card, &events[..1])
+            .await;
+        assert!(result.is_err());
+        assert_eq!(client.get_outbox_events(0, 10).await.unwrap().len(), 2);
+
+        // claimed events are leased to a single dispatcher
+        let lease = get_utc_datetime() + chrono::Duration::minutes(5);
+        let claimed = client
+            .claim_outbox_events("dispatcher_a", lease, 1)
             .await
             .unwrap();
-        assert_eq!(versions.len(), 10);
+        assert_eq!(claimed.len(), 1);
+        assert_eq!(claimed[0].id, pending[0].id);
 
-        let versions = client
-            .get_versions(&CardTable::Data, "repo1", "Data1", Some("1.*".to_string()))
+        let claimed = client
+            .claim_outbox_events("dispatcher_b", lease, 10)
             .await
             .unwrap();
-        assert_eq!(versions.len(), 4);
+        assert_eq!(claimed.len(), 1);
+        assert_eq!(claimed[0].id, pending[1].id);
+        assert!(client
+            .claim_outbox_events("dispatcher_c", lease, 10)
+            .await
+            .unwrap()
+            .is_empty());
 
-        let versions = client
-            .get_versions(
-                &CardTable::Data,
-                "repo1",
-                "Data1",
-                Some("1.1.*".to_string()),
-            )
+        // released and expired claims can be claimed again
+        client.release_outbox_events("dispatcher_b").await.unwrap();
+        let expired = get_utc_datetime() - chrono::Duration::minutes(5);
+        let claimed = client
+            .claim_outbox_events("dispatcher_c", expired, 10)
             .await
             .unwrap();
-        assert_eq!(versions.len(), 2);
+        assert_eq!(claimed.len(), 1);
+        assert_eq!(claimed[0].id, pending[1].id);
 
-        // check tilde pattern
-        let versions = client
-            .get_versions(&CardTable::Data, "repo1", "Data1", Some("~1".to_string()))
+        let claimed = client
+            .claim_outbox_events("dispatcher_a", lease, 10)
             .await
             .unwrap();
-        assert_eq!(versions.len(), 4);
+        assert_eq!(claimed.len(), 1);
+        assert_eq!(claimed[0].id, pending[1].id);
 
-        // check tilde pattern
-        let versions = client
-            .get_versions(&CardTable::Data, "repo1", "Data1", Some("~1.1".to_string()))
+        // delivered events are deleted by id
+        client.delete_outbox_event(pending[0].id).await.unwrap();
+        let remaining = client.get_outbox_events(0, 10).await.unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].id, pending[1].id);
+
+        // failed events are released until they reach the attempt limit, then parked
+        client
+            .fail_outbox_event(pending[1].id, "failed", 2)
             .await
             .unwrap();
-        assert_eq!(versions.len(), 2);
-
-        // check tilde pattern
-        let versions = client
-            .get_versions(
-                &CardTable::Data,
-                "repo1",
-                "Data1",
-                Some("~1.1.1".to_string()),
-            )
+        let claimed = client
+            .claim_outbox_events("dispatcher_b", lease, 10)
             .await
             .unwrap();
-        assert_eq!(versions.len(), 1);
+        assert_eq!(claimed.len(), 1);
+        assert_eq!(claimed[0].id, pending[1].id);
 
-        let versions = client
-            .get_versions(
-                &CardTable::Data,
-                "repo1",
-                "Data1",
-                Some("^2.0.0".to_string()),
-            )
+        client
+            .fail_outbox_event(pending[1].id, "failed", 2)
             .await
             .unwrap();
-        assert_eq!(versions.len(), 2);
+        assert!(client
+            .claim_outbox_events("dispatcher_b", lease, 10)
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(client.get_outbox_events(0, 10).await.unwrap().len(), 1);
 
-        let versions = client
-            .get_versions(
-                &CardTable::Data,
-                "repo1",
-                "Data1",
-                Some("2.0.0".to_string()),
-            )
+        // deletes, space changes and standalone writes
+        client
+            .delete_card(&CardTable::Data, &data_card.uid, &events[1..])
+            .await
+            .unwrap();
+        let space = SpaceRecord {
+            space: "space".to_string(),
+            description: "description".to_string(),
+        };
+        client
+            .insert_space_record(&space, &events[..1])
             .await
             .unwrap();
-        assert_eq!(versions.len(), 1);
-
-        let versions = client
-            .get_versions(&CardTable::Data, "repo1", "Data1", Some("2".to_string()))
+        client
+            .update_space_record(&space, &events[..1])
             .await
             .unwrap();
-
-        assert_eq!(versions.len(), 4);
+        client
+            .delete_space_record(&space.space, &events[..1])
+            .await
+            .unwrap();
+        client.insert_outbox_events(&events).await.unwrap();
+        assert_eq!(
+            client
+                .get_outbox_events(pending[1].id, 10)
+                .await
+                .unwrap()
+                .len(),
+            6
+        );
 
         cleanup();
     }
 
     #[tokio::test]
-    async fn test_sqlite_crud_cards() {
+    async fn test_sqlite_card_stage() {
         cleanup();
 
         let config = DatabaseSettings {
@@ -1519,57 +3515,225 @@ mod tests {
 
         let client = SqliteClient::new(&config).await.unwrap();
 
-        test_card_crud(&client, &CardTable::Data, "UpdatedDataName")
+        let mut uids = Vec::new();
+        for minor in 0..3 {
+            let card = ModelCardRecord {
+                name: "stage_model".to_string(),
+                space: "stage_space".to_string(),
+                minor,
+                version: format!("1.{minor}.0"),
+                ..Default::default()
+            };
+            uids.push(card.uid.clone());
+            client
+                .insert_card(&CardTable::Model, &ServerCard::Model(card), &[])
+                .await
+                .unwrap();
+        }
+
+        // new versions start unassigned
+        let record = client
+            .get_card_stage(&CardTable::Model, &uids[0])
             .await
+            .unwrap()
             .unwrap();
-        test_card_crud(&client, &CardTable::Model, "UpdatedModelName")
+        assert_eq!(record.stage, CardStage::Unassigned.to_string());
+
+        // promote two versions to production
+        for uid in &uids[..2] {
+            let plan = plan_stage_transition(
+                &client,
+                &CardTable::Model,
+                uid,
+                &CardStage::Production,
+                false,
+            )
             .await
             .unwrap();
-        test_card_crud(&client, &CardTable::Experiment, "UpdatedRunName")
+            assert!(plan.archived.is_empty());
+            client
+                .update_card_stages(&CardTable::Model, &plan, false, &[])
+                .await
+                .unwrap();
+        }
+
+        let holders = client
+            .get_stage_holders(
+                &CardTable::Model,
+                "stage_space",
+                "stage_model",
+                "production",
+            )
             .await
             .unwrap();
-        test_card_crud(&client, &CardTable::Audit, "UpdatedAuditName")
+        assert_eq!(holders.len(), 2);
+
+        // promoting with archive_existing archives the previous holders with the event
+        let plan = plan_stage_transition(
+            &client,
+            &CardTable::Model,
+            &uids[2],
+            &CardStage::Production,
+            true,
+        )
+        .await
+        .unwrap();
+        assert_eq!(plan.change.previous_stage, CardStage::Unassigned);
+        assert_eq!(plan.archived.len(), 2);
+
+        let events = vec![OutboxEvent::new("card", "{\"id\":1}".to_string())];
+        client
+            .update_card_stages(&CardTable::Model, &plan, true, &events)
             .await
             .unwrap();
-        test_card_crud(&client, &CardTable::Prompt, "UpdatedPromptName")
+        assert_eq!(client.get_outbox_events(0, 10).await.unwrap().len(), 1);
+
+        let holders = client
+            .get_stage_holders(
+                &CardTable::Model,
+                "stage_space",
+                "stage_model",
+                "production",
+            )
             .await
             .unwrap();
-        test_card_crud(&client, &CardTable::Service, "UpdatedDeckName")
+        assert_eq!(holders.len(), 1);
+        assert_eq!(holders[0].uid, uids[2]);
+
+        // filter cards by stage
+        let card_args = CardQueryArgs {
+            space: Some("stage_space".to_string()),
+            name: Some("stage_model".to_string()),
+            stage: Some(CardStage::Archived),
+            limit: Some(10),
+            ..Default::default()
+        };
+        let results = client
+            .query_cards(&CardTable::Model, &card_args)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 2);
+
+        // a plan made before another promotion committed is rejected instead of adding a second holder
+        let mut plans = Vec::new();
+        for uid in &uids[..2] {
+            let plan = plan_stage_transition(
+                &client,
+                &CardTable::Model,
+                uid,
+                &CardStage::Production,
+                true,
+            )
             .await
             .unwrap();
+            plans.push(plan);
+        }
+        client
+            .update_card_stages(&CardTable::Model, &plans[0], true, &[])
+            .await
+            .unwrap();
+        let result = client
+            .update_card_stages(&CardTable::Model, &plans[1], true, &[])
+            .await;
+        assert!(matches!(result, Err(SqlError::StageConflict(_))));
+
+        let holders = client
+            .get_stage_holders(
+                &CardTable::Model,
+                "stage_space",
+                "stage_model",
+                "production",
+            )
+            .await
+            .unwrap();
+        assert_eq!(holders.len(), 1);
+        assert_eq!(holders[0].uid, uids[0]);
+
+        // stages are only supported for model and service cards
+        let result = plan_stage_transition(
+            &client,
+            &CardTable::Data,
+            &uids[0],
+            &CardStage::Staging,
+            false,
+        )
+        .await;
+        assert!(matches!(result, Err(SqlError::StageNotSupported)));
+
+        let result = client.query_cards(&CardTable::Data, &card_args).await;
+        assert!(result.is_err());
 
         cleanup();
     }
 
     #[tokio::test]
-    async fn test_sqlite_unique_repos() {
+    async fn test_sqlite_card_stage_concurrent() {
         cleanup();
 
         let config = DatabaseSettings {
             connection_uri: get_connection_uri(),
-            max_connections: 1,
+            max_connections: 4,
             sql_type: SqlType::Sqlite,
         };
 
         let client = SqliteClient::new(&config).await.unwrap();
 
-        // Run the SQL script to populate the database
-        let script = std::fs::read_to_string("tests/populate_sqlite_test.sql").unwrap();
-        sqlx::query(&script).execute(&client.pool).await.unwrap();
+        let mut uids = Vec::new();
+        for minor in 0..4 {
+            let card = ModelCardRecord {
+                name: "concurrent_model".to_string(),
+                space: "stage_space".to_string(),
+                minor,
+                version: format!("1.{minor}.0"),
+                ..Default::default()
+            };
+            uids.push(card.uid.clone());
+            client
+                .insert_card(&CardTable::Model, &ServerCard::Model(card), &[])
+                .await
+                .unwrap();
+        }
 
-        // get unique space names
-        let repos = client
-            .get_unique_space_names(&CardTable::Model)
+        // every version is promoted to production at the same time
+        let promotions = uids.iter().map(|uid| {
+            let client = &client;
+            async move {
+                let plan = plan_stage_transition(
+                    client,
+                    &CardTable::Model,
+                    uid,
+                    &CardStage::Production,
+                    true,
+                )
+                .await?;
+                client
+                    .update_card_stages(&CardTable::Model, &plan, true, &[])
+                    .await
+            }
+        });
+        let results = futures::future::join_all(promotions).await;
+
+        assert!(results.iter().any(|result| result.is_ok()));
+        for result in &results {
+            assert!(matches!(result, Ok(()) | Err(SqlError::StageConflict(_))));
+        }
+
+        let holders = client
+            .get_stage_holders(
+                &CardTable::Model,
+                "stage_space",
+                "concurrent_model",
+                "production",
+            )
             .await
             .unwrap();
-
-        assert_eq!(repos.len(), 10);
+        assert_eq!(holders.len(), 1);
 
         cleanup();
     }
 
     #[tokio::test]
-    async fn test_sqlite_query_stats() {
+    async fn test_sqlite_card_stage_trashed() {
         cleanup();
 
         let config = DatabaseSettings {
@@ -1580,40 +3744,130 @@ mod tests {
 
         let client = SqliteClient::new(&config).await.unwrap();
 
-        // Run the SQL script to populate the database
-        let script = std::fs::read_to_string("tests/populate_sqlite_test.sql").unwrap();
-        sqlx::query(&script).execute(&client.pool).await.unwrap();
+        let mut uids = Vec::new();
+        for minor in 0..3 {
+            let card = ModelCardRecord {
+                name: "trashed_model".to_string(),
+                space: "stage_space".to_string(),
+                minor,
+                version: format!("1.{minor}.0"),
+                ..Default::default()
+            };
+            uids.push(card.uid.clone());
+            client
+                .insert_card(&CardTable::Model, &ServerCard::Model(card), &[])
+                .await
+                .unwrap();
+        }
 
-        // query stats
-        let stats = client
-            .query_stats(&CardTable::Model, None, None)
+        let plan = plan_stage_transition(
+            &client,
+            &CardTable::Model,
+            &uids[0],
+            &CardStage::Production,
+            true,
+        )
+        .await
+        .unwrap();
+        client
+            .update_card_stages(&CardTable::Model, &plan, true, &[])
             .await
             .unwrap();
 
-        assert_eq!(stats.nbr_names, 10);
-        assert_eq!(stats.nbr_versions, 10);
-        assert_eq!(stats.nbr_spaces, 10);
-
-        // query stats with search term
-        let stats = client
-            .query_stats(&CardTable::Model, Some("Model1"), None)
+        // a trashed card cannot be promoted, neither when planning nor when applying an older plan
+        let plan = plan_stage_transition(
+            &client,
+            &CardTable::Model,
+            &uids[2],
+            &CardStage::Staging,
+            true,
+        )
+        .await
+        .unwrap();
+        client
+            .soft_delete_card(&CardTable::Model, &uids[2], "admin", &[])
             .await
             .unwrap();
+        let result = client
+            .update_card_stages(&CardTable::Model, &plan, true, &[])
+            .await;
+        assert!(matches!(result, Err(SqlError::CardNotFound(_))));
+
+        let result = plan_stage_transition(
+            &client,
+            &CardTable::Model,
+            &uids[2],
+            &CardStage::Staging,
+            true,
+        )
+        .await;
+        assert!(matches!(result, Err(SqlError::CardNotFound(_))));
 
-        assert_eq!(stats.nbr_names, 2); // for Model1 and Model10
+        // a trashed production card is not a holder and is not archived by the next promotion
+        client
+            .soft_delete_card(&CardTable::Model, &uids[0], "admin", &[])
+            .await
+            .unwrap();
+        let plan = plan_stage_transition(
+            &client,
+            &CardTable::Model,
+            &uids[1],
+            &CardStage::Production,
+            true,
+        )
+        .await
+        .unwrap();
+        assert!(plan.archived.is_empty());
+        client
+            .update_card_stages(&CardTable::Model, &plan, true, &[])
+            .await
+            .unwrap();
 
-        let stats = client
-            .query_stats(&CardTable::Model, Some("Model1"), Some("repo1"))
+        // restoring the former production card archives it instead of adding a second holder
+        client
+            .restore_card(&CardTable::Model, &uids[0], &[])
+            .await
+            .unwrap();
+        let record = client
+            .get_card_stage(&CardTable::Model, &uids[0])
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(record.stage, CardStage::Archived.to_string());
+
+        let holders = client
+            .get_stage_holders(
+                &CardTable::Model,
+                "stage_space",
+                "trashed_model",
+                "production",
+            )
             .await
             .unwrap();
+        assert_eq!(holders.len(), 1);
+        assert_eq!(holders[0].uid, uids[1]);
 
-        assert_eq!(stats.nbr_names, 1); // for Model1
+        // a restored card keeps its stage when no live version has taken it
+        client
+            .soft_delete_card(&CardTable::Model, &uids[1], "admin", &[])
+            .await
+            .unwrap();
+        client
+            .restore_card(&CardTable::Model, &uids[1], &[])
+            .await
+            .unwrap();
+        let record = client
+            .get_card_stage(&CardTable::Model, &uids[1])
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(record.stage, CardStage::Production.to_string());
 
         cleanup();
     }
 
     #[tokio::test]
-    async fn test_sqlite_query_page() {
+    async fn test_sqlite_version_alias() {
         cleanup();
 
         let config = DatabaseSettings {
@@ -1624,67 +3878,134 @@ mod tests {
 
         let client = SqliteClient::new(&config).await.unwrap();
 
-        // Run the SQL script to populate the database
-        let script = std::fs::read_to_string("tests/populate_sqlite_test.sql").unwrap();
-        sqlx::query(&script).execute(&client.pool).await.unwrap();
+        let mut uids = Vec::new();
+        for minor in 0..2 {
+            let card = ModelCardRecord {
+                name: "alias_model".to_string(),
+                space: "alias_space".to_string(),
+                minor,
+                version: format!("1.{minor}.0"),
+                ..Default::default()
+            };
+            uids.push(card.uid.clone());
+            client
+                .insert_card(&CardTable::Model, &ServerCard::Model(card), &[])
+                .await
+                .unwrap();
+        }
 
-        // query page
-        let results = client
-            .query_page("name", 1, None, None, &CardTable::Data)
+        // aliases must point at an existing version and have a valid name
+        let missing = build_version_alias(
+            &client,
+            &RegistryType::Model,
+            "alias_space",
+            "alias_model",
+            "champion",
+            "2.0.0",
+            "admin",
+        )
+        .await;
+        assert!(matches!(missing, Err(SqlError::CardNotFound(_))));
+
+        let invalid = build_version_alias(
+            &client,
+            &RegistryType::Model,
+            "alias_space",
+            "alias_model",
+            "1.0.0",
+            "1.0.0",
+            "admin",
+        )
+        .await;
+        assert!(invalid.is_err());
+
+        // point the alias at 1.0.0, then move it to 1.1.0
+        for version in ["1.0.0", "1.1.0"] {
+            let alias = build_version_alias(
+                &client,
+                &RegistryType::Model,
+                "alias_space",
+                "alias_model",
+                "champion",
+                version,
+                "admin",
+            )
             .await
             .unwrap();
+            client.set_version_alias(&alias).await.unwrap();
+        }
 
-        assert_eq!(results.len(), 1);
-
-        // query page
-        let results = client
-            .query_page("name", 1, None, None, &CardTable::Model)
+        let alias = client
+            .get_version_alias("model", "alias_space", "alias_model", "champion")
             .await
+            .unwrap()
             .unwrap();
+        assert_eq!(alias.uid, uids[1]);
+        assert_eq!(alias.version, "1.1.0");
 
-        assert_eq!(results.len(), 10);
+        // resolve the alias in a card query
+        let card_args = CardQueryArgs {
+            space: Some("alias_space".to_string()),
+            name: Some("alias_model".to_string()),
+            version: Some("@champion".to_string()),
+            registry_type: RegistryType::Model,
+            ..Default::default()
+        };
+        let resolved = resolve_version_alias(&client, &card_args).await.unwrap();
+        assert_eq!(resolved.uid, Some(uids[1].clone()));
+        assert_eq!(resolved.version, None);
 
-        // query page
         let results = client
-            .query_page("name", 1, None, Some("repo3"), &CardTable::Model)
+            .query_cards(&CardTable::Model, &resolved)
             .await
             .unwrap();
-
         assert_eq!(results.len(), 1);
 
-        cleanup();
-    }
-
-    #[tokio::test]
-    async fn test_sqlite_version_page() {
-        cleanup();
-
-        let config = DatabaseSettings {
-            connection_uri: get_connection_uri(),
-            max_connections: 1,
-            sql_type: SqlType::Sqlite,
+        let unknown = CardQueryArgs {
+            version: Some("@unknown".to_string()),
+            ..card_args.clone()
         };
+        assert!(matches!(
+            resolve_version_alias(&client, &unknown).await,
+            Err(SqlError::VersionAliasNotFound(_))
+        ));
+
+        // delete the alias and check the history, newest first
+        assert!(client
+            .delete_version_alias("model", "alias_space", "alias_model", "champion", "admin")
+            .await
+            .unwrap());
+        assert!(!client
+            .delete_version_alias("model", "alias_space", "alias_model", "champion", "admin")
+            .await
+            .unwrap());
+        assert!(client
+            .get_version_aliases("model", "alias_space", "alias_model")
+            .await
+            .unwrap()
+            .is_empty());
 
-        let client = SqliteClient::new(&config).await.unwrap();
-
-        // Run the SQL script to populate the database
-        let script = std::fs::read_to_string("tests/populate_sqlite_test.sql").unwrap();
-        sqlx::query(&script).execute(&client.pool).await.unwrap();
-
-        // query page
-        let results = client
-            .version_page(1, Some("repo1"), Some("Model1"), &CardTable::Model)
+        let history = client
+            .get_version_alias_history("model", "alias_space", "alias_model", Some("champion"), 10)
             .await
             .unwrap();
-
-        assert_eq!(results.len(), 1);
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[0].version, None);
+        assert_eq!(history[0].previous_version, Some("1.1.0".to_string()));
+        assert_eq!(history[1].previous_version, Some("1.0.0".to_string()));
+        assert_eq!(history[2].previous_version, None);
+
+        let history = client
+            .get_version_alias_history("model", "alias_space", "alias_model", None, 1)
+            .await
+            .unwrap();
+        assert_eq!(history.len(), 1);
 
         cleanup();
     }
 
-    // test run metric
     #[tokio::test]
-    async fn test_sqlite_run_metric() {
+    async fn test_sqlite_card_soft_delete() {
         cleanup();
 
         let config = DatabaseSettings {
@@ -1695,101 +4016,85 @@ mod tests {
 
         let client = SqliteClient::new(&config).await.unwrap();
 
-        // Run the SQL script to populate the database
-        let script = std::fs::read_to_string("tests/populate_sqlite_test.sql").unwrap();
-
-        sqlx::query(&script).execute(&client.pool).await.unwrap();
-
-        let uid = "550e8400-e29b-41d4-a716-446655440000".to_string();
-        let metric_names = vec!["metric1", "metric2", "metric3"];
-
-        for name in metric_names {
-            let metric = MetricRecord {
-                experiment_uid: uid.clone(),
-                name: name.to_string(),
-                value: 1.0,
-                ..Default::default()
-            };
-
-            client.insert_experiment_metric(&metric).await.unwrap();
-        }
-
-        let records = client
-            .get_experiment_metric(&uid, &Vec::new())
+        let card = ModelCardRecord {
+            name: "trash_model".to_string(),
+            space: "trash_space".to_string(),
+            ..Default::default()
+        };
+        let uid = card.uid.clone();
+        client
+            .insert_card(&CardTable::Model, &ServerCard::Model(card), &[])
             .await
             .unwrap();
-        let names = client.get_experiment_metric_names(&uid).await.unwrap();
-
-        assert_eq!(records.len(), 3);
-
-        // assert names = "metric1"
-        assert_eq!(names.len(), 3);
-
-        // insert vec
-        let records = vec![
-            MetricRecord {
-                experiment_uid: uid.clone(),
-                name: "vec1".to_string(),
-                value: 1.0,
-                ..Default::default()
-            },
-            MetricRecord {
-                experiment_uid: uid.clone(),
-                name: "vec2".to_string(),
-                value: 1.0,
-                ..Default::default()
-            },
-        ];
 
-        client.insert_experiment_metrics(&records).await.unwrap();
+        let card_args = CardQueryArgs {
+            space: Some("trash_space".to_string()),
+            name: Some("trash_model".to_string()),
+            limit: Some(10),
+            ..Default::default()
+        };
 
-        let records = client
-            .get_experiment_metric(&uid, &Vec::new())
+        // deleted cards are hidden from queries
+        let (space, name) = client
+            .soft_delete_card(&CardTable::Model, &uid, "admin", &[])
             .await
             .unwrap();
+        assert_eq!(
+            (space.as_str(), name.as_str()),
+            ("trash_space", "trash_model")
+        );
 
-        assert_eq!(records.len(), 5);
-
-        cleanup();
-    }
-
-    #[tokio::test]
-    async fn test_sqlite_hardware_metric() {
-        cleanup();
-
-        let config = DatabaseSettings {
-            connection_uri: get_connection_uri(),
-            max_connections: 1,
-            sql_type: SqlType::Sqlite,
-        };
-
-        let client = SqliteClient::new(&config).await.unwrap();
-
-        // Run the SQL script to populate the database
-        let script = std::fs::read_to_string("tests/populate_sqlite_test.sql").unwrap();
-
-        sqlx::query(&script).execute(&client.pool).await.unwrap();
-
-        let uid = "550e8400-e29b-41d4-a716-446655440000".to_string();
+        let results = client
+            .query_cards(&CardTable::Model, &card_args)
+            .await
+            .unwrap();
+        assert!(results.is_empty());
 
-        // create a loop of 10
+        let deleted = client
+            .get_deleted_cards(&CardTable::Model, Some("trash_space"), None)
+            .await
+            .unwrap();
+        assert_eq!(deleted.len(), 1);
+        assert_eq!(deleted[0].uid, uid);
+        assert_eq!(deleted[0].deleted_by, "admin");
+
+        // only cards deleted before the cutoff are returned
+        let cutoff = get_utc_datetime() - chrono::Duration::days(1);
+        assert!(client
+            .get_deleted_cards(&CardTable::Model, None, Some(cutoff))
+            .await
+            .unwrap()
+            .is_empty());
 
-        let metric = HardwareMetricsRecord {
-            experiment_uid: uid.clone(),
-            created_at: get_utc_datetime(),
-            ..Default::default()
-        };
+        // a card can only be deleted once
+        assert!(matches!(
+            client
+                .soft_delete_card(&CardTable::Model, &uid, "admin", &[])
+                .await,
+            Err(SqlError::CardNotFound(_))
+        ));
 
-        client.insert_hardware_metrics(&metric).await.unwrap();
-        let records = client.get_hardware_metric(&uid).await.unwrap();
+        // restore the card
+        client
+            .restore_card(&CardTable::Model, &uid, &[])
+            .await
+            .unwrap();
 
-        assert_eq!(records.len(), 1);
+        let results = client
+            .query_cards(&CardTable::Model, &card_args)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(matches!(
+            client.restore_card(&CardTable::Model, &uid, &[]).await,
+            Err(SqlError::CardNotFound(_))
+        ));
 
         cleanup();
     }
 
     #[tokio::test]
-    async fn test_sqlite_parameter() {
+    async fn test_sqlite_card_search() {
         cleanup();
 
         let config = DatabaseSettings {
@@ -1800,45 +4105,161 @@ mod tests {
 
         let client = SqliteClient::new(&config).await.unwrap();
 
-        // Run the SQL script to populate the database
-        let script = std::fs::read_to_string("tests/populate_sqlite_test.sql").unwrap();
+        let model = ModelCardRecord {
+            name: "fraud_detector".to_string(),
+            space: "risk".to_string(),
+            tags: sqlx::types::Json(vec!["xgboost".to_string()]),
+            interface_type: "SklearnModel".to_string(),
+            username: "alice".to_string(),
+            ..Default::default()
+        };
+        let model_uid = model.uid.clone();
+        client
+            .insert_card(&CardTable::Model, &ServerCard::Model(model), &[])
+            .await
+            .unwrap();
 
-        sqlx::query(&script).execute(&client.pool).await.unwrap();
+        let data = DataCardRecord {
+            name: "transactions".to_string(),
+            space: "risk".to_string(),
+            username: "bob".to_string(),
+            ..Default::default()
+        };
+        let data_uid = data.uid.clone();
+        client
+            .insert_card(&CardTable::Data, &ServerCard::Data(data), &[])
+            .await
+            .unwrap();
 
-        let uid = "550e8400-e29b-41d4-a716-446655440000".to_string();
-        let mut params = vec![];
+        client
+            .update_search_readme(
+                &RegistryType::Model,
+                "risk",
+                "fraud_detector",
+                "Flags fraudulent card transactions",
+            )
+            .await
+            .unwrap();
 
-        // create a loop of 10
-        for i in 0..10 {
-            let parameter = ParameterRecord {
-                experiment_uid: uid.clone(),
-                name: format!("param{i}"),
-                ..Default::default()
-            };
+        let search = |query: &str| CardSearchQuery {
+            query: query.to_string(),
+            ..Default::default()
+        };
 
-            params.push(parameter.clone());
+        // name, tags, metadata and README content are searchable, with prefix matching
+        for query in ["fraud", "xgboost", "sklearn", "alice", "fraudulent card"] {
+            let results = client.search_cards(&search(query), None).await.unwrap();
+            assert_eq!(results.len(), 1, "{query}");
+            assert_eq!(results[0].uid, model_uid);
         }
 
-        client.insert_experiment_parameters(&params).await.unwrap();
-        let records = client
-            .get_experiment_parameter(&uid, &Vec::new())
+        let results = client
+            .search_cards(&search("transactions"), None)
             .await
             .unwrap();
+        assert_eq!(results.len(), 2);
+        // name matches rank above README matches
+        assert_eq!(results[0].uid, data_uid);
 
-        assert_eq!(records.len(), 10);
+        let results = client
+            .search_cards(
+                &CardSearchQuery {
+                    query: "transactions".to_string(),
+                    registry_type: Some(RegistryType::Data),
+                    ..Default::default()
+                },
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].uid, data_uid);
 
-        let param_records = client
-            .get_experiment_parameter(&uid, &["param1".to_string()])
+        // results can be restricted to a set of spaces
+        let spaces = ["risk".to_string()];
+        let results = client
+            .search_cards(&search("transactions"), Some(&spaces))
             .await
             .unwrap();
+        assert_eq!(results.len(), 2);
+
+        for spaces in [vec![], vec!["other".to_string()]] {
+            let results = client
+                .search_cards(&search("transactions"), Some(&spaces))
+                .await
+                .unwrap();
+            assert!(results.is_empty());
+        }
 
-        assert_eq!(param_records.len(), 1);
+        // new versions keep the README of the card
+        let model = ModelCardRecord {
+            name: "fraud_detector".to_string(),
+            space: "risk".to_string(),
+            major: 1,
+            minor: 1,
+            version: "1.1.0".to_string(),
+            ..Default::default()
+        };
+        let new_model_uid = model.uid.clone();
+        client
+            .insert_card(&CardTable::Model, &ServerCard::Model(model), &[])
+            .await
+            .unwrap();
+        assert_eq!(
+            client
+                .search_cards(&search("fraudulent"), None)
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+
+        // deleted cards are hidden until they are restored
+        client
+            .soft_delete_card(&CardTable::Model, &model_uid, "admin", &[])
+            .await
+            .unwrap();
+        let results = client
+            .search_cards(&search("fraudulent"), None)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].uid, new_model_uid);
+
+        client
+            .restore_card(&CardTable::Model, &model_uid, &[])
+            .await
+            .unwrap();
+        assert_eq!(
+            client
+                .search_cards(&search("fraudulent"), None)
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+
+        client
+            .delete_card(&CardTable::Model, &new_model_uid, &[])
+            .await
+            .unwrap();
+        let results = client
+            .search_cards(&search("fraudulent"), None)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].uid, model_uid);
+
+        assert!(matches!(
+            client.search_cards(&search(" -- "), None).await,
+            Err(SqlError::EmptySearchQuery)
+        ));
 
         cleanup();
     }
 
     #[tokio::test]
-    async fn test_sqlite_user() {
+    async fn test_sqlite_card_lineage() {
         cleanup();
 
         let config = DatabaseSettings {
@@ -1849,62 +4270,123 @@ mod tests {
 
         let client = SqliteClient::new(&config).await.unwrap();
 
-        let recovery_codes = vec!["recovery_code_1".to_string(), "recovery_code_2".to_string()];
-
-        let user = User::new(
-            "user".to_string(),
-            "pass".to_string(),
-            "email".to_string(),
-            recovery_codes,
-            None,
-            None,
-            None,
-            None,
-            None,
-        );
+        let data = DataCardRecord::default();
+        let data_uid = data.uid.clone();
+        let experiment = ExperimentCardRecord::default();
+        let experiment_uid = experiment.uid.clone();
 
-        let sso_user = User::new_from_sso("sso_user", "user@email.com");
+        let mut model = ModelCardRecord {
+            datacard_uid: Some(data_uid.clone()),
+            experimentcard_uid: Some(experiment_uid.clone()),
+            ..Default::default()
+        };
+        let model_uid = model.uid.clone();
 
-        client.insert_user(&user).await.unwrap();
-        client.insert_user(&sso_user).await.unwrap();
+        // the experiment also lists the model, which declares the same edge
+        let experiment = ExperimentCardRecord {
+            modelcard_uids: sqlx::types::Json(vec![model_uid.clone()]),
+            ..experiment
+        };
+        let service = ServiceCardRecord {
+            cards: sqlx::types::Json(vec![opsml_types::contracts::CardEntry {
+                registry_type: RegistryType::Model,
+                uid: model_uid.clone(),
+                version: model.version.clone(),
+                alias: "model".to_string(),
+            }]),
+            ..Default::default()
+        };
+        let service_uid = service.uid.clone();
 
-        let mut user_to_update = client.get_user("user", None).await.unwrap().unwrap();
+        client
+            .insert_card(&CardTable::Data, &ServerCard::Data(data), &[])
+            .await
+            .unwrap();
+        client
+            .insert_card(
+                &CardTable::Experiment,
+                &ServerCard::Experiment(experiment),
+                &[],
+            )
+            .await
+            .unwrap();
+        client
+            .insert_card(&CardTable::Model, &ServerCard::Model(model.clone()), &[])
+            .await
+            .unwrap();
+        client
+            .insert_card(&CardTable::Service, &ServerCard::Service(service), &[])
+            .await
+            .unwrap();
 
-        assert_eq!(user_to_update.username, "user");
-        assert_eq!(user_to_update.password_hash, "pass");
-        assert_eq!(user_to_update.group_permissions, vec!["user"]);
-        assert_eq!(user_to_update.email, "email");
+        let edges = |edges: Vec<CardLineageEdge>| {
+            let mut edges: Vec<(String, String)> = edges
+                .into_iter()
+                .map(|edge| (edge.source_uid, edge.target_uid))
+                .collect();
+            edges.sort();
+            edges
+        };
+        let edge = |source: &str, target: &str| (source.to_string(), target.to_string());
 
-        // update user
-        user_to_update.active = false;
-        user_to_update.refresh_token = Some("token".to_string());
+        // both directions
+        let mut expected = vec![
+            edge(&data_uid, &model_uid),
+            edge(&experiment_uid, &model_uid),
+            edge(&model_uid, &service_uid),
+        ];
+        expected.sort();
+        assert_eq!(
+            edges(client.get_lineage_edges(&model_uid, 3, 3).await.unwrap()),
+            expected
+        );
 
-        client.update_user(&user_to_update).await.unwrap();
-        let user = client.get_user("user", None).await.unwrap().unwrap();
+        let nodes = client.get_lineage_nodes(&model_uid, 3, 3).await.unwrap();
+        assert_eq!(nodes.len(), 4);
+        let node = nodes.iter().find(|node| node.uid == service_uid).unwrap();
+        assert_eq!(node.registry_type, "service");
 
-        assert!(!user.active);
-        assert_eq!(user.refresh_token.unwrap(), "token");
+        // downstream is limited by depth
+        assert_eq!(
+            edges(client.get_lineage_edges(&data_uid, 0, 1).await.unwrap()),
+            vec![edge(&data_uid, &model_uid)]
+        );
+        assert_eq!(
+            client
+                .get_lineage_nodes(&data_uid, 0, 2)
+                .await
+                .unwrap()
+                .len(),
+            3
+        );
 
-        // get users
-        let users = client.get_users().await.unwrap();
-        assert_eq!(users.len(), 2);
+        // upstream walks back from the service to the data and experiment
+        assert_eq!(
+            edges(client.get_lineage_edges(&service_uid, 2, 0).await.unwrap()),
+            expected
+        );
 
-        let user = client
-            .get_user("sso_user", Some("sso"))
+        // updating a card replaces the edges it declared
+        model.datacard_uid = None;
+        client
+            .update_card(&CardTable::Model, &ServerCard::Model(model), &[])
             .await
-            .unwrap()
             .unwrap();
-        assert!(user.active);
-
-        // delete
-        client.delete_user("sso_user").await.unwrap();
-
-        // get last admin
-        let is_last_admin = client.is_last_admin("user").await.unwrap();
-        assert!(!is_last_admin);
+        assert_eq!(
+            edges(client.get_lineage_edges(&model_uid, 1, 0).await.unwrap()),
+            vec![edge(&experiment_uid, &model_uid)]
+        );
 
-        // delete
-        client.delete_user("user").await.unwrap();
+        // purged cards leave the graph
+        client
+            .delete_card(&CardTable::Service, &service_uid, &[])
+            .await
+            .unwrap();
+        assert!(client
+            .get_lineage_edges(&model_uid, 0, 3)
+            .await
+            .unwrap()
+            .is_empty());
 
         cleanup();
     }
@@ -1929,6 +4411,7 @@ mod tests {
             registry_type: RegistryType::Data,
             encrypted_key: encrypted_key.clone(),
             storage_key: "opsml_registry".to_string(),
+            has_manifest: true,
         };
 
         client.insert_artifact_key(&key).await.unwrap();
@@ -1948,6 +4431,7 @@ mod tests {
             registry_type: RegistryType::Data,
             encrypted_key: encrypted_key.clone(),
             storage_key: "opsml_registry".to_string(),
+            has_manifest: true,
         };
 
         client.update_artifact_key(&key).await.unwrap();
@@ -1987,6 +4471,158 @@ mod tests {
         assert_eq!(result, "guest");
     }
 
+    #[tokio::test]
+    async fn test_sqlite_audit_chain() {
+        cleanup();
+
+        let config = DatabaseSettings {
+            connection_uri: get_connection_uri(),
+            max_connections: 1,
+            sql_type: SqlType::Sqlite,
+        };
+
+        let client = SqliteClient::new(&config).await.unwrap();
+
+        for user in ["user_1", "user_2", "user_3"] {
+            let event = AuditEvent {
+                username: user.to_string(),
+                ..Default::default()
+            };
+            client.insert_audit_event(event).await.unwrap();
+        }
+
+        let report = verify_audit_chain(&client, 2).await.unwrap();
+        assert!(report.verified);
+        assert_eq!(report.events_checked, 3);
+
+        let start = get_utc_datetime() - chrono::Duration::hours(1);
+        let end = get_utc_datetime() + chrono::Duration::hours(1);
+        let records = client
+            .get_audit_events_by_time(&start, &end, 0, 10)
+            .await
+            .unwrap();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[2].seq, Some(3));
+
+        // delete the most recent row
+        sqlx::query("DELETE FROM opsml_audit_event WHERE username = 'user_3'")
+            .execute(&client.pool)
+            .await
+            .unwrap();
+
+        let report = verify_audit_chain(&client, 2).await.unwrap();
+        assert!(!report.verified);
+        assert_eq!(report.events_checked, 2);
+        assert!(report
+            .first_break
+            .unwrap()
+            .reason
+            .contains("deleted from the end"));
+
+        // tamper with a row
+        let tampered_id = records[1].id;
+        sqlx::query("UPDATE opsml_audit_event SET username = 'mallory' WHERE username = 'user_2'")
+            .execute(&client.pool)
+            .await
+            .unwrap();
+
+        let report = verify_audit_chain(&client, 2).await.unwrap();
+        assert!(!report.verified);
+        assert_eq!(report.first_break.unwrap().id, tampered_id);
+
+        cleanup();
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_get_audit_events() {
+        cleanup();
+
+        let config = DatabaseSettings {
+            connection_uri: get_connection_uri(),
+            max_connections: 1,
+            sql_type: SqlType::Sqlite,
+        };
+
+        let client = SqliteClient::new(&config).await.unwrap();
+
+        let events = [
+            ("user_1", "space_a", Operation::Create, AuditStatus::Success),
+            ("user_2", "space_b", Operation::Delete, AuditStatus::Failed),
+            ("user_1", "space_b", Operation::Update, AuditStatus::Success),
+        ];
+
+        for (user, space, operation, status) in events {
+            let event = AuditEvent {
+                username: user.to_string(),
+                space: Some(space.to_string()),
+                operation,
+                status,
+                ..Default::default()
+            };
+            client.insert_audit_event(event).await.unwrap();
+        }
+
+        // events with a space are still part of the chain
+        let report = verify_audit_chain(&client, 10).await.unwrap();
+        assert!(report.verified);
+
+        // newest first
+        let records = client
+            .get_audit_events(&AuditEventQueryArgs::default())
+            .await
+            .unwrap();
+        assert_eq!(records.len(), 3);
+        assert!(records[0].id > records[2].id);
+
+        let args = AuditEventQueryArgs {
+            username: Some("user_1".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(client.get_audit_events(&args).await.unwrap().len(), 2);
+
+        let args = AuditEventQueryArgs {
+            space: Some("space_b".to_string()),
+            status: Some(AuditStatus::Success),
+            ..Default::default()
+        };
+        let records = client.get_audit_events(&args).await.unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].operation, "Update");
+
+        let args = AuditEventQueryArgs {
+            operation: Some(Operation::Delete),
+            registry_type: Some(RegistryType::Model),
+            start_time: Some(get_utc_datetime() - chrono::Duration::hours(1)),
+            end_time: Some(get_utc_datetime() + chrono::Duration::hours(1)),
+            ..Default::default()
+        };
+        let records = client.get_audit_events(&args).await.unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].username, "user_2");
+
+        // time window excludes everything
+        let args = AuditEventQueryArgs {
+            end_time: Some(get_utc_datetime() - chrono::Duration::hours(1)),
+            ..Default::default()
+        };
+        assert!(client.get_audit_events(&args).await.unwrap().is_empty());
+
+        // paginate with the cursor
+        let mut args = AuditEventQueryArgs {
+            limit: Some(2),
+            ..Default::default()
+        };
+        let first_page = client.get_audit_events(&args).await.unwrap();
+        assert_eq!(first_page.len(), 2);
+
+        args.cursor = Some(first_page.last().unwrap().id);
+        let second_page = client.get_audit_events(&args).await.unwrap();
+        assert_eq!(second_page.len(), 1);
+        assert_eq!(second_page[0].username, "user_1");
+
+        cleanup();
+    }
+
     #[tokio::test]
     async fn test_sqlite_get_load_card_key() {
         cleanup();
@@ -2001,7 +4637,10 @@ mod tests {
         let data_card = DataCardRecord::default();
         let card = ServerCard::Data(data_card.clone());
 
-        client.insert_card(&CardTable::Data, &card).await.unwrap();
+        client
+            .insert_card(&CardTable::Data, &card, &[])
+            .await
+            .unwrap();
         let encrypted_key: Vec<u8> = (0..32).collect();
         let key = ArtifactKey {
             uid: data_card.uid.clone(),
@@ -2009,6 +4648,7 @@ mod tests {
             registry_type: RegistryType::Data,
             encrypted_key: encrypted_key.clone(),
             storage_key: "opsml_registry".to_string(),
+            has_manifest: true,
         };
 
         client.insert_artifact_key(&key).await.unwrap();
@@ -2055,12 +4695,18 @@ mod tests {
         // insert datacard
         let data_card = DataCardRecord::default();
         let card = ServerCard::Data(data_card.clone());
-        client.insert_card(&CardTable::Data, &card).await.unwrap();
+        client
+            .insert_card(&CardTable::Data, &card, &[])
+            .await
+            .unwrap();
 
         // insert modelcard
         let model_card = ModelCardRecord::default();
         let card = ServerCard::Model(model_card.clone());
-        client.insert_card(&CardTable::Model, &card).await.unwrap();
+        client
+            .insert_card(&CardTable::Model, &card, &[])
+            .await
+            .unwrap();
 
         let space_event = SpaceNameEvent {
             space: data_card.space.clone(),
@@ -2088,7 +4734,10 @@ mod tests {
             ..Default::default()
         };
         let card = ServerCard::Model(model_card2.clone());
-        client.insert_card(&CardTable::Model, &card).await.unwrap();
+        client
+            .insert_card(&CardTable::Model, &card, &[])
+            .await
+            .unwrap();
 
         // update space stats again
         let space_event = SpaceNameEvent {
@@ -0,0 +1,13 @@
+-- Personal access tokens and service account tokens
+CREATE TABLE IF NOT EXISTS opsml_api_token (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    username TEXT NOT NULL,
+    name TEXT NOT NULL,
+    hashed_token TEXT NOT NULL UNIQUE,
+    scopes TEXT NOT NULL DEFAULT '[]',
+    expires_at TIMESTAMP,
+    revoked BOOLEAN NOT NULL DEFAULT FALSE
+);
+
+CREATE INDEX IF NOT EXISTS idx_opsml_api_token_username ON opsml_api_token (username);
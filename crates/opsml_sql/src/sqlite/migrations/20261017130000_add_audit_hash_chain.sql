@@ -0,0 +1,3 @@
+-- Hash chain columns for tamper-evident audit events
+ALTER TABLE opsml_audit_event ADD COLUMN prev_hash TEXT;
+ALTER TABLE opsml_audit_event ADD COLUMN hash TEXT;
@@ -0,0 +1,27 @@
+-- Webhook endpoints notified on card lifecycle events
+CREATE TABLE IF NOT EXISTS opsml_webhook (
+    uid TEXT PRIMARY KEY,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    url TEXT NOT NULL,
+    space TEXT,
+    event_types TEXT NOT NULL DEFAULT '[]',
+    encrypted_secret BLOB NOT NULL,
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_by TEXT NOT NULL
+);
+
+-- Delivery log for webhook requests
+CREATE TABLE IF NOT EXISTS opsml_webhook_delivery (
+    uid TEXT PRIMARY KEY,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    webhook_uid TEXT NOT NULL,
+    event_type TEXT NOT NULL,
+    payload TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending',
+    attempts INTEGER NOT NULL DEFAULT 0,
+    response_status INTEGER,
+    error_message TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_opsml_webhook_delivery_webhook_uid ON opsml_webhook_delivery (webhook_uid, created_at);
@@ -0,0 +1,14 @@
+-- Server events written in the same transaction as the change that produced them
+CREATE TABLE IF NOT EXISTS opsml_event_outbox (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    event_type TEXT NOT NULL,
+    payload TEXT NOT NULL
+);
+
+-- Last outbox event processed by each consumer
+CREATE TABLE IF NOT EXISTS opsml_event_offset (
+    consumer TEXT PRIMARY KEY,
+    last_id INTEGER NOT NULL,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
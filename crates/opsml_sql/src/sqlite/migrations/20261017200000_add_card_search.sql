@@ -0,0 +1,32 @@
+-- Full-text search index over card names, spaces, tags, metadata and README content
+CREATE VIRTUAL TABLE IF NOT EXISTS opsml_card_search USING fts5(
+    uid UNINDEXED,
+    registry_type UNINDEXED,
+    version UNINDEXED,
+    created_at UNINDEXED,
+    deleted UNINDEXED,
+    name,
+    space,
+    tags,
+    metadata,
+    readme
+);
+
+-- Index existing cards. README content is indexed the next time a README is saved
+INSERT INTO opsml_card_search (uid, registry_type, version, created_at, deleted, name, space, tags, metadata, readme)
+SELECT uid, 'data', version, created_at, deleted_at IS NOT NULL, name, space, COALESCE(tags, '[]'), COALESCE(username, '') || ' ' || COALESCE(interface_type, '') || ' ' || COALESCE(data_type, ''), '' FROM opsml_data_registry;
+
+INSERT INTO opsml_card_search (uid, registry_type, version, created_at, deleted, name, space, tags, metadata, readme)
+SELECT uid, 'model', version, created_at, deleted_at IS NOT NULL, name, space, COALESCE(tags, '[]'), COALESCE(username, '') || ' ' || COALESCE(interface_type, '') || ' ' || COALESCE(data_type, '') || ' ' || COALESCE(model_type, '') || ' ' || COALESCE(task_type, ''), '' FROM opsml_model_registry;
+
+INSERT INTO opsml_card_search (uid, registry_type, version, created_at, deleted, name, space, tags, metadata, readme)
+SELECT uid, 'experiment', version, created_at, deleted_at IS NOT NULL, name, space, COALESCE(tags, '[]'), COALESCE(username, ''), '' FROM opsml_experiment_registry;
+
+INSERT INTO opsml_card_search (uid, registry_type, version, created_at, deleted, name, space, tags, metadata, readme)
+SELECT uid, 'audit', version, created_at, deleted_at IS NOT NULL, name, space, COALESCE(tags, '[]'), COALESCE(username, ''), '' FROM opsml_audit_registry;
+
+INSERT INTO opsml_card_search (uid, registry_type, version, created_at, deleted, name, space, tags, metadata, readme)
+SELECT uid, 'prompt', version, created_at, deleted_at IS NOT NULL, name, space, COALESCE(tags, '[]'), COALESCE(username, ''), '' FROM opsml_prompt_registry;
+
+INSERT INTO opsml_card_search (uid, registry_type, version, created_at, deleted, name, space, tags, metadata, readme)
+SELECT uid, 'service', version, created_at, deleted_at IS NOT NULL, name, space, '[]', COALESCE(username, ''), '' FROM opsml_service_registry;
\ No newline at end of file
@@ -0,0 +1,73 @@
+-- Lineage edges between cards. An edge points from an upstream card to a card that depends on it
+-- and belongs to the card whose record declared the reference
+CREATE TABLE IF NOT EXISTS opsml_card_lineage (
+    card_uid TEXT NOT NULL,
+    source_uid TEXT NOT NULL,
+    target_uid TEXT NOT NULL,
+    PRIMARY KEY (card_uid, source_uid, target_uid)
+);
+
+CREATE INDEX IF NOT EXISTS idx_opsml_card_lineage_source ON opsml_card_lineage (source_uid);
+CREATE INDEX IF NOT EXISTS idx_opsml_card_lineage_target ON opsml_card_lineage (target_uid);
+
+-- Cards registered during an experiment
+INSERT OR IGNORE INTO opsml_card_lineage (card_uid, source_uid, target_uid)
+SELECT uid, experimentcard_uid, uid FROM opsml_data_registry WHERE experimentcard_uid IS NOT NULL AND experimentcard_uid != '';
+
+INSERT OR IGNORE INTO opsml_card_lineage (card_uid, source_uid, target_uid)
+SELECT uid, experimentcard_uid, uid FROM opsml_model_registry WHERE experimentcard_uid IS NOT NULL AND experimentcard_uid != '';
+
+INSERT OR IGNORE INTO opsml_card_lineage (card_uid, source_uid, target_uid)
+SELECT uid, experimentcard_uid, uid FROM opsml_prompt_registry WHERE experimentcard_uid IS NOT NULL AND experimentcard_uid != '';
+
+INSERT OR IGNORE INTO opsml_card_lineage (card_uid, source_uid, target_uid)
+SELECT experiment.uid, experiment.uid, card.value
+FROM opsml_experiment_registry AS experiment, json_each(experiment.datacard_uids) AS card;
+
+INSERT OR IGNORE INTO opsml_card_lineage (card_uid, source_uid, target_uid)
+SELECT experiment.uid, experiment.uid, card.value
+FROM opsml_experiment_registry AS experiment, json_each(experiment.modelcard_uids) AS card;
+
+INSERT OR IGNORE INTO opsml_card_lineage (card_uid, source_uid, target_uid)
+SELECT experiment.uid, experiment.uid, card.value
+FROM opsml_experiment_registry AS experiment, json_each(experiment.promptcard_uids) AS card;
+
+INSERT OR IGNORE INTO opsml_card_lineage (card_uid, source_uid, target_uid)
+SELECT experiment.uid, experiment.uid, card.value
+FROM opsml_experiment_registry AS experiment, json_each(experiment.service_card_uids) AS card;
+
+INSERT OR IGNORE INTO opsml_card_lineage (card_uid, source_uid, target_uid)
+SELECT experiment.uid, experiment.uid, card.value
+FROM opsml_experiment_registry AS experiment, json_each(experiment.experimentcard_uids) AS card;
+
+-- Data used to train a model
+INSERT OR IGNORE INTO opsml_card_lineage (card_uid, source_uid, target_uid)
+SELECT uid, datacard_uid, uid FROM opsml_model_registry WHERE datacard_uid IS NOT NULL AND datacard_uid != '';
+
+-- Cards included in a service
+INSERT OR IGNORE INTO opsml_card_lineage (card_uid, source_uid, target_uid)
+SELECT service.uid, json_extract(card.value, '$.uid'), service.uid
+FROM opsml_service_registry AS service, json_each(service.cards) AS card
+WHERE json_extract(card.value, '$.uid') IS NOT NULL;
+
+-- Cards covered by an audit
+INSERT OR IGNORE INTO opsml_card_lineage (card_uid, source_uid, target_uid)
+SELECT uid, uid, auditcard_uid FROM opsml_data_registry WHERE auditcard_uid IS NOT NULL AND auditcard_uid != '';
+
+INSERT OR IGNORE INTO opsml_card_lineage (card_uid, source_uid, target_uid)
+SELECT uid, uid, auditcard_uid FROM opsml_model_registry WHERE auditcard_uid IS NOT NULL AND auditcard_uid != '';
+
+INSERT OR IGNORE INTO opsml_card_lineage (card_uid, source_uid, target_uid)
+SELECT uid, uid, auditcard_uid FROM opsml_prompt_registry WHERE auditcard_uid IS NOT NULL AND auditcard_uid != '';
+
+INSERT OR IGNORE INTO opsml_card_lineage (card_uid, source_uid, target_uid)
+SELECT audit.uid, card.value, audit.uid
+FROM opsml_audit_registry AS audit, json_each(audit.datacard_uids) AS card;
+
+INSERT OR IGNORE INTO opsml_card_lineage (card_uid, source_uid, target_uid)
+SELECT audit.uid, card.value, audit.uid
+FROM opsml_audit_registry AS audit, json_each(audit.modelcard_uids) AS card;
+
+INSERT OR IGNORE INTO opsml_card_lineage (card_uid, source_uid, target_uid)
+SELECT audit.uid, card.value, audit.uid
+FROM opsml_audit_registry AS audit, json_each(audit.experimentcard_uids) AS card;
\ No newline at end of file
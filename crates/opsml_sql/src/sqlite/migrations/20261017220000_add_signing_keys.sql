@@ -0,0 +1,11 @@
+-- Public keys trusted to sign card manifests, per space
+CREATE TABLE IF NOT EXISTS opsml_signing_key (
+    key_id TEXT PRIMARY KEY,
+    space TEXT NOT NULL,
+    public_key TEXT NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    created_by TEXT NOT NULL,
+    active BOOLEAN NOT NULL DEFAULT TRUE
+);
+
+CREATE INDEX IF NOT EXISTS idx_opsml_signing_key_space ON opsml_signing_key (space);
@@ -0,0 +1,5 @@
+-- Outbox rows are claimed by a dispatcher with a lease and deleted by id once delivered
+ALTER TABLE opsml_event_outbox ADD COLUMN claimed_by TEXT;
+ALTER TABLE opsml_event_outbox ADD COLUMN claimed_until TIMESTAMP;
+
+DROP TABLE IF EXISTS opsml_event_offset;
@@ -0,0 +1,2 @@
+-- Keys created before card manifests were recorded have none, so their artifacts are not verified
+ALTER TABLE opsml_artifact_key ADD COLUMN has_manifest BOOLEAN NOT NULL DEFAULT FALSE;
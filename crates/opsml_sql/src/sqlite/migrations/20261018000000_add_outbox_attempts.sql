@@ -0,0 +1,4 @@
+-- Failed deliveries are counted, and events that keep failing are parked so they stop blocking the outbox
+ALTER TABLE opsml_event_outbox ADD COLUMN attempts INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE opsml_event_outbox ADD COLUMN last_error TEXT;
+ALTER TABLE opsml_event_outbox ADD COLUMN parked_at TIMESTAMP;
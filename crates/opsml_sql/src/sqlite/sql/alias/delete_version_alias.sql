@@ -0,0 +1 @@
+DELETE FROM opsml_version_alias WHERE registry_type = ? AND space = ? AND name = ? AND alias = ?;
\ No newline at end of file
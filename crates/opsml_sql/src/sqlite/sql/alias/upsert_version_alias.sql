@@ -0,0 +1 @@
+INSERT INTO opsml_version_alias (registry_type, space, name, alias, uid, version, updated_at, updated_by) VALUES (?, ?, ?, ?, ?, ?, ?, ?) ON CONFLICT (registry_type, space, name, alias) DO UPDATE SET uid = excluded.uid, version = excluded.version, updated_at = excluded.updated_at, updated_by = excluded.updated_by;
\ No newline at end of file
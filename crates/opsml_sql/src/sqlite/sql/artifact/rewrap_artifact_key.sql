@@ -0,0 +1 @@
+UPDATE opsml_artifact_key SET encrypted_key = ? WHERE uid = ? AND registry_type = ?;
\ No newline at end of file
@@ -0,0 +1,31 @@
+SELECT
+    id,
+    created_at,
+    username,
+    client_ip,
+    user_agent,
+    operation,
+    resource_type,
+    resource_id,
+    access_location,
+    status,
+    error_message,
+    metadata,
+    registry_type,
+    route,
+    prev_hash,
+    hash,
+    space,
+    seq
+FROM opsml_audit_event
+WHERE (?1 IS NULL OR username = ?1)
+  AND (?2 IS NULL OR space = ?2)
+  AND (?3 IS NULL OR registry_type = ?3)
+  AND (?4 IS NULL OR operation = ?4)
+  AND (?5 IS NULL OR status = ?5)
+  AND (?6 IS NULL OR resource_id = ?6)
+  AND (?7 IS NULL OR datetime(created_at) >= datetime(?7))
+  AND (?8 IS NULL OR datetime(created_at) <= datetime(?8))
+  AND (?9 IS NULL OR id < ?9)
+ORDER BY id DESC
+LIMIT ?10;
\ No newline at end of file
@@ -16,6 +16,7 @@ INSERT INTO opsml_experiment_registry (
     pre_tag, 
     build_tag,
     username,
-    opsml_version
+    opsml_version,
+    created_at
 ) 
-VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
\ No newline at end of file
+VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, DATETIME(?))
\ No newline at end of file
@@ -0,0 +1 @@
+DELETE FROM opsml_card_lineage WHERE card_uid = ?;
\ No newline at end of file
@@ -0,0 +1 @@
+INSERT OR IGNORE INTO opsml_card_lineage (card_uid, source_uid, target_uid) VALUES (?, ?, ?);
\ No newline at end of file
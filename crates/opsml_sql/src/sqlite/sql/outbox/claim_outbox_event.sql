@@ -0,0 +1 @@
+UPDATE opsml_event_outbox SET claimed_by = ?, claimed_until = ? WHERE id = ?;
\ No newline at end of file
@@ -0,0 +1,6 @@
+SELECT id, created_at, event_type, payload
+FROM opsml_event_outbox
+WHERE parked_at IS NULL
+  AND (claimed_until IS NULL OR datetime(claimed_until) < datetime(?))
+ORDER BY id ASC
+LIMIT ?;
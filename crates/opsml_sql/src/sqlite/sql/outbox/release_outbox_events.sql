@@ -0,0 +1 @@
+UPDATE opsml_event_outbox SET claimed_by = NULL, claimed_until = NULL WHERE claimed_by = ?;
\ No newline at end of file
@@ -0,0 +1 @@
+INSERT INTO opsml_card_search (uid, registry_type, version, created_at, deleted, name, space, tags, metadata, readme) VALUES (?, ?, ?, ?, 0, ?, ?, ?, ?, ?);
\ No newline at end of file
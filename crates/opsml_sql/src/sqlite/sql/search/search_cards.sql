@@ -0,0 +1 @@
+SELECT uid, registry_type, space, name, version, tags, created_at, -bm25(opsml_card_search, 0.0, 0.0, 0.0, 0.0, 0.0, 10.0, 10.0, 5.0, 2.0, 1.0) AS score FROM opsml_card_search WHERE opsml_card_search MATCH ?1 AND deleted = 0 AND (?2 IS NULL OR registry_type = ?2) AND (?3 IS NULL OR space = ?3) AND (?4 IS NULL OR space IN (SELECT value FROM json_each(?4))) ORDER BY score DESC, created_at DESC LIMIT ?5;
\ No newline at end of file
@@ -0,0 +1 @@
+SELECT id, created_at, username, name, hashed_token, scopes, expires_at, revoked FROM opsml_api_token WHERE username = ? ORDER BY created_at DESC;
\ No newline at end of file
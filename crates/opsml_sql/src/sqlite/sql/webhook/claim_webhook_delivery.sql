@@ -0,0 +1 @@
+UPDATE opsml_webhook_delivery SET updated_at = ? WHERE uid = ?;
\ No newline at end of file
@@ -0,0 +1 @@
+DELETE FROM opsml_webhook WHERE uid = ?;
\ No newline at end of file
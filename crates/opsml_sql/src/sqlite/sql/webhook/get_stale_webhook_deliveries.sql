@@ -0,0 +1,6 @@
+SELECT uid, created_at, updated_at, webhook_uid, event_type, payload, status, attempts, response_status, error_message
+FROM opsml_webhook_delivery
+WHERE status = ?
+  AND datetime(updated_at) < datetime(?)
+ORDER BY created_at ASC
+LIMIT ?;
\ No newline at end of file
@@ -0,0 +1 @@
+INSERT INTO opsml_webhook_delivery (uid, created_at, updated_at, webhook_uid, event_type, payload, status, attempts, response_status, error_message) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?);
\ No newline at end of file
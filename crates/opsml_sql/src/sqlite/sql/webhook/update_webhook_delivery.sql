@@ -0,0 +1 @@
+UPDATE opsml_webhook_delivery SET updated_at = ?, status = ?, attempts = ?, response_status = ?, error_message = ? WHERE uid = ?;
\ No newline at end of file
@@ -0,0 +1 @@
+UPDATE opsml_webhook SET encrypted_secret = ? WHERE uid = ?;
\ No newline at end of file
@@ -0,0 +1,152 @@
+use crate::base::SqlClient;
+use crate::error::SqlError;
+use crate::schemas::schema::CardStageRecord;
+use opsml_types::cards::CardTable;
+use opsml_types::contracts::{CardStageChange, CardStageResponse};
+use opsml_types::CardStage;
+use tracing::instrument;
+
+/// Works out the stage changes needed to move a card version to `stage`.
+///
+/// When `archive_existing` is set and the stage can only be held by one version (staging and
+/// production), the versions that currently hold the stage are moved to archived.
+/// Nothing is written; apply the result with `SqlClient::update_card_stages`, which checks the plan
+/// against the stages it reads inside its write transaction.
+///
+/// # Arguments
+///
+/// * `client` - The sql client
+/// * `table` - The card table. Must be the model or service table
+/// * `uid` - The uid of the card version to transition
+/// * `stage` - The new stage
+/// * `archive_existing` - Archive other versions that hold the stage
+///
+/// # Returns
+///
+/// * `CardStageResponse` - The requested change and any versions that will be archived
+#[instrument(skip_all)]
+pub async fn plan_stage_transition(
+    client: &impl SqlClient,
+    table: &CardTable,
+    uid: &str,
+    stage: &CardStage,
+    archive_existing: bool,
+) -> Result<CardStageResponse, SqlError> {
+    if !matches!(table, CardTable::Model | CardTable::Service) {
+        return Err(SqlError::StageNotSupported);
+    }
+
+    let record = client
+        .get_card_stage(table, uid)
+        .await?
+        .ok_or_else(|| SqlError::CardNotFound(uid.to_string()))?;
+
+    let mut archived = Vec::new();
+    if archive_existing && stage.is_exclusive() {
+        let holders = client
+            .get_stage_holders(table, &record.space, &record.name, &stage.to_string())
+            .await?;
+
+        archived = holders
+            .into_iter()
+            .filter(|holder| holder.uid != record.uid)
+            .map(|holder| CardStageChange {
+                previous_stage: stage.clone(),
+                stage: CardStage::Archived,
+                uid: holder.uid,
+                version: holder.version,
+            })
+            .collect();
+    }
+
+    Ok(CardStageResponse {
+        change: CardStageChange {
+            previous_stage: CardStage::from_string(&record.stage).unwrap_or_default(),
+            stage: stage.clone(),
+            uid: record.uid,
+            version: record.version,
+        },
+        space: record.space,
+        name: record.name,
+        archived,
+    })
+}
+
+/// Checks a planned transition against the current stages of the card versions, read inside the
+/// write transaction. Returns `SqlError::StageConflict` when the target version or the holders of
+/// an exclusive stage changed after the transition was planned.
+///
+/// # Arguments
+///
+/// * `transition` - The planned transition
+/// * `versions` - All versions of the card with their current stage
+/// * `archive_existing` - Whether the plan archives other versions that hold the stage
+pub fn verify_stage_transition(
+    transition: &CardStageResponse,
+    versions: &[CardStageRecord],
+    archive_existing: bool,
+) -> Result<(), SqlError> {
+    let uid = &transition.change.uid;
+    let record = versions
+        .iter()
+        .find(|version| &version.uid == uid)
+        .ok_or_else(|| SqlError::CardNotFound(uid.clone()))?;
+
+    if CardStage::from_string(&record.stage).unwrap_or_default() != transition.change.previous_stage
+    {
+        return Err(SqlError::StageConflict(uid.clone()));
+    }
+
+    if archive_existing && transition.change.stage.is_exclusive() {
+        let stage = transition.change.stage.to_string();
+        let mut holders = versions
+            .iter()
+            .filter(|version| version.stage == stage && &version.uid != uid)
+            .map(|version| version.uid.as_str())
+            .collect::<Vec<_>>();
+        let mut planned = transition
+            .archived
+            .iter()
+            .map(|change| change.uid.as_str())
+            .collect::<Vec<_>>();
+        holders.sort_unstable();
+        planned.sort_unstable();
+
+        if holders != planned {
+            return Err(SqlError::StageConflict(uid.clone()));
+        }
+    }
+
+    Ok(())
+}
+
+/// Converts a planned transition into the stage updates to write
+pub fn stage_updates(response: &CardStageResponse) -> Vec<CardStageRecord> {
+    std::iter::once(&response.change)
+        .chain(response.archived.iter())
+        .map(|change| CardStageRecord {
+            uid: change.uid.clone(),
+            space: response.space.clone(),
+            name: response.name.clone(),
+            version: change.version.clone(),
+            stage: change.stage.to_string(),
+        })
+        .collect()
+}
+
+/// Returns the stage a restored card version must be moved to, if any. A version trashed while
+/// holding an exclusive stage is archived when a live version has taken the stage since, so
+/// restoring it cannot leave two holders.
+///
+/// # Arguments
+///
+/// * `stage` - The stage of the restored version
+/// * `versions` - The live versions of the card with their current stage
+pub fn restored_stage(stage: &str, versions: &[CardStageRecord]) -> Option<CardStage> {
+    let taken = CardStage::from_string(stage)
+        .unwrap_or_default()
+        .is_exclusive()
+        && versions.iter().any(|version| version.stage == stage);
+
+    taken.then_some(CardStage::Archived)
+}
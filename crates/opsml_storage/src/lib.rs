@@ -5,3 +5,7 @@ pub use storage::enums::client::StorageClientEnum;
 
 pub use storage::filesystem::{reset_storage_client, storage_client, FileSystemStorage};
 pub use storage::http::client::HttpFSStorageClient;
+pub use storage::manifest::{
+    add_card_manifest, download_artifacts, load_artifact_manifest, load_manifest_signature,
+    record_artifacts, verify_card_artifacts,
+};
@@ -1,7 +1,9 @@
 use crate::storage::http::multipart::error::MultiPartError;
 use opsml_client::error::ApiClientError;
+use opsml_crypt::error::CryptError;
 use opsml_settings::error::SettingsError;
 use opsml_state::error::StateError;
+use opsml_types::error::TypeError;
 use opsml_utils::error::UtilError;
 use thiserror::Error;
 
@@ -97,6 +99,18 @@ pub enum StorageError {
 
     #[error(transparent)]
     MultipartError(#[from] MultiPartError),
+
+    #[error(transparent)]
+    CryptError(#[from] CryptError),
+
+    #[error(transparent)]
+    TypeError(#[from] TypeError),
+
+    #[error("Artifacts of card {0} do not match their manifest: {1}")]
+    IntegrityError(String, String),
+
+    #[error("Card {0} was saved with an artifact manifest, but the manifest is missing")]
+    MissingManifestError(String),
 }
 
 #[cfg(feature = "server")]
@@ -0,0 +1,206 @@
+use crate::storage::error::StorageError;
+use crate::storage::filesystem::storage_client;
+use opsml_crypt::{decrypt_directory, decrypt_file, encrypt_file};
+use opsml_state::app_state;
+use opsml_types::contracts::{
+    ArtifactIntegrityReport, ArtifactKey, ArtifactManifest, ManifestSignature,
+};
+use std::path::Path;
+use tempfile::TempDir;
+use tracing::{debug, error, instrument, warn};
+
+/// Loads the manifest of a card. Cards saved before manifests were recorded have none
+#[instrument(skip_all)]
+pub fn load_artifact_manifest(key: &ArtifactKey) -> Result<Option<ArtifactManifest>, StorageError> {
+    let storage = storage_client()?;
+    let rpath = key.storage_path().join(ArtifactManifest::path());
+
+    if !storage.exists(&rpath)? {
+        return Ok(None);
+    }
+
+    let tmp_dir = TempDir::new()?;
+    let lpath = tmp_dir.path().join(ArtifactManifest::path());
+
+    storage.get(&lpath, &rpath, false)?;
+    decrypt_file(&lpath, &key.get_decrypt_key()?)?;
+
+    let manifest = std::fs::read_to_string(&lpath)?;
+
+    Ok(Some(serde_json::from_str(&manifest)?))
+}
+
+/// Loads the manifest signature of a card. Cards registered without a signing key have none
+#[instrument(skip_all)]
+pub fn load_manifest_signature(
+    key: &ArtifactKey,
+) -> Result<Option<ManifestSignature>, StorageError> {
+    let storage = storage_client()?;
+    let rpath = key.storage_path().join(ArtifactManifest::signature_path());
+
+    if !storage.exists(&rpath)? {
+        return Ok(None);
+    }
+
+    let tmp_dir = TempDir::new()?;
+    let lpath = tmp_dir.path().join(ArtifactManifest::signature_path());
+
+    storage.get(&lpath, &rpath, false)?;
+    decrypt_file(&lpath, &key.get_decrypt_key()?)?;
+
+    let signature = std::fs::read_to_string(&lpath)?;
+
+    Ok(Some(serde_json::from_str(&signature)?))
+}
+
+/// Signs the manifest with the configured signing key. Keys only sign cards of their own space
+fn sign_artifact_manifest(
+    key: &ArtifactKey,
+    manifest: &ArtifactManifest,
+) -> Result<Option<ManifestSignature>, StorageError> {
+    let Some(signer) = app_state().config()?.signer()? else {
+        return Ok(None);
+    };
+
+    if signer.space != key.space {
+        warn!(
+            "Signing key {} belongs to space {}, not signing card {} in space {}",
+            signer.key_id, signer.space, key.uid, key.space
+        );
+        return Ok(None);
+    }
+
+    let signature = manifest.sign(key, &signer.signing_key()?)?;
+    debug!(
+        "Signed manifest of card {} with key {}",
+        key.uid, signature.key_id
+    );
+
+    Ok(Some(signature))
+}
+
+/// Writes the manifest, and its signature when a signing key is configured, into the directory
+/// `lpath`, encrypted like the other files of the card
+///
+/// # Returns
+/// * `bool` - Whether the manifest was signed
+fn write_artifact_manifest(
+    key: &ArtifactKey,
+    manifest: &ArtifactManifest,
+    lpath: &Path,
+) -> Result<bool, StorageError> {
+    let encryption_key = key.get_decrypt_key()?;
+
+    let manifest_path = lpath.join(ArtifactManifest::path());
+    std::fs::write(&manifest_path, serde_json::to_string(manifest)?)?;
+    encrypt_file(&manifest_path, &encryption_key)?;
+
+    match sign_artifact_manifest(key, manifest)? {
+        Some(signature) => {
+            let signature_path = lpath.join(ArtifactManifest::signature_path());
+            std::fs::write(&signature_path, serde_json::to_string(&signature)?)?;
+            encrypt_file(&signature_path, &encryption_key)?;
+
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Adds the encrypted card files in `lpath` to the manifest of the card and writes the manifest
+/// into `lpath`, so it is uploaded with them. `lpath` must be uploaded to the storage path of the card
+#[instrument(skip_all)]
+pub fn add_card_manifest(key: &ArtifactKey, lpath: &Path) -> Result<(), StorageError> {
+    let mut manifest = load_artifact_manifest(key)?.unwrap_or_default();
+    manifest.add_files(lpath, Path::new(""))?;
+
+    write_artifact_manifest(key, &manifest, lpath)?;
+
+    Ok(())
+}
+
+/// Adds encrypted files that were uploaded from `lpath` to `rpath` to the manifest of the card
+#[instrument(skip_all)]
+pub fn record_artifacts(key: &ArtifactKey, lpath: &Path, rpath: &Path) -> Result<(), StorageError> {
+    let prefix = rpath.strip_prefix(key.storage_path())?;
+
+    let mut manifest = load_artifact_manifest(key)?.unwrap_or_default();
+    manifest.add_files(lpath, prefix)?;
+
+    let tmp_dir = TempDir::new()?;
+    let signed = write_artifact_manifest(key, &manifest, tmp_dir.path())?;
+
+    let storage = storage_client()?;
+    storage.put(
+        &tmp_dir.path().join(ArtifactManifest::path()),
+        &key.storage_path().join(ArtifactManifest::path()),
+        false,
+    )?;
+
+    if signed {
+        storage.put(
+            &tmp_dir.path().join(ArtifactManifest::signature_path()),
+            &key.storage_path().join(ArtifactManifest::signature_path()),
+            false,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Downloads the artifacts of a card stored at `rpath` to `lpath` and decrypts them. Downloaded
+/// files are checked against the manifest of the card before they are decrypted
+#[instrument(skip_all)]
+pub fn download_artifacts(
+    key: &ArtifactKey,
+    lpath: &Path,
+    rpath: &Path,
+    recursive: bool,
+) -> Result<(), StorageError> {
+    storage_client()?.get(lpath, rpath, recursive)?;
+
+    match load_artifact_manifest(key)? {
+        Some(manifest) => {
+            let prefix = rpath.strip_prefix(key.storage_path())?;
+            let mismatches = manifest.verify_files(lpath, prefix, false)?;
+
+            if !mismatches.is_empty() {
+                let mismatches = mismatches
+                    .iter()
+                    .map(|mismatch| mismatch.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                error!(
+                    "Artifacts of card {} failed verification: {mismatches}",
+                    key.uid
+                );
+
+                return Err(StorageError::IntegrityError(key.uid.clone(), mismatches));
+            }
+        }
+        None if key.has_manifest => {
+            error!("Artifact manifest of card {} is missing", key.uid);
+
+            return Err(StorageError::MissingManifestError(key.uid.clone()));
+        }
+        None => debug!(
+            "Card {} has no artifact manifest, skipping verification",
+            key.uid
+        ),
+    }
+
+    decrypt_directory(lpath, &key.get_decrypt_key()?)?;
+
+    Ok(())
+}
+
+/// Downloads every stored file of a card and re-hashes it against the card manifest
+#[instrument(skip_all)]
+pub fn verify_card_artifacts(key: &ArtifactKey) -> Result<ArtifactIntegrityReport, StorageError> {
+    let tmp_dir = TempDir::new()?;
+    storage_client()?.get(tmp_dir.path(), &key.storage_path(), true)?;
+
+    let report = ArtifactIntegrityReport::from_directory(key, tmp_dir.path())?;
+
+    Ok(report)
+}
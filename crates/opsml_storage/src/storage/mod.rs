@@ -12,4 +12,5 @@ pub mod error;
 pub mod filesystem;
 pub mod http;
 pub mod local;
+pub mod manifest;
 pub mod utils;
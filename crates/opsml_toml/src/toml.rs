@@ -27,6 +27,7 @@ pub struct Card {
     pub alias: String,
     pub space: String,
     pub name: String,
+    /// Version or semver range of the card, or a version alias such as `@champion`
     pub version: Option<String>,
     #[serde(rename = "type")]
     pub registry_type: RegistryType,
@@ -13,6 +13,9 @@ pub enum RequestType {
 #[derive(Debug, Clone)]
 pub enum Routes {
     ArtifactKey,
+    AuditEvents,
+    AuditExport,
+    AuditVerify,
     AuthLogin,
     AuthRefresh,
     AuthValidate,
@@ -26,6 +29,15 @@ pub enum Routes {
     CardLoad,
     CardVersion,
     CardUpdate,
+    CardStage,
+    CardAlias,
+    CardAliasHistory,
+    CardRestore,
+    CardTrash,
+    CardSearch,
+    CardLineage,
+    CardImport,
+    CardVerify,
 
     CardMetadata,
     CardSpaces,
@@ -44,11 +56,14 @@ pub enum Routes {
     CompleteMultipart,
     Presigned,
     StorageSettings,
+    SigningKey,
     ExperimentMetrics,
     ExperimentGroupedMetrics,
     ExperimentMetricNames,
     ExperimentHardwareMetrics,
     ExperimentParameters,
+    ExperimentCompare,
+    ExperimentMetricQuery,
     ScouterAuthLogin,
 
     ScouterDriftCustom,
@@ -77,7 +92,11 @@ impl Routes {
             Routes::ArtifactKey => "files/key",
             Routes::Healthcheck => "healthcheck",
             Routes::StorageSettings => "storage/settings",
+            Routes::SigningKey => "signing_key",
             Routes::DeleteFiles => "files/delete",
+            Routes::AuditEvents => "audit/events",
+            Routes::AuditExport => "audit/export",
+            Routes::AuditVerify => "audit/verify",
             Routes::AuthLogin => "auth/login",
             Routes::AuthRefresh => "auth/refresh",
             Routes::AuthValidate => "auth/validate",
@@ -96,11 +115,22 @@ impl Routes {
             Routes::CardLoad => "card/load",
             Routes::CardVersion => "card/version",
             Routes::CardUpdate => "card/update",
+            Routes::CardStage => "card/stage",
+            Routes::CardAlias => "card/alias",
+            Routes::CardAliasHistory => "card/alias/history",
+            Routes::CardRestore => "card/restore",
+            Routes::CardTrash => "card/trash",
+            Routes::CardSearch => "card/search",
+            Routes::CardLineage => "card/lineage",
+            Routes::CardImport => "card/import",
+            Routes::CardVerify => "card/verify",
             Routes::ExperimentMetrics => "experiment/metrics",
             Routes::ExperimentGroupedMetrics => "experiment/metrics/grouped",
             Routes::ExperimentMetricNames => "experiment/metrics/names",
             Routes::ExperimentHardwareMetrics => "experiment/hardware/metrics",
             Routes::ExperimentParameters => "experiment/parameters",
+            Routes::ExperimentCompare => "experiment/compare",
+            Routes::ExperimentMetricQuery => "experiment/metrics/query",
 
             // Scouter Auth Routes
             Routes::ScouterAuthLogin => "scouter/auth/login",
@@ -1,9 +1,11 @@
 use crate::error::{PyTypeError, TypeError};
 use chrono::{DateTime, Utc};
-use opsml_utils::PyHelperFuncs;
+use opsml_utils::{json_to_pyobject_value, PyHelperFuncs};
 use pyo3::prelude::*;
 use pyo3::IntoPyObjectExt;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
 use sysinfo::{Networks, System};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -107,7 +109,7 @@ impl Metrics {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum ParameterValue {
     Int(i64),
     Float(f64),
@@ -126,6 +128,14 @@ impl ParameterValue {
             Err(TypeError::InvalidType.into())
         }
     }
+
+    pub fn to_py<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        match self {
+            ParameterValue::Int(value) => value.into_bound_py_any(py),
+            ParameterValue::Float(value) => value.into_bound_py_any(py),
+            ParameterValue::Str(value) => value.into_bound_py_any(py),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -148,11 +158,7 @@ impl Parameter {
 
     #[getter]
     pub fn value<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
-        match &self.value {
-            ParameterValue::Int(value) => Ok(value.into_bound_py_any(py)?),
-            ParameterValue::Float(value) => Ok(value.into_bound_py_any(py)?),
-            ParameterValue::Str(value) => Ok(value.into_bound_py_any(py)?),
-        }
+        self.value.to_py(py)
     }
 }
 
@@ -383,7 +389,127 @@ impl Default for HardwareMetricLogger {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// Search strategy of a hyperparameter sweep
+#[pyclass(eq, eq_int)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Default)]
+pub enum SweepStrategy {
+    /// Every combination of the choice values
+    #[default]
+    Grid,
+
+    /// Parameters sampled at random from the search space
+    Random,
+
+    /// Random configurations trained with a growing budget, keeping the best configurations of
+    /// each round
+    SuccessiveHalving,
+}
+
+impl Display for SweepStrategy {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let strategy = match self {
+            SweepStrategy::Grid => "Grid",
+            SweepStrategy::Random => "Random",
+            SweepStrategy::SuccessiveHalving => "SuccessiveHalving",
+        };
+
+        write!(f, "{strategy}")
+    }
+}
+
+/// A single parameter of a sweep search space
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SearchDimension {
+    /// One of a fixed set of values. Grid sweeps only accept choices
+    Choice { values: Vec<ParameterValue> },
+
+    /// A float between `low` and `high`, sampled on a log scale when `log` is set
+    Uniform { low: f64, high: f64, log: bool },
+
+    /// An integer between `low` and `high`, both inclusive
+    IntUniform { low: i64, high: i64 },
+}
+
+/// A trial of a sweep, run as a child experiment of the sweep experiment
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[pyclass]
+pub struct SweepTrial {
+    #[pyo3(get)]
+    pub experiment_uid: String,
+
+    #[pyo3(get)]
+    pub parameters: Vec<Parameter>,
+
+    /// Final value of the sweep metric. Not set when the trial failed or did not log the metric
+    #[pyo3(get)]
+    pub value: Option<f64>,
+
+    /// Budget given to the trial by successive halving
+    #[pyo3(get)]
+    pub budget: Option<i64>,
+}
+
+#[pymethods]
+impl SweepTrial {
+    pub fn __str__(&self) -> String {
+        PyHelperFuncs::__str__(self)
+    }
+}
+
+/// Sweep metadata recorded on the ExperimentCard of the experiment running the sweep
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[pyclass]
+pub struct Sweep {
+    pub search_space: BTreeMap<String, SearchDimension>,
+
+    #[pyo3(get)]
+    pub strategy: SweepStrategy,
+
+    /// Metric used to rank the trials
+    #[pyo3(get)]
+    pub metric: String,
+
+    #[pyo3(get)]
+    pub maximize: bool,
+
+    #[pyo3(get)]
+    pub best_uid: Option<String>,
+
+    #[pyo3(get)]
+    pub best_value: Option<f64>,
+
+    #[pyo3(get)]
+    pub trials: Vec<SweepTrial>,
+}
+
+#[pymethods]
+impl Sweep {
+    #[getter]
+    pub fn search_space(&self, py: Python) -> PyResult<PyObject> {
+        let value = serde_json::to_value(&self.search_space)
+            .map_err(|e| PyTypeError::from(TypeError::from(e)))?;
+
+        Ok(json_to_pyobject_value(py, &value)?)
+    }
+
+    /// The best trial, if any trial logged the sweep metric
+    #[getter]
+    pub fn best_trial(&self) -> Option<SweepTrial> {
+        self.best_uid.as_ref().and_then(|uid| {
+            self.trials
+                .iter()
+                .find(|trial| &trial.experiment_uid == uid)
+                .cloned()
+        })
+    }
+
+    pub fn __str__(&self) -> String {
+        PyHelperFuncs::__str__(self)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[pyclass]
 pub struct ComputeEnvironment {
     cpu_count: usize,
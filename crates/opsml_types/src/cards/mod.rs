@@ -1,5 +1,7 @@
+pub mod environment;
 pub mod experiment;
 pub mod types;
 
+pub use environment::*;
 pub use experiment::*;
 pub use types::*;
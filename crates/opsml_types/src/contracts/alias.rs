@@ -0,0 +1,151 @@
+use crate::contracts::{AuditableRequest, ResourceType};
+use crate::error::TypeError;
+use crate::RegistryType;
+use chrono::{DateTime, Utc};
+use opsml_utils::PyHelperFuncs;
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Prefix that marks a version alias in place of a semver (e.g. `@champion`)
+pub const VERSION_ALIAS_PREFIX: char = '@';
+
+/// Maximum length of a version alias name
+const MAX_ALIAS_LENGTH: usize = 64;
+
+/// Returns the alias name if `version` refers to a version alias (e.g. `@champion` -> `champion`)
+pub fn parse_version_alias(version: &str) -> Option<&str> {
+    version.strip_prefix(VERSION_ALIAS_PREFIX)
+}
+
+/// Validates a version alias name. Names are lowercase letters, digits, `-` and `_`,
+/// and must start with a letter so they can never be confused with a version
+pub fn validate_alias_name(alias: &str) -> Result<(), TypeError> {
+    let starts_with_letter = alias.chars().next().is_some_and(|c| c.is_ascii_lowercase());
+    let valid_chars = alias
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+
+    if !starts_with_letter || !valid_chars || alias.len() > MAX_ALIAS_LENGTH {
+        return Err(TypeError::InvalidVersionAlias(alias.to_string()));
+    }
+
+    Ok(())
+}
+
+/// Request to point a version alias at a card version
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct VersionAliasRequest {
+    pub space: String,
+    pub name: String,
+    pub registry_type: RegistryType,
+    /// Alias name without the `@` prefix
+    pub alias: String,
+    /// Exact version the alias points to
+    pub version: String,
+}
+
+impl AuditableRequest for VersionAliasRequest {
+    fn get_resource_id(&self) -> String {
+        format!("{}/{}@{}", self.space, self.name, self.alias)
+    }
+
+    fn get_metadata(&self) -> String {
+        serde_json::to_string(self)
+            .unwrap_or_else(|e| format!("Failed to serialize VersionAliasRequest: {e}"))
+    }
+
+    fn get_registry_type(&self) -> Option<RegistryType> {
+        Some(self.registry_type.clone())
+    }
+
+    fn get_resource_type(&self) -> ResourceType {
+        ResourceType::Database
+    }
+}
+
+/// Identifies the version aliases of a card. `alias` selects a single alias
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct VersionAliasQuery {
+    pub space: String,
+    pub name: String,
+    pub registry_type: RegistryType,
+    pub alias: Option<String>,
+}
+
+impl AuditableRequest for VersionAliasQuery {
+    fn get_resource_id(&self) -> String {
+        match &self.alias {
+            Some(alias) => format!("{}/{}@{}", self.space, self.name, alias),
+            None => format!("{}/{}", self.space, self.name),
+        }
+    }
+
+    fn get_metadata(&self) -> String {
+        serde_json::to_string(self)
+            .unwrap_or_else(|e| format!("Failed to serialize VersionAliasQuery: {e}"))
+    }
+
+    fn get_registry_type(&self) -> Option<RegistryType> {
+        Some(self.registry_type.clone())
+    }
+
+    fn get_resource_type(&self) -> ResourceType {
+        ResourceType::Database
+    }
+}
+
+/// A named pointer to a card version
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[pyclass]
+pub struct VersionAlias {
+    #[pyo3(get)]
+    pub registry_type: RegistryType,
+    #[pyo3(get)]
+    pub space: String,
+    #[pyo3(get)]
+    pub name: String,
+    #[pyo3(get)]
+    pub alias: String,
+    #[pyo3(get)]
+    pub uid: String,
+    #[pyo3(get)]
+    pub version: String,
+    #[pyo3(get)]
+    pub updated_at: DateTime<Utc>,
+    #[pyo3(get)]
+    pub updated_by: String,
+}
+
+#[pymethods]
+impl VersionAlias {
+    pub fn __str__(&self) -> String {
+        PyHelperFuncs::__str__(self)
+    }
+}
+
+/// A change to a version alias. `version` is None when the alias was deleted
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[pyclass]
+pub struct VersionAliasHistory {
+    #[pyo3(get)]
+    pub id: i64,
+    #[pyo3(get)]
+    pub created_at: DateTime<Utc>,
+    #[pyo3(get)]
+    pub alias: String,
+    #[pyo3(get)]
+    pub uid: Option<String>,
+    #[pyo3(get)]
+    pub version: Option<String>,
+    #[pyo3(get)]
+    pub previous_version: Option<String>,
+    #[pyo3(get)]
+    pub username: String,
+}
+
+#[pymethods]
+impl VersionAliasHistory {
+    pub fn __str__(&self) -> String {
+        PyHelperFuncs::__str__(self)
+    }
+}
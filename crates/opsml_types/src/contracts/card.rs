@@ -4,7 +4,7 @@ use crate::error::TypeError;
 use crate::{
     cards::CardTable,
     interfaces::{types::DataInterfaceType, ModelType, TaskType},
-    DataType, ModelInterfaceType, RegistryType,
+    CardStage, DataType, ModelInterfaceType, RegistryType,
 };
 use chrono::{DateTime, Utc};
 use opsml_colors::Colorize;
@@ -70,6 +70,113 @@ impl AuditableRequest for DeleteCardRequest {
     }
 }
 
+/// Lists the deleted cards of a registry that are waiting to be purged
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DeletedCardQuery {
+    pub registry_type: RegistryType,
+    pub space: Option<String>,
+}
+
+/// A soft-deleted card version. Deleted cards can be restored until they are purged
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[pyclass]
+pub struct DeletedCard {
+    #[pyo3(get)]
+    pub uid: String,
+    #[pyo3(get)]
+    pub space: String,
+    #[pyo3(get)]
+    pub name: String,
+    #[pyo3(get)]
+    pub version: String,
+    #[pyo3(get)]
+    pub registry_type: RegistryType,
+    #[pyo3(get)]
+    pub deleted_at: DateTime<Utc>,
+    #[pyo3(get)]
+    pub deleted_by: String,
+}
+
+#[pymethods]
+impl DeletedCard {
+    pub fn __str__(&self) -> String {
+        PyHelperFuncs::__str__(self)
+    }
+}
+
+/// Request to move a model or service card version to a new stage
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CardStageRequest {
+    pub uid: String,
+    pub registry_type: RegistryType,
+    pub stage: CardStage,
+    /// Move other versions of the card that hold `stage` to archived
+    #[serde(default)]
+    pub archive_existing: bool,
+}
+
+impl AuditableRequest for CardStageRequest {
+    fn get_resource_id(&self) -> String {
+        self.uid.clone()
+    }
+
+    fn get_metadata(&self) -> String {
+        serde_json::to_string(self)
+            .unwrap_or_else(|e| format!("Failed to serialize CardStageRequest: {e}"))
+    }
+
+    fn get_registry_type(&self) -> Option<RegistryType> {
+        Some(self.registry_type.clone())
+    }
+
+    fn get_resource_type(&self) -> ResourceType {
+        ResourceType::Database
+    }
+}
+
+/// A stage change applied to a card version
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[pyclass]
+pub struct CardStageChange {
+    #[pyo3(get)]
+    pub uid: String,
+    #[pyo3(get)]
+    pub version: String,
+    #[pyo3(get)]
+    pub previous_stage: CardStage,
+    #[pyo3(get)]
+    pub stage: CardStage,
+}
+
+#[pymethods]
+impl CardStageChange {
+    pub fn __str__(&self) -> String {
+        PyHelperFuncs::__str__(self)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[pyclass]
+pub struct CardStageResponse {
+    #[pyo3(get)]
+    pub space: String,
+    #[pyo3(get)]
+    pub name: String,
+    /// The requested transition
+    #[pyo3(get)]
+    pub change: CardStageChange,
+    /// Versions moved to archived because they held the requested stage
+    #[pyo3(get)]
+    pub archived: Vec<CardStageChange>,
+}
+
+#[pymethods]
+impl CardStageResponse {
+    pub fn __str__(&self) -> String {
+        PyHelperFuncs::__str__(self)
+    }
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct UidResponse {
     pub exists: bool,
@@ -249,12 +356,13 @@ pub struct CardVersionRequest {
 /// * `uid` - The unique identifier of the card
 /// * `name` - The name of the card
 /// * `space` - The space of the card
-/// * `version` - The version of the card
+/// * `version` - The version of the card, or a version alias such as `@champion`
 /// * `max_date` - The maximum date of the card
 /// * `tags` - The tags of the card
 /// * `limit` - The maximum number of cards to return
 /// * `query_terms` - The query terms to search for
 /// * `sort_by_timestamp` - Whether to sort by timestamp
+/// * `stage` - The lifecycle stage of the card (model and service cards only)
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone)]
 pub struct CardQueryArgs {
@@ -267,6 +375,8 @@ pub struct CardQueryArgs {
     pub limit: Option<i32>,
     pub sort_by_timestamp: Option<bool>,
     pub registry_type: RegistryType,
+    /// Only return model or service cards in this stage
+    pub stage: Option<CardStage>,
 }
 
 impl AuditableRequest for CardQueryArgs {
@@ -345,6 +455,8 @@ pub struct ModelCardClientRecord {
     pub task_type: String,
     pub opsml_version: String,
     pub username: String,
+    #[serde(default)]
+    pub stage: CardStage,
 }
 
 impl Default for ModelCardClientRecord {
@@ -367,6 +479,7 @@ impl Default for ModelCardClientRecord {
             task_type: TaskType::Undefined.to_string(),
             opsml_version: opsml_version::version(),
             username: "guest".to_string(),
+            stage: CardStage::default(),
         }
     }
 }
@@ -495,6 +608,8 @@ pub struct ServiceCardClientRecord {
     pub cards: Vec<CardEntry>,
     pub opsml_version: String,
     pub username: String,
+    #[serde(default)]
+    pub stage: CardStage,
 }
 
 impl Default for ServiceCardClientRecord {
@@ -509,6 +624,7 @@ impl Default for ServiceCardClientRecord {
             opsml_version: opsml_version::version(),
             username: "guest".to_string(),
             cards: Vec::new(),
+            stage: CardStage::default(),
         }
     }
 }
@@ -616,6 +732,15 @@ impl CardRecord {
         }
     }
 
+    #[getter]
+    pub fn stage(&self) -> Option<CardStage> {
+        match self {
+            Self::Model(card) => Some(card.stage.clone()),
+            Self::Service(card) => Some(card.stage.clone()),
+            _ => None,
+        }
+    }
+
     #[getter]
     pub fn datacard_uids(&self) -> Option<Vec<&str>> {
         match self {
@@ -969,6 +1094,44 @@ pub struct UpdateCardResponse {
     pub updated: bool,
 }
 
+/// Recreates a card exported from another registry. The uid and version of the card are kept
+/// and `encryption_key` is the unwrapped key its artifacts were encrypted with
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ImportCardRequest {
+    pub registry_type: RegistryType,
+    pub card: CardRecord,
+    pub encryption_key: Vec<u8>,
+    /// Whether the artifacts of the card include a manifest
+    #[serde(default)]
+    pub has_manifest: bool,
+}
+
+impl AuditableRequest for ImportCardRequest {
+    fn get_resource_id(&self) -> String {
+        self.card.uid().to_string()
+    }
+
+    // the encryption key is left out of the audit trail
+    fn get_metadata(&self) -> String {
+        serde_json::to_string(&self.card)
+            .unwrap_or_else(|e| format!("Failed to serialize ImportCardRequest: {e}"))
+    }
+
+    fn get_registry_type(&self) -> Option<RegistryType> {
+        Some(self.registry_type.clone())
+    }
+
+    fn get_resource_type(&self) -> ResourceType {
+        ResourceType::Database
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImportCardResponse {
+    pub imported: bool,
+    pub key: ArtifactKey,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct CardEntry {
     pub registry_type: RegistryType,
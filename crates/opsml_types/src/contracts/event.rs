@@ -1,7 +1,15 @@
 use crate::contracts::{AuditStatus, Operation, ResourceType};
+use crate::error::TypeError;
 use crate::RegistryType;
+use chrono::{DateTime, Utc};
+use opsml_colors::Colorize;
+use opsml_utils::get_utc_datetime;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use tabled::settings::{format::Format, object::Rows, Alignment, Color, Style};
+use tabled::{Table, Tabled};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AuditEvent {
     pub username: String,
     pub client_ip: String,
@@ -15,6 +23,7 @@ pub struct AuditEvent {
     pub metadata: String,
     pub registry_type: Option<RegistryType>,
     pub route: String,
+    pub space: Option<String>,
 }
 
 impl Default for AuditEvent {
@@ -32,13 +41,330 @@ impl Default for AuditEvent {
             metadata: "unknown".to_string(),
             registry_type: Some(RegistryType::Model),
             route: "unknown".to_string(),
+            space: None,
         }
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SpaceNameEvent {
     pub space: String,
     pub name: String,
     pub registry_type: RegistryType,
 }
+
+/// Card lifecycle events published on the event bus and delivered to webhooks
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CardEventType {
+    CardRegistered,
+    CardUpdated,
+    CardDeleted,
+    DriftProfileStatusChanged,
+    CardStageChanged,
+    CardRestored,
+    CardPurged,
+}
+
+impl CardEventType {
+    pub fn from_string(s: &str) -> Result<Self, TypeError> {
+        match s.to_lowercase().as_str() {
+            "card_registered" => Ok(CardEventType::CardRegistered),
+            "card_updated" => Ok(CardEventType::CardUpdated),
+            "card_deleted" => Ok(CardEventType::CardDeleted),
+            "drift_profile_status_changed" => Ok(CardEventType::DriftProfileStatusChanged),
+            "card_stage_changed" => Ok(CardEventType::CardStageChanged),
+            "card_restored" => Ok(CardEventType::CardRestored),
+            "card_purged" => Ok(CardEventType::CardPurged),
+            _ => Err(TypeError::InvalidEventType(s.to_string())),
+        }
+    }
+}
+
+impl Display for CardEventType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CardEventType::CardRegistered => write!(f, "card_registered"),
+            CardEventType::CardUpdated => write!(f, "card_updated"),
+            CardEventType::CardDeleted => write!(f, "card_deleted"),
+            CardEventType::DriftProfileStatusChanged => write!(f, "drift_profile_status_changed"),
+            CardEventType::CardStageChanged => write!(f, "card_stage_changed"),
+            CardEventType::CardRestored => write!(f, "card_restored"),
+            CardEventType::CardPurged => write!(f, "card_purged"),
+        }
+    }
+}
+
+/// A change to a card in a registry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CardEvent {
+    pub event_type: CardEventType,
+    pub registry_type: RegistryType,
+    pub space: String,
+    pub name: String,
+    pub version: Option<String>,
+    pub uid: Option<String>,
+    pub username: String,
+    pub created_at: DateTime<Utc>,
+    /// Event specific details, e.g. the new status of a drift profile
+    pub details: Option<serde_json::Value>,
+}
+
+impl CardEvent {
+    pub fn new(
+        event_type: CardEventType,
+        registry_type: RegistryType,
+        space: &str,
+        name: &str,
+        username: &str,
+    ) -> Self {
+        Self {
+            event_type,
+            registry_type,
+            space: space.to_string(),
+            name: name.to_string(),
+            version: None,
+            uid: None,
+            username: username.to_string(),
+            created_at: get_utc_datetime(),
+            details: None,
+        }
+    }
+
+    pub fn with_version(mut self, version: &str) -> Self {
+        self.version = Some(version.to_string());
+        self
+    }
+
+    pub fn with_uid(mut self, uid: &str) -> Self {
+        self.uid = Some(uid.to_string());
+        self
+    }
+
+    pub fn with_details(mut self, details: serde_json::Value) -> Self {
+        self.details = Some(details);
+        self
+    }
+}
+
+/// Space changes published on the event bus
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpaceEventType {
+    SpaceCreated,
+    SpaceUpdated,
+    SpaceDeleted,
+}
+
+impl Display for SpaceEventType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SpaceEventType::SpaceCreated => write!(f, "space_created"),
+            SpaceEventType::SpaceUpdated => write!(f, "space_updated"),
+            SpaceEventType::SpaceDeleted => write!(f, "space_deleted"),
+        }
+    }
+}
+
+/// A change to a space record
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpaceEvent {
+    pub event_type: SpaceEventType,
+    pub space: String,
+    pub description: Option<String>,
+    pub username: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl SpaceEvent {
+    pub fn new(
+        event_type: SpaceEventType,
+        space: &str,
+        description: Option<String>,
+        username: &str,
+    ) -> Self {
+        Self {
+            event_type,
+            space: space.to_string(),
+            description,
+            username: username.to_string(),
+            created_at: get_utc_datetime(),
+        }
+    }
+}
+
+/// Event sent to clients of the registry event stream
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "event", rename_all = "snake_case")]
+pub enum RegistryEvent {
+    Card(CardEvent),
+    Space(SpaceEvent),
+}
+
+impl RegistryEvent {
+    pub fn event_type(&self) -> String {
+        match self {
+            RegistryEvent::Card(event) => event.event_type.to_string(),
+            RegistryEvent::Space(event) => event.event_type.to_string(),
+        }
+    }
+
+    pub fn space(&self) -> &str {
+        match self {
+            RegistryEvent::Card(event) => &event.space,
+            RegistryEvent::Space(event) => &event.space,
+        }
+    }
+
+    /// Space events do not belong to a registry
+    pub fn registry_type(&self) -> Option<&RegistryType> {
+        match self {
+            RegistryEvent::Card(event) => Some(&event.registry_type),
+            RegistryEvent::Space(_) => None,
+        }
+    }
+}
+
+/// Filters for the registry event stream.
+///
+/// `last_event_id` resumes the stream after the given event. Browsers send the standard
+/// `Last-Event-ID` header instead, which takes precedence.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct EventStreamQuery {
+    pub registry_type: Option<RegistryType>,
+    pub space: Option<String>,
+    pub last_event_id: Option<u64>,
+}
+
+/// Sent on the registry event stream when events published after the client's last event are
+/// no longer buffered. Clients should reload their state, as events were missed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventStreamReset {
+    /// Last event id received by the client
+    pub last_event_id: u64,
+    /// Oldest event id the stream resumes from
+    pub first_event_id: u64,
+}
+
+impl EventStreamReset {
+    pub const EVENT_TYPE: &'static str = "reset";
+}
+
+/// The first point at which the audit hash chain could not be verified
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditChainBreak {
+    pub id: i32,
+    pub created_at: DateTime<Utc>,
+    pub reason: String,
+}
+
+/// Result of walking the audit event hash chain
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct AuditChainReport {
+    pub verified: bool,
+    pub events_checked: i64,
+    /// Events written before hash chaining was enabled
+    pub unchained_events: i64,
+    pub last_id: Option<i32>,
+    /// Hash of the last verified event. Can be stored externally to detect truncation of the log
+    pub last_hash: Option<String>,
+    /// Sequence number of the last verified event
+    #[serde(default)]
+    pub last_seq: Option<i64>,
+    pub first_break: Option<AuditChainBreak>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditExportRequest {
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+}
+
+/// Filters for querying audit events. All filters are optional and combined with AND.
+///
+/// Results are returned newest first. Pass the `next_cursor` of a previous page as `cursor`
+/// to fetch the next page.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct AuditEventQueryArgs {
+    pub username: Option<String>,
+    pub space: Option<String>,
+    pub registry_type: Option<RegistryType>,
+    pub operation: Option<Operation>,
+    pub status: Option<AuditStatus>,
+    pub resource_id: Option<String>,
+    pub start_time: Option<DateTime<Utc>>,
+    pub end_time: Option<DateTime<Utc>>,
+    pub cursor: Option<i32>,
+    pub limit: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEventClientRecord {
+    pub id: i32,
+    pub created_at: DateTime<Utc>,
+    pub username: String,
+    pub client_ip: String,
+    pub user_agent: Option<String>,
+    pub operation: String,
+    pub resource_type: String,
+    pub resource_id: String,
+    pub access_location: Option<String>,
+    pub status: String,
+    pub error_message: Option<String>,
+    pub metadata: Option<String>,
+    pub registry_type: Option<String>,
+    pub route: Option<String>,
+    pub space: Option<String>,
+}
+
+#[derive(Tabled)]
+struct AuditEventTableEntry {
+    id: i32,
+    created_at: String,
+    username: String,
+    operation: String,
+    status: String,
+    space: String,
+    registry_type: String,
+    resource_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEventPage {
+    pub events: Vec<AuditEventClientRecord>,
+    /// Cursor for the next (older) page. None when there are no more events
+    pub next_cursor: Option<i32>,
+}
+
+impl AuditEventPage {
+    pub fn as_table(&self) {
+        let entries: Vec<AuditEventTableEntry> = self
+            .events
+            .iter()
+            .map(|event| AuditEventTableEntry {
+                id: event.id,
+                created_at: event.created_at.to_string(),
+                username: event.username.clone(),
+                operation: event.operation.clone(),
+                status: event.status.clone(),
+                space: event.space.clone().unwrap_or_default(),
+                registry_type: event.registry_type.clone().unwrap_or_default(),
+                resource_id: Colorize::purple(&event.resource_id),
+            })
+            .collect();
+
+        let mut table = Table::new(entries);
+
+        table.with(Style::sharp());
+        table.modify(
+            Rows::new(0..1),
+            (
+                Format::content(Colorize::green),
+                Alignment::center(),
+                Color::BOLD,
+            ),
+        );
+
+        println!("{}", &table);
+    }
+}
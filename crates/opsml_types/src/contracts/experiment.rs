@@ -1,7 +1,22 @@
-use crate::cards::{HardwareMetrics, Metric, Parameter};
+use crate::cards::{HardwareMetrics, Metric, Parameter, ParameterValue};
 use crate::contracts::{traits::AuditableRequest, ResourceType};
 use crate::RegistryType;
+use opsml_utils::PyHelperFuncs;
+use pyo3::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// Maximum number of experiments compared in a single request
+pub const MAX_COMPARED_EXPERIMENTS: usize = 50;
+
+/// Number of metrics returned per page when no limit is given
+pub const DEFAULT_METRIC_PAGE_SIZE: i64 = 10_000;
+
+/// Maximum number of metrics returned per page
+pub const MAX_METRIC_PAGE_SIZE: i64 = 100_000;
+
+/// Maximum number of points a metric can be downsampled to
+pub const MAX_METRIC_POINTS: i64 = 10_000;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct MetricRequest {
@@ -43,6 +58,67 @@ impl AuditableRequest for GetMetricRequest {
     }
 }
 
+/// Metric query restricted to a step or timestamp range. Ranges are inclusive, and metrics
+/// logged without a step are excluded by a step range.
+///
+/// When `max_points` is set, each metric is downsampled to at most that many points by keeping
+/// the lowest and highest value of equally sized buckets, in logging order. `max_points` must be
+/// between 1 and `MAX_METRIC_POINTS`. Downsampled results are not paginated.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct MetricQuery {
+    pub experiment_uid: String,
+    #[serde(default)]
+    pub names: Vec<String>,
+    pub start_step: Option<i32>,
+    pub end_step: Option<i32>,
+    pub start_timestamp: Option<i64>,
+    pub end_timestamp: Option<i64>,
+    pub max_points: Option<i64>,
+    /// `next_cursor` of the previous page
+    pub cursor: Option<i64>,
+    pub limit: Option<i64>,
+}
+
+impl MetricQuery {
+    /// Number of metrics per page, capped at `MAX_METRIC_PAGE_SIZE`
+    pub fn limit(&self) -> i64 {
+        self.limit
+            .unwrap_or(DEFAULT_METRIC_PAGE_SIZE)
+            .clamp(1, MAX_METRIC_PAGE_SIZE)
+    }
+
+    /// Number of buckets each metric is split into when downsampling. Every bucket keeps up to two points
+    pub fn buckets(&self) -> Option<i64> {
+        self.max_points.map(|max_points| (max_points / 2).max(1))
+    }
+}
+
+impl AuditableRequest for MetricQuery {
+    fn get_resource_id(&self) -> String {
+        self.experiment_uid.clone()
+    }
+
+    fn get_metadata(&self) -> String {
+        serde_json::to_string(self)
+            .unwrap_or_else(|e| format!("Failed to serialize MetricQuery: {e}"))
+    }
+
+    fn get_registry_type(&self) -> Option<RegistryType> {
+        Some(RegistryType::Experiment)
+    }
+
+    fn get_resource_type(&self) -> ResourceType {
+        ResourceType::Database
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct MetricPage {
+    pub metrics: Vec<Metric>,
+    /// Pass as `cursor` to get the next page. None on the last page
+    pub next_cursor: Option<i64>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Experiment {
     pub uid: String,
@@ -96,7 +172,7 @@ pub struct ParameterResponse {
     pub success: bool,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct HardwareMetricRequest {
     pub experiment_uid: String,
     pub metrics: HardwareMetrics,
@@ -111,3 +187,352 @@ pub struct GetHardwareMetricRequest {
 pub struct HardwareMetricResponse {
     pub success: bool,
 }
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ExperimentComparisonRequest {
+    pub experiment_uids: Vec<String>,
+    /// Metrics to compare. All metrics of the experiments are compared when empty
+    #[serde(default)]
+    pub metric_names: Vec<String>,
+    /// Parameters to compare. All parameters of the experiments are compared when empty
+    #[serde(default)]
+    pub parameter_names: Vec<String>,
+    /// Metrics where a higher value is better. Lower is better for every other metric
+    #[serde(default)]
+    pub maximize: Vec<String>,
+}
+
+/// Summary of one experiment's values for a metric
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[pyclass]
+pub struct MetricSummary {
+    #[pyo3(get)]
+    pub min: f64,
+    #[pyo3(get)]
+    pub max: f64,
+    /// Value at the highest step
+    #[pyo3(get)]
+    pub last: f64,
+    /// Lowest value, or highest value for maximized metrics
+    #[pyo3(get)]
+    pub best: f64,
+    #[pyo3(get)]
+    pub best_step: i64,
+    #[pyo3(get)]
+    pub count: usize,
+}
+
+#[pymethods]
+impl MetricSummary {
+    pub fn __str__(&self) -> String {
+        PyHelperFuncs::__str__(self)
+    }
+}
+
+/// One experiment's values for a metric, aligned to the steps of the comparison
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[pyclass]
+pub struct MetricRun {
+    #[pyo3(get)]
+    pub experiment_uid: String,
+    /// Value at each step of the comparison, None where the experiment did not log the step
+    #[pyo3(get)]
+    pub values: Vec<Option<f64>>,
+    /// None when the experiment did not log the metric
+    #[pyo3(get)]
+    pub summary: Option<MetricSummary>,
+}
+
+#[pymethods]
+impl MetricRun {
+    pub fn __str__(&self) -> String {
+        PyHelperFuncs::__str__(self)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[pyclass]
+pub struct MetricComparison {
+    #[pyo3(get)]
+    pub name: String,
+    /// Every step logged by any of the experiments, ascending
+    #[pyo3(get)]
+    pub steps: Vec<i64>,
+    /// One entry per experiment, in the order of `ExperimentComparison.experiment_uids`
+    #[pyo3(get)]
+    pub runs: Vec<MetricRun>,
+}
+
+#[pymethods]
+impl MetricComparison {
+    pub fn __str__(&self) -> String {
+        PyHelperFuncs::__str__(self)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[pyclass]
+pub struct ParameterComparison {
+    #[pyo3(get)]
+    pub name: String,
+    /// One value per experiment, in the order of `ExperimentComparison.experiment_uids`
+    pub values: Vec<Option<ParameterValue>>,
+}
+
+#[pymethods]
+impl ParameterComparison {
+    #[getter]
+    pub fn values<'py>(&self, py: Python<'py>) -> PyResult<Vec<Option<Bound<'py, PyAny>>>> {
+        self.values
+            .iter()
+            .map(|value| value.as_ref().map(|value| value.to_py(py)).transpose())
+            .collect()
+    }
+
+    /// Whether the experiments used different values for the parameter
+    #[getter]
+    pub fn differs(&self) -> bool {
+        self.values.windows(2).any(|pair| pair[0] != pair[1])
+    }
+
+    pub fn __str__(&self) -> String {
+        PyHelperFuncs::__str__(self)
+    }
+}
+
+/// Parameters and metrics of several experiments, aligned for side by side comparison
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[pyclass]
+pub struct ExperimentComparison {
+    #[pyo3(get)]
+    pub experiment_uids: Vec<String>,
+    #[pyo3(get)]
+    pub parameters: Vec<ParameterComparison>,
+    #[pyo3(get)]
+    pub metrics: Vec<MetricComparison>,
+}
+
+#[pymethods]
+impl ExperimentComparison {
+    pub fn __str__(&self) -> String {
+        PyHelperFuncs::__str__(self)
+    }
+}
+
+impl ExperimentComparison {
+    /// Aligns the metrics and parameters of each experiment.
+    ///
+    /// Metrics logged without a step use their position in the experiment's series. When a step
+    /// is logged more than once, the last value wins.
+    ///
+    /// # Arguments
+    ///
+    /// * `experiment_uids` - Experiments in the order they are compared
+    /// * `metrics` - Metrics of each experiment, in the order they were logged
+    /// * `parameters` - Parameters of each experiment
+    /// * `maximize` - Metrics where a higher value is better
+    pub fn new(
+        experiment_uids: Vec<String>,
+        metrics: HashMap<String, Vec<Metric>>,
+        parameters: HashMap<String, Vec<Parameter>>,
+        maximize: &[String],
+    ) -> Self {
+        // metric name -> experiment uid -> step -> value
+        let mut series: BTreeMap<String, HashMap<&str, BTreeMap<i64, f64>>> = BTreeMap::new();
+        for uid in &experiment_uids {
+            let mut logged: HashMap<&str, i64> = HashMap::new();
+            for metric in metrics.get(uid).into_iter().flatten() {
+                let idx = logged.entry(metric.name.as_str()).or_default();
+                let step = metric.step.map(i64::from).unwrap_or(*idx);
+                *idx += 1;
+
+                series
+                    .entry(metric.name.clone())
+                    .or_default()
+                    .entry(uid.as_str())
+                    .or_default()
+                    .insert(step, metric.value);
+            }
+        }
+
+        let metrics = series
+            .into_iter()
+            .map(|(name, runs)| {
+                let steps: Vec<i64> = runs
+                    .values()
+                    .flat_map(|points| points.keys().copied())
+                    .collect::<BTreeSet<_>>()
+                    .into_iter()
+                    .collect();
+                let maximized = maximize.contains(&name);
+
+                let runs = experiment_uids
+                    .iter()
+                    .map(|uid| {
+                        let points = runs.get(uid.as_str());
+                        MetricRun {
+                            experiment_uid: uid.clone(),
+                            values: steps
+                                .iter()
+                                .map(|step| points.and_then(|points| points.get(step).copied()))
+                                .collect(),
+                            summary: points.and_then(|points| summarize(points, maximized)),
+                        }
+                    })
+                    .collect();
+
+                MetricComparison { name, steps, runs }
+            })
+            .collect();
+
+        let mut table: BTreeMap<String, HashMap<&str, ParameterValue>> = BTreeMap::new();
+        for uid in &experiment_uids {
+            for parameter in parameters.get(uid).into_iter().flatten() {
+                table
+                    .entry(parameter.name.clone())
+                    .or_default()
+                    .insert(uid.as_str(), parameter.value.clone());
+            }
+        }
+
+        let parameters = table
+            .into_iter()
+            .map(|(name, values)| ParameterComparison {
+                values: experiment_uids
+                    .iter()
+                    .map(|uid| values.get(uid.as_str()).cloned())
+                    .collect(),
+                name,
+            })
+            .collect();
+
+        Self {
+            experiment_uids,
+            parameters,
+            metrics,
+        }
+    }
+}
+
+fn summarize(points: &BTreeMap<i64, f64>, maximize: bool) -> Option<MetricSummary> {
+    let (&last_step, &last) = points.last_key_value()?;
+    let mut summary = MetricSummary {
+        min: last,
+        max: last,
+        last,
+        best: last,
+        best_step: last_step,
+        count: points.len(),
+    };
+
+    // walk back from the last step so ties resolve to the earliest step
+    for (&step, &value) in points.iter().rev() {
+        summary.min = summary.min.min(value);
+        summary.max = summary.max.max(value);
+
+        let better = if maximize {
+            value >= summary.best
+        } else {
+            value <= summary.best
+        };
+        if better {
+            summary.best = value;
+            summary.best_step = step;
+        }
+    }
+
+    Some(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metric(name: &str, value: f64, step: Option<i32>) -> Metric {
+        Metric {
+            name: name.to_string(),
+            value,
+            step,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_experiment_comparison() {
+        let metrics = HashMap::from([
+            (
+                "a".to_string(),
+                vec![
+                    metric("loss", 0.9, Some(0)),
+                    metric("loss", 0.5, Some(1)),
+                    metric("loss", 0.6, Some(2)),
+                    metric("accuracy", 0.7, None),
+                    metric("accuracy", 0.8, None),
+                ],
+            ),
+            (
+                "b".to_string(),
+                vec![metric("loss", 0.8, Some(0)), metric("loss", 0.4, Some(3))],
+            ),
+        ]);
+        let parameters = HashMap::from([
+            (
+                "a".to_string(),
+                vec![
+                    Parameter {
+                        name: "lr".to_string(),
+                        value: ParameterValue::Float(0.1),
+                    },
+                    Parameter {
+                        name: "epochs".to_string(),
+                        value: ParameterValue::Int(3),
+                    },
+                ],
+            ),
+            (
+                "b".to_string(),
+                vec![Parameter {
+                    name: "lr".to_string(),
+                    value: ParameterValue::Float(0.1),
+                }],
+            ),
+        ]);
+
+        let comparison = ExperimentComparison::new(
+            vec!["a".to_string(), "b".to_string()],
+            metrics,
+            parameters,
+            &["accuracy".to_string()],
+        );
+
+        // metrics and parameters are sorted by name
+        let accuracy = &comparison.metrics[0];
+        assert_eq!(accuracy.name, "accuracy");
+        assert_eq!(accuracy.steps, vec![0, 1]);
+        assert_eq!(accuracy.runs[0].values, vec![Some(0.7), Some(0.8)]);
+        assert_eq!(accuracy.runs[0].summary.as_ref().unwrap().best_step, 1);
+        assert!(accuracy.runs[1].summary.is_none());
+        assert_eq!(accuracy.runs[1].values, vec![None, None]);
+
+        let loss = &comparison.metrics[1];
+        assert_eq!(loss.steps, vec![0, 1, 2, 3]);
+        assert_eq!(loss.runs[1].values, vec![Some(0.8), None, None, Some(0.4)]);
+        assert_eq!(
+            loss.runs[0].summary,
+            Some(MetricSummary {
+                min: 0.5,
+                max: 0.9,
+                last: 0.6,
+                best: 0.5,
+                best_step: 1,
+                count: 3,
+            })
+        );
+
+        let epochs = &comparison.parameters[0];
+        assert_eq!(epochs.name, "epochs");
+        assert_eq!(epochs.values, vec![Some(ParameterValue::Int(3)), None]);
+        assert!(epochs.differs());
+        assert!(!comparison.parameters[1].differs());
+    }
+}
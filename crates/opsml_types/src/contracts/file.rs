@@ -2,13 +2,14 @@ use crate::error::TypeError;
 use crate::interfaces::DriftProfileUri;
 use crate::RegistryType;
 use crate::StorageType;
-use opsml_crypt::decrypt_key;
+use opsml_crypt::{decrypt_key, encrypted_key, KeyContext, KeyProvider};
 use opsml_utils::{uid_to_byte_key, PyHelperFuncs};
 use pyo3::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fmt::Display;
 use std::path::PathBuf;
+use tracing::warn;
 
 #[derive(Serialize, Deserialize)]
 pub struct MultiPartQuery {
@@ -176,6 +177,9 @@ pub struct ArtifactKeyRequest {
     pub registry_type: RegistryType,
 }
 
+/// Key provider context purpose of artifact keys stored by the server
+const ARTIFACT_KEY_PURPOSE: &str = "artifact_key";
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ArtifactKey {
     pub uid: String,
@@ -183,6 +187,10 @@ pub struct ArtifactKey {
     pub registry_type: RegistryType,
     pub encrypted_key: Vec<u8>,
     pub storage_key: String,
+    /// Whether the artifacts of the card are recorded in a manifest. Cards saved before
+    /// manifests were introduced have none and their artifacts cannot be verified
+    #[serde(default)]
+    pub has_manifest: bool,
 }
 
 impl ArtifactKey {
@@ -196,6 +204,71 @@ impl ArtifactKey {
     pub fn storage_path(&self) -> PathBuf {
         PathBuf::from(&self.storage_key)
     }
+
+    fn key_context(&self) -> KeyContext<'_> {
+        KeyContext {
+            purpose: ARTIFACT_KEY_PURPOSE,
+            id: &self.uid,
+        }
+    }
+
+    /// Converts a key returned to clients into the form stored in the database, where it is
+    /// wrapped by the server key provider instead of with the card uid
+    pub async fn seal(&self, provider: &dyn KeyProvider) -> Result<Self, TypeError> {
+        let key = self.get_decrypt_key()?;
+
+        Ok(Self {
+            encrypted_key: provider.wrap(self.key_context(), &key).await?,
+            ..self.clone()
+        })
+    }
+
+    /// Unwraps a key stored in the database with the key provider, so it can be returned to clients
+    pub async fn unseal(&self, provider: &dyn KeyProvider) -> Result<Self, TypeError> {
+        let key = self.unwrap_stored_key(provider).await?;
+        let uid_key = uid_to_byte_key(&self.uid)?;
+
+        Ok(Self {
+            encrypted_key: encrypted_key(&uid_key, &key)?,
+            ..self.clone()
+        })
+    }
+
+    /// Decrypts a stored key. Keys stored before keys were wrapped by the key provider are
+    /// wrapped with the card uid. Keys in the format of the provider are never read that way,
+    /// so provider errors for them are returned as is
+    pub async fn unwrap_stored_key(
+        &self,
+        provider: &dyn KeyProvider,
+    ) -> Result<Vec<u8>, TypeError> {
+        let error = match provider
+            .unwrap(self.key_context(), &self.encrypted_key)
+            .await
+        {
+            Ok(key) => return Ok(key),
+            Err(e) if provider.is_provider_format(&self.encrypted_key) => return Err(e.into()),
+            Err(e) => e,
+        };
+
+        match self.get_decrypt_key() {
+            Ok(key) => {
+                warn!(
+                    "Artifact key of card {} is not wrapped by the key provider. Run `opsml db rotate-key` to wrap it",
+                    self.uid
+                );
+                Ok(key)
+            }
+            Err(_) => Err(error.into()),
+        }
+    }
+
+    /// Whether the stored key is wrapped by the given key provider
+    pub async fn is_sealed_with(&self, provider: &dyn KeyProvider) -> bool {
+        provider
+            .unwrap(self.key_context(), &self.encrypted_key)
+            .await
+            .is_ok()
+    }
 }
 
 // implement Display for ArtifactKey and mask the encrypted_key and storage_key
@@ -209,7 +282,7 @@ impl Display for ArtifactKey {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Operation {
     Create,
     Read,
@@ -244,7 +317,27 @@ impl Display for Operation {
     }
 }
 
-#[derive(Debug, Clone)]
+impl Operation {
+    pub fn from_string(s: &str) -> Result<Self, TypeError> {
+        match s.to_lowercase().as_str() {
+            "read" => Ok(Operation::Read),
+            "write" => Ok(Operation::Write),
+            "delete" => Ok(Operation::Delete),
+            "list" => Ok(Operation::List),
+            "info" => Ok(Operation::Info),
+            "encrypt" => Ok(Operation::Encrypt),
+            "decrypt" => Ok(Operation::Decrypt),
+            "create" => Ok(Operation::Create),
+            "load" => Ok(Operation::Load),
+            "check" => Ok(Operation::Check),
+            "update" => Ok(Operation::Update),
+            "unknown" => Ok(Operation::Unknown),
+            _ => Err(TypeError::InvalidOperation(s.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ResourceType {
     File,
     Database,
@@ -263,7 +356,7 @@ impl Display for ResourceType {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum AuditStatus {
     Success,
     Failed,
@@ -280,6 +373,17 @@ impl Display for AuditStatus {
     }
 }
 
+impl AuditStatus {
+    pub fn from_string(s: &str) -> Result<Self, TypeError> {
+        match s.to_lowercase().as_str() {
+            "success" => Ok(AuditStatus::Success),
+            "failed" => Ok(AuditStatus::Failed),
+            "denied" => Ok(AuditStatus::Denied),
+            _ => Err(TypeError::InvalidAuditStatus(s.to_string())),
+        }
+    }
+}
+
 /// Request to get a file from the registry
 ///
 /// # Arguments
@@ -0,0 +1,288 @@
+use crate::contracts::{AuditableRequest, ResourceType};
+use crate::error::TypeError;
+use crate::RegistryType;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Display};
+
+/// Number of hops walked in each direction when no depth is given
+pub const DEFAULT_LINEAGE_DEPTH: i32 = 3;
+
+/// Maximum number of hops walked in each direction
+pub const MAX_LINEAGE_DEPTH: i32 = 10;
+
+/// Direction to walk from the requested card. Upstream cards are the cards it was
+/// produced from (e.g. the datacard of a model), downstream cards are the cards that depend on it
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum LineageDirection {
+    Upstream,
+    Downstream,
+    #[default]
+    Both,
+}
+
+impl Display for LineageDirection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LineageDirection::Upstream => write!(f, "upstream"),
+            LineageDirection::Downstream => write!(f, "downstream"),
+            LineageDirection::Both => write!(f, "both"),
+        }
+    }
+}
+
+impl LineageDirection {
+    pub fn from_string(s: &str) -> Result<Self, TypeError> {
+        match s.to_lowercase().as_str() {
+            "upstream" => Ok(LineageDirection::Upstream),
+            "downstream" => Ok(LineageDirection::Downstream),
+            "both" => Ok(LineageDirection::Both),
+            _ => Err(TypeError::InvalidLineageDirection(s.to_string())),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct CardLineageRequest {
+    pub uid: String,
+    pub depth: Option<i32>,
+    pub direction: Option<LineageDirection>,
+}
+
+impl CardLineageRequest {
+    /// Number of hops to walk, capped at `MAX_LINEAGE_DEPTH`
+    pub fn depth(&self) -> i32 {
+        self.depth
+            .unwrap_or(DEFAULT_LINEAGE_DEPTH)
+            .clamp(1, MAX_LINEAGE_DEPTH)
+    }
+
+    pub fn upstream_depth(&self) -> i32 {
+        match self.direction.clone().unwrap_or_default() {
+            LineageDirection::Downstream => 0,
+            _ => self.depth(),
+        }
+    }
+
+    pub fn downstream_depth(&self) -> i32 {
+        match self.direction.clone().unwrap_or_default() {
+            LineageDirection::Upstream => 0,
+            _ => self.depth(),
+        }
+    }
+}
+
+impl AuditableRequest for CardLineageRequest {
+    fn get_resource_id(&self) -> String {
+        self.uid.clone()
+    }
+
+    fn get_metadata(&self) -> String {
+        serde_json::to_string(self)
+            .unwrap_or_else(|e| format!("Failed to serialize CardLineageRequest: {e}"))
+    }
+
+    fn get_registry_type(&self) -> Option<RegistryType> {
+        None
+    }
+
+    fn get_resource_type(&self) -> ResourceType {
+        ResourceType::Database
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LineageNode {
+    pub uid: String,
+    pub registry_type: RegistryType,
+    pub space: String,
+    pub name: String,
+    pub version: String,
+    /// Whether the card is in the trash
+    pub deleted: bool,
+}
+
+impl LineageNode {
+    fn label(&self) -> String {
+        format!(
+            "{}: {}/{} v{}",
+            self.registry_type, self.space, self.name, self.version
+        )
+    }
+}
+
+/// A dependency between two cards. `source_uid` is upstream of `target_uid`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LineageEdge {
+    pub source_uid: String,
+    pub target_uid: String,
+}
+
+/// Cards reachable from `root_uid` and the dependencies between them
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CardLineage {
+    pub root_uid: String,
+    pub nodes: Vec<LineageNode>,
+    pub edges: Vec<LineageEdge>,
+}
+
+impl CardLineage {
+    /// Keeps the nodes matching `keep` and the edges between them
+    pub fn retain_nodes(&mut self, keep: impl Fn(&LineageNode) -> bool) {
+        self.nodes.retain(|node| keep(node));
+
+        let uids: HashSet<&str> = self.nodes.iter().map(|node| node.uid.as_str()).collect();
+        self.edges.retain(|edge| {
+            uids.contains(edge.source_uid.as_str()) && uids.contains(edge.target_uid.as_str())
+        });
+    }
+
+    /// Short node ids used in rendered graphs, as uids are not valid Mermaid identifiers
+    fn node_ids(&self) -> HashMap<&str, String> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (node.uid.as_str(), format!("n{i}")))
+            .collect()
+    }
+
+    /// Renders the lineage as a Mermaid flowchart
+    pub fn to_mermaid(&self) -> String {
+        let ids = self.node_ids();
+        let mut lines = vec!["graph LR".to_string()];
+
+        for node in &self.nodes {
+            lines.push(format!(
+                "    {}[\"{}\"]",
+                ids[node.uid.as_str()],
+                node.label().replace('"', "#quot;")
+            ));
+        }
+
+        for edge in &self.edges {
+            if let (Some(source), Some(target)) = (
+                ids.get(edge.source_uid.as_str()),
+                ids.get(edge.target_uid.as_str()),
+            ) {
+                lines.push(format!("    {source} --> {target}"));
+            }
+        }
+
+        lines.push("    classDef root stroke-width:3px".to_string());
+        lines.push("    classDef deleted stroke-dasharray:5 5".to_string());
+
+        if let Some(root) = ids.get(self.root_uid.as_str()) {
+            lines.push(format!("    class {root} root"));
+        }
+
+        for node in self.nodes.iter().filter(|node| node.deleted) {
+            lines.push(format!("    class {} deleted", ids[node.uid.as_str()]));
+        }
+
+        lines.join("\n")
+    }
+
+    /// Renders the lineage as a Graphviz DOT digraph
+    pub fn to_dot(&self) -> String {
+        let mut lines = vec![
+            "digraph lineage {".to_string(),
+            "    rankdir=LR;".to_string(),
+        ];
+
+        for node in &self.nodes {
+            let mut attributes = vec![format!("label=\"{}\"", node.label().replace('"', "\\\""))];
+
+            if node.uid == self.root_uid {
+                attributes.push("penwidth=3".to_string());
+            }
+
+            if node.deleted {
+                attributes.push("style=dashed".to_string());
+            }
+
+            lines.push(format!("    \"{}\" [{}];", node.uid, attributes.join(", ")));
+        }
+
+        for edge in &self.edges {
+            lines.push(format!(
+                "    \"{}\" -> \"{}\";",
+                edge.source_uid, edge.target_uid
+            ));
+        }
+
+        lines.push("}".to_string());
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lineage() -> CardLineage {
+        let node =
+            |uid: &str, registry_type: RegistryType, name: &str, deleted: bool| LineageNode {
+                uid: uid.to_string(),
+                registry_type,
+                space: "space".to_string(),
+                name: name.to_string(),
+                version: "1.0.0".to_string(),
+                deleted,
+            };
+
+        CardLineage {
+            root_uid: "model-uid".to_string(),
+            nodes: vec![
+                node("model-uid", RegistryType::Model, "model", false),
+                node("data-uid", RegistryType::Data, "data", true),
+            ],
+            edges: vec![LineageEdge {
+                source_uid: "data-uid".to_string(),
+                target_uid: "model-uid".to_string(),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_lineage_request_depth() {
+        let request = CardLineageRequest {
+            uid: "uid".to_string(),
+            depth: Some(50),
+            direction: Some(LineageDirection::Upstream),
+        };
+
+        assert_eq!(request.upstream_depth(), MAX_LINEAGE_DEPTH);
+        assert_eq!(request.downstream_depth(), 0);
+
+        let request = CardLineageRequest {
+            uid: "uid".to_string(),
+            ..Default::default()
+        };
+
+        assert_eq!(request.upstream_depth(), DEFAULT_LINEAGE_DEPTH);
+        assert_eq!(request.downstream_depth(), DEFAULT_LINEAGE_DEPTH);
+    }
+
+    #[test]
+    fn test_lineage_to_mermaid() {
+        let mermaid = lineage().to_mermaid();
+
+        assert!(mermaid.starts_with("graph LR"));
+        assert!(mermaid.contains("n0[\"model: space/model v1.0.0\"]"));
+        assert!(mermaid.contains("n1 --> n0"));
+        assert!(mermaid.contains("class n0 root"));
+        assert!(mermaid.contains("class n1 deleted"));
+    }
+
+    #[test]
+    fn test_lineage_to_dot() {
+        let dot = lineage().to_dot();
+
+        assert!(dot.starts_with("digraph lineage {"));
+        assert!(dot.contains("\"model-uid\" [label=\"model: space/model v1.0.0\", penwidth=3];"));
+        assert!(dot.contains("\"data-uid\" [label=\"data: space/data v1.0.0\", style=dashed];"));
+        assert!(dot.contains("\"data-uid\" -> \"model-uid\";"));
+        assert!(dot.ends_with('}'));
+    }
+}
@@ -0,0 +1,531 @@
+use crate::contracts::{ArtifactKey, SignatureStatus, SpaceSigningKey};
+use crate::error::TypeError;
+use crate::{RegistryType, SaveName, Suffix};
+use base64::prelude::*;
+use opsml_colors::Colorize;
+use opsml_crypt::{decrypt_file, sha256_file, verify_signature, SigningKey};
+use opsml_utils::FileUtils;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+use std::path::{Path, PathBuf};
+use tabled::settings::{format::Format, object::Rows, Alignment, Color, Style};
+use tabled::{Table, Tabled};
+
+/// Digest of a stored file
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ManifestEntry {
+    pub sha256: String,
+    pub size: u64,
+}
+
+/// SHA-256 digests of the files stored for a card. Digests are taken from the stored (encrypted)
+/// bytes, so artifacts can be checked before they are decrypted.
+///
+/// The manifest is saved as `manifest.json` in the storage path of the card and entries are keyed
+/// by their path relative to it, using `/` as separator
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct ArtifactManifest {
+    pub files: BTreeMap<String, ManifestEntry>,
+}
+
+/// Ed25519 signature over the manifest of a card by a signing key of the card space.
+///
+/// Saved as `manifest_signature.json` next to the manifest. The signature covers the manifest
+/// together with the identity of the card (see [`ArtifactManifest::signing_payload`])
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ManifestSignature {
+    pub key_id: String,
+    /// Base64 encoded signature
+    pub signature: String,
+}
+
+/// Joins a manifest key prefix and a relative path
+fn manifest_key(prefix: &Path, relative_path: &Path) -> String {
+    prefix
+        .join(relative_path)
+        .to_string_lossy()
+        .replace('\\', "/")
+        .trim_matches('/')
+        .to_string()
+}
+
+impl ArtifactManifest {
+    /// Path of the manifest relative to the storage path of a card
+    pub fn path() -> PathBuf {
+        PathBuf::from(SaveName::Manifest).with_extension(Suffix::Json)
+    }
+
+    /// Path of the manifest signature relative to the storage path of a card
+    pub fn signature_path() -> PathBuf {
+        PathBuf::from(SaveName::ManifestSignature).with_extension(Suffix::Json)
+    }
+
+    fn is_manifest(key: &str) -> bool {
+        Path::new(key) == Self::path() || Path::new(key) == Self::signature_path()
+    }
+
+    /// Bytes covered by the manifest signature. The card identity is included so a signed
+    /// manifest cannot be copied to another card
+    pub fn signing_payload(&self, key: &ArtifactKey) -> Result<Vec<u8>, TypeError> {
+        let mut payload = format!(
+            "opsml-card-manifest-v1\n{}\n{}\n{}\n",
+            key.registry_type, key.space, key.uid
+        )
+        .into_bytes();
+        payload.extend(serde_json::to_vec(self)?);
+
+        Ok(payload)
+    }
+
+    pub fn sign(
+        &self,
+        key: &ArtifactKey,
+        signing_key: &SigningKey,
+    ) -> Result<ManifestSignature, TypeError> {
+        let signature = signing_key.sign(&self.signing_payload(key)?);
+
+        Ok(ManifestSignature {
+            key_id: signing_key.key_id(),
+            signature: BASE64_STANDARD.encode(signature),
+        })
+    }
+
+    /// Checks `signature` against the signing keys registered for the card space
+    pub fn verify_signature(
+        &self,
+        key: &ArtifactKey,
+        signature: &ManifestSignature,
+        keys: &[SpaceSigningKey],
+    ) -> Result<SignatureStatus, TypeError> {
+        let Some(signing_key) = keys
+            .iter()
+            .find(|k| k.key_id == signature.key_id && k.space == key.space)
+        else {
+            return Ok(SignatureStatus::Invalid(format!(
+                "key {} is not registered for space {}",
+                signature.key_id, key.space
+            )));
+        };
+
+        if !signing_key.active {
+            return Ok(SignatureStatus::Invalid(format!(
+                "key {} was revoked",
+                signature.key_id
+            )));
+        }
+
+        let Ok(signature_bytes) = BASE64_STANDARD.decode(&signature.signature) else {
+            return Ok(SignatureStatus::Invalid(
+                "signature is not valid base64".to_string(),
+            ));
+        };
+
+        match verify_signature(
+            &signing_key.public_key_bytes()?,
+            &self.signing_payload(key)?,
+            &signature_bytes,
+        ) {
+            Ok(()) => Ok(SignatureStatus::Valid(signing_key.key_id.clone())),
+            Err(_) => Ok(SignatureStatus::Invalid(
+                "manifest does not match the signature".to_string(),
+            )),
+        }
+    }
+
+    /// Files below `lpath` (a file or directory) stored under `prefix`, keyed by their manifest path
+    fn local_files(lpath: &Path, prefix: &Path) -> Result<Vec<(String, PathBuf)>, TypeError> {
+        if lpath.is_file() {
+            return Ok(vec![(
+                manifest_key(prefix, Path::new("")),
+                lpath.to_path_buf(),
+            )]);
+        }
+
+        let mut files = Vec::new();
+        for file in FileUtils::list_files(lpath)? {
+            let relative_path = file
+                .strip_prefix(lpath)
+                .map_err(|e| TypeError::ManifestPathError(e.to_string()))?;
+            let key = manifest_key(prefix, relative_path);
+
+            if !Self::is_manifest(&key) {
+                files.push((key, file));
+            }
+        }
+
+        Ok(files)
+    }
+
+    /// Hashes the files below `lpath` (a file or directory) that are stored under `prefix`,
+    /// replacing existing entries
+    pub fn add_files(&mut self, lpath: &Path, prefix: &Path) -> Result<(), TypeError> {
+        for (key, file) in Self::local_files(lpath, prefix)? {
+            let (sha256, size) = sha256_file(&file)?;
+            self.files.insert(key, ManifestEntry { sha256, size });
+        }
+
+        Ok(())
+    }
+
+    /// Compares files downloaded from `prefix` to `lpath` (a file or directory) with the manifest.
+    ///
+    /// Every entry stored under `prefix` must exist and match its digest. When `include_untracked`
+    /// is set, downloaded files without an entry are reported as well
+    pub fn verify_files(
+        &self,
+        lpath: &Path,
+        prefix: &Path,
+        include_untracked: bool,
+    ) -> Result<Vec<ArtifactMismatch>, TypeError> {
+        let prefix_key = manifest_key(prefix, Path::new(""));
+        let mut mismatches = Vec::new();
+
+        for (key, entry) in &self.files {
+            let file = if *key == prefix_key {
+                lpath.to_path_buf()
+            } else if prefix_key.is_empty() {
+                lpath.join(key)
+            } else {
+                match key.strip_prefix(&format!("{prefix_key}/")) {
+                    Some(relative_path) => lpath.join(relative_path),
+                    None => continue,
+                }
+            };
+
+            if !file.is_file() {
+                mismatches.push(ArtifactMismatch {
+                    path: key.clone(),
+                    kind: MismatchKind::Missing,
+                    expected: Some(entry.sha256.clone()),
+                    actual: None,
+                });
+                continue;
+            }
+
+            let (sha256, size) = sha256_file(&file)?;
+            if sha256 != entry.sha256 || size != entry.size {
+                mismatches.push(ArtifactMismatch {
+                    path: key.clone(),
+                    kind: MismatchKind::Modified,
+                    expected: Some(entry.sha256.clone()),
+                    actual: Some(sha256),
+                });
+            }
+        }
+
+        if include_untracked {
+            for (key, file) in Self::local_files(lpath, prefix)? {
+                if !self.files.contains_key(&key) {
+                    mismatches.push(ArtifactMismatch {
+                        path: key,
+                        kind: MismatchKind::Untracked,
+                        expected: None,
+                        actual: Some(sha256_file(&file)?.0),
+                    });
+                }
+            }
+        }
+
+        Ok(mismatches)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum MismatchKind {
+    /// The manifest lists the file but it is not stored
+    Missing,
+    /// The stored file does not match its digest
+    Modified,
+    /// The file is stored but not listed in the manifest
+    Untracked,
+}
+
+impl Display for MismatchKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MismatchKind::Missing => write!(f, "missing"),
+            MismatchKind::Modified => write!(f, "modified"),
+            MismatchKind::Untracked => write!(f, "untracked"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ArtifactMismatch {
+    pub path: String,
+    pub kind: MismatchKind,
+    pub expected: Option<String>,
+    pub actual: Option<String>,
+}
+
+impl Display for ArtifactMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ({})", self.path, self.kind)
+    }
+}
+
+#[derive(Tabled)]
+struct ArtifactMismatchTableEntry {
+    path: String,
+    status: String,
+    expected: String,
+    actual: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct VerifyArtifactsRequest {
+    pub uid: String,
+    pub registry_type: RegistryType,
+}
+
+/// Result of re-hashing the stored artifacts of a card
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ArtifactIntegrityReport {
+    pub uid: String,
+    pub registry_type: RegistryType,
+    /// Cards saved before manifests were introduced have none and cannot be checked
+    pub has_manifest: bool,
+    pub files_checked: usize,
+    pub mismatches: Vec<ArtifactMismatch>,
+}
+
+impl ArtifactIntegrityReport {
+    /// Checks `lpath`, holding every stored file of the card, against the card manifest. The
+    /// manifest is decrypted in place and the other files are left encrypted
+    pub fn from_directory(key: &ArtifactKey, lpath: &Path) -> Result<Self, TypeError> {
+        let manifest_path = lpath.join(ArtifactManifest::path());
+
+        let (has_manifest, files_checked, mismatches) = match manifest_path.is_file() {
+            true => {
+                decrypt_file(&manifest_path, &key.get_decrypt_key()?)?;
+                let manifest: ArtifactManifest =
+                    serde_json::from_str(&std::fs::read_to_string(&manifest_path)?)?;
+                let mismatches = manifest.verify_files(lpath, Path::new(""), true)?;
+
+                (true, manifest.files.len(), mismatches)
+            }
+            // a manifest was recorded for the card, so it must not be silently dropped
+            false if key.has_manifest => (
+                false,
+                0,
+                vec![ArtifactMismatch {
+                    path: manifest_key(&ArtifactManifest::path(), Path::new("")),
+                    kind: MismatchKind::Missing,
+                    expected: None,
+                    actual: None,
+                }],
+            ),
+            false => (false, 0, Vec::new()),
+        };
+
+        Ok(Self {
+            uid: key.uid.clone(),
+            registry_type: key.registry_type.clone(),
+            has_manifest,
+            files_checked,
+            mismatches,
+        })
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.has_manifest && self.mismatches.is_empty()
+    }
+
+    pub fn as_table(&self) {
+        let entries: Vec<ArtifactMismatchTableEntry> = self
+            .mismatches
+            .iter()
+            .map(|mismatch| ArtifactMismatchTableEntry {
+                path: mismatch.path.clone(),
+                status: Colorize::alert(&mismatch.kind.to_string()),
+                expected: mismatch.expected.clone().unwrap_or_default(),
+                actual: mismatch.actual.clone().unwrap_or_default(),
+            })
+            .collect();
+
+        let mut table = Table::new(entries);
+
+        table.with(Style::sharp());
+        table.modify(
+            Rows::new(0..1),
+            (
+                Format::content(Colorize::green),
+                Alignment::center(),
+                Color::BOLD,
+            ),
+        );
+
+        println!("{}", &table);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(path: &Path, content: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    fn artifact_key(space: &str, uid: &str) -> ArtifactKey {
+        ArtifactKey {
+            uid: uid.to_string(),
+            space: space.to_string(),
+            registry_type: RegistryType::Model,
+            encrypted_key: vec![],
+            storage_key: format!("opsml_model_registry/{space}/model/v1.0.0"),
+            has_manifest: true,
+        }
+    }
+
+    fn registered_key(space: &str, signing_key: &SigningKey) -> SpaceSigningKey {
+        SpaceSigningKey {
+            key_id: signing_key.key_id(),
+            space: space.to_string(),
+            public_key: BASE64_STANDARD.encode(signing_key.public_key()),
+            created_at: chrono::Utc::now(),
+            created_by: "admin".to_string(),
+            active: true,
+        }
+    }
+
+    #[test]
+    fn test_manifest_verify_files() {
+        let dir = tempfile::tempdir().unwrap();
+        write(&dir.path().join("card.json"), "card");
+        write(&dir.path().join("artifacts/model.bin"), "model");
+        write(&dir.path().join("manifest.json"), "{}");
+
+        let mut manifest = ArtifactManifest::default();
+        manifest.add_files(dir.path(), Path::new("")).unwrap();
+
+        // the manifest never lists itself
+        assert_eq!(
+            manifest.files.keys().collect::<Vec<_>>(),
+            vec!["artifacts/model.bin", "card.json"]
+        );
+        assert!(manifest
+            .verify_files(dir.path(), Path::new(""), true)
+            .unwrap()
+            .is_empty());
+
+        // a sub directory and a single file are checked against the entries below them
+        let artifacts = dir.path().join("artifacts");
+        assert!(manifest
+            .verify_files(&artifacts, Path::new("artifacts"), false)
+            .unwrap()
+            .is_empty());
+        assert!(manifest
+            .verify_files(
+                &artifacts.join("model.bin"),
+                Path::new("artifacts/model.bin"),
+                false
+            )
+            .unwrap()
+            .is_empty());
+
+        write(&artifacts.join("model.bin"), "tampered");
+        write(&artifacts.join("extra.txt"), "extra");
+        std::fs::remove_file(dir.path().join("card.json")).unwrap();
+
+        let kinds = manifest
+            .verify_files(dir.path(), Path::new(""), true)
+            .unwrap()
+            .into_iter()
+            .map(|mismatch| (mismatch.path, mismatch.kind))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            kinds,
+            vec![
+                ("artifacts/model.bin".to_string(), MismatchKind::Modified),
+                ("card.json".to_string(), MismatchKind::Missing),
+                ("artifacts/extra.txt".to_string(), MismatchKind::Untracked),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_integrity_report_missing_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        write(&dir.path().join("card.json"), "card");
+
+        // cards saved before manifests were recorded are reported as unchecked
+        let legacy_key = ArtifactKey {
+            has_manifest: false,
+            ..artifact_key("space", "uid")
+        };
+        let report = ArtifactIntegrityReport::from_directory(&legacy_key, dir.path()).unwrap();
+        assert!(!report.is_valid());
+        assert!(report.mismatches.is_empty());
+
+        // a recorded manifest that was removed from storage is reported as missing
+        let report =
+            ArtifactIntegrityReport::from_directory(&artifact_key("space", "uid"), dir.path())
+                .unwrap();
+        assert_eq!(
+            report
+                .mismatches
+                .iter()
+                .map(|mismatch| (mismatch.path.as_str(), &mismatch.kind))
+                .collect::<Vec<_>>(),
+            vec![("manifest.json", &MismatchKind::Missing)]
+        );
+    }
+
+    #[test]
+    fn test_manifest_signature() {
+        let mut manifest = ArtifactManifest::default();
+        manifest.files.insert(
+            "card.json".to_string(),
+            ManifestEntry {
+                sha256: "abc".to_string(),
+                size: 3,
+            },
+        );
+
+        let key = artifact_key("space", "uid");
+        let signing_key = SigningKey::generate().unwrap();
+        let mut keys = vec![registered_key("space", &signing_key)];
+        let signature = manifest.sign(&key, &signing_key).unwrap();
+
+        assert_eq!(
+            manifest.verify_signature(&key, &signature, &keys).unwrap(),
+            SignatureStatus::Valid(signing_key.key_id())
+        );
+
+        // the signature is bound to the card and to the manifest digests
+        let other_card = artifact_key("space", "other-uid");
+        assert!(matches!(
+            manifest
+                .verify_signature(&other_card, &signature, &keys)
+                .unwrap(),
+            SignatureStatus::Invalid(_)
+        ));
+
+        let mut tampered = manifest.clone();
+        tampered.files.get_mut("card.json").unwrap().sha256 = "def".to_string();
+        assert!(matches!(
+            tampered.verify_signature(&key, &signature, &keys).unwrap(),
+            SignatureStatus::Invalid(_)
+        ));
+
+        // keys only sign cards of their own space
+        let other_space = artifact_key("other-space", "uid");
+        assert!(matches!(
+            manifest
+                .verify_signature(&other_space, &signature, &keys)
+                .unwrap(),
+            SignatureStatus::Invalid(_)
+        ));
+
+        keys[0].active = false;
+        assert!(matches!(
+            manifest.verify_signature(&key, &signature, &keys).unwrap(),
+            SignatureStatus::Invalid(_)
+        ));
+    }
+}
@@ -1,13 +1,23 @@
+pub mod alias;
 pub mod card;
 pub mod event;
 pub mod experiment;
 pub mod file;
+pub mod lineage;
+pub mod manifest;
 pub mod scouter;
+pub mod search;
+pub mod signing;
 pub mod traits;
 
+pub use alias::*;
 pub use card::*;
 pub use event::*;
 pub use experiment::*;
 pub use file::*;
+pub use lineage::*;
+pub use manifest::*;
 pub use scouter::*;
+pub use search::*;
+pub use signing::*;
 pub use traits::*;
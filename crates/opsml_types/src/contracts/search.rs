@@ -0,0 +1,139 @@
+use crate::contracts::{AuditableRequest, ResourceType};
+use crate::RegistryType;
+use chrono::{DateTime, Utc};
+use opsml_colors::Colorize;
+use opsml_utils::PyHelperFuncs;
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+use tabled::settings::{format::Format, object::Rows, Alignment, Color, Style};
+use tabled::{Table, Tabled};
+
+/// Number of results returned by a card search when no limit is given
+pub const DEFAULT_SEARCH_LIMIT: i32 = 25;
+
+/// Maximum number of results returned by a card search
+pub const MAX_SEARCH_LIMIT: i32 = 100;
+
+/// Splits a search string into lowercase terms of letters, digits and underscores.
+/// Terms are matched as prefixes and every term must match, regardless of the backend
+pub fn search_terms(query: &str) -> Vec<String> {
+    query
+        .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .filter(|term| !term.is_empty())
+        .map(|term| term.to_lowercase())
+        .collect()
+}
+
+/// Full-text search across card names, spaces, tags, README content and metadata
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct CardSearchQuery {
+    pub query: String,
+    /// Restrict results to a single registry
+    pub registry_type: Option<RegistryType>,
+    pub space: Option<String>,
+    pub limit: Option<i32>,
+}
+
+impl CardSearchQuery {
+    /// Number of results to return, capped at `MAX_SEARCH_LIMIT`
+    pub fn limit(&self) -> i32 {
+        self.limit
+            .unwrap_or(DEFAULT_SEARCH_LIMIT)
+            .clamp(1, MAX_SEARCH_LIMIT)
+    }
+}
+
+impl AuditableRequest for CardSearchQuery {
+    fn get_resource_id(&self) -> String {
+        self.query.clone()
+    }
+
+    fn get_metadata(&self) -> String {
+        serde_json::to_string(self)
+            .unwrap_or_else(|e| format!("Failed to serialize CardSearchQuery: {e}"))
+    }
+
+    fn get_registry_type(&self) -> Option<RegistryType> {
+        self.registry_type.clone()
+    }
+
+    fn get_resource_type(&self) -> ResourceType {
+        ResourceType::Database
+    }
+}
+
+/// A card version matching a search. Results are ordered by `score`, highest first
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[pyclass]
+pub struct CardSearchResult {
+    #[pyo3(get)]
+    pub uid: String,
+    #[pyo3(get)]
+    pub registry_type: RegistryType,
+    #[pyo3(get)]
+    pub space: String,
+    #[pyo3(get)]
+    pub name: String,
+    #[pyo3(get)]
+    pub version: String,
+    #[pyo3(get)]
+    pub tags: Vec<String>,
+    #[pyo3(get)]
+    pub created_at: DateTime<Utc>,
+    /// Backend-specific relevance score. Only comparable within a single search
+    #[pyo3(get)]
+    pub score: f64,
+}
+
+#[pymethods]
+impl CardSearchResult {
+    pub fn __str__(&self) -> String {
+        PyHelperFuncs::__str__(self)
+    }
+}
+
+#[derive(Tabled)]
+struct CardSearchTableEntry {
+    registry_type: String,
+    space: String,
+    name: String,
+    version: String,
+    score: String,
+    uid: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CardSearchResults {
+    pub results: Vec<CardSearchResult>,
+}
+
+impl CardSearchResults {
+    pub fn as_table(&self) {
+        let entries: Vec<CardSearchTableEntry> = self
+            .results
+            .iter()
+            .map(|result| CardSearchTableEntry {
+                registry_type: result.registry_type.to_string(),
+                space: result.space.clone(),
+                name: result.name.clone(),
+                version: result.version.clone(),
+                score: format!("{:.3}", result.score),
+                uid: Colorize::purple(&result.uid),
+            })
+            .collect();
+
+        let mut table = Table::new(entries);
+
+        table.with(Style::sharp());
+        table.modify(
+            Rows::new(0..1),
+            (
+                Format::content(Colorize::green),
+                Alignment::center(),
+                Color::BOLD,
+            ),
+        );
+
+        println!("{}", &table);
+    }
+}
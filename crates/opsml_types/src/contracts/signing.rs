@@ -0,0 +1,202 @@
+use crate::error::TypeError;
+use base64::prelude::*;
+use chrono::{DateTime, Utc};
+use opsml_colors::Colorize;
+use opsml_crypt::SigningKey;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+use std::path::Path;
+use tabled::settings::{format::Format, object::Rows, Alignment, Color, Style};
+use tabled::{Table, Tabled};
+
+/// Request to register the public key of a space signing key
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CreateSigningKeyRequest {
+    pub space: String,
+    /// Base64 encoded raw Ed25519 public key
+    pub public_key: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct SigningKeyQuery {
+    /// Only return the keys of this space
+    pub space: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RevokeSigningKeyRequest {
+    pub key_id: String,
+}
+
+/// Public key trusted to sign the cards of a space
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SpaceSigningKey {
+    pub key_id: String,
+    pub space: String,
+    /// Base64 encoded raw Ed25519 public key
+    pub public_key: String,
+    pub created_at: DateTime<Utc>,
+    pub created_by: String,
+    /// Revoked keys are kept so existing signatures can be reported, but no longer verify
+    pub active: bool,
+}
+
+impl SpaceSigningKey {
+    pub fn public_key_bytes(&self) -> Result<Vec<u8>, TypeError> {
+        Ok(BASE64_STANDARD.decode(&self.public_key)?)
+    }
+}
+
+#[derive(Tabled)]
+struct SigningKeyTableEntry {
+    key_id: String,
+    space: String,
+    status: String,
+    created_by: String,
+    created_at: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SigningKeyListResponse {
+    pub keys: Vec<SpaceSigningKey>,
+}
+
+impl SigningKeyListResponse {
+    pub fn as_table(&self) {
+        let entries: Vec<SigningKeyTableEntry> = self
+            .keys
+            .iter()
+            .map(|key| SigningKeyTableEntry {
+                key_id: Colorize::purple(&key.key_id),
+                space: key.space.clone(),
+                status: match key.active {
+                    true => "active".to_string(),
+                    false => Colorize::alert("revoked"),
+                },
+                created_by: key.created_by.clone(),
+                created_at: key.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            })
+            .collect();
+
+        let mut table = Table::new(entries);
+
+        table.with(Style::sharp());
+        table.modify(
+            Rows::new(0..1),
+            (
+                Format::content(Colorize::green),
+                Alignment::center(),
+                Color::BOLD,
+            ),
+        );
+
+        println!("{}", &table);
+    }
+}
+
+/// Private half of a space signing key, kept by the pipeline that registers cards.
+/// Point `OPSML_SIGNING_KEY` at this file to sign the manifests of registered cards
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SignerKeyFile {
+    pub key_id: String,
+    pub space: String,
+    /// Base64 encoded PKCS#8 Ed25519 private key
+    pub private_key: String,
+}
+
+impl SignerKeyFile {
+    pub fn new(space: &str, key: &SigningKey) -> Self {
+        SignerKeyFile {
+            key_id: key.key_id(),
+            space: space.to_string(),
+            private_key: BASE64_STANDARD.encode(key.to_pkcs8()),
+        }
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self, TypeError> {
+        Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    /// Writes the key file, readable by the owner only
+    pub fn write(&self, path: &Path) -> Result<(), TypeError> {
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+        let file = options.open(path)?;
+        serde_json::to_writer_pretty(file, self)?;
+
+        Ok(())
+    }
+
+    pub fn signing_key(&self) -> Result<SigningKey, TypeError> {
+        Ok(SigningKey::from_pkcs8(
+            &BASE64_STANDARD.decode(&self.private_key)?,
+        )?)
+    }
+}
+
+impl fmt::Debug for SignerKeyFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SignerKeyFile")
+            .field("key_id", &self.key_id)
+            .field("space", &self.space)
+            .finish()
+    }
+}
+
+/// How card signatures are enforced when cards are loaded or downloaded
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SignaturePolicy {
+    /// Signatures are not checked
+    Off,
+    /// Invalid signatures are logged, unsigned cards are accepted
+    #[default]
+    Warn,
+    /// Unsigned cards and invalid signatures are refused
+    Require,
+}
+
+impl SignaturePolicy {
+    pub fn from_string(policy: &str) -> Result<Self, TypeError> {
+        match policy.to_lowercase().as_str() {
+            "off" => Ok(SignaturePolicy::Off),
+            "warn" => Ok(SignaturePolicy::Warn),
+            "require" => Ok(SignaturePolicy::Require),
+            _ => Err(TypeError::InvalidSignaturePolicy(policy.to_string())),
+        }
+    }
+}
+
+impl Display for SignaturePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SignaturePolicy::Off => write!(f, "off"),
+            SignaturePolicy::Warn => write!(f, "warn"),
+            SignaturePolicy::Require => write!(f, "require"),
+        }
+    }
+}
+
+/// Outcome of checking the manifest signature of a card
+#[derive(Debug, Clone, PartialEq)]
+pub enum SignatureStatus {
+    /// Signed by an active key of the card space
+    Valid(String),
+    Unsigned,
+    /// Signed, but the signature cannot be trusted. Holds the reason
+    Invalid(String),
+}
+
+impl Display for SignatureStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SignatureStatus::Valid(key_id) => write!(f, "signed by key {key_id}"),
+            SignatureStatus::Unsigned => write!(f, "unsigned"),
+            SignatureStatus::Invalid(reason) => write!(f, "invalid signature: {reason}"),
+        }
+    }
+}
@@ -19,9 +19,36 @@ pub enum TypeError {
     #[error("Invalid type")]
     InvalidType,
 
+    #[error("Invalid operation: {0}")]
+    InvalidOperation(String),
+
+    #[error("Invalid audit status: {0}")]
+    InvalidAuditStatus(String),
+
+    #[error("Invalid event type: {0}")]
+    InvalidEventType(String),
+
+    #[error("Invalid card stage: {0}")]
+    InvalidCardStage(String),
+
+    #[error("Invalid lineage direction: {0}. Expected upstream, downstream or both")]
+    InvalidLineageDirection(String),
+
+    #[error("Invalid version alias: {0}. Aliases must start with a lowercase letter and only contain lowercase letters, digits, '-' and '_'")]
+    InvalidVersionAlias(String),
+
     #[error("Key not found")]
     MissingKeyError,
 
+    #[error("Invalid signature policy: {0}. Expected off, warn or require")]
+    InvalidSignaturePolicy(String),
+
+    #[error(transparent)]
+    DecodeError(#[from] base64::DecodeError),
+
+    #[error("Invalid artifact path: {0}")]
+    ManifestPathError(String),
+
     #[error(transparent)]
     SerdeError(#[from] serde_json::Error),
 
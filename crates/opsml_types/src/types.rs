@@ -103,6 +103,46 @@ impl RegistryType {
     }
 }
 
+/// Lifecycle stage of a model or service card version
+#[pyclass(eq, eq_int)]
+#[derive(Debug, Eq, Hash, PartialEq, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum CardStage {
+    #[default]
+    Unassigned,
+    Staging,
+    Production,
+    Archived,
+}
+
+impl Display for CardStage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CardStage::Unassigned => write!(f, "unassigned"),
+            CardStage::Staging => write!(f, "staging"),
+            CardStage::Production => write!(f, "production"),
+            CardStage::Archived => write!(f, "archived"),
+        }
+    }
+}
+
+impl CardStage {
+    pub fn from_string(s: &str) -> Result<Self, TypeError> {
+        match s.to_lowercase().as_str() {
+            "unassigned" => Ok(CardStage::Unassigned),
+            "staging" => Ok(CardStage::Staging),
+            "production" => Ok(CardStage::Production),
+            "archived" => Ok(CardStage::Archived),
+            _ => Err(TypeError::InvalidCardStage(s.to_string())),
+        }
+    }
+
+    /// Whether only one version of a card is expected to hold the stage at a time
+    pub fn is_exclusive(&self) -> bool {
+        matches!(self, CardStage::Staging | CardStage::Production)
+    }
+}
+
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub enum PlotType {
     Line,
@@ -342,6 +382,8 @@ pub enum SaveName {
     ReadMe,
     ServiceCard,
     CardMap,
+    Manifest,
+    ManifestSignature,
 }
 
 #[pymethods]
@@ -376,6 +418,8 @@ impl SaveName {
             "README" => Some(SaveName::ReadMe),
             "service" => Some(SaveName::ServiceCard),
             "card_map" => Some(SaveName::CardMap),
+            "manifest" => Some(SaveName::Manifest),
+            "manifest_signature" => Some(SaveName::ManifestSignature),
             _ => None,
         }
     }
@@ -409,6 +453,8 @@ impl SaveName {
             SaveName::ReadMe => "README",
             SaveName::ServiceCard => "service",
             SaveName::CardMap => "card_map",
+            SaveName::Manifest => "manifest",
+            SaveName::ManifestSignature => "manifest_signature",
         }
     }
 
@@ -453,6 +499,8 @@ impl AsRef<Path> for SaveName {
             SaveName::ReadMe => Path::new("README"),
             SaveName::ServiceCard => Path::new("service"),
             SaveName::CardMap => Path::new("card_map"),
+            SaveName::Manifest => Path::new("manifest"),
+            SaveName::ManifestSignature => Path::new("manifest_signature"),
         }
     }
 }
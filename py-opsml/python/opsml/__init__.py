@@ -62,6 +62,8 @@ ExperimentCard = card.ExperimentCard
 start_experiment = experiment.start_experiment
 get_experiment_metrics = experiment.get_experiment_metrics
 get_experiment_parameters = experiment.get_experiment_parameters
+compare_experiments = experiment.compare_experiments
+diff_experiment_environments = experiment.diff_experiment_environments
 
 # logging
 RustyLogger = logging.RustyLogger
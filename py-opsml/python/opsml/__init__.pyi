@@ -21,6 +21,8 @@ from .data import PandasData as PandasData
 from .data import PolarsData as PolarsData
 from .data import SqlData as SqlData
 from .data import TorchData as TorchData
+from .experiment import compare_experiments as compare_experiments
+from .experiment import diff_experiment_environments as diff_experiment_environments
 from .experiment import get_experiment_metrics as get_experiment_metrics
 from .experiment import get_experiment_parameters as get_experiment_parameters
 from .experiment import start_experiment as start_experiment
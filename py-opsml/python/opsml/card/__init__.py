@@ -12,6 +12,13 @@ DataCardMetadata = card.DataCardMetadata
 RegistryTestHelper = card.RegistryTestHelper
 RegistryType = card.RegistryType
 RegistryMode = card.RegistryMode
+CardStage = card.CardStage
+CardStageChange = card.CardStageChange
+CardStageResponse = card.CardStageResponse
+CardSearchResult = card.CardSearchResult
+DeletedCard = card.DeletedCard
+VersionAlias = card.VersionAlias
+VersionAliasHistory = card.VersionAliasHistory
 ModelCard = card.ModelCard
 ModelCardMetadata = card.ModelCardMetadata
 ExperimentCard = card.ExperimentCard
@@ -30,6 +37,13 @@ __all__ = [
     "CardRegistry",
     "RegistryType",
     "RegistryMode",
+    "CardStage",
+    "CardStageChange",
+    "CardStageResponse",
+    "CardSearchResult",
+    "DeletedCard",
+    "VersionAlias",
+    "VersionAliasHistory",
     "ModelCard",
     "ModelCardMetadata",
     "ExperimentCard",
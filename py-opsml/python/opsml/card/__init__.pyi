@@ -16,7 +16,7 @@ from typing import (
 )
 
 from ..data import DataInterface, DataLoadKwargs, DataSaveKwargs, DataType
-from ..experiment import Metrics, Parameters
+from ..experiment import EnvironmentSnapshot, ExperimentComparison, Metrics, Parameters, Sweep
 from ..model import (
     DriftProfileMap,
     FeatureSchema,
@@ -44,6 +44,80 @@ class RegistryMode:
     Client: "RegistryMode"
     Server: "RegistryMode"
 
+class CardStage:
+    Unassigned: "CardStage"
+    Staging: "CardStage"
+    Production: "CardStage"
+    Archived: "CardStage"
+
+class CardStageChange:
+    uid: str
+    version: str
+    previous_stage: CardStage
+    stage: CardStage
+
+    def __str__(self) -> str:
+        """Return a string representation of the stage change"""
+
+class CardStageResponse:
+    space: str
+    name: str
+    change: CardStageChange
+    archived: List[CardStageChange]
+
+    def __str__(self) -> str:
+        """Return a string representation of the stage transition"""
+
+class DeletedCard:
+    uid: str
+    space: str
+    name: str
+    version: str
+    registry_type: RegistryType
+    deleted_at: datetime
+    deleted_by: str
+
+    def __str__(self) -> str:
+        """Return a string representation of the deleted card"""
+
+class CardSearchResult:
+    uid: str
+    registry_type: RegistryType
+    space: str
+    name: str
+    version: str
+    tags: List[str]
+    created_at: datetime
+    score: float
+
+    def __str__(self) -> str:
+        """Return a string representation of the search result"""
+
+class VersionAlias:
+    registry_type: RegistryType
+    space: str
+    name: str
+    alias: str
+    uid: str
+    version: str
+    updated_at: datetime
+    updated_by: str
+
+    def __str__(self) -> str:
+        """Return a string representation of the version alias"""
+
+class VersionAliasHistory:
+    id: int
+    created_at: datetime
+    alias: str
+    uid: Optional[str]
+    version: Optional[str]
+    previous_version: Optional[str]
+    username: str
+
+    def __str__(self) -> str:
+        """Return a string representation of the alias change"""
+
 class CardRecord:
     uid: Optional[str]
     created_at: Optional[str]
@@ -60,6 +134,7 @@ class CardRecord:
     data_type: Optional[str]
     model_type: Optional[str]
     task_type: Optional[str]
+    stage: Optional[CardStage]
 
     def __str__(self) -> str:
         """Return a string representation of the Card.
@@ -781,6 +856,14 @@ class ExperimentCard:
     def created_at(self) -> datetime:
         """Returns the created at timestamp"""
 
+    @property
+    def sweep(self) -> Optional[Sweep]:
+        """Returns the hyperparameter sweep run by the experiment, if any"""
+
+    @property
+    def environment(self) -> Optional[EnvironmentSnapshot]:
+        """Returns the environment captured when the experiment started"""
+
     def add_child_experiment(self, uid: str) -> None:
         """Add a child experiment to the experiment card
 
@@ -1306,6 +1389,7 @@ class CardRegistry(Generic[CardType]):
         tags: Optional[List[str]] = None,
         sort_by_timestamp: Optional[bool] = False,
         limit: int = 25,
+        stage: Optional[CardStage] = None,
     ) -> CardList:
         """Retrieves records from registry
 
@@ -1317,8 +1401,8 @@ class CardRegistry(Generic[CardType]):
             name (str):
                 Optional name of card
             version (str):
-                Optional version number of existing data. If not specified, the
-                most recent version will be used
+                Optional version number of existing data, or a version alias such as
+                "@champion". If not specified, the most recent version will be used
             tags (List[str]):
                 Optional list of tags to search for
             max_date (str):
@@ -1329,6 +1413,8 @@ class CardRegistry(Generic[CardType]):
             limit (int):
                 Places a limit on result list. Results are sorted by SemVer.
                 Defaults to 25.
+            stage (CardStage):
+                Optional stage to filter by. Only supported for model and service cards
 
         Returns:
             List of Cards
@@ -1423,7 +1509,8 @@ class CardRegistry(Generic[CardType]):
             name (str, optional):
                 Name of the card.
             version (str, optional):
-                Version number of existing card. If not specified, the most recent version will be used.
+                Version number of existing card, or a version alias such as "@champion".
+                If not specified, the most recent version will be used.
             interface (LoadInterfaceType, optional):
                 Interface to load the card with. Required for cards registered with custom interfaces.
                 The expected interface type depends on the registry:
@@ -1458,8 +1545,10 @@ class CardRegistry(Generic[CardType]):
         self,
         card: CardType,
     ) -> None:
-        """Delete a Card from the registry. This will also remove
-        the underlying artifacts associated with the card.
+        """Move a Card to the trash. Deleted cards are hidden from the registry
+        and can be restored with `restore_card` until they are purged. The
+        underlying artifacts are removed when the card is purged after the server's
+        trash retention period.
 
         Args:
             card (ArtifactCard):
@@ -1467,6 +1556,166 @@ class CardRegistry(Generic[CardType]):
                 experimentcard.
         """
 
+    def restore_card(self, uid: str) -> None:
+        """Restore a deleted Card from the trash
+
+        Args:
+            uid (str):
+                Unique identifier of the deleted card
+        """
+
+    def list_deleted_cards(self, space: Optional[str] = None) -> List[DeletedCard]:
+        """List the deleted cards of the registry that have not been purged yet,
+        most recently deleted first
+
+        Args:
+            space (str, optional):
+                Only return cards in this space
+        """
+
+    def search(
+        self,
+        query: str,
+        space: Optional[str] = None,
+        limit: Optional[int] = None,
+    ) -> List[CardSearchResult]:
+        """Full-text search of the registry across card names, spaces, tags,
+        README content and metadata such as the interface type and the username
+        that registered the card. Results are ordered by relevance.
+
+        Args:
+            query (str):
+                Words to search for. Every word must match and words are
+                matched as prefixes
+            space (str, optional):
+                Only search cards in this space
+            limit (int, optional):
+                Maximum number of results. Defaults to 25
+
+        Example:
+        ```python
+        registry = CardRegistry(RegistryType.Model)
+        results = registry.search("fraud xgboost")
+        ```
+        """
+
+    def transition_stage(
+        self,
+        uid: str,
+        stage: CardStage,
+        archive_existing: bool = False,
+    ) -> CardStageResponse:
+        """Move a model or service card version to a new stage.
+
+        Args:
+            uid (str):
+                Unique identifier of the card version
+            stage (CardStage):
+                The new stage
+            archive_existing (bool):
+                If True, other versions of the card in the stage are moved to
+                CardStage.Archived. Only applies to Staging and Production.
+
+        Returns:
+            The applied stage changes
+        """
+
+    def set_version_alias(
+        self,
+        space: str,
+        name: str,
+        alias: str,
+        version: str,
+    ) -> VersionAlias:
+        """Point a version alias at a card version. The card can then be loaded
+        with `version="@<alias>"`. Setting an existing alias moves it.
+
+        Args:
+            space (str):
+                Space of the card
+            name (str):
+                Name of the card
+            alias (str):
+                Alias name without the "@" prefix, e.g. "champion". Lowercase letters,
+                digits, "-" and "_", starting with a letter
+            version (str):
+                Exact version the alias points to
+
+        Returns:
+            The version alias
+        """
+
+    def delete_version_alias(self, space: str, name: str, alias: str) -> None:
+        """Delete a version alias
+
+        Args:
+            space (str):
+                Space of the card
+            name (str):
+                Name of the card
+            alias (str):
+                Alias name without the "@" prefix
+        """
+
+    def list_version_aliases(self, space: str, name: str) -> List[VersionAlias]:
+        """List the version aliases of a card
+
+        Args:
+            space (str):
+                Space of the card
+            name (str):
+                Name of the card
+        """
+
+    def get_version_alias_history(
+        self,
+        space: str,
+        name: str,
+        alias: Optional[str] = None,
+    ) -> List[VersionAliasHistory]:
+        """Get the history of the version aliases of a card, newest first
+
+        Args:
+            space (str):
+                Space of the card
+            name (str):
+                Name of the card
+            alias (str, optional):
+                Only return the history of this alias
+        """
+
+    def compare_experiments(
+        self,
+        experiment_uids: List[str],
+        metrics: Optional[List[str]] = None,
+        parameters: Optional[List[str]] = None,
+        maximize: Optional[List[str]] = None,
+    ) -> ExperimentComparison:
+        """Compare the parameters and metrics of several experiments. Metric series
+        are aligned by step and summarized per experiment. Only supported by the
+        experiment registry
+
+        Args:
+            experiment_uids (List[str]):
+                UIDs of the experiments to compare
+            metrics (List[str], optional):
+                Only compare these metrics. All metrics are compared if not provided
+            parameters (List[str], optional):
+                Only compare these parameters. All parameters are compared if not provided
+            maximize (List[str], optional):
+                Metrics where a higher value is better. Lower is better for all other metrics
+
+        Example:
+        ```python
+        registry = CardRegistry(RegistryType.Experiment)
+        comparison = registry.compare_experiments([run_a.uid, run_b.uid])
+
+        for parameter in comparison.parameters:
+            if parameter.differs:
+                print(parameter.name, parameter.values)
+        ```
+        """
+
 class CardRegistries:
     def __init__(self) -> None: ...
     @property
@@ -10,6 +10,24 @@ Parameter = experiment.Parameter
 Parameters = experiment.Parameters
 get_experiment_metrics = experiment.get_experiment_metrics
 get_experiment_parameters = experiment.get_experiment_parameters
+compare_experiments = experiment.compare_experiments
+ExperimentComparison = experiment.ExperimentComparison
+MetricComparison = experiment.MetricComparison
+MetricRun = experiment.MetricRun
+MetricSummary = experiment.MetricSummary
+ParameterComparison = experiment.ParameterComparison
+Choice = experiment.Choice
+Uniform = experiment.Uniform
+IntUniform = experiment.IntUniform
+Sweep = experiment.Sweep
+SweepStrategy = experiment.SweepStrategy
+SweepTrial = experiment.SweepTrial
+GitInfo = experiment.GitInfo
+EnvironmentSnapshot = experiment.EnvironmentSnapshot
+EnvironmentSection = experiment.EnvironmentSection
+EnvironmentChange = experiment.EnvironmentChange
+EnvironmentDiff = experiment.EnvironmentDiff
+diff_experiment_environments = experiment.diff_experiment_environments
 
 __all__ = [
     "Experiment",
@@ -18,4 +36,22 @@ __all__ = [
     "Parameter",
     "get_experiment_metrics",
     "get_experiment_parameters",
+    "compare_experiments",
+    "ExperimentComparison",
+    "MetricComparison",
+    "MetricRun",
+    "MetricSummary",
+    "ParameterComparison",
+    "Choice",
+    "Uniform",
+    "IntUniform",
+    "Sweep",
+    "SweepStrategy",
+    "SweepTrial",
+    "GitInfo",
+    "EnvironmentSnapshot",
+    "EnvironmentSection",
+    "EnvironmentChange",
+    "EnvironmentDiff",
+    "diff_experiment_environments",
 ]
@@ -2,7 +2,7 @@
 
 from datetime import datetime
 from pathlib import Path
-from typing import Optional, Union
+from typing import Any, Callable, Dict, Optional, Union
 
 from ..card import DataCard, ExperimentCard, ModelCard, PromptCard
 from ..data import DataSaveKwargs
@@ -52,7 +52,9 @@ class Experiment:
         created_at: Optional[datetime] = None,
     ) -> None:
         """
-        Log a metric
+        Log a metric. Metrics are buffered and sent to the server in batches
+        from a background thread. Buffered metrics are sent when the experiment
+        exits, or earlier with `flush`.
 
         Args:
             name (str):
@@ -100,6 +102,14 @@ class Experiment:
                 List of parameters to log
         """
 
+    def flush(self) -> None:
+        """
+        Send the buffered metrics and parameters to the server and wait for the
+        send to complete. If the server cannot be reached, batches are written to
+        `.opsml/spill/<experiment_uid>.jsonl` in the working directory and resent
+        on the next flush.
+        """
+
     def log_artifact(
         self,
         path: Path,
@@ -126,6 +136,85 @@ class Experiment:
                 All files in the directory will be logged.
         """
 
+    def sweep(
+        self,
+        objective: Callable[["Experiment", Dict[str, Union[int, float, str]]], Optional[float]],
+        search_space: Dict[str, Union[list, "Choice", "Uniform", "IntUniform"]],
+        metric: str,
+        strategy: "SweepStrategy" = ...,
+        maximize: bool = False,
+        num_trials: Optional[int] = None,
+        seed: Optional[int] = None,
+        min_budget: int = 1,
+        max_budget: Optional[int] = None,
+        reduction_factor: int = 3,
+        budget_parameter: str = "budget",
+        name: Optional[str] = None,
+    ) -> "Sweep":
+        """
+        Run a hyperparameter sweep. Every trial is a child experiment of this
+        experiment. The trial parameters are logged to the child experiment and
+        `objective` is called with the child experiment and a dict of the parameters.
+
+        If `objective` returns a value, it is logged as `metric`. Otherwise the last
+        value the trial logged for `metric` is used. Trials that raise are recorded
+        without a value. The sweep is saved on the ExperimentCard of this experiment.
+
+        Args:
+            objective (Callable[[Experiment, dict], float | None]):
+                Function running a single trial
+            search_space (dict):
+                Parameter name to a list of values, `Choice`, `Uniform` or `IntUniform`
+            metric (str):
+                Metric used to pick the best trial
+            strategy (SweepStrategy):
+                `Grid` runs every combination of choices, `Random` samples `num_trials`
+                configurations and `SuccessiveHalving` samples `num_trials` configurations
+                and runs the best of each round again with a larger budget.
+                Default is SweepStrategy.Grid
+            maximize (bool):
+                Whether higher metric values are better
+            num_trials (int | None):
+                Number of sampled configurations. Default is 10. Limits the number of
+                combinations of a grid sweep
+            seed (int | None):
+                Seed for sampling configurations
+            min_budget (int):
+                Budget of the first successive halving round
+            max_budget (int | None):
+                Successive halving stops before a round would exceed this budget
+            reduction_factor (int):
+                Successive halving keeps 1 / reduction_factor of the configurations after
+                each round and multiplies the budget by reduction_factor
+            budget_parameter (str):
+                Name of the parameter passing the budget to `objective`
+            name (str | None):
+                Name of the trial experiments. Defaults to "<experiment name>-trial"
+
+        Returns:
+            Sweep
+
+        Example:
+            ```python
+            from opsml.experiment import Uniform, SweepStrategy
+
+            def objective(exp, params):
+                model = train(lr=params["lr"], depth=params["depth"])
+                return model.score()
+
+            with start_experiment(space="opsml") as exp:
+                sweep = exp.sweep(
+                    objective,
+                    {"lr": Uniform(1e-4, 1e-1, log=True), "depth": [2, 4, 8]},
+                    metric="accuracy",
+                    strategy=SweepStrategy.Random,
+                    maximize=True,
+                    num_trials=20,
+                )
+                print(sweep.best_uid)
+            ```
+        """
+
     @property
     def card(self) -> "ExperimentCard":
         """
@@ -163,6 +252,8 @@ def start_experiment(
     code_dir: Optional[Path] = None,
     log_hardware: bool = False,
     experiment_uid: Optional[str] = None,
+    offline_dir: Optional[Path] = None,
+    env_vars: Optional[list[str]] = None,
 ) -> Experiment:
     """
     Start an Experiment
@@ -178,6 +269,14 @@ def start_experiment(
             Whether to log hardware information or not
         experiment_uid (str | None):
             Experiment UID. If provided, the experiment will be loaded from the server.
+        offline_dir (Path | None):
+            Record the experiment in this directory without contacting the server.
+            Register it later with `opsml experiment sync <offline_dir>`. Cards cannot
+            be registered from an offline experiment.
+        env_vars (list[str] | None):
+            Environment variables to record with the environment of the experiment.
+            Names ending in `*` match every variable with that prefix. Other variables
+            are never recorded, as they may hold secrets.
 
     Returns:
         Experiment
@@ -281,6 +380,11 @@ class Parameter:
 def get_experiment_metrics(
     experiment_uid: str,
     names: Optional[list[str]] = None,
+    start_step: Optional[int] = None,
+    end_step: Optional[int] = None,
+    start_timestamp: Optional[int] = None,
+    end_timestamp: Optional[int] = None,
+    max_points: Optional[int] = None,
 ) -> Metrics:
     """
     Get metrics of an experiment
@@ -290,6 +394,31 @@ def get_experiment_metrics(
             UID of the experiment
         names (list[str] | None):
             Names of the metrics to get. If None, all metrics will be returned.
+        start_step (int | None):
+            Only return metrics logged at or after this step. Metrics without a step are excluded.
+        end_step (int | None):
+            Only return metrics logged at or before this step. Metrics without a step are excluded.
+        start_timestamp (int | None):
+            Only return metrics with a timestamp at or after this timestamp.
+        end_timestamp (int | None):
+            Only return metrics with a timestamp at or before this timestamp.
+        max_points (int | None):
+            Downsample each metric on the server to at most this many points. Each bucket
+            of the series keeps its lowest and highest value.
+
+    Example:
+    ```python
+    from opsml import get_experiment_metrics
+
+    # at most 500 points of the loss between steps 10k and 1M
+    metrics = get_experiment_metrics(
+        experiment_uid,
+        names=["loss"],
+        start_step=10_000,
+        end_step=1_000_000,
+        max_points=500,
+    )
+    ```
 
     Returns:
         Metrics
@@ -311,3 +440,362 @@ def get_experiment_parameters(
     Returns:
         Parameters
     """
+
+class SweepStrategy:
+    Grid: "SweepStrategy"
+    Random: "SweepStrategy"
+    SuccessiveHalving: "SweepStrategy"
+
+class Choice:
+    def __init__(self, values: list[Union[int, float, str]]) -> None:
+        """A parameter taking one of a fixed set of values
+
+        Args:
+            values (list[int | float | str]):
+                Values to choose from
+        """
+
+class Uniform:
+    def __init__(self, low: float, high: float, log: bool = False) -> None:
+        """A float parameter sampled between low and high
+
+        Args:
+            low (float):
+                Lowest value
+            high (float):
+                Highest value
+            log (bool):
+                Sample on a log scale. low must be positive
+        """
+
+class IntUniform:
+    def __init__(self, low: int, high: int) -> None:
+        """An integer parameter sampled between low and high, both inclusive
+
+        Args:
+            low (int):
+                Lowest value
+            high (int):
+                Highest value
+        """
+
+class SweepTrial:
+    @property
+    def experiment_uid(self) -> str:
+        """UID of the trial experiment"""
+
+    @property
+    def parameters(self) -> list[Parameter]:
+        """Parameters of the trial"""
+
+    @property
+    def value(self) -> Optional[float]:
+        """Final value of the sweep metric. None if the trial failed or did not log the metric"""
+
+    @property
+    def budget(self) -> Optional[int]:
+        """Budget given to the trial by successive halving"""
+
+    def __str__(self): ...
+
+class Sweep:
+    @property
+    def search_space(self) -> Dict[str, Any]:
+        """Search space of the sweep"""
+
+    @property
+    def strategy(self) -> SweepStrategy:
+        """Search strategy"""
+
+    @property
+    def metric(self) -> str:
+        """Metric used to pick the best trial"""
+
+    @property
+    def maximize(self) -> bool:
+        """Whether higher metric values are better"""
+
+    @property
+    def best_uid(self) -> Optional[str]:
+        """UID of the best trial experiment"""
+
+    @property
+    def best_value(self) -> Optional[float]:
+        """Metric value of the best trial"""
+
+    @property
+    def best_trial(self) -> Optional[SweepTrial]:
+        """The best trial"""
+
+    @property
+    def trials(self) -> list[SweepTrial]:
+        """Every trial, in the order they ran"""
+
+    def __str__(self): ...
+
+class GitInfo:
+    """Git state of the working directory when the experiment started"""
+
+    @property
+    def commit(self) -> str:
+        """Commit checked out"""
+
+    @property
+    def branch(self) -> Optional[str]:
+        """Branch checked out. None for a detached HEAD"""
+
+    @property
+    def remote_url(self) -> Optional[str]:
+        """Url of the origin remote, without credentials"""
+
+    @property
+    def dirty(self) -> bool:
+        """Whether the working tree had uncommitted or untracked changes"""
+
+    @property
+    def diff(self) -> Optional[str]:
+        """`git diff HEAD` of the uncommitted changes to tracked files"""
+
+    @property
+    def diff_truncated(self) -> bool:
+        """Whether the diff was truncated because it was too large"""
+
+    def __str__(self): ...
+
+class EnvironmentSnapshot:
+    """Reproducibility metadata recorded when an experiment starts"""
+
+    @property
+    def git(self) -> Optional[GitInfo]:
+        """Git state. None when the experiment did not run from a git repository"""
+
+    @property
+    def python_version(self) -> str:
+        """Python version"""
+
+    @property
+    def os(self) -> str:
+        """Operating system name"""
+
+    @property
+    def os_version(self) -> str:
+        """Operating system version"""
+
+    @property
+    def architecture(self) -> str:
+        """CPU architecture"""
+
+    @property
+    def cpu_count(self) -> int:
+        """Number of CPUs available to the process"""
+
+    @property
+    def packages(self) -> Dict[str, str]:
+        """Installed python packages and their versions"""
+
+    @property
+    def env_vars(self) -> Dict[str, str]:
+        """Allow-listed environment variables"""
+
+    @property
+    def captured_at(self) -> datetime:
+        """When the environment was captured"""
+
+    def __str__(self): ...
+
+class EnvironmentSection:
+    Git: "EnvironmentSection"
+    System: "EnvironmentSection"
+    Package: "EnvironmentSection"
+    EnvVar: "EnvironmentSection"
+
+class EnvironmentChange:
+    """A value that differs between two environments"""
+
+    @property
+    def section(self) -> EnvironmentSection:
+        """Section of the environment the value belongs to"""
+
+    @property
+    def key(self) -> str:
+        """Name of the value"""
+
+    @property
+    def left(self) -> Optional[str]:
+        """Value of the left experiment. None when missing"""
+
+    @property
+    def right(self) -> Optional[str]:
+        """Value of the right experiment. None when missing"""
+
+    def __str__(self): ...
+
+class EnvironmentDiff:
+    """Differences between the environments of two experiments"""
+
+    @property
+    def left_uid(self) -> str:
+        """Uid of the left experiment"""
+
+    @property
+    def right_uid(self) -> str:
+        """Uid of the right experiment"""
+
+    @property
+    def changes(self) -> list[EnvironmentChange]:
+        """Values that differ between the environments"""
+
+    def as_table(self) -> None:
+        """Print the changes as a table"""
+
+    def __len__(self) -> int: ...
+    def __str__(self): ...
+
+def diff_experiment_environments(left_uid: str, right_uid: str) -> EnvironmentDiff:
+    """
+    Compare the environments recorded by two experiments
+
+    Args:
+        left_uid (str):
+            UID of the first experiment
+        right_uid (str):
+            UID of the second experiment
+
+    Example:
+    ```python
+    from opsml.experiment import diff_experiment_environments
+
+    diff = diff_experiment_environments(run_a.uid, run_b.uid)
+    diff.as_table()
+    ```
+
+    Returns:
+        EnvironmentDiff
+    """
+
+class MetricSummary:
+    """Summary of one experiment's values for a metric"""
+
+    @property
+    def min(self) -> float:
+        """Lowest value of the metric"""
+
+    @property
+    def max(self) -> float:
+        """Highest value of the metric"""
+
+    @property
+    def last(self) -> float:
+        """Value at the highest step"""
+
+    @property
+    def best(self) -> float:
+        """Lowest value, or highest value if the metric is maximized"""
+
+    @property
+    def best_step(self) -> int:
+        """First step where the best value was logged"""
+
+    @property
+    def count(self) -> int:
+        """Number of steps logged"""
+
+    def __str__(self): ...
+
+class MetricRun:
+    """One experiment's values for a metric, aligned to the steps of the comparison"""
+
+    @property
+    def experiment_uid(self) -> str:
+        """UID of the experiment"""
+
+    @property
+    def values(self) -> list[Optional[float]]:
+        """Value at each step of the comparison. None where the experiment did not log the step"""
+
+    @property
+    def summary(self) -> Optional[MetricSummary]:
+        """Summary statistics. None if the experiment did not log the metric"""
+
+    def __str__(self): ...
+
+class MetricComparison:
+    @property
+    def name(self) -> str:
+        """Name of the metric"""
+
+    @property
+    def steps(self) -> list[int]:
+        """Every step logged by any of the experiments, ascending"""
+
+    @property
+    def runs(self) -> list[MetricRun]:
+        """One entry per experiment, in the order of the compared experiment uids"""
+
+    def __str__(self): ...
+
+class ParameterComparison:
+    @property
+    def name(self) -> str:
+        """Name of the parameter"""
+
+    @property
+    def values(self) -> list[Optional[Union[int, float, str]]]:
+        """One value per experiment, in the order of the compared experiment uids"""
+
+    @property
+    def differs(self) -> bool:
+        """Whether the experiments used different values for the parameter"""
+
+    def __str__(self): ...
+
+class ExperimentComparison:
+    """Parameters and metrics of several experiments, aligned for side by side comparison"""
+
+    @property
+    def experiment_uids(self) -> list[str]:
+        """Compared experiment uids"""
+
+    @property
+    def parameters(self) -> list[ParameterComparison]:
+        """Parameter table, sorted by parameter name"""
+
+    @property
+    def metrics(self) -> list[MetricComparison]:
+        """Metric series, sorted by metric name"""
+
+    def __str__(self): ...
+
+def compare_experiments(
+    experiment_uids: list[str],
+    metrics: Optional[list[str]] = None,
+    parameters: Optional[list[str]] = None,
+    maximize: Optional[list[str]] = None,
+) -> ExperimentComparison:
+    """
+    Compare the parameters and metrics of several experiments in a single request
+
+    Args:
+        experiment_uids (list[str]):
+            UIDs of the experiments to compare
+        metrics (list[str] | None):
+            Names of the metrics to compare. If None, all metrics will be compared.
+        parameters (list[str] | None):
+            Names of the parameters to compare. If None, all parameters will be compared.
+        maximize (list[str] | None):
+            Metrics where a higher value is better. Lower is better for all other metrics.
+
+    Example:
+    ```python
+    from opsml import compare_experiments
+
+    comparison = compare_experiments([run_a.uid, run_b.uid], maximize=["accuracy"])
+
+    for metric in comparison.metrics:
+        for run in metric.runs:
+            print(metric.name, run.experiment_uid, run.summary.best)
+    ```
+
+    Returns:
+        ExperimentComparison
+    """
@@ -4,8 +4,11 @@ use opsml_cards::{
 };
 
 use opsml_registry::{CardRegistries, CardRegistry};
-use opsml_types::contracts::{CardList, CardRecord};
-use opsml_types::{cards::ComputeEnvironment, RegistryMode, RegistryType};
+use opsml_types::contracts::{
+    CardList, CardRecord, CardSearchResult, CardStageChange, CardStageResponse, DeletedCard,
+    VersionAlias, VersionAliasHistory,
+};
+use opsml_types::{cards::ComputeEnvironment, CardStage, RegistryMode, RegistryType};
 
 #[cfg(feature = "server")]
 use opsml_registry::RegistryTestHelper;
@@ -24,6 +27,13 @@ pub fn card(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<CardRegistries>()?;
     m.add_class::<RegistryType>()?;
     m.add_class::<RegistryMode>()?;
+    m.add_class::<CardStage>()?;
+    m.add_class::<CardStageChange>()?;
+    m.add_class::<CardStageResponse>()?;
+    m.add_class::<DeletedCard>()?;
+    m.add_class::<CardSearchResult>()?;
+    m.add_class::<VersionAlias>()?;
+    m.add_class::<VersionAliasHistory>()?;
 
     #[cfg(feature = "server")]
     m.add_class::<RegistryTestHelper>()?;
@@ -1,7 +1,16 @@
 use opsml_experiment::{
-    get_experiment_metrics, get_experiment_parameters, start_experiment, Experiment,
+    compare_experiments, diff_experiment_environments, get_experiment_metrics,
+    get_experiment_parameters, start_experiment, Choice, Experiment, IntUniform, Uniform,
+};
+use opsml_types::cards::experiment::{
+    Metric, Metrics, Parameter, Parameters, Sweep, SweepStrategy, SweepTrial,
+};
+use opsml_types::cards::{
+    EnvironmentChange, EnvironmentDiff, EnvironmentSection, EnvironmentSnapshot, GitInfo,
+};
+use opsml_types::contracts::{
+    ExperimentComparison, MetricComparison, MetricRun, MetricSummary, ParameterComparison,
 };
-use opsml_types::cards::experiment::{Metric, Metrics, Parameter, Parameters};
 use pyo3::prelude::*;
 
 #[pymodule]
@@ -11,8 +20,26 @@ pub fn experiment(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<Parameter>()?;
     m.add_class::<Parameters>()?;
     m.add_class::<Metrics>()?;
+    m.add_class::<ExperimentComparison>()?;
+    m.add_class::<MetricComparison>()?;
+    m.add_class::<MetricRun>()?;
+    m.add_class::<MetricSummary>()?;
+    m.add_class::<ParameterComparison>()?;
+    m.add_class::<Choice>()?;
+    m.add_class::<Uniform>()?;
+    m.add_class::<IntUniform>()?;
+    m.add_class::<Sweep>()?;
+    m.add_class::<SweepStrategy>()?;
+    m.add_class::<SweepTrial>()?;
+    m.add_class::<GitInfo>()?;
+    m.add_class::<EnvironmentSnapshot>()?;
+    m.add_class::<EnvironmentSection>()?;
+    m.add_class::<EnvironmentChange>()?;
+    m.add_class::<EnvironmentDiff>()?;
     m.add_function(wrap_pyfunction!(get_experiment_metrics, m)?)?;
     m.add_function(wrap_pyfunction!(get_experiment_parameters, m)?)?;
+    m.add_function(wrap_pyfunction!(compare_experiments, m)?)?;
     m.add_function(wrap_pyfunction!(start_experiment, m)?)?;
+    m.add_function(wrap_pyfunction!(diff_experiment_environments, m)?)?;
     Ok(())
 }
@@ -2,6 +2,12 @@ from opsml.mock import OpsmlTestServer
 from opsml.experiment import (
     Metric,
     Parameter,
+    Choice,
+    IntUniform,
+    SweepStrategy,
+    Uniform,
+    EnvironmentSection,
+    diff_experiment_environments,
 )
 from opsml import (  # type: ignore
     start_experiment,
@@ -81,6 +87,10 @@ def test_experimentcard():
             exp.log_parameter(name="test", value=1.0)
             exp.log_parameters([Parameter(name="test1", value=1.0)])
 
+            # buffered metrics are visible after a flush
+            exp.flush()
+            assert len(get_experiment_metrics(exp.card.uid)) == 3
+
             # create fake items
             file_path = create_fake_file()
             exp.log_artifact(file_path)
@@ -152,6 +162,95 @@ def test_experimentcard():
     cleanup_manually_created_directories()
 
 
+@pytest.mark.skipif(WINDOWS_EXCLUDE, reason="skipping")
+def test_experiment_sweep():
+    with OpsmlTestServer():
+        with start_experiment(space="test", name="sweep") as exp:
+
+            def objective(trial, params):
+                return params["lr"] * params["depth"]
+
+            grid = exp.sweep(
+                objective,
+                {"lr": [0.1, 0.2], "depth": Choice([1, 2, 3])},
+                metric="score",
+                maximize=True,
+            )
+
+            assert len(grid.trials) == 6
+            assert grid.best_value == pytest.approx(0.6)
+            assert grid.best_trial.experiment_uid == grid.best_uid
+
+            # trials are child experiments with their parameters logged
+            assert grid.best_uid in exp.card.uids.experimentcard_uids
+            params = get_experiment_parameters(grid.best_uid)
+            assert {p.name: p.value for p in params} == {"lr": 0.2, "depth": 3}
+
+            def invalid_objective(trial, params):
+                if params["depth"] == 2:
+                    return "not a number"
+                return params["depth"]
+
+            # a trial returning a value that is not a number fails without stopping the sweep
+            invalid = exp.sweep(
+                invalid_objective,
+                {"depth": Choice([1, 2, 3])},
+                metric="depth_score",
+                maximize=True,
+            )
+            assert [t.value for t in invalid.trials] == [1.0, None, 3.0]
+            assert invalid.best_value == pytest.approx(3.0)
+
+            def logged_objective(trial, params):
+                for step in range(params["budget"]):
+                    trial.log_metric("loss", params["lr"] / (step + 1), step=step)
+
+            halving = exp.sweep(
+                logged_objective,
+                {"lr": Uniform(0.01, 1.0, log=True), "depth": IntUniform(1, 4)},
+                metric="loss",
+                strategy=SweepStrategy.SuccessiveHalving,
+                num_trials=9,
+                seed=42,
+            )
+
+            # 9 trials with budget 1, 3 with budget 3 and 1 with budget 9
+            assert [t.budget for t in halving.trials] == [1] * 9 + [3] * 3 + [9]
+            assert halving.best_uid == halving.trials[-1].experiment_uid
+
+        assert exp.card.sweep.best_uid == halving.best_uid
+        assert exp.card.sweep.strategy == SweepStrategy.SuccessiveHalving
+
+
+def test_experiment_environment(monkeypatch):
+    with OpsmlTestServer():
+        monkeypatch.setenv("OPSML_TEST_SEED", "1")
+        monkeypatch.setenv("OPSML_TEST_SECRET", "hidden")
+        with start_experiment(space="test", env_vars=["OPSML_TEST_SEED"]) as left:
+            pass
+
+        monkeypatch.setenv("OPSML_TEST_SEED", "2")
+        with start_experiment(space="test", env_vars=["OPSML_TEST_*"]) as right:
+            pass
+
+        environment = left.card.environment
+        assert environment.env_vars == {"OPSML_TEST_SEED": "1"}
+        assert environment.python_version
+        assert environment.cpu_count > 0
+        assert "pytest" in environment.packages
+
+        diff = diff_experiment_environments(left.card.uid, right.card.uid)
+        env_changes = {
+            change.key: (change.left, change.right)
+            for change in diff.changes
+            if change.section == EnvironmentSection.EnvVar
+        }
+        assert env_changes == {
+            "OPSML_TEST_SECRET": (None, "hidden"),
+            "OPSML_TEST_SEED": ("1", "2"),
+        }
+
+
 @pytest.mark.skipif(WINDOWS_EXCLUDE, reason="skipping")
 def _test_experimentcard_register(
     pandas_data: PandasData,